@@ -0,0 +1,165 @@
+//! Antenna pattern cuts.
+//!
+//! The footprints only use the half-power beamwidths of the antennas: the
+//! pattern cuts assumed around them, in the elevation and azimuth planes, are
+//! a uniformly illuminated aperture (sinc²), a Gaussian beam, or a pattern
+//! table imported by the user.
+
+use crate::bsar::{sinc, SINC_WIDTH_AT_HALF_POWER};
+
+/// Lowest normalized gain in dB of the pattern cuts (the sinc² nulls)
+pub const PATTERN_FLOOR_DB: f64 = -60.0;
+/// -3 dB, the exact half-power level 10.log10(1/2)
+const HALF_POWER_DB: f64 = -3.010299956639812;
+
+/// Normalized gain in dB versus the angle from the boresight in degrees,
+/// linearly interpolated between its points and held beyond them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PatternTable {
+    /// (angle, gain) in degrees and dB, sorted by angle, the peak at 0 dB
+    points: Vec<(f64, f64)>,
+}
+
+impl PatternTable {
+    /// Creates a table from (angle, gain) points in degrees and dB, normalized
+    /// to its peak gain.
+    pub fn new(mut points: Vec<(f64, f64)>) -> Result<Self, String> {
+        if points.len() < 2 {
+            return Err("A pattern table needs at least two (angle, gain) points".to_string());
+        }
+        if let Some((angle_deg, _)) = points.iter().find(|(angle_deg, _)| !(-180.0..=180.0).contains(angle_deg)) {
+            return Err(format!("Angle {angle_deg}° is not within -180° - 180°"));
+        }
+        points.sort_by(|a, b| a.0.total_cmp(&b.0));
+        let peak_db = points.iter().map(|&(_, gain_db)| gain_db).fold(f64::NEG_INFINITY, f64::max);
+        points.iter_mut().for_each(|(_, gain_db)| *gain_db -= peak_db);
+        Ok(Self { points })
+    }
+
+    /// Reads a pattern table: one "angle gain" pair in degrees and dB per
+    /// line, separated by spaces, commas or semicolons, `#` starting a comment.
+    pub fn from_table(text: &str) -> Result<Self, String> {
+        let points = text.lines()
+            .enumerate()
+            .map(|(i, line)| (i, line.split('#').next().unwrap_or_default().trim()))
+            .filter(|(_, line)| !line.is_empty())
+            .map(|(i, line)| {
+                let values = line.split([' ', '\t', ',', ';'])
+                    .filter(|value| !value.is_empty())
+                    .map(str::parse::<f64>)
+                    .collect::<Result<Vec<_>, _>>();
+                match values.as_deref() {
+                    Ok(&[angle_deg, gain_db]) => Ok((angle_deg, gain_db)),
+                    _ => Err(format!("Line {}: expected \"angle gain\"", i + 1)),
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Self::new(points)
+    }
+
+    /// Gets the (angle, gain) points in degrees and dB, sorted by angle.
+    #[inline]
+    pub fn points(&self) -> &[(f64, f64)] {
+        &self.points
+    }
+
+    /// Gets the normalized gain in dB at `angle_deg`.
+    pub fn gain_db_at(&self, angle_deg: f64) -> f64 {
+        let upper = self.points.partition_point(|&(point_angle_deg, _)| point_angle_deg < angle_deg);
+        if upper == 0 {
+            return self.points[0].1;
+        }
+        if upper == self.points.len() {
+            return self.points[upper - 1].1;
+        }
+        let ((a0, g0), (a1, g1)) = (self.points[upper - 1], self.points[upper]);
+        if a1 - a0 > 0.0 { g0 + (angle_deg - a0) / (a1 - a0) * (g1 - g0) } else { g0 }
+    }
+
+    /// Gets the half-power beamwidth in degrees, between the -3 dB crossings
+    /// on each side of the peak, or `None` if the pattern does not fall 3 dB
+    /// on both sides.
+    pub fn half_power_beam_width_deg(&self) -> Option<f64> {
+        let peak = self.points.iter().position(|&(_, gain_db)| gain_db == 0.0)?;
+        let crossing = |(a0, g0): (f64, f64), (a1, g1): (f64, f64)| a0 + (HALF_POWER_DB - g0) / (g1 - g0) * (a1 - a0);
+        let lower = (1..=peak).rev()
+            .find(|&i| self.points[i - 1].1 <= HALF_POWER_DB)
+            .map(|i| crossing(self.points[i - 1], self.points[i]))?;
+        let upper = (peak + 1..self.points.len())
+            .find(|&i| self.points[i].1 <= HALF_POWER_DB)
+            .map(|i| crossing(self.points[i - 1], self.points[i]))?;
+        Some(upper - lower)
+    }
+}
+
+/// Antenna pattern assumed around the half-power beamwidths.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum AntennaPattern {
+    /// Uniformly illuminated aperture, sinc²
+    #[default]
+    Sinc,
+    /// Gaussian beam
+    Gaussian,
+    /// User tables for the elevation and azimuth cuts
+    Table { elevation: PatternTable, azimuth: PatternTable },
+}
+
+/// Plane of a pattern cut.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PatternCut {
+    Elevation,
+    Azimuth,
+}
+
+impl AntennaPattern {
+    /// Gets the normalized gain in dB at `angle_deg` from the boresight in the
+    /// `cut` plane, of half-power beamwidth `beam_width_deg` for the analytic
+    /// patterns, not below [`PATTERN_FLOOR_DB`].
+    pub fn gain_db(&self, cut: PatternCut, angle_deg: f64, beam_width_deg: f64) -> f64 {
+        let gain_db = match self {
+            // sinc²(x) is at half power for x = SINC_WIDTH_AT_HALF_POWER / 2
+            Self::Sinc => 20.0 * sinc(SINC_WIDTH_AT_HALF_POWER * angle_deg / beam_width_deg).abs().log10(),
+            // exp(-4.ln(2).(θ/θ3dB)²) is at half power for θ = θ3dB / 2
+            Self::Gaussian => 4.0 * HALF_POWER_DB * (angle_deg / beam_width_deg).powi(2),
+            Self::Table { elevation, azimuth } => match cut {
+                PatternCut::Elevation => elevation.gain_db_at(angle_deg),
+                PatternCut::Azimuth => azimuth.gain_db_at(angle_deg),
+            },
+        };
+        gain_db.max(PATTERN_FLOOR_DB)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn analytic_patterns_at_half_power() {
+        for pattern in [AntennaPattern::Sinc, AntennaPattern::Gaussian] {
+            for cut in [PatternCut::Elevation, PatternCut::Azimuth] {
+                assert!(pattern.gain_db(cut, 0.0, 10.0).abs() < 1e-12);
+                assert!((pattern.gain_db(cut, 5.0, 10.0) - HALF_POWER_DB).abs() < 1e-9);
+                assert!((pattern.gain_db(cut, -5.0, 10.0) - HALF_POWER_DB).abs() < 1e-9);
+            }
+        }
+        // First sinc² null, and far Gaussian tail, floored
+        let first_null_deg = 10.0 / SINC_WIDTH_AT_HALF_POWER;
+        assert_eq!(AntennaPattern::Sinc.gain_db(PatternCut::Azimuth, first_null_deg, 10.0), PATTERN_FLOOR_DB);
+        assert_eq!(AntennaPattern::Gaussian.gain_db(PatternCut::Azimuth, 100.0, 10.0), PATTERN_FLOOR_DB);
+    }
+
+    #[test]
+    fn pattern_tables() {
+        let table = PatternTable::from_table("# angle gain\n-10, 14\n10 14\n0;20 # Peak\n").unwrap();
+        assert_eq!(table.points(), &[(-10.0, -6.0), (0.0, 0.0), (10.0, -6.0)]);
+        assert_eq!(table.gain_db_at(5.0), -3.0);
+        assert_eq!(table.gain_db_at(-20.0), -6.0);
+        let beam_width_deg = table.half_power_beam_width_deg().unwrap();
+        assert!((beam_width_deg - 2.0 * 10.0 * HALF_POWER_DB / -6.0).abs() < 1e-12);
+        assert!(PatternTable::from_table("0 0\n10 -1").unwrap().half_power_beam_width_deg().is_none());
+        assert!(PatternTable::from_table("0 0").is_err());
+        assert!(PatternTable::from_table("0 0\n10").is_err());
+        assert!(PatternTable::from_table("0 0\n200 -3").is_err());
+    }
+}
@@ -0,0 +1,285 @@
+//! Headless evaluation of scenario files.
+//!
+//! `bsargeom --batch <paths>... [--output <file.csv>] [--analytic-footprint]`
+//! evaluates every scenario
+//! file (`.bsargeom`) of the paths and writes one CSV row of BSAR infos per
+//! scenario, for trade-space tables. A path is a scenario file, a directory
+//! (its scenario files) or a file name pattern with `*` and `?` wildcards
+//! (`runs/pass_*.bsargeom`). The table goes to the standard output without
+//! `--output`. `--analytic-footprint` approximates the footprints by their
+//! analytic ellipses, faster for large sweeps.
+
+use bevy::asset::AssetPlugin;
+use bevy::ecs::system::SystemState;
+use bevy::prelude::*;
+
+use crate::{
+    bsar::BsarInfos,
+    entities::{AntennaBeamFootprintState, FootprintModel, IsoContours, IsoRangeDopplerPlaneState},
+    scenario::{Scenario, SCENARIO_EXTENSION},
+    scene::{
+        spawn_scene, BsarInfosState, CarrierLimitsState, ScenarioStates,
+        RxAntennaBeamFootprintState, RxAntennaBeamState, RxAntennaState, RxCarrierState,
+        TxAntennaBeamFootprintState, TxAntennaBeamState, TxAntennaState, TxCarrierState,
+    },
+    ui::{MenuWidget, PipelinePlugin, RxPanelPlugin, TxPanelPlugin},
+};
+
+/// Command line flag selecting the batch evaluation
+pub const BATCH_FLAG: &str = "--batch";
+/// Command line flag selecting the analytic footprints
+const ANALYTIC_FOOTPRINT_FLAG: &str = "--analytic-footprint";
+
+/// Headless App running the real spawned scene graph and the real update
+/// pipeline (update_rx, update_tx, then update_bsar_system), without
+/// rendering.
+pub fn headless_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AssetPlugin::default()));
+    app.init_asset::<Mesh>();
+    app.init_asset::<StandardMaterial>();
+    app.init_asset::<Image>();
+    // Scene state resources, as ScenePlugin initializes them (without its
+    // camera/world rendering plugins)
+    app.init_resource::<TxCarrierState>();
+    app.init_resource::<TxAntennaState>();
+    app.init_resource::<TxAntennaBeamState>();
+    app.init_resource::<TxAntennaBeamFootprintState>();
+    app.init_resource::<RxCarrierState>();
+    app.init_resource::<RxAntennaState>();
+    app.init_resource::<RxAntennaBeamState>();
+    app.init_resource::<RxAntennaBeamFootprintState>();
+    app.init_resource::<BsarInfosState>();
+    app.init_resource::<IsoRangeDopplerPlaneState>();
+    app.init_resource::<IsoContours>();
+    app.init_resource::<CarrierLimitsState>();
+    app.init_resource::<MenuWidget>();
+    app.add_plugins((PipelinePlugin, TxPanelPlugin, RxPanelPlugin));
+    app.add_systems(Startup, spawn_scene);
+    app
+}
+
+/// BSAR infos and footprints computed for a scenario.
+pub struct Evaluation {
+    pub infos: BsarInfos,
+    pub tx_footprint: AntennaBeamFootprintState,
+    pub rx_footprint: AntennaBeamFootprintState,
+    /// Unknown parameters of the scenario, skipped
+    pub unknown_keys: Vec<String>,
+}
+
+/// Evaluates a scenario in a fresh headless App, the footprints computed with
+/// `footprint_model`.
+pub fn evaluate(scenario: &Scenario, footprint_model: FootprintModel) -> Evaluation {
+    let mut app = headless_app();
+    app.update(); // Startup: spawns the default scene
+    let mut states = SystemState::<(
+        ResMut<TxCarrierState>, ResMut<TxAntennaState>, ResMut<TxAntennaBeamState>,
+        ResMut<RxCarrierState>, ResMut<RxAntennaState>, ResMut<RxAntennaBeamState>,
+    )>::new(app.world_mut());
+    let (
+        mut tx_carrier_state, mut tx_antenna_state, mut tx_antenna_beam_state,
+        mut rx_carrier_state, mut rx_antenna_state, mut rx_antenna_beam_state,
+    ) = states.get_mut(app.world_mut());
+    let unknown_keys = ScenarioStates {
+        tx: (&mut tx_carrier_state, &mut tx_antenna_state, &mut tx_antenna_beam_state),
+        rx: (&mut rx_carrier_state, &mut rx_antenna_state, &mut rx_antenna_beam_state),
+    }
+    .apply(&scenario.parameters);
+    let world = app.world_mut();
+    world.resource_mut::<TxAntennaBeamFootprintState>().inner.model = footprint_model;
+    world.resource_mut::<RxAntennaBeamFootprintState>().inner.model = footprint_model;
+    app.update(); // Updates the scene and the infos from the scenario
+    let world = app.world_mut();
+    Evaluation {
+        infos: std::mem::take(&mut world.resource_mut::<BsarInfosState>().inner),
+        tx_footprint: std::mem::take(&mut world.resource_mut::<TxAntennaBeamFootprintState>().inner),
+        rx_footprint: std::mem::take(&mut world.resource_mut::<RxAntennaBeamFootprintState>().inner),
+        unknown_keys,
+    }
+}
+
+/// Runs the batch evaluation from the arguments following [`BATCH_FLAG`],
+/// returning the process exit code: 0 when every scenario was evaluated.
+pub fn run(args: &[String]) -> i32 {
+    let mut patterns = Vec::new();
+    let mut output = None;
+    let mut footprint_model = FootprintModel::Numeric;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--output" | "-o" => match args.next() {
+                Some(path) => output = Some(path.clone()),
+                None => {
+                    eprintln!("{arg}: missing output file");
+                    return 2;
+                }
+            },
+            ANALYTIC_FOOTPRINT_FLAG => footprint_model = FootprintModel::AnalyticEllipse,
+            _ => patterns.push(arg.clone()),
+        }
+    }
+    if patterns.is_empty() {
+        eprintln!("usage: bsargeom {BATCH_FLAG} <file|directory|pattern>... [--output <file.csv>] [{ANALYTIC_FOOTPRINT_FLAG}]");
+        return 2;
+    }
+
+    let mut is_failed = false;
+    let mut paths = Vec::new();
+    for pattern in &patterns {
+        match scenario_paths(pattern) {
+            Ok(pattern_paths) if pattern_paths.is_empty() => {
+                eprintln!("{pattern}: no scenario file");
+                is_failed = true;
+            }
+            Ok(pattern_paths) => paths.extend(pattern_paths),
+            Err(error) => {
+                eprintln!("{error}");
+                is_failed = true;
+            }
+        }
+    }
+
+    let mut csv = format!("file,title,{}\n", BsarInfos::CSV_HEADER);
+    for path in &paths {
+        let scenario = std::fs::read_to_string(path)
+            .map_err(|error| error.to_string())
+            .and_then(|text| Scenario::from_text(&text));
+        match scenario {
+            Ok(scenario) => {
+                let evaluation = evaluate(&scenario, footprint_model);
+                if !evaluation.unknown_keys.is_empty() {
+                    eprintln!("{}: unknown parameters skipped: {}", path.display(), evaluation.unknown_keys.join(", "));
+                }
+                csv.push_str(&format!(
+                    "{},{},{}\n",
+                    csv_field(&path.display().to_string()),
+                    csv_field(scenario.metadata.title.trim()),
+                    evaluation.infos.to_csv_row()
+                ));
+            }
+            Err(error) => {
+                eprintln!("{}: {error}", path.display());
+                is_failed = true;
+            }
+        }
+    }
+
+    match output {
+        Some(output) => {
+            if let Err(error) = std::fs::write(&output, csv) {
+                eprintln!("{output}: {error}");
+                return 1;
+            }
+            eprintln!("{} scenario(s) written to {output}", paths.len());
+        }
+        None => print!("{csv}"),
+    }
+    if is_failed { 1 } else { 0 }
+}
+
+/// Gets the scenario files of a path: the file itself, the scenario files of
+/// a directory, or the files matching a file name pattern, sorted by name.
+fn scenario_paths(pattern: &str) -> Result<Vec<std::path::PathBuf>, String> {
+    use std::path::Path;
+
+    let path = Path::new(pattern);
+    let file_name = path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+    let (directory, name_pattern) = if path.is_dir() {
+        (path, format!("*.{SCENARIO_EXTENSION}"))
+    } else if file_name.contains(['*', '?']) {
+        (path.parent().filter(|parent| !parent.as_os_str().is_empty()).unwrap_or(Path::new(".")), file_name)
+    } else {
+        return Ok(vec![path.to_path_buf()]);
+    };
+    let entries = std::fs::read_dir(directory).map_err(|error| format!("{}: {error}", directory.display()))?;
+    let mut paths: Vec<_> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file())
+        .filter(|path| path.file_name().is_some_and(|name| matches_pattern(&name.to_string_lossy(), &name_pattern)))
+        .collect();
+    paths.sort();
+    Ok(paths)
+}
+
+/// Whether a file name matches a pattern, `*` matching any characters and `?`
+/// one character.
+fn matches_pattern(name: &str, pattern: &str) -> bool {
+    let name: Vec<char> = name.chars().collect();
+    let pattern: Vec<char> = pattern.chars().collect();
+    // Backtracks to the last `*`, extending its match by one character
+    let (mut i, mut j) = (0, 0);
+    let mut star = None;
+    while i < name.len() {
+        if j < pattern.len() && (pattern[j] == '?' || pattern[j] == name[i]) {
+            i += 1;
+            j += 1;
+        } else if j < pattern.len() && pattern[j] == '*' {
+            star = Some((j, i));
+            j += 1;
+        } else if let Some((star_j, star_i)) = star {
+            star = Some((star_j, star_i + 1));
+            i = star_i + 1;
+            j = star_j + 1;
+        } else {
+            return false;
+        }
+    }
+    pattern[j..].iter().all(|c| *c == '*')
+}
+
+/// Quotes a CSV field holding a separator, a quote or a line break.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+
+    #[test]
+    fn file_name_patterns() {
+        assert!(matches_pattern("pass_1.bsargeom", "*.bsargeom"));
+        assert!(matches_pattern("pass_1.bsargeom", "pass_?.*"));
+        assert!(matches_pattern("pass_12.bsargeom", "pass_*2.bsargeom"));
+        assert!(!matches_pattern("pass_12.csv", "*.bsargeom"));
+        assert!(!matches_pattern("pass_12.bsargeom", "pass_?.bsargeom"));
+        assert!(matches_pattern("a", "**"));
+        assert_eq!(csv_field("Pass 1, low"), "\"Pass 1, low\"");
+        assert_eq!(csv_field("Pass \"1\""), "\"Pass \"\"1\"\"\"");
+        assert_eq!(csv_field("Pass 1"), "Pass 1");
+    }
+
+    /// A scenario raising the Tx changes the infos, the default scenario
+    /// giving those of the default scene.
+    #[test]
+    fn evaluate_applies_the_parameters() {
+        let default = evaluate(&Scenario::default(), FootprintModel::Numeric);
+        assert!(default.unknown_keys.is_empty());
+        assert!(default.infos.nesz.is_finite());
+
+        let scenario = Scenario {
+            parameters: BTreeMap::from([("tx.height_m".to_string(), 6000.0), ("tx.wingspan_m".to_string(), 10.0)]),
+            ..Scenario::default()
+        };
+        let evaluation = evaluate(&scenario, FootprintModel::Numeric);
+        assert_eq!(evaluation.unknown_keys, vec!["tx.wingspan_m".to_string()]);
+        assert!(evaluation.infos.range_max_m > default.infos.range_max_m);
+        assert!(evaluation.tx_footprint.range_max_m > default.tx_footprint.range_max_m);
+
+        // The analytic footprints approximate the numeric ones, coarsely for
+        // the broad default beams
+        let analytic = evaluate(&scenario, FootprintModel::AnalyticEllipse);
+        assert_eq!(analytic.tx_footprint.model, FootprintModel::AnalyticEllipse);
+        assert!(analytic.tx_footprint.points.len() < evaluation.tx_footprint.points.len());
+        let relative_error = |analytic: f64, numeric: f64| (analytic - numeric).abs() / numeric;
+        assert!(relative_error(analytic.tx_footprint.area_m2, evaluation.tx_footprint.area_m2) < 0.2);
+        assert!(analytic.infos.nesz.is_finite());
+    }
+}
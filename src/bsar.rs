@@ -1,9 +1,14 @@
 //! BSAR geometry and resolutions functions.
 
+pub mod ambiguity;
+pub mod kspace;
+pub mod psf;
+
 use bevy::math::DVec3;
 
 use crate::{
-    constants::TO_Y_UP_F64,
+    antenna_pattern::{AntennaPattern, PatternCut},
+    constants::{MAX_PRF_HZ, TO_Y_UP_F64},
     entities::{AntennaBeamFootprintState, AntennaBeamState},
     scene::{RxCarrierState, TxCarrierState}
 };
@@ -18,13 +23,50 @@ pub const SPEED_OF_LIGHT_IN_VACUUM: f64 = 299792458.0; // m/s
 /// [`CODATA`]: https://codata.org/
 /// [`NIST`]: https://pml.nist.gov/cuu/Constants/
 pub const BOLTZMANN_CONSTANT: f64 = 1.380649e-23; // J/K
+/// Reference temperature `T0` \[K\] of the noise figures (IEEE standard).
+pub const REFERENCE_TEMPERATURE_K: f64 = 290.0;
 /// The width of squared normalized cardinal sine function at half height.
 /// 
 /// This constant is twice the positive solution of sinc²(x) = 1/2.
-const SINC_WIDTH_AT_HALF_POWER: f64 = 0.885892941378904715150369091935531;
+pub(crate) const SINC_WIDTH_AT_HALF_POWER: f64 = 0.885892941378904715150369091935531;
 /// The squared value of [`SINC_WIDTH_AT_HALF_POWER`].
 const SINC_WIDTH_AT_HALF_POWER_SQUARED: f64 = 0.784806303584967506070224247343716;
 
+/// Bistatic angle in degrees from which the geometry is near forward scatter:
+/// the bisector vanishes as the angle approaches 180° and the resolutions
+/// diverge.
+pub const FORWARD_SCATTER_BISTATIC_ANGLE_DEG: f64 = 170.0;
+/// Largest displayed resolution in meters, the larger ones of near forward
+/// scatter geometries being meaningless.
+pub const MAX_DISPLAYED_RESOLUTION_M: f64 = 1e4;
+
+/// Number of ambiguous pulses on each side of the echo summed by the RASR.
+const RASR_AMBIGUITIES: i32 = 5;
+/// Number of swath samples over which the RASR is evaluated.
+const RASR_SWATH_SAMPLES: usize = 33;
+/// Smallest bistatic range slope along the ground used by the RASR, bounding
+/// the ground extent of the range cells at the minimum bistatic range (nadir
+/// or specular point).
+const RASR_MIN_RANGE_SLOPE: f64 = 1e-2;
+
+/// Number of ambiguous Doppler bands on each side of the spectrum summed by
+/// the AASR.
+const AASR_AMBIGUITIES: i32 = 10;
+/// Number of samples of the processed Doppler bandwidth integrated by the AASR.
+const AASR_BANDWIDTH_SAMPLES: usize = 101;
+/// AASR in dB above which the azimuth ambiguities are visible in the image.
+pub const AASR_WARNING_DB: f64 = -20.0;
+
+/// Number of bisection steps of the PRF bounds search.
+const PRF_SEARCH_ITERATIONS: usize = 30;
+/// Margin of the suggested PRF over the lowest PRF meeting the Doppler
+/// sampling and azimuth ambiguity constraints.
+const PRF_SUGGESTION_MARGIN: f64 = 1.1;
+
+/// Relative difference from which the resolutions of the two formulas are
+/// said to diverge.
+pub const RESOLUTION_DIVERGENCE: f64 = 0.01;
+
 /// Returns `num / den` if `den` is strictly positive, `NaN` otherwise.
 ///
 /// All callers pass denominators built from norms or products of non-negative
@@ -36,6 +78,96 @@ fn div_or_nan(num: f64, den: f64) -> f64 {
     if den > 0.0 { num / den } else { f64::NAN }
 }
 
+/// Impact on the image of the Tx center frequency offset by an oscillator
+/// drift, the receiver demodulating and focusing at the nominal frequency.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FrequencyOffsetImpact {
+    /// Carrier frequency offset in Hz, left after the demodulation
+    pub carrier_offset_hz: f64,
+    /// Doppler frequency and rate changes, scaling with the carrier frequency
+    pub doppler_change_hz: f64,
+    pub doppler_rate_change_hzps: f64,
+    /// Shift of the image in m across the iso-Doppler lines: the Doppler
+    /// error over the ground Doppler gradient, with its sign
+    pub lateral_shift_m: f64,
+    /// Range scaling error in m at the scene center, the timing (PRF,
+    /// sampling) derived from the same oscillator
+    pub range_scaling_m: f64,
+    /// Quadratic phase error in degrees at the edges of the integration time,
+    /// focusing with the nominal Doppler rate
+    pub quadratic_phase_error_deg: f64,
+}
+
+/// A PRF and integration time pair giving square pixels: the lateral
+/// resolution equal to the range one, ground or slant as the receiver pixels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PrfSuggestion {
+    pub prf_hz: f64,
+    pub integration_time_s: f64,
+}
+
+impl Default for PrfSuggestion {
+    fn default() -> Self {
+        Self { prf_hz: f64::NAN, integration_time_s: f64::NAN }
+    }
+}
+
+impl PrfSuggestion {
+    #[inline]
+    pub fn is_valid(&self) -> bool {
+        self.prf_hz.is_finite() && self.integration_time_s.is_finite()
+    }
+}
+
+/// Formula of the resolutions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ResolutionFormula {
+    /// Bistatic bisector and its time derivative at the scene center: the
+    /// bisector rotates linearly over the integration time (small angle).
+    #[default]
+    Bisector,
+    /// Numerical gradients of the bistatic range at the start and end of the
+    /// integration time, the carriers moving along their trajectories.
+    ExactGradient,
+}
+
+impl ResolutionFormula {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Bisector => "Bisector",
+            Self::ExactGradient => "Exact gradient",
+        }
+    }
+}
+
+/// The resolutions of a formula, in meters and square meters.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Resolutions {
+    pub slant_range_m: f64,
+    pub slant_lateral_m: f64,
+    pub ground_range_m: f64,
+    pub ground_lateral_m: f64,
+    pub area_m2: f64,
+}
+
+impl Default for Resolutions {
+    fn default() -> Self {
+        Self {
+            slant_range_m: f64::NAN,
+            slant_lateral_m: f64::NAN,
+            ground_range_m: f64::NAN,
+            ground_lateral_m: f64::NAN,
+            area_m2: f64::NAN,
+        }
+    }
+}
+
+/// Checks whether two resolutions differ by more than
+/// [`RESOLUTION_DIVERGENCE`], `false` if one is invalid.
+pub fn resolutions_diverge(resolution: f64, other_resolution: f64) -> bool {
+    (resolution - other_resolution).abs() > RESOLUTION_DIVERGENCE * resolution.abs().min(other_resolution.abs())
+}
+
 pub struct BsarInfos {
     /// The bistatic range extrema over the footprint in meters.
     pub range_min_m: f64,
@@ -51,6 +183,10 @@ pub struct BsarInfos {
     pub ground_range_resolution_m: f64,
     pub ground_lateral_resolution_m: f64,
     pub resolution_area_m2: f64,
+    /// The formula of the resolution parameters, and the resolutions of the
+    /// other formula.
+    pub resolution_formula: ResolutionFormula,
+    pub alternative_resolutions: Resolutions,
     /// The Doppler frequency in Hz.
     pub doppler_frequency_hz: f64,
     /// The Doppler rate in Hz/s.
@@ -59,11 +195,26 @@ pub struct BsarInfos {
     pub integration_time_s: f64,
     /// The processed Doppler bandwidth in Hz.
     pub processed_doppler_bandwidth_hz: f64,
-    /// The PRF bounds in Hz (not computed yet).
+    /// The PRF bounds in Hz: the lowest PRF sampling the processed Doppler
+    /// bandwidth with an AASR under [`AASR_WARNING_DB`], and the highest one
+    /// keeping the receive window within the pulse repetition interval.
     pub prf_min_hz: f64,
     pub prf_max_hz: f64,
+    /// The suggested PRF and integration time for square pixels.
+    pub prf_suggestion: PrfSuggestion,
     /// The Noise-Equivalent Sigma Zero (linear scale).
     pub nesz: f64,
+    /// The Range Ambiguity to Signal Ratio (linear scale), worst case over the
+    /// swath.
+    pub rasr: f64,
+    /// The Azimuth Ambiguity to Signal Ratio (linear scale) of the processed
+    /// Doppler bandwidth.
+    pub aasr: f64,
+    /// The receiver range sampling frequency in Hz.
+    pub sampling_frequency_hz: f64,
+    /// The receiver data rate in complex samples per second, sampling the
+    /// receive window of every pulse.
+    pub data_rate_sps: f64,
     /// Ground-projected bistatic bisector vector and its time derivative
     /// (`z = 0`), reused to plot the Generalized Ambiguity Function.
     pub betag: DVec3,
@@ -83,13 +234,20 @@ impl Default for BsarInfos {
             ground_range_resolution_m: f64::NAN,
             ground_lateral_resolution_m: f64::NAN,
             resolution_area_m2: f64::NAN,
+            resolution_formula: ResolutionFormula::default(),
+            alternative_resolutions: Resolutions::default(),
             doppler_frequency_hz: f64::NAN,
             doppler_rate_hzps: f64::NAN,
             integration_time_s: f64::NAN,
             processed_doppler_bandwidth_hz: f64::NAN,
             prf_min_hz: f64::NAN,
             prf_max_hz: f64::NAN,
+            prf_suggestion: PrfSuggestion::default(),
             nesz: f64::NAN,
+            rasr: f64::NAN,
+            aasr: f64::NAN,
+            sampling_frequency_hz: f64::NAN,
+            data_rate_sps: f64::NAN,
             betag: DVec3::splat(f64::NAN),
             dbetag: DVec3::splat(f64::NAN),
         }
@@ -97,6 +255,117 @@ impl Default for BsarInfos {
 }
 
 impl BsarInfos {
+    /// Gets the ground range resolution direction: the unit vector (ENU,
+    /// `z = 0`) of the ground-projected bisector, across the iso-range lines.
+    ///
+    /// Returns `None` for invalid or degenerate (nadir) geometries.
+    #[inline]
+    pub fn ground_range_direction(&self) -> Option<DVec3> {
+        self.betag.try_normalize()
+    }
+
+    /// Gets the ground lateral resolution direction: the unit vector (ENU,
+    /// `z = 0`) of the ground-projected bisector derivative, across the
+    /// iso-Doppler lines.
+    ///
+    /// Returns `None` for invalid or degenerate (motionless) geometries.
+    #[inline]
+    pub fn ground_lateral_direction(&self) -> Option<DVec3> {
+        self.dbetag.try_normalize()
+    }
+
+    /// Gets the resolution parameters, of [`BsarInfos::resolution_formula`].
+    pub fn resolutions(&self) -> Resolutions {
+        Resolutions {
+            slant_range_m: self.slant_range_resolution_m,
+            slant_lateral_m: self.slant_lateral_resolution_m,
+            ground_range_m: self.ground_range_resolution_m,
+            ground_lateral_m: self.ground_lateral_resolution_m,
+            area_m2: self.resolution_area_m2,
+        }
+    }
+
+    /// Gets the impact of an offset of `offset_ppm` ppm of the Tx center
+    /// frequency `center_frequency_hz` on the Doppler and range scaling.
+    pub fn frequency_offset_impact(&self, center_frequency_hz: f64, offset_ppm: f64) -> FrequencyOffsetImpact {
+        let offset = 1e-6 * offset_ppm;
+        let lem = SPEED_OF_LIGHT_IN_VACUUM / center_frequency_hz; // wavelength in m
+        let carrier_offset_hz = center_frequency_hz * offset;
+        let doppler_change_hz = self.doppler_frequency_hz * offset;
+        let doppler_rate_change_hzps = self.doppler_rate_hzps * offset;
+        // Doppler gradient on the ground in Hz/m, as the ground lateral resolution
+        let doppler_gradient_hzpm = self.dbetag.length() / lem;
+        let half_integration_time_s = 0.5 * self.integration_time_s;
+        FrequencyOffsetImpact {
+            carrier_offset_hz,
+            doppler_change_hz,
+            doppler_rate_change_hzps,
+            lateral_shift_m: div_or_nan(carrier_offset_hz + doppler_change_hz, doppler_gradient_hzpm),
+            range_scaling_m: self.range_center_m * offset,
+            // π.ΔFR.(T/2)² in rad
+            quadratic_phase_error_deg: 180.0 * (doppler_rate_change_hzps * half_integration_time_s * half_integration_time_s).abs(),
+        }
+    }
+
+    fn set_resolutions(&mut self, resolutions: &Resolutions) {
+        self.slant_range_resolution_m = resolutions.slant_range_m;
+        self.slant_lateral_resolution_m = resolutions.slant_lateral_m;
+        self.ground_range_resolution_m = resolutions.ground_range_m;
+        self.ground_lateral_resolution_m = resolutions.ground_lateral_m;
+        self.resolution_area_m2 = resolutions.area_m2;
+    }
+
+    /// Checks whether the bistatic angle is at least
+    /// [`FORWARD_SCATTER_BISTATIC_ANGLE_DEG`], where the range and lateral
+    /// resolutions blow up.
+    #[inline]
+    pub fn is_near_forward_scatter(&self) -> bool {
+        self.bistatic_angle_deg >= FORWARD_SCATTER_BISTATIC_ANGLE_DEG
+    }
+
+    /// Header of the CSV rows written by [`BsarInfos::to_csv_row`].
+    pub const CSV_HEADER: &'static str = "range_min_m,range_max_m,range_center_m,direct_range_m,\
+bistatic_angle_deg,slant_range_resolution_m,slant_lateral_resolution_m,\
+ground_range_resolution_m,ground_lateral_resolution_m,resolution_area_m2,\
+doppler_frequency_hz,doppler_rate_hzps,integration_time_s,processed_doppler_bandwidth_hz,\
+nesz_db,sampling_frequency_hz,data_rate_sps,rasr_db,aasr_db";
+
+    /// Gets the scalar infos in the order of [`BsarInfos::CSV_HEADER`] (NESZ
+    /// and ambiguity ratios in dB, invalid values being NaN).
+    pub fn csv_values(&self) -> [f64; 19] {
+        [
+            self.range_min_m,
+            self.range_max_m,
+            self.range_center_m,
+            self.direct_range_m,
+            self.bistatic_angle_deg,
+            self.slant_range_resolution_m,
+            self.slant_lateral_resolution_m,
+            self.ground_range_resolution_m,
+            self.ground_lateral_resolution_m,
+            self.resolution_area_m2,
+            self.doppler_frequency_hz,
+            self.doppler_rate_hzps,
+            self.integration_time_s,
+            self.processed_doppler_bandwidth_hz,
+            10.0 * self.nesz.log10(),
+            self.sampling_frequency_hz,
+            self.data_rate_sps,
+            10.0 * self.rasr.log10(),
+            10.0 * self.aasr.log10(),
+        ]
+    }
+
+    /// Formats the scalar infos as a CSV row matching [`BsarInfos::CSV_HEADER`]
+    /// (NESZ and ambiguity ratios in dB, invalid values written as `NaN`).
+    pub fn to_csv_row(&self) -> String {
+        self.csv_values()
+            .iter()
+            .map(|value| value.to_string())
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
     pub fn update_from_state(
         &mut self,
         tx_state: &TxCarrierState,
@@ -106,41 +375,166 @@ impl BsarInfos {
         tx_footprint: &AntennaBeamFootprintState,
         rx_footprint: &AntennaBeamFootprintState,
     ) {
-        self.update(
+        self.update_from_state_at(
+            tx_state,
+            rx_state,
+            tx_antenna_beam_state,
+            rx_antenna_beam_state,
+            tx_footprint,
+            rx_footprint,
+            &DVec3::ZERO,
+        );
+        self.rasr = range_ambiguity_to_signal_ratio(
+            &tx_state.inner.position_m,
+            &rx_state.inner.position_m,
+            tx_footprint,
+            rx_footprint,
+            tx_state.prf_hz
+        );
+        let vrx = if rx_state.is_stationary { DVec3::ZERO } else { rx_state.inner.velocity_vector_mps };
+        let doppler_centroid_hz = self.doppler_frequency_hz;
+        let aasr = |processed_doppler_bandwidth_hz: f64, prf_hz: f64| azimuth_ambiguity_to_signal_ratio(
             &(-tx_state.inner.position_m),
             &tx_state.inner.velocity_vector_mps,
             &(-rx_state.inner.position_m),
-            &rx_state.inner.velocity_vector_mps,
+            &vrx,
+            SPEED_OF_LIGHT_IN_VACUUM / (tx_state.center_frequency_ghz * 1e9), // wavelength in m
+            tx_footprint,
+            rx_footprint,
+            doppler_centroid_hz,
+            processed_doppler_bandwidth_hz,
+            prf_hz
+        );
+        self.aasr = aasr(self.processed_doppler_bandwidth_hz, tx_state.prf_hz);
+        let processed_doppler_bandwidth_hz = self.processed_doppler_bandwidth_hz;
+        self.prf_min_hz = azimuth_prf_min_hz(processed_doppler_bandwidth_hz, |prf_hz| aasr(processed_doppler_bandwidth_hz, prf_hz));
+        // Square pixels: the lateral resolution scales as 1/T_int, the processed
+        // Doppler bandwidth as T_int
+        let (range_resolution_m, lateral_resolution_m) = if rx_state.pixel_resolution.is_ground() {
+            (self.ground_range_resolution_m, self.ground_lateral_resolution_m)
+        } else {
+            (self.slant_range_resolution_m, self.slant_lateral_resolution_m)
+        };
+        let integration_time_s = self.integration_time_s * div_or_nan(lateral_resolution_m, range_resolution_m);
+        let processed_doppler_bandwidth_hz = integration_time_s * self.doppler_rate_hzps.abs();
+        let prf_min_hz = azimuth_prf_min_hz(processed_doppler_bandwidth_hz, |prf_hz| aasr(processed_doppler_bandwidth_hz, prf_hz));
+        self.prf_suggestion = PrfSuggestion {
+            prf_hz: (PRF_SUGGESTION_MARGIN * prf_min_hz).ceil(),
+            integration_time_s,
+        };
+    }
+
+    /// Updates the infos at the ground point `point_m` (ENU, `z = 0`) instead
+    /// of the scene center, with the boresight gains of the antennas. The
+    /// footprint quantities (range extrema, data rate) stay the scene center
+    /// ones, and the ambiguity ratios, swath quantities, are left to
+    /// [`BsarInfos::update_from_state`].
+    pub fn update_from_state_at(
+        &mut self,
+        tx_state: &TxCarrierState,
+        rx_state: &RxCarrierState,
+        tx_antenna_beam_state: &AntennaBeamState,
+        rx_antenna_beam_state: &AntennaBeamState,
+        tx_footprint: &AntennaBeamFootprintState,
+        rx_footprint: &AntennaBeamFootprintState,
+        point_m: &DVec3,
+    ) {
+        // Carriers to the point vectors
+        let txp = point_m - tx_state.inner.position_m;
+        let rxp = point_m - rx_state.inner.position_m;
+        // A fixed receiver does not move: only the Tx motion contributes to the
+        // Doppler frequency and rate, and to the integration time
+        let vrx = if rx_state.is_stationary { DVec3::ZERO } else { rx_state.inner.velocity_vector_mps };
+        self.update(
+            &txp,
+            &tx_state.inner.velocity_vector_mps,
+            &rxp,
+            &vrx,
             tx_footprint,
             rx_footprint,
             tx_state.center_frequency_ghz * 1e9, // Convert GHz to Hz
             tx_state.bandwidth_mhz * 1e6, // Convert MHz to Hz
             rx_state.integration_time_s,
-            rx_state.squared_pixels, // If `true` the integration time is computed to have squared pixels ignoring input integration_time_s
+            rx_state.fixed_pixel_aspect_ratio.then_some(rx_state.pixel_aspect_ratio), // If set the integration time is computed for this pixel aspect ratio ignoring input integration_time_s
             rx_state.pixel_resolution.is_ground()
         );
+        if *point_m != DVec3::ZERO && self.range_min_m.is_finite() {
+            (self.range_min_m, self.range_max_m) = bsar_range_min_max(
+                &(-tx_state.inner.position_m),
+                &(-rx_state.inner.position_m),
+                tx_footprint,
+                rx_footprint
+            );
+        }
+        // Maneuvering carriers (turn, speed change) add a second-order term
+        // to the Doppler rate
+        let arx = if rx_state.is_stationary { None } else { rx_state.inner.acceleration_vector_mps2 };
+        if tx_state.inner.acceleration_vector_mps2.is_some() || arx.is_some() {
+            self.update_doppler_rate_with_accelerations(
+                &txp,
+                &tx_state.inner.acceleration_vector_mps2.unwrap_or(DVec3::ZERO),
+                &rxp,
+                &arx.unwrap_or(DVec3::ZERO),
+                tx_state.center_frequency_ghz * 1e9 // Convert GHz to Hz
+            );
+        }
+        // Resolutions of the selected formula, the bisector ones being those of
+        // update(), for the integration time it computed
+        let exact_resolutions = exact_gradient_resolutions(
+            &txp,
+            &tx_state.inner.velocity_vector_mps,
+            &tx_state.inner.acceleration_vector_mps2.unwrap_or(DVec3::ZERO),
+            &rxp,
+            &vrx,
+            &arx.unwrap_or(DVec3::ZERO),
+            tx_state.center_frequency_ghz * 1e9, // Convert GHz to Hz
+            tx_state.bandwidth_mhz * 1e6, // Convert MHz to Hz
+            self.integration_time_s
+        );
+        self.resolution_formula = rx_state.resolution_formula;
+        self.alternative_resolutions = match rx_state.resolution_formula {
+            ResolutionFormula::Bisector => exact_resolutions,
+            ResolutionFormula::ExactGradient => {
+                let bisector_resolutions = self.resolutions();
+                self.set_resolutions(&exact_resolutions);
+                bisector_resolutions
+            }
+        };
         // NESZ (Noise-Equivalent Sigma Zero) from the bistatic radar equation:
         //
-        //        (4π)³.R_tx².R_rx².k.T_rx.10^((L_tx + F_rx - G_tx - G_rx)/10)
-        // NESZ = ------------------------------------------------------------
-        //                    λ².P_peak.duty_cycle.T_int.A_res
+        //        (4π)³.R_tx².R_rx².k.T_rx.10^((L_tx + F_rx - G_tx - G_rx)/10).B_n/B
+        // NESZ = -----------------------------------------------------------------
+        //                      λ².P_peak.duty_cycle.T_int.A_res
         //
-        // with duty_cycle = pulse_duration.PRF and A_res the resolution cell area.
+        // with duty_cycle = pulse_duration.PRF, A_res the resolution cell area
+        // and B_n/B the receiver noise bandwidth over the Tx bandwidth (1 for a
+        // matched receiver).
         // Invalid geometries (T_int or A_res NaN) and zero duty cycle yield NaN.
         let lem = SPEED_OF_LIGHT_IN_VACUUM / (tx_state.center_frequency_ghz * 1e9); // wavelength in m
         let duty_cycle = tx_state.pulse_duration_us * 1e-6 * tx_state.prf_hz;
         self.nesz = div_or_nan(
             64.0 * std::f64::consts::PI.powi(3) *
-                tx_state.inner.position_m.length_squared() * // = R_tx²
-                rx_state.inner.position_m.length_squared() * // = R_rx²
+                txp.length_squared() * // = R_tx²
+                rxp.length_squared() * // = R_rx²
                 BOLTZMANN_CONSTANT * rx_state.noise_temperature_k *
                 10f64.powf(0.1 * (
                     tx_state.loss_factor_db + rx_state.noise_factor_db -
                     tx_antenna_beam_state.one_way_gain_dbi - rx_antenna_beam_state.one_way_gain_dbi
-                )),
+                )) * rx_state.noise_bandwidth_mhz,
             lem * lem * tx_state.peak_power_w * duty_cycle *
-                self.integration_time_s * self.resolution_area_m2
+                self.integration_time_s * self.resolution_area_m2 * tx_state.bandwidth_mhz
         );
+        // Data rate: the noise bandwidth is sampled over the receive window of
+        // every pulse, from the closest echo start to the farthest echo end
+        self.sampling_frequency_hz = rx_state.range_oversampling * rx_state.noise_bandwidth_mhz * 1e6;
+        let receive_window_s = if self.range_min_m <= self.range_max_m {
+            (self.range_max_m - self.range_min_m) / SPEED_OF_LIGHT_IN_VACUUM + tx_state.pulse_duration_us * 1e-6
+        } else {
+            f64::NAN // No footprint
+        };
+        self.data_rate_sps = self.sampling_frequency_hz * receive_window_s * tx_state.prf_hz;
+        // The echoes of a pulse are received before the next one is transmitted
+        self.prf_max_hz = receive_window_s.recip();
     }
 
     pub fn update(
@@ -154,7 +548,7 @@ impl BsarInfos {
         center_frequency_hz: f64,
         bandwidth_hz: f64,
         integration_time_s: f64,
-        squared_pixels: bool, // If `true` the integration time is computed to have squared pixels ignoring input integration_time_s
+        pixel_aspect_ratio: Option<f64>, // If set the integration time is computed for this range:lateral pixel aspect ratio ignoring input integration_time_s
         ground_resolution: bool, // If `true` the integration time is computed for ground resolution, otherwise for slant resolution
     ) {
         let mut txp_norm = txp.length_squared();
@@ -179,11 +573,13 @@ impl BsarInfos {
                 let dbetag_norm = dbetag.length();
                 // Integration time
                 let lem = SPEED_OF_LIGHT_IN_VACUUM / center_frequency_hz; // wavelength in m
-                self.integration_time_s = if squared_pixels {
+                // The range resolution k.c/(B.|β|) being `ratio` times the lateral
+                // resolution k.λ/(T.|dβ|) gives T = ratio.B/fc.|β|/|dβ|
+                self.integration_time_s = if let Some(ratio) = pixel_aspect_ratio {
                     if ground_resolution {
-                        bandwidth_hz / center_frequency_hz * div_or_nan(betag_norm, dbetag_norm)
+                        ratio * bandwidth_hz / center_frequency_hz * div_or_nan(betag_norm, dbetag_norm)
                     } else {
-                        bandwidth_hz / center_frequency_hz * div_or_nan(beta_norm, dbeta_norm)
+                        ratio * bandwidth_hz / center_frequency_hz * div_or_nan(beta_norm, dbeta_norm)
                     }
                 } else {
                     integration_time_s
@@ -199,12 +595,9 @@ impl BsarInfos {
                 // Direct range
                 self.direct_range_m = (txp - rxp).length();
                 // Bistatic angle
-                let arg = 0.5 * beta_norm;
-                self.bistatic_angle_deg = if arg > 1.0 { // Check range outside 1
-                    180.0
-                } else {
-                    (2.0 * arg.acos()).to_degrees()
-                };
+                // |beta| = 2.cos(bistatic angle / 2), rounding may exceed 2 for
+                // a zero angle
+                self.bistatic_angle_deg = (2.0 * (0.5 * beta_norm).min(1.0).acos()).to_degrees();
                 // Resolution parameters (guarded: degenerate geometries yield NaN, not inf)
                 self.slant_range_resolution_m =
                     div_or_nan(SINC_WIDTH_AT_HALF_POWER * SPEED_OF_LIGHT_IN_VACUUM, bandwidth_hz * beta_norm);
@@ -227,7 +620,7 @@ impl BsarInfos {
                     vrx.length_squared() * (1.0 - singamma_rx * singamma_rx) / rxp_norm
                 ) / lem;
                 self.processed_doppler_bandwidth_hz = self.integration_time_s * self.doppler_rate_hzps.abs();
-                // TODO NESZ
+                // The NESZ needs the radar parameters, see update_from_state_at
             } else {
                 // rxp is a zero vector: all fields are invalid (NaN)
                 *self = Self::default();
@@ -237,6 +630,100 @@ impl BsarInfos {
             *self = Self::default();
         }
     }
+
+    /// Adds the carriers acceleration terms to the Doppler rate computed by
+    /// [`BsarInfos::update`] (uniform straight motions), and updates the
+    /// processed Doppler bandwidth accordingly.
+    ///
+    /// With `u` the unit vector from a carrier to the scene center, the Doppler
+    /// frequency `v·u / λ` has the time derivative `(a·u + v·du/dt) / λ`: the
+    /// acceleration `a` adds `a·u / λ` per carrier to the Doppler rate.
+    pub fn update_doppler_rate_with_accelerations(
+        &mut self,
+        txp: &DVec3,
+        atx: &DVec3,
+        rxp: &DVec3,
+        arx: &DVec3,
+        center_frequency_hz: f64,
+    ) {
+        let lem = SPEED_OF_LIGHT_IN_VACUUM / center_frequency_hz; // wavelength in m
+        self.doppler_rate_hzps += (atx.dot(txp.normalize_or_zero()) + arx.dot(rxp.normalize_or_zero())) / lem;
+        self.processed_doppler_bandwidth_hz = self.integration_time_s * self.doppler_rate_hzps.abs();
+    }
+}
+
+/// Computes the resolutions from the numerical gradients of the bistatic
+/// range, without the small-angle approximation of the bisector formulas.
+///
+/// The range resolutions come from the gradient `∇R` of the bistatic range
+/// at the scene center, the lateral ones from its rotation `Δ∇R` between the
+/// start and the end of the integration time, the carriers moving with their
+/// velocities and accelerations (`k` = [`SINC_WIDTH_AT_HALF_POWER`]):
+///
+/// ```text
+/// range = k.c / (B.|∇R|)     lateral = k.λ / |Δ∇R|     area = k².c.λ / (B.|∇R_g × Δ∇R_g|)
+/// ```
+///
+/// the ground resolutions using the ground projections (`z = 0`) `∇R_g` and
+/// `Δ∇R_g`. For short integration times `Δ∇R = T_int.dβ/dt` and they match
+/// [`BsarInfos::update`].
+///
+/// * `txp` and `rxp` are the carriers -> scene center vectors in m (ENU)
+/// * `vtx`, `vrx` and `atx`, `arx` are the carriers velocity and acceleration
+///   vectors in m/s and m/s² (ENU)
+pub fn exact_gradient_resolutions(
+    txp: &DVec3,
+    vtx: &DVec3,
+    atx: &DVec3,
+    rxp: &DVec3,
+    vrx: &DVec3,
+    arx: &DVec3,
+    center_frequency_hz: f64,
+    bandwidth_hz: f64,
+    integration_time_s: f64,
+) -> Resolutions {
+    // Central finite differences, the scene center at the origin
+    let step_m = 1e-3 * txp.length().min(rxp.length());
+    if !(step_m > 0.0) {
+        return Resolutions::default();
+    }
+    let range_gradient = |t: f64| {
+        let tx_m = -*txp + *vtx * t + 0.5 * *atx * t * t;
+        let rx_m = -*rxp + *vrx * t + 0.5 * *arx * t * t;
+        let range = |point_m: DVec3| point_m.distance(tx_m) + point_m.distance(rx_m);
+        DVec3::new(
+            range(DVec3::X * step_m) - range(-DVec3::X * step_m),
+            range(DVec3::Y * step_m) - range(-DVec3::Y * step_m),
+            range(DVec3::Z * step_m) - range(-DVec3::Z * step_m),
+        ) / (2.0 * step_m)
+    };
+    let gradient = range_gradient(0.0);
+    let rotation = range_gradient(0.5 * integration_time_s) - range_gradient(-0.5 * integration_time_s);
+    let (ground_gradient, ground_rotation) = (gradient.with_z(0.0), rotation.with_z(0.0));
+    let lem = SPEED_OF_LIGHT_IN_VACUUM / center_frequency_hz; // wavelength in m
+    Resolutions {
+        slant_range_m: div_or_nan(SINC_WIDTH_AT_HALF_POWER * SPEED_OF_LIGHT_IN_VACUUM, bandwidth_hz * gradient.length()),
+        slant_lateral_m: div_or_nan(SINC_WIDTH_AT_HALF_POWER * lem, rotation.length()),
+        ground_range_m: div_or_nan(SINC_WIDTH_AT_HALF_POWER * SPEED_OF_LIGHT_IN_VACUUM, bandwidth_hz * ground_gradient.length()),
+        ground_lateral_m: div_or_nan(SINC_WIDTH_AT_HALF_POWER * lem, ground_rotation.length()),
+        area_m2: div_or_nan(
+            SINC_WIDTH_AT_HALF_POWER_SQUARED * SPEED_OF_LIGHT_IN_VACUUM * lem,
+            bandwidth_hz * ground_gradient.cross(ground_rotation).length()
+        ),
+    }
+}
+
+/// Gets the footprint used for the BSAR system extrema: heuristically the one
+/// with the smallest `ground_range_swath_m`.
+fn bsar_footprint<'a>(
+    tx_footprint: &'a AntennaBeamFootprintState,
+    rx_footprint: &'a AntennaBeamFootprintState,
+) -> &'a AntennaBeamFootprintState {
+    if rx_footprint.ground_range_swath_m <= tx_footprint.ground_range_swath_m {
+        rx_footprint
+    } else {
+        tx_footprint
+    }
 }
 
 /// Computes the BSAR system min and max ranges in meters
@@ -251,44 +738,339 @@ pub fn bsar_range_min_max(
 ) -> (f64, f64) {
     // Transform to Y-up coordinate system for computation with antenna beam footprint
     let txp_yup = TO_Y_UP_F64 * *txp;
-    let rxp_yup = TO_Y_UP_F64 * *rxp;    
+    let rxp_yup = TO_Y_UP_F64 * *rxp;
     let mut min_range = f64::MAX;
     let mut max_range = 0.0;
     // Temporary variables
     let mut range: f64;
-    if rx_footprint.ground_range_swath_m <= tx_footprint.ground_range_swath_m {
-        // Use Rx footprint
-        for p in rx_footprint.points.iter() {
-            // Compute range to Tx footprint
-            range = (txp_yup + p).length() + (rxp_yup + p).length();
-            // Min range
-            if range < min_range {
-                min_range = range;
-            }
-            // Max range
-            if range > max_range {
-                max_range = range;
-            }
+    for p in bsar_footprint(tx_footprint, rx_footprint).points.iter() {
+        // Compute range to footprint point
+        range = (txp_yup + p).length() + (rxp_yup + p).length();
+        // Min range
+        if range < min_range {
+            min_range = range;
         }
-    } else {
-        // Use Tx footprint
-        for p in tx_footprint.points.iter() {
-            // Compute range to Rx footprint
-            range = (txp_yup + p).length() + (rxp_yup + p).length();
-            // Min range
-            if range < min_range {
-                min_range = range;
-            }
-            // Max range
-            if range > max_range {
-                max_range = range;
-            }
+        // Max range
+        if range > max_range {
+            max_range = range;
         }
     }
 
     (min_range, max_range)
 }
 
+/// Elevation cut of an antenna beam, through the elevation edges of its
+/// footprint.
+struct ElevationCut {
+    /// Antenna position in meters (ENU)
+    position_m: DVec3,
+    /// Unit vectors of the boresight and of the elevation plane normal
+    boresight: DVec3,
+    normal: DVec3,
+    /// Half-power beam width in degrees
+    beam_width_deg: f64,
+}
+
+impl ElevationCut {
+    /// Gets the elevation cut of the antenna at `position_m` (ENU), or `None`
+    /// for a degenerate footprint.
+    fn new(position_m: &DVec3, footprint: &AntennaBeamFootprintState) -> Option<Self> {
+        let [_, elevation_plus, _, elevation_minus] = footprint.edge_points();
+        let to_enu = TO_Y_UP_F64.inverse();
+        let plus = (to_enu * elevation_plus - *position_m).try_normalize()?;
+        let minus = (to_enu * elevation_minus - *position_m).try_normalize()?;
+        Some(Self {
+            position_m: *position_m,
+            boresight: (plus + minus).try_normalize()?,
+            normal: plus.cross(minus).try_normalize()?,
+            beam_width_deg: plus.angle_between(minus).to_degrees(),
+        })
+    }
+
+    /// Gets the one-way power gain (linear, normalized) towards `point_m`
+    /// (ENU) of a uniformly illuminated aperture (sinc²), from its angle to
+    /// the boresight in the elevation plane.
+    fn gain(&self, point_m: &DVec3) -> f64 {
+        let direction = *point_m - self.position_m;
+        let angle_deg = self.boresight.angle_between(direction - direction.dot(self.normal) * self.normal).to_degrees();
+        10f64.powf(0.1 * AntennaPattern::Sinc.gain_db(PatternCut::Elevation, angle_deg, self.beam_width_deg))
+    }
+}
+
+/// Computes the Range Ambiguity to Signal Ratio (linear) of the BSAR system,
+/// worst case over the swath, or `NaN` for a degenerate geometry.
+///
+/// The echoes are taken along the ground range line through the scene center
+/// (ENU origin), along the ground-projected bisector: at each sample of the
+/// swath (the bistatic ranges of [`bsar_range_min_max`]), the ground points
+/// whose echoes of the `±1..±RASR_AMBIGUITIES` neighbouring pulses arrive at
+/// the same time, i.e. of bistatic range shifted by multiples of `c / PRF`,
+/// are summed on both sides of the minimum bistatic range:
+///
+/// ```text
+///        Σ_j G_tx(P_j).G_rx(P_j) / (R_tx(P_j)².R_rx(P_j)².|dR/ds(P_j)|)
+/// RASR = --------------------------------------------------------------
+///              G_tx(P).G_rx(P) / (R_tx(P)².R_rx(P)².|dR/ds(P)|)
+/// ```
+///
+/// with a uniform backscatter, `1 / |dR/ds|` being the ground extent of a
+/// range cell and the gains the elevation cuts of uniformly illuminated
+/// apertures of the footprint elevation beam widths (flat Earth).
+///
+/// * `tx_position_m` and `rx_position_m` are the carriers positions in m (ENU)
+/// * `prf_hz` is the pulse repetition frequency in Hz
+pub fn range_ambiguity_to_signal_ratio(
+    tx_position_m: &DVec3,
+    rx_position_m: &DVec3,
+    tx_footprint: &AntennaBeamFootprintState,
+    rx_footprint: &AntennaBeamFootprintState,
+    prf_hz: f64,
+) -> f64 {
+    let (Some(tx_cut), Some(rx_cut)) = (
+        ElevationCut::new(tx_position_m, tx_footprint),
+        ElevationCut::new(rx_position_m, rx_footprint)
+    ) else {
+        return f64::NAN;
+    };
+    // Ground range line through the scene center
+    let beta = -(tx_position_m.normalize_or_zero() + rx_position_m.normalize_or_zero());
+    let Some(direction) = DVec3::new(beta.x, beta.y, 0.0).try_normalize() else {
+        return f64::NAN;
+    };
+    if prf_hz <= 0.0 {
+        return f64::NAN;
+    }
+    let point = |s: f64| s * direction;
+    let range = |s: f64| tx_position_m.distance(point(s)) + rx_position_m.distance(point(s));
+    let weight = |s: f64| {
+        let p = point(s);
+        let (txp, rxp) = (p - *tx_position_m, p - *rx_position_m);
+        let slope = direction.dot(txp.normalize_or_zero() + rxp.normalize_or_zero());
+        div_or_nan(
+            tx_cut.gain(&p) * rx_cut.gain(&p),
+            txp.length_squared() * rxp.length_squared() * slope.abs().max(RASR_MIN_RANGE_SLOPE)
+        )
+    };
+    // Minimum bistatic range on the line (golden section search), the range
+    // being convex along it and larger than at the center beyond `bound`
+    let bound = tx_position_m.length() + rx_position_m.length();
+    let (mut a, mut b) = (-bound, bound);
+    let ratio = 0.5 * (5f64.sqrt() - 1.0);
+    for _ in 0..100 {
+        let (c, d) = (b - ratio * (b - a), a + ratio * (b - a));
+        if range(c) < range(d) { b = d } else { a = c }
+    }
+    let s_min = 0.5 * (a + b);
+    let range_min_on_line = range(s_min);
+    // Point of bistatic range `range_m` on the side `side` (±1) of the minimum
+    let root = |range_m: f64, side: f64| {
+        if !range_m.is_finite() || range_m < range_min_on_line {
+            return None;
+        }
+        let mut step = bound;
+        while range(s_min + side * step) < range_m {
+            step *= 2.0;
+        }
+        let (mut near, mut far) = (0.0, step);
+        for _ in 0..100 {
+            let middle = 0.5 * (near + far);
+            if range(s_min + side * middle) < range_m { near = middle } else { far = middle }
+        }
+        Some(s_min + side * 0.5 * (near + far))
+    };
+    // Swath on the scene center side of the minimum
+    let side = if s_min <= 0.0 { 1.0 } else { -1.0 };
+    let (range_min_m, range_max_m) = bsar_range_min_max(&(-*tx_position_m), &(-*rx_position_m), tx_footprint, rx_footprint);
+    let (Some(s_near), Some(s_far)) = (root(range_min_m.max(range_min_on_line), side), root(range_max_m, side)) else {
+        return f64::NAN;
+    };
+    let ambiguous_range_m = SPEED_OF_LIGHT_IN_VACUUM / prf_hz;
+    let mut rasr = f64::NAN;
+    for i in 0..RASR_SWATH_SAMPLES {
+        let s = s_near + (s_far - s_near) * i as f64 / (RASR_SWATH_SAMPLES - 1) as f64;
+        let range_m = range(s);
+        let ambiguities: f64 = (-RASR_AMBIGUITIES..=RASR_AMBIGUITIES)
+            .filter(|&j| j != 0)
+            .flat_map(|j| [-1.0, 1.0].map(|side| root(range_m + j as f64 * ambiguous_range_m, side)))
+            .flatten()
+            .map(weight)
+            .sum();
+        rasr = rasr.max(div_or_nan(ambiguities, weight(s)));
+    }
+    rasr
+}
+
+/// Computes the Azimuth Ambiguity to Signal Ratio (linear) of the BSAR
+/// system, or `NaN` for a degenerate geometry.
+///
+/// The azimuth pattern of each antenna is mapped to the Doppler frequency: a
+/// uniformly illuminated aperture (sinc²) centered on the Doppler frequency
+/// between its footprint azimuth edges, of half-power width their Doppler
+/// span. The two-way pattern `G(f) = G_tx(f).G_rx(f)` aliased by the PRF is
+/// integrated over the processed Doppler bandwidth `B_p` centered on the scene
+/// center Doppler frequency `f_dc`:
+///
+/// ```text
+///        Σ_m≠0 ∫ G(f + m.PRF) df
+/// AASR = -----------------------,  f in [f_dc - B_p/2, f_dc + B_p/2]
+///              ∫ G(f) df
+/// ```
+///
+/// * `txp` and `rxp` are the carriers -> scene center vectors in m (ENU)
+/// * `vtx` and `vrx` are the carriers velocity vectors in m/s (ENU)
+/// * `lem` is the wavelength in m
+pub fn azimuth_ambiguity_to_signal_ratio(
+    txp: &DVec3,
+    vtx: &DVec3,
+    rxp: &DVec3,
+    vrx: &DVec3,
+    lem: f64,
+    tx_footprint: &AntennaBeamFootprintState,
+    rx_footprint: &AntennaBeamFootprintState,
+    doppler_centroid_hz: f64,
+    processed_doppler_bandwidth_hz: f64,
+    prf_hz: f64,
+) -> f64 {
+    // Transform to Y-up coordinate system for computation with antenna beam footprint
+    let txp_yup = TO_Y_UP_F64 * *txp;
+    let rxp_yup = TO_Y_UP_F64 * *rxp;
+    let vtx_yup = TO_Y_UP_F64 * *vtx;
+    let vrx_yup = TO_Y_UP_F64 * *vrx;
+    let doppler = |p: &DVec3| doppler_frequency_sg(lem, &(txp_yup + p), &vtx_yup, &(rxp_yup + p), &vrx_yup);
+    // Doppler center and half-power width of an azimuth pattern
+    let pattern = |footprint: &AntennaBeamFootprintState| {
+        let [azimuth_plus, _, azimuth_minus, _] = footprint.edge_points();
+        let (doppler_plus_hz, doppler_minus_hz) = (doppler(&azimuth_plus), doppler(&azimuth_minus));
+        (0.5 * (doppler_plus_hz + doppler_minus_hz), (doppler_plus_hz - doppler_minus_hz).abs())
+    };
+    let patterns = [pattern(tx_footprint), pattern(rx_footprint)];
+    let is_valid = prf_hz > 0.0
+        && processed_doppler_bandwidth_hz > 0.0
+        && doppler_centroid_hz.is_finite()
+        && patterns.iter().all(|&(center_hz, width_hz)| center_hz.is_finite() && width_hz > 0.0);
+    if !is_valid {
+        return f64::NAN;
+    }
+    let gain = |doppler_hz: f64| {
+        patterns.iter()
+            .map(|&(center_hz, width_hz)| {
+                10f64.powf(0.1 * AntennaPattern::Sinc.gain_db(PatternCut::Azimuth, doppler_hz - center_hz, width_hz))
+            })
+            .product::<f64>()
+    };
+    let (mut signal, mut ambiguities) = (0.0, 0.0);
+    for i in 0..AASR_BANDWIDTH_SAMPLES {
+        let doppler_hz = doppler_centroid_hz
+            + processed_doppler_bandwidth_hz * (i as f64 / (AASR_BANDWIDTH_SAMPLES - 1) as f64 - 0.5);
+        signal += gain(doppler_hz);
+        ambiguities += (-AASR_AMBIGUITIES..=AASR_AMBIGUITIES)
+            .filter(|&m| m != 0)
+            .map(|m| gain(doppler_hz + m as f64 * prf_hz))
+            .sum::<f64>();
+    }
+    div_or_nan(ambiguities, signal)
+}
+
+/// Gets the lowest PRF in Hz sampling the processed Doppler bandwidth with an
+/// Azimuth Ambiguity to Signal Ratio `aasr(prf_hz)` (linear) at most
+/// [`AASR_WARNING_DB`], or `NaN` when no PRF up to [`MAX_PRF_HZ`] meets
+/// it. The AASR falls with the PRF: the bound is searched by bisection on a
+/// logarithmic scale.
+pub fn azimuth_prf_min_hz(processed_doppler_bandwidth_hz: f64, aasr: impl Fn(f64) -> f64) -> f64 {
+    let max_aasr = 10f64.powf(0.1 * AASR_WARNING_DB);
+    // NaN ratios never meet the bound
+    let is_met = |prf_hz: f64| aasr(prf_hz) <= max_aasr;
+    let is_valid = processed_doppler_bandwidth_hz > 0.0
+        && is_met(MAX_PRF_HZ.max(processed_doppler_bandwidth_hz));
+    if !is_valid {
+        return f64::NAN;
+    }
+    if is_met(processed_doppler_bandwidth_hz) {
+        return processed_doppler_bandwidth_hz;
+    }
+    let (mut low, mut high) = (processed_doppler_bandwidth_hz.ln(), MAX_PRF_HZ.ln());
+    for _ in 0..PRF_SEARCH_ITERATIONS {
+        let middle = 0.5 * (low + high);
+        if is_met(middle.exp()) {
+            high = middle;
+        } else {
+            low = middle;
+        }
+    }
+    high.exp()
+}
+
+/// A BSAR system extremum over the footprint.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BsarExtremum {
+    /// Footprint point in World frame (Y-up) in meters
+    pub point_m: DVec3,
+    /// Value at the footprint point
+    pub value: f64,
+}
+
+/// The BSAR system bistatic range and Doppler frequency extrema over the
+/// footprint, with the footprint points where they are reached.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BsarExtrema {
+    /// Bistatic range extrema in meters
+    pub range_min: BsarExtremum,
+    pub range_max: BsarExtremum,
+    /// Doppler frequency extrema in Hz
+    pub doppler_min: BsarExtremum,
+    pub doppler_max: BsarExtremum,
+}
+
+/// Computes the BSAR system bistatic range and Doppler frequency extrema over
+/// the footprint used by [`bsar_range_min_max`], or `None` for an empty
+/// footprint.
+///
+/// * `txp` and `rxp` are the carriers -> scene center vectors in m (ENU)
+/// * `vtx` and `vrx` are the carriers velocity vectors in m/s (ENU)
+/// * `lem` is the wavelength in m
+pub fn bsar_extrema(
+    txp: &DVec3,
+    vtx: &DVec3,
+    rxp: &DVec3,
+    vrx: &DVec3,
+    lem: f64,
+    tx_footprint: &AntennaBeamFootprintState,
+    rx_footprint: &AntennaBeamFootprintState,
+) -> Option<BsarExtrema> {
+    // Transform to Y-up coordinate system for computation with antenna beam footprint
+    let txp_yup = TO_Y_UP_F64 * *txp;
+    let rxp_yup = TO_Y_UP_F64 * *rxp;
+    let vtx_yup = TO_Y_UP_F64 * *vtx;
+    let vrx_yup = TO_Y_UP_F64 * *vrx;
+    let mut points = bsar_footprint(tx_footprint, rx_footprint).points.iter();
+    let extremum = |p: &DVec3, value: f64| BsarExtremum { point_m: *p, value };
+    let first = points.next()?;
+    let range = |p: &DVec3| bistatic_range_sg(&(txp_yup + p), &(rxp_yup + p));
+    let doppler = |p: &DVec3| doppler_frequency_sg(lem, &(txp_yup + p), &vtx_yup, &(rxp_yup + p), &vrx_yup);
+    let mut extrema = BsarExtrema {
+        range_min: extremum(first, range(first)),
+        range_max: extremum(first, range(first)),
+        doppler_min: extremum(first, doppler(first)),
+        doppler_max: extremum(first, doppler(first)),
+    };
+    for p in points {
+        let range = range(p);
+        if range < extrema.range_min.value {
+            extrema.range_min = extremum(p, range);
+        } else if range > extrema.range_max.value {
+            extrema.range_max = extremum(p, range);
+        }
+        let doppler = doppler(p);
+        if doppler < extrema.doppler_min.value {
+            extrema.doppler_min = extremum(p, doppler);
+        } else if doppler > extrema.doppler_max.value {
+            extrema.doppler_max = extremum(p, doppler);
+        }
+    }
+
+    Some(extrema)
+}
+
 /// Returns the bistatic angle formed by triangle Transmitter - ground point - Receiver in radians.
 ///
 /// * `txp` is the Transmitter -> ground point vector in m, i.e., `TxP = OP - OTx` with `OP` the targeted ground point
@@ -305,12 +1087,8 @@ pub fn bistatic_angle_sg(
         let arg = 0.5 * (
             txp / txp_norm.sqrt() +
             rxp / rxp_norm.sqrt()
-        ).length(); // = 0.5 * beta.length()
-        if arg > 1.0 { // Check range outside 1            
-            std::f64::consts::PI
-        } else {
-            2.0 * arg.acos()
-        }
+        ).length(); // = 0.5 * beta.length(), rounding may exceed 1 for a zero angle
+        2.0 * arg.min(1.0).acos()
     } else { // There is no triangle
         0.0
     }
@@ -355,6 +1133,22 @@ pub fn doppler_frequency_sg(
     }
 }
 
+/// Computes the system noise temperature in K of a receiving chain, referred
+/// to the antenna output: the antenna of noise temperature
+/// `antenna_temperature_k`, a cable of loss `cable_loss_db` at the reference
+/// temperature, then the LNA of noise figure `lna_noise_figure_db` (Friis
+/// formula, the LNA gain masking the following stages):
+///
+/// ```text
+/// T_sys = T_A + (L - 1).T0 + L.(F - 1).T0
+/// ```
+pub fn system_noise_temperature_k(antenna_temperature_k: f64, lna_noise_figure_db: f64, cable_loss_db: f64) -> f64 {
+    let cable_loss = 10f64.powf(0.1 * cable_loss_db);
+    let noise_factor = 10f64.powf(0.1 * lna_noise_figure_db);
+    antenna_temperature_k + (cable_loss - 1.0) * REFERENCE_TEMPERATURE_K
+        + cable_loss * (noise_factor - 1.0) * REFERENCE_TEMPERATURE_K
+}
+
 /// Normalized cardinal sine `sin(πx)/(πx)`, with `sinc(0) = 1`.
 /// Matches BSARConf's `sinc` (used to plot the Generalized Ambiguity Function).
 #[inline]
@@ -397,7 +1191,7 @@ mod tests {
 
     /// Runs `update()` for a monostatic broadside geometry:
     /// carrier at range R with velocity orthogonal to the line of sight.
-    fn monostatic_broadside(velocity: f64, tint: f64, squared_pixels: bool) -> BsarInfos {
+    fn monostatic_broadside(velocity: f64, tint: f64, pixel_aspect_ratio: Option<f64>) -> BsarInfos {
         let mut infos = BsarInfos::default();
         let txp = DVec3::new(0.0, 10_000.0, 0.0); // carrier -> target vector, R = 10 km
         let vtx = DVec3::new(velocity, 0.0, 0.0); // broadside: v orthogonal to LOS
@@ -408,7 +1202,7 @@ mod tests {
             10.0e9,  // 10 GHz
             300.0e6, // 300 MHz
             tint,
-            squared_pixels,
+            pixel_aspect_ratio,
             true
         );
         infos
@@ -419,7 +1213,7 @@ mod tests {
         let r = 10_000.0;
         let (fc, bandwidth, tint, v) = (10.0e9, 300.0e6, 1.0, 100.0);
         let lem = SPEED_OF_LIGHT_IN_VACUUM / fc;
-        let infos = monostatic_broadside(v, tint, false);
+        let infos = monostatic_broadside(v, tint, None);
         // Monostatic: zero bistatic angle, ranges are twice the slant range
         assert_close(infos.bistatic_angle_deg, 0.0, 1e-12);
         assert_close(infos.range_center_m, 2.0 * r, 1e-12);
@@ -444,10 +1238,175 @@ mod tests {
         assert_close(infos.doppler_rate_hzps, -2.0 * v * v / (lem * r), 1e-12);
     }
 
+    #[test]
+    fn forward_scatter_is_detected() {
+        let mut infos = monostatic_broadside(100.0, 1.0, None);
+        assert!(!infos.is_near_forward_scatter());
+        // Target 10 m below the Tx/Rx line, 1 km from the Tx and 2 km from the Rx
+        let (txp, rxp) = (DVec3::new(1_000.0, 0.0, -10.0), DVec3::new(-2_000.0, 0.0, -10.0));
+        let v = DVec3::new(0.0, 100.0, 0.0);
+        infos.update(
+            &txp, &v, &rxp, &v,
+            &AntennaBeamFootprintState::default(),
+            &AntennaBeamFootprintState::default(),
+            10.0e9,
+            300.0e6,
+            1.0,
+            None,
+            true
+        );
+        assert_close(infos.bistatic_angle_deg, 180.0 - (0.01f64.atan() + 0.005f64.atan()).to_degrees(), 1e-9);
+        assert!(infos.is_near_forward_scatter());
+        // |beta| = 2.cos(89.57°): the range resolutions blow up
+        assert!(infos.slant_range_resolution_m > 40.0);
+        assert!(infos.ground_range_resolution_m > MAX_DISPLAYED_RESOLUTION_M);
+    }
+
+    #[test]
+    fn rasr_grows_with_the_prf() {
+        // Monostatic side-looking carrier, the elevation beam edges 3 km before
+        // and beyond the scene center: 10.7 km of bistatic range swath
+        let position_m = DVec3::new(-10_000.0, 0.0, 5_000.0);
+        let mut footprint = AntennaBeamFootprintState { points: vec![DVec3::ZERO; 5], ..Default::default() };
+        footprint.points[1] = TO_Y_UP_F64 * DVec3::new(-3_000.0, 0.0, 0.0);
+        footprint.points[3] = TO_Y_UP_F64 * DVec3::new(3_000.0, 0.0, 0.0);
+        let rasr_db = |prf_hz: f64| {
+            10.0 * range_ambiguity_to_signal_ratio(&position_m, &position_m, &footprint, &footprint, prf_hz).log10()
+        };
+        // Ambiguities in the far sidelobes
+        assert!(rasr_db(2e3) < -50.0);
+        assert!(rasr_db(5e3) < rasr_db(10e3));
+        // c / PRF = 15 km: the swath edges are ambiguous with the first sidelobes
+        assert!(rasr_db(20e3) > -10.0);
+        assert!(range_ambiguity_to_signal_ratio(&position_m, &position_m, &footprint, &footprint, 0.0).is_nan());
+    }
+
+    #[test]
+    fn aasr_falls_with_the_prf() {
+        // Monostatic side-looking carrier flying north, the azimuth beam edges
+        // 1 km north and south of the scene center: 1188 Hz of Doppler span
+        let (txp, v, lem) = (DVec3::new(10_000.0, 0.0, -5_000.0), DVec3::new(0.0, 100.0, 0.0), 0.03);
+        let mut footprint = AntennaBeamFootprintState { points: vec![DVec3::ZERO; 5], ..Default::default() };
+        footprint.points[0] = TO_Y_UP_F64 * DVec3::new(0.0, 1_000.0, 0.0);
+        footprint.points[2] = TO_Y_UP_F64 * DVec3::new(0.0, -1_000.0, 0.0);
+        let doppler_span_hz = 4.0 * 100.0 * 1_000.0 / (lem * 126e6f64.sqrt());
+        let aasr_db = |prf_hz: f64| {
+            10.0 * azimuth_ambiguity_to_signal_ratio(
+                &txp, &v, &txp, &v, lem, &footprint, &footprint, 0.0, 0.8 * doppler_span_hz, prf_hz
+            ).log10()
+        };
+        // Sampling the Doppler span: ambiguities from the main lobe edges
+        assert!(aasr_db(doppler_span_hz) > -15.0);
+        assert!(aasr_db(1.5 * doppler_span_hz) < AASR_WARNING_DB);
+        assert!(aasr_db(3.0 * doppler_span_hz) < aasr_db(2.0 * doppler_span_hz));
+        assert!(aasr_db(0.0).is_nan());
+    }
+
+    #[test]
+    fn frequency_offset_impact_scales_with_the_offset() {
+        let bsar_infos = BsarInfos {
+            range_center_m: 20_000.0,
+            doppler_frequency_hz: 100.0,
+            doppler_rate_hzps: -40.0,
+            integration_time_s: 2.0,
+            dbetag: DVec3::new(0.0, 0.01, 0.0),
+            ..BsarInfos::default()
+        };
+        // 10 GHz: 3 cm wavelength, 1/3 Hz/m Doppler gradient
+        let impact = bsar_infos.frequency_offset_impact(10e9, 1.0);
+        assert_close(impact.carrier_offset_hz, 1e4, 1e-12);
+        assert_close(impact.doppler_change_hz, 1e-4, 1e-12);
+        assert_close(impact.doppler_rate_change_hzps, -4e-5, 1e-12);
+        assert_close(impact.lateral_shift_m, (1e4 + 1e-4) * 0.0299792458 / 0.01, 1e-12);
+        assert_close(impact.range_scaling_m, 0.02, 1e-12);
+        assert_close(impact.quadratic_phase_error_deg, 180.0 * 4e-5, 1e-12);
+        let opposite = bsar_infos.frequency_offset_impact(10e9, -1.0);
+        assert_close(opposite.lateral_shift_m, -impact.lateral_shift_m, 1e-12);
+        // No Doppler gradient without motion
+        let motionless = BsarInfos { dbetag: DVec3::ZERO, ..bsar_infos };
+        assert!(motionless.frequency_offset_impact(10e9, 1.0).lateral_shift_m.is_nan());
+    }
+
+    #[test]
+    fn system_noise_temperature_of_the_chain() {
+        let double_db = 10.0 * 2f64.log10();
+        assert_eq!(system_noise_temperature_k(50.0, 0.0, 0.0), 50.0);
+        assert_close(system_noise_temperature_k(50.0, 0.0, double_db), 340.0, 1e-12);
+        assert_close(system_noise_temperature_k(50.0, double_db, 0.0), 340.0, 1e-12);
+        // The cable loss amplifies the LNA noise referred to the antenna
+        assert_close(system_noise_temperature_k(50.0, double_db, double_db), 920.0, 1e-12);
+    }
+
+    #[test]
+    fn prf_min_meets_the_aasr() {
+        // AASR of -20 dB at 1000.10^(1/2) Hz
+        let aasr = |prf_hz: f64| (1000.0 / prf_hz).powi(4);
+        assert_close(azimuth_prf_min_hz(500.0, aasr), 1000.0 * 10f64.sqrt(), 1e-6);
+        // Sampling the processed Doppler bandwidth is enough
+        assert_eq!(azimuth_prf_min_hz(5000.0, aasr), 5000.0);
+        assert!(azimuth_prf_min_hz(500.0, |_| f64::NAN).is_nan());
+        assert!(azimuth_prf_min_hz(0.0, aasr).is_nan());
+    }
+
+    #[test]
+    fn exact_gradient_resolutions_without_small_angle() {
+        let (fc, bandwidth) = (10.0e9, 300.0e6);
+        let lem = SPEED_OF_LIGHT_IN_VACUUM / fc;
+        let txp = DVec3::new(0.0, 10_000.0, 0.0);
+        let v = DVec3::new(100.0, 0.0, 0.0);
+        let exact = |tint: f64| exact_gradient_resolutions(&txp, &v, &DVec3::ZERO, &txp, &v, &DVec3::ZERO, fc, bandwidth, tint);
+        // 100 m aperture: the bisector formulas hold
+        let (infos, resolutions) = (monostatic_broadside(100.0, 1.0, None), exact(1.0));
+        assert_close(resolutions.slant_range_m, infos.slant_range_resolution_m, 1e-6);
+        assert_close(resolutions.ground_lateral_m, infos.ground_lateral_resolution_m, 1e-4);
+        assert!(!resolutions_diverge(resolutions.area_m2, infos.resolution_area_m2));
+        // 10 km aperture at 10 km: each line of sight rotates by atan(0.5),
+        // |Δ∇R| = 4.sin(atan(0.5)) instead of 2.v.T_int / R = 2
+        let (infos, resolutions) = (monostatic_broadside(100.0, 100.0, None), exact(100.0));
+        assert_close(resolutions.slant_lateral_m, SINC_WIDTH_AT_HALF_POWER * lem / (4.0 * 0.2f64.sqrt()), 1e-5);
+        assert!(resolutions_diverge(resolutions.slant_lateral_m, infos.slant_lateral_resolution_m));
+        assert!(!resolutions_diverge(f64::NAN, infos.slant_lateral_resolution_m));
+    }
+
+    #[test]
+    fn circular_flight_around_the_target_cancels_the_doppler_rate() {
+        // Carriers circling the target at range R: the centripetal acceleration
+        // v²/R towards the target cancels the -v²/(λ.R) term of each carrier
+        let (r, v) = (10_000.0, 100.0);
+        let mut infos = monostatic_broadside(v, 1.0, None);
+        let txp = DVec3::new(0.0, r, 0.0);
+        let acceleration = (v * v / r) * txp.normalize();
+        infos.update_doppler_rate_with_accelerations(&txp, &acceleration, &txp, &acceleration, 10.0e9);
+        assert!(infos.doppler_rate_hzps.abs() < 1e-9, "{}", infos.doppler_rate_hzps);
+        assert!(infos.processed_doppler_bandwidth_hz.abs() < 1e-9);
+    }
+
+    #[test]
+    fn integration_time_solved_for_the_pixel_aspect_ratio() {
+        for ratio in [1.0, 2.0, 0.5] {
+            let infos = monostatic_broadside(100.0, 1.0, Some(ratio));
+            assert_close(infos.ground_range_resolution_m / infos.ground_lateral_resolution_m, ratio, 1e-12);
+        }
+        // 2:1 pixels need twice the integration time of squared pixels
+        let squared = monostatic_broadside(100.0, 1.0, Some(1.0));
+        assert_close(monostatic_broadside(100.0, 1.0, Some(2.0)).integration_time_s, 2.0 * squared.integration_time_s, 1e-12);
+    }
+
+    #[test]
+    fn ground_resolution_directions() {
+        // Monostatic broadside looking North, flying East: range resolution
+        // along North, lateral resolution along West (bisector derivative)
+        let infos = monostatic_broadside(100.0, 1.0, None);
+        assert!((infos.ground_range_direction().unwrap() - DVec3::Y).length() < 1e-12);
+        assert!((infos.ground_lateral_direction().unwrap() - DVec3::NEG_X).length() < 1e-12);
+        assert!(monostatic_broadside(0.0, 1.0, None).ground_lateral_direction().is_none());
+        assert!(BsarInfos::default().ground_range_direction().is_none());
+    }
+
     #[test]
     fn zero_velocity_yields_nan_not_inf() {
         // Regression test: divisions by |dbeta| = 0 used to produce silent inf
-        let infos = monostatic_broadside(0.0, 1.0, false);
+        let infos = monostatic_broadside(0.0, 1.0, None);
         assert!(infos.slant_lateral_resolution_m.is_nan());
         assert!(infos.ground_lateral_resolution_m.is_nan());
         assert!(infos.resolution_area_m2.is_nan());
@@ -457,7 +1416,7 @@ mod tests {
         assert_close(infos.doppler_frequency_hz, 0.0, 1e-12);
 
         // Squared pixels: the auto integration time is undefined too
-        let infos = monostatic_broadside(0.0, 1.0, true);
+        let infos = monostatic_broadside(0.0, 1.0, Some(1.0));
         assert!(infos.integration_time_s.is_nan());
     }
 
@@ -471,7 +1430,7 @@ mod tests {
             &txp, &vtx, &txp, &vtx,
             &AntennaBeamFootprintState::default(),
             &AntennaBeamFootprintState::default(),
-            10.0e9, 300.0e6, 1.0, false, true
+            10.0e9, 300.0e6, 1.0, None, true
         );
         assert!(infos.ground_range_resolution_m.is_nan()); // |betag| = 0
         assert!(infos.slant_range_resolution_m.is_finite());
@@ -494,8 +1453,9 @@ mod tests {
         rx_state.inner.velocity_vector_mps = DVec3::new(0.0, 100.0, 0.0);
         rx_state.noise_temperature_k = 290.0;
         rx_state.noise_factor_db = 5.0;
+        rx_state.noise_bandwidth_mhz = 300.0; // Matched receiver
         rx_state.integration_time_s = 1.0;
-        rx_state.squared_pixels = false;
+        rx_state.fixed_pixel_aspect_ratio = false;
         let tx_beam = AntennaBeamState {
             elevation_beam_width_deg: 20.0,
             azimuth_beam_width_deg: 20.0,
@@ -524,6 +1484,52 @@ mod tests {
         assert_close(infos.nesz, 6.426137576501484e-3, 1e-12); // = -21.92 dB
     }
 
+    #[test]
+    fn nesz_and_data_rate_follow_the_noise_bandwidth() {
+        let (tx_state, mut rx_state, tx_beam, rx_beam) = nesz_reference_states();
+        rx_state.noise_bandwidth_mhz = 600.0; // Twice the Tx bandwidth: twice the noise
+        rx_state.range_oversampling = 1.25;
+        let mut infos = BsarInfos::default();
+        infos.update_from_state(
+            &tx_state, &rx_state, &tx_beam, &rx_beam,
+            &AntennaBeamFootprintState::default(),
+            &AntennaBeamFootprintState::default(),
+        );
+        assert_close(infos.nesz, 2.0 * 6.426137576501484e-3, 1e-12);
+        assert_close(infos.sampling_frequency_hz, 750e6, 1e-12);
+        // Footprints reduced to the scene center: the receive window is the pulse
+        assert_eq!(infos.range_min_m, infos.range_max_m);
+        assert_close(infos.data_rate_sps, 750e6 * 10e-6 * 2000.0, 1e-12);
+    }
+
+    #[test]
+    fn infos_at_a_ground_point_are_the_translated_scene_ones() {
+        let (tx_state, rx_state, tx_beam, rx_beam) = nesz_reference_states();
+        let point_m = DVec3::new(500.0, -300.0, 0.0);
+        let mut infos = BsarInfos::default();
+        infos.update_from_state_at(
+            &tx_state, &rx_state, &tx_beam, &rx_beam,
+            &AntennaBeamFootprintState::default(),
+            &AntennaBeamFootprintState::default(),
+            &point_m,
+        );
+        // The same geometry with the point moved to the scene center
+        let (mut tx_shifted, mut rx_shifted) = (tx_state, rx_state);
+        tx_shifted.inner.position_m -= point_m;
+        rx_shifted.inner.position_m -= point_m;
+        let mut expected = BsarInfos::default();
+        expected.update_from_state(
+            &tx_shifted, &rx_shifted, &tx_beam, &rx_beam,
+            &AntennaBeamFootprintState::default(),
+            &AntennaBeamFootprintState::default(),
+        );
+        assert_close(infos.nesz, expected.nesz, 1e-12);
+        assert_close(infos.resolution_area_m2, expected.resolution_area_m2, 1e-12);
+        assert_close(infos.doppler_frequency_hz, expected.doppler_frequency_hz, 1e-12);
+        // Not the scene center one
+        assert!((infos.nesz / 6.426137576501484e-3 - 1.0).abs() > 1e-3);
+    }
+
     #[test]
     fn nesz_is_nan_for_zero_duty_cycle() {
         let (mut tx_state, rx_state, tx_beam, rx_beam) = nesz_reference_states();
@@ -538,20 +1544,87 @@ mod tests {
         assert!(infos.resolution_area_m2.is_finite()); // Geometry itself is valid
     }
 
+    #[test]
+    fn stationary_receiver_keeps_the_tx_motion_only() {
+        let (tx_state, mut rx_state, tx_beam, rx_beam) = nesz_reference_states();
+        rx_state.is_stationary = true; // Its velocity vector is ignored
+        let mut infos = BsarInfos::default();
+        infos.update_from_state(
+            &tx_state, &rx_state, &tx_beam, &rx_beam,
+            &AntennaBeamFootprintState::default(),
+            &AntennaBeamFootprintState::default(),
+        );
+        let mut expected = BsarInfos::default();
+        expected.update(
+            &(-tx_state.inner.position_m), &tx_state.inner.velocity_vector_mps,
+            &(-rx_state.inner.position_m), &DVec3::ZERO,
+            &AntennaBeamFootprintState::default(),
+            &AntennaBeamFootprintState::default(),
+            tx_state.center_frequency_ghz * 1e9, tx_state.bandwidth_mhz * 1e6, 1.0, None, true
+        );
+        assert_eq!(infos.doppler_frequency_hz, expected.doppler_frequency_hz);
+        assert_eq!(infos.doppler_rate_hzps, expected.doppler_rate_hzps);
+        // The Tx flies along x from (0, -8 km): broadside, no Doppler
+        assert!(infos.doppler_frequency_hz.abs() < 1e-9);
+        assert!(infos.doppler_rate_hzps < 0.0 && infos.slant_lateral_resolution_m.is_finite());
+    }
+
     #[test]
     fn zero_position_invalidates_all_fields() {
-        let mut infos = monostatic_broadside(100.0, 1.0, false);
+        let mut infos = monostatic_broadside(100.0, 1.0, None);
         assert!(infos.range_center_m.is_finite());
         // Degenerate call: carrier at the target position
         infos.update(
             &DVec3::ZERO, &DVec3::X, &DVec3::Y, &DVec3::X,
             &AntennaBeamFootprintState::default(),
             &AntennaBeamFootprintState::default(),
-            10.0e9, 300.0e6, 1.0, false, true
+            10.0e9, 300.0e6, 1.0, None, true
         );
         assert!(infos.range_center_m.is_nan());
         assert!(infos.doppler_frequency_hz.is_nan());
         assert!(infos.nesz.is_nan());
     }
-}
 
+    #[test]
+    fn csv_row_matches_header() {
+        let infos = monostatic_broadside(100.0, 1.0, None);
+        let row = infos.to_csv_row();
+        assert_eq!(row.split(',').count(), BsarInfos::CSV_HEADER.split(',').count());
+        assert!(!BsarInfos::CSV_HEADER.contains(' '));
+        // Invalid infos are written as NaN
+        assert!(BsarInfos::default().to_csv_row().split(',').all(|value| value == "NaN"));
+    }
+
+    #[test]
+    fn extrema_points_on_the_footprint() {
+        // Monostatic carrier South of the scene center flying East, footprint
+        // points (Y-up) at the center, North, South, East and West
+        let txp = DVec3::new(0.0, 10_000.0, -5_000.0);
+        let vtx = DVec3::new(100.0, 0.0, 0.0);
+        let footprint = AntennaBeamFootprintState {
+            points: vec![
+                DVec3::ZERO,
+                DVec3::new(1_000.0, 0.0, 0.0),
+                DVec3::new(-1_000.0, 0.0, 0.0),
+                DVec3::new(0.0, 0.0, 1_000.0),
+                DVec3::new(0.0, 0.0, -1_000.0),
+            ],
+            ..Default::default()
+        };
+        let extrema = bsar_extrema(&txp, &vtx, &txp, &vtx, 0.03, &footprint, &footprint).unwrap();
+        // Nearest and farthest points along North
+        assert_eq!(extrema.range_min.point_m, DVec3::new(-1_000.0, 0.0, 0.0));
+        assert_eq!(extrema.range_max.point_m, DVec3::new(1_000.0, 0.0, 0.0));
+        assert_eq!(
+            (extrema.range_min.value, extrema.range_max.value),
+            bsar_range_min_max(&txp, &txp, &footprint, &footprint)
+        );
+        // Approaching point ahead (East), receding point behind (West)
+        assert_eq!(extrema.doppler_max.point_m, DVec3::new(0.0, 0.0, 1_000.0));
+        assert_eq!(extrema.doppler_min.point_m, DVec3::new(0.0, 0.0, -1_000.0));
+        assert_close(extrema.doppler_max.value, -extrema.doppler_min.value, 1e-12);
+        // Same swath: the Rx footprint is used
+        let empty = AntennaBeamFootprintState { points: vec![], ..Default::default() };
+        assert!(bsar_extrema(&txp, &vtx, &txp, &vtx, 0.03, &footprint, &empty).is_none());
+    }
+}
@@ -0,0 +1,204 @@
+//! Bistatic point-target ambiguity function.
+//!
+//! Response of a point target at the scene center focused by backprojection
+//! at a ground point offset by `r`, the carriers following their actual
+//! trajectories (velocities and accelerations) over the integration time,
+//! without the small angle approximation of the bisector formulas:
+//!
+//! ```text
+//! χ(r) = 1/N |Σ_n sinc(B.ΔR_n(r)/c).exp(2jπ.ΔR_n(r)/λ)|,  ΔR_n(r) = R(r, t_n) - R(0, t_n)
+//! ```
+//!
+//! with `R(r, t)` the bistatic range of the point `r` at the slow time `t`,
+//! the range compressed pulses having a rectangular spectrum of bandwidth `B`.
+//! The slow time is sampled finely enough to ignore the PRF: its azimuth
+//! ambiguities are left to the AASR.
+
+use bevy::math::DVec3;
+
+use super::{sinc, SPEED_OF_LIGHT_IN_VACUUM};
+
+/// Number of slow time samples over the integration time.
+const SLOW_TIME_SAMPLES: usize = 512;
+
+/// Carriers motion and waveform of the ambiguity function.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AmbiguityGeometry {
+    /// Carriers -> scene center vectors in m (ENU) at the middle of the
+    /// integration time
+    pub txp: DVec3,
+    pub rxp: DVec3,
+    /// Carriers velocity vectors in m/s (ENU)
+    pub vtx: DVec3,
+    pub vrx: DVec3,
+    /// Carriers acceleration vectors in m/s² (ENU)
+    pub atx: DVec3,
+    pub arx: DVec3,
+    pub center_frequency_hz: f64,
+    pub bandwidth_hz: f64,
+    pub integration_time_s: f64,
+}
+
+impl AmbiguityGeometry {
+    /// Gets the carriers -> scene center vectors in m (ENU) at the slow time
+    /// `t` from the middle of the integration time.
+    pub fn carriers_at(&self, t: f64) -> (DVec3, DVec3) {
+        (
+            self.txp - self.vtx * t - 0.5 * self.atx * t * t,
+            self.rxp - self.vrx * t - 0.5 * self.arx * t * t,
+        )
+    }
+
+    /// Gets the normalized ambiguity function (linear amplitude, 1 at the
+    /// scene center) at the offset `offset_m` (ENU) from the scene center, or
+    /// `NaN` for a degenerate geometry.
+    pub fn amplitude(&self, offset_m: &DVec3) -> f64 {
+        let is_valid = self.bandwidth_hz > 0.0 && self.center_frequency_hz > 0.0 && self.integration_time_s >= 0.0;
+        if !is_valid {
+            return f64::NAN;
+        }
+        let lem = SPEED_OF_LIGHT_IN_VACUUM / self.center_frequency_hz; // wavelength in m
+        let (mut real, mut imaginary) = (0.0, 0.0);
+        for n in 0..SLOW_TIME_SAMPLES {
+            let t = self.integration_time_s * (n as f64 / (SLOW_TIME_SAMPLES - 1) as f64 - 0.5);
+            let (txp, rxp) = self.carriers_at(t);
+            let range_difference_m = (txp + offset_m).length() + (rxp + offset_m).length() - txp.length() - rxp.length();
+            let amplitude = sinc(self.bandwidth_hz * range_difference_m / SPEED_OF_LIGHT_IN_VACUUM);
+            let phase = std::f64::consts::TAU * range_difference_m / lem;
+            real += amplitude * phase.cos();
+            imaginary += amplitude * phase.sin();
+        }
+        real.hypot(imaginary) / SLOW_TIME_SAMPLES as f64
+    }
+
+    /// Gets the cut of the ambiguity function along the ground `direction`
+    /// (ENU), as `sample_count` points (offset in m, amplitude in dB) from
+    /// `-half_extent_m` to `half_extent_m`.
+    pub fn cut_db(&self, direction: &DVec3, half_extent_m: f64, sample_count: usize) -> Vec<[f64; 2]> {
+        let Some(direction) = direction.try_normalize() else {
+            return Vec::new();
+        };
+        (0..sample_count)
+            .map(|i| {
+                let offset_m = half_extent_m * (2.0 * i as f64 / (sample_count - 1).max(1) as f64 - 1.0);
+                [offset_m, 20.0 * self.amplitude(&(offset_m * direction)).log10()]
+            })
+            .collect()
+    }
+}
+
+/// Main lobe and sidelobes of an ambiguity function cut.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CutMetrics {
+    /// Width of the main lobe at -3 dB in m: the resolution
+    pub half_power_width_m: f64,
+    /// Peak Sidelobe Ratio in dB: the highest sidelobe under the main lobe
+    pub pslr_db: f64,
+}
+
+/// Measures the main lobe and the sidelobes of a cut given by
+/// [`AmbiguityGeometry::cut_db`], `None` when the main lobe is not within the
+/// cut. The sidelobes are the maxima beyond the first nulls, `NaN` PSLR
+/// without them.
+pub fn cut_metrics(cut: &[[f64; 2]]) -> Option<CutMetrics> {
+    let peak = cut.iter().enumerate()
+        .filter(|(_, point)| point[1].is_finite())
+        .max_by(|(_, a), (_, b)| a[1].total_cmp(&b[1]))?
+        .0;
+    let peak_db = cut[peak][1];
+    let level_db = peak_db - 10.0 * 2f64.log10();
+    // -3 dB crossings on each side, linearly interpolated
+    let crossing = |indices: &mut dyn Iterator<Item = usize>| {
+        indices
+            .map(|i| (cut[i], cut[i + 1]))
+            .find(|(a, b)| (a[1] - level_db) * (b[1] - level_db) <= 0.0 && a[1] != b[1])
+            .map(|(a, b)| a[0] + (b[0] - a[0]) * (level_db - a[1]) / (b[1] - a[1]))
+    };
+    let after_m = crossing(&mut (peak..cut.len() - 1))?;
+    let before_m = crossing(&mut (0..peak).rev())?;
+    // Main lobe down to the first minima on each side
+    let mut end = peak;
+    while end + 1 < cut.len() && cut[end + 1][1] <= cut[end][1] {
+        end += 1;
+    }
+    let mut start = peak;
+    while start > 0 && cut[start - 1][1] <= cut[start][1] {
+        start -= 1;
+    }
+    let sidelobe_db = cut[..start].iter().chain(cut[end + 1..].iter())
+        .map(|point| point[1])
+        .filter(|value_db| value_db.is_finite())
+        .fold(f64::NAN, f64::max);
+    Some(CutMetrics {
+        half_power_width_m: after_m - before_m,
+        pslr_db: sidelobe_db - peak_db,
+    })
+}
+
+/// Acquisition geometries shared by the tests of the ambiguity function, the
+/// impulse response and the k-space support.
+#[cfg(test)]
+pub(crate) mod test_geometry {
+    use super::*;
+    use crate::bsar::SINC_WIDTH_AT_HALF_POWER;
+
+    /// Monostatic side-looking carrier flying north at 100 m/s over a 1 s
+    /// integration time (a 100 m aperture), 10 km West and 5 km above the scene
+    /// center, at 10 GHz with a 100 MHz bandwidth, with its ground range and
+    /// lateral resolutions in m from the bisector formulas.
+    pub(crate) fn monostatic_test_geometry() -> (AmbiguityGeometry, f64, f64) {
+        let txp = DVec3::new(10_000.0, 0.0, -5_000.0);
+        let v = DVec3::new(0.0, 100.0, 0.0);
+        let geometry = AmbiguityGeometry {
+            txp,
+            rxp: txp,
+            vtx: v,
+            vrx: v,
+            atx: DVec3::ZERO,
+            arx: DVec3::ZERO,
+            center_frequency_hz: 10e9,
+            bandwidth_hz: 100e6,
+            integration_time_s: 1.0,
+        };
+        let range_m = txp.length();
+        // k.c/(B.|βg|) and k.λ/(T.|dβg|), |βg| = 2.x/R and |dβg| = 2.v/R
+        let ground_range_m = SINC_WIDTH_AT_HALF_POWER * SPEED_OF_LIGHT_IN_VACUUM / (100e6 * 2.0 * 10_000.0 / range_m);
+        let ground_lateral_m = SINC_WIDTH_AT_HALF_POWER * 0.0299792458 / (2.0 * 100.0 / range_m);
+        (geometry, ground_range_m, ground_lateral_m)
+    }
+
+    /// Transmitter of [`monostatic_test_geometry`] with a fixed receiver 2 km
+    /// West, 4 km South and 1 km above the scene center: the ground bisector
+    /// `βg` points North-East while its derivative `dβg`, of the transmitter
+    /// alone, points South, so that the resolution cell is skewed.
+    pub(crate) fn bistatic_test_geometry() -> AmbiguityGeometry {
+        AmbiguityGeometry {
+            rxp: DVec3::new(2_000.0, 4_000.0, -1_000.0),
+            vrx: DVec3::ZERO,
+            ..monostatic_test_geometry().0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::test_geometry::monostatic_test_geometry;
+
+    /// The cuts of [`monostatic_test_geometry`] are the sinc of the bisector
+    /// formulas.
+    #[test]
+    fn monostatic_cuts_match_the_bisector_resolutions() {
+        let (geometry, ground_range_m, ground_lateral_m) = monostatic_test_geometry();
+        assert!((geometry.amplitude(&DVec3::ZERO) - 1.0).abs() < 1e-12);
+        for (direction, resolution_m) in [(DVec3::X, ground_range_m), (DVec3::Y, ground_lateral_m)] {
+            let cut = geometry.cut_db(&direction, 8.0 * resolution_m, 401);
+            let metrics = cut_metrics(&cut).unwrap();
+            assert!((metrics.half_power_width_m / resolution_m - 1.0).abs() < 0.01, "{metrics:?} {resolution_m}");
+            // First sidelobe of the sinc
+            assert!((metrics.pslr_db + 13.26).abs() < 0.3, "{metrics:?}");
+        }
+        // Main lobe wider than the cut
+        assert_eq!(cut_metrics(&geometry.cut_db(&DVec3::X, 0.1 * ground_range_m, 11)), None);
+    }
+}
@@ -0,0 +1,150 @@
+//! Wavenumber (k-space) support of the bistatic acquisition.
+//!
+//! Each transmitted frequency `f` at the slow time `t` measures the scene
+//! spectrum at the ground wavenumber
+//!
+//! ```text
+//! K(t, f) = 2π.f/c.βg(t)
+//! ```
+//!
+//! with `βg(t)` the ground-projected bistatic bisector vector of the carriers
+//! at `t`. Over the bandwidth and the integration time, the wavenumbers cover
+//! a support region, close to a parallelogram: its extent along the bisector
+//! (the bandwidth) sets the ground range resolution and its extent along the
+//! bisector rotation (the integration time) the ground lateral resolution, as
+//! `k.2π/ΔK`.
+
+use bevy::math::{DVec2, DVec3};
+
+use super::{ambiguity::AmbiguityGeometry, SINC_WIDTH_AT_HALF_POWER, SPEED_OF_LIGHT_IN_VACUUM};
+
+/// Ground wavenumbers bounding the k-space support, with its extents.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KSpaceSupport {
+    /// Boundary of the support in rad/m (ENU), closed, relative to the center
+    /// wavenumber: the lowest frequency over the integration time, the end of
+    /// the integration time over the bandwidth, then back
+    pub boundary_radpm: Vec<DVec2>,
+    /// Ground wavenumber at the center frequency and the middle of the
+    /// integration time in rad/m (ENU)
+    pub center_radpm: DVec2,
+    /// Extent in rad/m over the bandwidth, at the middle of the integration time
+    pub range_extent_radpm: f64,
+    /// Extent in rad/m over the integration time, at the center frequency
+    pub lateral_extent_radpm: f64,
+}
+
+impl KSpaceSupport {
+    /// Gets the ground range resolution in m given by the support.
+    #[inline]
+    pub fn ground_range_resolution_m(&self) -> f64 {
+        SINC_WIDTH_AT_HALF_POWER * std::f64::consts::TAU / self.range_extent_radpm
+    }
+
+    /// Gets the ground lateral resolution in m given by the support.
+    #[inline]
+    pub fn ground_lateral_resolution_m(&self) -> f64 {
+        SINC_WIDTH_AT_HALF_POWER * std::f64::consts::TAU / self.lateral_extent_radpm
+    }
+}
+
+/// Gets the ground wavenumber in rad/m (ENU) at the slow time `t` and the
+/// frequency `frequency_hz`.
+fn ground_wavenumber(geometry: &AmbiguityGeometry, t: f64, frequency_hz: f64) -> DVec2 {
+    let (txp, rxp) = geometry.carriers_at(t);
+    let beta: DVec3 = txp.normalize_or_zero() + rxp.normalize_or_zero();
+    std::f64::consts::TAU * frequency_hz / SPEED_OF_LIGHT_IN_VACUUM * beta.truncate()
+}
+
+/// Computes the k-space support of `geometry`, each edge of its boundary
+/// sampled with `edge_samples` points, `None` for a degenerate geometry.
+pub fn kspace_support(geometry: &AmbiguityGeometry, edge_samples: usize) -> Option<KSpaceSupport> {
+    let is_valid = geometry.bandwidth_hz > 0.0
+        && geometry.center_frequency_hz > 0.5 * geometry.bandwidth_hz
+        && geometry.integration_time_s >= 0.0;
+    if !is_valid || edge_samples < 2 {
+        return None;
+    }
+    let (f_min, f_max) = (
+        geometry.center_frequency_hz - 0.5 * geometry.bandwidth_hz,
+        geometry.center_frequency_hz + 0.5 * geometry.bandwidth_hz,
+    );
+    let half_time_s = 0.5 * geometry.integration_time_s;
+    let center_radpm = ground_wavenumber(geometry, 0.0, geometry.center_frequency_hz);
+    if !center_radpm.is_finite() || center_radpm == DVec2::ZERO {
+        return None; // Nadir geometry: no ground wavenumber
+    }
+    let ratio = |i: usize| i as f64 / (edge_samples - 1) as f64;
+    let time = |i: usize| -half_time_s + geometry.integration_time_s * ratio(i);
+    let frequency = |i: usize| f_min + geometry.bandwidth_hz * ratio(i);
+    let edges = [
+        // Lowest frequency forward in time, end of the integration over the
+        // bandwidth, highest frequency backward in time, start back over the bandwidth
+        (0..edge_samples).map(|i| (time(i), f_min)).collect::<Vec<_>>(),
+        (0..edge_samples).map(|i| (half_time_s, frequency(i))).collect(),
+        (0..edge_samples).rev().map(|i| (time(i), f_max)).collect(),
+        (0..edge_samples).rev().map(|i| (-half_time_s, frequency(i))).collect(),
+    ];
+    // The edges share their end points, the last one closing the boundary
+    let boundary_radpm = std::iter::once(&(-half_time_s, f_min))
+        .chain(edges.iter().flat_map(|edge| edge.iter().skip(1)))
+        .map(|&(t, frequency_hz)| ground_wavenumber(geometry, t, frequency_hz) - center_radpm)
+        .collect();
+    Some(KSpaceSupport {
+        boundary_radpm,
+        center_radpm,
+        range_extent_radpm: ground_wavenumber(geometry, 0.0, f_max).distance(ground_wavenumber(geometry, 0.0, f_min)),
+        lateral_extent_radpm: ground_wavenumber(geometry, half_time_s, geometry.center_frequency_hz)
+            .distance(ground_wavenumber(geometry, -half_time_s, geometry.center_frequency_hz)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bsar::ambiguity::test_geometry::{bistatic_test_geometry, monostatic_test_geometry};
+
+    /// The support of [`monostatic_test_geometry`] gives the resolutions of
+    /// the bisector formulas.
+    #[test]
+    fn monostatic_support_matches_the_bisector_resolutions() {
+        let (geometry, ground_range_m, ground_lateral_m) = monostatic_test_geometry();
+        let support = kspace_support(&geometry, 16).unwrap();
+        // 4 edges of 15 new points each, plus the closing point
+        assert_eq!(support.boundary_radpm.len(), 61);
+        assert_eq!(support.boundary_radpm.first(), support.boundary_radpm.last());
+        assert!((support.ground_range_resolution_m() / ground_range_m - 1.0).abs() < 1e-3);
+        assert!((support.ground_lateral_resolution_m() / ground_lateral_m - 1.0).abs() < 1e-3);
+        // The support is centered on the center wavenumber, along the bisector (East)
+        assert!(support.center_radpm.x > 0.0 && support.center_radpm.y.abs() < 1e-9);
+        // Nadir: no ground wavenumber
+        let nadir = AmbiguityGeometry { txp: DVec3::new(0.0, 0.0, -5_000.0), rxp: DVec3::new(0.0, 0.0, -5_000.0), ..geometry };
+        assert_eq!(kspace_support(&nadir, 16), None);
+    }
+
+    /// The support of [`bistatic_test_geometry`] is a parallelogram with its
+    /// bandwidth edges along the ground bisector `βg` (North-East) and its
+    /// integration time edges along `dβg` (South).
+    #[test]
+    fn bistatic_support_is_skewed_along_the_bisector() {
+        let geometry = bistatic_test_geometry();
+        let support = kspace_support(&geometry, 16).unwrap();
+        let betag = (geometry.txp.normalize() + geometry.rxp.normalize()).truncate();
+        let dbetag_norm = 100.0 / geometry.txp.length(); // Of the transmitter alone
+        assert!(support.center_radpm.normalize().dot(betag.normalize()) > 1.0 - 1e-9);
+        // Lowest frequency over the integration time, then end of the integration over the bandwidth
+        let boundary = &support.boundary_radpm;
+        let (time_edge, frequency_edge) = (boundary[15] - boundary[0], boundary[30] - boundary[15]);
+        assert!(time_edge.normalize().dot(DVec2::NEG_Y) > 1.0 - 1e-4, "{time_edge}");
+        assert!(frequency_edge.normalize().dot(betag.normalize()) > 1.0 - 1e-4, "{frequency_edge}");
+        // Edges at the angle of βg and dβg, not orthogonal
+        let cos_angle = time_edge.normalize().dot(frequency_edge.normalize());
+        assert!((cos_angle + betag.normalize().y).abs() < 1e-2, "{cos_angle}");
+        assert!(cos_angle.abs() > 0.5);
+        // k.c/(B.|βg|) and k.λ/(T.|dβg|)
+        let ground_range_m = SINC_WIDTH_AT_HALF_POWER * SPEED_OF_LIGHT_IN_VACUUM / (100e6 * betag.length());
+        let ground_lateral_m = SINC_WIDTH_AT_HALF_POWER * 0.0299792458 / dbetag_norm;
+        assert!((support.ground_range_resolution_m() / ground_range_m - 1.0).abs() < 1e-3);
+        assert!((support.ground_lateral_resolution_m() / ground_lateral_m - 1.0).abs() < 1e-3);
+    }
+}
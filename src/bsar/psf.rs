@@ -0,0 +1,145 @@
+//! Point-target impulse response (PSF) of the bistatic acquisition.
+//!
+//! The response of a point target at the scene center focused by
+//! backprojection, the ambiguity function of [`super::ambiguity`], sampled
+//! over a square ground patch (East, North) centered on the target: the image
+//! shows the shape and the orientation of the resolution cell, skewed when the
+//! ground range and lateral directions are not orthogonal. The resolutions are
+//! measured on the image, along these directions, as the widths of its main
+//! lobe at -3 dB.
+
+use bevy::math::{DVec2, DVec3};
+
+use super::ambiguity::{cut_metrics, AmbiguityGeometry};
+
+/// Synthesized impulse response over a square ground patch.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImpulseResponse {
+    /// Side of the image in pixels, odd to put the target on a pixel
+    pub size: usize,
+    /// Half side of the ground patch in m
+    pub half_extent_m: f64,
+    /// Normalized amplitude (linear, 1 at the target) by row from the North
+    /// edge, each row from the West edge, `NaN` for a degenerate geometry
+    pub amplitude: Vec<f64>,
+}
+
+impl ImpulseResponse {
+    /// Synthesizes the impulse response of `geometry` over the patch of half
+    /// side `half_extent_m`, with `size` pixels per side made odd.
+    pub fn synthesize(geometry: &AmbiguityGeometry, half_extent_m: f64, size: usize) -> Self {
+        let size = size.max(1) | 1;
+        let pitch_m = 2.0 * half_extent_m / size as f64;
+        let center = (size / 2) as f64;
+        let amplitude = (0..size * size)
+            .map(|index| {
+                let (row, column) = (index / size, index % size);
+                let offset_m = DVec3::new((column as f64 - center) * pitch_m, (center - row as f64) * pitch_m, 0.0);
+                geometry.amplitude(&offset_m)
+            })
+            .collect();
+        Self { size, half_extent_m, amplitude }
+    }
+
+    /// Gets the side of a pixel in m.
+    #[inline]
+    pub fn pitch_m(&self) -> f64 {
+        2.0 * self.half_extent_m / self.size as f64
+    }
+
+    /// Gets the amplitude at the ground offset `offset_m` (East, North) from
+    /// the target, bilinearly interpolated between the pixel centers, `NaN`
+    /// beyond them.
+    pub fn amplitude_at(&self, offset_m: DVec2) -> f64 {
+        let center = (self.size / 2) as f64;
+        let (column, row) = (center + offset_m.x / self.pitch_m(), center - offset_m.y / self.pitch_m());
+        let last = (self.size - 1) as f64;
+        if !(0.0..=last).contains(&column) || !(0.0..=last).contains(&row) {
+            return f64::NAN;
+        }
+        if self.size == 1 {
+            return self.amplitude[0];
+        }
+        let (column_0, row_0) = ((column as usize).min(self.size - 2), (row as usize).min(self.size - 2));
+        let (u, v) = (column - column_0 as f64, row - row_0 as f64);
+        let pixel = |row: usize, column: usize| self.amplitude[row * self.size + column];
+        (1.0 - v) * ((1.0 - u) * pixel(row_0, column_0) + u * pixel(row_0, column_0 + 1))
+            + v * ((1.0 - u) * pixel(row_0 + 1, column_0) + u * pixel(row_0 + 1, column_0 + 1))
+    }
+
+    /// Gets the cut of the image through the target along the ground
+    /// `direction` (ENU), as `sample_count` points (offset in m, amplitude in
+    /// dB) up to the edges of the patch, as [`AmbiguityGeometry::cut_db`].
+    pub fn cut_db(&self, direction: &DVec3, sample_count: usize) -> Vec<[f64; 2]> {
+        let Some(direction) = direction.truncate().try_normalize() else {
+            return Vec::new();
+        };
+        // Up to the outermost pixel centers
+        let half_extent_m = (self.half_extent_m - 0.5 * self.pitch_m()) / direction.x.abs().max(direction.y.abs());
+        (0..sample_count)
+            .map(|i| {
+                let offset_m = half_extent_m * (2.0 * i as f64 / (sample_count - 1).max(1) as f64 - 1.0);
+                [offset_m, 20.0 * self.amplitude_at(offset_m * direction).log10()]
+            })
+            .collect()
+    }
+
+    /// Measures the width in m of the main lobe at -3 dB along the ground
+    /// `direction` (ENU), `None` when it is not within the patch.
+    pub fn half_power_width_m(&self, direction: &DVec3) -> Option<f64> {
+        cut_metrics(&self.cut_db(direction, 4 * self.size)).map(|metrics| metrics.half_power_width_m)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bsar::{
+        ambiguity::test_geometry::{bistatic_test_geometry, monostatic_test_geometry},
+        SINC_WIDTH_AT_HALF_POWER, SPEED_OF_LIGHT_IN_VACUUM,
+    };
+
+    /// The widths measured on the image of [`monostatic_test_geometry`] are
+    /// the bisector resolutions.
+    #[test]
+    fn monostatic_response_matches_the_bisector_resolutions() {
+        let (geometry, ground_range_m, ground_lateral_m) = monostatic_test_geometry();
+        let response = ImpulseResponse::synthesize(&geometry, 6.0, 100);
+        assert_eq!(response.size, 101);
+        assert_eq!(response.amplitude.len(), 101 * 101);
+        assert!((response.amplitude_at(DVec2::ZERO) - 1.0).abs() < 1e-12);
+        assert!(response.amplitude_at(DVec2::new(6.0, 0.0)).is_nan());
+        for (direction, resolution_m) in [(DVec3::X, ground_range_m), (DVec3::Y, ground_lateral_m)] {
+            let width_m = response.half_power_width_m(&direction).unwrap();
+            assert!((width_m / resolution_m - 1.0).abs() < 0.02, "{width_m} {resolution_m}");
+        }
+        // Main lobe wider than the patch
+        let small = ImpulseResponse::synthesize(&geometry, 0.2, 11);
+        assert_eq!(small.half_power_width_m(&DVec3::X), None);
+    }
+
+    /// The resolution cell of [`bistatic_test_geometry`] is a parallelogram
+    /// with its sides along the iso-range lines (across `βg`) and the
+    /// iso-Doppler lines (across `dβg`, here East): along each side, the width
+    /// is the bisector resolution across the other lines over the cosine of
+    /// their skew.
+    #[test]
+    fn bistatic_response_is_skewed_along_the_iso_lines() {
+        let geometry = bistatic_test_geometry();
+        let response = ImpulseResponse::synthesize(&geometry, 8.0, 101);
+        let betag = (geometry.txp.normalize() + geometry.rxp.normalize()).truncate();
+        let dbetag_norm = 100.0 / geometry.txp.length(); // Of the transmitter alone, South
+        // k.c/(B.|βg|) and k.λ/(T.|dβg|)
+        let ground_range_m = SINC_WIDTH_AT_HALF_POWER * SPEED_OF_LIGHT_IN_VACUUM / (100e6 * betag.length());
+        let ground_lateral_m = SINC_WIDTH_AT_HALF_POWER * 0.0299792458 / dbetag_norm;
+        let iso_doppler = DVec3::X;
+        let iso_range = DVec3::new(-betag.y, betag.x, 0.0).normalize();
+        let cos_range_skew = iso_doppler.truncate().dot(betag.normalize());
+        let cos_lateral_skew = iso_range.y;
+        assert!(cos_range_skew < 0.9 && cos_lateral_skew < 0.9);
+        for (direction, width_m) in [(iso_doppler, ground_range_m / cos_range_skew), (iso_range, ground_lateral_m / cos_lateral_skew)] {
+            let measured_m = response.half_power_width_m(&direction).unwrap();
+            assert!((measured_m / width_m - 1.0).abs() < 0.02, "{direction} {measured_m} {width_m}");
+        }
+    }
+}
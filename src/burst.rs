@@ -0,0 +1,121 @@
+//! ScanSAR / TOPS burst timing.
+//!
+//! Burst modes share the illumination time between sub-swaths: the beam
+//! dwells on each sub-swath during a burst, the bursts cycling through the
+//! sub-swaths. A ground target is then only seen during the part of its
+//! illumination covered by the bursts of its sub-swath: in ScanSAR this dwell
+//! depends on the target azimuth position (the scalloping and resolution
+//! variation), while TOPS sweeps the beam backward to forward during the
+//! burst so that every target gets the same, shorter, dwell.
+
+/// Targets positions sampled over a burst cycle for the dwell statistics
+const DWELL_SAMPLES: usize = 1000;
+
+/// Burst acquisition mode.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BurstMode {
+    ScanSar,
+    /// The beam sweeps along azimuth at `steering_rate_degps` during the burst
+    Tops { steering_rate_degps: f64 },
+}
+
+/// Dwell times in seconds of the targets of one sub-swath.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BurstDwell {
+    pub min_s: f64,
+    pub max_s: f64,
+    pub mean_s: f64,
+}
+
+impl BurstDwell {
+    /// Gets the relative dwell variation (max - min) / mean between targets.
+    pub fn variation(&self) -> f64 {
+        if self.mean_s > 0.0 { (self.max_s - self.min_s) / self.mean_s } else { f64::NAN }
+    }
+}
+
+/// Gets the time in seconds of `[start_s, start_s + illumination_s]` covered by
+/// the bursts `[n.cycle_s + burst_start_s, n.cycle_s + burst_start_s + burst_s]`.
+fn covered_time_s(start_s: f64, illumination_s: f64, burst_start_s: f64, burst_s: f64, cycle_s: f64) -> f64 {
+    let end_s = start_s + illumination_s;
+    let first = ((start_s - burst_start_s - burst_s) / cycle_s).floor() as i64;
+    let last = ((end_s - burst_start_s) / cycle_s).ceil() as i64;
+    (first..=last)
+        .map(|n| {
+            let burst_begin_s = n as f64 * cycle_s + burst_start_s;
+            ((burst_begin_s + burst_s).min(end_s) - burst_begin_s.max(start_s)).max(0.0)
+        })
+        .sum()
+}
+
+/// Gets the dwell times of the targets of a sub-swath whose bursts of
+/// `burst_s` start at `burst_start_s` in each cycle of `cycle_s`, a target
+/// being illuminated during `illumination_s`.
+///
+/// `ground_angular_velocity_degps` is the angular velocity of the beam over
+/// the ground without steering, which the TOPS steering rate adds to: the beam
+/// sweeps faster over the targets by the shrinking factor
+/// `1 + steering_rate / ground_angular_velocity`.
+pub fn burst_dwell(
+    mode: BurstMode,
+    illumination_s: f64,
+    burst_start_s: f64,
+    burst_s: f64,
+    cycle_s: f64,
+    ground_angular_velocity_degps: f64,
+) -> BurstDwell {
+    match mode {
+        BurstMode::ScanSar => {
+            let dwells: Vec<f64> = (0..DWELL_SAMPLES)
+                .map(|i| covered_time_s(i as f64 / DWELL_SAMPLES as f64 * cycle_s, illumination_s, burst_start_s, burst_s, cycle_s))
+                .collect();
+            BurstDwell {
+                min_s: dwells.iter().copied().fold(f64::INFINITY, f64::min),
+                max_s: dwells.iter().copied().fold(0.0, f64::max),
+                mean_s: illumination_s * burst_s / cycle_s,
+            }
+        }
+        BurstMode::Tops { steering_rate_degps } => {
+            let shrinking_factor = 1.0 + steering_rate_degps.abs() / ground_angular_velocity_degps;
+            let dwell_s = (illumination_s / shrinking_factor).min(burst_s);
+            BurstDwell { min_s: dwell_s, max_s: dwell_s, mean_s: dwell_s }
+        }
+    }
+}
+
+/// Gets the lateral resolution in meters for a dwell of `dwell_s`, the
+/// resolution being `reference_resolution_m` for an integration time of
+/// `reference_integration_time_s` (resolution inversely proportional to the
+/// integration time).
+pub fn resolution_for_dwell_m(reference_resolution_m: f64, reference_integration_time_s: f64, dwell_s: f64) -> f64 {
+    if dwell_s > 0.0 { reference_resolution_m * reference_integration_time_s / dwell_s } else { f64::INFINITY }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scansar_dwell_depends_on_the_target_position() {
+        // 3 s illumination, 1 s bursts every 2 s: each target sees 1 to 2 s
+        let dwell = burst_dwell(BurstMode::ScanSar, 3.0, 0.0, 1.0, 2.0, 0.1);
+        assert!((dwell.min_s - 1.0).abs() < 1e-9, "{dwell:?}");
+        assert!((dwell.max_s - 2.0).abs() < 1e-9, "{dwell:?}");
+        assert_eq!(dwell.mean_s, 1.5);
+        assert!((dwell.variation() - 2.0 / 3.0).abs() < 1e-9);
+        // The burst start in the cycle only shifts the targets
+        assert_eq!(burst_dwell(BurstMode::ScanSar, 3.0, 1.0, 1.0, 2.0, 0.1).mean_s, 1.5);
+        // Illumination shorter than the cycle gap: some targets are missed
+        assert_eq!(burst_dwell(BurstMode::ScanSar, 0.5, 0.0, 1.0, 2.0, 0.1).min_s, 0.0);
+    }
+
+    #[test]
+    fn tops_dwell_is_uniform() {
+        // Steering 3 times the ground angular velocity: 4 times shorter dwell
+        let dwell = burst_dwell(BurstMode::Tops { steering_rate_degps: 0.3 }, 8.0, 0.0, 5.0, 10.0, 0.1);
+        assert!((dwell.mean_s - 2.0).abs() < 1e-12);
+        assert_eq!((dwell.min_s, dwell.max_s), (dwell.mean_s, dwell.mean_s));
+        assert_eq!(dwell.variation(), 0.0);
+        assert_eq!(resolution_for_dwell_m(3.0, 1.0, 2.0), 1.5);
+    }
+}
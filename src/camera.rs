@@ -1,4 +1,4 @@
-use std::f32::consts::FRAC_PI_4;
+use std::f32::consts::{FRAC_PI_2, FRAC_PI_4};
 
 use bevy::{
     prelude::*,
@@ -10,13 +10,18 @@ use bevy_panorbit_camera::{EguiFocusIncludesHover, PanOrbitCamera};
 use crate::{
     entities::Carrier,
     scene::{Rx, Tx},
-    ui::{CameraFocus, MenuWidget, SidePanelRects},
+    ui::{CameraFocus, MenuWidget, RoiWidget, SidePanelRects},
 };
 
 /// Initial camera viewpoint, also the target of the menu "reset view" button.
 const INITIAL_YAW_RAD: f32 = -FRAC_PI_4;
 const INITIAL_PITCH_RAD: f32 = FRAC_PI_4;
 const INITIAL_RADIUS_M: f32 = 25_980.762; // = sqrt(HALF_PLANE_SIZE**2 * 3)
+/// Gamepad camera rates at full stick/trigger deflection: orbit in rad/s, pan
+/// in camera radii per second and zoom in e-folds of the radius per second.
+const GAMEPAD_ORBIT_RATE_RADPS: f32 = 1.5;
+const GAMEPAD_PAN_RATE: f32 = 0.5;
+const GAMEPAD_ZOOM_RATE: f32 = 1.0;
 
 pub struct CameraPlugin;
 
@@ -28,7 +33,7 @@ impl Plugin for CameraPlugin {
                 PreStartup,
                 spawn_camera.before(EguiStartupSet::InitContexts),
             )
-            .add_systems(Update, (block_camera_over_panels, update_camera_focus));
+            .add_systems(Update, (block_camera_over_panels, gamepad_camera_control, update_camera_focus).chain());
     }
 }
 
@@ -71,6 +76,41 @@ pub(crate) fn update_camera_focus(
     }
 }
 
+/// Orbits, pans and zooms the camera with a gamepad, for demo setups where the
+/// mouse is awkward: left stick to orbit, right stick to pan and right/left
+/// triggers to zoom in/out. SpaceMouse-style 3D mice exposed as joysticks by
+/// the OS (e.g. by `spacenavd` on Linux) map their first axes to the sticks.
+/// `force_update` keeps it working while the pointer rests over a panel.
+fn gamepad_camera_control(
+    time: Res<Time>,
+    gamepad_q: Query<&Gamepad>,
+    mut pan_orbit_camera_q: Query<(&mut PanOrbitCamera, &Transform)>,
+) {
+    let dt = time.delta_secs();
+    for gamepad in gamepad_q.iter() {
+        let orbit = gamepad.left_stick();
+        let pan = gamepad.right_stick();
+        let zoom = gamepad.get(GamepadButton::RightTrigger2).unwrap_or(0.0)
+            - gamepad.get(GamepadButton::LeftTrigger2).unwrap_or(0.0);
+        if orbit == Vec2::ZERO && pan == Vec2::ZERO && zoom == 0.0 {
+            continue;
+        }
+        for (mut pan_orbit_camera, transform) in pan_orbit_camera_q.iter_mut() {
+            pan_orbit_camera.target_yaw -= orbit.x * GAMEPAD_ORBIT_RATE_RADPS * dt;
+            let pitch = pan_orbit_camera.target_pitch - orbit.y * GAMEPAD_ORBIT_RATE_RADPS * dt;
+            pan_orbit_camera.target_pitch = pitch.clamp(
+                pan_orbit_camera.pitch_lower_limit.unwrap_or(-FRAC_PI_2),
+                pan_orbit_camera.pitch_upper_limit.unwrap_or(FRAC_PI_2),
+            );
+            let radius = pan_orbit_camera.target_radius;
+            pan_orbit_camera.target_focus += (*transform.right() * pan.x + *transform.up() * pan.y)
+                * radius * GAMEPAD_PAN_RATE * dt;
+            pan_orbit_camera.target_radius = radius * (-zoom * GAMEPAD_ZOOM_RATE * dt).exp();
+            pan_orbit_camera.force_update = true;
+        }
+    }
+}
+
 /// Disables the camera while the pointer is over a side panel.
 ///
 /// egui cannot report panels laid out on the background layer through
@@ -82,13 +122,17 @@ pub(crate) fn update_camera_focus(
 fn block_camera_over_panels(
     window_q: Query<&Window, With<PrimaryWindow>>,
     side_panel_rects: Res<SidePanelRects>,
+    roi_widget: Res<RoiWidget>,
     mut pan_orbit_camera_q: Query<&mut PanOrbitCamera>,
 ) {
     let Ok(window) = window_q.single() else { return; };
     let over_panel = window.cursor_position().is_some_and(|pos|
         pos.x <= side_panel_rects.left_max_x ||
-        pos.x >= side_panel_rects.right_min_x
+        pos.x >= side_panel_rects.right_min_x ||
+        pos.y >= side_panel_rects.bottom_min_y
     );
+    // Held while a region of interest is drawn by dragging
+    let over_panel = over_panel || (roi_widget.is_enabled && roi_widget.is_drawing);
     for mut pan_orbit_camera in pan_orbit_camera_q.iter_mut() {
         if pan_orbit_camera.enabled == over_panel { // Avoids triggering change detection every frame
             pan_orbit_camera.enabled = !over_panel;
@@ -0,0 +1,72 @@
+//! Linear frequency modulated (chirp) pulse.
+//!
+//! The transmitted pulse sweeps its bandwidth over the pulse duration, and
+//! the range compression (matched filter) concentrates its energy into a
+//! compressed pulse whose width is set by the bandwidth only. The compression
+//! gain, the time-bandwidth product (TBP), is the one assumed by the NESZ
+//! through the duty cycle; it is only reached by chirps with a large TBP,
+//! whose spectrum is close to rectangular.
+
+use crate::bsar::SINC_WIDTH_AT_HALF_POWER;
+
+/// Smallest time-bandwidth product for which the compression gain assumed by
+/// the NESZ holds
+pub const MIN_TIME_BANDWIDTH_PRODUCT: f64 = 100.0;
+
+/// Chirp pulse.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Chirp {
+    pub bandwidth_hz: f64,
+    pub pulse_duration_s: f64,
+}
+
+impl Chirp {
+    /// Gets the chirp rate in Hz/s, `NaN` for a zero pulse duration.
+    pub fn rate_hzps(&self) -> f64 {
+        if self.pulse_duration_s > 0.0 { self.bandwidth_hz / self.pulse_duration_s } else { f64::NAN }
+    }
+
+    /// Gets the time-bandwidth product, the range compression gain.
+    #[inline]
+    pub fn time_bandwidth_product(&self) -> f64 {
+        self.bandwidth_hz * self.pulse_duration_s
+    }
+
+    /// Gets the range compression gain in dB.
+    #[inline]
+    pub fn compression_gain_db(&self) -> f64 {
+        10.0 * self.time_bandwidth_product().log10()
+    }
+
+    /// Gets the compressed pulse width in seconds, at half power.
+    #[inline]
+    pub fn compressed_pulse_width_s(&self) -> f64 {
+        SINC_WIDTH_AT_HALF_POWER / self.bandwidth_hz
+    }
+
+    /// Checks whether the time-bandwidth product is large enough for the
+    /// compression gain assumed by the NESZ.
+    #[inline]
+    pub fn has_full_compression_gain(&self) -> bool {
+        self.time_bandwidth_product() >= MIN_TIME_BANDWIDTH_PRODUCT
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn derived_chirp_quantities() {
+        let chirp = Chirp { bandwidth_hz: 800e6, pulse_duration_s: 10e-6 };
+        assert!((chirp.rate_hzps() / 8e13 - 1.0).abs() < 1e-12);
+        assert!((chirp.time_bandwidth_product() - 8000.0).abs() < 1e-9);
+        assert!((chirp.compression_gain_db() - 39.0309).abs() < 1e-4);
+        assert!((chirp.compressed_pulse_width_s() - 1.1074e-9).abs() < 1e-13);
+        assert!(chirp.has_full_compression_gain());
+
+        let short_chirp = Chirp { bandwidth_hz: 5e6, pulse_duration_s: 10e-6 };
+        assert!(!short_chirp.has_full_compression_gain());
+        assert!(Chirp { bandwidth_hz: 5e6, pulse_duration_s: 0.0 }.rate_hzps().is_nan());
+    }
+}
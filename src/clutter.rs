@@ -0,0 +1,192 @@
+//! Clutter locus in the range-Doppler domain.
+//!
+//! Every ground point of the common footprint echoes at its bistatic range and
+//! Doppler frequency: the clutter fills a ridge of the range-Doppler plane,
+//! whose Doppler spread at a given range bounds the velocities a moving target
+//! must exceed to stand out of the clutter (GMTI, STAP). Over all ranges, the
+//! histogram of the clutter Doppler frequencies is its Doppler spectrum, the
+//! ground area echoing at each frequency, to be sampled by the PRF.
+
+use bevy::math::{DVec2, DVec3};
+
+use crate::bsar::{bistatic_range_sg, doppler_frequency_sg};
+
+/// Doppler span of the clutter within one range bin.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RidgeBin {
+    /// Bistatic range at the bin center in meters
+    pub range_m: f64,
+    pub doppler_min_hz: f64,
+    pub doppler_max_hz: f64,
+}
+
+impl RidgeBin {
+    #[inline]
+    pub fn doppler_spread_hz(&self) -> f64 {
+        self.doppler_max_hz - self.doppler_min_hz
+    }
+}
+
+/// Fraction of the clutter within one Doppler bin.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DopplerBin {
+    /// Doppler frequency at the bin center in Hz
+    pub doppler_hz: f64,
+    /// Fraction of the samples within the bin
+    pub fraction: f64,
+}
+
+/// Bistatic range and Doppler frequency of clutter samples.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ClutterLocus {
+    /// (bistatic range in m, Doppler frequency in Hz) of each sample
+    pub samples: Vec<[f64; 2]>,
+}
+
+impl ClutterLocus {
+    /// Gets the locus of the ENU `ground_points_m` (East, North) seen by the
+    /// Tx and the Rx at `tx_position_m` and `rx_position_m` moving at
+    /// `tx_velocity_mps` and `rx_velocity_mps` (World frame, Z-up), at
+    /// wavelength `lem`. Points under a carrier are skipped.
+    pub fn new(
+        lem: f64,
+        tx_position_m: &DVec3,
+        tx_velocity_mps: &DVec3,
+        rx_position_m: &DVec3,
+        rx_velocity_mps: &DVec3,
+        ground_points_m: impl IntoIterator<Item = DVec2>,
+    ) -> Self {
+        let samples = ground_points_m.into_iter()
+            .filter_map(|point_m| {
+                let point_m = point_m.extend(0.0);
+                let (txp, rxp) = (point_m - tx_position_m, point_m - rx_position_m);
+                let doppler_hz = doppler_frequency_sg(lem, &txp, tx_velocity_mps, &rxp, rx_velocity_mps);
+                doppler_hz.is_finite().then(|| [bistatic_range_sg(&txp, &rxp), doppler_hz])
+            })
+            .collect();
+        Self { samples }
+    }
+
+    /// Gets the Doppler spread of the whole locus in Hz.
+    pub fn doppler_spread_hz(&self) -> Option<f64> {
+        let (min, max) = self.samples.iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &[_, doppler_hz]| (min.min(doppler_hz), max.max(doppler_hz)));
+        (min <= max).then_some(max - min)
+    }
+
+    /// Gets the Doppler spectrum: the fraction of the samples within
+    /// `bin_count` Doppler bins over the Doppler extent of the locus, the empty
+    /// bins included.
+    pub fn doppler_histogram(&self, bin_count: usize) -> Vec<DopplerBin> {
+        let (doppler_min_hz, doppler_max_hz) = self.samples.iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &[_, doppler_hz]| (min.min(doppler_hz), max.max(doppler_hz)));
+        if bin_count == 0 || doppler_min_hz > doppler_max_hz {
+            return Vec::new();
+        }
+        let bin_hz = ((doppler_max_hz - doppler_min_hz) / bin_count as f64).max(f64::MIN_POSITIVE);
+        let mut counts = vec![0usize; bin_count];
+        for &[_, doppler_hz] in self.samples.iter() {
+            counts[(((doppler_hz - doppler_min_hz) / bin_hz) as usize).min(bin_count - 1)] += 1;
+        }
+        counts.into_iter()
+            .enumerate()
+            .map(|(index, count)| DopplerBin {
+                doppler_hz: doppler_min_hz + (index as f64 + 0.5) * bin_hz,
+                fraction: count as f64 / self.samples.len() as f64,
+            })
+            .collect()
+    }
+
+    /// Gets the fraction of the samples out of the Doppler band
+    /// `[doppler_center_hz - prf_hz/2, doppler_center_hz + prf_hz/2]`, folded
+    /// back into it by the pulse sampling.
+    pub fn aliased_fraction(&self, doppler_center_hz: f64, prf_hz: f64) -> f64 {
+        if self.samples.is_empty() {
+            return f64::NAN;
+        }
+        let aliased = self.samples.iter()
+            .filter(|&&[_, doppler_hz]| (doppler_hz - doppler_center_hz).abs() > 0.5 * prf_hz)
+            .count();
+        aliased as f64 / self.samples.len() as f64
+    }
+
+    /// Gets the clutter ridge: the Doppler span of the samples within
+    /// `bin_count` range bins over the range extent of the locus, the empty
+    /// bins being skipped.
+    pub fn ridge(&self, bin_count: usize) -> Vec<RidgeBin> {
+        let (range_min_m, range_max_m) = self.samples.iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &[range_m, _]| (min.min(range_m), max.max(range_m)));
+        if bin_count == 0 || range_min_m > range_max_m {
+            return Vec::new();
+        }
+        let bin_m = ((range_max_m - range_min_m) / bin_count as f64).max(f64::MIN_POSITIVE);
+        let mut bins = vec![(f64::INFINITY, f64::NEG_INFINITY); bin_count];
+        for &[range_m, doppler_hz] in self.samples.iter() {
+            let index = (((range_m - range_min_m) / bin_m) as usize).min(bin_count - 1);
+            let (min, max) = &mut bins[index];
+            (*min, *max) = (min.min(doppler_hz), max.max(doppler_hz));
+        }
+        bins.into_iter()
+            .enumerate()
+            .filter(|(_, (min, max))| min <= max)
+            .map(|(index, (doppler_min_hz, doppler_max_hz))| RidgeBin {
+                range_m: range_min_m + (index as f64 + 0.5) * bin_m,
+                doppler_min_hz,
+                doppler_max_hz,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn monostatic_broadside_locus_is_symmetric() {
+        // Carrier 4000 m West of the scene center at 3000 m, flying North at 200 m/s
+        let (position_m, velocity_mps) = (DVec3::new(-4000.0, 0.0, 3000.0), DVec3::new(0.0, 200.0, 0.0));
+        let points_m = (-5..=5).flat_map(|i| (-5..=5).map(move |j| DVec2::new(100.0 * i as f64, 100.0 * j as f64)));
+        let locus = ClutterLocus::new(0.03, &position_m, &velocity_mps, &position_m, &velocity_mps, points_m);
+        assert_eq!(locus.samples.len(), 121);
+        // Scene center: twice the slant range, no Doppler
+        assert!(locus.samples.iter().any(|&[range_m, doppler_hz]| (range_m - 10_000.0).abs() < 1e-9 && doppler_hz.abs() < 1e-9));
+        // Points ahead and behind cancel out
+        let doppler_sum_hz: f64 = locus.samples.iter().map(|&[_, doppler_hz]| doppler_hz).sum();
+        assert!(doppler_sum_hz.abs() < 1e-6);
+        let spread_hz = locus.doppler_spread_hz().unwrap();
+        let ridge = locus.ridge(4);
+        assert_eq!(ridge.len(), 4);
+        assert!(ridge.windows(2).all(|bins| bins[0].range_m < bins[1].range_m));
+        assert!(ridge.iter().all(|bin| bin.doppler_spread_hz() > 0.0 && bin.doppler_spread_hz() <= spread_hz));
+        assert!(ridge.iter().all(|bin| (bin.doppler_min_hz + bin.doppler_max_hz).abs() < 1e-6));
+    }
+
+    #[test]
+    fn doppler_histogram_sums_to_one() {
+        let (position_m, velocity_mps) = (DVec3::new(-4000.0, 0.0, 3000.0), DVec3::new(0.0, 200.0, 0.0));
+        let points_m = (-5..=5).flat_map(|i| (-5..=5).map(move |j| DVec2::new(100.0 * i as f64, 100.0 * j as f64)));
+        let locus = ClutterLocus::new(0.03, &position_m, &velocity_mps, &position_m, &velocity_mps, points_m);
+        let histogram = locus.doppler_histogram(8);
+        assert_eq!(histogram.len(), 8);
+        assert!(histogram.windows(2).all(|bins| bins[0].doppler_hz < bins[1].doppler_hz));
+        assert!((histogram.iter().map(|bin| bin.fraction).sum::<f64>() - 1.0).abs() < 1e-12);
+        // Symmetric about the zero Doppler of the scene center
+        assert!((histogram[0].doppler_hz + histogram[7].doppler_hz).abs() < 1e-6);
+        assert!((histogram[0].fraction - histogram[7].fraction).abs() < 1e-12);
+        // Nothing aliased by a PRF over the Doppler spread, the edges by a narrower one
+        let spread_hz = locus.doppler_spread_hz().unwrap();
+        assert_eq!(locus.aliased_fraction(0.0, 1.01 * spread_hz), 0.0);
+        assert!(locus.aliased_fraction(0.0, 0.5 * spread_hz) > 0.0);
+        assert!(locus.aliased_fraction(0.0, 0.5 * spread_hz) < 1.0);
+    }
+
+    #[test]
+    fn empty_locus_has_no_ridge() {
+        let locus = ClutterLocus::default();
+        assert!(locus.doppler_spread_hz().is_none());
+        assert!(locus.ridge(8).is_empty());
+        assert!(locus.doppler_histogram(8).is_empty());
+        assert!(locus.aliased_fraction(0.0, 100.0).is_nan());
+    }
+}
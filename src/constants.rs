@@ -20,6 +20,8 @@ pub const MAX_BORESIGHT_RANGE_M: f64 = CONE_LENGTH;
 pub const CARRIER_SIZE: f32 = 150.0; // Size of the carrier
 /// Antenna "size", i.e. length of arrows of its referential in meters
 pub const ANTENNA_SIZE: f32 = 100.0;  // Size of the antenna
+/// Minimum height in meters of the drawn footprints above the ground plane
+pub const FOOTPRINT_MIN_LIFT_M: f64 = 0.05;
 
 /// ENU to NED rotation quaternion
 pub const ENU_TO_NED: Quat = Quat::from_xyzw(
@@ -121,7 +123,22 @@ pub static YELLOW_MATERIAL: LazyLock<StandardMaterial> = LazyLock::new(|| {
 });
 
 /// UI constants
-/// Maximum height in meters for the carrier
-pub const MAX_HEIGHT_M: f64 = 1e6;
-/// Maximum velocity in m/s for the velocity vector
-pub const MAX_VELOCITY_MPS: f64 = 10_000.0;
+/// Default maximum height in meters for the carrier (see [`crate::limits`])
+pub const DEFAULT_MAX_HEIGHT_M: f64 = 1e6;
+/// Maximum tower height in meters for a fixed receiver
+pub const MAX_TOWER_HEIGHT_M: f64 = 1000.0;
+/// Default maximum velocity in m/s for the velocity vector (see [`crate::limits`])
+pub const DEFAULT_MAX_VELOCITY_MPS: f64 = 10_000.0;
+/// Maximum PRF in Hz of the transmitter, also the upper bound of the PRF searches
+pub const MAX_PRF_HZ: f64 = 1e6;
+/// Maximum noise temperature in K of the receiver's system
+pub const MAX_NOISE_TEMPERATURE_K: f64 = 1000.0;
+/// Maximum integration time in s of the receiver
+pub const MAX_INTEGRATION_TIME_S: f64 = 100.0;
+/// Maximum turn rate in °/s of the carrier motion profile
+pub const MAX_TURN_RATE_DEGPS: f64 = 30.0;
+/// Maximum speed change rate in m/s² of the carrier motion profile
+pub const MAX_SPEED_RATE_MPS2: f64 = 50.0;
+/// Maximum antenna half-power beamwidth in degrees (broad passive receivers),
+/// kept below 180° where the elliptical cone beam model degenerates
+pub const MAX_BEAM_WIDTH_DEG: f64 = 170.0;
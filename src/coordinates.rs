@@ -3,3 +3,12 @@ pub use geopoint::{CartesianECEFPoint, GeographicPoint};
 
 mod ellipsoid;
 pub use ellipsoid::{Ellipsoid, LocalCartesian};
+
+mod utm;
+pub use utm::UtmPoint;
+
+mod mgrs;
+pub use mgrs::MgrsPoint;
+
+mod moving_local_cartesian;
+pub use moving_local_cartesian::MovingLocalCartesian;
@@ -41,8 +41,36 @@ impl Ellipsoid {
         e2: WGS84_SQUARED_ECCENTRICITY,
     };
 
+    /// [GRS80](https://en.wikipedia.org/wiki/GRS_80) Ellipsoid
+    pub const GRS80: Ellipsoid = Ellipsoid::new(6378137.0, 1.0 / 298.257222101);
+
+    /// [Bessel 1841](https://epsg.org/ellipsoid_7004/Bessel-1841.html) Ellipsoid
+    pub const BESSEL_1841: Ellipsoid = Ellipsoid::new(6377397.155, 1.0 / 299.1528128);
+
+    /// [Clarke 1880 (RGS)](https://epsg.org/ellipsoid_7012/Clarke-1880-RGS.html) Ellipsoid
+    pub const CLARKE_1880: Ellipsoid = Ellipsoid::new(6378249.145, 1.0 / 293.465);
+
+    /// Mars IAU 2000 Ellipsoid (equatorial radius 3396.19 km, polar radius 3376.2 km)
+    pub const MARS: Ellipsoid = Ellipsoid::new(
+        3396190.0,
+        (3396190.0 - 3376200.0) / 3396190.0
+    );
+
+    /// Moon IAU 2000 sphere (radius 1737.4 km)
+    pub const MOON: Ellipsoid = Ellipsoid::new(1737400.0, 0.0);
+
+    /// Named Ellipsoid presets, WGS84 first.
+    pub const PRESETS: [(&'static str, Ellipsoid); 6] = [
+        ("WGS84", Ellipsoid::WGS84),
+        ("GRS80", Ellipsoid::GRS80),
+        ("Bessel 1841", Ellipsoid::BESSEL_1841),
+        ("Clarke 1880", Ellipsoid::CLARKE_1880),
+        ("Mars", Ellipsoid::MARS),
+        ("Moon", Ellipsoid::MOON),
+    ];
+
     /// Creates a new Ellipsoid from its equatorial radius in meters and first flattening parameters.
-    pub fn new(equatorial_radius_m: f64, first_flattening: f64) -> Self {
+    pub const fn new(equatorial_radius_m: f64, first_flattening: f64) -> Self {
         Self {
             a: equatorial_radius_m,
             b: (1.0 - first_flattening) * equatorial_radius_m,
@@ -182,6 +210,160 @@ impl Ellipsoid {
         };
         pos + t * axis
     }
+
+    /// Solves the direct geodesic problem on this Ellipsoid: the point reached from
+    /// `gp` after travelling `distance_m` meters along the geodesic with initial
+    /// azimuth `azimuth_deg` (clockwise from North).
+    ///
+    /// Returns the destination point (with the height of `gp`) and the azimuth in
+    /// degrees of the geodesic at the destination point.
+    ///
+    /// The computation uses the Vincenty formulas[^note], accurate to less than a
+    /// millimeter on the Earth ellipsoids. A non-finite input never converges:
+    /// the iteration stops after a fixed count, giving a `NaN` point.
+    ///
+    /// [^note]: Vincenty, T., *Direct and inverse solutions of geodesics on the ellipsoid
+    /// with application of nested equations*. Survey Review 23, 88–93 (1975).
+    /// <https://doi.org/10.1179/sre.1975.23.176.88>
+    pub fn geodesic_direct(
+        &self,
+        gp: &GeographicPoint,
+        azimuth_deg: f64,
+        distance_m: f64,
+    ) -> (GeographicPoint, f64) {
+        const MAX_ITERATIONS: usize = 200;
+        let f = self.f;
+        let (sin_alpha1, cos_alpha1) = azimuth_deg.to_radians().sin_cos();
+        // Reduced latitude
+        let tan_u1 = (1.0 - f) * gp.lat_rad().tan();
+        let cos_u1 = 1.0 / tan_u1.hypot(1.0);
+        let sin_u1 = tan_u1 * cos_u1;
+        let sigma1 = tan_u1.atan2(cos_alpha1); // Angular distance on the sphere from the equator
+        let sin_alpha = cos_u1 * sin_alpha1; // Azimuth of the geodesic at the equator
+        let cos2_alpha = 1.0 - sin_alpha * sin_alpha;
+        let u2 = cos2_alpha * (self.a * self.a - self.b * self.b) / (self.b * self.b);
+        let a_coef = 1.0 + u2 / 16384.0 * (4096.0 + u2 * (-768.0 + u2 * (320.0 - 175.0 * u2)));
+        let b_coef = u2 / 1024.0 * (256.0 + u2 * (-128.0 + u2 * (74.0 - 47.0 * u2)));
+
+        let sigma0 = distance_m / (self.b * a_coef);
+        let mut sigma = sigma0;
+        let (mut sin_sigma, mut cos_sigma, mut cos_2sigma_m);
+        let mut iteration = 0;
+        loop {
+            cos_2sigma_m = (2.0 * sigma1 + sigma).cos();
+            (sin_sigma, cos_sigma) = sigma.sin_cos();
+            let delta_sigma = b_coef * sin_sigma * (
+                cos_2sigma_m + 0.25 * b_coef * (
+                    cos_sigma * (2.0 * cos_2sigma_m * cos_2sigma_m - 1.0)
+                    - b_coef / 6.0 * cos_2sigma_m
+                        * (4.0 * sin_sigma * sin_sigma - 3.0)
+                        * (4.0 * cos_2sigma_m * cos_2sigma_m - 3.0)
+                )
+            );
+            let sigma_prev = sigma;
+            sigma = sigma0 + delta_sigma;
+            iteration += 1;
+            if (sigma - sigma_prev).abs() < 1e-12 || iteration == MAX_ITERATIONS {
+                break;
+            }
+        }
+        cos_2sigma_m = (2.0 * sigma1 + sigma).cos();
+        (sin_sigma, cos_sigma) = sigma.sin_cos();
+
+        let x = sin_u1 * sin_sigma - cos_u1 * cos_sigma * cos_alpha1;
+        let lat_rad = (sin_u1 * cos_sigma + cos_u1 * sin_sigma * cos_alpha1)
+            .atan2((1.0 - f) * sin_alpha.hypot(x));
+        let lambda = (sin_sigma * sin_alpha1)
+            .atan2(cos_u1 * cos_sigma - sin_u1 * sin_sigma * cos_alpha1);
+        let c = f / 16.0 * cos2_alpha * (4.0 + f * (4.0 - 3.0 * cos2_alpha));
+        let l = lambda - (1.0 - c) * f * sin_alpha * (
+            sigma + c * sin_sigma * (
+                cos_2sigma_m + c * cos_sigma * (2.0 * cos_2sigma_m * cos_2sigma_m - 1.0)
+            )
+        );
+        let lon_deg = (gp.lon_deg() + l.to_degrees() + 540.0).rem_euclid(360.0) - 180.0;
+        let final_azimuth_deg = sin_alpha.atan2(-x).to_degrees().rem_euclid(360.0);
+
+        (
+            GeographicPoint::from_degrees(lon_deg, lat_rad.to_degrees(), gp.height_m()),
+            final_azimuth_deg
+        )
+    }
+
+    /// Solves the inverse geodesic problem on this Ellipsoid: the length in
+    /// meters of the geodesic between `gp1` and `gp2` (heights ignored).
+    ///
+    /// Returns the distance in meters and the azimuths in degrees (clockwise from
+    /// North) of the geodesic at `gp1` and at `gp2`.
+    ///
+    /// The computation uses the Vincenty formulas (see [`Ellipsoid::geodesic_direct`]),
+    /// which may not converge for nearly antipodal points: the last iterate is
+    /// then used.
+    pub fn geodesic_inverse(
+        &self,
+        gp1: &GeographicPoint,
+        gp2: &GeographicPoint,
+    ) -> (f64, f64, f64) {
+        const MAX_ITERATIONS: usize = 200;
+        let f = self.f;
+        let l = gp2.lon_rad() - gp1.lon_rad();
+        // Reduced latitudes
+        let (sin_u1, cos_u1) = ((1.0 - f) * gp1.lat_rad().tan()).atan().sin_cos();
+        let (sin_u2, cos_u2) = ((1.0 - f) * gp2.lat_rad().tan()).atan().sin_cos();
+
+        let mut lambda = l;
+        let (mut sin_sigma, mut cos_sigma, mut sigma, mut cos2_alpha, mut cos_2sigma_m);
+        let mut iteration = 0;
+        loop {
+            let (sin_lambda, cos_lambda) = lambda.sin_cos();
+            sin_sigma = (cos_u2 * sin_lambda).hypot(cos_u1 * sin_u2 - sin_u1 * cos_u2 * cos_lambda);
+            if sin_sigma == 0.0 {
+                return (0.0, 0.0, 0.0); // Coincident points
+            }
+            cos_sigma = sin_u1 * sin_u2 + cos_u1 * cos_u2 * cos_lambda;
+            sigma = sin_sigma.atan2(cos_sigma);
+            let sin_alpha = cos_u1 * cos_u2 * sin_lambda / sin_sigma;
+            cos2_alpha = 1.0 - sin_alpha * sin_alpha;
+            // Equatorial line: cos²α = 0
+            cos_2sigma_m = if cos2_alpha != 0.0 { cos_sigma - 2.0 * sin_u1 * sin_u2 / cos2_alpha } else { 0.0 };
+            let c = f / 16.0 * cos2_alpha * (4.0 + f * (4.0 - 3.0 * cos2_alpha));
+            let lambda_prev = lambda;
+            lambda = l + (1.0 - c) * f * sin_alpha * (
+                sigma + c * sin_sigma * (
+                    cos_2sigma_m + c * cos_sigma * (2.0 * cos_2sigma_m * cos_2sigma_m - 1.0)
+                )
+            );
+            iteration += 1;
+            if (lambda - lambda_prev).abs() < 1e-12 || iteration == MAX_ITERATIONS {
+                break;
+            }
+        }
+
+        let u2 = cos2_alpha * (self.a * self.a - self.b * self.b) / (self.b * self.b);
+        let a_coef = 1.0 + u2 / 16384.0 * (4096.0 + u2 * (-768.0 + u2 * (320.0 - 175.0 * u2)));
+        let b_coef = u2 / 1024.0 * (256.0 + u2 * (-128.0 + u2 * (74.0 - 47.0 * u2)));
+        let delta_sigma = b_coef * sin_sigma * (
+            cos_2sigma_m + 0.25 * b_coef * (
+                cos_sigma * (2.0 * cos_2sigma_m * cos_2sigma_m - 1.0)
+                - b_coef / 6.0 * cos_2sigma_m
+                    * (4.0 * sin_sigma * sin_sigma - 3.0)
+                    * (4.0 * cos_2sigma_m * cos_2sigma_m - 3.0)
+            )
+        );
+        let distance_m = self.b * a_coef * (sigma - delta_sigma);
+
+        let (sin_lambda, cos_lambda) = lambda.sin_cos();
+        let azimuth1_deg = (cos_u2 * sin_lambda)
+            .atan2(cos_u1 * sin_u2 - sin_u1 * cos_u2 * cos_lambda)
+            .to_degrees()
+            .rem_euclid(360.0);
+        let azimuth2_deg = (cos_u1 * sin_lambda)
+            .atan2(-sin_u1 * cos_u2 + cos_u1 * sin_u2 * cos_lambda)
+            .to_degrees()
+            .rem_euclid(360.0);
+
+        (distance_m, azimuth1_deg, azimuth2_deg)
+    }
 }
 
 /// A Local Cartesian reference frame on a given Ellipsoid of revolution.
@@ -193,6 +375,7 @@ impl Ellipsoid {
 /// See [Local Tangent Plane](https://en.wikipedia.org/wiki/Local_tangent_plane_coordinates) for more details.
 #[derive(Debug, Clone, PartialEq)]
 pub struct LocalCartesian {
+    ellipsoid: Ellipsoid,
     origin: (GeographicPoint, CartesianECEFPoint),
     transform: DAffine3, // local NED to ECEF isometry, i.e. translation + rotation for 'Point' and only rotation for 'Vector'
     inverse_transform: DAffine3, // ECEF to local NED isometry
@@ -209,6 +392,7 @@ impl Default for LocalCartesian {
         let (transform, inverse_transform) =
             Self::set_ned_to_ecef_transform(&GP, &cp);
         Self {
+            ellipsoid: Ellipsoid::WGS84,
             origin: (GP, cp),
             transform,
             inverse_transform
@@ -217,7 +401,8 @@ impl Default for LocalCartesian {
 }
 
 impl LocalCartesian {
-    /// Creates a new Local Cartesian reference frame on the given Ellipsoid of revolution.
+    /// Creates a new Local Cartesian reference frame on the WGS84 Ellipsoid
+    /// (see [`LocalCartesian::with_ellipsoid`] for other ellipsoids).
     /// 
     /// The origin of the frame is set at the intersection of the Greenwich meridian and equator lines,
     /// i.e. at geographic coordinates (0°, 0°, 0m).
@@ -227,13 +412,14 @@ impl LocalCartesian {
         let (transform, inverse_transform) =
             Self::set_ned_to_ecef_transform(&GP, &cp);
         Self {
+            ellipsoid: Ellipsoid::WGS84,
             origin: (GP, cp),
             transform,
             inverse_transform
         }
     }
 
-    /// Creates a new Local Cartesian reference frame on the given Ellipsoid of revolution
+    /// Creates a new Local Cartesian reference frame on the WGS84 Ellipsoid
     /// with its origin set at the given [`GeographicPoint`].
     #[inline]
     pub fn from_geographic_point(gp: &GeographicPoint) -> Self {
@@ -241,13 +427,14 @@ impl LocalCartesian {
         let (transform, inverse_transform) =
             Self::set_ned_to_ecef_transform(gp, &cp);
         Self {
+            ellipsoid: Ellipsoid::WGS84,
             origin: (gp.clone(), cp),
             transform,
             inverse_transform
         }
     }
 
-    /// Creates a new Local Cartesian reference frame on the given Ellipsoid of revolution
+    /// Creates a new Local Cartesian reference frame on the WGS84 Ellipsoid
     /// with its origin set at the given [`CartesianECEFPoint`].
     #[inline]
     pub fn from_cartesian_ecef_point(cp: &CartesianECEFPoint) -> Self {
@@ -255,6 +442,7 @@ impl LocalCartesian {
         let (transform, inverse_transform) =
             Self::set_ned_to_ecef_transform(&gp, cp);
         Self {
+            ellipsoid: Ellipsoid::WGS84,
             origin: (gp, *cp),
             transform,
             inverse_transform
@@ -264,7 +452,7 @@ impl LocalCartesian {
     /// Sets the origin of the Local Cartesian reference frame from a [`GeographicPoint`].
     #[inline]
     pub fn set_origin_from_geographic_point(&mut self, gp: &GeographicPoint) -> &mut Self {
-        let cp = Ellipsoid::WGS84.to_cartesian_ecef_point(gp);
+        let cp = self.ellipsoid.to_cartesian_ecef_point(gp);
         self.origin = (gp.clone(), cp);
         (self.transform, self.inverse_transform) =
             Self::set_ned_to_ecef_transform(gp, &cp);
@@ -274,13 +462,38 @@ impl LocalCartesian {
     /// Sets the origin of the Local Cartesian reference frame from a [`CartesianECEFPoint`].
     #[inline]
     pub fn set_origin_from_cartesian_ecef_point(&mut self, cp: &CartesianECEFPoint) -> &mut Self {
-        let gp = Ellipsoid::WGS84.to_geographic_point(cp);
+        let gp = self.ellipsoid.to_geographic_point(cp);
         self.origin = (gp.clone(), *cp);
         (self.transform, self.inverse_transform) =
             Self::set_ned_to_ecef_transform(&gp, cp);
         self
     }
 
+    /// Sets the Ellipsoid of revolution of the Local Cartesian reference frame.
+    ///
+    /// The geographic coordinates of the origin are kept, i.e. the origin is moved
+    /// in the geocentric Cartesian ECEF frame.
+    #[inline]
+    pub fn set_ellipsoid(&mut self, ellipsoid: &Ellipsoid) -> &mut Self {
+        self.ellipsoid = *ellipsoid;
+        let gp = self.origin.0.clone();
+        self.set_origin_from_geographic_point(&gp)
+    }
+
+    /// Creates a copy of this Local Cartesian reference frame on the given Ellipsoid of
+    /// revolution, keeping the geographic coordinates of the origin.
+    #[inline]
+    pub fn with_ellipsoid(mut self, ellipsoid: &Ellipsoid) -> Self {
+        self.set_ellipsoid(ellipsoid);
+        self
+    }
+
+    /// Gets the Ellipsoid of revolution of the Local Cartesian reference frame.
+    #[inline]
+    pub const fn ellipsoid(&self) -> &Ellipsoid {
+        &self.ellipsoid
+    }
+
     /// Gets the origin of the Local Cartesian reference frame as a [`GeographicPoint`].
     #[inline]
     pub const fn origin_as_geographic_point(&self) -> &GeographicPoint {
@@ -311,7 +524,7 @@ impl LocalCartesian {
         &self,
         point: &DVec3
     ) -> GeographicPoint {
-        Ellipsoid::WGS84
+        self.ellipsoid
             .to_geographic_point(
                 &self.transform_from_ned_point_to_cartesian_ecef_point(point)
             )
@@ -333,7 +546,7 @@ impl LocalCartesian {
         gp: &GeographicPoint
     ) -> DVec3 {
         self.transform_from_cartesian_ecef_point_to_ned_point(
-            &Ellipsoid::WGS84.to_cartesian_ecef_point(gp),
+            &self.ellipsoid.to_cartesian_ecef_point(gp),
         )
     }
 
@@ -357,7 +570,7 @@ impl LocalCartesian {
         &self,
         point: &DVec3
     ) -> GeographicPoint {
-        Ellipsoid::WGS84.to_geographic_point(
+        self.ellipsoid.to_geographic_point(
             &self.transform_from_enu_point_to_cartesian_ecef_point(point)
         )
     }
@@ -384,6 +597,20 @@ impl LocalCartesian {
         )
     }
 
+    /// Transforms a point from Local Cartesian ENU coordinates to the ENU coordinates
+    /// of an `other` Local Cartesian reference frame, e.g. to rebase scene positions
+    /// when the origin of the frame moves.
+    #[inline]
+    pub fn transform_from_enu_point_to_other_enu_point(
+        &self,
+        point: &DVec3,
+        other: &LocalCartesian
+    ) -> DVec3 {
+        other.transform_from_cartesian_ecef_point_to_enu_point(
+            &self.transform_from_enu_point_to_cartesian_ecef_point(point)
+        )
+    }
+
     /************************************************/
     /* ENU/NED <-> ECEF vector transform */
     /************************************************/
@@ -561,4 +788,103 @@ mod tests {
         let back = local.transform_from_enu_point_to_cartesian_ecef_point(&enu);
         assert_close(back.distance(offset), 0.0, 1e-8);
     }
+
+    #[test]
+    fn ellipsoid_presets() {
+        // GRS80 and WGS84 polar radii differ by ~0.1 mm
+        assert_close(Ellipsoid::GRS80.polar_radius_m(), Ellipsoid::WGS84.polar_radius_m(), 1e-3);
+        assert_close(Ellipsoid::BESSEL_1841.polar_radius_m(), 6356078.963, 1e-3);
+        assert_close(Ellipsoid::CLARKE_1880.polar_radius_m(), 6356514.870, 1e-3);
+        assert_close(Ellipsoid::MARS.polar_radius_m(), 3376200.0, 1e-6);
+        assert_eq!(Ellipsoid::MOON.eccentricity_squared(), 0.0);
+        assert_eq!(Ellipsoid::PRESETS[0].1, Ellipsoid::default());
+    }
+
+    #[test]
+    fn local_cartesian_on_other_ellipsoid() {
+        let origin = GeographicPoint::from_degrees(-30.0, 20.0, 0.0);
+        let local = LocalCartesian::from_geographic_point(&origin).with_ellipsoid(&Ellipsoid::MARS);
+        // The geographic origin is kept on the new Ellipsoid
+        assert_eq!(local.origin_as_geographic_point(), &origin);
+        let origin_ecef = Ellipsoid::MARS.to_cartesian_ecef_point(&origin);
+        assert_close(local.origin_as_cartesian_ecef_point().distance(origin_ecef), 0.0, 1e-9);
+        // Geographic conversions use the Mars Ellipsoid
+        let gp = local.transform_from_enu_point_to_geographic_point(&DVec3::new(0.0, 0.0, 250.0));
+        assert_close(gp.lon_deg(), -30.0, 1e-11);
+        assert_close(gp.lat_deg(), 20.0, 1e-11);
+        assert_close(gp.height_m(), 250.0, 1e-7);
+    }
+
+    #[test]
+    fn geodesic_direct_reference_line() {
+        // Flinders Peak -> Buninyong (Vincenty's reference line on GRS80)
+        let start = GeographicPoint::from_degrees(
+            GeographicPoint::dms_to_dd(144.0, 25.0, 29.52440),
+            -GeographicPoint::dms_to_dd(37.0, 57.0, 3.72030),
+            0.0
+        );
+        let (end, final_azimuth_deg) = Ellipsoid::GRS80.geodesic_direct(
+            &start,
+            GeographicPoint::dms_to_dd(306.0, 52.0, 5.37),
+            54972.271
+        );
+        assert_close(end.lon_deg(), GeographicPoint::dms_to_dd(143.0, 55.0, 35.38390), 1e-8);
+        assert_close(end.lat_deg(), -GeographicPoint::dms_to_dd(37.0, 39.0, 10.15610), 1e-8);
+        assert_close(final_azimuth_deg, GeographicPoint::dms_to_dd(307.0, 10.0, 25.07), 1e-5);
+    }
+
+    #[test]
+    fn geodesic_direct_along_equator() {
+        // Eastward along the equator the geodesic is the equator itself
+        let wgs84 = Ellipsoid::WGS84;
+        let (end, azimuth_deg) = wgs84.geodesic_direct(&GeographicPoint::from_degrees(179.0, 0.0, 10.0), 90.0, 250e3);
+        assert_close(end.lat_deg(), 0.0, 1e-12);
+        assert_close(end.lon_deg(), (179.0 + (250e3 / wgs84.equatorial_radius_m()).to_degrees()) - 360.0, 1e-9);
+        assert_close(end.height_m(), 10.0, 0.0);
+        assert_close(azimuth_deg, 90.0, 1e-9);
+        // Non-finite inputs stop after the last iteration
+        let (end, _) = wgs84.geodesic_direct(&GeographicPoint::from_degrees(0.0, 0.0, 0.0), 90.0, f64::NAN);
+        assert!(end.lat_deg().is_nan());
+    }
+
+    #[test]
+    fn geodesic_inverse_reference_line() {
+        // Flinders Peak -> Buninyong (Vincenty's reference line on GRS80)
+        let start = GeographicPoint::from_degrees(
+            GeographicPoint::dms_to_dd(144.0, 25.0, 29.52440),
+            -GeographicPoint::dms_to_dd(37.0, 57.0, 3.72030),
+            0.0
+        );
+        let end = GeographicPoint::from_degrees(
+            GeographicPoint::dms_to_dd(143.0, 55.0, 35.38390),
+            -GeographicPoint::dms_to_dd(37.0, 39.0, 10.15610),
+            0.0
+        );
+        let (distance_m, azimuth1_deg, azimuth2_deg) = Ellipsoid::GRS80.geodesic_inverse(&start, &end);
+        assert_close(distance_m, 54972.271, 1e-3);
+        assert_close(azimuth1_deg, GeographicPoint::dms_to_dd(306.0, 52.0, 5.37), 1e-5);
+        assert_close(azimuth2_deg, GeographicPoint::dms_to_dd(307.0, 10.0, 25.07), 1e-5);
+        assert_eq!(Ellipsoid::GRS80.geodesic_inverse(&start, &start).0, 0.0);
+        // Along the equator, the arc of the equatorial circle
+        let wgs84 = Ellipsoid::WGS84;
+        let (distance_m, ..) = wgs84.geodesic_inverse(
+            &GeographicPoint::from_degrees(0.0, 0.0, 0.0),
+            &GeographicPoint::from_degrees(1.0, 0.0, 0.0)
+        );
+        assert_close(distance_m, wgs84.equatorial_radius_m() * 1f64.to_radians(), 1e-6);
+    }
+
+    #[test]
+    fn enu_point_rebased_to_other_frame() {
+        let frame_a = LocalCartesian::from_geographic_point(&GeographicPoint::from_degrees(5.93, 43.12, 0.0));
+        let frame_b = LocalCartesian::from_geographic_point(&GeographicPoint::from_degrees(6.0, 43.2, 0.0));
+        let point = DVec3::new(1500.0, -300.0, 12000.0);
+        let rebased = frame_a.transform_from_enu_point_to_other_enu_point(&point, &frame_b);
+        let back = frame_b.transform_from_enu_point_to_other_enu_point(&rebased, &frame_a);
+        assert_close(back.distance(point), 0.0, 1e-8);
+        // The origin of frame_b is at its own ENU origin
+        let origin_b = frame_a.transform_from_geographic_point_to_enu_point(frame_b.origin_as_geographic_point());
+        let rebased_origin = frame_a.transform_from_enu_point_to_other_enu_point(&origin_b, &frame_b);
+        assert_close(rebased_origin.length(), 0.0, 1e-8);
+    }
 }
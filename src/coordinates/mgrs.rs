@@ -0,0 +1,235 @@
+use std::str::FromStr;
+
+use crate::coordinates::{Ellipsoid, GeographicPoint, UtmPoint};
+
+/// MGRS latitude bands, 8° each from 80°S (the last band "X" spans 72°N - 84°N)
+const LATITUDE_BANDS: &[u8] = b"CDEFGHJKLMNPQRSTUVWXX";
+/// 100 km square column letters, cycling every 3 zones
+const COLUMN_LETTERS: [&[u8]; 3] = [b"STUVWXYZ", b"ABCDEFGH", b"JKLMNPQR"];
+/// 100 km square row letters, cycling every 2000 km
+const ROW_LETTERS: &[u8] = b"ABCDEFGHJKLMNPQRSTUV";
+
+/// A [Military Grid Reference System] (MGRS) grid reference.
+///
+/// MGRS is defined on top of the UTM projection of the [`Ellipsoid::WGS84`] ellipsoid:
+/// a grid reference is the UTM zone, the latitude band, the 100 km square letters and
+/// the easting/northing within that square. The polar (UPS) areas are not supported.
+///
+/// [Military Grid Reference System]: https://en.wikipedia.org/wiki/Military_Grid_Reference_System
+#[derive(Debug, Clone, PartialEq)]
+pub struct MgrsPoint {
+    zone: u8,
+    band: char,
+    column: char,
+    row: char,
+    easting_m: f64,  // easting within the 100 km square
+    northing_m: f64, // northing within the 100 km square
+}
+
+impl MgrsPoint {
+    /// Converts a [`GeographicPoint`] (on the WGS84 ellipsoid) to its MGRS grid reference.
+    pub fn from_geographic_point(gp: &GeographicPoint) -> Result<Self, String> {
+        let utm = UtmPoint::from_geographic_point(gp, &Ellipsoid::WGS84)?;
+        let band_index = (((gp.lat_deg() + 80.0) / 8.0).floor() as usize).min(LATITUDE_BANDS.len() - 1);
+        let column_set = COLUMN_LETTERS[utm.zone() as usize % 3];
+        let column_index = (utm.easting_m() / 100_000.0).floor() as usize;
+        if !(1..=8).contains(&column_index) {
+            return Err(format!("easting {:.0} m is outside the MGRS grid", utm.easting_m()));
+        }
+        let row_offset = if utm.zone().is_multiple_of(2) { 5 } else { 0 };
+        let row_index = ((utm.northing_m() / 100_000.0).floor() as usize + row_offset) % ROW_LETTERS.len();
+        Ok(Self {
+            zone: utm.zone(),
+            band: LATITUDE_BANDS[band_index] as char,
+            column: column_set[column_index - 1] as char,
+            row: ROW_LETTERS[row_index] as char,
+            easting_m: utm.easting_m().rem_euclid(100_000.0),
+            northing_m: utm.northing_m().rem_euclid(100_000.0),
+        })
+    }
+
+    /// Converts this grid reference back to a [`GeographicPoint`] (at zero height) on the
+    /// WGS84 ellipsoid.
+    ///
+    /// Note: a parsed grid reference stands for the south-west corner of its grid cell.
+    pub fn to_geographic_point(&self) -> Result<GeographicPoint, String> {
+        Ok(self.to_utm_point()?.to_geographic_point(&Ellipsoid::WGS84))
+    }
+
+    /// Converts this grid reference to a full [`UtmPoint`].
+    pub fn to_utm_point(&self) -> Result<UtmPoint, String> {
+        let band_index = LATITUDE_BANDS.iter()
+            .position(|&b| b as char == self.band)
+            .ok_or_else(|| format!("invalid latitude band '{}'", self.band))?;
+        let column_set = COLUMN_LETTERS[self.zone as usize % 3];
+        let column_index = column_set.iter()
+            .position(|&c| c as char == self.column)
+            .ok_or_else(|| format!("invalid column letter '{}' for zone {}", self.column, self.zone))?;
+        let row_index = ROW_LETTERS.iter()
+            .position(|&r| r as char == self.row)
+            .ok_or_else(|| format!("invalid row letter '{}'", self.row))?;
+        let row_offset = if self.zone.is_multiple_of(2) { 5 } else { 0 };
+        let row_index = (row_index + ROW_LETTERS.len() - row_offset) % ROW_LETTERS.len();
+
+        let north = self.band >= 'N';
+        let easting_m = (column_index + 1) as f64 * 100_000.0 + self.easting_m;
+        let northing_m = row_index as f64 * 100_000.0 + self.northing_m;
+        // The row letters repeat every 2000 km: pick the first repetition above the
+        // bottom of the latitude band, measured on the central meridian where the
+        // northing of a parallel is the smallest
+        let band_bottom_lat_deg = -80.0 + 8.0 * band_index as f64;
+        let band_bottom = UtmPoint::from_geographic_point_in_zone(
+            &GeographicPoint::from_degrees(6.0 * self.zone as f64 - 183.0, band_bottom_lat_deg, 0.0),
+            &Ellipsoid::WGS84,
+            self.zone,
+        )?;
+        let mut offset_m = 0.0;
+        while northing_m + offset_m < band_bottom.northing_m() - 100_000.0 {
+            offset_m += 2_000_000.0;
+        }
+        UtmPoint::new(self.zone, north, easting_m, northing_m + offset_m)
+    }
+
+    /// Formats this grid reference with `digits` (0 - 5) digits per coordinate,
+    /// e.g. 5 digits is a 1 m precision and 0 digits only names the 100 km square.
+    pub fn to_string_with_precision(&self, digits: usize) -> String {
+        let digits = digits.min(5);
+        let scale = 10f64.powi(5 - digits as i32);
+        let easting = (self.easting_m / scale).floor() as u64;
+        let northing = (self.northing_m / scale).floor() as u64;
+        if digits == 0 {
+            format!("{:02}{}{}{}", self.zone, self.band, self.column, self.row)
+        } else {
+            format!(
+                "{:02}{}{}{} {:0digits$} {:0digits$}",
+                self.zone, self.band, self.column, self.row, easting, northing
+            )
+        }
+    }
+
+    /// Gets the UTM zone number (1 - 60).
+    #[inline]
+    pub const fn zone(&self) -> u8 {
+        self.zone
+    }
+
+    /// Gets the latitude band letter.
+    #[inline]
+    pub const fn band(&self) -> char {
+        self.band
+    }
+}
+
+impl std::fmt::Display for MgrsPoint {
+    /// Formats the grid reference with a 1 m precision (5 digits per coordinate).
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.to_string_with_precision(5))
+    }
+}
+
+impl FromStr for MgrsPoint {
+    type Err = String;
+
+    /// Parses a grid reference such as `"31U DQ 48251 11932"` or `"31UDQ4825111932"`.
+    /// Spaces are ignored and letters are case-insensitive.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let compact: String = s.chars()
+            .filter(|c| !c.is_whitespace())
+            .collect::<String>()
+            .to_ascii_uppercase();
+        let zone_len = compact.chars().take_while(|c| c.is_ascii_digit()).count();
+        if !(1..=2).contains(&zone_len) {
+            return Err(format!("'{s}' does not start with a UTM zone number"));
+        }
+        let zone: u8 = compact[..zone_len].parse().map_err(|_| format!("invalid zone in '{s}'"))?;
+        if !(1..=60).contains(&zone) {
+            return Err(format!("invalid UTM zone {zone} (expected 1 - 60)"));
+        }
+        let mut letters = compact[zone_len..].chars();
+        let (Some(band), Some(column), Some(row)) = (letters.next(), letters.next(), letters.next()) else {
+            return Err(format!("'{s}' is missing the band and 100 km square letters"));
+        };
+        let digits: String = letters.collect();
+        if !digits.chars().all(|c| c.is_ascii_digit()) || !digits.len().is_multiple_of(2) || digits.len() > 10 {
+            return Err(format!("'{s}' must end with an even number (0 - 10) of digits"));
+        }
+        let half = digits.len() / 2;
+        let scale = 10f64.powi(5 - half as i32);
+        let parse = |part: &str| -> f64 { part.parse::<f64>().unwrap_or(0.0) * scale };
+        let point = Self {
+            zone,
+            band,
+            column,
+            row,
+            easting_m: if half > 0 { parse(&digits[..half]) } else { 0.0 },
+            northing_m: if half > 0 { parse(&digits[half..]) } else { 0.0 },
+        };
+        point.to_utm_point()?; // Validates the letters
+        Ok(point)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(value: f64, expected: f64, abs_tol: f64) {
+        assert!(
+            (value - expected).abs() <= abs_tol,
+            "value = {value}, expected = {expected}"
+        );
+    }
+
+    #[test]
+    fn encode_reference_point() {
+        // Eiffel Tower, UTM 31N 448251.795 5411932.678
+        let mgrs = MgrsPoint::from_geographic_point(
+            &GeographicPoint::from_degrees(2.2945, 48.8582, 0.0)
+        ).unwrap();
+        assert_eq!(mgrs.to_string(), "31UDQ 48251 11932");
+        assert_eq!(mgrs.to_string_with_precision(2), "31UDQ 48 11");
+        assert_eq!(mgrs.to_string_with_precision(0), "31UDQ");
+    }
+
+    #[test]
+    fn parse_reference_point() {
+        let gp = "31U DQ 48251 11932".parse::<MgrsPoint>().unwrap()
+            .to_geographic_point().unwrap();
+        // South-west corner of the 1 m cell: within ~1.5 m of the encoded point
+        assert_close(gp.lon_deg(), 2.2945, 2e-5);
+        assert_close(gp.lat_deg(), 48.8582, 2e-5);
+        // Compact and lowercase forms are accepted
+        assert_eq!(
+            "31udq4825111932".parse::<MgrsPoint>().unwrap(),
+            "31U DQ 48251 11932".parse::<MgrsPoint>().unwrap()
+        );
+    }
+
+    #[test]
+    fn roundtrip_various_latitudes() {
+        for &(lon, lat) in [
+            (5.93, 43.12),
+            (-120.5, -33.87),
+            (151.2, -79.5),
+            (-70.0, 83.0),
+            (0.5, 0.01),
+            (0.5, -0.01),
+            (23.0, 65.0),
+        ].iter() {
+            let gp = GeographicPoint::from_degrees(lon, lat, 0.0);
+            let text = MgrsPoint::from_geographic_point(&gp).unwrap().to_string();
+            let back = text.parse::<MgrsPoint>().unwrap().to_geographic_point().unwrap();
+            assert_close(back.lon_deg(), lon, 1e-4);
+            assert_close(back.lat_deg(), lat, 1e-4);
+        }
+    }
+
+    #[test]
+    fn invalid_references_are_rejected() {
+        assert!("".parse::<MgrsPoint>().is_err());
+        assert!("61UDQ".parse::<MgrsPoint>().is_err()); // Zone out of range
+        assert!("31UDQ 123 45".parse::<MgrsPoint>().is_err()); // Odd digit count
+        assert!("31UIQ".parse::<MgrsPoint>().is_err()); // 'I' is never used
+        assert!("31USQ".parse::<MgrsPoint>().is_err()); // 'S' is not a zone 31 column
+    }
+}
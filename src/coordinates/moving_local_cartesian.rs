@@ -0,0 +1,153 @@
+use bevy::math::DVec3;
+
+use crate::coordinates::{Ellipsoid, GeographicPoint, LocalCartesian};
+
+/// A Local Cartesian reference frame whose origin moves along a geodesic of its
+/// Ellipsoid of revolution at a constant ground speed.
+///
+/// It can keep the local coordinates of a moving scene (e.g. following the scene
+/// center during a long orbital pass) small while remaining georeferenced; the
+/// timeline does not use it yet, its scene frame staying fixed. At any time the
+/// frame is a plain [`LocalCartesian`] whose origin is the point reached along
+/// the geodesic, and positions can be rebased from the previous frame with
+/// [`MovingLocalCartesian::set_time_s`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct MovingLocalCartesian {
+    start: GeographicPoint, // Origin at time 0
+    start_azimuth_deg: f64, // Initial azimuth of the geodesic (clockwise from North)
+    ground_speed_mps: f64, // Ground speed of the origin along the geodesic
+    time_s: f64, // Current time
+    azimuth_deg: f64, // Azimuth of the geodesic at the current origin
+    frame: LocalCartesian, // Local Cartesian frame at the current time
+}
+
+impl MovingLocalCartesian {
+    /// Creates a new moving Local Cartesian reference frame on the given Ellipsoid,
+    /// with its origin at `start` at time 0 s moving along the geodesic of initial
+    /// azimuth `azimuth_deg` at `ground_speed_mps`.
+    pub fn new(
+        start: &GeographicPoint,
+        ellipsoid: &Ellipsoid,
+        azimuth_deg: f64,
+        ground_speed_mps: f64,
+    ) -> Self {
+        Self {
+            start: start.clone(),
+            start_azimuth_deg: azimuth_deg,
+            ground_speed_mps,
+            time_s: 0.0,
+            azimuth_deg,
+            frame: LocalCartesian::from_geographic_point(start).with_ellipsoid(ellipsoid),
+        }
+    }
+
+    /// Creates a fixed Local Cartesian reference frame, i.e. with a zero ground speed.
+    #[inline]
+    pub fn fixed(frame: &LocalCartesian) -> Self {
+        Self::new(frame.origin_as_geographic_point(), frame.ellipsoid(), 0.0, 0.0)
+    }
+
+    /// Moves the origin to its position at `time_s` seconds.
+    ///
+    /// Returns the frame before the move, so that positions expressed in it can be
+    /// rebased with [`LocalCartesian::transform_from_enu_point_to_other_enu_point`].
+    pub fn set_time_s(&mut self, time_s: f64) -> LocalCartesian {
+        let previous_frame = self.frame.clone();
+        let (origin, azimuth_deg) = self.frame.ellipsoid().geodesic_direct(
+            &self.start,
+            self.start_azimuth_deg,
+            self.ground_speed_mps * time_s
+        );
+        self.frame.set_origin_from_geographic_point(&origin);
+        self.azimuth_deg = azimuth_deg;
+        self.time_s = time_s;
+        previous_frame
+    }
+
+    /// Transforms a point from the ENU coordinates of the frame at `time_s` to the
+    /// ENU coordinates of the current frame, without moving it.
+    pub fn transform_from_enu_point_at_time(&self, point: &DVec3, time_s: f64) -> DVec3 {
+        let mut frame_at_time = self.clone();
+        frame_at_time.set_time_s(time_s);
+        frame_at_time.frame.transform_from_enu_point_to_other_enu_point(point, &self.frame)
+    }
+
+    /// Gets the current time in seconds.
+    #[inline]
+    pub const fn time_s(&self) -> f64 {
+        self.time_s
+    }
+
+    /// Gets the azimuth in degrees (clockwise from North) of the geodesic at the current origin.
+    #[inline]
+    pub const fn azimuth_deg(&self) -> f64 {
+        self.azimuth_deg
+    }
+
+    /// Gets the ground speed in meters per second of the origin.
+    #[inline]
+    pub const fn ground_speed_mps(&self) -> f64 {
+        self.ground_speed_mps
+    }
+
+    /// Gets the Local Cartesian reference frame at the current time.
+    #[inline]
+    pub const fn local_cartesian(&self) -> &LocalCartesian {
+        &self.frame
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(value: f64, expected: f64, abs_tol: f64) {
+        assert!(
+            (value - expected).abs() <= abs_tol,
+            "value = {value}, expected = {expected}"
+        );
+    }
+
+    #[test]
+    fn origin_follows_the_geodesic() {
+        let start = GeographicPoint::from_degrees(5.93, 43.12, 0.0);
+        let mut moving = MovingLocalCartesian::new(&start, &Ellipsoid::WGS84, 30.0, 7000.0);
+        assert_eq!(moving.local_cartesian().origin_as_geographic_point(), &start);
+
+        let previous_frame = moving.set_time_s(60.0);
+        assert_eq!(&previous_frame, &LocalCartesian::from_geographic_point(&start));
+        let (expected, azimuth_deg) = Ellipsoid::WGS84.geodesic_direct(&start, 30.0, 420e3);
+        assert_eq!(moving.local_cartesian().origin_as_geographic_point(), &expected);
+        assert_close(moving.azimuth_deg(), azimuth_deg, 0.0);
+        // Back to time 0 restores the starting frame
+        moving.set_time_s(0.0);
+        assert_close(
+            moving.local_cartesian().origin_as_cartesian_ecef_point()
+                .distance(Ellipsoid::WGS84.to_cartesian_ecef_point(&start)),
+            0.0,
+            1e-6
+        );
+    }
+
+    #[test]
+    fn rebased_points_stay_georeferenced() {
+        let start = GeographicPoint::from_degrees(-70.0, -20.0, 0.0);
+        let mut moving = MovingLocalCartesian::new(&start, &Ellipsoid::WGS84, 180.0, 100.0);
+        let point = DVec3::new(2000.0, -1000.0, 500.0);
+        let gp = moving.local_cartesian().transform_from_enu_point_to_geographic_point(&point);
+        // The same geographic point, rebased into the moved frame
+        let previous_frame = moving.set_time_s(30.0);
+        let rebased = previous_frame.transform_from_enu_point_to_other_enu_point(&point, moving.local_cartesian());
+        let gp_rebased = moving.local_cartesian().transform_from_enu_point_to_geographic_point(&rebased);
+        assert_close(gp_rebased.lon_deg(), gp.lon_deg(), 1e-11);
+        assert_close(gp_rebased.lat_deg(), gp.lat_deg(), 1e-11);
+        assert_close(gp_rebased.height_m(), gp.height_m(), 1e-6);
+        // Moving South by 3 km shifts the point North in the new frame
+        assert_close(rebased.y, point.y + 3000.0, 1.0);
+        assert_close(
+            moving.transform_from_enu_point_at_time(&point, 0.0).distance(rebased),
+            0.0,
+            1e-6
+        );
+    }
+}
@@ -0,0 +1,306 @@
+use crate::coordinates::{Ellipsoid, GeographicPoint};
+
+/// UTM scale factor on the central meridian
+const UTM_SCALE_FACTOR: f64 = 0.9996;
+/// UTM false easting in meters
+const UTM_FALSE_EASTING_M: f64 = 500_000.0;
+/// UTM false northing in meters (southern hemisphere only)
+const UTM_FALSE_NORTHING_M: f64 = 10_000_000.0;
+/// Latitude limits in degrees of the UTM grid (polar areas use UPS instead)
+const UTM_MIN_LAT_DEG: f64 = -80.0;
+const UTM_MAX_LAT_DEG: f64 = 84.0;
+
+/// A point expressed in the [Universal Transverse Mercator] (UTM) projection.
+///
+/// The projection uses the Krüger series to 6th order in the third flattening
+/// `n`[^note], which is accurate to a few nanometers within the UTM zones.
+///
+/// [Universal Transverse Mercator]: https://en.wikipedia.org/wiki/Universal_Transverse_Mercator_coordinate_system
+/// [^note]: Karney, C. F. F., *Transverse Mercator with an accuracy of a few nanometers*.
+/// Journal of Geodesy 85, 475–485 (2011). <https://doi.org/10.1007/s00190-011-0445-3>
+#[derive(Debug, Clone, PartialEq)]
+pub struct UtmPoint {
+    zone: u8,
+    north: bool,
+    easting_m: f64,
+    northing_m: f64,
+}
+
+impl UtmPoint {
+    /// Creates a new [`UtmPoint`] from its zone (1 - 60), hemisphere, easting and northing in meters.
+    pub fn new(zone: u8, north: bool, easting_m: f64, northing_m: f64) -> Result<Self, String> {
+        if !(1..=60).contains(&zone) {
+            return Err(format!("invalid UTM zone {zone} (expected 1 - 60)"));
+        }
+        Ok(Self { zone, north, easting_m, northing_m })
+    }
+
+    /// Projects a [`GeographicPoint`] on the given Ellipsoid into its own UTM zone,
+    /// including the Norway / Svalbard zone exceptions.
+    ///
+    /// The height of the point is ignored. Latitudes outside [-80°, 84°] are rejected
+    /// since the polar areas are covered by the UPS projection.
+    pub fn from_geographic_point(gp: &GeographicPoint, ellipsoid: &Ellipsoid) -> Result<Self, String> {
+        let lat_deg = gp.lat_deg();
+        let lon_deg = wrap_longitude_deg(gp.lon_deg());
+        if !(UTM_MIN_LAT_DEG..=UTM_MAX_LAT_DEG).contains(&lat_deg) {
+            return Err(format!("latitude {lat_deg:.6}° is outside the UTM limits (-80° - 84°)"));
+        }
+        let zone = Self::zone_of(lon_deg, lat_deg);
+        Self::from_geographic_point_in_zone(gp, ellipsoid, zone)
+    }
+
+    /// Projects a [`GeographicPoint`] on the given Ellipsoid into a forced UTM zone.
+    pub fn from_geographic_point_in_zone(
+        gp: &GeographicPoint,
+        ellipsoid: &Ellipsoid,
+        zone: u8,
+    ) -> Result<Self, String> {
+        if !(1..=60).contains(&zone) {
+            return Err(format!("invalid UTM zone {zone} (expected 1 - 60)"));
+        }
+        let series = KruegerSeries::new(ellipsoid);
+        let lambda = (wrap_longitude_deg(gp.lon_deg()) - central_meridian_deg(zone)).to_radians();
+        let phi = gp.lat_rad();
+        let e = ellipsoid.eccentricity();
+
+        // Conformal latitude (as its tangent)
+        let tau = phi.tan();
+        let sigma = (e * (e * tau / tau.hypot(1.0)).atanh()).sinh();
+        let tau_p = tau * sigma.hypot(1.0) - sigma * tau.hypot(1.0);
+        let (slam, clam) = lambda.sin_cos();
+        let xi_p = tau_p.atan2(clam);
+        let eta_p = (slam / tau_p.hypot(clam)).asinh();
+
+        let (mut xi, mut eta) = (xi_p, eta_p);
+        for (j, alpha) in series.alpha.iter().enumerate() {
+            let k = 2.0 * (j + 1) as f64;
+            xi += alpha * (k * xi_p).sin() * (k * eta_p).cosh();
+            eta += alpha * (k * xi_p).cos() * (k * eta_p).sinh();
+        }
+
+        let easting_m = UTM_SCALE_FACTOR * series.a * eta + UTM_FALSE_EASTING_M;
+        let mut northing_m = UTM_SCALE_FACTOR * series.a * xi;
+        let north = phi >= 0.0;
+        if !north {
+            northing_m += UTM_FALSE_NORTHING_M;
+        }
+        Ok(Self { zone, north, easting_m, northing_m })
+    }
+
+    /// Transforms this [`UtmPoint`] back to a [`GeographicPoint`] (at zero height) on the given Ellipsoid.
+    pub fn to_geographic_point(&self, ellipsoid: &Ellipsoid) -> GeographicPoint {
+        let series = KruegerSeries::new(ellipsoid);
+        let e = ellipsoid.eccentricity();
+        let e2 = ellipsoid.eccentricity_squared();
+        let y = if self.north { self.northing_m } else { self.northing_m - UTM_FALSE_NORTHING_M };
+        let xi = y / (UTM_SCALE_FACTOR * series.a);
+        let eta = (self.easting_m - UTM_FALSE_EASTING_M) / (UTM_SCALE_FACTOR * series.a);
+
+        let (mut xi_p, mut eta_p) = (xi, eta);
+        for (j, beta) in series.beta.iter().enumerate() {
+            let k = 2.0 * (j + 1) as f64;
+            xi_p -= beta * (k * xi).sin() * (k * eta).cosh();
+            eta_p -= beta * (k * xi).cos() * (k * eta).sinh();
+        }
+
+        let sinh_eta_p = eta_p.sinh();
+        let (sxi_p, cxi_p) = xi_p.sin_cos();
+        let tau_p = sxi_p / sinh_eta_p.hypot(cxi_p);
+        let lambda = sinh_eta_p.atan2(cxi_p);
+
+        // Newton-Raphson iterations from the conformal latitude to the geodetic one
+        let mut tau = tau_p;
+        for _ in 0..10 {
+            let sigma = (e * (e * tau / tau.hypot(1.0)).atanh()).sinh();
+            let tau_i = tau * sigma.hypot(1.0) - sigma * tau.hypot(1.0);
+            let delta = (tau_p - tau_i) / tau_i.hypot(1.0)
+                * (1.0 + (1.0 - e2) * tau * tau)
+                / ((1.0 - e2) * tau.hypot(1.0));
+            tau += delta;
+            if delta.abs() < 1e-12 {
+                break;
+            }
+        }
+
+        GeographicPoint::from_degrees(
+            wrap_longitude_deg(lambda.to_degrees() + central_meridian_deg(self.zone)),
+            tau.atan().to_degrees(),
+            0.0,
+        )
+    }
+
+    /// Gets the UTM zone number (1 - 60).
+    #[inline]
+    pub const fn zone(&self) -> u8 {
+        self.zone
+    }
+
+    /// Returns `true` for a point of the northern hemisphere.
+    #[inline]
+    pub const fn is_north(&self) -> bool {
+        self.north
+    }
+
+    /// Gets the easting in meters (including the 500 km false easting).
+    #[inline]
+    pub const fn easting_m(&self) -> f64 {
+        self.easting_m
+    }
+
+    /// Gets the northing in meters (including the 10000 km false northing in the southern hemisphere).
+    #[inline]
+    pub const fn northing_m(&self) -> f64 {
+        self.northing_m
+    }
+
+    /// Gets the UTM zone number of the given longitude/latitude in degrees,
+    /// including the Norway (32V) and Svalbard (31X - 37X) exceptions.
+    pub fn zone_of(lon_deg: f64, lat_deg: f64) -> u8 {
+        let lon_deg = wrap_longitude_deg(lon_deg);
+        let mut zone = (((lon_deg + 180.0) / 6.0).floor() as i32 + 1).clamp(1, 60) as u8;
+        if (56.0..64.0).contains(&lat_deg) && (3.0..12.0).contains(&lon_deg) {
+            zone = 32; // Norway
+        }
+        if (72.0..=84.0).contains(&lat_deg) && lon_deg >= 0.0 {
+            // Svalbard: zones 32, 34 and 36 are not used
+            zone = if lon_deg < 9.0 {
+                31
+            } else if lon_deg < 21.0 {
+                33
+            } else if lon_deg < 33.0 {
+                35
+            } else if lon_deg < 42.0 {
+                37
+            } else {
+                zone
+            };
+        }
+        zone
+    }
+}
+
+impl std::fmt::Display for UtmPoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {} {:.0} {:.0}",
+            self.zone,
+            if self.north { "N" } else { "S" },
+            self.easting_m,
+            self.northing_m
+        )
+    }
+}
+
+/// Krüger series coefficients of an Ellipsoid (6th order in the third flattening).
+struct KruegerSeries {
+    a: f64,          // rectifying radius in meters
+    alpha: [f64; 6], // forward (geographic -> UTM) coefficients
+    beta: [f64; 6],  // inverse (UTM -> geographic) coefficients
+}
+
+impl KruegerSeries {
+    fn new(ellipsoid: &Ellipsoid) -> Self {
+        let f = ellipsoid.first_flattening();
+        let n = f / (2.0 - f); // third flattening
+        let (n2, n3) = (n * n, n * n * n);
+        let (n4, n5, n6) = (n3 * n, n3 * n2, n3 * n3);
+        Self {
+            a: ellipsoid.equatorial_radius_m() / (1.0 + n) * (1.0 + n2 / 4.0 + n4 / 64.0 + n6 / 256.0),
+            alpha: [
+                n / 2.0 - 2.0 * n2 / 3.0 + 5.0 * n3 / 16.0 + 41.0 * n4 / 180.0 - 127.0 * n5 / 288.0 + 7891.0 * n6 / 37800.0,
+                13.0 * n2 / 48.0 - 3.0 * n3 / 5.0 + 557.0 * n4 / 1440.0 + 281.0 * n5 / 630.0 - 1983433.0 * n6 / 1935360.0,
+                61.0 * n3 / 240.0 - 103.0 * n4 / 140.0 + 15061.0 * n5 / 26880.0 + 167603.0 * n6 / 181440.0,
+                49561.0 * n4 / 161280.0 - 179.0 * n5 / 168.0 + 6601661.0 * n6 / 7257600.0,
+                34729.0 * n5 / 80640.0 - 3418889.0 * n6 / 1995840.0,
+                212378941.0 * n6 / 319334400.0,
+            ],
+            beta: [
+                n / 2.0 - 2.0 * n2 / 3.0 + 37.0 * n3 / 96.0 - n4 / 360.0 - 81.0 * n5 / 512.0 + 96199.0 * n6 / 604800.0,
+                n2 / 48.0 + n3 / 15.0 - 437.0 * n4 / 1440.0 + 46.0 * n5 / 105.0 - 1118711.0 * n6 / 3870720.0,
+                17.0 * n3 / 480.0 - 37.0 * n4 / 840.0 - 209.0 * n5 / 4480.0 + 5569.0 * n6 / 90720.0,
+                4397.0 * n4 / 161280.0 - 11.0 * n5 / 504.0 - 830251.0 * n6 / 7257600.0,
+                4583.0 * n5 / 161280.0 - 108847.0 * n6 / 3991680.0,
+                20648693.0 * n6 / 638668800.0,
+            ],
+        }
+    }
+}
+
+/// Longitude in degrees of the central meridian of the given UTM zone.
+#[inline]
+fn central_meridian_deg(zone: u8) -> f64 {
+    6.0 * zone as f64 - 183.0
+}
+
+/// Wraps a longitude in degrees into [-180°, 180°).
+#[inline]
+fn wrap_longitude_deg(lon_deg: f64) -> f64 {
+    (lon_deg + 180.0).rem_euclid(360.0) - 180.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(value: f64, expected: f64, abs_tol: f64) {
+        assert!(
+            (value - expected).abs() <= abs_tol,
+            "value = {value}, expected = {expected}"
+        );
+    }
+
+    #[test]
+    fn central_meridian_on_equator() {
+        let utm = UtmPoint::from_geographic_point(
+            &GeographicPoint::from_degrees(3.0, 0.0, 0.0),
+            &Ellipsoid::WGS84,
+        ).unwrap();
+        assert_eq!(utm.zone(), 31);
+        assert!(utm.is_north());
+        assert_close(utm.easting_m(), 500_000.0, 1e-6);
+        assert_close(utm.northing_m(), 0.0, 1e-6);
+    }
+
+    #[test]
+    fn reference_point_eiffel_tower() {
+        // Published reference value (Veness, geodesy library test suite)
+        let utm = UtmPoint::from_geographic_point(
+            &GeographicPoint::from_degrees(2.2945, 48.8582, 0.0),
+            &Ellipsoid::WGS84,
+        ).unwrap();
+        assert_eq!(utm.zone(), 31);
+        assert_close(utm.easting_m(), 448_251.795, 1e-3);
+        assert_close(utm.northing_m(), 5_411_932.678, 1e-3);
+    }
+
+    #[test]
+    fn roundtrip_both_hemispheres() {
+        for &(lon, lat) in [
+            (5.93, 43.12),
+            (-120.5, -33.87),
+            (179.9, 83.5),
+            (-0.1, -79.9),
+            (8.0, 60.0), // Norway exception (zone 32)
+        ].iter() {
+            let gp = GeographicPoint::from_degrees(lon, lat, 0.0);
+            let utm = UtmPoint::from_geographic_point(&gp, &Ellipsoid::WGS84).unwrap();
+            let back = utm.to_geographic_point(&Ellipsoid::WGS84);
+            assert_close(back.lon_deg(), lon, 1e-9);
+            assert_close(back.lat_deg(), lat, 1e-9);
+        }
+    }
+
+    #[test]
+    fn zone_exceptions_and_polar_rejection() {
+        assert_eq!(UtmPoint::zone_of(5.0, 60.0), 32); // Norway
+        assert_eq!(UtmPoint::zone_of(5.0, 50.0), 31);
+        assert_eq!(UtmPoint::zone_of(10.0, 78.0), 33); // Svalbard
+        assert_eq!(UtmPoint::zone_of(180.0, 0.0), 1); // Wraps to -180°
+        assert!(UtmPoint::from_geographic_point(
+            &GeographicPoint::from_degrees(0.0, 85.0, 0.0),
+            &Ellipsoid::WGS84
+        ).is_err());
+    }
+}
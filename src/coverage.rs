@@ -0,0 +1,314 @@
+//! Ground coverage accumulation.
+//!
+//! A square raster centered on the scene center counts, for each ground cell,
+//! how many samples it was covered by the intersection of a set of polygons
+//! (the Tx and Rx antenna beam footprints). Accumulated along the timeline,
+//! this is the mission coverage map, rendered as a heat layer over the ground.
+
+/// Heat colormap stops (count over max count, (R, G, B)): dark red for the
+/// cells seen once, up to pale yellow for the most covered ones.
+const HEAT_COLORMAP: [(f64, (u8, u8, u8)); 4] = [
+    (0.0, (128, 0, 38)),
+    (0.35, (227, 26, 28)),
+    (0.7, (253, 141, 60)),
+    (1.0, (255, 237, 160)),
+];
+/// Opacity of the covered cells in the heat layer.
+const HEAT_ALPHA: u8 = 180;
+
+/// Coverage counts over a `size` × `size` square raster of side
+/// `2 * half_extent_m`, centered on the origin.
+///
+/// Polygon vertices are `(a, b)` ground coordinates in meters, `a` along the
+/// raster columns and `b` along its rows (both increasing with the index).
+#[derive(Debug, Clone, PartialEq)]
+pub struct CoverageRaster {
+    half_extent_m: f64,
+    size: usize,
+    counts: Vec<u32>,
+    samples: u32, // Number of accumulated samples
+}
+
+impl CoverageRaster {
+    /// Creates an empty coverage raster.
+    pub fn new(half_extent_m: f64, size: usize) -> Self {
+        let size = size.max(1);
+        Self {
+            half_extent_m,
+            size,
+            counts: vec![0; size * size],
+            samples: 0,
+        }
+    }
+
+    /// Resets all counts to zero.
+    pub fn clear(&mut self) {
+        self.counts.fill(0);
+        self.samples = 0;
+    }
+
+    /// Adds one sample: every cell whose center lies inside *all* of `polygons`
+    /// (even-odd rule) has its count incremented. Returns the number of cells
+    /// covered by this sample.
+    pub fn accumulate(&mut self, polygons: &[&[(f64, f64)]]) -> usize {
+        if polygons.is_empty() {
+            self.samples += 1;
+            return 0;
+        }
+        let mask = self.intersection_mask(polygons);
+        self.add_sample(mask)
+    }
+
+    /// Adds one sample like [`Self::accumulate`], the cells having also to lie
+    /// inside at least one of `any_of` (e.g. the simultaneous beams of a
+    /// receiver).
+    pub fn accumulate_any_of(&mut self, polygons: &[&[(f64, f64)]], any_of: &[&[(f64, f64)]]) -> usize {
+        let mut mask = self.intersection_mask(polygons);
+        let mut union_mask = vec![false; self.counts.len()];
+        for polygon in any_of {
+            let polygon_mask = self.polygon_mask(polygon);
+            union_mask.iter_mut().zip(polygon_mask).for_each(|(inside, polygon_inside)| *inside |= polygon_inside);
+        }
+        mask.iter_mut().zip(union_mask).for_each(|(inside, union_inside)| *inside &= union_inside);
+        self.add_sample(mask)
+    }
+
+    /// Gets the `(a, b)` centers of the cells inside all of `polygons`, without
+    /// accumulating them: the ground grid of the quantities sampled over the
+    /// intersection.
+    pub fn cell_centers_inside(&self, polygons: &[&[(f64, f64)]]) -> Vec<(f64, f64)> {
+        self.intersection_mask(polygons).into_iter()
+            .enumerate()
+            .filter(|(_, inside)| *inside)
+            .map(|(i, _)| (self.cell_center_m(i % self.size), self.cell_center_m(i / self.size)))
+            .collect()
+    }
+
+    /// Gets the cells inside all of `polygons` (all of them when empty).
+    fn intersection_mask(&self, polygons: &[&[(f64, f64)]]) -> Vec<bool> {
+        let mut mask = vec![true; self.counts.len()];
+        for polygon in polygons {
+            let polygon_mask = self.polygon_mask(polygon);
+            mask.iter_mut().zip(polygon_mask).for_each(|(inside, polygon_inside)| *inside &= polygon_inside);
+        }
+        mask
+    }
+
+    /// Increments the counts of the cells of `mask`, returning their number.
+    fn add_sample(&mut self, mask: Vec<bool>) -> usize {
+        self.samples += 1;
+        let mut covered = 0;
+        for (count, _) in self.counts.iter_mut().zip(mask).filter(|(_, inside)| *inside) {
+            *count += 1;
+            covered += 1;
+        }
+        covered
+    }
+
+    /// Scanline fill of one polygon at the cell centers.
+    fn polygon_mask(&self, polygon: &[(f64, f64)]) -> Vec<bool> {
+        let mut mask = vec![false; self.counts.len()];
+        if polygon.len() < 3 {
+            return mask;
+        }
+        let cell_m = self.cell_size_m();
+        let mut crossings = Vec::new();
+        for row in 0..self.size {
+            let b = self.cell_center_m(row);
+            crossings.clear();
+            for (i, &(a0, b0)) in polygon.iter().enumerate() {
+                let (a1, b1) = polygon[(i + 1) % polygon.len()];
+                // Half-open rule so a vertex on the scanline is counted once
+                if (b0 <= b) != (b1 <= b) {
+                    crossings.push(a0 + (b - b0) / (b1 - b0) * (a1 - a0));
+                }
+            }
+            crossings.sort_by(f64::total_cmp);
+            for span in crossings.chunks_exact(2) {
+                // Cells whose center lies within the span
+                let first = ((span[0] + self.half_extent_m) / cell_m - 0.5).ceil().max(0.0) as usize;
+                let last = ((span[1] + self.half_extent_m) / cell_m - 0.5).floor();
+                if last < 0.0 {
+                    continue;
+                }
+                let last = (last as usize).min(self.size - 1);
+                if first <= last {
+                    mask[row * self.size + first..=row * self.size + last].fill(true);
+                }
+            }
+        }
+        mask
+    }
+
+    /// Gets the ground coordinate in meters of the center of the cells at `index`
+    /// (column or row).
+    #[inline]
+    pub fn cell_center_m(&self, index: usize) -> f64 {
+        -self.half_extent_m + (index as f64 + 0.5) * self.cell_size_m()
+    }
+
+    /// Gets the side of a cell in meters.
+    #[inline]
+    pub fn cell_size_m(&self) -> f64 {
+        2.0 * self.half_extent_m / self.size as f64
+    }
+
+    /// Gets the half side of the raster in meters.
+    #[inline]
+    pub const fn half_extent_m(&self) -> f64 {
+        self.half_extent_m
+    }
+
+    /// Gets the number of cells per side.
+    #[inline]
+    pub const fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Gets the coverage counts, row-major.
+    #[inline]
+    pub fn counts(&self) -> &[u32] {
+        &self.counts
+    }
+
+    /// Gets the number of accumulated samples.
+    #[inline]
+    pub const fn samples(&self) -> u32 {
+        self.samples
+    }
+
+    /// Gets the highest coverage count.
+    pub fn max_count(&self) -> u32 {
+        self.counts.iter().copied().max().unwrap_or(0)
+    }
+
+    /// Gets the ground area in square meters covered at least once.
+    pub fn covered_area_m2(&self) -> f64 {
+        let cell_m = self.cell_size_m();
+        self.counts.iter().filter(|&&count| count > 0).count() as f64 * cell_m * cell_m
+    }
+
+    /// Gets the mean coverage count over the covered cells.
+    pub fn mean_count(&self) -> f64 {
+        let (sum, covered) = self.counts.iter()
+            .filter(|&&count| count > 0)
+            .fold((0u64, 0u64), |(sum, covered), &count| (sum + count as u64, covered + 1));
+        if covered > 0 { sum as f64 / covered as f64 } else { 0.0 }
+    }
+
+    /// Renders the counts as an RGBA8 heat layer, row-major: uncovered cells are
+    /// transparent, covered ones follow [`HEAT_COLORMAP`] up to the highest count.
+    pub fn to_rgba8(&self) -> Vec<u8> {
+        let max_count = self.max_count().max(1) as f64;
+        let mut rgba = vec![0u8; 4 * self.counts.len()];
+        for (pixel, &count) in rgba.chunks_exact_mut(4).zip(self.counts.iter()) {
+            if count == 0 {
+                continue;
+            }
+            let (r, g, b) = heat_color(if max_count > 1.0 { (count - 1) as f64 / (max_count - 1.0) } else { 1.0 });
+            pixel.copy_from_slice(&[r, g, b, HEAT_ALPHA]);
+        }
+        rgba
+    }
+}
+
+/// Linear interpolation of [`HEAT_COLORMAP`] at `t` in [0, 1].
+fn heat_color(t: f64) -> (u8, u8, u8) {
+    let t = t.clamp(0.0, 1.0);
+    let upper = HEAT_COLORMAP.iter().position(|&(stop, _)| stop >= t).unwrap_or(HEAT_COLORMAP.len() - 1).max(1);
+    let ((t0, c0), (t1, c1)) = (HEAT_COLORMAP[upper - 1], HEAT_COLORMAP[upper]);
+    let w = (t - t0) / (t1 - t0);
+    let lerp = |a: u8, b: u8| (a as f64 + w * (b as f64 - a as f64)).round() as u8;
+    (lerp(c0.0, c1.0), lerp(c0.1, c1.1), lerp(c0.2, c1.2))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(center: (f64, f64), half_side: f64) -> Vec<(f64, f64)> {
+        vec![
+            (center.0 - half_side, center.1 - half_side),
+            (center.0 + half_side, center.1 - half_side),
+            (center.0 + half_side, center.1 + half_side),
+            (center.0 - half_side, center.1 + half_side),
+        ]
+    }
+
+    #[test]
+    fn accumulates_the_intersection_of_polygons() {
+        // 1 m cells over ±50 m
+        let mut raster = CoverageRaster::new(50.0, 100);
+        let tx = square((0.0, 0.0), 20.0);
+        let rx = square((10.0, 0.0), 20.0);
+        // Intersection: a ∈ [-10, 20], b ∈ [-20, 20] → 30 × 40 cells
+        assert_eq!(raster.accumulate(&[&tx, &rx]), 1200);
+        assert_eq!(raster.covered_area_m2(), 1200.0);
+        // Shifted second sample: a ∈ [0, 30] overlaps the first on 20 × 40 cells
+        let tx = square((10.0, 0.0), 20.0);
+        let rx = square((20.0, 0.0), 20.0);
+        raster.accumulate(&[&tx, &rx]);
+        assert_eq!(raster.samples(), 2);
+        assert_eq!(raster.max_count(), 2);
+        assert_eq!(raster.covered_area_m2(), 1600.0);
+        assert_eq!(raster.mean_count(), 1.5); // 800 cells twice, 800 cells once
+        // Cell (a = 5.5, b = 0.5) was covered twice, (a = -5.5, b = 0.5) once
+        assert_eq!(raster.counts()[50 * 100 + 55], 2);
+        assert_eq!(raster.counts()[50 * 100 + 44], 1);
+
+        raster.clear();
+        assert_eq!((raster.samples(), raster.max_count()), (0, 0));
+    }
+
+    #[test]
+    fn accumulates_within_any_of_the_beams() {
+        let mut raster = CoverageRaster::new(50.0, 100);
+        let tx = square((0.0, 0.0), 30.0);
+        // Two overlapping beams: a ∈ [-30, 10] and a ∈ [0, 40], b ∈ [-10, 10]
+        let beam_1 = [(-30.0, -10.0), (10.0, -10.0), (10.0, 10.0), (-30.0, 10.0)];
+        let beam_2 = [(0.0, -10.0), (40.0, -10.0), (40.0, 10.0), (0.0, 10.0)];
+        // Union within the Tx: a ∈ [-30, 30] → 60 × 20 cells, counted once
+        assert_eq!(raster.accumulate_any_of(&[&tx], &[&beam_1, &beam_2]), 1200);
+        assert_eq!(raster.max_count(), 1);
+        assert_eq!(raster.accumulate_any_of(&[&tx], &[]), 0);
+        assert_eq!(raster.samples(), 2);
+    }
+
+    #[test]
+    fn cell_centers_of_the_intersection() {
+        let raster = CoverageRaster::new(10.0, 10);
+        let tx = square((0.0, 0.0), 4.0);
+        let rx = square((4.0, 0.0), 4.0);
+        // 2 m cells: a ∈ [0, 4] → centers 1, 3; b ∈ [-4, 4] → centers -3, -1, 1, 3
+        let centers = raster.cell_centers_inside(&[&tx, &rx]);
+        assert_eq!(centers.len(), 8);
+        assert_eq!(centers[0], (1.0, -3.0));
+        assert_eq!(centers[7], (3.0, 3.0));
+        // Nothing is accumulated
+        assert_eq!(raster.samples(), 0);
+        assert!(raster.cell_centers_inside(&[&square((100.0, 0.0), 4.0)]).is_empty());
+    }
+
+    #[test]
+    fn polygons_outside_the_raster_are_clipped() {
+        let mut raster = CoverageRaster::new(10.0, 20);
+        let polygon = square((15.0, 15.0), 10.0);
+        // Only a ∈ [5, 10], b ∈ [5, 10] is inside the raster
+        assert_eq!(raster.accumulate(&[&polygon]), 25);
+        let far = square((100.0, 100.0), 10.0);
+        assert_eq!(raster.accumulate(&[&far]), 0);
+    }
+
+    #[test]
+    fn heat_layer_is_transparent_where_uncovered() {
+        let mut raster = CoverageRaster::new(10.0, 20);
+        raster.accumulate(&[&square((0.0, 0.0), 5.0)]);
+        let rgba = raster.to_rgba8();
+        assert_eq!(rgba.len(), 4 * 400);
+        assert_eq!(rgba[3], 0); // Corner cell
+        let center = 4 * (10 * 20 + 10);
+        assert_eq!(rgba[center + 3], HEAT_ALPHA);
+        assert_eq!(heat_color(0.0), HEAT_COLORMAP[0].1);
+        assert_eq!(heat_color(1.0), HEAT_COLORMAP[3].1);
+    }
+}
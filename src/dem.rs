@@ -0,0 +1,197 @@
+//! Digital Elevation Models.
+//!
+//! A DEM is a regular grid of heights in geographic coordinates, read from an
+//! SRTM `.hgt` tile or an ESRI ASCII grid (`.asc`, in degrees). Heights are
+//! kept as read (above the geoid for SRTM): they are only used relative to the
+//! height at the scene center, which cancels the geoid offset over a scene.
+
+/// SRTM void value.
+const HGT_VOID: i16 = -32768;
+
+/// Heights on a regular longitude / latitude grid, row-major from the
+/// north-west sample, rows going south.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dem {
+    /// Longitude in degrees of the first column samples
+    west_lon_deg: f64,
+    /// Latitude in degrees of the first row samples
+    north_lat_deg: f64,
+    lon_spacing_deg: f64,
+    lat_spacing_deg: f64,
+    num_cols: usize,
+    num_rows: usize,
+    /// Heights in m, NaN for voids
+    heights_m: Vec<f32>,
+}
+
+impl Dem {
+    /// Reads a DEM from a file, its format being given by the `file_name` extension.
+    pub fn from_file(file_name: &str, bytes: &[u8]) -> Result<Self, String> {
+        let extension = file_name.rsplit('.').next().unwrap_or_default().to_ascii_lowercase();
+        match extension.as_str() {
+            "hgt" => Self::from_hgt(file_name, bytes),
+            "asc" => std::str::from_utf8(bytes)
+                .map_err(|_| format!("{file_name} is not a text file"))
+                .and_then(Self::from_esri_ascii),
+            _ => Err(format!("Unsupported DEM format \"{extension}\" (expected .hgt or .asc)")),
+        }
+    }
+
+    /// Reads an SRTM tile: big-endian 16-bit heights over a square 1° tile
+    /// (1201 × 1201 at 3", 3601 × 3601 at 1"), its south-west corner being
+    /// given by the file name, e.g. `N45E006.hgt`.
+    pub fn from_hgt(file_name: &str, bytes: &[u8]) -> Result<Self, String> {
+        let size = match bytes.len() {
+            2_884_802 => 1201,
+            25_934_402 => 3601,
+            length => return Err(format!("{length} bytes is not an SRTM 3\" or 1\" tile size")),
+        };
+        let (south_lat_deg, west_lon_deg) = hgt_tile_corner(file_name)?;
+        let heights_m = bytes.chunks_exact(2)
+            .map(|pair| match i16::from_be_bytes([pair[0], pair[1]]) {
+                HGT_VOID => f32::NAN,
+                height => height as f32,
+            })
+            .collect();
+        let spacing_deg = 1.0 / (size - 1) as f64;
+        Ok(Self {
+            west_lon_deg,
+            north_lat_deg: south_lat_deg + 1.0,
+            lon_spacing_deg: spacing_deg,
+            lat_spacing_deg: spacing_deg,
+            num_cols: size,
+            num_rows: size,
+            heights_m,
+        })
+    }
+
+    /// Reads an ESRI ASCII grid in geographic coordinates (degrees), corner
+    /// (`xllcorner`) or center (`xllcenter`) registered.
+    pub fn from_esri_ascii(text: &str) -> Result<Self, String> {
+        let mut tokens = text.split_whitespace().peekable();
+        let (mut num_cols, mut num_rows, mut cell_size_deg) = (None, None, None);
+        let (mut x_ll, mut y_ll, mut is_corner) = (None, None, true);
+        let mut no_data = None;
+        // Header: "key value" pairs until the first number
+        while let Some(key) = tokens.peek().filter(|token| token.parse::<f64>().is_err()).map(|key| key.to_ascii_lowercase()) {
+            tokens.next();
+            let value = tokens.next()
+                .and_then(|value| value.parse::<f64>().ok())
+                .ok_or_else(|| format!("Missing or invalid value of \"{key}\""))?;
+            match key.as_str() {
+                "ncols" => num_cols = Some(value as usize),
+                "nrows" => num_rows = Some(value as usize),
+                "cellsize" => cell_size_deg = Some(value),
+                "xllcorner" => x_ll = Some(value),
+                "yllcorner" => y_ll = Some(value),
+                "xllcenter" => { x_ll = Some(value); is_corner = false; }
+                "yllcenter" => { y_ll = Some(value); is_corner = false; }
+                "nodata_value" => no_data = Some(value as f32),
+                _ => return Err(format!("Unknown ESRI ASCII grid header key \"{key}\"")),
+            }
+        }
+        let missing = |name: &str| format!("Missing \"{name}\" in the ESRI ASCII grid header");
+        let num_cols = num_cols.filter(|&n| n > 1).ok_or_else(|| missing("ncols"))?;
+        let num_rows = num_rows.filter(|&n| n > 1).ok_or_else(|| missing("nrows"))?;
+        let cell_size_deg = cell_size_deg.filter(|&size| size > 0.0).ok_or_else(|| missing("cellsize"))?;
+        let (x_ll, y_ll) = (x_ll.ok_or_else(|| missing("xllcorner"))?, y_ll.ok_or_else(|| missing("yllcorner"))?);
+        let heights_m = tokens
+            .map(|token| token.parse::<f32>()
+                .map(|height| if Some(height) == no_data { f32::NAN } else { height })
+                .map_err(|_| format!("Invalid height \"{token}\""))
+            )
+            .collect::<Result<Vec<_>, _>>()?;
+        if heights_m.len() != num_cols * num_rows {
+            return Err(format!("{} heights read, {} × {} expected", heights_m.len(), num_rows, num_cols));
+        }
+        let half_cell_deg = if is_corner { 0.5 * cell_size_deg } else { 0.0 };
+        Ok(Self {
+            west_lon_deg: x_ll + half_cell_deg,
+            north_lat_deg: y_ll + half_cell_deg + (num_rows - 1) as f64 * cell_size_deg,
+            lon_spacing_deg: cell_size_deg,
+            lat_spacing_deg: cell_size_deg,
+            num_cols,
+            num_rows,
+            heights_m,
+        })
+    }
+
+    /// Gets the number of (columns, rows).
+    #[inline]
+    pub const fn size(&self) -> (usize, usize) {
+        (self.num_cols, self.num_rows)
+    }
+
+    /// Gets the (west, south, east, north) bounds in degrees of the samples.
+    pub fn bounds_deg(&self) -> (f64, f64, f64, f64) {
+        (
+            self.west_lon_deg,
+            self.north_lat_deg - (self.num_rows - 1) as f64 * self.lat_spacing_deg,
+            self.west_lon_deg + (self.num_cols - 1) as f64 * self.lon_spacing_deg,
+            self.north_lat_deg,
+        )
+    }
+
+    /// Gets the bilinearly interpolated height in m at a geographic position,
+    /// `None` outside the DEM or next to a void.
+    pub fn height_m_at(&self, lon_deg: f64, lat_deg: f64) -> Option<f64> {
+        let x = (lon_deg - self.west_lon_deg) / self.lon_spacing_deg;
+        let y = (self.north_lat_deg - lat_deg) / self.lat_spacing_deg;
+        if !(0.0..=(self.num_cols - 1) as f64).contains(&x) || !(0.0..=(self.num_rows - 1) as f64).contains(&y) {
+            return None;
+        }
+        let (col, row) = ((x as usize).min(self.num_cols - 2), (y as usize).min(self.num_rows - 2));
+        let (wx, wy) = (x - col as f64, y - row as f64);
+        let height = |row: usize, col: usize| self.heights_m[row * self.num_cols + col] as f64;
+        let value = (1.0 - wy) * ((1.0 - wx) * height(row, col) + wx * height(row, col + 1))
+            + wy * ((1.0 - wx) * height(row + 1, col) + wx * height(row + 1, col + 1));
+        value.is_finite().then_some(value)
+    }
+}
+
+/// Gets the (latitude, longitude) in degrees of the south-west corner of an
+/// SRTM tile from its file name, e.g. `N45E006.hgt` or `s12w077.hgt`.
+fn hgt_tile_corner(file_name: &str) -> Result<(f64, f64), String> {
+    let stem = file_name.rsplit(['/', '\\']).next().unwrap_or(file_name);
+    let invalid = || format!("\"{file_name}\" is not an SRTM tile name (e.g. N45E006.hgt)");
+    let stem = stem.get(..7).ok_or_else(invalid)?.to_ascii_uppercase();
+    let lat_deg = stem[1..3].parse::<f64>().map_err(|_| invalid())?;
+    let lon_deg = stem[4..7].parse::<f64>().map_err(|_| invalid())?;
+    let lat_deg = match &stem[..1] { "N" => lat_deg, "S" => -lat_deg, _ => return Err(invalid()) };
+    let lon_deg = match &stem[3..4] { "E" => lon_deg, "W" => -lon_deg, _ => return Err(invalid()) };
+    Ok((lat_deg, lon_deg))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_esri_ascii_grids() {
+        let text = "ncols 3\nnrows 2\nxllcorner 6.0\nyllcorner 45.0\ncellsize 0.5\nNODATA_value -9999\n\
+                    10 20 30\n40 50 -9999\n";
+        let dem = Dem::from_esri_ascii(text).unwrap();
+        assert_eq!(dem.size(), (3, 2));
+        // Corner registered: the samples are at the cells centers
+        assert_eq!(dem.bounds_deg(), (6.25, 45.25, 7.25, 45.75));
+        assert_eq!(dem.height_m_at(6.25, 45.75), Some(10.0));
+        assert_eq!(dem.height_m_at(6.5, 45.5), Some(30.0)); // (10 + 20 + 40 + 50) / 4
+        assert_eq!(dem.height_m_at(7.0, 45.5), None); // Next to the void
+        assert_eq!(dem.height_m_at(8.0, 45.5), None);
+        assert!(Dem::from_esri_ascii("ncols 3\nnrows 2\n1 2 3").is_err());
+    }
+
+    #[test]
+    fn reads_srtm_tiles() {
+        let mut bytes = vec![0u8; 2 * 1201 * 1201];
+        bytes[..2].copy_from_slice(&1000i16.to_be_bytes()); // North-west sample
+        bytes[2..4].copy_from_slice(&HGT_VOID.to_be_bytes());
+        let dem = Dem::from_file("tiles/S12W077.hgt", &bytes).unwrap();
+        assert_eq!(dem.bounds_deg(), (-77.0, -12.0, -76.0, -11.0));
+        assert_eq!(dem.height_m_at(-77.0, -11.0), None); // Next to the void
+        let dem = Dem::from_hgt("N45E006.hgt", &bytes[..]).unwrap();
+        assert_eq!(dem.height_m_at(6.5, 45.5), Some(0.0));
+        assert!(Dem::from_file("N45E006.tif", &bytes).is_err());
+        assert!(Dem::from_hgt("tile.hgt", &bytes).is_err());
+    }
+}
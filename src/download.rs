@@ -1,4 +1,5 @@
-//! Saving a generated image from both the desktop and the web build.
+//! Saving a generated file (image, table) from both the desktop and the web build,
+//! and opening an input file (DEM) on the desktop or by dropping it on the window.
 //!
 //! Native builds ask for a destination with an in-app "save as" dialog; the web
 //! build hands the bytes straight to the browser as a download, since a wasm
@@ -7,11 +8,70 @@
 //!
 //! ```text
 //! if clicked            { state.request = Some(SaveRequest::new(name, bytes)); }
-//! if let Some(r) = ...  { if let Some(status) = r.update(ctx) { /* finished */ } }
+//! if let Some(status) = SaveRequest::drive(&mut state.request, ctx, toasts) { /* finished */ }
 //! ```
 
 use bevy_egui::egui;
 
+use crate::ui::Toasts;
+
+/// Type of a saved file: sets the "save as" dialog filter on native builds and
+/// the MIME type of the download on the web build.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SaveFileType {
+    Png,
+    Csv,
+    Npy,
+    Glb,
+    Svg,
+    Dxf,
+    Scenario,
+}
+
+impl SaveFileType {
+    /// Name of the file filter shown in the "save as" dialog.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn filter_name(self) -> &'static str {
+        match self {
+            SaveFileType::Png => "PNG image",
+            SaveFileType::Csv => "CSV table",
+            SaveFileType::Npy => "NumPy array",
+            SaveFileType::Glb => "glTF binary scene",
+            SaveFileType::Svg => "SVG drawing",
+            SaveFileType::Dxf => "DXF drawing",
+            SaveFileType::Scenario => "BSARGeom scenario",
+        }
+    }
+
+    /// File name extension (without the dot).
+    #[cfg(not(target_arch = "wasm32"))]
+    fn extension(self) -> &'static str {
+        match self {
+            SaveFileType::Png => "png",
+            SaveFileType::Csv => "csv",
+            SaveFileType::Npy => "npy",
+            SaveFileType::Glb => "glb",
+            SaveFileType::Svg => "svg",
+            SaveFileType::Dxf => "dxf",
+            SaveFileType::Scenario => crate::scenario::SCENARIO_EXTENSION,
+        }
+    }
+
+    /// MIME type of the browser download.
+    #[cfg(target_arch = "wasm32")]
+    fn mime_type(self) -> &'static str {
+        match self {
+            SaveFileType::Png => "image/png",
+            SaveFileType::Csv => "text/csv",
+            SaveFileType::Npy => "application/octet-stream",
+            SaveFileType::Glb => "model/gltf-binary",
+            SaveFileType::Svg => "image/svg+xml",
+            SaveFileType::Dxf => "image/vnd.dxf",
+            SaveFileType::Scenario => "text/plain",
+        }
+    }
+}
+
 /// A save operation in flight. [`SaveRequest::update`] returns `Some(status)`
 /// once it resolves (saved, cancelled or failed), and the caller drops it.
 pub struct SaveRequest {
@@ -26,11 +86,16 @@ pub struct SaveRequest {
 
 impl SaveRequest {
     /// Starts saving `bytes` under a suggested `file_name` (a PNG image).
-    #[cfg(not(target_arch = "wasm32"))]
     pub fn new(file_name: &str, bytes: Vec<u8>) -> Self {
+        Self::with_file_type(file_name, bytes, SaveFileType::Png)
+    }
+
+    /// Starts saving `bytes` of the given file type under a suggested `file_name`.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn with_file_type(file_name: &str, bytes: Vec<u8>, file_type: SaveFileType) -> Self {
         let mut dialog = egui_file_dialog::FileDialog::new()
-            .add_file_filter_extensions("PNG image", vec!["png"])
-            .default_file_filter("PNG image")
+            .add_file_filter_extensions(file_type.filter_name(), vec![file_type.extension()])
+            .default_file_filter(file_type.filter_name())
             .default_file_name(file_name)
             // Modal is `egui-file-dialog`'s own default; stated explicitly so the
             // behaviour is visible here and survives an upstream default change.
@@ -66,10 +131,10 @@ impl SaveRequest {
 
     /// Web build: wrap the bytes in a Blob and click a synthetic download link.
     #[cfg(target_arch = "wasm32")]
-    pub fn new(file_name: &str, bytes: Vec<u8>) -> Self {
+    pub fn with_file_type(file_name: &str, bytes: Vec<u8>, file_type: SaveFileType) -> Self {
         Self {
             status: Some(
-                download_in_browser(file_name, &bytes)
+                download_in_browser(file_name, &bytes, file_type.mime_type())
                     .unwrap_or_else(|error| format!("Save failed: {error}")),
             ),
         }
@@ -80,10 +145,91 @@ impl SaveRequest {
     pub fn update(&mut self, _ctx: &egui::Context) -> Option<String> {
         self.status.take()
     }
+
+    /// Drives the save in flight of a window, if any: on native its dialog is
+    /// a window of its own, so it must keep running even when the calling
+    /// window is collapsed or closed. Once resolved, its outcome is reported
+    /// in `toasts`, the request dropped and its status returned.
+    pub fn drive(request: &mut Option<Self>, ctx: &egui::Context, toasts: &mut Toasts) -> Option<String> {
+        let status = request.as_mut()?.update(ctx)?;
+        toasts.save_status(&status);
+        *request = None;
+        Some(status)
+    }
+}
+
+/// An open operation in flight. [`OpenRequest::update`] returns `Some(result)`
+/// once it resolves, with the picked file name and bytes, and the caller drops it.
+pub struct OpenRequest {
+    #[cfg(not(target_arch = "wasm32"))]
+    dialog: egui_file_dialog::FileDialog,
+}
+
+impl OpenRequest {
+    /// Starts picking a file among the given `extensions` (without the dot).
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn with_extensions(filter_name: &str, extensions: &[&str]) -> Self {
+        let mut dialog = egui_file_dialog::FileDialog::new()
+            .add_file_filter_extensions(filter_name, extensions.to_vec())
+            .default_file_filter(filter_name)
+            // Same modal, anchored dialog as SaveRequest
+            .as_modal(true)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO);
+        dialog.pick_file();
+        Self { dialog }
+    }
+
+    /// Draws the dialog and reads the picked file once the user is done.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn update(&mut self, ctx: &egui::Context) -> Option<Result<(String, Vec<u8>), String>> {
+        use egui_file_dialog::DialogState;
+
+        self.dialog.update(ctx);
+        match self.dialog.state() {
+            DialogState::Open => None,
+            DialogState::Picked(path) => Some(
+                std::fs::read(path)
+                    .map(|bytes| (path.display().to_string(), bytes))
+                    .map_err(|error| format!("Open failed: {error}"))
+            ),
+            _ => Some(Err("Open cancelled".to_string())),
+        }
+    }
+
+    /// Web build: a wasm page has no filesystem to browse, files are dropped instead.
+    #[cfg(target_arch = "wasm32")]
+    pub fn with_extensions(_filter_name: &str, _extensions: &[&str]) -> Self {
+        Self {}
+    }
+
+    /// Web build: resolves at once, pointing at drag and drop.
+    #[cfg(target_arch = "wasm32")]
+    pub fn update(&mut self, _ctx: &egui::Context) -> Option<Result<(String, Vec<u8>), String>> {
+        Some(Err("Drop the file on the window to open it".to_string()))
+    }
+}
+
+/// Gets the first file with one of `extensions` dropped on the window this
+/// frame, with its name and bytes (read from its path on native builds).
+pub fn dropped_file(ctx: &egui::Context, extensions: &[&str]) -> Option<Result<(String, Vec<u8>), String>> {
+    let has_extension = |file: &egui::DroppedFile| {
+        let name = file.path.as_ref().map_or_else(|| file.name.clone(), |path| path.display().to_string());
+        name.rsplit_once('.').is_some_and(|(_, extension)| extensions.iter().any(|accepted| extension.eq_ignore_ascii_case(accepted)))
+    };
+    let file = ctx.input(|input| input.raw.dropped_files.iter().find(|file| has_extension(file)).cloned())?;
+    if let Some(bytes) = file.bytes {
+        return Some(Ok((file.name, bytes.to_vec())));
+    }
+    let path = file.path?;
+    Some(
+        std::fs::read(&path)
+            .map(|bytes| (path.display().to_string(), bytes))
+            .map_err(|error| format!("Open failed: {error}"))
+    )
 }
 
 #[cfg(target_arch = "wasm32")]
-fn download_in_browser(file_name: &str, bytes: &[u8]) -> Result<String, String> {
+fn download_in_browser(file_name: &str, bytes: &[u8], mime_type: &str) -> Result<String, String> {
     use wasm_bindgen::JsCast as _;
 
     let to_error = |value: wasm_bindgen::JsValue| {
@@ -96,7 +242,7 @@ fn download_in_browser(file_name: &str, bytes: &[u8]) -> Result<String, String>
     let parts = js_sys::Array::new();
     parts.push(&array);
     let options = web_sys::BlobPropertyBag::new();
-    options.set_type("image/png");
+    options.set_type(mime_type);
     let blob =
         web_sys::Blob::new_with_u8_array_sequence_and_options(&parts, &options).map_err(to_error)?;
     let url = web_sys::Url::create_object_url_with_blob(&blob).map_err(to_error)?;
@@ -4,8 +4,11 @@ pub use antenna_beam::spawn_antenna_beam;
 mod antenna_beam_footprint;
 pub use antenna_beam_footprint::{
     AntennaBeamFootprintState,
+    FootprintModel,
     spawn_antenna_beam_footprint,
+    update_antenna_beam_footprint_from_state,
     update_antenna_beam_footprint_mesh_from_state,
+    antenna_beam_footprint_state_from_state,
     update_ground_angular_velocity,
     update_illumination_time,
     spawn_antenna_beam_footprint_elevation_line,
@@ -15,7 +18,7 @@ pub use antenna_beam_footprint::{
 };
 
 mod axes_helper;
-pub use axes_helper::spawn_axes_helper;
+pub use axes_helper::{spawn_axes_helper, AxesHelperPart};
 
 mod carrier;
 pub use carrier::{
@@ -23,7 +26,9 @@ pub use carrier::{
     Carrier, VelocityVector,
     AntennaBeamState, AntennaState, CarrierState,
     antenna_beam_transform_from_state,
+    antenna_state_pointing_to_center,
     antenna_transform_from_state,
+    carrier_state_at_antenna,
     carrier_transform_from_state, spawn_carrier,
     velocity_indicator_transform_from_state,
     update_velocity_vector
@@ -37,13 +42,16 @@ pub use iso_range_doppler_plane::{
     spawn_iso_range_doppler_plane,
     iso_range_doppler_plane_transform_from_state,
     refresh_iso_range_doppler_plane,
-    IsoRangeDopplerPlaneState
+    update_iso_contours,
+    enu_to_world,
+    ContourSpacing, IsoContours, IsoRangeDopplerPlaneState, LevelContours, PlaneWindow
 };
 
 mod iso_range_ellipsoid;
 pub use iso_range_ellipsoid::{
     spawn_iso_range_ellipsoid,
-    iso_range_ellipsoid_transform_from_state
+    iso_range_ellipsoid_transform_from_state,
+    beam_iso_range_curve,
 };
 
 mod lines;
@@ -1,4 +1,4 @@
-use std::f64::consts::TAU;
+use std::f64::consts::{PI, TAU};
 use bevy::{
     asset::RenderAssetUsages,
     math::{DQuat, DVec3},
@@ -8,19 +8,46 @@ use bevy::{
 
 
 use crate::{
-    constants::{ENU_TO_NED_F64, MAX_BORESIGHT_RANGE_M, TO_Y_UP_F64, BLUE_MATERIAL, GREEN_MATERIAL},
-    entities::{AntennaBeamState, AntennaState, CarrierState}
+    constants::{FOOTPRINT_MIN_LIFT_M, MAX_BORESIGHT_RANGE_M, TO_Y_UP_F64, BLUE_MATERIAL, GREEN_MATERIAL},
+    coordinates::{Ellipsoid, LocalCartesian},
+    entities::{AntennaBeamState, AntennaState, CarrierState},
+    incidence::local_incidence_deg,
+    squint::SquintConvention,
 };
 
 const ANTENNA_BEAM_FOOTPRINT_SIZE: usize = 2501; // Size of the antenna beam footprint mesh
-const ANTENNA_ELV_AZI_LINES_INDEX: usize = 625; // = (ANTENNA_BEAM_FOOTPRINT_SIZE - 1) / 4
 const STEP_THETA: f64 = TAU / (ANTENNA_BEAM_FOOTPRINT_SIZE - 1) as f64; // Step size for the antenna beam footprint mesh
+const ANALYTIC_FOOTPRINT_SIZE: usize = 257; // Size of the analytic elliptical footprint (divisible by 4 plus the closing point)
+
+/// Computation of the antenna beam footprint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FootprintModel {
+    /// Intersection of the half-power beam edge with the ground plane
+    #[default]
+    Numeric,
+    /// Ellipse centered on the boresight point, from the beam widths
+    /// projected on the ground with the incidence: exact for narrow beams,
+    /// faster for sweeps and low-power clients
+    AnalyticEllipse,
+}
+
+impl FootprintModel {
+    pub fn label(&self) -> &'static str {
+        match self {
+            FootprintModel::Numeric => "Numeric",
+            FootprintModel::AnalyticEllipse => "Analytic ellipse",
+        }
+    }
+}
 
+#[derive(Clone)]
 pub struct AntennaBeamFootprintState {
     pub points: Vec<DVec3>, // Antenna Footprint line coordinates in World frame (Y-up)
     pub range_center_m: f64, // Slant range from antenna to antenna beam footprint center in meters
     pub range_min_m: f64, // Minimum slant range from antenna to antenna beam footprint in meters
     pub range_max_m: f64, // Maximum slant range from antenna to antenna beam footprint in meters
+    pub index_range_min: usize, // Index in `points` of the minimum slant range point
+    pub index_range_max: usize, // Index in `points` of the maximum slant range point
     pub loc_incidence_center_deg: f64, // Local incidence angle at the antenna beam footprint center in degrees
     pub loc_incidence_min_deg: f64, // Local incidence angle at the minimum range point in degrees
     pub loc_incidence_max_deg: f64, // Local incidence angle at the maximum range point in degrees
@@ -28,9 +55,13 @@ pub struct AntennaBeamFootprintState {
     // pub ground_max_coord_m: f64, // Ground maximum coordinates of the antenna beam footprint in meters
     pub ground_max_extent_m: f64, // Ground maximum extent of the antenna beam footprint in meters (between scene center and 3d footpint)
     pub area_m2: f64, // half-power antenna beam footprint area in meters squared
-    pub antenna_squint_deg: f64, // Antenna squint angle in degrees
+    pub antenna_squint_deg: f64, // Antenna squint angle in degrees, with `squint_convention`
     pub illumination_time_s: f64, // Illumination time in seconds
     pub ground_angular_velocity_degps: f64, // Ground angular velocity in degrees per second
+    pub lift_m: f64, // Height of the drawn footprint and lines above the ground plane in meters (avoids z-fighting)
+    pub squint_convention: SquintConvention, // Reference and sign of the antenna squint angle
+    pub model: FootprintModel, // Computation of the footprint points, which sets their number
+    pub earth_frame: Option<LocalCartesian>, // Scene frame on its ellipsoid in Earth-curvature mode: swath and extent along its geodesics
 }
 
 impl Default for AntennaBeamFootprintState {
@@ -40,6 +71,8 @@ impl Default for AntennaBeamFootprintState {
             range_center_m: 0.0, // Default slant range from antenna to antenna beam footprint center
             range_min_m: 0.0, // Default minimum slant range
             range_max_m: 0.0, // Default maximum slant range
+            index_range_min: 0, // Default index of the minimum slant range point
+            index_range_max: 0, // Default index of the maximum slant range point
             loc_incidence_center_deg: 0.0, // Default local incidence angle at the antenna beam footprint center
             loc_incidence_min_deg: 0.0, // Default local incidence angle at the minimum range point
             loc_incidence_max_deg: 0.0, // Default local incidence angle at the maximum range point
@@ -49,7 +82,71 @@ impl Default for AntennaBeamFootprintState {
             antenna_squint_deg: 0.0, // Default antenna squint angle
             illumination_time_s: 0.0, // Default illumination time
             ground_angular_velocity_degps: 0.0, // Default ground angular velocity
+            lift_m: FOOTPRINT_MIN_LIFT_M, // Default height of the drawn footprint
+            squint_convention: SquintConvention::default(), // Default squint from broadside, positive forward
+            model: FootprintModel::default(), // Default numeric intersection
+            earth_frame: None, // Default flat Earth
+        }
+    }
+}
+
+impl AntennaBeamFootprintState {
+    /// Gets the four footprint points at the ends of the azimuth and elevation
+    /// lines in World frame (Y-up), ordered as: azimuth edge (+), elevation edge (+),
+    /// azimuth edge (-), elevation edge (-).
+    pub fn edge_points(&self) -> [DVec3; 4] {
+        let index = self.lines_index();
+        [
+            self.points[0],
+            self.points[index],
+            self.points[2*index],
+            self.points[3*index],
+        ]
+    }
+
+    /// Gets the footprint points one every `stride`, the closing point kept
+    /// whatever the number of points: the decimated line stays closed.
+    pub fn decimated_points(&self, stride: usize) -> impl Iterator<Item = &DVec3> {
+        let stride = stride.max(1);
+        let closing_point = (self.points.len().saturating_sub(1) % stride != 0)
+            .then(|| self.points.last())
+            .flatten();
+        self.points.iter().step_by(stride).chain(closing_point)
+    }
+
+    /// Gets the index of the elevation edge (+) point, a quarter of the
+    /// footprint line whatever its number of points.
+    fn lines_index(&self) -> usize {
+        (self.points.len() - 1) / 4
+    }
+
+    /// Gets the footprint points of minimum and maximum slant range in World
+    /// frame (Y-up).
+    pub fn range_extrema_points(&self) -> [DVec3; 2] {
+        [self.points[self.index_range_min], self.points[self.index_range_max]]
+    }
+
+    /// Header of [`AntennaBeamFootprintState::vertices_csv`].
+    pub const VERTICES_CSV_HEADER: &'static str = "index,east_m,north_m,up_m,slant_range_m,local_incidence_deg";
+
+    /// Formats the footprint vertices as a CSV table: ENU coordinates from the
+    /// scene center, slant range from the carrier at `carrier_position_m` (ENU)
+    /// and local incidence on the flat ground. The footprint line being closed,
+    /// its last vertex repeats the first one.
+    pub fn vertices_csv(&self, carrier_position_m: &DVec3) -> String {
+        let mut csv = format!("{}\n", Self::VERTICES_CSV_HEADER);
+        for (index, point) in self.points.iter().enumerate() {
+            let point_m = DVec3::new(point.z, point.x, point.y); // Y-up to ENU
+            csv.push_str(&format!(
+                "{index},{},{},{},{},{}\n",
+                point_m.x,
+                point_m.y,
+                point_m.z,
+                carrier_position_m.distance(point_m),
+                local_incidence_deg(carrier_position_m, &point_m),
+            ));
         }
+        csv
     }
 }
 
@@ -87,13 +184,18 @@ pub fn spawn_antenna_beam_footprint(
     )).id()
 }
 
-pub fn update_antenna_beam_footprint_mesh_from_state(
+/// Computes the antenna beam footprint geometry: the points where the
+/// half-power beam edge intersects the ground plane, the ranges, local
+/// incidences, swath, area, squint, illumination time and ground angular
+/// velocity. Only `lift_m` and `squint_convention` are read from the state,
+/// which is otherwise overwritten.
+/// note: independent of any mesh, for headless use and tests
+pub fn update_antenna_beam_footprint_from_state(
     carrier_state: &CarrierState,
     antenna_state: &AntennaState,
     antenna_beam_state: &AntennaBeamState,
     antenna_beam_footprint_state: &mut AntennaBeamFootprintState,
-    mesh: &mut Mesh // Should be the mesh of the antenna beam footprint entity
-)  {
+) {
     // Closures definitions
     let area = |points: &[DVec3]| -> f64 { // Computes the half-power antenna beam footprint area using the "Shoelace" formula.
         points.iter()
@@ -109,144 +211,261 @@ pub fn update_antenna_beam_footprint_mesh_from_state(
                 .acos()
                 .to_degrees()
     };
-    // Computes this antenna squint angle in degrees from the antenna beam axis (normalized) and the carrier velocity vector
-    let squint = |axis: &DVec3, vel: &DVec3| -> f64 {
-        let vel_norm = vel.length_squared();
-        if vel_norm > 0.0 {
-            axis.dot(vel / vel_norm.sqrt())
-                .clamp(-1.0, 1.0)
-                .asin()
-                .to_degrees()
-        } else {
-            0.0
-        }
-    };
-
 
-    if let Some(VertexAttributeValues::Float32x3(mesh_pos)) =
-        mesh.attribute_mut(Mesh::ATTRIBUTE_POSITION) {
-        // Rotation to transform ground plane origin and normal into Antena referential
-        // World to Antenna: R = R_enu_to_ned * R_carrier * R_antenna
-        // => Antenna to World: R^-1 = R_antenna^-1 * R_carrier^-1 * R_enu_to_ned^-1
-        let carrier_rotation = ENU_TO_NED_F64 * DQuat::from_euler(
-            EulerRot::ZYX,
-            carrier_state.heading_deg.to_radians(),
-            carrier_state.elevation_deg.to_radians(),
-            carrier_state.bank_deg.to_radians()
-        );
-        let antenna_rotation = DQuat::from_euler(
-            EulerRot::ZYX,
-            antenna_state.heading_deg.to_radians(),
-            antenna_state.elevation_deg.to_radians(),
-            antenna_state.bank_deg.to_radians()
-        );
-        let mut rot_antenna_to_world = carrier_rotation * antenna_rotation;
-        let rot_world_to_antenna = rot_antenna_to_world.inverse(); // Inverse rotation to transform from World frame to Antenna frame
-        rot_antenna_to_world = TO_Y_UP_F64 * rot_antenna_to_world; // Convert from Z-up to Y-up frame
-        let carrier_position_y_up = TO_Y_UP_F64 * carrier_state.position_m; // Carrier position vector in World frame (Y-up)
-        // Parameters for the plane/cone intersection computation
-        let n = rot_world_to_antenna * DVec3::Z; // Normal vector of the ground plane in Antenna referential
-        let o = rot_world_to_antenna * carrier_state.position_m; // Origin of the ground plane in Antenna referential
-        let d =  -n.dot(o); // Distance from the origin to the ground plane in Antenna referential
-        let ty = (0.5 * antenna_beam_state.azimuth_beam_width_deg.to_radians()).tan(); // Half of the azimuth beam width in radians
-        let tz = (0.5 * antenna_beam_state.elevation_beam_width_deg.to_radians()).tan(); // Half of the elevation beam width in radians
-        let nyty = n.y * ty; // Normal vector component in the Y direction scaled by the azimuth beam width
-        let nztz = n.z * tz; // Normal vector component in the Z direction
-        // Parameters for ranges and extent computation
-        let mut ground_max_extent_m = 0.0f64;
-        let mut range_min_m = f64::MAX;
-        let mut range_max_m = 0.0;
-        let mut range_m: f64; // Temporary range variable
-        let mut index_min_range: usize = 0; // Index of the minimum range point in the antenna beam footprint
-        let mut index_max_range: usize = 0; // Index of the maximum range point in the
-        // Compute the intersection points and update corresponding mesh positions
-        let (mut s, mut c): (f64, f64); // (sin(theta), cos(theta))
-        for (i, point) in antenna_beam_footprint_state.points.iter_mut().enumerate() {
-            (s, c) = (i as f64 * STEP_THETA).sin_cos(); // Angle in radians
-            // Update resource with the new point in Antenna referential.
-            // When the beam edge grazes or points above the horizon the denominator
-            // tends to 0 or becomes negative (intersection behind the antenna):
-            // clamp the slant distance to MAX_BORESIGHT_RANGE_M to keep the footprint finite.
-            let r = d / (n.x + nyty * c + nztz * s);
-            point.x = if r.is_finite() && r >= 0.0 {
-                r.min(MAX_BORESIGHT_RANGE_M)
-            } else {
-                MAX_BORESIGHT_RANGE_M
-            };
-            point.y = ty * c * point.x;
-            point.z = tz * s * point.x;
-            // Transform point to World frame
-            *point = rot_antenna_to_world * *point + carrier_position_y_up; // Transform point to World frame and Y-up frame
-            point.y = 0.0; // Ensure to have a real zero in Z-up frame (which is here Y axis)
-            // Update mesh with the new point
-            mesh_pos[i] = [point.x as f32, 0.05, point.z as f32];// note: 0.05 in z-direction to be slightly above the ground plane (here Y axis)                
-            // Update ranges and extent computation
-            ground_max_extent_m = ground_max_extent_m.max(
-                (point.x * point.x + point.z * point.z).sqrt() // Update maximum extent in the ground plane (x and z coordinates in Y-up frame)
-            ); // Update maximum extent in the ground plane
-            range_m =  carrier_position_y_up.distance(*point); // Compute the slant range from the antenna to the point
-            if range_m < range_min_m {
-                range_min_m = range_m; // Update minimum range
-                index_min_range = i; // Update index of the minimum range point
+    // Rotation to transform ground plane origin and normal into Antena referential
+    // World to Antenna: R = R_enu_to_ned * R_carrier * R_antenna
+    // => Antenna to World: R^-1 = R_antenna^-1 * R_carrier^-1 * R_enu_to_ned^-1
+    let carrier_rotation = carrier_state.rotation(); // Turned along its trajectory
+    let antenna_rotation = DQuat::from_euler(
+        EulerRot::ZYX,
+        antenna_state.heading_deg.to_radians(),
+        antenna_state.elevation_deg.to_radians(),
+        antenna_state.bank_deg.to_radians()
+    );
+    let mut rot_antenna_to_world = carrier_rotation * antenna_rotation;
+    let rot_world_to_antenna = rot_antenna_to_world.inverse(); // Inverse rotation to transform from World frame to Antenna frame
+    rot_antenna_to_world = TO_Y_UP_F64 * rot_antenna_to_world; // Convert from Z-up to Y-up frame
+    let carrier_position_y_up = TO_Y_UP_F64 * carrier_state.position_m; // Carrier position vector in World frame (Y-up)
+    // Compute the footprint points
+    let size = match antenna_beam_footprint_state.model {
+        FootprintModel::Numeric => ANTENNA_BEAM_FOOTPRINT_SIZE,
+        FootprintModel::AnalyticEllipse => ANALYTIC_FOOTPRINT_SIZE,
+    };
+    antenna_beam_footprint_state.points.resize(size, DVec3::ZERO);
+    let ty = (0.5 * antenna_beam_state.azimuth_beam_width_deg.to_radians()).tan(); // Half of the azimuth beam width in radians
+    let tz = (0.5 * antenna_beam_state.elevation_beam_width_deg.to_radians()).tan(); // Half of the elevation beam width in radians
+    // Area of the analytic ellipse, the other footprints being integrated
+    let ellipse_area_m2 = match antenna_beam_footprint_state.model {
+        FootprintModel::Numeric => {
+            // Parameters for the plane/cone intersection computation
+            let n = rot_world_to_antenna * DVec3::Z; // Normal vector of the ground plane in Antenna referential
+            let o = rot_world_to_antenna * carrier_state.position_m; // Origin of the ground plane in Antenna referential
+            let d =  -n.dot(o); // Distance from the origin to the ground plane in Antenna referential
+            let nyty = n.y * ty; // Normal vector component in the Y direction scaled by the azimuth beam width
+            let nztz = n.z * tz; // Normal vector component in the Z direction
+            let (mut s, mut c): (f64, f64); // (sin(theta), cos(theta))
+            for (i, point) in antenna_beam_footprint_state.points.iter_mut().enumerate() {
+                (s, c) = (i as f64 * STEP_THETA).sin_cos(); // Angle in radians
+                // Update resource with the new point in Antenna referential.
+                // When the beam edge grazes or points above the horizon the denominator
+                // tends to 0 or becomes negative (intersection behind the antenna):
+                // clamp the slant distance to MAX_BORESIGHT_RANGE_M to keep the footprint finite.
+                // note: the clamp applies along the beam edge ray, whose length is
+                // sqrt(1 + (ty.c)² + (tz.s)²) times its x-component, i.e. up to
+                // ~11 times for the broadest (170°) beams
+                let r = d / (n.x + nyty * c + nztz * s);
+                let r_max = MAX_BORESIGHT_RANGE_M / (1.0 + (ty * c).powi(2) + (tz * s).powi(2)).sqrt();
+                point.x = if r.is_finite() && r >= 0.0 {
+                    r.min(r_max)
+                } else {
+                    r_max
+                };
+                point.y = ty * c * point.x;
+                point.z = tz * s * point.x;
+                // Transform point to World frame
+                *point = rot_antenna_to_world * *point + carrier_position_y_up; // Transform point to World frame and Y-up frame
+                point.y = 0.0; // Ensure to have a real zero in Z-up frame (which is here Y axis)
             }
-            if range_m > range_max_m {
-                range_max_m = range_m; // Update maximum range
-                index_max_range = i; // Update index of the maximum range point
+            None
+        }
+        FootprintModel::AnalyticEllipse => {
+            // The beam edge is an ellipse of semi-axes R.ty and R.tz in the
+            // plane normal to the boresight at the scene center, projected on
+            // the ground along the boresight: the semi-axes are stretched by
+            // 1/cos(incidence) along the ground range
+            let range_m = carrier_position_y_up.length();
+            let boresight = rot_antenna_to_world * DVec3::X; // In World frame (Y-up)
+            // note: the boresight grazing or above the horizon has no ground
+            // projection, hence the clamps of the projection and of the points
+            let cos_incidence = (-boresight.y).max(f64::EPSILON);
+            let project = |v: DVec3| v + boresight * (v.y / cos_incidence);
+            let azimuth_axis = project(range_m * ty * (rot_antenna_to_world * DVec3::Y));
+            let elevation_axis = project(range_m * tz * (rot_antenna_to_world * DVec3::Z));
+            let step_theta = TAU / (size - 1) as f64;
+            for (i, point) in antenna_beam_footprint_state.points.iter_mut().enumerate() {
+                let (s, c) = (i as f64 * step_theta).sin_cos();
+                *point = (c * azimuth_axis + s * elevation_axis).clamp_length_max(MAX_BORESIGHT_RANGE_M);
+                point.y = 0.0; // Ensure to have a real zero in Z-up frame (which is here Y axis)
             }
+            Some(PI * azimuth_axis.cross(elevation_axis).length())
         }
+    };
+    // Compute the ranges and extent
+    let mut ground_max_extent_m = 0.0f64;
+    let mut range_min_m = f64::MAX;
+    let mut range_max_m = 0.0;
+    let mut range_m: f64; // Temporary range variable
+    let mut index_min_range: usize = 0; // Index of the minimum range point in the antenna beam footprint
+    let mut index_max_range: usize = 0; // Index of the maximum range point in the
+    for (i, point) in antenna_beam_footprint_state.points.iter().enumerate() {
+        ground_max_extent_m = ground_max_extent_m.max(
+            (point.x * point.x + point.z * point.z).sqrt() // Update maximum extent in the ground plane (x and z coordinates in Y-up frame)
+        ); // Update maximum extent in the ground plane
+        range_m =  carrier_position_y_up.distance(*point); // Compute the slant range from the antenna to the point
+        if range_m < range_min_m {
+            range_min_m = range_m; // Update minimum range
+            index_min_range = i; // Update index of the minimum range point
+        }
+        if range_m > range_max_m {
+            range_max_m = range_m; // Update maximum range
+            index_max_range = i; // Update index of the maximum range point
+        }
+    }
 
-        // Update the antenna beam footprint ranges
-        antenna_beam_footprint_state.range_center_m = carrier_position_y_up.length();
-        antenna_beam_footprint_state.range_min_m = range_min_m;
-        antenna_beam_footprint_state.range_max_m = range_max_m;
-        antenna_beam_footprint_state.ground_max_extent_m = ground_max_extent_m;
-
-        // Update the ground range swath and local incidences
-        let point_min_range = antenna_beam_footprint_state.points[index_min_range];
-        let point_max_range = antenna_beam_footprint_state.points[index_max_range];
-            // Ground range swath
-        antenna_beam_footprint_state.ground_range_swath_m = point_min_range.distance(point_max_range);
-            // Local incidence angle at the antenna beam footprint center
-        let neg_antenna_beam_axis = if antenna_beam_footprint_state.range_center_m > 0.0 {  // Antenna beam (negative) axis in World frame (Y-up)    
-            carrier_position_y_up / antenna_beam_footprint_state.range_center_m
+    // Update the antenna beam footprint ranges
+    antenna_beam_footprint_state.range_center_m = carrier_position_y_up.length();
+    antenna_beam_footprint_state.range_min_m = range_min_m;
+    antenna_beam_footprint_state.range_max_m = range_max_m;
+    antenna_beam_footprint_state.index_range_min = index_min_range;
+    antenna_beam_footprint_state.index_range_max = index_max_range;
+    antenna_beam_footprint_state.ground_max_extent_m = ground_max_extent_m;
+
+    // Update the ground range swath and local incidences
+    let [point_min_range, point_max_range] = antenna_beam_footprint_state.range_extrema_points();
+        // Ground range swath
+    antenna_beam_footprint_state.ground_range_swath_m = point_min_range.distance(point_max_range);
+        // Local incidence angle at the antenna beam footprint center
+    let neg_antenna_beam_axis = if antenna_beam_footprint_state.range_center_m > 0.0 {  // Antenna beam (negative) axis in World frame (Y-up)    
+        carrier_position_y_up / antenna_beam_footprint_state.range_center_m
+    } else {
+        DVec3::ZERO
+    };
+    antenna_beam_footprint_state.loc_incidence_center_deg = incidence(&neg_antenna_beam_axis);
+        // Local incidence angle at the minimum range point
+    let neg_antenna_beam_axis_min = (
+        carrier_position_y_up - point_min_range
+    ).normalize_or_zero(); // Antenna beam (negative) axis at minimum range in World frame (Y-up)
+    antenna_beam_footprint_state.loc_incidence_min_deg = incidence(&neg_antenna_beam_axis_min);
+        // Local incidence angle at the maximum range point
+    let neg_antenna_beam_axis_max = (
+        carrier_position_y_up - point_max_range
+    ).normalize_or_zero(); // Antenna beam (negative) axis at minimum range in World frame (Y-up)
+    antenna_beam_footprint_state.loc_incidence_max_deg = incidence(&neg_antenna_beam_axis_max);
+
+    // In Earth-curvature mode, the swath and the extent along the ellipsoid surface
+    if let Some(earth_frame) = &antenna_beam_footprint_state.earth_frame {
+        (antenna_beam_footprint_state.ground_range_swath_m, antenna_beam_footprint_state.ground_max_extent_m) =
+            geodesic_swath_and_extent_m(earth_frame, &carrier_state.position_m, &antenna_beam_footprint_state.points);
+    }
+
+    // Computes the half-power antenna beam footprint area using the "Shoelace" formula.
+    antenna_beam_footprint_state.area_m2 = ellipse_area_m2.unwrap_or_else(|| area(&antenna_beam_footprint_state.points));
+
+    // Update the antenna squint angle
+    antenna_beam_footprint_state.antenna_squint_deg = antenna_beam_footprint_state.squint_convention.squint_deg(
+        &-carrier_state.position_m.normalize_or_zero(), // Antenna beam axis in World frame (Z-up)
+        &carrier_state.velocity_vector_mps // Carrier velocity vector in World frame (Z-up)
+    );
+
+    // Update the illumination time
+    update_illumination_time(
+        carrier_state,
+        antenna_beam_footprint_state
+    );
+
+    // Update the ground angular velocity
+    update_ground_angular_velocity(
+        carrier_state,
+        antenna_beam_footprint_state
+    );
+}
+
+/// Gets the ground range swath and the ground maximum extent in meters along
+/// the geodesics of the ellipsoid of `earth_frame`, the scene ENU frame.
+///
+/// The footprint `points` (Y-up) lie on the tangent plane: the rays from the
+/// carrier at `carrier_position_m` (ENU) through them are intersected with the
+/// ellipsoid raised to the scene origin height, the swath joining the
+/// intersections of min and max slant range and the extent being measured from
+/// the scene origin. A ray missing the ellipsoid keeps its tangent plane point.
+fn geodesic_swath_and_extent_m(
+    earth_frame: &LocalCartesian,
+    carrier_position_m: &DVec3,
+    points: &[DVec3],
+) -> (f64, f64) {
+    let ellipsoid = earth_frame.ellipsoid();
+    let origin = earth_frame.origin_as_geographic_point();
+    let (a_m, b_m) = (ellipsoid.equatorial_radius_m() + origin.height_m(), ellipsoid.polar_radius_m() + origin.height_m());
+    let raised_ellipsoid = Ellipsoid::new(a_m, Ellipsoid::first_flattening_from_radii(a_m, b_m));
+    let carrier_ecef = earth_frame.transform_from_enu_point_to_cartesian_ecef_point(carrier_position_m);
+    let (mut range_min_m, mut range_max_m) = (f64::MAX, 0.0);
+    let (mut point_min, mut point_max) = (origin.clone(), origin.clone());
+    let mut ground_max_extent_m = 0.0f64;
+    for point in points {
+        let point_enu = TO_Y_UP_F64.inverse() * *point;
+        let direction = earth_frame
+            .transform_vector_from_enu_to_ecef(&(point_enu - *carrier_position_m))
+            .normalize_or_zero();
+        let hit_ecef = raised_ellipsoid.line_intersection(&carrier_ecef, &direction);
+        // note: line_intersection returns the carrier itself on a miss, and
+        // the point behind the carrier for a ray pointing away
+        let range_m = (hit_ecef - carrier_ecef).dot(direction);
+        let (gp, range_m) = if range_m > 0.0 {
+            (ellipsoid.to_geographic_point(&hit_ecef), range_m)
         } else {
-            DVec3::ZERO
+            (earth_frame.transform_from_enu_point_to_geographic_point(&point_enu), point_enu.distance(*carrier_position_m))
         };
-        antenna_beam_footprint_state.loc_incidence_center_deg = incidence(&neg_antenna_beam_axis);
-            // Local incidence angle at the minimum range point
-        let neg_antenna_beam_axis_min = (
-            carrier_position_y_up - point_min_range
-        ).normalize_or_zero(); // Antenna beam (negative) axis at minimum range in World frame (Y-up)
-        antenna_beam_footprint_state.loc_incidence_min_deg = incidence(&neg_antenna_beam_axis_min);
-            // Local incidence angle at the maximum range point
-        let neg_antenna_beam_axis_max = (
-            carrier_position_y_up - point_max_range
-        ).normalize_or_zero(); // Antenna beam (negative) axis at minimum range in World frame (Y-up)
-        antenna_beam_footprint_state.loc_incidence_max_deg = incidence(&neg_antenna_beam_axis_max);
-
-        // Computes the half-power antenna beam footprint area using the "Shoelace" formula.
-        antenna_beam_footprint_state.area_m2 = area(&antenna_beam_footprint_state.points);
-
-        // Update the antenna squint angle
-        antenna_beam_footprint_state.antenna_squint_deg = -squint(
-            &carrier_state.position_m.normalize_or_zero(), // Antenna beam axis in World frame (Z-up)
-            &carrier_state.velocity_vector_mps // Carrier velocity vector in World frame (Z-up)
-        );
-
-        // Update the illumination time
-        update_illumination_time(
-            carrier_state,
-            antenna_beam_footprint_state
-        );
+        ground_max_extent_m = ground_max_extent_m.max(ellipsoid.geodesic_inverse(origin, &gp).0);
+        if range_m < range_min_m {
+            range_min_m = range_m;
+            point_min = gp.clone();
+        }
+        if range_m > range_max_m {
+            range_max_m = range_m;
+            point_max = gp;
+        }
+    }
+    (ellipsoid.geodesic_inverse(&point_min, &point_max).0, ground_max_extent_m)
+}
 
-        // Update the ground angular velocity
-        update_ground_angular_velocity(
-            carrier_state,
-            antenna_beam_footprint_state
-        );
+/// Computes the antenna beam footprint with
+/// [`update_antenna_beam_footprint_from_state`] and draws it on its mesh,
+/// lifted by `lift_m` above the ground plane.
+pub fn update_antenna_beam_footprint_mesh_from_state(
+    carrier_state: &CarrierState,
+    antenna_state: &AntennaState,
+    antenna_beam_state: &AntennaBeamState,
+    antenna_beam_footprint_state: &mut AntennaBeamFootprintState,
+    mesh: &mut Mesh // Should be the mesh of the antenna beam footprint entity
+)  {
+    update_antenna_beam_footprint_from_state(
+        carrier_state,
+        antenna_state,
+        antenna_beam_state,
+        antenna_beam_footprint_state
+    );
+    if let Some(VertexAttributeValues::Float32x3(mesh_pos)) =
+        mesh.attribute_mut(Mesh::ATTRIBUTE_POSITION) {
+        let lift_m = antenna_beam_footprint_state.lift_m as f32; // Height of the drawn footprint above the ground plane
+        // note: the mesh has the size of the numeric footprint, the positions
+        // past the points of a smaller footprint repeat its closing point
+        let points = antenna_beam_footprint_state.points.iter()
+            .chain(std::iter::repeat(antenna_beam_footprint_state.points.last().unwrap_or(&DVec3::ZERO)));
+        for (position, point) in mesh_pos.iter_mut().zip(points) {
+            *position = [point.x as f32, lift_m, point.z as f32]; // note: lifted to be slightly above the ground plane (here Y axis)
+        }
     }
 }
 
+/// Computes the antenna beam footprint state of an antenna pointing without any
+/// drawn footprint, e.g. for the other beam positions of a steered antenna.
+/// note: the footprint is still lifted by `lift_m` for the overlays drawn from it
+pub fn antenna_beam_footprint_state_from_state(
+    carrier_state: &CarrierState,
+    antenna_state: &AntennaState,
+    antenna_beam_state: &AntennaBeamState,
+    lift_m: f64,
+) -> AntennaBeamFootprintState {
+    let mut antenna_beam_footprint_state = AntennaBeamFootprintState { lift_m, ..default() };
+    update_antenna_beam_footprint_from_state(
+        carrier_state,
+        antenna_state,
+        antenna_beam_state,
+        &mut antenna_beam_footprint_state
+    );
+    antenna_beam_footprint_state
+}
+
 /// Computes the antenna ground angular velocity in degrees per second
 /// note: it has its own function to be called if velocity value is update,
 ///       without the need to update the whole antenna beam footprint mesh and so on.
@@ -355,12 +574,14 @@ pub fn update_antenna_beam_footprint_elevation_line_mesh_from_state(
 )  {
     if let Some(VertexAttributeValues::Float32x3(mesh_pos)) =
         mesh.attribute_mut(Mesh::ATTRIBUTE_POSITION) {
+        let lift_m = antenna_beam_footprint_state.lift_m as f32; // Height of the drawn line above the ground plane
 
-        let p0 = antenna_beam_footprint_state.points[ANTENNA_ELV_AZI_LINES_INDEX]; // Elevation line first point (pi/2)
-        mesh_pos[0] = [p0.x as f32, 0.05, p0.z as f32]; // note: 0.05 in z-direction to be slightly above the ground plane
+        let index = antenna_beam_footprint_state.lines_index();
+        let p0 = antenna_beam_footprint_state.points[index]; // Elevation line first point (pi/2)
+        mesh_pos[0] = [p0.x as f32, lift_m, p0.z as f32]; // note: lifted in z-direction to be slightly above the ground plane
 
-        let p1 = antenna_beam_footprint_state.points[3*ANTENNA_ELV_AZI_LINES_INDEX]; // Elevation line last point (3*pi/2)
-        mesh_pos[1] = [p1.x as f32, 0.05, p1.z as f32]; // note: 0.05 in z-direction to be slightly above the ground plane
+        let p1 = antenna_beam_footprint_state.points[3*index]; // Elevation line last point (3*pi/2)
+        mesh_pos[1] = [p1.x as f32, lift_m, p1.z as f32]; // note: lifted in z-direction to be slightly above the ground plane
     }
 }
 
@@ -399,19 +620,23 @@ pub fn update_antenna_beam_footprint_azimuth_line_mesh_from_state(
 )  {
     if let Some(VertexAttributeValues::Float32x3(mesh_pos)) =
         mesh.attribute_mut(Mesh::ATTRIBUTE_POSITION) {
+        let lift_m = antenna_beam_footprint_state.lift_m as f32; // Height of the drawn line above the ground plane
 
         let p0 = antenna_beam_footprint_state.points[0]; // Azimuth line first point (0)
-        mesh_pos[0] = [p0.x as f32, 0.05, p0.z as f32]; // note: 0.05 in z-direction to be slightly above the ground plane
+        mesh_pos[0] = [p0.x as f32, lift_m, p0.z as f32]; // note: lifted in z-direction to be slightly above the ground plane
 
-        let p1 = antenna_beam_footprint_state.points[2*ANTENNA_ELV_AZI_LINES_INDEX]; // Azimuth line last point (pi)
-        mesh_pos[1] = [p1.x as f32, 0.05, p1.z as f32]; // note: 0.05 in z-direction to be slightly above the ground plane
+        let p1 = antenna_beam_footprint_state.points[2*antenna_beam_footprint_state.lines_index()]; // Azimuth line last point (pi)
+        mesh_pos[1] = [p1.x as f32, lift_m, p1.z as f32]; // note: lifted in z-direction to be slightly above the ground plane
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::entities::{carrier_transform_from_state, LineStrip};
+    use crate::{
+        entities::{carrier_transform_from_state, LineStrip},
+        motion::MotionProfile,
+    };
 
     fn assert_close(value: f64, expected: f64, rel_tol: f64) {
         assert!(
@@ -429,11 +654,14 @@ mod tests {
             velocity_mps,
             position_m: DVec3::ZERO,
             velocity_vector_mps: DVec3::ZERO,
+            acceleration_vector_mps2: None,
+            time_s: 0.0,
+            motion: MotionProfile::default(),
         }
     }
 
     fn antenna_state(elevation_deg: f64) -> AntennaState {
-        AntennaState { heading_deg: 0.0, elevation_deg, bank_deg: 0.0 }
+        AntennaState { heading_deg: 0.0, elevation_deg, bank_deg: 0.0, lever_arm_m: DVec3::ZERO }
     }
 
     fn antenna_beam_state(beam_width_deg: f64) -> AntennaBeamState {
@@ -474,6 +702,150 @@ mod tests {
         assert!(footprint.antenna_squint_deg.abs() < 1e-9);
     }
 
+    /// The mesh is drawn from the points of the mesh-free computation, lifted
+    #[test]
+    fn mesh_is_drawn_from_the_footprint_geometry() {
+        let mut carrier = carrier_state(3000.0, 100.0);
+        let antenna = antenna_state(-45.0);
+        let beam = antenna_beam_state(20.0);
+        carrier_transform_from_state(&mut carrier, &antenna);
+        let footprint = antenna_beam_footprint_state_from_state(&carrier, &antenna, &beam, 0.5);
+        assert!(footprint.points.iter().all(|point| point.y == 0.0));
+        assert!(footprint.range_min_m < footprint.range_center_m && footprint.range_center_m < footprint.range_max_m);
+
+        let mut drawn_footprint = AntennaBeamFootprintState { lift_m: 0.5, ..default() };
+        let mut mesh = footprint_mesh();
+        update_antenna_beam_footprint_mesh_from_state(&carrier, &antenna, &beam, &mut drawn_footprint, &mut mesh);
+        assert_eq!(drawn_footprint.points, footprint.points);
+        assert_eq!(drawn_footprint.area_m2, footprint.area_m2);
+        let Some(VertexAttributeValues::Float32x3(positions)) = mesh.attribute(Mesh::ATTRIBUTE_POSITION) else {
+            panic!("no mesh positions");
+        };
+        for (position, point) in positions.iter().zip(footprint.points.iter()) {
+            assert_eq!(*position, [point.x as f32, 0.5, point.z as f32]);
+        }
+    }
+
+    /// The analytic ellipse is the numeric footprint at nadir, and close to it
+    /// for a narrow oblique beam
+    #[test]
+    fn analytic_ellipse_approximates_the_footprint() {
+        let (height, half_beam_width) = (3000.0, 10.0f64);
+        let mut carrier = carrier_state(height, 100.0);
+        let antenna = antenna_state(-90.0);
+        let beam = antenna_beam_state(2.0 * half_beam_width);
+        carrier_transform_from_state(&mut carrier, &antenna);
+        let mut footprint = AntennaBeamFootprintState { model: FootprintModel::AnalyticEllipse, ..default() };
+        update_antenna_beam_footprint_from_state(&carrier, &antenna, &beam, &mut footprint);
+        assert_eq!(footprint.points.len(), ANALYTIC_FOOTPRINT_SIZE);
+        let radius = height * half_beam_width.to_radians().tan();
+        assert_close(footprint.ground_max_extent_m, radius, 1e-9);
+        assert_close(footprint.area_m2, PI * radius * radius, 1e-12);
+        assert_close(footprint.edge_points()[1].length(), radius, 1e-9);
+
+        let antenna = antenna_state(-45.0);
+        let beam = antenna_beam_state(4.0);
+        carrier_transform_from_state(&mut carrier, &antenna);
+        let numeric = antenna_beam_footprint_state_from_state(&carrier, &antenna, &beam, 0.5);
+        update_antenna_beam_footprint_from_state(&carrier, &antenna, &beam, &mut footprint);
+        assert_close(footprint.area_m2, numeric.area_m2, 5e-3);
+        assert_close(footprint.range_min_m, numeric.range_min_m, 5e-3);
+        assert_close(footprint.range_max_m, numeric.range_max_m, 5e-3);
+        assert_close(footprint.loc_incidence_center_deg, 45.0, 1e-9);
+        // Along the ground range, stretched by 1/cos(45°)
+        let [_, elevation_edge, _, _] = footprint.edge_points();
+        assert_close(elevation_edge.length(), numeric.range_center_m * 2f64.to_radians().tan() * 2f64.sqrt(), 1e-9);
+
+        // Decimated outlines end at the closing point, kept once
+        for stride in [1, 5, 10, 64] {
+            let outline: Vec<&DVec3> = footprint.decimated_points(stride).collect();
+            assert_eq!(outline.last().copied(), footprint.points.last());
+            assert_eq!(outline.len(), (ANALYTIC_FOOTPRINT_SIZE - 1).div_ceil(stride) + 1, "{stride}");
+        }
+
+        // Back to the numeric model, with its number of points
+        footprint.model = FootprintModel::Numeric;
+        update_antenna_beam_footprint_from_state(&carrier, &antenna, &beam, &mut footprint);
+        assert_eq!(footprint.points, numeric.points);
+    }
+
+    /// In Earth-curvature mode, the extent of a nadir footprint is the arc of
+    /// the sphere, and an oblique swath is longer than on the tangent plane
+    #[test]
+    fn earth_curvature_measures_along_the_ellipsoid() {
+        let (height, half_beam_width) = (500e3, 5.0f64);
+        let moon = LocalCartesian::new().with_ellipsoid(&Ellipsoid::MOON);
+        let radius = Ellipsoid::MOON.equatorial_radius_m();
+        let mut carrier = carrier_state(height, 1600.0);
+        let antenna = antenna_state(-90.0);
+        let beam = antenna_beam_state(2.0 * half_beam_width);
+        carrier_transform_from_state(&mut carrier, &antenna);
+        let mut footprint = AntennaBeamFootprintState { earth_frame: Some(moon.clone()), ..default() };
+        update_antenna_beam_footprint_from_state(&carrier, &antenna, &beam, &mut footprint);
+        let half_beam_width = half_beam_width.to_radians();
+        let central_angle = ((radius + height) / radius * half_beam_width.sin()).asin() - half_beam_width;
+        assert_close(footprint.ground_max_extent_m, radius * central_angle, 1e-6);
+
+        let antenna = antenna_state(-60.0);
+        carrier_transform_from_state(&mut carrier, &antenna);
+        let flat = antenna_beam_footprint_state_from_state(&carrier, &antenna, &beam, 0.5);
+        update_antenna_beam_footprint_from_state(&carrier, &antenna, &beam, &mut footprint);
+        assert!(footprint.ground_range_swath_m > flat.ground_range_swath_m);
+        assert!(footprint.ground_max_extent_m > flat.ground_max_extent_m);
+        // The footprint points and ranges stay on the tangent plane
+        assert_eq!(footprint.points, flat.points);
+        assert_eq!(footprint.range_max_m, flat.range_max_m);
+    }
+
+    #[test]
+    fn vertices_csv_gives_ranges_and_incidences() {
+        let (height, half_beam_width) = (3000.0, 10.0f64);
+        let mut carrier = carrier_state(height, 100.0);
+        let antenna = antenna_state(-90.0);
+        let beam = antenna_beam_state(2.0 * half_beam_width);
+        let mut footprint = AntennaBeamFootprintState::default();
+        let mut mesh = footprint_mesh();
+        carrier_transform_from_state(&mut carrier, &antenna);
+        update_antenna_beam_footprint_mesh_from_state(&carrier, &antenna, &beam, &mut footprint, &mut mesh);
+
+        let csv = footprint.vertices_csv(&carrier.position_m);
+        let mut lines = csv.lines();
+        assert_eq!(lines.next(), Some(AntennaBeamFootprintState::VERTICES_CSV_HEADER));
+        let rows: Vec<Vec<f64>> = lines
+            .map(|line| line.split(',').map(|value| value.parse().unwrap()).collect())
+            .collect();
+        assert_eq!(rows.len(), ANTENNA_BEAM_FOOTPRINT_SIZE);
+        // Nadir pointing: every vertex on the circle at the beam edge
+        let slant = height / half_beam_width.to_radians().cos();
+        for row in rows.iter() {
+            assert_eq!(row.len(), 6);
+            assert_eq!(row[3], 0.0);
+            assert_close(row[4], slant, 1e-9);
+            assert_close(row[5], half_beam_width, 1e-9);
+        }
+    }
+
+    #[test]
+    fn range_extrema_points_match_the_ranges() {
+        // Side-looking antenna: the nearest and farthest points on the
+        // elevation line, across the track
+        let mut carrier = carrier_state(3000.0, 100.0);
+        let antenna = antenna_state(-45.0);
+        let beam = antenna_beam_state(20.0);
+        let mut footprint = AntennaBeamFootprintState::default();
+        let mut mesh = footprint_mesh();
+        carrier_transform_from_state(&mut carrier, &antenna);
+        update_antenna_beam_footprint_mesh_from_state(&carrier, &antenna, &beam, &mut footprint, &mut mesh);
+
+        let carrier_position_y_up = TO_Y_UP_F64 * carrier.position_m;
+        let [point_min_range, point_max_range] = footprint.range_extrema_points();
+        assert_close(carrier_position_y_up.distance(point_min_range), footprint.range_min_m, 1e-12);
+        assert_close(carrier_position_y_up.distance(point_max_range), footprint.range_max_m, 1e-12);
+        assert_close(point_min_range.distance(point_max_range), footprint.ground_range_swath_m, 1e-12);
+        let edge_points = footprint.edge_points();
+        assert!(edge_points.contains(&point_min_range) && edge_points.contains(&point_max_range));
+    }
+
     #[test]
     fn horizon_grazing_beam_stays_finite() {
         // Regression test: antenna elevation 0 deg (UI slider bound) used to send
@@ -496,6 +868,34 @@ mod tests {
         assert!(footprint.area_m2.is_finite());
     }
 
+    #[test]
+    fn broad_beams_stay_within_the_clamp_range() {
+        // 160° beam at nadir: still a circle, of radius h.tan(80°)
+        let (height, half_beam_width) = (3000.0, 80.0f64);
+        let mut carrier = carrier_state(height, 100.0);
+        let antenna = antenna_state(-90.0);
+        let beam = antenna_beam_state(2.0 * half_beam_width);
+        let mut footprint = AntennaBeamFootprintState::default();
+        let mut mesh = footprint_mesh();
+        carrier_transform_from_state(&mut carrier, &antenna);
+        update_antenna_beam_footprint_mesh_from_state(&carrier, &antenna, &beam, &mut footprint, &mut mesh);
+        let radius = height * half_beam_width.to_radians().tan();
+        assert_close(footprint.ground_max_extent_m, radius, 1e-9);
+        assert_close(footprint.range_max_m, height / half_beam_width.to_radians().cos(), 1e-9);
+
+        // 170° beam looking 30° down: half of it sees the sky, clamped along
+        // the beam edge rays to MAX_BORESIGHT_RANGE_M
+        let antenna = antenna_state(-30.0);
+        let beam = antenna_beam_state(170.0);
+        carrier_transform_from_state(&mut carrier, &antenna);
+        update_antenna_beam_footprint_mesh_from_state(&carrier, &antenna, &beam, &mut footprint, &mut mesh);
+        for point in footprint.points.iter() {
+            assert!(point.is_finite());
+        }
+        assert!(footprint.range_max_m <= MAX_BORESIGHT_RANGE_M * (1.0 + 1e-9), "{}", footprint.range_max_m);
+        assert!(footprint.range_min_m < height / 30f64.to_radians().sin());
+    }
+
     /// Square footprint of half-size 100 m centred on the origin, in Y-up frame.
     fn square_footprint() -> Vec<DVec3> {
         vec![
@@ -7,6 +7,15 @@ use crate::constants::{
     POS_YAXIS_TO_XAXIS, TRANSFORM_TO_Y_UP
 };
 
+/// A part of an axes helper, recolored by the display palette.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AxesHelperPart {
+    X,
+    Y,
+    Z,
+    Origin,
+}
+
 // https://users.rust-lang.org/t/solved-placement-of-mut-in-function-parameters/19891
 pub fn spawn_axes_helper(
     commands: &mut Commands,
@@ -38,10 +47,12 @@ pub fn spawn_axes_helper(
     let xaxis = commands.spawn(( // base
         Mesh3d(meshes.add(cylinder_mesh)),
         MeshMaterial3d(materials.add(RED_MATERIAL.clone())),
+        AxesHelperPart::X,
         Transform::from_rotation(POS_YAXIS_TO_XAXIS) // Rotate to align with X-axis
     )).with_child(( // arrow
         Mesh3d(meshes.add(cone_mesh)),
         MeshMaterial3d(materials.add(RED_MATERIAL.clone())),
+        AxesHelperPart::X,
         Transform::from_translation(0.9 * size * Vec3::Y)
     )).id();
 
@@ -49,9 +60,11 @@ pub fn spawn_axes_helper(
     let yaxis = commands.spawn(( // base
         Mesh3d(meshes.add(cylinder_mesh)),
         MeshMaterial3d(materials.add(GREEN_MATERIAL.clone())),
+        AxesHelperPart::Y,
     )).with_child(( // arrow
         Mesh3d(meshes.add(cone_mesh)),
         MeshMaterial3d(materials.add(GREEN_MATERIAL.clone())),
+        AxesHelperPart::Y,
         Transform::from_translation(0.9 * size * Vec3::Y)
     )).id();
 
@@ -59,12 +72,14 @@ pub fn spawn_axes_helper(
     let zaxis = commands.spawn(( // base
         Mesh3d(meshes.add(cylinder_mesh)),
         MeshMaterial3d(materials.add(BLUE_MATERIAL.clone())),
+        AxesHelperPart::Z,
         Transform::from_rotation(
             Quat::from_rotation_x(FRAC_PI_2) // Rotate to align with Z-axis
         ) 
     )).with_child(( // arrow
         Mesh3d(meshes.add(cone_mesh)),
         MeshMaterial3d(materials.add(BLUE_MATERIAL.clone())),
+        AxesHelperPart::Z,
         Transform::from_translation(0.9 * size * Vec3::Y)
     )).id();
 
@@ -72,7 +87,8 @@ pub fn spawn_axes_helper(
     let mut sphere_base = commands.spawn((
         Mesh3d(meshes.add(Sphere::new(0.0125 * size))),
         MeshMaterial3d(materials.add(YELLOW_MATERIAL.clone())),
-        TRANSFORM_TO_Y_UP // Rotates axes to be Y-up
+        TRANSFORM_TO_Y_UP, // Rotates axes to be Y-up
+        AxesHelperPart::Origin,
     ));
 
     // Axes helper entity
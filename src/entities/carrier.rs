@@ -16,9 +16,13 @@ use crate::{
         spawn_axes_helper,
         spawn_velocity_indicator,
         AntennaBeamFootprintState
-    }
+    },
+    motion::MotionProfile,
 };
 
+/// Beam cone length relative to the farthest footprint point
+const BEAM_CONE_LENGTH_MARGIN: f64 = 1.2;
+
 /// Component marker to identify the Carrier
 #[derive(Component)]
 pub struct Carrier;
@@ -48,7 +52,7 @@ pub struct AntennaBeamAzimuthLine;
 pub struct VelocityVector;
 
 /// Struct to keep the internal state of the Transmitter
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub struct CarrierState {
     /// Carrier orientation in World frame (NED referential)
     pub heading_deg: f64,
@@ -62,25 +66,77 @@ pub struct CarrierState {
     pub position_m: DVec3,
     /// Carrier velocity vector in World frame (Z-up)
     pub velocity_vector_mps: DVec3,
+    /// Carrier acceleration vector in World frame (Z-up), `None` along a
+    /// straight trajectory at constant speed
+    pub acceleration_vector_mps2: Option<DVec3>,
+    /// Carrier time along its trajectory in seconds (timeline), the antenna
+    /// boresight intersects the scene center at time 0 s
+    pub time_s: f64,
+    /// Turn and speed profile along the trajectory, the orientation and the
+    /// velocity above being those at time 0 s
+    pub motion: MotionProfile,
+}
+
+impl CarrierState {
+    /// Gets the carrier rotation from ENU to NED frame + orientation at time 0 s.
+    pub fn initial_rotation(&self) -> DQuat {
+        ENU_TO_NED_F64 * DQuat::from_euler(
+            EulerRot::ZYX,
+            self.heading_deg.to_radians(),
+            self.elevation_deg.to_radians(),
+            self.bank_deg.to_radians()
+        )
+    }
+
+    /// Gets the carrier rotation from ENU to NED frame + orientation at the
+    /// current time, turned by its motion profile.
+    pub fn rotation(&self) -> DQuat {
+        self.motion.turn_at(self.time_s) * self.initial_rotation()
+    }
+
+    /// Gets the carrier velocity vector in World frame (Z-up) at time 0 s.
+    pub fn initial_velocity_vector_mps(&self) -> DVec3 {
+        self.initial_rotation() * DVec3::new(
+            self.velocity_mps, // Carrier velocity follows x-axis in Carrier NED frame
+            0.0,
+            0.0
+        )
+    }
 }
 
 /// Struct to keep the internal state of the Antenna
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub struct AntennaState {
     /// Antenna orientation relative to Carrier
     pub heading_deg: f64,
     pub elevation_deg: f64,
     pub bank_deg: f64,
+    /// Antenna phase center offset from the carrier reference point in the
+    /// Carrier NED frame (x forward, y right, z down), in meters
+    pub lever_arm_m: DVec3,
 }
 
 /// Struct to keep the internal state of the Antenna Beam
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub struct AntennaBeamState {
     pub elevation_beam_width_deg: f64,
     pub azimuth_beam_width_deg: f64,
     pub one_way_gain_dbi: f64,
 }
 
+/// Gain-beamwidth product in square degrees of a typical aperture antenna: the
+/// 41253 deg² of an ideal uniform beam times an aperture efficiency of ~0.63
+const GAIN_BEAM_WIDTHS_PRODUCT_DEG2: f64 = 26_000.0;
+
+impl AntennaBeamState {
+    /// Gets the one-way gain in dBi of a typical aperture antenna with the
+    /// half-power beam widths of this beam: G = 26000 / (θ_az.θ_el), the beam
+    /// widths in degrees.
+    pub fn gain_from_beam_widths_dbi(&self) -> f64 {
+        10.0 * (GAIN_BEAM_WIDTHS_PRODUCT_DEG2 / (self.azimuth_beam_width_deg * self.elevation_beam_width_deg)).log10()
+    }
+}
+
 pub fn spawn_carrier(
     commands: &mut Commands,
     meshes: &mut ResMut<Assets<Mesh>>,
@@ -130,7 +186,6 @@ pub fn spawn_carrier(
     );
     commands
         .entity(antenna_beam_entity)
-        .insert(antenna_beam_transform_from_state(antenna_beam_state))
         .insert(AntennaBeam) // Add AntennaBeam component
         .insert(Name::new(format!("{} Antenna Beam", name)));
 
@@ -169,6 +224,10 @@ pub fn spawn_carrier(
         antenna_beam_footprint_state,
         antenna_beam_footprint_material
     );
+    // Antenna beam transform, its length following the footprint ranges
+    commands
+        .entity(antenna_beam_entity)
+        .insert(antenna_beam_transform_from_state(antenna_beam_state, antenna_beam_footprint_state));
     let antenna_beam_footprint_id = commands
         .entity(antenna_beam_footprint_entity)
         .insert(AntennaBeamFootprint) // Add AntennaBeamFootprint component
@@ -218,13 +277,8 @@ pub fn carrier_transform_from_state(
     // with the ground plane (z = 0) then we apply the inverse translation to get the position
     // of the carrier in the World frame.
 
-    // Carrier rotation from ENU to NED frame + orientation
-    let carrier_rotation = ENU_TO_NED_F64 * DQuat::from_euler(
-        EulerRot::ZYX,
-        carrier_state.heading_deg.to_radians(),
-        carrier_state.elevation_deg.to_radians(),
-        carrier_state.bank_deg.to_radians()
-    );
+    // Carrier rotation from ENU to NED frame + orientation at time 0 s
+    let carrier_rotation = carrier_state.initial_rotation();
     // Antenna rotation relative to Carrier
     let antenna_rotation = DQuat::from_euler(
         EulerRot::ZYX,
@@ -239,33 +293,44 @@ pub fn carrier_transform_from_state(
         DVec3::X // Antenna points towards X-axis in its local frame
     ).normalize();
 
-    let t = if carrier_state.height_m > 0.0 {
+    // The ranges are taken from the antenna phase center, offset from the
+    // carrier reference point (at the carrier height) by the lever arm
+    let phase_center_height_m = (carrier_state.height_m + (carrier_rotation * antenna_state.lever_arm_m).z).max(0.0);
+    let t = if phase_center_height_m > 0.0 {
         // Clamp to keep the carrier position finite when the boresight
         // is horizontal (ax.z ~ 0) or points above the horizon
-        (carrier_state.height_m / ax.z).clamp(-MAX_BORESIGHT_RANGE_M, MAX_BORESIGHT_RANGE_M)
+        (phase_center_height_m / ax.z).clamp(-MAX_BORESIGHT_RANGE_M, MAX_BORESIGHT_RANGE_M)
     } else {
         0.0
     };
 
-    // Update carrier position in World frame (Z-up)
+    // Update carrier velocity vector in World frame (Z-up) for the current timeline time
+    let initial_velocity_vector_mps = carrier_state.initial_velocity_vector_mps();
+    carrier_state.velocity_vector_mps = carrier_state.motion.velocity_at(
+        &initial_velocity_vector_mps,
+        carrier_state.time_s
+    );
+    carrier_state.acceleration_vector_mps2 = carrier_state.motion.acceleration_at(
+        &initial_velocity_vector_mps,
+        carrier_state.time_s
+    );
+    // Update carrier position in World frame (Z-up), moved along its trajectory
+    // for the current timeline time
     carrier_state.position_m = DVec3::new(
         t * ax.x,
         t * ax.y,
-        carrier_state.height_m
-    );
-    // Update carrier velocity vector in World frame (Z-up)
-    carrier_state.velocity_vector_mps =
-        carrier_rotation * DVec3::new(
-            carrier_state.velocity_mps, // Carrier velocity follows x-axis in Carrier NED frame
-            0.0,
-            0.0
-        );
+        phase_center_height_m
+    ) + carrier_state.motion.displacement_at(&initial_velocity_vector_mps, carrier_state.time_s);
+    // Carrier rotation turned along its trajectory
+    let carrier_rotation = carrier_state.rotation();
+    // Carrier reference point, the antenna being placed at its lever arm
+    let reference_point_m = carrier_state.position_m - carrier_rotation * antenna_state.lever_arm_m;
 
     Transform {
         translation: TO_Y_UP * Vec3::new( // Transforms from Z-up to Y-up
-            carrier_state.position_m.x as f32,
-            carrier_state.position_m.y as f32,
-            carrier_state.position_m.z as f32
+            reference_point_m.x as f32,
+            reference_point_m.y as f32,
+            reference_point_m.z as f32
         ),
         rotation: TO_Y_UP * Quat::from_xyzw( // Transforms from Z-up to Y-up
             carrier_rotation.x as f32,
@@ -277,6 +342,44 @@ pub fn carrier_transform_from_state(
     }
 }
 
+/// Gets `antenna_state` oriented relative to the carrier, at time 0 s, so that
+/// its boresight from `position_m` (World frame, Z-up) intersects the scene
+/// center, its bank and lever arm being kept.
+///
+/// Placed by [`carrier_transform_from_state`] with its phase center at
+/// `position_m` when the carrier height is the one of `position_m` less the
+/// lever arm height.
+pub fn antenna_state_pointing_to_center(
+    carrier_state: &CarrierState,
+    position_m: &DVec3,
+    antenna_state: &AntennaState,
+) -> AntennaState {
+    // Boresight direction in the carrier NED frame
+    let direction = carrier_state.initial_rotation().inverse() * -position_m.normalize();
+    AntennaState {
+        heading_deg: direction.y.atan2(direction.x).to_degrees(),
+        // Elevation is positive upwards, i.e. towards -z in NED frame
+        elevation_deg: (-direction.z).clamp(-1.0, 1.0).asin().to_degrees(),
+        ..antenna_state.clone()
+    }
+}
+
+/// Gets the carrier state seen from the phase center of `antenna_state`, an
+/// antenna of the carrier whose position is the phase center of
+/// `reference_antenna_state`, e.g. to compute the footprint of another antenna
+/// of the carrier.
+pub fn carrier_state_at_antenna(
+    carrier_state: &CarrierState,
+    reference_antenna_state: &AntennaState,
+    antenna_state: &AntennaState,
+) -> CarrierState {
+    CarrierState {
+        position_m: carrier_state.position_m
+            + carrier_state.rotation() * (antenna_state.lever_arm_m - reference_antenna_state.lever_arm_m),
+        ..carrier_state.clone()
+    }
+}
+
 /// Computes antenna transform from antenna state
 /// related to carrier NED frame
 pub fn antenna_transform_from_state(
@@ -289,24 +392,38 @@ pub fn antenna_transform_from_state(
         antenna_state.bank_deg.to_radians() as f32
     );
     // Note: we don't apply ENU_TO_NED here because the antenna is already in the NED frame
-    Transform::from_rotation(rotation)
+    Transform::from_rotation(rotation).with_translation(antenna_state.lever_arm_m.as_vec3())
 }
 
+/// Computes the antenna beam cone transform from the antenna beam state, the
+/// cone edges reaching the farthest footprint point (plus a margin) rather than
+/// the full [`CONE_LENGTH`], so that low-altitude scenes are not dominated by
+/// kilometer-long cones.
 pub fn antenna_beam_transform_from_state(
-    antenna_beam_state: &AntennaBeamState
+    antenna_beam_state: &AntennaBeamState,
+    antenna_beam_footprint_state: &AntennaBeamFootprintState,
 ) -> Transform {
+    // Cone edge length, the full length when the footprint is not (yet) known
+    let range_max_m = antenna_beam_footprint_state.range_max_m;
+    let edge_length = if range_max_m.is_finite() && range_max_m > 0.0 {
+        (BEAM_CONE_LENGTH_MARGIN * range_max_m).min(CONE_LENGTH)
+    } else {
+        CONE_LENGTH
+    };
+    // Cone height such that its longest edge has the above length: broad beams
+    // (up to MAX_BEAM_WIDTH_DEG) give short and wide cones instead of
+    // unbounded tan-scaled bases
+    let half_azi = 0.5 * antenna_beam_state.azimuth_beam_width_deg.to_radians();
+    let half_elv = 0.5 * antenna_beam_state.elevation_beam_width_deg.to_radians();
+    let cone_length = edge_length * half_azi.max(half_elv).cos();
     // Compute scale factors for cone base, based on beam widths
-    let scale_azi = CONE_LENGTH * (
-        0.5 * antenna_beam_state.azimuth_beam_width_deg.to_radians()
-    ).tan();
-    let scale_elv = CONE_LENGTH * (
-        0.5 * antenna_beam_state.elevation_beam_width_deg.to_radians()
-    ).tan();
+    let scale_azi = cone_length * half_azi.tan();
+    let scale_elv = cone_length * half_elv.tan();
 
     Transform {
         translation: Vec3::ZERO,
         rotation: NEG_YAXIS_TO_XAXIS,
-        scale: Vec3::new(scale_azi as f32, 1.0, scale_elv as f32)
+        scale: Vec3::new(scale_azi as f32, (cone_length / CONE_LENGTH) as f32, scale_elv as f32)
     }
 }
 
@@ -314,7 +431,7 @@ pub fn antenna_beam_transform_from_state(
 pub fn velocity_indicator_transform_from_state(
     carrier_state: &CarrierState
 ) -> Transform {
-    let x = carrier_state.velocity_mps;
+    let x = carrier_state.velocity_vector_mps.length(); // Current speed along the trajectory
     let scale = if x <= 150.0 { // = CARRIER_SIZE
         let length = 150.0 * x.ln_1p() / 150.0f64.ln_1p() ;// logarithmic growth: F(x) = ymax * ln(1 + x) / ln(1 + xmax)
         Vec3::new(1.0, length as f32, 1.0)
@@ -335,20 +452,17 @@ pub fn velocity_indicator_transform_from_state(
 pub fn update_velocity_vector(
     carrier_state: &mut CarrierState
 ) {
-    // Carrier rotation from ENU to NED frame + orientation
-    let carrier_rotation = ENU_TO_NED_F64 * DQuat::from_euler(
-        EulerRot::ZYX,
-        carrier_state.heading_deg.to_radians(),
-        carrier_state.elevation_deg.to_radians(),
-        carrier_state.bank_deg.to_radians()
+    // Update carrier velocity and acceleration vectors in World frame (Z-up)
+    // for the current timeline time
+    let initial_velocity_vector_mps = carrier_state.initial_velocity_vector_mps();
+    carrier_state.velocity_vector_mps = carrier_state.motion.velocity_at(
+        &initial_velocity_vector_mps,
+        carrier_state.time_s
+    );
+    carrier_state.acceleration_vector_mps2 = carrier_state.motion.acceleration_at(
+        &initial_velocity_vector_mps,
+        carrier_state.time_s
     );
-    // Update carrier velocity vector in World frame (Z-up)
-    carrier_state.velocity_vector_mps =
-        carrier_rotation * DVec3::new(
-            carrier_state.velocity_mps, // Carrier velocity follows x-axis in Carrier NED frame
-            0.0,
-            0.0
-        );
 }
 #[cfg(test)]
 mod tests {
@@ -373,8 +487,11 @@ mod tests {
             velocity_mps: 100.0,
             position_m: DVec3::ZERO,
             velocity_vector_mps: DVec3::ZERO,
+            acceleration_vector_mps2: None,
+            time_s: 0.0,
+            motion: MotionProfile::default(),
         };
-        let antenna = AntennaState { heading_deg: 90.0, elevation_deg: -45.0, bank_deg: 0.0 };
+        let antenna = AntennaState { heading_deg: 90.0, elevation_deg: -45.0, bank_deg: 0.0, lever_arm_m: DVec3::ZERO };
         carrier_transform_from_state(&mut carrier, &antenna);
         // Carrier heading North looking East(-down): placed West of the target
         assert_close(carrier.position_m.x, -3000.0, 1e-9);
@@ -386,6 +503,65 @@ mod tests {
         assert_close(carrier.velocity_vector_mps.z, 0.0, 1e-9);
     }
 
+    #[test]
+    fn carrier_moves_along_velocity_vector_with_time() {
+        let mut carrier = CarrierState {
+            heading_deg: 90.0,
+            elevation_deg: 0.0,
+            bank_deg: 0.0,
+            height_m: 3000.0,
+            velocity_mps: 100.0,
+            position_m: DVec3::ZERO,
+            velocity_vector_mps: DVec3::ZERO,
+            acceleration_vector_mps2: None,
+            time_s: 0.0,
+            motion: MotionProfile::default(),
+        };
+        let antenna = AntennaState { heading_deg: 90.0, elevation_deg: -45.0, bank_deg: 0.0, lever_arm_m: DVec3::ZERO };
+        carrier_transform_from_state(&mut carrier, &antenna);
+        let position_t0 = carrier.position_m;
+        carrier.time_s = 12.5;
+        let transform = carrier_transform_from_state(&mut carrier, &antenna);
+        // Heading East: 1250 m further East, same height
+        assert_close(carrier.position_m.x - position_t0.x, 1250.0, 1e-9);
+        assert_close(carrier.position_m.y, position_t0.y, 1e-9);
+        assert_close(carrier.position_m.z, 3000.0, 1e-9);
+        assert_close(transform.translation.distance(crate::constants::TO_Y_UP * carrier.position_m.as_vec3()) as f64, 0.0, 1e-2);
+    }
+
+    #[test]
+    fn turning_carrier_follows_its_motion_profile() {
+        // North at 100 m/s turning right at 6°/s: heading East after 15 s,
+        // a quarter of a circle of radius 100 / (6° in rad) further
+        let mut carrier = CarrierState {
+            heading_deg: 0.0,
+            elevation_deg: 0.0,
+            bank_deg: 0.0,
+            height_m: 3000.0,
+            velocity_mps: 100.0,
+            position_m: DVec3::ZERO,
+            velocity_vector_mps: DVec3::ZERO,
+            acceleration_vector_mps2: None,
+            time_s: 0.0,
+            motion: MotionProfile { turn_rate_degps: 6.0, speed_rate_mps2: 0.0 },
+        };
+        let antenna = AntennaState { heading_deg: 90.0, elevation_deg: -45.0, bank_deg: 0.0, lever_arm_m: DVec3::ZERO };
+        carrier_transform_from_state(&mut carrier, &antenna);
+        let position_t0 = carrier.position_m;
+        carrier.time_s = 15.0;
+        let transform = carrier_transform_from_state(&mut carrier, &antenna);
+        let radius_m = 100.0 / 6f64.to_radians();
+        assert_close(carrier.position_m.x - position_t0.x, radius_m, 1e-6);
+        assert_close(carrier.position_m.y - position_t0.y, radius_m, 1e-6);
+        assert_close(carrier.position_m.z, 3000.0, 1e-9);
+        assert_close(carrier.velocity_vector_mps.x, 100.0, 1e-9);
+        assert_close(carrier.velocity_vector_mps.y, 0.0, 1e-9);
+        // Same orientation as a carrier heading East
+        let mut heading_east = CarrierState { heading_deg: 90.0, time_s: 0.0, ..carrier.clone() };
+        let transform_east = carrier_transform_from_state(&mut heading_east, &antenna);
+        assert!(transform.rotation.angle_between(transform_east.rotation) < 1e-3);
+    }
+
     #[test]
     fn horizontal_boresight_is_clamped_finite() {
         // Regression test: a horizontal boresight (ax.z = 0) used to place the
@@ -398,8 +574,11 @@ mod tests {
             velocity_mps: 100.0,
             position_m: DVec3::ZERO,
             velocity_vector_mps: DVec3::ZERO,
+            acceleration_vector_mps2: None,
+            time_s: 0.0,
+            motion: MotionProfile::default(),
         };
-        let antenna = AntennaState { heading_deg: 0.0, elevation_deg: 0.0, bank_deg: 0.0 };
+        let antenna = AntennaState { heading_deg: 0.0, elevation_deg: 0.0, bank_deg: 0.0, lever_arm_m: DVec3::ZERO };
         let transform = carrier_transform_from_state(&mut carrier, &antenna);
         assert!(carrier.position_m.is_finite());
         assert!(transform.translation.is_finite());
@@ -407,4 +586,95 @@ mod tests {
         let ground_offset = (carrier.position_m.x.powi(2) + carrier.position_m.y.powi(2)).sqrt();
         assert!(ground_offset <= crate::constants::MAX_BORESIGHT_RANGE_M);
     }
+
+    #[test]
+    fn antenna_pointing_to_center_places_the_carrier() {
+        // Pitched and banked carrier: the pointing is solved in its own frame
+        let mut carrier = CarrierState {
+            heading_deg: 30.0,
+            elevation_deg: 5.0,
+            bank_deg: 3.0,
+            height_m: 3000.0,
+            velocity_mps: 100.0,
+            position_m: DVec3::ZERO,
+            velocity_vector_mps: DVec3::ZERO,
+            acceleration_vector_mps2: None,
+            time_s: 0.0,
+            motion: MotionProfile::default(),
+        };
+        let antenna = AntennaState { heading_deg: 80.0, elevation_deg: -40.0, bank_deg: 2.0, lever_arm_m: DVec3::ZERO };
+        carrier_transform_from_state(&mut carrier, &antenna);
+        // The antenna pointing from the carrier position is found again
+        let pointing = antenna_state_pointing_to_center(&carrier, &carrier.position_m, &antenna);
+        assert_close(pointing.heading_deg, antenna.heading_deg, 1e-9);
+        assert_close(pointing.elevation_deg, antenna.elevation_deg, 1e-9);
+        assert_close(pointing.bank_deg, antenna.bank_deg, 0.0);
+        // Moved 200 m North at the same height, the carrier is placed there
+        let position_m = carrier.position_m + DVec3::new(0.0, 200.0, 0.0);
+        let pointing = antenna_state_pointing_to_center(&carrier, &position_m, &antenna);
+        carrier_transform_from_state(&mut carrier, &pointing);
+        assert!(carrier.position_m.distance(position_m) < 1e-6);
+    }
+
+    #[test]
+    fn antenna_lever_arm_offsets_the_phase_center() {
+        // Antenna 10 m forward and 5 m below the reference point of a
+        // carrier heading North at 3000 m
+        let mut carrier = CarrierState {
+            heading_deg: 0.0,
+            elevation_deg: 0.0,
+            bank_deg: 0.0,
+            height_m: 3000.0,
+            velocity_mps: 100.0,
+            position_m: DVec3::ZERO,
+            velocity_vector_mps: DVec3::ZERO,
+            acceleration_vector_mps2: None,
+            time_s: 0.0,
+            motion: MotionProfile::default(),
+        };
+        let antenna = AntennaState { heading_deg: 90.0, elevation_deg: -45.0, bank_deg: 0.0, lever_arm_m: DVec3::new(10.0, 0.0, 5.0) };
+        let transform = carrier_transform_from_state(&mut carrier, &antenna);
+        // The boresight from the phase center, 2995 m high, hits the scene center
+        assert_close(carrier.position_m.x, -2995.0, 1e-9);
+        assert_close(carrier.position_m.y, 0.0, 1e-9);
+        assert_close(carrier.position_m.z, 2995.0, 1e-9);
+        // The carrier reference point lies 10 m behind and 5 m above it
+        let reference_point = TO_Y_UP.inverse() * transform.translation;
+        assert!(reference_point.distance(Vec3::new(-2995.0, -10.0, 3000.0)) < 1e-2);
+        assert_eq!(antenna_transform_from_state(&antenna).translation, Vec3::new(10.0, 0.0, 5.0));
+    }
+
+    #[test]
+    fn aft_antenna_phase_center_lies_behind() {
+        let mut carrier = CarrierState {
+            heading_deg: 90.0,
+            elevation_deg: 0.0,
+            bank_deg: 0.0,
+            height_m: 3000.0,
+            velocity_mps: 100.0,
+            position_m: DVec3::ZERO,
+            velocity_vector_mps: DVec3::ZERO,
+            acceleration_vector_mps2: None,
+            time_s: 0.0,
+            motion: MotionProfile::default(),
+        };
+        let fore = AntennaState { heading_deg: 90.0, elevation_deg: -45.0, bank_deg: 0.0, lever_arm_m: DVec3::new(2.0, 0.0, 0.0) };
+        let aft = AntennaState { lever_arm_m: DVec3::new(-2.0, 0.0, 0.0), ..fore.clone() };
+        carrier_transform_from_state(&mut carrier, &fore);
+        // Heading East: the aft phase center lies 4 m West of the fore one
+        let aft_carrier = carrier_state_at_antenna(&carrier, &fore, &aft);
+        assert!((aft_carrier.position_m - carrier.position_m).distance(DVec3::new(-4.0, 0.0, 0.0)) < 1e-9);
+        assert_eq!(aft_carrier.velocity_vector_mps, carrier.velocity_vector_mps);
+        // Seen from the fore antenna itself, unchanged
+        assert_eq!(carrier_state_at_antenna(&carrier, &fore, &fore).position_m, carrier.position_m);
+    }
+
+    #[test]
+    fn gain_from_beam_widths() {
+        let beam = AntennaBeamState { elevation_beam_width_deg: 10.0, azimuth_beam_width_deg: 2.6, one_way_gain_dbi: 0.0 };
+        assert_close(beam.gain_from_beam_widths_dbi(), 30.0, 1e-12);
+        // Halving a beam width doubles the gain
+        let narrow = AntennaBeamState { azimuth_beam_width_deg: 1.3, ..beam.clone() };
+        assert_close(narrow.gain_from_beam_widths_dbi() - beam.gain_from_beam_widths_dbi(), 10.0 * 2f64.log10(), 1e-12);
+    }
 }
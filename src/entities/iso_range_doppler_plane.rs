@@ -1,7 +1,7 @@
 use bevy::{
     asset::RenderAssetUsages,
     ecs::query::QueryFilter,
-    math::DVec3,
+    math::{DVec2, DVec3},
     prelude::*,
     render::render_resource::{Extent3d, TextureDimension, TextureFormat}
 };
@@ -10,20 +10,18 @@ use crate::{
     contour::{march_levels, Field},
     constants::HALF_PLANE_LENGTH,
     entities::AntennaBeamFootprintState,
+    palette::ColorPalette,
     raster::{draw_polyline_bgrx, fill_bgrx},
     scene::{IsoRangeDopplerPlane, TxCarrierState, RxCarrierState},
     textdraw::draw_text_bgrx,
 };
 
 const MAX_PLANE_LENGTH: f64 = 2.0 * HALF_PLANE_LENGTH as f64;
+const MIN_PLANE_LENGTH: f64 = 1.0;
 const TEXTURE_WIDTH: usize  = 2048;
 const TEXTURE_HEIGHT: usize = 2048;
 const GRID_SIZE: usize = 151; // 251; // Note: with anti-aliasing, 151² grid points is large enough to produce a 2048² texture with no visible pixelation
 const NLEVELS: usize = 50;
-// Colors (R, G, B) for the ground, the IsoRange and the IsoDoppler contours
-const GROUND_GREY_RGB: (u8, u8, u8) = (128, 128, 128);
-const ISO_RANGE_RGB: (u8, u8, u8) = (214, 39, 40);
-const ISO_DOPPLER_RGB: (u8, u8, u8) = (31, 119, 180);
 // Stroke widths in texture pixels. The iso-Doppler lines are thinner so the two
 // families stay distinguishable where they cross (BSARConf weights them 2:1).
 const ISO_RANGE_STROKE_PX: f32 = 6.0;
@@ -38,6 +36,97 @@ const LABEL_MIN_SPACING_PX: f32 = 220.0;
 // Ground-colored halo around a label, interrupting the contour it sits on.
 const LABEL_PADDING_PX: f32 = 8.0;
 
+/// Contours of one level: the level value and its polylines in the ENU ground
+/// plane (m).
+pub type LevelContours = (f64, Vec<Vec<DVec2>>);
+
+/// Contours of the iso-range/Doppler plane in meters, in the ENU ground plane
+/// of the scene (not in texture pixels), refreshed with the plane so that
+/// the exports and the 3D views share them instead of re-deriving them.
+#[derive(Resource, Default)]
+pub struct IsoContours {
+    /// Iso-range contours, bistatic range in m
+    pub iso_range: Vec<LevelContours>,
+    /// Iso-Doppler contours, Doppler frequency in Hz
+    pub iso_doppler: Vec<LevelContours>,
+}
+
+impl IsoContours {
+    /// Gets the polylines of `contours` in World frame (Y-up), at `height_m`
+    /// above the ground plane.
+    pub fn world_polylines(contours: &[LevelContours], height_m: f64) -> Vec<Vec<Vec3>> {
+        contours.iter()
+            .flat_map(|(_, lines)| lines.iter())
+            .map(|line| line.iter().map(|&point| enu_to_world(point, height_m)).collect())
+            .collect()
+    }
+
+    /// Measures the local spacing of `contours` along the line through `point`
+    /// (ENU, m) of direction `direction`: the two consecutive crossings of
+    /// different levels closest to `point`. `None` when fewer than two levels
+    /// cross the line.
+    pub fn local_spacing(contours: &[LevelContours], point: DVec2, direction: DVec2) -> Option<ContourSpacing> {
+        let direction = direction.try_normalize()?;
+        let normal = direction.perp();
+        // Abscissas along the line of the crossings, with their level
+        let mut crossings: Vec<(f64, f64)> = contours.iter()
+            .flat_map(|(level, lines)| lines.iter().map(move |line| (*level, line)))
+            .flat_map(|(level, line)| line.windows(2).map(move |segment| (level, segment[0], segment[1])))
+            .filter_map(|(level, a, b)| {
+                let (da, db) = (normal.dot(a - point), normal.dot(b - point));
+                // Half-open test: a vertex shared by two segments counts once
+                ((da < 0.0) != (db < 0.0)).then(|| {
+                    let crossing = a + (b - a) * (da / (da - db));
+                    (direction.dot(crossing - point), level)
+                })
+            })
+            .collect();
+        crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+        crossings.windows(2)
+            .filter(|pair| pair[0].1 != pair[1].1)
+            .min_by(|a, b| (a[0].0 + a[1].0).abs().total_cmp(&(b[0].0 + b[1].0).abs()))
+            .map(|pair| ContourSpacing {
+                spacing_m: pair[1].0 - pair[0].0,
+                level_step: pair[1].1 - pair[0].1,
+            })
+    }
+}
+
+/// Local spacing of the contours of a field, see [`IsoContours::local_spacing`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ContourSpacing {
+    /// Distance between two consecutive contours in m
+    pub spacing_m: f64,
+    /// Level difference between the two contours, signed along the direction
+    pub level_step: f64,
+}
+
+impl ContourSpacing {
+    /// Gets the field gradient along the direction, in level unit per meter.
+    pub fn gradient(&self) -> f64 {
+        self.level_step / self.spacing_m
+    }
+}
+
+/// Gets the World frame (Y-up) point of the ENU ground `point` (East, North),
+/// at `height_m` above the ground plane.
+#[inline]
+pub fn enu_to_world(point: DVec2, height_m: f64) -> Vec3 {
+    Vec3::new(point.y as f32, height_m as f32, point.x as f32)
+}
+
+/// Refreshes the [`IsoContours`] when the plane changes.
+pub fn update_iso_contours(
+    iso_range_doppler_plane_state: Res<IsoRangeDopplerPlaneState>,
+    mut iso_contours: ResMut<IsoContours>,
+) {
+    if !iso_range_doppler_plane_state.is_changed() {
+        return;
+    }
+    iso_contours.iso_range = iso_range_doppler_plane_state.iso_range_contours_enu();
+    iso_contours.iso_doppler = iso_range_doppler_plane_state.iso_doppler_contours_enu();
+}
+
 /// A pending contour label: value text at a grid-coordinate anchor, drawn into
 /// the pixel buffer after the plotters drawing area is released.
 struct Label {
@@ -122,24 +211,28 @@ pub fn iso_range_doppler_plane_transform_from_state(
 ) -> Result<Transform, Box<dyn std::error::Error>> {
     let lem = SPEED_OF_LIGHT_IN_VACUUM /
         (tx_carrier_state.center_frequency_ghz * 1e9); // wavelength λ [m] (= c/f, consistent with bsar.rs)
-    let extent = f64::min(
-        MAX_PLANE_LENGTH,
-        2.1 * tx_antenna_beam_footprint_state.ground_max_extent_m.max(
-            rx_antenna_beam_footprint_state.ground_max_extent_m
-        )
-    );
+    let (center, extent) = match iso_range_doppler_plane_state.window {
+        PlaneWindow::Footprints => (
+            DVec2::ZERO,
+            2.1 * tx_antenna_beam_footprint_state.ground_max_extent_m.max(
+                rx_antenna_beam_footprint_state.ground_max_extent_m
+            )
+        ),
+        PlaneWindow::Region { center_m, extent_m } => (center_m, extent_m),
+    };
+    let extent = extent.clamp(MIN_PLANE_LENGTH, MAX_PLANE_LENGTH);
     // Update the texture of the IsoRangeDopplerPlaneState
     iso_range_doppler_plane_state.update_texture(
         &tx_carrier_state.inner.position_m, // OT in world frame
         &tx_carrier_state.inner.velocity_vector_mps, // VT in world frame
         &rx_carrier_state.inner.position_m, // OR in world frame
         &rx_carrier_state.inner.velocity_vector_mps, // VR in world frame
-        lem, extent,
+        lem, center, extent,
         image
     )?;
     // Update the transform of the IsoRangeDopplerPlaneState
     let transform = Transform {
-        translation: Vec3::new(0.0, 0.1, 0.0), // Slightly above the ground
+        translation: Vec3::new(center.y as f32, 0.1, center.x as f32), // Slightly above the ground
         rotation: Quat::from_rotation_y(-std::f32::consts::FRAC_PI_2), // Rotate 90 degrees around Y-axis
         scale: Vec3::new(extent as f32, 1.0, extent as f32),
     };
@@ -185,10 +278,43 @@ pub fn refresh_iso_range_doppler_plane<F: QueryFilter>(
     }
 }
 
+/// Bistatic geometry sampled by the plane fields: carriers positions and
+/// velocities in the ENU frame, and wavelength.
+#[derive(Clone, Copy)]
+struct PlaneGeometry {
+    ot: DVec3,
+    vt: DVec3,
+    or: DVec3,
+    vr: DVec3,
+    lem: f64,
+}
+
+/// Ground window over which the plane fields are evaluated, the contours
+/// resolution following its size.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum PlaneWindow {
+    /// Centered on the scene center, 2.1 times the largest footprint extent
+    #[default]
+    Footprints,
+    /// Square of side `extent_m` centered on `center_m` (East, North) in
+    /// meters, following the camera view or a region of interest
+    Region { center_m: DVec2, extent_m: f64 },
+}
+
 #[derive(Resource)]
 pub struct IsoRangeDopplerPlaneState {
     iso_range: IsoRange,
     iso_doppler: IsoDoppler,
+    /// Center of the plane (East, North) in meters
+    center_m: DVec2,
+    /// Side of the square plane in meters
+    extent_m: f64,
+    geometry: PlaneGeometry,
+    /// Evaluation window, applied at the next plane update
+    pub window: PlaneWindow,
+    /// Colors of the ground and of the contours, applied at the next plane
+    /// update
+    pub palette: ColorPalette,
 }
 
 impl Default for IsoRangeDopplerPlaneState {
@@ -208,11 +334,81 @@ impl Default for IsoRangeDopplerPlaneState {
                 GRID_SIZE,
                 GRID_SIZE
             ),
+            center_m: DVec2::ZERO,
+            extent_m: 1000.0,
+            geometry: PlaneGeometry {
+                ot: DVec3::ZERO,
+                vt: DVec3::ONE,
+                or: DVec3::ZERO,
+                vr: DVec3::ONE,
+                lem: 0.3,
+            },
+            window: PlaneWindow::default(),
+            palette: ColorPalette::default(),
         }
     }
 }
 
 impl IsoRangeDopplerPlaneState {
+    /// Gets the iso-range contours (bistatic range in m) of the plane, at the
+    /// levels it draws, in the ENU ground plane.
+    pub fn iso_range_contours_enu(&self) -> Vec<LevelContours> {
+        self.contours_enu(&self.iso_range, &self.iso_range.levels(NLEVELS))
+    }
+
+    /// Gets the iso-Doppler contours (Doppler frequency in Hz) of the plane, at
+    /// the levels it draws, in the ENU ground plane.
+    pub fn iso_doppler_contours_enu(&self) -> Vec<LevelContours> {
+        self.contours_enu(&self.iso_doppler, &self.iso_doppler.levels(NLEVELS))
+    }
+
+    /// Gets the iso-Doppler contours of the plane at `doppler_hz` in the ENU
+    /// ground plane.
+    pub fn iso_doppler_contours_at_enu(&self, doppler_hz: f64) -> Option<LevelContours> {
+        self.contours_enu(&self.iso_doppler, &[doppler_hz]).pop()
+    }
+
+    /// Gets the contours of the plane passing through the ENU ground `point`:
+    /// the iso-range contours at its bistatic range (m) and the iso-Doppler
+    /// contours at its Doppler frequency (Hz), or `None` outside the plane.
+    pub fn probe_contours_enu(&self, point: DVec2) -> Option<(LevelContours, LevelContours)> {
+        if (point - self.center_m).abs().max_element() > 0.5 * self.extent_m {
+            return None;
+        }
+        let PlaneGeometry { ot, vt, or, vr, lem } = self.geometry;
+        let op = point.extend(0.0);
+        let range_m = bistatic_range_sg(&(op - ot), &(op - or));
+        let doppler_hz = doppler_frequency_sg(lem, &(op - ot), &vt, &(op - or), &vr);
+        let iso_range_contours = self.contours_enu(&self.iso_range, &[range_m]).pop()?;
+        let iso_doppler_contours = self.contours_enu(&self.iso_doppler, &[doppler_hz]).pop()?;
+        Some((iso_range_contours, iso_doppler_contours))
+    }
+
+    /// Gets the evaluation window in use: its center (East, North) and side in
+    /// meters.
+    #[inline]
+    pub fn window_m(&self) -> (DVec2, f64) {
+        (self.center_m, self.extent_m)
+    }
+
+    /// Maps the grid contours of `field` onto the ENU ground plane: column 0 is
+    /// the West edge, row 0 the North edge.
+    fn contours_enu(&self, field: &impl Field, levels: &[f64]) -> Vec<LevelContours> {
+        let (width, height) = field.dimensions();
+        let half_extent = 0.5 * self.extent_m;
+        let dx = self.extent_m / (width - 1) as f64;
+        let dy = self.extent_m / (height - 1) as f64;
+        let center = self.center_m;
+        levels.iter().zip(march_levels(field, levels)).map(|(&level, contours)| {
+            let lines = contours.into_iter().map(|line| {
+                line.into_iter()
+                    .map(|(col, row)| center + DVec2::new(-half_extent + col * dx, half_extent - row * dy))
+                    .collect()
+            }).collect();
+            (level, lines)
+        }).collect()
+    }
+
     fn update_texture(
         &mut self,
         ot: &DVec3,
@@ -220,9 +416,17 @@ impl IsoRangeDopplerPlaneState {
         or: &DVec3,
         vr: &DVec3,
         lem: f64,
+        center: DVec2,
         extent: f64,
         image: &mut Image
     ) -> Result<(), Box<dyn std::error::Error>> {
+        self.center_m = center;
+        self.extent_m = extent;
+        self.geometry = PlaneGeometry { ot: *ot, vt: *vt, or: *or, vr: *vr, lem };
+        // The fields depend on the carriers positions relative to the ground
+        // points: the grid centered on the window center samples them with the
+        // carriers moved by the opposite offset
+        let (ot, or) = (&(ot - center.extend(0.0)), &(or - center.extend(0.0)));
         // Update iso-range data
         self.iso_range.update_data(
             ot, or, extent
@@ -237,7 +441,7 @@ impl IsoRangeDopplerPlaneState {
         // Value labels: adaptive unit per family, one label per level
         let format_range = label_formatter(&iso_range_levels, "m", "km");
         let format_doppler = label_formatter(&iso_doppler_levels, "Hz", "kHz");
-        //
+        let colors = self.palette.colors();
         if let Some(ref mut bytes) = image.data {
             let mut labels: Vec<Label> = Vec::new();
             // Grid coordinates map linearly onto the whole texture, row 0 at the
@@ -251,7 +455,7 @@ impl IsoRangeDopplerPlaneState {
                     .collect()
             };
 
-            fill_bgrx(bytes, GROUND_GREY_RGB);
+            fill_bgrx(bytes, colors.ground_rgb);
             // Contours of every level in a single pass over each grid
             let iso_range_contours = march_levels(&self.iso_range, &iso_range_levels);
             let iso_doppler_contours = march_levels(&self.iso_doppler, &iso_doppler_levels);
@@ -268,7 +472,7 @@ impl IsoRangeDopplerPlaneState {
                         TEXTURE_HEIGHT,
                         &to_pixels(&line),
                         ISO_RANGE_STROKE_PX,
-                        ISO_RANGE_RGB,
+                        colors.iso_range_rgb,
                         None,
                     );
                 }
@@ -279,7 +483,7 @@ impl IsoRangeDopplerPlaneState {
                         text: format_range(level),
                         anchor,
                         tangent,
-                        color: ISO_RANGE_RGB,
+                        color: colors.iso_range_rgb,
                     });
                 }
             }
@@ -296,7 +500,7 @@ impl IsoRangeDopplerPlaneState {
                         TEXTURE_HEIGHT,
                         &to_pixels(&line),
                         ISO_DOPPLER_STROKE_PX,
-                        ISO_DOPPLER_RGB,
+                        colors.iso_doppler_rgb,
                         (level < 0.0).then_some(ISO_DOPPLER_DASH_PX),
                     );
                 }
@@ -307,7 +511,7 @@ impl IsoRangeDopplerPlaneState {
                         text: format_doppler(level),
                         anchor,
                         tangent,
-                        color: ISO_DOPPLER_RGB,
+                        color: colors.iso_doppler_rgb,
                     });
                 }
             }
@@ -346,7 +550,7 @@ impl IsoRangeDopplerPlaneState {
                     LABEL_FONT_SIZE,
                     label.color,
                     // Ground-colored halo interrupting the contour underneath
-                    Some(GROUND_GREY_RGB),
+                    Some(colors.ground_rgb),
                     LABEL_PADDING_PX,
                     &label.text,
                 );
@@ -570,6 +774,7 @@ mod tests {
                 &DVec3::new(3000.0, 0.0, 4000.0),
                 &DVec3::new(0.0, 100.0, 0.0),
                 0.03,
+                DVec2::ZERO,
                 20_000.0,
                 &mut image,
             )
@@ -581,7 +786,141 @@ mod tests {
             .any(|px| px[0] != 128 || px[1] != 128 || px[2] != 128));
     }
 
+    /// The exported contours lie on their level once mapped back to ENU: the
+    /// grid to ground mapping must match the one used to sample the fields.
+    #[test]
+    fn contours_enu_lie_on_their_level() {
+        let mut state = IsoRangeDopplerPlaneState::default();
+        let mut image = Image::new_fill(
+            Extent3d {
+                width: TEXTURE_WIDTH as u32,
+                height: TEXTURE_HEIGHT as u32,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            &[0, 0, 0, 0],
+            TextureFormat::Bgra8UnormSrgb,
+            RenderAssetUsages::MAIN_WORLD,
+        );
+        let (ot, or) = (DVec3::new(0.0, -8000.0, 6000.0), DVec3::new(3000.0, 0.0, 4000.0));
+        state
+            .update_texture(&ot, &DVec3::new(150.0, 0.0, 0.0), &or, &DVec3::new(0.0, 100.0, 0.0), 0.03, DVec2::ZERO, 20_000.0, &mut image)
+            .unwrap();
+        let contours = state.iso_range_contours_enu();
+        assert_eq!(contours.len(), NLEVELS);
+        let mut num_points = 0;
+        for (level, lines) in contours.iter() {
+            for point in lines.iter().flatten() {
+                let op = point.extend(0.0);
+                let range_m = bistatic_range_sg(&(op - ot), &(op - or));
+                assert!((range_m - level).abs() < 1e-3 * level, "{range_m} != {level} at {point}");
+                assert!(point.abs().max_element() <= 10_000.0 + 1e-9);
+                num_points += 1;
+            }
+        }
+        assert!(num_points > 0);
+    }
+
+    /// The World frame polylines are the ENU contours, East along +Z and North
+    /// along +X.
+    #[test]
+    fn world_polylines_of_the_enu_contours() {
+        let contours = vec![(1.0, vec![vec![DVec2::new(10.0, 20.0), DVec2::new(-30.0, 40.0)]])];
+        let polylines = IsoContours::world_polylines(&contours, 0.5);
+        assert_eq!(polylines, vec![vec![Vec3::new(20.0, 0.5, 10.0), Vec3::new(40.0, 0.5, -30.0)]]);
+    }
+
+    /// The local spacing is measured between the crossings nearest the point,
+    /// along the given direction.
+    #[test]
+    fn local_spacing_of_straight_contours() {
+        let contours: Vec<LevelContours> = [0.0, 10.0, 20.0].iter()
+            .map(|&x| (0.5 * x, vec![vec![DVec2::new(x, -50.0), DVec2::new(x, 0.0), DVec2::new(x, 50.0)]]))
+            .collect();
+        let point = DVec2::new(3.0, 1.0);
+        let spacing = IsoContours::local_spacing(&contours, point, DVec2::X).unwrap();
+        assert_eq!(spacing, ContourSpacing { spacing_m: 10.0, level_step: 5.0 });
+        let spacing = IsoContours::local_spacing(&contours, point, -DVec2::X).unwrap();
+        assert_eq!(spacing.gradient(), -0.5);
+        let spacing = IsoContours::local_spacing(&contours, point, DVec2::ONE).unwrap();
+        assert!((spacing.spacing_m - 10.0 * 2f64.sqrt()).abs() < 1e-9);
+        assert_eq!(IsoContours::local_spacing(&contours, point, DVec2::Y), None);
+    }
 
+    /// The probed contours pass through the probed point.
+    #[test]
+    fn probe_contours_pass_through_the_point() {
+        let mut state = IsoRangeDopplerPlaneState::default();
+        let mut image = Image::new_fill(
+            Extent3d {
+                width: TEXTURE_WIDTH as u32,
+                height: TEXTURE_HEIGHT as u32,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            &[0, 0, 0, 0],
+            TextureFormat::Bgra8UnormSrgb,
+            RenderAssetUsages::MAIN_WORLD,
+        );
+        state
+            .update_texture(&DVec3::new(0.0, -8000.0, 6000.0), &DVec3::new(150.0, 0.0, 0.0), &DVec3::new(3000.0, 0.0, 4000.0), &DVec3::new(0.0, 100.0, 0.0), 0.03, DVec2::ZERO, 20_000.0, &mut image)
+            .unwrap();
+        let point = DVec2::new(1234.0, -2345.0);
+        let (iso_range_contours, iso_doppler_contours) = state.probe_contours_enu(point).unwrap();
+        // Within a small fraction of a grid cell from the point
+        let cell_m = 20_000.0 / (GRID_SIZE - 1) as f64;
+        for (_, lines) in [iso_range_contours, iso_doppler_contours] {
+            let distance_m = lines.iter()
+                .flat_map(|line| line.windows(2))
+                .map(|segment| {
+                    let (a, b) = (segment[0], segment[1]);
+                    let t = ((point - a).dot(b - a) / (b - a).length_squared()).clamp(0.0, 1.0);
+                    point.distance(a + t * (b - a))
+                })
+                .fold(f64::INFINITY, f64::min);
+            assert!(distance_m < 0.1 * cell_m, "{distance_m} m");
+        }
+        assert!(state.probe_contours_enu(DVec2::new(10_001.0, 0.0)).is_none());
+    }
+
+
+
+    /// An evaluation window away from the scene center samples the fields at
+    /// its own ground points.
+    #[test]
+    fn offset_window_contours_lie_on_their_level() {
+        let mut state = IsoRangeDopplerPlaneState::default();
+        let mut image = Image::new_fill(
+            Extent3d {
+                width: TEXTURE_WIDTH as u32,
+                height: TEXTURE_HEIGHT as u32,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            &[0, 0, 0, 0],
+            TextureFormat::Bgra8UnormSrgb,
+            RenderAssetUsages::MAIN_WORLD,
+        );
+        let (ot, or) = (DVec3::new(0.0, -8000.0, 6000.0), DVec3::new(3000.0, 0.0, 4000.0));
+        let center = DVec2::new(2500.0, -1500.0);
+        state
+            .update_texture(&ot, &DVec3::new(150.0, 0.0, 0.0), &or, &DVec3::new(0.0, 100.0, 0.0), 0.03, center, 2000.0, &mut image)
+            .unwrap();
+        assert_eq!(state.window_m(), (center, 2000.0));
+        let mut num_points = 0;
+        for (level, lines) in state.iso_range_contours_enu().iter() {
+            for point in lines.iter().flatten() {
+                let op = point.extend(0.0);
+                let range_m = bistatic_range_sg(&(op - ot), &(op - or));
+                assert!((range_m - level).abs() < 1e-3 * level, "{range_m} != {level} at {point}");
+                assert!((*point - center).abs().max_element() <= 1000.0 + 1e-9);
+                num_points += 1;
+            }
+        }
+        assert!(num_points > 0);
+        assert!(state.probe_contours_enu(center + DVec2::new(900.0, 900.0)).is_some());
+        assert!(state.probe_contours_enu(DVec2::ZERO).is_none());
+    }
 
     /// Regression test for the label placement mapping.
     ///
@@ -605,7 +944,7 @@ mod tests {
             TEXTURE_HEIGHT,
             &line,
             ISO_DOPPLER_STROKE_PX,
-            ISO_DOPPLER_RGB,
+            ColorPalette::default().colors().iso_doppler_rgb,
             None,
         );
         // Row of the inked (non-grey) pixels
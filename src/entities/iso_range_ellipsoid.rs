@@ -1,10 +1,14 @@
+use std::f64::consts::TAU;
 use bevy::{
     math::{DMat3, DQuat, DVec3},
     prelude::*,
     mesh::{SphereKind, SphereMeshBuilder}
 };
 
-use crate::constants::TO_Y_UP_F64;
+use crate::{
+    constants::TO_Y_UP_F64,
+    entities::{AntennaBeamState, AntennaState, CarrierState},
+};
 
 pub fn spawn_iso_range_ellipsoid(
     commands: &mut Commands,
@@ -66,4 +70,93 @@ pub fn iso_range_ellipsoid_transform_from_state(
         rotation: rotation_y_up.as_quat(),
         scale: Vec3::new(x_radius as f32, y_radius as f32, y_radius as f32),
     }
-}
\ No newline at end of file
+}
+
+/// Number of points of the beam / iso-range ellipsoid intersection curves
+const BEAM_ISO_RANGE_CURVE_SIZE: usize = 361;
+
+/// Computes the curve where the half-power beam edge of a carrier intersects
+/// the iso-range ellipsoid through the scene center, whose foci are the carrier
+/// at `carrier_state.position_m` and the other carrier at `other_position_m`
+/// (World frame, Z-up): the part of the range shell illuminated by the beam.
+///
+/// Returns the closed curve in World frame (Y-up), in the order of the footprint
+/// points.
+/// note: the carrier being a focus, the range along each beam edge ray is
+/// closed-form: r = (L² - D²) / (2 (L + d.(T - R))), with L the range sum and D
+/// the Tx-Rx distance, hence a sphere in the monostatic case
+pub fn beam_iso_range_curve(
+    carrier_state: &CarrierState,
+    antenna_state: &AntennaState,
+    antenna_beam_state: &AntennaBeamState,
+    other_position_m: &DVec3,
+) -> Vec<DVec3> {
+    let carrier_position_m = carrier_state.position_m;
+    let range_sum_m = carrier_position_m.length() + other_position_m.length();
+    let baseline_m = carrier_position_m - *other_position_m;
+    let antenna_rotation = DQuat::from_euler(
+        EulerRot::ZYX,
+        antenna_state.heading_deg.to_radians(),
+        antenna_state.elevation_deg.to_radians(),
+        antenna_state.bank_deg.to_radians()
+    );
+    let rot_antenna_to_world = carrier_state.rotation() * antenna_rotation; // Antenna to World frame (Z-up)
+    let ty = (0.5 * antenna_beam_state.azimuth_beam_width_deg.to_radians()).tan();
+    let tz = (0.5 * antenna_beam_state.elevation_beam_width_deg.to_radians()).tan();
+    let step_theta = TAU / (BEAM_ISO_RANGE_CURVE_SIZE - 1) as f64;
+    (0..BEAM_ISO_RANGE_CURVE_SIZE)
+        .map(|i| {
+            let (s, c) = (i as f64 * step_theta).sin_cos();
+            let direction = (rot_antenna_to_world * DVec3::new(1.0, ty * c, tz * s)).normalize();
+            // note: the denominator is positive, L being larger than D
+            let range_m = (range_sum_m * range_sum_m - baseline_m.length_squared())
+                / (2.0 * (range_sum_m + direction.dot(baseline_m)));
+            TO_Y_UP_F64 * (carrier_position_m + range_m * direction)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{entities::carrier_transform_from_state, motion::MotionProfile};
+
+    fn carrier_state(heading_deg: f64, height_m: f64) -> CarrierState {
+        CarrierState {
+            heading_deg,
+            elevation_deg: 0.0,
+            bank_deg: 0.0,
+            height_m,
+            velocity_mps: 100.0,
+            position_m: DVec3::ZERO,
+            velocity_vector_mps: DVec3::ZERO,
+            acceleration_vector_mps2: None,
+            time_s: 0.0,
+            motion: MotionProfile::default(),
+        }
+    }
+
+    /// The curve lies on the iso-range ellipsoid through the scene center
+    #[test]
+    fn beam_iso_range_curve_lies_on_the_ellipsoid() {
+        let antenna = AntennaState { heading_deg: 90.0, elevation_deg: -30.0, bank_deg: 0.0, lever_arm_m: DVec3::ZERO };
+        let beam = AntennaBeamState { elevation_beam_width_deg: 20.0, azimuth_beam_width_deg: 10.0, one_way_gain_dbi: 20.0 };
+        let mut tx = carrier_state(0.0, 3000.0);
+        carrier_transform_from_state(&mut tx, &antenna);
+        let mut rx = carrier_state(45.0, 1000.0);
+        carrier_transform_from_state(&mut rx, &AntennaState { elevation_deg: -45.0, ..antenna.clone() });
+        let range_sum_m = tx.position_m.length() + rx.position_m.length();
+
+        let curve = beam_iso_range_curve(&tx, &antenna, &beam, &rx.position_m);
+        assert_eq!(curve.len(), BEAM_ISO_RANGE_CURVE_SIZE);
+        for point in curve.iter() {
+            let point_m = TO_Y_UP_F64.inverse() * *point;
+            let bistatic_range_m = point_m.distance(tx.position_m) + point_m.distance(rx.position_m);
+            assert!((bistatic_range_m - range_sum_m).abs() < 1e-6 * range_sum_m, "{bistatic_range_m} != {range_sum_m}");
+        }
+        // Monostatic: the sphere of the carrier range
+        let curve = beam_iso_range_curve(&tx, &antenna, &beam, &tx.position_m);
+        let tx_position_y_up = TO_Y_UP_F64 * tx.position_m;
+        assert!(curve.iter().all(|point| (point.distance(tx_position_y_up) - tx.position_m.length()).abs() < 1e-6));
+    }
+}
@@ -0,0 +1,188 @@
+//! Absolute time of the scenario.
+//!
+//! The scenario time 0 s is attached to a UTC epoch so that the timeline, the
+//! orbit propagation and the exports share the same absolute time. Times are
+//! counted in seconds since the Unix epoch (1970-01-01T00:00:00Z) without leap
+//! seconds, as UTC clocks and TLE epochs do.
+
+/// Seconds per day
+const DAY_S: f64 = 86400.0;
+/// J2000.0 epoch (2000-01-01T12:00:00Z) in seconds since the Unix epoch
+const J2000_S: f64 = 946728000.0;
+
+/// A UTC date and time.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct UtcTime {
+    /// Seconds since 1970-01-01T00:00:00Z
+    unix_s: f64,
+}
+
+impl UtcTime {
+    /// Creates a time from seconds since 1970-01-01T00:00:00Z.
+    #[inline]
+    pub fn from_unix_s(unix_s: f64) -> Self {
+        Self { unix_s }
+    }
+
+    /// Creates a time from its UTC calendar date and time of day.
+    pub fn from_calendar(year: i64, month: i64, day: i64, hour: i64, minute: i64, second: f64) -> Result<Self, String> {
+        if !(1..=12).contains(&month) || !(1..=days_in_month(year, month)).contains(&day) {
+            return Err(format!("invalid date {year:04}-{month:02}-{day:02}"));
+        }
+        if !(0..24).contains(&hour) || !(0..60).contains(&minute) || !(0.0..60.0).contains(&second) {
+            return Err(format!("invalid time of day {hour:02}:{minute:02}:{second:02}"));
+        }
+        let days = days_from_civil(year, month, day);
+        Ok(Self { unix_s: days as f64 * DAY_S + (hour * 3600 + minute * 60) as f64 + second })
+    }
+
+    /// Reads an ISO 8601 UTC time `YYYY-MM-DD[Thh:mm[:ss[.sss]]][Z]`, the time
+    /// of day being optional and `T` replaceable by a space.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let invalid = || format!("\"{text}\" is not a YYYY-MM-DDThh:mm:ssZ UTC time");
+        let text = text.trim();
+        let text = text.strip_suffix('Z').unwrap_or(text);
+        let (date, time) = text.split_once(['T', ' ']).unwrap_or((text, "00:00"));
+        let date: Vec<i64> = date.split('-')
+            .map(|field| field.parse().map_err(|_| invalid()))
+            .collect::<Result<_, _>>()?;
+        let time: Vec<f64> = time.split(':')
+            .map(|field| field.parse().map_err(|_| invalid()))
+            .collect::<Result<_, _>>()?;
+        let (&[year, month, day], &[hour, minute, ..]) = (&date[..], &time[..]) else {
+            return Err(invalid());
+        };
+        if time.len() > 3 || hour.fract() != 0.0 || minute.fract() != 0.0 {
+            return Err(invalid());
+        }
+        Self::from_calendar(year, month, day, hour as i64, minute as i64, time.get(2).copied().unwrap_or(0.0))
+    }
+
+    /// Gets the current time of the system clock.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn now() -> Self {
+        let unix_s = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0.0, |duration| duration.as_secs_f64());
+        Self { unix_s }
+    }
+
+    /// Gets the current time of the browser clock.
+    #[cfg(target_arch = "wasm32")]
+    pub fn now() -> Self {
+        Self { unix_s: js_sys::Date::now() * 1e-3 } // Convert ms to s
+    }
+
+    /// Gets the seconds since 1970-01-01T00:00:00Z.
+    #[inline]
+    pub fn unix_s(&self) -> f64 {
+        self.unix_s
+    }
+
+    /// Gets the time `seconds` later.
+    #[inline]
+    pub fn after_s(&self, seconds: f64) -> Self {
+        Self { unix_s: self.unix_s + seconds }
+    }
+
+    /// Gets the seconds elapsed since `other`.
+    #[inline]
+    pub fn seconds_since(&self, other: &Self) -> f64 {
+        self.unix_s - other.unix_s
+    }
+
+    /// Gets the days elapsed since the J2000.0 epoch (2000-01-01T12:00:00Z).
+    #[inline]
+    pub fn days_since_j2000(&self) -> f64 {
+        (self.unix_s - J2000_S) / DAY_S
+    }
+
+    /// Gets the Greenwich Mean Sidereal Time in degrees, i.e. the angle from
+    /// the vernal equinox to the Greenwich meridian.
+    #[inline]
+    pub fn gmst_deg(&self) -> f64 {
+        (280.46061837 + 360.98564736629 * self.days_since_j2000()).rem_euclid(360.0)
+    }
+
+    /// Gets the UTC calendar (year, month, day, hour, minute, second).
+    pub fn to_calendar(&self) -> (i64, i64, i64, i64, i64, f64) {
+        let days = (self.unix_s / DAY_S).floor();
+        let second_of_day = self.unix_s - days * DAY_S;
+        let (year, month, day) = civil_from_days(days as i64);
+        let hour = (second_of_day / 3600.0).floor();
+        let minute = ((second_of_day - 3600.0 * hour) / 60.0).floor();
+        (year, month, day, hour as i64, minute as i64, second_of_day - 3600.0 * hour - 60.0 * minute)
+    }
+}
+
+impl std::fmt::Display for UtcTime {
+    /// Formats the time as ISO 8601 to the millisecond, `2026-10-16T12:00:00.000Z`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Counted in whole milliseconds so that 59.9996 s carries to the next minute
+        let millisecond = (self.unix_s * 1e3).round() as i64;
+        let (year, month, day) = civil_from_days(millisecond.div_euclid(86_400_000));
+        let millisecond = millisecond.rem_euclid(86_400_000);
+        write!(
+            f,
+            "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}.{:03}Z",
+            millisecond / 3_600_000,
+            millisecond / 60_000 % 60,
+            millisecond / 1000 % 60,
+            millisecond % 1000
+        )
+    }
+}
+
+/// Days from 1970-01-01 to the given proleptic Gregorian date.
+pub(crate) fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// Proleptic Gregorian date (year, month, day) of the days from 1970-01-01.
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days - era * 146097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153; // From March
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+/// Days in the month of the proleptic Gregorian calendar.
+fn days_in_month(year: i64, month: i64) -> i64 {
+    let next_month = if month == 12 { days_from_civil(year + 1, 1, 1) } else { days_from_civil(year, month + 1, 1) };
+    next_month - days_from_civil(year, month, 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn calendar_round_trip() {
+        let j2000 = UtcTime::parse("2000-01-01T12:00:00Z").unwrap();
+        assert_eq!(j2000.days_since_j2000(), 0.0);
+        assert!((j2000.gmst_deg() - 280.46061837).abs() < 1e-9);
+        assert_eq!(UtcTime::parse("1970-01-01").unwrap().unix_s(), 0.0);
+
+        let time = UtcTime::parse("2024-02-29 23:59:59.9996").unwrap();
+        let (year, month, day, hour, ..) = time.to_calendar();
+        assert_eq!((year, month, day, hour), (2024, 2, 29, 23));
+        assert_eq!(time.to_string(), "2024-03-01T00:00:00.000Z");
+        assert_eq!(time.after_s(-0.5).to_string(), "2024-02-29T23:59:59.500Z");
+        assert_eq!(UtcTime::parse("1969-12-31T23:00Z").unwrap().to_string(), "1969-12-31T23:00:00.000Z");
+
+        for text in ["2023-02-29", "2024-13-01", "2024-01-01T24:00", "2024-01-01T12", "noon"] {
+            assert!(UtcTime::parse(text).is_err(), "{text}");
+        }
+    }
+}
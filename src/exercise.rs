@@ -0,0 +1,124 @@
+//! Randomized bistatic geometries for teaching.
+//!
+//! An exercise draws a plausible airborne bistatic geometry: both carriers fly
+//! straight and level, their side-looking antennas pointing at the scene
+//! center, within the usual airborne heights, velocities and depressions. The
+//! student predicts metrics of the geometry before they are revealed.
+
+use std::ops::Range;
+
+/// Small deterministic pseudo-random generator (SplitMix64), enough to draw
+/// exercises reproducibly from a seed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Gets a uniform value in `[0, 1)`.
+    pub fn next_f64(&mut self) -> f64 {
+        // 53 random bits of mantissa
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Gets a uniform value in `range`.
+    pub fn in_range(&mut self, range: Range<f64>) -> f64 {
+        range.start + (range.end - range.start) * self.next_f64()
+    }
+}
+
+/// Drawn state of a carrier and of its antenna.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CarrierDraw {
+    /// Carrier heading clockwise from the North in degrees
+    pub heading_deg: f64,
+    pub height_m: f64,
+    pub velocity_mps: f64,
+    /// Antenna heading relative to the carrier in degrees (±90° side-looking,
+    /// plus a squint)
+    pub antenna_heading_deg: f64,
+    /// Antenna elevation relative to the carrier in degrees (negative below
+    /// the horizon)
+    pub antenna_elevation_deg: f64,
+}
+
+/// Randomized bistatic geometry of an exercise.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExerciseGeometry {
+    pub tx: CarrierDraw,
+    pub rx: CarrierDraw,
+}
+
+impl ExerciseGeometry {
+    pub const HEIGHT_RANGE_M: Range<f64> = 1000.0..8000.0;
+    pub const VELOCITY_RANGE_MPS: Range<f64> = 60.0..250.0;
+    pub const SQUINT_RANGE_DEG: Range<f64> = -30.0..30.0;
+    pub const DEPRESSION_RANGE_DEG: Range<f64> = 20.0..60.0;
+
+    /// Draws a geometry, the heights and velocities being capped at
+    /// `max_height_m` and `max_velocity_mps`.
+    pub fn random(rng: &mut SplitMix64, max_height_m: f64, max_velocity_mps: f64) -> Self {
+        let mut draw = || {
+            let side_deg = if rng.next_f64() < 0.5 { -90.0 } else { 90.0 };
+            CarrierDraw {
+                heading_deg: rng.in_range(0.0..360.0),
+                height_m: rng.in_range(Self::HEIGHT_RANGE_M).min(max_height_m),
+                velocity_mps: rng.in_range(Self::VELOCITY_RANGE_MPS).min(max_velocity_mps),
+                antenna_heading_deg: side_deg + rng.in_range(Self::SQUINT_RANGE_DEG),
+                antenna_elevation_deg: -rng.in_range(Self::DEPRESSION_RANGE_DEG),
+            }
+        };
+        Self { tx: draw(), rx: draw() }
+    }
+}
+
+/// Gets the relative error of a `prediction` of `value`, `None` for a null
+/// value.
+pub fn relative_error(prediction: f64, value: f64) -> Option<f64> {
+    (value != 0.0).then(|| ((prediction - value) / value).abs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generator_is_reproducible_and_uniform() {
+        let (mut a, mut b) = (SplitMix64::new(42), SplitMix64::new(42));
+        assert!((0..100).all(|_| a.next_u64() == b.next_u64()));
+        let mut rng = SplitMix64::new(7);
+        let samples: Vec<f64> = (0..10_000).map(|_| rng.next_f64()).collect();
+        assert!(samples.iter().all(|sample| (0.0..1.0).contains(sample)));
+        let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+        assert!((mean - 0.5).abs() < 0.02);
+    }
+
+    #[test]
+    fn drawn_geometries_are_plausible() {
+        let mut rng = SplitMix64::new(1);
+        for _ in 0..1000 {
+            let geometry = ExerciseGeometry::random(&mut rng, 5000.0, 1000.0);
+            for draw in [geometry.tx, geometry.rx] {
+                assert!((0.0..360.0).contains(&draw.heading_deg));
+                assert!((ExerciseGeometry::HEIGHT_RANGE_M.start..=5000.0).contains(&draw.height_m));
+                assert!(ExerciseGeometry::VELOCITY_RANGE_MPS.contains(&draw.velocity_mps));
+                assert!((60.0..120.0).contains(&draw.antenna_heading_deg.abs()));
+                assert!(ExerciseGeometry::DEPRESSION_RANGE_DEG.contains(&-draw.antenna_elevation_deg));
+            }
+        }
+        assert_eq!(relative_error(11.0, 10.0), Some(0.1));
+        assert_eq!(relative_error(1.0, 0.0), None);
+    }
+}
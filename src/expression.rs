@@ -0,0 +1,174 @@
+//! Numeric expressions typed in the parameter fields.
+//!
+//! The fields accept simple arithmetic expressions, e.g. `3e3`, `2*1852` or
+//! `c/9.6e9`, with `+ - * / ^`, parentheses, and the constants `c` (speed of
+//! light in m/s) and `pi`.
+
+use crate::bsar::SPEED_OF_LIGHT_IN_VACUUM;
+
+/// Evaluates an arithmetic expression.
+pub fn evaluate(text: &str) -> Result<f64, String> {
+    let mut parser = Parser { chars: text.chars().collect(), position: 0 };
+    let value = parser.sum()?;
+    match parser.peek() {
+        None if value.is_finite() => Ok(value),
+        None => Err(format!("\"{text}\" is not a finite number")),
+        Some(c) => Err(format!("unexpected '{c}' in \"{text}\"")),
+    }
+}
+
+/// Parser of the parameter fields: the value of the expression, `None` when
+/// it is invalid so that the field keeps its value.
+pub fn parse(text: &str) -> Option<f64> {
+    evaluate(text).ok()
+}
+
+/// Recursive descent parser, from the lowest to the highest precedence.
+struct Parser {
+    chars: Vec<char>,
+    position: usize,
+}
+
+impl Parser {
+    /// Next character, within a number or a name.
+    fn current(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    /// Next character past the whitespace, between tokens.
+    fn peek(&mut self) -> Option<char> {
+        while self.current().is_some_and(char::is_whitespace) {
+            self.position += 1;
+        }
+        self.current()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek();
+        self.position += 1;
+        c
+    }
+
+    /// `product (('+' | '-') product)*`
+    fn sum(&mut self) -> Result<f64, String> {
+        let mut value = self.product()?;
+        while let Some(operator @ ('+' | '-')) = self.peek() {
+            self.position += 1;
+            let rhs = self.product()?;
+            value = if operator == '+' { value + rhs } else { value - rhs };
+        }
+        Ok(value)
+    }
+
+    /// `unary (('*' | '/') unary)*`
+    fn product(&mut self) -> Result<f64, String> {
+        let mut value = self.unary()?;
+        while let Some(operator @ ('*' | '/')) = self.peek() {
+            self.position += 1;
+            let rhs = self.unary()?;
+            value = if operator == '*' { value * rhs } else { value / rhs };
+        }
+        Ok(value)
+    }
+
+    /// `('-' | '+') unary | power`
+    fn unary(&mut self) -> Result<f64, String> {
+        match self.peek() {
+            Some('-') => {
+                self.position += 1;
+                Ok(-self.unary()?)
+            }
+            Some('+') => {
+                self.position += 1;
+                self.unary()
+            }
+            _ => self.power(),
+        }
+    }
+
+    /// `atom ('^' unary)?`, right-associative so that `-2^2 = -4` and `2^-1 = 0.5`
+    fn power(&mut self) -> Result<f64, String> {
+        let base = self.atom()?;
+        if self.peek() == Some('^') {
+            self.position += 1;
+            return Ok(base.powf(self.unary()?));
+        }
+        Ok(base)
+    }
+
+    /// `number | constant | '(' sum ')'`
+    fn atom(&mut self) -> Result<f64, String> {
+        match self.peek() {
+            Some('(') => {
+                self.position += 1;
+                let value = self.sum()?;
+                match self.next() {
+                    Some(')') => Ok(value),
+                    _ => Err("missing ')'".to_string()),
+                }
+            }
+            Some(c) if c.is_ascii_digit() || c == '.' => self.number(),
+            Some(c) if c.is_ascii_alphabetic() => {
+                let start = self.position;
+                while self.current().is_some_and(|c| c.is_ascii_alphanumeric() || c == '_') {
+                    self.position += 1;
+                }
+                let name: String = self.chars[start..self.position].iter().collect();
+                match name.as_str() {
+                    "c" => Ok(SPEED_OF_LIGHT_IN_VACUUM),
+                    "pi" => Ok(std::f64::consts::PI),
+                    _ => Err(format!("unknown constant \"{name}\"")),
+                }
+            }
+            Some(c) => Err(format!("unexpected '{c}'")),
+            None => Err("incomplete expression".to_string()),
+        }
+    }
+
+    /// Decimal number with an optional exponent, `1852`, `.5`, `9.6e9`, `1E-3`
+    fn number(&mut self) -> Result<f64, String> {
+        let start = self.position;
+        while self.current().is_some_and(|c| c.is_ascii_digit() || c == '.') {
+            self.position += 1;
+        }
+        if let Some('e' | 'E') = self.current() {
+            let mantissa_end = self.position;
+            self.position += 1;
+            if let Some('+' | '-') = self.current() {
+                self.position += 1;
+            }
+            if !self.current().is_some_and(|c| c.is_ascii_digit()) {
+                self.position = mantissa_end; // Not an exponent
+            }
+            while self.current().is_some_and(|c| c.is_ascii_digit()) {
+                self.position += 1;
+            }
+        }
+        let text: String = self.chars[start..self.position].iter().collect();
+        text.parse().map_err(|_| format!("invalid number \"{text}\""))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic_expressions() {
+        assert_eq!(evaluate("3e3"), Ok(3000.0));
+        assert_eq!(evaluate(" 2 * 1852 "), Ok(3704.0));
+        assert_eq!(evaluate("c/9.6e9"), Ok(SPEED_OF_LIGHT_IN_VACUUM / 9.6e9));
+        assert_eq!(evaluate("1 + 2 * 3"), Ok(7.0));
+        assert_eq!(evaluate("(1 + 2) * 3"), Ok(9.0));
+        assert_eq!(evaluate("-2^2"), Ok(-4.0));
+        assert_eq!(evaluate("2^3^2"), Ok(512.0));
+        assert_eq!(evaluate("2^-1E+1"), Ok(0.0009765625));
+        assert_eq!(evaluate("8 - 2 - 1"), Ok(5.0));
+        assert_eq!(evaluate(".5"), Ok(0.5));
+
+        for text in ["", "2*", "(1", "1)", "1/0", "e3", "2e", "1.2.3", "km", "3 x", "1 2", "9.6 e9"] {
+            assert!(evaluate(text).is_err(), "{text}");
+        }
+        assert_eq!(parse("1/0"), None);
+    }
+}
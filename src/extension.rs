@@ -0,0 +1,195 @@
+//! Extension API: panels, overlays and metrics added without forking the UI.
+//!
+//! An [`Extension`] reads the geometry through a [`SceneView`] of the shared
+//! state resources. Its metrics are recomputed when the geometry changes and
+//! listed in the "Extensions" window, beside its own panel; its overlay labels
+//! are painted over the 3D view. Extensions are registered on the App, as the
+//! built-in [`FootprintCentroids`]:
+//!
+//! ```ignore
+//! app.add_plugins(AppPlugin).add_extension(LinkBudgetMargins::default());
+//! ```
+
+use bevy::{math::DVec3, prelude::*};
+use bevy_egui::egui;
+
+use crate::{
+    bsar::BsarInfos,
+    entities::{AntennaBeamFootprintState, AntennaState},
+    scene::{RxCarrierState, TxCarrierState},
+};
+
+/// Read-only view on the geometry shared by the panels.
+pub struct SceneView<'a> {
+    pub tx_carrier: &'a TxCarrierState,
+    pub tx_antenna: &'a AntennaState,
+    pub tx_footprint: &'a AntennaBeamFootprintState,
+    pub rx_carrier: &'a RxCarrierState,
+    pub rx_antenna: &'a AntennaState,
+    pub rx_footprint: &'a AntennaBeamFootprintState,
+    pub infos: &'a BsarInfos,
+}
+
+/// A scalar computed by an extension.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Metric {
+    pub name: String,
+    /// NaN for an invalid geometry
+    pub value: f64,
+    pub unit: String,
+}
+
+impl Metric {
+    pub fn new(name: impl Into<String>, value: f64, unit: impl Into<String>) -> Self {
+        Self { name: name.into(), value, unit: unit.into() }
+    }
+}
+
+/// A labelled point painted over the 3D view.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OverlayLabel {
+    /// Point in World frame (Y-up) in meters
+    pub point_m: DVec3,
+    pub text: String,
+    pub color: egui::Color32,
+}
+
+/// An extension of the visualizer, every part being optional.
+pub trait Extension: Send + Sync + 'static {
+    /// Name of the extension, heading its section of the "Extensions" window.
+    fn name(&self) -> &str;
+
+    /// Computes the metrics of the geometry, called when it changes.
+    fn metrics(&mut self, _scene: &SceneView) -> Vec<Metric> {
+        Vec::new()
+    }
+
+    /// Gets the labels painted over the 3D view, called when the geometry changes.
+    fn overlay(&mut self, _scene: &SceneView) -> Vec<OverlayLabel> {
+        Vec::new()
+    }
+
+    /// Shows the own settings or results of the extension under its metrics.
+    fn panel_ui(&mut self, _ui: &mut egui::Ui, _scene: &SceneView) {}
+}
+
+/// A registered extension with its last results.
+pub struct RegisteredExtension {
+    pub extension: Box<dyn Extension>,
+    pub metrics: Vec<Metric>,
+    pub labels: Vec<OverlayLabel>,
+    /// The overlay is painted, toggled in the "Extensions" window
+    pub show_overlay: bool,
+}
+
+/// Registered extensions, in their registration order.
+#[derive(Resource, Default)]
+pub struct Extensions {
+    pub registered: Vec<RegisteredExtension>,
+}
+
+impl Extensions {
+    pub fn push(&mut self, extension: impl Extension) {
+        self.registered.push(RegisteredExtension {
+            extension: Box::new(extension),
+            metrics: Vec::new(),
+            labels: Vec::new(),
+            show_overlay: true,
+        });
+    }
+
+    /// Recomputes the metrics and the overlays of every extension.
+    pub fn update(&mut self, scene: &SceneView) {
+        for registered in self.registered.iter_mut() {
+            registered.metrics = registered.extension.metrics(scene);
+            registered.labels = registered.extension.overlay(scene);
+        }
+    }
+}
+
+/// Registration of the extensions on the App.
+pub trait AppExtensionsExt {
+    fn add_extension(&mut self, extension: impl Extension) -> &mut Self;
+}
+
+impl AppExtensionsExt for App {
+    fn add_extension(&mut self, extension: impl Extension) -> &mut Self {
+        self.world_mut().get_resource_or_init::<Extensions>().push(extension);
+        self
+    }
+}
+
+/// Built-in extension, also the reference implementation: the vertex mean of
+/// the Tx and Rx footprints and their distance, a measure of the beams
+/// misalignment.
+pub struct FootprintCentroids;
+
+impl FootprintCentroids {
+    fn centroids(scene: &SceneView) -> Option<(DVec3, DVec3)> {
+        let centroid = |footprint: &AntennaBeamFootprintState| {
+            (!footprint.points.is_empty())
+                .then(|| footprint.points.iter().sum::<DVec3>() / footprint.points.len() as f64)
+                .filter(|centroid| centroid.is_finite())
+        };
+        Some((centroid(scene.tx_footprint)?, centroid(scene.rx_footprint)?))
+    }
+}
+
+impl Extension for FootprintCentroids {
+    fn name(&self) -> &str {
+        "Footprint centroids"
+    }
+
+    fn metrics(&mut self, scene: &SceneView) -> Vec<Metric> {
+        let (tx_offset_m, rx_offset_m, distance_m) = match Self::centroids(scene) {
+            Some((tx_centroid, rx_centroid)) => (tx_centroid.length(), rx_centroid.length(), tx_centroid.distance(rx_centroid)),
+            None => (f64::NAN, f64::NAN, f64::NAN),
+        };
+        vec![
+            Metric::new("Tx centroid to scene center", tx_offset_m, "m"),
+            Metric::new("Rx centroid to scene center", rx_offset_m, "m"),
+            Metric::new("Tx to Rx centroids", distance_m, "m"),
+        ]
+    }
+
+    fn overlay(&mut self, scene: &SceneView) -> Vec<OverlayLabel> {
+        let Some((tx_centroid, rx_centroid)) = Self::centroids(scene) else {
+            return Vec::new();
+        };
+        vec![
+            OverlayLabel { point_m: tx_centroid, text: "Tx centroid".to_string(), color: egui::Color32::from_rgb(255, 127, 14) },
+            OverlayLabel { point_m: rx_centroid, text: "Rx centroid".to_string(), color: egui::Color32::from_rgb(44, 160, 44) },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scene::{RxAntennaState, TxAntennaState};
+
+    #[test]
+    fn extensions_update_their_results() {
+        let (tx_carrier, rx_carrier) = (TxCarrierState::default(), RxCarrierState::default());
+        let (tx_antenna, rx_antenna) = (TxAntennaState::default(), RxAntennaState::default());
+        let tx_footprint = AntennaBeamFootprintState { points: vec![DVec3::new(1.0, 0.0, 0.0), DVec3::new(3.0, 0.0, 0.0)], ..default() };
+        let rx_footprint = AntennaBeamFootprintState { points: vec![DVec3::new(0.0, 0.0, 2.0), DVec3::new(0.0, 0.0, 4.0)], ..default() };
+        let infos = BsarInfos::default();
+        let scene = SceneView {
+            tx_carrier: &tx_carrier,
+            tx_antenna: &tx_antenna.inner,
+            tx_footprint: &tx_footprint,
+            rx_carrier: &rx_carrier,
+            rx_antenna: &rx_antenna.inner,
+            rx_footprint: &rx_footprint,
+            infos: &infos,
+        };
+        let mut extensions = Extensions::default();
+        extensions.push(FootprintCentroids);
+        extensions.update(&scene);
+        let registered = &extensions.registered[0];
+        let values: Vec<f64> = registered.metrics.iter().map(|metric| metric.value).collect();
+        assert_eq!(values, vec![2.0, 3.0, 13f64.sqrt()]);
+        assert_eq!(registered.labels[1].point_m, DVec3::new(0.0, 0.0, 3.0));
+    }
+}
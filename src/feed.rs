@@ -0,0 +1,275 @@
+//! External platform feeds.
+//!
+//! Live positions of platforms of opportunity (ADS-B aircraft, AIS ships,
+//! tracked targets) are read as text lines from a network feed, to plan
+//! opportunistic bistatic experiments with them as receivers or illuminators.
+//! Two line formats are read:
+//!
+//! - SBS-1 (BaseStation) messages, e.g. the ADS-B feed of dump1090 on port
+//!   30003: `MSG,type,...,hex_id,...,callsign,altitude_ft,ground_speed_kt,
+//!   track_deg,latitude_deg,longitude_deg,...`, each message carrying a part
+//!   of the platform state;
+//! - generic target lines `id,latitude_deg,longitude_deg,altitude_m[,speed_mps,
+//!   track_deg]`, e.g. decoded AIS positions.
+
+use bevy::math::DVec3;
+
+/// Feet to meters
+const FOOT_M: f64 = 0.3048;
+/// Knots to meters per second
+const KNOT_MPS: f64 = 1852.0 / 3600.0;
+
+/// Part of a platform state read from one feed line.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PlatformUpdate {
+    pub id: String,
+    pub callsign: Option<String>,
+    /// (latitude, longitude) in degrees
+    pub position_deg: Option<(f64, f64)>,
+    /// Altitude above the ellipsoid in meters
+    pub altitude_m: Option<f64>,
+    pub ground_speed_mps: Option<f64>,
+    /// Ground track in degrees, clockwise from the north
+    pub track_deg: Option<f64>,
+}
+
+/// Parses an optional numeric field, empty fields being missing.
+fn field<T: std::str::FromStr>(fields: &[&str], index: usize) -> Option<T> {
+    fields.get(index).map(|value| value.trim()).filter(|value| !value.is_empty())?.parse().ok()
+}
+
+impl PlatformUpdate {
+    /// Reads a feed line, `None` for unknown or malformed lines.
+    pub fn from_line(line: &str) -> Option<Self> {
+        let fields: Vec<&str> = line.trim().split(',').collect();
+        if fields[0] == "MSG" {
+            // SBS-1: the position, altitude and velocity come in separate messages
+            let id = fields.get(4).map(|id| id.trim()).filter(|id| !id.is_empty())?;
+            let update = Self {
+                id: id.to_string(),
+                callsign: fields.get(10).map(|callsign| callsign.trim().to_string()).filter(|callsign| !callsign.is_empty()),
+                position_deg: field(&fields, 14).zip(field(&fields, 15)),
+                altitude_m: field::<f64>(&fields, 11).map(|altitude_ft| altitude_ft * FOOT_M),
+                ground_speed_mps: field::<f64>(&fields, 12).map(|ground_speed_kt| ground_speed_kt * KNOT_MPS),
+                track_deg: field(&fields, 13),
+            };
+            return Some(update);
+        }
+        // Generic target line: the position is mandatory
+        let id = fields[0].trim();
+        let position_deg = field(&fields, 1).zip(field(&fields, 2))?;
+        let altitude_m = field(&fields, 3)?;
+        (!id.is_empty() && (4..=6).contains(&fields.len())).then(|| Self {
+            id: id.to_string(),
+            callsign: None,
+            position_deg: Some(position_deg),
+            altitude_m: Some(altitude_m),
+            ground_speed_mps: field(&fields, 4),
+            track_deg: field(&fields, 5),
+        })
+    }
+}
+
+/// Last known state of a platform.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Platform {
+    pub id: String,
+    pub callsign: Option<String>,
+    pub position_deg: Option<(f64, f64)>,
+    pub altitude_m: Option<f64>,
+    pub ground_speed_mps: Option<f64>,
+    pub track_deg: Option<f64>,
+}
+
+impl Platform {
+    /// Gets the platform name: its callsign, its id otherwise.
+    pub fn name(&self) -> &str {
+        self.callsign.as_deref().unwrap_or(&self.id)
+    }
+
+    /// Gets (latitude, longitude, altitude) when the platform is located.
+    pub fn location(&self) -> Option<(f64, f64, f64)> {
+        self.position_deg.zip(self.altitude_m).map(|((latitude_deg, longitude_deg), altitude_m)| (latitude_deg, longitude_deg, altitude_m))
+    }
+}
+
+/// Platforms of a feed, by order of appearance.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PlatformTable {
+    platforms: Vec<Platform>,
+}
+
+impl PlatformTable {
+    /// Gets the platforms.
+    #[inline]
+    pub fn platforms(&self) -> &[Platform] {
+        &self.platforms
+    }
+
+    /// Merges a platform update, the missing fields keeping their last value.
+    pub fn update(&mut self, update: PlatformUpdate) {
+        let index = match self.platforms.iter().position(|platform| platform.id == update.id) {
+            Some(index) => index,
+            None => {
+                self.platforms.push(Platform { id: update.id.clone(), ..Default::default() });
+                self.platforms.len() - 1
+            }
+        };
+        let platform = &mut self.platforms[index];
+        platform.callsign = update.callsign.or(platform.callsign.take());
+        platform.position_deg = update.position_deg.or(platform.position_deg);
+        platform.altitude_m = update.altitude_m.or(platform.altitude_m);
+        platform.ground_speed_mps = update.ground_speed_mps.or(platform.ground_speed_mps);
+        platform.track_deg = update.track_deg.or(platform.track_deg);
+    }
+
+    /// Removes all the platforms.
+    pub fn clear(&mut self) {
+        self.platforms.clear();
+    }
+}
+
+/// Connection to a TCP feed, read line by line in a background thread (the web
+/// build has no sockets).
+#[cfg(not(target_arch = "wasm32"))]
+pub struct FeedConnection {
+    lines: std::sync::Arc<std::sync::Mutex<Vec<String>>>,
+    error: std::sync::Arc<std::sync::Mutex<Option<String>>>,
+    is_running: std::sync::Arc<std::sync::atomic::AtomicBool>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl FeedConnection {
+    /// Time a blocked read waits before checking whether the connection is closed
+    const READ_TIMEOUT: std::time::Duration = std::time::Duration::from_millis(250);
+    const CONNECT_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+    /// Connects to `address` (`host:port`) in a background thread.
+    pub fn connect(address: &str) -> Self {
+        use std::{
+            io::{BufRead, BufReader, ErrorKind},
+            net::{TcpStream, ToSocketAddrs},
+            sync::{atomic::{AtomicBool, Ordering}, Arc, Mutex},
+        };
+
+        let connection = Self {
+            lines: Arc::new(Mutex::new(Vec::new())),
+            error: Arc::new(Mutex::new(None)),
+            is_running: Arc::new(AtomicBool::new(true)),
+        };
+        let (lines, error, is_running) = (connection.lines.clone(), connection.error.clone(), connection.is_running.clone());
+        let address = address.to_string();
+        std::thread::spawn(move || {
+            let stream = address.to_socket_addrs()
+                .map_err(|e| e.to_string())
+                .and_then(|mut addresses| addresses.next().ok_or_else(|| "Unknown address".to_string()))
+                .and_then(|socket_address| TcpStream::connect_timeout(&socket_address, Self::CONNECT_TIMEOUT).map_err(|e| e.to_string()))
+                .and_then(|stream| stream.set_read_timeout(Some(Self::READ_TIMEOUT)).map(|_| stream).map_err(|e| e.to_string()));
+            let stream = match stream {
+                Ok(stream) => stream,
+                Err(e) => {
+                    *error.lock().unwrap() = Some(format!("Connection to {address} failed: {e}"));
+                    is_running.store(false, Ordering::Relaxed);
+                    return;
+                }
+            };
+            let mut reader = BufReader::new(stream);
+            // A timed out read keeps its partial line, completed by the next reads
+            let mut line = String::new();
+            while is_running.load(Ordering::Relaxed) {
+                match reader.read_line(&mut line) {
+                    Ok(0) => {
+                        *error.lock().unwrap() = Some(format!("Feed {address} closed"));
+                        break;
+                    }
+                    Ok(_) => {
+                        lines.lock().unwrap().push(std::mem::take(&mut line));
+                    }
+                    Err(e) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {}
+                    Err(e) => {
+                        *error.lock().unwrap() = Some(format!("Feed {address} failed: {e}"));
+                        break;
+                    }
+                }
+            }
+            is_running.store(false, Ordering::Relaxed);
+        });
+        connection
+    }
+
+    /// Takes the lines received since the last call.
+    pub fn take_lines(&self) -> Vec<String> {
+        std::mem::take(&mut *self.lines.lock().unwrap())
+    }
+
+    /// Gets the error which stopped the connection, if any.
+    pub fn error(&self) -> Option<String> {
+        self.error.lock().unwrap().clone()
+    }
+
+    /// Checks whether the connection is still open.
+    pub fn is_running(&self) -> bool {
+        self.is_running.load(std::sync::atomic::Ordering::Relaxed)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Drop for FeedConnection {
+    fn drop(&mut self) {
+        // Stops the background thread at its next read timeout
+        self.is_running.store(false, std::sync::atomic::Ordering::Relaxed);
+    }
+}
+
+/// Gets the carrier height and the antenna (heading, elevation) in degrees,
+/// relative to a level carrier heading `carrier_heading_deg`, that point the
+/// antenna boresight from `position_m` (ENU) to the scene center.
+pub fn pointing_to_scene_center(position_m: &DVec3, carrier_heading_deg: f64) -> (f64, f64, f64) {
+    let direction = -*position_m;
+    let azimuth_deg = direction.x.atan2(direction.y).to_degrees(); // Clockwise from the north
+    let elevation_deg = direction.z.atan2(direction.x.hypot(direction.y)).to_degrees();
+    let antenna_heading_deg = (azimuth_deg - carrier_heading_deg + 180.0).rem_euclid(360.0) - 180.0;
+    (position_m.z, antenna_heading_deg, elevation_deg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merges_the_feed_messages() {
+        let mut table = PlatformTable::default();
+        let lines = [
+            "MSG,1,111,11111,3C49CC,111111,2026/10/16,10:00:00.000,2026/10/16,10:00:00.000,DLH4AB,,,,,,,,,,,0",
+            "MSG,3,111,11111,3C49CC,111111,2026/10/16,10:00:01.000,2026/10/16,10:00:01.000,,10000,,,48.85,2.35,,,0,0,0,0",
+            "MSG,4,111,11111,3C49CC,111111,2026/10/16,10:00:02.000,2026/10/16,10:00:02.000,,,400,90,,,-64,,0,0,0,0",
+            "227006760, 43.29, 5.36, 0, 6.2, 270",
+            "# comment",
+        ];
+        for line in lines {
+            if let Some(update) = PlatformUpdate::from_line(line) {
+                table.update(update);
+            }
+        }
+        assert_eq!(table.platforms().len(), 2);
+        let aircraft = &table.platforms()[0];
+        assert_eq!(aircraft.name(), "DLH4AB");
+        assert_eq!(aircraft.location(), Some((48.85, 2.35, 3048.0)));
+        assert!((aircraft.ground_speed_mps.unwrap() - 205.777).abs() < 1e-3);
+        assert_eq!(aircraft.track_deg, Some(90.0));
+        let ship = &table.platforms()[1];
+        assert_eq!(ship.name(), "227006760");
+        assert_eq!((ship.location(), ship.ground_speed_mps, ship.track_deg), (Some((43.29, 5.36, 0.0)), Some(6.2), Some(270.0)));
+        assert!(PlatformUpdate::from_line("id,48.0").is_none());
+    }
+
+    #[test]
+    fn points_the_antenna_to_the_scene_center() {
+        // 3 km north-east of the scene center at 3 km height, heading east
+        let position_m = DVec3::new(3000.0 / 2f64.sqrt(), 3000.0 / 2f64.sqrt(), 3000.0);
+        let (height_m, heading_deg, elevation_deg) = pointing_to_scene_center(&position_m, 90.0);
+        assert_eq!(height_m, 3000.0);
+        assert!((heading_deg - 135.0).abs() < 1e-9); // Looking south-west, to the right
+        assert!((elevation_deg + 45.0).abs() < 1e-9);
+    }
+}
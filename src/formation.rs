@@ -0,0 +1,213 @@
+//! Formation flying of the Rx with the Tx.
+//!
+//! In quasi-monostatic mode the Rx carrier flies at a fixed offset from the Tx
+//! carrier, its lever arm, given in the Tx track frame: along the carrier
+//! heading, across it to the right and along the local vertical.
+//!
+//! The Tx and Rx channels then form a single-pass interferometer: with a
+//! single transmitter, the phase difference between the two channels only
+//! comes from the receive paths, so that the pair behaves as a monostatic
+//! interferometer with half the physical baseline (the effective baseline).
+
+use bevy::math::DVec3;
+
+/// Offset of the Rx carrier from the Tx carrier in meters.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LeverArm {
+    /// Ahead of the Tx along its heading (negative behind)
+    pub along_track_m: f64,
+    /// Right of the Tx track (negative left)
+    pub across_track_m: f64,
+    /// Above the Tx (negative below)
+    pub vertical_m: f64,
+}
+
+impl Default for LeverArm {
+    fn default() -> Self {
+        Self {
+            along_track_m: 100.0,
+            across_track_m: 0.0,
+            vertical_m: 0.0,
+        }
+    }
+}
+
+impl LeverArm {
+    /// Gets the offset in ENU frame for a carrier heading `heading_deg`
+    /// (clockwise from the North).
+    pub fn offset_m(&self, heading_deg: f64) -> DVec3 {
+        let (sin, cos) = heading_deg.to_radians().sin_cos();
+        let along_track = DVec3::new(sin, cos, 0.0);
+        let across_track = DVec3::new(cos, -sin, 0.0);
+        self.along_track_m * along_track + self.across_track_m * across_track + self.vertical_m * DVec3::Z
+    }
+
+    #[inline]
+    pub fn length_m(&self) -> f64 {
+        DVec3::new(self.along_track_m, self.across_track_m, self.vertical_m).length()
+    }
+}
+
+/// Interferometric quantities of the Tx/Rx pair at the scene center.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InterferometricBaselines {
+    /// Effective along-track baseline in meters
+    pub along_track_m: f64,
+    /// Effective baseline across the line of sight, in the plane orthogonal
+    /// to the track, in meters (positive above the line of sight)
+    pub perpendicular_m: f64,
+    /// Effective baseline along the line of sight in meters
+    pub parallel_m: f64,
+    /// Tx incidence angle at the scene center in degrees
+    pub incidence_deg: f64,
+    /// Tx slant range to the scene center in meters
+    pub slant_range_m: f64,
+    /// Wavelength in meters
+    pub wavelength_m: f64,
+}
+
+impl InterferometricBaselines {
+    /// Effective baseline factor of a single transmitter pair
+    const EFFECTIVE_BASELINE_FACTOR: f64 = 0.5;
+
+    /// Gets the effective baselines of the Rx at `baseline_m` (ENU) from the
+    /// Tx at `tx_position_m` (ENU), for a carrier heading `heading_deg`.
+    ///
+    /// Returns `None` when the Tx stands at the scene center or looks along
+    /// its track, the line of sight then having no cross-track component.
+    pub fn new(tx_position_m: &DVec3, baseline_m: &DVec3, heading_deg: f64, wavelength_m: f64) -> Option<Self> {
+        let frame = CrossTrackFrame::new(tx_position_m, heading_deg)?;
+        let (along_track_m, parallel_m, perpendicular_m) = frame.components(baseline_m);
+        Some(Self {
+            along_track_m: Self::EFFECTIVE_BASELINE_FACTOR * along_track_m,
+            perpendicular_m: Self::EFFECTIVE_BASELINE_FACTOR * perpendicular_m,
+            parallel_m: Self::EFFECTIVE_BASELINE_FACTOR * parallel_m,
+            incidence_deg: frame.incidence_rad.to_degrees(),
+            slant_range_m: frame.slant_range_m,
+            wavelength_m,
+        })
+    }
+
+    /// Gets the height of ambiguity in meters, the height difference
+    /// wrapping the interferometric phase by 2π (infinite without
+    /// perpendicular baseline).
+    pub fn height_of_ambiguity_m(&self) -> f64 {
+        self.wavelength_m * self.slant_range_m * self.incidence_deg.to_radians().sin()
+            / (2.0 * self.perpendicular_m.abs())
+    }
+
+    /// Gets the vertical wavenumber in rad/m, the interferometric phase
+    /// change per meter of height.
+    #[inline]
+    pub fn vertical_wavenumber_radpm(&self) -> f64 {
+        2.0 * std::f64::consts::PI / self.height_of_ambiguity_m()
+    }
+}
+
+/// Along-track direction and line of sight frame of the Tx, the line of sight
+/// being taken to the scene center.
+struct CrossTrackFrame {
+    along_track: DVec3,
+    /// Line of sight projected onto the cross-track plane
+    line_of_sight: DVec3,
+    /// Normal to the line of sight in the cross-track plane, upwards
+    normal: DVec3,
+    incidence_rad: f64,
+    slant_range_m: f64,
+}
+
+impl CrossTrackFrame {
+    fn new(tx_position_m: &DVec3, heading_deg: f64) -> Option<Self> {
+        let slant_range_m = tx_position_m.length();
+        let (sin, cos) = heading_deg.to_radians().sin_cos();
+        let along_track = DVec3::new(sin, cos, 0.0);
+        let line_of_sight = -*tx_position_m / slant_range_m;
+        let line_of_sight = (line_of_sight - line_of_sight.dot(along_track) * along_track).try_normalize()?;
+        let normal = along_track.cross(line_of_sight);
+        Some(Self {
+            along_track,
+            line_of_sight,
+            normal: if normal.z < 0.0 { -normal } else { normal },
+            incidence_rad: (tx_position_m.z / slant_range_m).clamp(-1.0, 1.0).acos(),
+            slant_range_m,
+        })
+    }
+
+    /// Gets the (along-track, parallel, perpendicular) components of a
+    /// baseline.
+    fn components(&self, baseline_m: &DVec3) -> (f64, f64, f64) {
+        (baseline_m.dot(self.along_track), baseline_m.dot(self.line_of_sight), baseline_m.dot(self.normal))
+    }
+}
+
+impl LeverArm {
+    /// Gets the shortest lever arm giving the height of ambiguity
+    /// `height_of_ambiguity_m` with the Tx at `tx_position_m` (ENU) heading
+    /// `heading_deg`: its along-track component is kept and its cross-track
+    /// one is orthogonal to the line of sight, on the side of the current one.
+    pub fn for_height_of_ambiguity(
+        &self,
+        tx_position_m: &DVec3,
+        heading_deg: f64,
+        wavelength_m: f64,
+        height_of_ambiguity_m: f64,
+    ) -> Option<Self> {
+        let frame = CrossTrackFrame::new(tx_position_m, heading_deg)?;
+        if height_of_ambiguity_m <= 0.0 {
+            return None;
+        }
+        let (_, _, perpendicular_m) = frame.components(&self.offset_m(heading_deg));
+        // Physical perpendicular baseline, twice the effective one
+        let required_m = wavelength_m * frame.slant_range_m * frame.incidence_rad.sin() / height_of_ambiguity_m;
+        let cross_track_m = if perpendicular_m < 0.0 { -required_m } else { required_m } * frame.normal;
+        let (sin, cos) = heading_deg.to_radians().sin_cos();
+        Some(Self {
+            along_track_m: self.along_track_m,
+            across_track_m: cross_track_m.dot(DVec3::new(cos, -sin, 0.0)),
+            vertical_m: cross_track_m.z,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offset_in_the_track_frame() {
+        let lever_arm = LeverArm { along_track_m: 100.0, across_track_m: 20.0, vertical_m: -5.0 };
+        // Heading North: ahead is North, right is East
+        let offset_m = lever_arm.offset_m(0.0);
+        assert!(offset_m.distance(DVec3::new(20.0, 100.0, -5.0)) < 1e-12);
+        // Heading East: ahead is East, right is South
+        let offset_m = lever_arm.offset_m(90.0);
+        assert!(offset_m.distance(DVec3::new(100.0, -20.0, -5.0)) < 1e-12);
+        assert!((offset_m.length() - lever_arm.length_m()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn height_of_ambiguity_of_the_formation() {
+        // Heading North, looking East from 3000 m at 45° incidence
+        let tx_position_m = DVec3::new(-3000.0, 0.0, 3000.0);
+        let wavelength_m = 0.03;
+        // Vertical offset: 1/√2 of it across the line of sight, upwards
+        let lever_arm = LeverArm { along_track_m: 50.0, across_track_m: 0.0, vertical_m: 10.0 };
+        let baselines = InterferometricBaselines::new(&tx_position_m, &lever_arm.offset_m(0.0), 0.0, wavelength_m).unwrap();
+        assert!((baselines.along_track_m - 25.0).abs() < 1e-9);
+        assert!((baselines.perpendicular_m - 5.0 / 2f64.sqrt()).abs() < 1e-9);
+        assert!((baselines.parallel_m + 5.0 / 2f64.sqrt()).abs() < 1e-9);
+        assert!((baselines.incidence_deg - 45.0).abs() < 1e-9);
+        // λ R sinθ / (2 B⊥) with R = 3000 √2 m
+        let expected_m = wavelength_m * 3000.0 / (2.0 * 5.0 / 2f64.sqrt());
+        assert!((baselines.height_of_ambiguity_m() - expected_m).abs() < 1e-9);
+
+        // Tuned for a 20 m height of ambiguity, the along-track offset kept
+        let tuned = lever_arm.for_height_of_ambiguity(&tx_position_m, 0.0, wavelength_m, 20.0).unwrap();
+        assert_eq!(tuned.along_track_m, lever_arm.along_track_m);
+        let baselines = InterferometricBaselines::new(&tx_position_m, &tuned.offset_m(0.0), 0.0, wavelength_m).unwrap();
+        assert!((baselines.height_of_ambiguity_m() - 20.0).abs() < 1e-9);
+        assert!(baselines.perpendicular_m > 0.0 && baselines.parallel_m.abs() < 1e-9);
+        // At the scene center, no line of sight
+        assert!(InterferometricBaselines::new(&DVec3::ZERO, &DVec3::X, 0.0, wavelength_m).is_none());
+    }
+}
@@ -0,0 +1,220 @@
+//! Binary glTF 2.0 (`.glb`) writer of the 3D scene.
+//!
+//! The scene meshes are baked in World frame (Y-up, meters, as glTF expects)
+//! into one node per mesh, each with an unlit flat-colored material
+//! (`KHR_materials_unlit`), so that the exported scene looks as in the app
+//! once opened in another 3D viewer or embedded in a presentation.
+//!
+//! A `.glb` file is a 12 bytes header followed by a JSON chunk (the scene
+//! description) and a BIN chunk (vertices and indices), both 4-byte aligned.
+//! The scenario metadata are written as the `extras` of the asset.
+
+use crate::scenario::ScenarioMetadata;
+
+/// glTF binary magic (`glTF` in ASCII)
+const GLB_MAGIC: u32 = 0x4654_6C67;
+const GLB_VERSION: u32 = 2;
+/// Chunk types (`JSON` and `BIN\0` in ASCII)
+const GLB_CHUNK_JSON: u32 = 0x4E4F_534A;
+const GLB_CHUNK_BIN: u32 = 0x004E_4942;
+/// Accessor component types and buffer view targets
+const COMPONENT_TYPE_FLOAT: u32 = 5126;
+const COMPONENT_TYPE_UNSIGNED_INT: u32 = 5125;
+const TARGET_ARRAY_BUFFER: u32 = 34962;
+const TARGET_ELEMENT_ARRAY_BUFFER: u32 = 34963;
+
+/// Primitive topology of an exported mesh.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GltfPrimitiveMode {
+    Points,
+    Lines,
+    LineStrip,
+    Triangles,
+    TriangleStrip,
+}
+
+impl GltfPrimitiveMode {
+    /// glTF `mode` code of the primitive.
+    fn code(self) -> u32 {
+        match self {
+            GltfPrimitiveMode::Points => 0,
+            GltfPrimitiveMode::Lines => 1,
+            GltfPrimitiveMode::LineStrip => 3,
+            GltfPrimitiveMode::Triangles => 4,
+            GltfPrimitiveMode::TriangleStrip => 5,
+        }
+    }
+}
+
+/// A mesh of the exported scene, in World frame (Y-up).
+#[derive(Debug, Clone, PartialEq)]
+pub struct GltfMesh {
+    pub name: String,
+    pub positions: Vec<[f32; 3]>,
+    /// Vertex indices, `None` to draw the vertices in order
+    pub indices: Option<Vec<u32>>,
+    pub mode: GltfPrimitiveMode,
+    /// Linear RGBA base color, blended when not opaque
+    pub color: [f32; 4],
+}
+
+/// Escapes a string for a JSON string literal.
+fn json_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+/// Writes `meshes` as a binary glTF 2.0 scene.
+///
+/// Meshes without vertices are skipped, their vertices must be finite.
+pub fn to_glb(meshes: &[GltfMesh], metadata: &ScenarioMetadata) -> Vec<u8> {
+    let mut bin: Vec<u8> = Vec::new();
+    let (mut nodes, mut json_meshes, mut materials) = (Vec::new(), Vec::new(), Vec::new());
+    let (mut accessors, mut buffer_views) = (Vec::new(), Vec::new());
+    for mesh in meshes.iter().filter(|mesh| !mesh.positions.is_empty()) {
+        // Positions: the accessor needs their bounds
+        let (mut min, mut max) = ([f32::MAX; 3], [f32::MIN; 3]);
+        let offset = bin.len();
+        for position in mesh.positions.iter() {
+            for axis in 0..3 {
+                min[axis] = min[axis].min(position[axis]);
+                max[axis] = max[axis].max(position[axis]);
+                bin.extend_from_slice(&position[axis].to_le_bytes());
+            }
+        }
+        buffer_views.push(format!(
+            r#"{{"buffer":0,"byteOffset":{offset},"byteLength":{},"target":{TARGET_ARRAY_BUFFER}}}"#,
+            bin.len() - offset
+        ));
+        accessors.push(format!(
+            r#"{{"bufferView":{},"componentType":{COMPONENT_TYPE_FLOAT},"count":{},"type":"VEC3","min":[{},{},{}],"max":[{},{},{}]}}"#,
+            buffer_views.len() - 1, mesh.positions.len(), min[0], min[1], min[2], max[0], max[1], max[2]
+        ));
+        let position_accessor = accessors.len() - 1;
+        // Indices
+        let indices_accessor = mesh.indices.as_ref().map(|indices| {
+            let offset = bin.len();
+            for index in indices.iter() {
+                bin.extend_from_slice(&index.to_le_bytes());
+            }
+            buffer_views.push(format!(
+                r#"{{"buffer":0,"byteOffset":{offset},"byteLength":{},"target":{TARGET_ELEMENT_ARRAY_BUFFER}}}"#,
+                bin.len() - offset
+            ));
+            accessors.push(format!(
+                r#"{{"bufferView":{},"componentType":{COMPONENT_TYPE_UNSIGNED_INT},"count":{},"type":"SCALAR"}}"#,
+                buffer_views.len() - 1, indices.len()
+            ));
+            accessors.len() - 1
+        });
+        // Material
+        let [r, g, b, a] = mesh.color;
+        let alpha_mode = if a < 1.0 { "BLEND" } else { "OPAQUE" };
+        materials.push(format!(
+            r#"{{"pbrMetallicRoughness":{{"baseColorFactor":[{r},{g},{b},{a}],"metallicFactor":0,"roughnessFactor":1}},"alphaMode":"{alpha_mode}","doubleSided":true,"extensions":{{"KHR_materials_unlit":{{}}}}}}"#
+        ));
+        // Mesh and its node
+        let indices = indices_accessor.map_or(String::new(), |accessor| format!(r#","indices":{accessor}"#));
+        json_meshes.push(format!(
+            r#"{{"name":{},"primitives":[{{"attributes":{{"POSITION":{position_accessor}}}{indices},"material":{},"mode":{}}}]}}"#,
+            json_string(&mesh.name), materials.len() - 1, mesh.mode.code()
+        ));
+        nodes.push(format!(r#"{{"name":{},"mesh":{}}}"#, json_string(&mesh.name), json_meshes.len() - 1));
+    }
+    let node_indices: Vec<String> = (0..nodes.len()).map(|i| i.to_string()).collect();
+    let buffers = if bin.is_empty() { String::new() } else { format!(r#","buffers":[{{"byteLength":{}}}]"#, bin.len()) };
+    let fields: Vec<String> = metadata.fields().iter()
+        .map(|(name, value)| format!("{}:{}", json_string(name), json_string(value)))
+        .collect();
+    let extras = if fields.is_empty() { String::new() } else { format!(r#","extras":{{{}}}"#, fields.join(",")) };
+    let mut json = format!(
+        r#"{{"asset":{{"version":"2.0","generator":"BSARGeom"{extras}}},"extensionsUsed":["KHR_materials_unlit"],"scene":0,"scenes":[{{"name":"BSARGeom","nodes":[{}]}}],"nodes":[{}],"meshes":[{}],"materials":[{}],"accessors":[{}],"bufferViews":[{}]{buffers}}}"#,
+        node_indices.join(","), nodes.join(","), json_meshes.join(","), materials.join(","),
+        accessors.join(","), buffer_views.join(",")
+    );
+    // Chunks are 4-byte aligned: JSON padded with spaces, BIN with zeros
+    json.push_str(&" ".repeat(json.len().next_multiple_of(4) - json.len()));
+    bin.resize(bin.len().next_multiple_of(4), 0);
+
+    let bin_chunk_len = if bin.is_empty() { 0 } else { 8 + bin.len() };
+    let total_len = 12 + 8 + json.len() + bin_chunk_len;
+    let mut glb = Vec::with_capacity(total_len);
+    glb.extend_from_slice(&GLB_MAGIC.to_le_bytes());
+    glb.extend_from_slice(&GLB_VERSION.to_le_bytes());
+    glb.extend_from_slice(&(total_len as u32).to_le_bytes());
+    glb.extend_from_slice(&(json.len() as u32).to_le_bytes());
+    glb.extend_from_slice(&GLB_CHUNK_JSON.to_le_bytes());
+    glb.extend_from_slice(json.as_bytes());
+    if !bin.is_empty() {
+        glb.extend_from_slice(&(bin.len() as u32).to_le_bytes());
+        glb.extend_from_slice(&GLB_CHUNK_BIN.to_le_bytes());
+        glb.extend_from_slice(&bin);
+    }
+    glb
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read_u32(bytes: &[u8], offset: usize) -> u32 {
+        u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap())
+    }
+
+    #[test]
+    fn glb_layout() {
+        let meshes = [
+            GltfMesh {
+                name: "Tx \"Carrier\"".to_string(),
+                positions: vec![[0.0, 0.0, 0.0], [1.0, 2.0, 3.0], [-1.0, 0.5, 0.0]],
+                indices: Some(vec![0, 1, 2]),
+                mode: GltfPrimitiveMode::Triangles,
+                color: [1.0, 0.0, 0.0, 0.5],
+            },
+            GltfMesh {
+                name: "Baseline".to_string(),
+                positions: vec![[0.0, 0.0, 0.0], [10.0, 0.0, 0.0]],
+                indices: None,
+                mode: GltfPrimitiveMode::Lines,
+                color: [1.0, 1.0, 1.0, 1.0],
+            },
+            GltfMesh { // Skipped
+                name: "Empty".to_string(),
+                positions: Vec::new(),
+                indices: None,
+                mode: GltfPrimitiveMode::LineStrip,
+                color: [1.0; 4],
+            },
+        ];
+        let metadata = ScenarioMetadata { title: "Pass 1".to_string(), ..Default::default() };
+        let glb = to_glb(&meshes, &metadata);
+        assert_eq!(read_u32(&glb, 0), GLB_MAGIC);
+        assert_eq!(read_u32(&glb, 4), 2);
+        assert_eq!(read_u32(&glb, 8) as usize, glb.len());
+        let json_len = read_u32(&glb, 12) as usize;
+        assert!(json_len.is_multiple_of(4));
+        assert_eq!(read_u32(&glb, 16), GLB_CHUNK_JSON);
+        let json = std::str::from_utf8(&glb[20..20 + json_len]).unwrap();
+        assert!(json.contains(r#""name":"Tx \"Carrier\"""#));
+        assert!(json.contains(r#""min":[-1,0,0],"max":[1,2,3]"#));
+        assert!(json.contains(r#""alphaMode":"BLEND""#));
+        assert!(json.contains(r#""mode":1"#) && json.contains(r#""mode":4"#));
+        assert!(!json.contains("Empty"));
+        assert!(json.contains(r#""generator":"BSARGeom","extras":{"title":"Pass 1"}}"#));
+        // 5 positions (60 bytes) + 3 indices (12 bytes)
+        let bin_offset = 20 + json_len;
+        assert_eq!(read_u32(&glb, bin_offset), 72);
+        assert_eq!(read_u32(&glb, bin_offset + 4), GLB_CHUNK_BIN);
+        assert!(json.contains(r#""buffers":[{"byteLength":72}]"#));
+    }
+}
@@ -0,0 +1,148 @@
+//! Ground moving target indication (GMTI) with two displaced phase centers.
+//!
+//! Two receive phase centers displaced along the track see the scene from the
+//! same positions with a time lag: the clutter echoes are identical in both
+//! channels while a target moving along the line of sight gains a phase
+//! difference (along-track interferometry, ATI), or leaves a residual once the
+//! channels are subtracted (displaced phase center antenna, DPCA).
+//!
+//! With a single transmitter, displacing the receive phase center by `d` moves
+//! the two-way phase center by `d/2`: the effective along-track baseline is
+//! half the physical one. Radial velocities are taken as half the bistatic
+//! range rate, the line-of-sight velocity in the monostatic case.
+
+use std::f64::consts::PI;
+
+/// Along-track interferometer formed by two receive phase centers.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AlongTrackInterferometer {
+    /// Effective along-track baseline in meters (signed, positive when the
+    /// second phase center is ahead)
+    pub effective_baseline_m: f64,
+    /// Carrier velocity in meters per second
+    pub velocity_mps: f64,
+    /// Wavelength in meters
+    pub wavelength_m: f64,
+}
+
+impl AlongTrackInterferometer {
+    /// Effective baseline factor of a single transmitter pair
+    const EFFECTIVE_BASELINE_FACTOR: f64 = 0.5;
+
+    /// Gets the interferometer of receive phase centers `baseline_m` apart
+    /// along the track, on a carrier at `velocity_mps`.
+    ///
+    /// Returns `None` without along-track baseline or velocity, the channels
+    /// then seeing the scene at the same time.
+    pub fn new(baseline_m: f64, velocity_mps: f64, wavelength_m: f64) -> Option<Self> {
+        (baseline_m != 0.0 && velocity_mps > 0.0 && wavelength_m > 0.0).then_some(Self {
+            effective_baseline_m: Self::EFFECTIVE_BASELINE_FACTOR * baseline_m,
+            velocity_mps,
+            wavelength_m,
+        })
+    }
+
+    /// Gets the time lag between the channels in seconds, the time the
+    /// trailing phase center takes to reach the position of the leading one.
+    #[inline]
+    pub fn time_lag_s(&self) -> f64 {
+        self.effective_baseline_m.abs() / self.velocity_mps
+    }
+
+    /// Gets the interferometric phase of a target at `radial_velocity_mps`
+    /// in radians (unwrapped).
+    #[inline]
+    pub fn phase_rad(&self, radial_velocity_mps: f64) -> f64 {
+        4.0 * PI * radial_velocity_mps * self.time_lag_s() / self.wavelength_m
+    }
+
+    /// Gets the first blind radial velocity in meters per second, wrapping
+    /// the phase by 2π: the unambiguous velocities span half of it on either
+    /// side of 0.
+    #[inline]
+    pub fn blind_velocity_mps(&self) -> f64 {
+        self.wavelength_m / (2.0 * self.time_lag_s())
+    }
+
+    /// Gets the minimum detectable radial velocity in meters per second, the
+    /// one whose phase stands `threshold` times the phase standard deviation of
+    /// the clutter at `clutter_to_noise_ratio` (linear) over `looks` looks.
+    ///
+    /// Returns `None` without clutter above the noise.
+    pub fn minimum_detectable_velocity_mps(&self, clutter_to_noise_ratio: f64, looks: u32, threshold: f64) -> Option<f64> {
+        let phase_std_rad = phase_std_rad(clutter_to_noise_ratio, looks)?;
+        Some(threshold * phase_std_rad * self.wavelength_m / (4.0 * PI * self.time_lag_s()))
+    }
+
+    /// Gets the pulse lag best matching the time lag at `prf_hz`, and the
+    /// residual displacement between the phase centers in meters at that lag
+    /// (0 when the DPCA condition is met).
+    pub fn dpca_pulse_lag(&self, prf_hz: f64) -> Option<(u32, f64)> {
+        if prf_hz <= 0.0 {
+            return None;
+        }
+        let pulses = (self.time_lag_s() * prf_hz).round().max(1.0);
+        Some((pulses as u32, self.velocity_mps * (pulses / prf_hz - self.time_lag_s()).abs()))
+    }
+}
+
+/// Gets the interferometric phase standard deviation in radians of two
+/// channels at `clutter_to_noise_ratio` (linear), averaged over `looks` looks
+/// (Cramér-Rao bound, valid over a few looks).
+///
+/// Returns `None` without clutter above the noise or without looks.
+pub fn phase_std_rad(clutter_to_noise_ratio: f64, looks: u32) -> Option<f64> {
+    if clutter_to_noise_ratio.is_nan() || clutter_to_noise_ratio <= 0.0 || looks == 0 {
+        return None;
+    }
+    // Coherence of the channels limited by the thermal noise
+    let coherence = clutter_to_noise_ratio / (1.0 + clutter_to_noise_ratio);
+    Some((1.0 - coherence * coherence).sqrt() / (coherence * (2.0 * looks as f64).sqrt()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn time_lag_and_blind_velocity() {
+        // Phase centers 2 m apart at 200 m/s, X-band
+        let interferometer = AlongTrackInterferometer::new(-2.0, 200.0, 0.03).unwrap();
+        assert_eq!(interferometer.effective_baseline_m, -1.0);
+        assert!((interferometer.time_lag_s() - 5e-3).abs() < 1e-15);
+        // The phase wraps by 2π at the blind velocity
+        let blind_velocity_mps = interferometer.blind_velocity_mps();
+        assert!((blind_velocity_mps - 3.0).abs() < 1e-12);
+        assert!((interferometer.phase_rad(blind_velocity_mps) - 2.0 * PI).abs() < 1e-12);
+        // No baseline or no motion, no lag
+        assert!(AlongTrackInterferometer::new(0.0, 200.0, 0.03).is_none());
+        assert!(AlongTrackInterferometer::new(2.0, 0.0, 0.03).is_none());
+    }
+
+    #[test]
+    fn minimum_detectable_velocity_falls_with_the_clutter_to_noise_ratio() {
+        let interferometer = AlongTrackInterferometer::new(2.0, 200.0, 0.03).unwrap();
+        let mdv_mps = |cnr_db: f64| interferometer.minimum_detectable_velocity_mps(10f64.powf(0.1 * cnr_db), 4, 3.0).unwrap();
+        assert!(mdv_mps(20.0) < mdv_mps(10.0));
+        // At the MDV, the phase stands the threshold times its deviation
+        let phase_std_at_20_db_rad = phase_std_rad(100.0, 4).unwrap();
+        assert!((interferometer.phase_rad(mdv_mps(20.0)) - 3.0 * phase_std_at_20_db_rad).abs() < 1e-12);
+        assert!(phase_std_rad(0.0, 4).is_none());
+        assert!(phase_std_rad(100.0, 0).is_none());
+    }
+
+    #[test]
+    fn dpca_pulse_lag_at_the_prf() {
+        // 5 ms time lag
+        let interferometer = AlongTrackInterferometer::new(2.0, 200.0, 0.03).unwrap();
+        // Met at 1 kHz after 5 pulses
+        let (pulses, residual_m) = interferometer.dpca_pulse_lag(1000.0).unwrap();
+        assert_eq!(pulses, 5);
+        assert!(residual_m < 1e-9);
+        // 0.45 ms off at 1.1 kHz: 6 pulses, 9 cm apart
+        let (pulses, residual_m) = interferometer.dpca_pulse_lag(1100.0).unwrap();
+        assert_eq!(pulses, 6);
+        assert!((residual_m - 200.0 * (6.0 / 1100.0 - 5e-3)).abs() < 1e-12);
+        assert!(interferometer.dpca_pulse_lag(0.0).is_none());
+    }
+}
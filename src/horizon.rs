@@ -0,0 +1,123 @@
+//! Azimuth-dependent horizon masks.
+//!
+//! A horizon mask gives, around a carrier, the minimum elevation above which
+//! the antenna sees the scene: below it the beam is blocked by the terrain or
+//! by obstacles. It is read from a user table or derived from a DEM (see
+//! [`crate::terrain::TerrainMasks::horizon_mask`]).
+
+use bevy::math::DVec3;
+
+/// Minimum elevation angle in degrees versus azimuth (clockwise from North),
+/// linearly interpolated between its points and periodic over 360°.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HorizonMask {
+    /// (azimuth, elevation) in degrees, azimuths sorted within [0, 360)
+    points: Vec<(f64, f64)>,
+}
+
+impl HorizonMask {
+    /// Creates a mask from (azimuth, elevation) points in degrees.
+    pub fn new(points: Vec<(f64, f64)>) -> Result<Self, String> {
+        if points.is_empty() {
+            return Err("A horizon mask needs at least one (azimuth, elevation) point".to_string());
+        }
+        if let Some((_, elevation_deg)) = points.iter().find(|(_, elevation_deg)| !(-90.0..=90.0).contains(elevation_deg)) {
+            return Err(format!("Elevation {elevation_deg}° is not within -90° - 90°"));
+        }
+        let mut points: Vec<_> = points.into_iter()
+            .map(|(azimuth_deg, elevation_deg)| (azimuth_deg.rem_euclid(360.0), elevation_deg))
+            .collect();
+        points.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Ok(Self { points })
+    }
+
+    /// Reads a mask table: one "azimuth elevation" pair in degrees per line,
+    /// separated by spaces, commas or semicolons, `#` starting a comment.
+    pub fn from_table(text: &str) -> Result<Self, String> {
+        let points = text.lines()
+            .enumerate()
+            .map(|(i, line)| (i, line.split('#').next().unwrap_or_default().trim()))
+            .filter(|(_, line)| !line.is_empty())
+            .map(|(i, line)| {
+                let values = line.split([' ', '\t', ',', ';'])
+                    .filter(|value| !value.is_empty())
+                    .map(str::parse::<f64>)
+                    .collect::<Result<Vec<_>, _>>();
+                match values.as_deref() {
+                    Ok(&[azimuth_deg, elevation_deg]) => Ok((azimuth_deg, elevation_deg)),
+                    _ => Err(format!("Line {}: expected \"azimuth elevation\"", i + 1)),
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Self::new(points)
+    }
+
+    /// Gets the (azimuth, elevation) points in degrees, sorted by azimuth.
+    #[inline]
+    pub fn points(&self) -> &[(f64, f64)] {
+        &self.points
+    }
+
+    /// Gets the mask elevation in degrees at `azimuth_deg`.
+    pub fn elevation_deg_at(&self, azimuth_deg: f64) -> f64 {
+        let azimuth_deg = azimuth_deg.rem_euclid(360.0);
+        let upper = self.points.partition_point(|&(point_azimuth_deg, _)| point_azimuth_deg < azimuth_deg);
+        // Neighbours, wrapping around North
+        let (a0, e0) = if upper == 0 {
+            self.points.last().map(|&(a, e)| (a - 360.0, e)).unwrap_or_default()
+        } else {
+            self.points[upper - 1]
+        };
+        let (a1, e1) = if upper == self.points.len() {
+            self.points.first().map(|&(a, e)| (a + 360.0, e)).unwrap_or_default()
+        } else {
+            self.points[upper]
+        };
+        if a1 - a0 > 0.0 { e0 + (azimuth_deg - a0) / (a1 - a0) * (e1 - e0) } else { e0 }
+    }
+
+    /// Checks whether the ENU `direction` is below the mask.
+    pub fn is_blocked(&self, direction: &DVec3) -> bool {
+        let ground_length = direction.truncate().length();
+        let elevation_deg = direction.z.atan2(ground_length).to_degrees();
+        let azimuth_deg = direction.x.atan2(direction.y).to_degrees();
+        elevation_deg < self.elevation_deg_at(azimuth_deg)
+    }
+
+    /// Gets the fraction of `targets` seen from `origin` below the mask (ENU points).
+    pub fn blocked_fraction(&self, origin: &DVec3, targets: &[DVec3]) -> f64 {
+        if targets.is_empty() {
+            return 0.0;
+        }
+        targets.iter().filter(|&target| self.is_blocked(&(*target - *origin))).count() as f64 / targets.len() as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interpolates_around_north() {
+        let mask = HorizonMask::from_table("# az el\n350, 10\n10 0\n 180;5  # South\n").unwrap();
+        assert_eq!(mask.points(), &[(10.0, 0.0), (180.0, 5.0), (350.0, 10.0)]);
+        assert_eq!(mask.elevation_deg_at(0.0), 5.0);
+        assert_eq!(mask.elevation_deg_at(-10.0), 10.0);
+        assert_eq!(mask.elevation_deg_at(95.0), 2.5);
+        assert_eq!(mask.elevation_deg_at(360.0 + 180.0), 5.0);
+        assert!(HorizonMask::from_table("10").is_err());
+        assert!(HorizonMask::from_table("10 95").is_err());
+        assert!(HorizonMask::from_table("").is_err());
+    }
+
+    #[test]
+    fn blocks_directions_below_the_mask() {
+        let mask = HorizonMask::new(vec![(90.0, 10.0), (270.0, -10.0)]).unwrap();
+        let origin = DVec3::new(0.0, 0.0, 100.0);
+        // East at ~5.7° below: blocked, West at the same depression: free
+        let (east, west) = (DVec3::new(1000.0, 0.0, 0.0), DVec3::new(-1000.0, 0.0, 0.0));
+        assert!(mask.is_blocked(&(east - origin)));
+        assert!(!mask.is_blocked(&(west - origin)));
+        assert_eq!(mask.blocked_fraction(&origin, &[east, west]), 0.5);
+    }
+}
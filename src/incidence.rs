@@ -0,0 +1,86 @@
+//! Allowed incidence angle windows.
+//!
+//! A radar system is usually specified over a range of incidence angles
+//! (grazing angles on the other side of 90°): too steep, the ground range
+//! resolution degrades; too shallow, the clutter-to-noise ratio and the
+//! shadowing do. The local incidence along the footprint edge is checked
+//! against the window, on the flat ground plane of the scene.
+
+use bevy::math::DVec3;
+
+/// Allowed local incidence angles in degrees, bounds included.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IncidenceWindow {
+    pub min_deg: f64,
+    pub max_deg: f64,
+}
+
+impl Default for IncidenceWindow {
+    fn default() -> Self {
+        Self { min_deg: 20.0, max_deg: 60.0 }
+    }
+}
+
+/// Gets the local incidence angle in degrees at the ground point `point_m`
+/// seen from `carrier_position_m` (ENU, flat ground): the angle between the
+/// local vertical and the direction to the carrier.
+pub fn local_incidence_deg(carrier_position_m: &DVec3, point_m: &DVec3) -> f64 {
+    (*carrier_position_m - *point_m).normalize_or_zero().z.clamp(-1.0, 1.0).acos().to_degrees()
+}
+
+impl IncidenceWindow {
+    /// Checks whether `incidence_deg` is allowed.
+    #[inline]
+    pub fn contains(&self, incidence_deg: f64) -> bool {
+        (self.min_deg..=self.max_deg).contains(&incidence_deg)
+    }
+
+    /// Lists the indices `i` of the footprint edge segments `[i, i + 1]` (ENU)
+    /// whose local incidence, at their middle, falls outside the window.
+    pub fn segments_outside(&self, carrier_position_m: &DVec3, footprint_m: &[DVec3]) -> Vec<usize> {
+        footprint_m.windows(2)
+            .enumerate()
+            .filter(|(_, segment)| {
+                let middle = 0.5 * (segment[0] + segment[1]);
+                !self.contains(local_incidence_deg(carrier_position_m, &middle))
+            })
+            .map(|(i, _)| i)
+            .collect()
+    }
+}
+
+/// Gets the (min, max) local incidences in degrees along the footprint edge (ENU).
+pub fn incidence_range_deg(carrier_position_m: &DVec3, footprint_m: &[DVec3]) -> Option<(f64, f64)> {
+    footprint_m.iter()
+        .map(|point| local_incidence_deg(carrier_position_m, point))
+        .fold(None, |range, incidence_deg| match range {
+            None => Some((incidence_deg, incidence_deg)),
+            Some((min, max)) => Some((f64::min(min, incidence_deg), f64::max(max, incidence_deg))),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn footprint_segments_outside_the_window() {
+        // Carrier 1000 m high above the origin: the incidence at a ground range
+        // x is atan(x / 1000)
+        let carrier = DVec3::new(0.0, 0.0, 1000.0);
+        assert!(local_incidence_deg(&carrier, &DVec3::ZERO).abs() < 1e-12);
+        assert!((local_incidence_deg(&carrier, &DVec3::new(1000.0, 0.0, 0.0)) - 45.0).abs() < 1e-12);
+        // Ground ranges of 0.2, 1, 2, 4 and 10 km: 11.3°, 45°, 63.4°, 76° and 84.3°
+        let footprint: Vec<DVec3> = [200.0, 1000.0, 2000.0, 4000.0, 10000.0].iter()
+            .map(|&x| DVec3::new(x, 0.0, 0.0))
+            .collect();
+        let (min_deg, max_deg) = incidence_range_deg(&carrier, &footprint).unwrap();
+        assert!((min_deg - 0.2f64.atan().to_degrees()).abs() < 1e-9);
+        assert!((max_deg - 10f64.atan().to_degrees()).abs() < 1e-9);
+        // Segment middles at 0.6, 1.5, 3 and 7 km: 31°, 56.3°, 71.6° and 81.9°
+        let window = IncidenceWindow { min_deg: 30.0, max_deg: 60.0 };
+        assert_eq!(window.segments_outside(&carrier, &footprint), vec![2, 3]);
+        assert!(window.contains(30.0) && window.contains(60.0) && !window.contains(60.1));
+        assert_eq!(incidence_range_deg(&carrier, &[]), None);
+    }
+}
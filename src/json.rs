@@ -0,0 +1,284 @@
+//! Minimal JSON values, read and written without dependency.
+//!
+//! Enough for the compute endpoint requests and responses: objects keep the
+//! order of their members, numbers are `f64` and non-finite numbers are
+//! written as `null`.
+
+use std::fmt;
+
+/// Deepest nesting of arrays and objects: the parser recurses on each level,
+/// and a request body of brackets would otherwise overflow the stack
+const MAX_DEPTH: usize = 64;
+
+/// A JSON value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonValue {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<JsonValue>),
+    /// Members in their order
+    Object(Vec<(String, JsonValue)>),
+}
+
+impl JsonValue {
+    /// Parses a JSON text, failing on trailing characters.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut parser = Parser { chars: text.char_indices().peekable(), text, depth: 0 };
+        let value = parser.value()?;
+        parser.skip_whitespace();
+        match parser.chars.next() {
+            None => Ok(value),
+            Some((i, _)) => Err(format!("unexpected character at {i}")),
+        }
+    }
+
+    /// Gets the member `key` of an object, `None` for other values.
+    pub fn get(&self, key: &str) -> Option<&JsonValue> {
+        match self {
+            JsonValue::Object(members) => members.iter().find(|(name, _)| name == key).map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    /// Gets an array of numbers.
+    pub fn numbers(values: impl IntoIterator<Item = f64>) -> Self {
+        JsonValue::Array(values.into_iter().map(JsonValue::Number).collect())
+    }
+}
+
+impl fmt::Display for JsonValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JsonValue::Null => write!(f, "null"),
+            JsonValue::Bool(value) => write!(f, "{value}"),
+            JsonValue::Number(value) if value.is_finite() => write!(f, "{value}"),
+            JsonValue::Number(_) => write!(f, "null"),
+            JsonValue::String(value) => write_string(f, value),
+            JsonValue::Array(values) => {
+                write!(f, "[")?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{value}")?;
+                }
+                write!(f, "]")
+            }
+            JsonValue::Object(members) => {
+                write!(f, "{{")?;
+                for (i, (name, value)) in members.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write_string(f, name)?;
+                    write!(f, ":{value}")?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+fn write_string(f: &mut fmt::Formatter<'_>, value: &str) -> fmt::Result {
+    write!(f, "\"")?;
+    for c in value.chars() {
+        match c {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\r' => write!(f, "\\r")?,
+            '\t' => write!(f, "\\t")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{c}")?,
+        }
+    }
+    write!(f, "\"")
+}
+
+/// Recursive descent parser over the characters of the text.
+struct Parser<'a> {
+    chars: std::iter::Peekable<std::str::CharIndices<'a>>,
+    text: &'a str,
+    /// Arrays and objects being parsed
+    depth: usize,
+}
+
+impl Parser<'_> {
+    fn skip_whitespace(&mut self) {
+        while self.chars.next_if(|(_, c)| c.is_ascii_whitespace()).is_some() {}
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        match self.chars.next() {
+            Some((_, c)) if c == expected => Ok(()),
+            Some((i, c)) => Err(format!("expected '{expected}' at {i}, found '{c}'")),
+            None => Err(format!("expected '{expected}', found the end")),
+        }
+    }
+
+    fn value(&mut self) -> Result<JsonValue, String> {
+        self.skip_whitespace();
+        let Some(&(i, c)) = self.chars.peek() else {
+            return Err("unexpected end".to_string());
+        };
+        match c {
+            '{' | '[' => {
+                if self.depth >= MAX_DEPTH {
+                    return Err("nesting too deep".to_string());
+                }
+                self.depth += 1;
+                let value = if c == '{' { self.object() } else { self.array() };
+                self.depth -= 1;
+                value
+            }
+            '"' => self.string().map(JsonValue::String),
+            't' => self.literal("true", JsonValue::Bool(true)),
+            'f' => self.literal("false", JsonValue::Bool(false)),
+            'n' => self.literal("null", JsonValue::Null),
+            '-' | '0'..='9' => self.number(),
+            _ => Err(format!("unexpected character '{c}' at {i}")),
+        }
+    }
+
+    fn literal(&mut self, literal: &str, value: JsonValue) -> Result<JsonValue, String> {
+        for expected in literal.chars() {
+            self.expect(expected)?;
+        }
+        Ok(value)
+    }
+
+    fn number(&mut self) -> Result<JsonValue, String> {
+        let start = self.chars.peek().map_or(self.text.len(), |(i, _)| *i);
+        let mut end = start;
+        while let Some((i, c)) = self.chars.next_if(|(_, c)| c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E')) {
+            end = i + c.len_utf8();
+        }
+        let number = &self.text[start..end];
+        number.parse::<f64>()
+            .map(JsonValue::Number)
+            .map_err(|_| format!("invalid number \"{number}\" at {start}"))
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        self.expect('"')?;
+        let mut string = String::new();
+        loop {
+            match self.chars.next() {
+                None => return Err("unterminated string".to_string()),
+                Some((_, '"')) => return Ok(string),
+                Some((i, '\\')) => match self.chars.next().map(|(_, c)| c) {
+                    Some('"') => string.push('"'),
+                    Some('\\') => string.push('\\'),
+                    Some('/') => string.push('/'),
+                    Some('b') => string.push('\u{8}'),
+                    Some('f') => string.push('\u{c}'),
+                    Some('n') => string.push('\n'),
+                    Some('r') => string.push('\r'),
+                    Some('t') => string.push('\t'),
+                    Some('u') => {
+                        let code = self.hex4()?;
+                        // A high surrogate is followed by its low surrogate
+                        let code = if (0xD800..0xDC00).contains(&code) {
+                            self.expect('\\')?;
+                            self.expect('u')?;
+                            0x10000 + ((code - 0xD800) << 10) + (self.hex4()?.wrapping_sub(0xDC00) & 0x3FF)
+                        } else {
+                            code
+                        };
+                        string.push(char::from_u32(code).ok_or_else(|| format!("invalid escape at {i}"))?);
+                    }
+                    _ => return Err(format!("invalid escape at {i}")),
+                },
+                Some((_, c)) => string.push(c),
+            }
+        }
+    }
+
+    fn hex4(&mut self) -> Result<u32, String> {
+        let mut code = 0;
+        for _ in 0..4 {
+            let digit = self.chars.next()
+                .and_then(|(_, c)| c.to_digit(16))
+                .ok_or("invalid \\u escape")?;
+            code = code * 16 + digit;
+        }
+        Ok(code)
+    }
+
+    fn array(&mut self) -> Result<JsonValue, String> {
+        self.expect('[')?;
+        let mut values = Vec::new();
+        self.skip_whitespace();
+        if self.chars.next_if(|(_, c)| *c == ']').is_some() {
+            return Ok(JsonValue::Array(values));
+        }
+        loop {
+            values.push(self.value()?);
+            self.skip_whitespace();
+            if self.chars.next_if(|(_, c)| *c == ',').is_none() {
+                self.expect(']')?;
+                return Ok(JsonValue::Array(values));
+            }
+        }
+    }
+
+    fn object(&mut self) -> Result<JsonValue, String> {
+        self.expect('{')?;
+        let mut members = Vec::new();
+        self.skip_whitespace();
+        if self.chars.next_if(|(_, c)| *c == '}').is_some() {
+            return Ok(JsonValue::Object(members));
+        }
+        loop {
+            self.skip_whitespace();
+            let name = self.string()?;
+            self.skip_whitespace();
+            self.expect(':')?;
+            members.push((name, self.value()?));
+            self.skip_whitespace();
+            if self.chars.next_if(|(_, c)| *c == ',').is_none() {
+                self.expect('}')?;
+                return Ok(JsonValue::Object(members));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_roundtrip() {
+        let text = r#" {"title": "Pass \"1\"\né", "parameters": {"tx.height_m": 3000, "rx.is_stationary": true},
+            "values": [-1.5e3, 0, null, false], "empty": {}, "none": []} "#;
+        let value = JsonValue::parse(text).unwrap();
+        assert_eq!(value.get("title"), Some(&JsonValue::String("Pass \"1\"\né".to_string())));
+        assert_eq!(value.get("parameters").and_then(|parameters| parameters.get("tx.height_m")), Some(&JsonValue::Number(3000.0)));
+        assert_eq!(value.get("values"), Some(&JsonValue::Array(vec![
+            JsonValue::Number(-1500.0), JsonValue::Number(0.0), JsonValue::Null, JsonValue::Bool(false),
+        ])));
+        assert_eq!(JsonValue::parse(&value.to_string()), Ok(value));
+        assert_eq!(JsonValue::numbers([1.0, f64::NAN]).to_string(), "[1,null]");
+        assert_eq!(JsonValue::parse(r#""\ud83d\ude00""#), Ok(JsonValue::String("😀".to_string())));
+    }
+
+    #[test]
+    fn invalid_json() {
+        for text in ["", "{", "[1,]", "{\"a\" 1}", "tru", "\"open", "1 2", "{\"a\":-}"] {
+            assert!(JsonValue::parse(text).is_err(), "{text}");
+        }
+    }
+
+    #[test]
+    fn nesting_is_limited() {
+        let nested = |depth: usize| format!("{}{}", "[".repeat(depth), "]".repeat(depth));
+        assert!(JsonValue::parse(&nested(MAX_DEPTH)).is_ok());
+        assert_eq!(JsonValue::parse(&nested(MAX_DEPTH + 1)), Err("nesting too deep".to_string()));
+        // A request body of brackets fails instead of overflowing the stack
+        assert_eq!(JsonValue::parse(&"[".repeat(1 << 20)), Err("nesting too deep".to_string()));
+        assert_eq!(JsonValue::parse(&"{\"a\":".repeat(1 << 16)), Err("nesting too deep".to_string()));
+    }
+}
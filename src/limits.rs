@@ -0,0 +1,93 @@
+//! Carrier input limits.
+//!
+//! The carrier height and velocity inputs are bounded by configurable limits,
+//! by default wide enough for spaceborne LEO carriers. They can be set at
+//! startup from the `BSARGEOM_MAX_HEIGHT_M` and `BSARGEOM_MAX_VELOCITY_MPS`
+//! environment variables, or in the "Display" window, and are validated
+//! against [`CarrierLimits::MAX_HEIGHT_BOUNDS_M`] and
+//! [`CarrierLimits::MAX_VELOCITY_BOUNDS_MPS`].
+
+use std::ops::RangeInclusive;
+
+use crate::constants::{DEFAULT_MAX_HEIGHT_M, DEFAULT_MAX_VELOCITY_MPS};
+
+/// Environment variables setting the limits at startup.
+pub const MAX_HEIGHT_ENV_VAR: &str = "BSARGEOM_MAX_HEIGHT_M";
+pub const MAX_VELOCITY_ENV_VAR: &str = "BSARGEOM_MAX_VELOCITY_MPS";
+
+/// Maximum carrier height and velocity accepted by the inputs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CarrierLimits {
+    pub max_height_m: f64,
+    pub max_velocity_mps: f64,
+}
+
+impl Default for CarrierLimits {
+    fn default() -> Self {
+        Self {
+            max_height_m: DEFAULT_MAX_HEIGHT_M,
+            max_velocity_mps: DEFAULT_MAX_VELOCITY_MPS,
+        }
+    }
+}
+
+impl CarrierLimits {
+    /// Accepted maximum heights, up to beyond the geostationary orbit.
+    pub const MAX_HEIGHT_BOUNDS_M: RangeInclusive<f64> = 100.0..=4e7;
+    /// Accepted maximum velocities, up to beyond the Earth escape velocity.
+    pub const MAX_VELOCITY_BOUNDS_MPS: RangeInclusive<f64> = 10.0..=12_000.0;
+
+    /// Creates validated limits.
+    pub fn new(max_height_m: f64, max_velocity_mps: f64) -> Result<Self, String> {
+        let check = |name: &str, value: f64, bounds: &RangeInclusive<f64>, unit: &str| {
+            if bounds.contains(&value) {
+                Ok(value)
+            } else {
+                Err(format!("Maximum {name} {value} {unit} is outside {} - {} {unit}", bounds.start(), bounds.end()))
+            }
+        };
+        Ok(Self {
+            max_height_m: check("height", max_height_m, &Self::MAX_HEIGHT_BOUNDS_M, "m")?,
+            max_velocity_mps: check("velocity", max_velocity_mps, &Self::MAX_VELOCITY_BOUNDS_MPS, "m/s")?,
+        })
+    }
+
+    /// Reads the limits from optional configuration values, the missing ones
+    /// keeping their default.
+    pub fn from_values(max_height_m: Option<&str>, max_velocity_mps: Option<&str>) -> Result<Self, String> {
+        let parse = |value: Option<&str>, name: &str, default: f64| {
+            value.map_or(Ok(default), |value| {
+                value.trim().parse::<f64>().map_err(|_| format!("Invalid {name} \"{value}\""))
+            })
+        };
+        let defaults = Self::default();
+        Self::new(
+            parse(max_height_m, MAX_HEIGHT_ENV_VAR, defaults.max_height_m)?,
+            parse(max_velocity_mps, MAX_VELOCITY_ENV_VAR, defaults.max_velocity_mps)?
+        )
+    }
+
+    /// Reads the limits from the environment variables.
+    pub fn from_env() -> Result<Self, String> {
+        let (max_height_m, max_velocity_mps) = (std::env::var(MAX_HEIGHT_ENV_VAR).ok(), std::env::var(MAX_VELOCITY_ENV_VAR).ok());
+        Self::from_values(max_height_m.as_deref(), max_velocity_mps.as_deref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validated_limits() {
+        assert_eq!(CarrierLimits::from_values(None, None), Ok(CarrierLimits::default()));
+        // Geostationary transmitter
+        let limits = CarrierLimits::from_values(Some(" 3.6e7"), None).unwrap();
+        assert_eq!(limits.max_height_m, 3.6e7);
+        assert_eq!(limits.max_velocity_mps, DEFAULT_MAX_VELOCITY_MPS);
+        assert!(CarrierLimits::from_values(None, Some("fast")).is_err());
+        assert!(CarrierLimits::new(1e9, 7500.0).is_err());
+        assert!(CarrierLimits::new(7e5, -1.0).is_err());
+        assert!(CarrierLimits::new(f64::NAN, 7500.0).is_err());
+    }
+}
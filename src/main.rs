@@ -6,23 +6,76 @@
 use bevy::prelude::*;
 use bevy_panorbit_camera::PanOrbitCameraPlugin;
 
+pub mod antenna_pattern;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod batch;
 pub mod bsar;
+pub mod burst;
 pub mod camera;
+pub mod chirp;
+pub mod clutter;
 pub mod constants;
 pub mod contour;
 pub mod coordinates;
+pub mod coverage;
+pub mod dem;
 pub mod download;
 pub mod entities;
+pub mod exercise;
+pub mod expression;
+pub mod extension;
+pub mod epoch;
+pub mod feed;
+pub mod formation;
+pub mod gmti;
+pub mod gltf;
+pub mod horizon;
+pub mod incidence;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod json;
+pub mod limits;
+pub mod motion;
+pub mod moving_target;
+pub mod nadir;
+pub mod orbit;
+pub mod palette;
+pub mod phased_array;
+pub mod preferences;
+pub mod propagation;
 pub mod raster;
+pub mod raw_data;
+pub mod roi;
+pub mod scenario;
 pub mod scene;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod server;
+pub mod snippets;
+pub mod squint;
+pub mod steering;
+pub mod sun;
+pub mod terrain;
 pub mod textdraw;
 pub mod ui;
+pub mod vector_export;
+pub mod visibility;
 pub mod world;
 
 use scene::ScenePlugin;
 use ui::AppPlugin;
 
 fn main() {
+    // Headless batch evaluation of scenario files, or compute endpoint,
+    // without window
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let args: Vec<String> = std::env::args().skip(1).collect();
+        match args.first().map(String::as_str) {
+            Some(batch::BATCH_FLAG) => std::process::exit(batch::run(&args[1..])),
+            Some(server::SERVE_FLAG) => std::process::exit(server::run(&args[1..])),
+            _ => {}
+        }
+    }
+
     let mut app = App::new();
     app
         .insert_resource(ClearColor(Color::BLACK))
@@ -41,6 +94,8 @@ fn main() {
                     prevent_default_event_handling: false,
                     ..default()
                 }),
+                // The detached infos window must not keep the app running
+                exit_condition: bevy::window::ExitCondition::OnPrimaryClosed,
                 ..default()
             }))           
         .add_plugins(PanOrbitCameraPlugin)
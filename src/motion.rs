@@ -0,0 +1,131 @@
+//! Carrier motion along its trajectory.
+//!
+//! Carriers move from their time 0 s position with their initial velocity
+//! vector, optionally turning at a constant rate around the local vertical
+//! (ships, vehicles or aircraft in a coordinated turn) and changing speed at a
+//! constant rate. Without turn nor speed change the trajectory is the straight
+//! line of the former linear model.
+
+use bevy::math::{DQuat, DVec3};
+
+/// Integration steps per second of trajectory (Simpson rule)
+const STEPS_PER_SECOND: f64 = 20.0;
+/// Bounds on the number of integration steps
+const MIN_STEPS: usize = 16;
+const MAX_STEPS: usize = 4096;
+
+/// Turn and speed profile of a carrier along its trajectory.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct MotionProfile {
+    /// Heading change rate in degrees per second, positive turning right
+    /// (clockwise seen from above)
+    pub turn_rate_degps: f64,
+    /// Speed change rate in m/s², the speed stopping at zero
+    pub speed_rate_mps2: f64,
+}
+
+impl MotionProfile {
+    /// Checks whether the trajectory is a straight line at constant speed.
+    #[inline]
+    pub fn is_straight(&self) -> bool {
+        self.turn_rate_degps == 0.0 && self.speed_rate_mps2 == 0.0
+    }
+
+    /// Gets the heading change in degrees after `time_s`.
+    #[inline]
+    pub fn heading_change_deg(&self, time_s: f64) -> f64 {
+        self.turn_rate_degps * time_s
+    }
+
+    /// Gets the rotation (ENU) of the carrier around the local vertical after `time_s`.
+    #[inline]
+    pub fn turn_at(&self, time_s: f64) -> DQuat {
+        // Headings are clockwise seen from above, i.e. around -Up
+        DQuat::from_rotation_z(-self.heading_change_deg(time_s).to_radians())
+    }
+
+    /// Gets the velocity vector (ENU) after `time_s` from the initial one.
+    pub fn velocity_at(&self, initial_velocity_mps: &DVec3, time_s: f64) -> DVec3 {
+        let initial_speed_mps = initial_velocity_mps.length();
+        if initial_speed_mps == 0.0 {
+            return DVec3::ZERO; // No direction to move along
+        }
+        let speed_mps = (initial_speed_mps + self.speed_rate_mps2 * time_s).max(0.0);
+        self.turn_at(time_s) * *initial_velocity_mps * (speed_mps / initial_speed_mps)
+    }
+
+    /// Gets the acceleration vector (ENU) after `time_s` from the initial
+    /// velocity vector: tangential (speed change) plus centripetal (turn).
+    ///
+    /// Returns `None` for a straight trajectory at constant speed.
+    pub fn acceleration_at(&self, initial_velocity_mps: &DVec3, time_s: f64) -> Option<DVec3> {
+        if self.is_straight() {
+            return None;
+        }
+        let velocity_mps = self.velocity_at(initial_velocity_mps, time_s);
+        if velocity_mps == DVec3::ZERO {
+            return Some(DVec3::ZERO); // Stopped
+        }
+        // Turn rate vector along -Up (clockwise seen from above)
+        let turn_rate_radps = DVec3::new(0.0, 0.0, -self.turn_rate_degps.to_radians());
+        Some(self.speed_rate_mps2 * velocity_mps.normalize() + turn_rate_radps.cross(velocity_mps))
+    }
+
+    /// Gets the displacement (ENU) after `time_s` from the initial velocity vector.
+    pub fn displacement_at(&self, initial_velocity_mps: &DVec3, time_s: f64) -> DVec3 {
+        if self.is_straight() {
+            return time_s * *initial_velocity_mps;
+        }
+        // Composite Simpson rule (even number of steps)
+        let steps = ((STEPS_PER_SECOND * time_s.abs()).ceil() as usize).clamp(MIN_STEPS, MAX_STEPS) & !1;
+        let step_s = time_s / steps as f64;
+        let sum = (0..=steps).fold(DVec3::ZERO, |sum, i| {
+            let weight = if i == 0 || i == steps { 1.0 } else if i % 2 == 1 { 4.0 } else { 2.0 };
+            sum + weight * self.velocity_at(initial_velocity_mps, i as f64 * step_s)
+        });
+        sum * step_s / 3.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn straight_and_accelerated_motions() {
+        let velocity_mps = DVec3::new(0.0, 10.0, 1.0);
+        let straight = MotionProfile::default();
+        assert!(straight.is_straight());
+        assert_eq!(straight.displacement_at(&velocity_mps, 3.0), 3.0 * velocity_mps);
+        assert_eq!(straight.velocity_at(&velocity_mps, 3.0), velocity_mps);
+        // Braking at 2 m/s² from √101 m/s: stops, then stays
+        let braking = MotionProfile { speed_rate_mps2: -2.0, ..Default::default() };
+        let stop_s = velocity_mps.length() / 2.0;
+        assert_eq!(braking.velocity_at(&velocity_mps, stop_s + 1.0), DVec3::ZERO);
+        let expected_m = 0.5 * stop_s * velocity_mps;
+        assert!((braking.displacement_at(&velocity_mps, 10.0) - expected_m).length() < 1e-2);
+        assert_eq!(straight.acceleration_at(&velocity_mps, 3.0), None);
+        let acceleration_mps2 = braking.acceleration_at(&velocity_mps, 1.0).unwrap();
+        assert!((acceleration_mps2 + 2.0 * velocity_mps.normalize()).length() < 1e-12);
+        assert_eq!(braking.acceleration_at(&velocity_mps, stop_s + 1.0), Some(DVec3::ZERO));
+    }
+
+    #[test]
+    fn turns_on_a_circle() {
+        // 10 m/s North turning right at 6°/s: a 60 s circle of radius 10 / (6° in rad)
+        let velocity_mps = DVec3::new(0.0, 10.0, 0.0);
+        let turn = MotionProfile { turn_rate_degps: 6.0, ..Default::default() };
+        let radius_m = 10.0 / 6f64.to_radians();
+        // After a quarter turn: heading East, at (radius, radius)
+        let velocity_quarter = turn.velocity_at(&velocity_mps, 15.0);
+        assert!((velocity_quarter - DVec3::new(10.0, 0.0, 0.0)).length() < 1e-9);
+        // Centripetal acceleration v²/R towards the circle center, South then
+        let acceleration_mps2 = turn.acceleration_at(&velocity_mps, 15.0).unwrap();
+        assert!((acceleration_mps2 - DVec3::new(0.0, -100.0 / radius_m, 0.0)).length() < 1e-9);
+        let displacement = turn.displacement_at(&velocity_mps, 15.0);
+        assert!((displacement - DVec3::new(radius_m, radius_m, 0.0)).length() < 1e-6, "{displacement}");
+        // After a half turn: 2 radii East of the start
+        let displacement = turn.displacement_at(&velocity_mps, 30.0);
+        assert!((displacement - DVec3::new(2.0 * radius_m, 0.0, 0.0)).length() < 1e-6, "{displacement}");
+    }
+}
@@ -0,0 +1,143 @@
+//! Ground moving targets.
+//!
+//! A target moving on the ground shifts its Doppler frequency from the one of
+//! the clutter at its location. Focused as the clutter, it appears displaced
+//! along its iso-range contour, at the clutter point sharing its Doppler
+//! frequency (the "train off the track" effect).
+
+use bevy::math::{DVec2, DVec3};
+
+use crate::bsar::doppler_frequency_sg;
+
+/// A target moving on the ground plane at constant velocity.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MovingTarget {
+    /// Position (East, North) in meters, in the ENU ground plane
+    pub position_m: DVec2,
+    pub speed_mps: f64,
+    /// Heading clockwise from the North in degrees
+    pub heading_deg: f64,
+}
+
+impl Default for MovingTarget {
+    fn default() -> Self {
+        Self {
+            position_m: DVec2::ZERO,
+            speed_mps: 15.0,
+            heading_deg: 90.0,
+        }
+    }
+}
+
+/// Doppler signature of a moving target in the BSAR image.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MovingTargetSignature {
+    /// Doppler frequency of the clutter at the target location in Hz
+    pub clutter_doppler_hz: f64,
+    /// Doppler shift of the target from the clutter in Hz
+    pub doppler_shift_hz: f64,
+    /// Radial velocity of the target in meters per second, half its bistatic
+    /// range rate (positive receding)
+    pub radial_velocity_mps: f64,
+    /// Apparent position (East, North) of the target in the image in meters,
+    /// in the ENU ground plane
+    pub apparent_position_m: DVec2,
+}
+
+impl MovingTargetSignature {
+    /// Gets the Doppler frequency of the target in Hz.
+    #[inline]
+    pub fn target_doppler_hz(&self) -> f64 {
+        self.clutter_doppler_hz + self.doppler_shift_hz
+    }
+}
+
+impl MovingTarget {
+    /// Gets the target velocity vector in ENU frame in meters per second.
+    pub fn velocity_mps(&self) -> DVec3 {
+        let (sin, cos) = self.heading_deg.to_radians().sin_cos();
+        self.speed_mps * DVec3::new(sin, cos, 0.0)
+    }
+
+    /// Gets the Doppler signature of the target seen by the Tx and the Rx at
+    /// `tx_position_m` and `rx_position_m` moving at `tx_velocity_mps` and
+    /// `rx_velocity_mps` (World frame, Z-up), at wavelength `lem`.
+    ///
+    /// The azimuth displacement is linearized along the iso-range contour.
+    /// Returns `None` when a carrier stands on the target or when the clutter
+    /// Doppler frequency does not vary along the iso-range contour.
+    pub fn signature(
+        &self,
+        lem: f64,
+        tx_position_m: &DVec3,
+        tx_velocity_mps: &DVec3,
+        rx_position_m: &DVec3,
+        rx_velocity_mps: &DVec3,
+    ) -> Option<MovingTargetSignature> {
+        let position_m = self.position_m.extend(0.0);
+        let (txp, rxp) = (position_m - tx_position_m, position_m - rx_position_m);
+        let clutter_doppler_hz = doppler_frequency_sg(lem, &txp, tx_velocity_mps, &rxp, rx_velocity_mps);
+        if !clutter_doppler_hz.is_finite() {
+            return None;
+        }
+        let (utxp, urxp) = (txp.normalize(), rxp.normalize());
+        // The target velocity lengthens the bistatic range
+        let bistatic_range_rate_mps = self.velocity_mps().dot(utxp + urxp);
+        let doppler_shift_hz = -bistatic_range_rate_mps / lem;
+        // Gradient of the clutter Doppler frequency over the ground, across the
+        // lines of sight
+        let doppler_gradient = |velocity_mps: &DVec3, unit: DVec3, range_m: f64| {
+            (*velocity_mps - velocity_mps.dot(unit) * unit) / range_m
+        };
+        let doppler_gradient_hzpm = ((doppler_gradient(tx_velocity_mps, utxp, txp.length())
+            + doppler_gradient(rx_velocity_mps, urxp, rxp.length())) / lem).truncate();
+        // Along the iso-range contour, orthogonal to the bistatic range gradient
+        let tangent = (utxp + urxp).truncate().perp().try_normalize()?;
+        let doppler_slope_hzpm = doppler_gradient_hzpm.dot(tangent);
+        if doppler_slope_hzpm.abs() < f64::EPSILON {
+            return None;
+        }
+        Some(MovingTargetSignature {
+            clutter_doppler_hz,
+            doppler_shift_hz,
+            radial_velocity_mps: 0.5 * bistatic_range_rate_mps,
+            apparent_position_m: self.position_m + doppler_shift_hz / doppler_slope_hzpm * tangent,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn monostatic_target_is_displaced_by_its_radial_velocity() {
+        // Carrier 4000 m West of the target at 3000 m, flying North at 200 m/s
+        let (position_m, velocity_mps) = (DVec3::new(-4000.0, 0.0, 3000.0), DVec3::new(0.0, 200.0, 0.0));
+        let lem = 0.03;
+        // Target moving East, away from the carrier, at 10 m/s
+        let target = MovingTarget { position_m: DVec2::ZERO, speed_mps: 10.0, heading_deg: 90.0 };
+        let signature = target.signature(lem, &position_m, &velocity_mps, &position_m, &velocity_mps).unwrap();
+        // Broadside: no clutter Doppler
+        assert!(signature.clutter_doppler_hz.abs() < 1e-9);
+        // Radial velocity of 10 m/s x 4000 / 5000
+        assert!((signature.radial_velocity_mps - 8.0).abs() < 1e-12);
+        assert!((signature.doppler_shift_hz + 2.0 * 8.0 / lem).abs() < 1e-9);
+        assert_eq!(signature.target_doppler_hz(), signature.clutter_doppler_hz + signature.doppler_shift_hz);
+        // Displaced along the track by - R vr / V = -200 m
+        assert!(signature.apparent_position_m.distance(DVec2::new(0.0, -200.0)) < 1e-9);
+    }
+
+    #[test]
+    fn target_moving_along_the_iso_range_is_not_shifted() {
+        let (position_m, velocity_mps) = (DVec3::new(-4000.0, 0.0, 3000.0), DVec3::new(0.0, 200.0, 0.0));
+        // Moving North, across the line of sight at broadside
+        let target = MovingTarget { position_m: DVec2::ZERO, speed_mps: 10.0, heading_deg: 0.0 };
+        let signature = target.signature(0.03, &position_m, &velocity_mps, &position_m, &velocity_mps).unwrap();
+        assert!(signature.doppler_shift_hz.abs() < 1e-9);
+        assert!(signature.apparent_position_m.length() < 1e-9);
+        // A carrier standing on the target
+        let on_target_m = DVec3::ZERO;
+        assert!(target.signature(0.03, &on_target_m, &velocity_mps, &position_m, &velocity_mps).is_none());
+    }
+}
@@ -0,0 +1,208 @@
+//! Nadir echo location.
+//!
+//! The flat ground reflects a strong specular echo, received well before the
+//! scene echoes: at the Tx nadir in monostatic, at the specular point between
+//! the carriers in bistatic (the ground point of minimum bistatic range). The
+//! echoes of the following pulses then fall into the receive window of the
+//! current pulse when the range to the scene exceeds the nadir echo range by
+//! a multiple of the range ambiguity c/PRF, blinding the scene along an
+//! iso-range band: a classic design check of the PRF.
+
+use std::ops::RangeInclusive;
+
+use bevy::math::DVec3;
+
+use crate::bsar::SPEED_OF_LIGHT_IN_VACUUM;
+
+/// Iso-range band of the scene overlapped by the nadir echo of a later pulse.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NadirEchoBand {
+    /// Pulses between the scene echo and the overlapping nadir echo
+    pub order: usize,
+    /// Bistatic ranges of the band in meters
+    pub range_min_m: f64,
+    pub range_max_m: f64,
+}
+
+/// Gets the bistatic range in meters from `tx_position_m` to `point_m` to
+/// `rx_position_m`.
+#[inline]
+pub fn bistatic_range_m(tx_position_m: &DVec3, rx_position_m: &DVec3, point_m: &DVec3) -> f64 {
+    tx_position_m.distance(*point_m) + rx_position_m.distance(*point_m)
+}
+
+/// Gets the specular point of the flat ground (ENU, `z = 0`), where the
+/// bistatic range is minimum, `None` when a carrier is not above the ground.
+pub fn specular_point_m(tx_position_m: &DVec3, rx_position_m: &DVec3) -> Option<DVec3> {
+    if tx_position_m.z <= 0.0 || rx_position_m.z <= 0.0 {
+        return None;
+    }
+    // On the line from the Tx to the Rx image below the ground
+    let t = tx_position_m.z / (tx_position_m.z + rx_position_m.z);
+    let point_m = tx_position_m.lerp(*rx_position_m, t);
+    Some(DVec3::new(point_m.x, point_m.y, 0.0))
+}
+
+/// Lists the iso-range bands within the receive window `range_min_m` -
+/// `range_max_m` (bistatic ranges of the scene) overlapped by the nadir echo
+/// at `nadir_range_m`, each echo lasting one pulse of `pulse_duration_s`.
+pub fn nadir_echo_bands(
+    nadir_range_m: f64,
+    pulse_duration_s: f64,
+    prf_hz: f64,
+    range_min_m: f64,
+    range_max_m: f64,
+) -> Vec<NadirEchoBand> {
+    if !(prf_hz > 0.0 && nadir_range_m.is_finite() && range_min_m <= range_max_m) {
+        return vec![];
+    }
+    let ambiguous_range_m = SPEED_OF_LIGHT_IN_VACUUM / prf_hz;
+    // Scene and nadir echoes overlap when their ranges differ by less than one pulse
+    let pulse_range_m = SPEED_OF_LIGHT_IN_VACUUM * pulse_duration_s;
+    let first_order = ((range_min_m - pulse_range_m - nadir_range_m) / ambiguous_range_m).ceil().max(0.0) as usize;
+    (first_order..)
+        .map(|order| (order, nadir_range_m + order as f64 * ambiguous_range_m))
+        .take_while(|(_, echo_range_m)| echo_range_m - pulse_range_m < range_max_m)
+        .map(|(order, echo_range_m)| NadirEchoBand {
+            order,
+            range_min_m: (echo_range_m - pulse_range_m).max(range_min_m),
+            range_max_m: (echo_range_m + pulse_range_m).min(range_max_m),
+        })
+        .filter(|band| band.range_min_m < band.range_max_m)
+        .collect()
+}
+
+/// Gets the bistatic ranges in meters of the scene blinded, at `prf_hz`, by
+/// the event at `offset_range_m` (0 for the pulse transmission, the nadir echo
+/// range for the nadir echo) of the pulse `order` pulses later: the scene
+/// echoes overlapping it, each lasting one pulse of `pulse_duration_s`.
+#[inline]
+pub fn timing_band_m(offset_range_m: f64, pulse_duration_s: f64, order: usize, prf_hz: f64) -> (f64, f64) {
+    let echo_range_m = offset_range_m + order as f64 * SPEED_OF_LIGHT_IN_VACUUM / prf_hz;
+    let pulse_range_m = SPEED_OF_LIGHT_IN_VACUUM * pulse_duration_s;
+    (echo_range_m - pulse_range_m, echo_range_m + pulse_range_m)
+}
+
+/// Gets the orders of the [`timing_band_m`] crossing the ranges from
+/// `range_min_m` to `range_max_m` for a PRF from `prf_min_hz` to `prf_max_hz`:
+/// the bands of the PRF timing (zebra) diagram, the band ranges falling with
+/// the PRF. `None` when no band crosses them.
+pub fn timing_band_orders(
+    offset_range_m: f64,
+    pulse_duration_s: f64,
+    prf_min_hz: f64,
+    prf_max_hz: f64,
+    range_min_m: f64,
+    range_max_m: f64,
+) -> Option<RangeInclusive<usize>> {
+    let pulse_range_m = SPEED_OF_LIGHT_IN_VACUUM * pulse_duration_s;
+    let is_valid = prf_min_hz > 0.0 && prf_min_hz <= prf_max_hz
+        && offset_range_m.is_finite() && range_min_m <= range_max_m;
+    if !is_valid || offset_range_m - pulse_range_m >= range_max_m {
+        return None;
+    }
+    let first_order = ((range_min_m - pulse_range_m - offset_range_m) * prf_min_hz / SPEED_OF_LIGHT_IN_VACUUM).ceil().max(0.0);
+    let last_order = ((range_max_m + pulse_range_m - offset_range_m) * prf_max_hz / SPEED_OF_LIGHT_IN_VACUUM).floor();
+    (first_order <= last_order).then_some(first_order as usize..=last_order as usize)
+}
+
+/// Gets the closed iso-range curve of `range_m` on the flat ground (ENU,
+/// `z = 0`) as `point_count` points, `None` below the specular point range.
+pub fn iso_range_curve_m(tx_position_m: &DVec3, rx_position_m: &DVec3, range_m: f64, point_count: usize) -> Option<Vec<DVec3>> {
+    let specular_point_m = specular_point_m(tx_position_m, rx_position_m)?;
+    let range_at = |point_m: &DVec3| bistatic_range_m(tx_position_m, rx_position_m, point_m);
+    if range_m <= range_at(&specular_point_m) {
+        return None;
+    }
+    // The bistatic range being convex, it increases along any ray from its
+    // minimum: the curve is found by bisection along the rays
+    let curve = (0..point_count)
+        .map(|i| {
+            let angle = std::f64::consts::TAU * i as f64 / (point_count - 1) as f64;
+            let direction = DVec3::new(angle.cos(), angle.sin(), 0.0);
+            let mut distance_max_m = range_m;
+            while range_at(&(specular_point_m + distance_max_m * direction)) < range_m {
+                distance_max_m *= 2.0;
+            }
+            let mut distance_min_m = 0.0;
+            for _ in 0..64 {
+                let distance_m = 0.5 * (distance_min_m + distance_max_m);
+                if range_at(&(specular_point_m + distance_m * direction)) < range_m {
+                    distance_min_m = distance_m;
+                } else {
+                    distance_max_m = distance_m;
+                }
+            }
+            specular_point_m + 0.5 * (distance_min_m + distance_max_m) * direction
+        })
+        .collect();
+    Some(curve)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn monostatic_nadir_echo_bands() {
+        let position_m = DVec3::new(100.0, -50.0, 1000.0);
+        let nadir_m = specular_point_m(&position_m, &position_m).unwrap();
+        assert_eq!(nadir_m, DVec3::new(100.0, -50.0, 0.0));
+        let nadir_range_m = bistatic_range_m(&position_m, &position_m, &nadir_m);
+        assert_eq!(nadir_range_m, 2000.0);
+
+        // 3 km range ambiguity, 1 µs pulse: the echo of the next pulse at 5 km
+        let prf_hz = SPEED_OF_LIGHT_IN_VACUUM / 3000.0;
+        let pulse_range_m = SPEED_OF_LIGHT_IN_VACUUM * 1e-6;
+        let bands = nadir_echo_bands(nadir_range_m, 1e-6, prf_hz, 4500.0, 8100.0);
+        assert_eq!(bands.len(), 2);
+        assert_eq!(bands[0].order, 1);
+        assert!((bands[0].range_min_m - (5000.0 - pulse_range_m)).abs() < 1e-6);
+        assert!((bands[0].range_max_m - (5000.0 + pulse_range_m)).abs() < 1e-6);
+        // Clipped to the receive window
+        assert_eq!(bands[1].order, 2);
+        assert_eq!(bands[1].range_max_m, 8100.0);
+        assert!(nadir_echo_bands(nadir_range_m, 1e-6, prf_hz, 5500.0, 7500.0).is_empty());
+
+        // Monostatic iso-range: circle around the nadir
+        let curve = iso_range_curve_m(&position_m, &position_m, 5000.0, 9).unwrap();
+        assert_eq!(curve.len(), 9);
+        for point_m in curve.iter() {
+            assert!((point_m.distance(nadir_m) - (2500f64.powi(2) - 1000f64.powi(2)).sqrt()).abs() < 1e-6);
+        }
+        assert!(iso_range_curve_m(&position_m, &position_m, 1500.0, 9).is_none());
+    }
+
+    #[test]
+    fn timing_diagram_bands() {
+        // 3 km range ambiguity at 100 kHz, 1 µs pulse
+        let prf_hz = SPEED_OF_LIGHT_IN_VACUUM / 3000.0;
+        let pulse_range_m = SPEED_OF_LIGHT_IN_VACUUM * 1e-6;
+        let (range_min_m, range_max_m) = timing_band_m(2000.0, 1e-6, 2, prf_hz);
+        assert!((range_min_m - (8000.0 - pulse_range_m)).abs() < 1e-6);
+        assert!((range_max_m - (8000.0 + pulse_range_m)).abs() < 1e-6);
+        // The nadir echo bands at a PRF are the timing bands of the nadir echo
+        for band in nadir_echo_bands(2000.0, 1e-6, prf_hz, 4500.0, 8100.0) {
+            let (range_min_m, range_max_m) = timing_band_m(2000.0, 1e-6, band.order, prf_hz);
+            assert!(band.range_min_m >= range_min_m && band.range_max_m <= range_max_m);
+        }
+        // Transmitted pulses crossing 4.5 - 8.1 km from 50 to 100 kHz: 6 km at
+        // 50 kHz (order 1) up to 8.1 km at 100 kHz (order 2)
+        assert_eq!(timing_band_orders(0.0, 1e-6, 0.5 * prf_hz, prf_hz, 4500.0, 8100.0), Some(1..=2));
+        assert_eq!(timing_band_orders(9000.0, 1e-6, 0.5 * prf_hz, prf_hz, 4500.0, 8100.0), None);
+        assert_eq!(timing_band_orders(0.0, 1e-6, 0.0, prf_hz, 4500.0, 8100.0), None);
+    }
+
+    #[test]
+    fn bistatic_specular_point() {
+        let tx_position_m = DVec3::new(0.0, 0.0, 3000.0);
+        let rx_position_m = DVec3::new(4000.0, 0.0, 1000.0);
+        let specular_point_m = specular_point_m(&tx_position_m, &rx_position_m).unwrap();
+        assert_eq!(specular_point_m, DVec3::new(3000.0, 0.0, 0.0));
+        // Minimum of the bistatic range on the ground
+        let range_m = bistatic_range_m(&tx_position_m, &rx_position_m, &specular_point_m);
+        for offset_m in [DVec3::X, DVec3::NEG_X, DVec3::Y] {
+            assert!(bistatic_range_m(&tx_position_m, &rx_position_m, &(specular_point_m + offset_m)) > range_m);
+        }
+    }
+}
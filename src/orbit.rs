@@ -0,0 +1,453 @@
+//! Orbital transmitters: Keplerian orbit propagation and scene access windows.
+//!
+//! Orbits are propagated analytically from mean Keplerian elements with the J2
+//! secular drifts of the node, the perigee and the mean anomaly, which is
+//! accurate enough (a few km per day) for access and revisit planning. Orbits
+//! can be defined from their elements or read from a Two-Line Element set
+//! (TLE), whose mean elements are used as-is (no SGP4 perturbations), and
+//! constellations from Walker delta parameters or a list of TLEs.
+
+use bevy::math::{DQuat, DVec3};
+
+use crate::{
+    coordinates::{CartesianECEFPoint, Ellipsoid, GeographicPoint, LocalCartesian},
+    epoch::{days_from_civil, UtcTime},
+    visibility::time_windows,
+};
+
+/// Earth gravitational parameter `μ` \[m<sup>3</sup>.s<sup>-2</sup>\] (WGS84).
+pub const EARTH_MU: f64 = 3.986004418e14;
+/// Earth second zonal harmonic `J2` (EGM96).
+pub const EARTH_J2: f64 = 1.08262668e-3;
+/// Earth equatorial radius \[m\] (WGS84).
+pub const EARTH_EQUATORIAL_RADIUS_M: f64 = 6378137.0;
+/// Earth rotation rate \[rad.s<sup>-1</sup>\] (WGS84).
+pub const EARTH_ROTATION_RATE_RADPS: f64 = 7.292115e-5;
+/// Time resolution in seconds of the minimum incidence time of access windows.
+const ACCESS_BOUND_RESOLUTION_S: f64 = 0.01;
+
+/// Mean Keplerian elements of an Earth orbit at its epoch.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Orbit {
+    pub semi_major_axis_m: f64,
+    pub eccentricity: f64,
+    pub inclination_deg: f64,
+    /// Right ascension of the ascending node
+    pub raan_deg: f64,
+    pub arg_perigee_deg: f64,
+    pub mean_anomaly_deg: f64,
+    /// Greenwich Mean Sidereal Time at epoch, i.e. the angle from the inertial
+    /// X axis (vernal equinox) to the Greenwich meridian
+    pub gmst_at_epoch_deg: f64,
+    /// UTC epoch, `None` for an orbit not attached to an absolute time
+    pub epoch: Option<UtcTime>,
+}
+
+impl Orbit {
+    /// Creates a circular orbit at `altitude_m` above the equatorial radius, the
+    /// satellite at `arg_latitude_deg` from the ascending node at epoch, and the
+    /// Greenwich meridian on the inertial X axis.
+    pub fn circular(altitude_m: f64, inclination_deg: f64, raan_deg: f64, arg_latitude_deg: f64) -> Self {
+        Self {
+            semi_major_axis_m: EARTH_EQUATORIAL_RADIUS_M + altitude_m,
+            eccentricity: 0.0,
+            inclination_deg,
+            raan_deg,
+            arg_perigee_deg: 0.0,
+            mean_anomaly_deg: arg_latitude_deg,
+            gmst_at_epoch_deg: 0.0,
+            epoch: None,
+        }
+    }
+
+    /// Attaches the orbit to the UTC `epoch`, the Greenwich meridian moving to
+    /// its sidereal time.
+    pub fn at_epoch(self, epoch: UtcTime) -> Self {
+        Self {
+            gmst_at_epoch_deg: epoch.gmst_deg(),
+            epoch: Some(epoch),
+            ..self
+        }
+    }
+
+    /// Reads the mean elements of a Two-Line Element set.
+    ///
+    /// Both lines are checked for their line number, length and checksum.
+    pub fn from_tle(line1: &str, line2: &str) -> Result<Self, String> {
+        let (line1, line2) = (line1.trim_end(), line2.trim_end());
+        check_tle_line(line1, '1')?;
+        check_tle_line(line2, '2')?;
+        let field = |line: &str, range: std::ops::Range<usize>, name: &str| -> Result<f64, String> {
+            line[range].trim().parse::<f64>().map_err(|_| format!("invalid TLE {name}"))
+        };
+        let epoch_year = field(line1, 18..20, "epoch year")? as i64;
+        let epoch_day = field(line1, 20..32, "epoch day")?;
+        let mean_motion_revpd = field(line2, 52..63, "mean motion")?;
+        if mean_motion_revpd <= 0.0 {
+            return Err("invalid TLE mean motion".to_string());
+        }
+        let mean_motion_radps = mean_motion_revpd * std::f64::consts::TAU / 86400.0;
+        // Two-digit years: 57 - 99 => 1957 - 1999, 00 - 56 => 2000 - 2056
+        let year = if epoch_year >= 57 { 1900 + epoch_year } else { 2000 + epoch_year };
+        let epoch = UtcTime::from_unix_s((days_from_civil(year, 1, 1) as f64 + epoch_day - 1.0) * 86400.0);
+        Ok(Self {
+            semi_major_axis_m: (EARTH_MU / (mean_motion_radps * mean_motion_radps)).cbrt(),
+            eccentricity: field(line2, 26..33, "eccentricity")? * 1e-7, // Implied leading decimal point
+            inclination_deg: field(line2, 8..16, "inclination")?,
+            raan_deg: field(line2, 17..25, "right ascension of the ascending node")?,
+            arg_perigee_deg: field(line2, 34..42, "argument of perigee")?,
+            mean_anomaly_deg: field(line2, 43..51, "mean anomaly")?,
+            gmst_at_epoch_deg: epoch.gmst_deg(),
+            epoch: Some(epoch),
+        })
+    }
+
+    /// Gets the mean motion in radians per second.
+    #[inline]
+    pub fn mean_motion_radps(&self) -> f64 {
+        (EARTH_MU / self.semi_major_axis_m.powi(3)).sqrt()
+    }
+
+    /// Gets the (Keplerian) orbital period in seconds.
+    #[inline]
+    pub fn period_s(&self) -> f64 {
+        std::f64::consts::TAU / self.mean_motion_radps()
+    }
+
+    /// Gets the J2 secular rates in radians per second of the right ascension of
+    /// the ascending node, of the argument of perigee and of the mean anomaly.
+    pub fn secular_rates_radps(&self) -> (f64, f64, f64) {
+        let n = self.mean_motion_radps();
+        let e2 = self.eccentricity * self.eccentricity;
+        let p = self.semi_major_axis_m * (1.0 - e2);
+        let k = 0.75 * n * EARTH_J2 * (EARTH_EQUATORIAL_RADIUS_M / p).powi(2);
+        let cos_i = self.inclination_deg.to_radians().cos();
+        (
+            -2.0 * k * cos_i,
+            k * (5.0 * cos_i * cos_i - 1.0),
+            n + k * (1.0 - e2).sqrt() * (3.0 * cos_i * cos_i - 1.0),
+        )
+    }
+
+    /// Gets the position and velocity in the inertial frame at `time_s` seconds
+    /// after the epoch.
+    pub fn state_eci_at(&self, time_s: f64) -> (DVec3, DVec3) {
+        let (raan_rate, arg_perigee_rate, mean_anomaly_rate) = self.secular_rates_radps();
+        let raan = self.raan_deg.to_radians() + raan_rate * time_s;
+        let arg_perigee = self.arg_perigee_deg.to_radians() + arg_perigee_rate * time_s;
+        let mean_anomaly = (self.mean_anomaly_deg.to_radians() + mean_anomaly_rate * time_s)
+            .rem_euclid(std::f64::consts::TAU);
+        // Kepler's equation M = E - e.sin(E), by Newton iterations
+        let e = self.eccentricity;
+        let mut eccentric_anomaly = if e < 0.8 { mean_anomaly } else { std::f64::consts::PI };
+        for _ in 0..30 {
+            let delta = (eccentric_anomaly - e * eccentric_anomaly.sin() - mean_anomaly)
+                / (1.0 - e * eccentric_anomaly.cos());
+            eccentric_anomaly -= delta;
+            if delta.abs() < 1e-14 {
+                break;
+            }
+        }
+        let (sin_e, cos_e) = eccentric_anomaly.sin_cos();
+        let a = self.semi_major_axis_m;
+        let b = a * (1.0 - e * e).sqrt();
+        // Perifocal frame: X towards the perigee, Z along the angular momentum
+        let position = DVec3::new(a * (cos_e - e), b * sin_e, 0.0);
+        let eccentric_anomaly_rate = mean_anomaly_rate / (1.0 - e * cos_e);
+        let velocity = DVec3::new(-a * sin_e, b * cos_e, 0.0) * eccentric_anomaly_rate;
+        let rotation = DQuat::from_rotation_z(raan)
+            * DQuat::from_rotation_x(self.inclination_deg.to_radians())
+            * DQuat::from_rotation_z(arg_perigee);
+        (rotation * position, rotation * velocity)
+    }
+
+    /// Gets the position and velocity in the Earth-fixed (ECEF) frame at `time_s`
+    /// seconds after the epoch.
+    pub fn state_ecef_at(&self, time_s: f64) -> (CartesianECEFPoint, DVec3) {
+        let (position, velocity) = self.state_eci_at(time_s);
+        let gmst = self.gmst_at_epoch_deg.to_radians() + EARTH_ROTATION_RATE_RADPS * time_s;
+        let to_ecef = DQuat::from_rotation_z(-gmst);
+        let position_ecef = to_ecef * position;
+        let earth_rotation = DVec3::new(0.0, 0.0, EARTH_ROTATION_RATE_RADPS);
+        (position_ecef, to_ecef * velocity - earth_rotation.cross(position_ecef))
+    }
+
+    /// Gets the position in the ENU coordinates of `scene` at `time_s` seconds
+    /// after the epoch.
+    #[inline]
+    pub fn position_enu_at(&self, scene: &LocalCartesian, time_s: f64) -> DVec3 {
+        scene.transform_from_cartesian_ecef_point_to_enu_point(&self.state_ecef_at(time_s).0)
+    }
+
+    /// Gets the ground track (sub-satellite points on the WGS84 Ellipsoid) over
+    /// `[start_s, start_s + duration_s]` sampled every `step_s` seconds.
+    pub fn ground_track(&self, start_s: f64, duration_s: f64, step_s: f64) -> Vec<GeographicPoint> {
+        let num_steps = (duration_s / step_s.max(1e-3)).ceil().max(0.0) as usize;
+        (0..=num_steps)
+            .map(|i| {
+                let time_s = (start_s + i as f64 * step_s).min(start_s + duration_s);
+                let gp = Ellipsoid::WGS84.to_geographic_point(&self.state_ecef_at(time_s).0);
+                GeographicPoint::from_degrees(gp.lon_deg(), gp.lat_deg(), 0.0)
+            })
+            .collect()
+    }
+
+    /// Gets the incidence angle in degrees at the origin of `scene` (from its
+    /// local vertical), `None` when the satellite is below the horizon.
+    pub fn incidence_deg_at(&self, scene: &LocalCartesian, time_s: f64) -> Option<f64> {
+        let (position_ecef, _) = self.state_ecef_at(time_s);
+        let position_enu = scene.transform_from_cartesian_ecef_point_to_enu_point(&position_ecef);
+        (position_enu.z > 0.0).then(|| (position_enu.z / position_enu.length()).acos().to_degrees())
+    }
+
+    /// Computes the windows during which the origin of `scene` is seen from the
+    /// orbit with an incidence angle within `incidence_range_deg`, over
+    /// `[start_s, start_s + duration_s]` sampled every `step_s` seconds.
+    ///
+    /// The windows bounds are refined by bisection; windows shorter than
+    /// `step_s` may be missed.
+    pub fn access_windows(
+        &self,
+        scene: &LocalCartesian,
+        start_s: f64,
+        duration_s: f64,
+        step_s: f64,
+        incidence_range_deg: (f64, f64),
+    ) -> Vec<AccessWindow> {
+        let is_accessible = |time_s: f64| self.incidence_deg_at(scene, time_s)
+            .is_some_and(|incidence_deg| (incidence_range_deg.0..=incidence_range_deg.1).contains(&incidence_deg));
+        time_windows(is_accessible, start_s, duration_s, step_s)
+            .into_iter()
+            .map(|window| self.access_window(scene, window.start_s, window.end_s))
+            .collect()
+    }
+
+    /// Builds the access window over `[start_s, end_s]` with its minimum incidence.
+    fn access_window(&self, scene: &LocalCartesian, start_s: f64, end_s: f64) -> AccessWindow {
+        // The incidence is unimodal over a pass: golden-section search of its minimum
+        let incidence = |time_s: f64| self.incidence_deg_at(scene, time_s).unwrap_or(90.0);
+        let inverse_golden_ratio = 0.5 * (5f64.sqrt() - 1.0);
+        let (mut a, mut b) = (start_s, end_s);
+        while b - a > ACCESS_BOUND_RESOLUTION_S {
+            let c = b - inverse_golden_ratio * (b - a);
+            let d = a + inverse_golden_ratio * (b - a);
+            if incidence(c) < incidence(d) { b = d; } else { a = c; }
+        }
+        let min_incidence_time_s = 0.5 * (a + b);
+        AccessWindow {
+            start_s,
+            end_s,
+            min_incidence_time_s,
+            min_incidence_deg: incidence(min_incidence_time_s),
+        }
+    }
+}
+
+/// A time interval during which a point is accessible from an orbit.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccessWindow {
+    /// Start time in seconds after the orbit epoch
+    pub start_s: f64,
+    /// End time in seconds after the orbit epoch
+    pub end_s: f64,
+    /// Time of the minimum incidence angle in seconds after the orbit epoch
+    pub min_incidence_time_s: f64,
+    pub min_incidence_deg: f64,
+}
+
+impl AccessWindow {
+    /// Gets the window duration in seconds.
+    #[inline]
+    pub fn duration_s(&self) -> f64 {
+        self.end_s - self.start_s
+    }
+}
+
+/// Builds a Walker delta constellation `inclination: total / planes / phasing`
+/// of circular orbits at `altitude_m`: `planes` planes evenly spread over 360°
+/// of node, `total / planes` satellites evenly spaced in each plane, and the
+/// satellites of adjacent planes shifted by `phasing × 360° / total`.
+pub fn walker_delta(
+    altitude_m: f64,
+    inclination_deg: f64,
+    total: usize,
+    planes: usize,
+    phasing: usize,
+) -> Result<Vec<Orbit>, String> {
+    if total == 0 || planes == 0 || !total.is_multiple_of(planes) {
+        return Err(format!("{total} satellites cannot be evenly spread over {planes} planes"));
+    }
+    if phasing >= planes {
+        return Err(format!("phasing {phasing} must be lower than the number of planes {planes}"));
+    }
+    let per_plane = total / planes;
+    Ok((0..planes)
+        .flat_map(|plane| (0..per_plane).map(move |slot| (plane, slot)))
+        .map(|(plane, slot)| Orbit::circular(
+            altitude_m,
+            inclination_deg,
+            360.0 * plane as f64 / planes as f64,
+            (360.0 * slot as f64 / per_plane as f64 + 360.0 * (phasing * plane) as f64 / total as f64)
+                .rem_euclid(360.0)
+        ))
+        .collect())
+}
+
+/// Reads a list of TLEs, each with an optional name line before its two lines
+/// (the "3LE" format); unnamed ones are named after their catalog number.
+pub fn parse_tle_set(text: &str) -> Result<Vec<(String, Orbit)>, String> {
+    let lines: Vec<&str> = text.lines().map(str::trim_end).filter(|line| !line.trim().is_empty()).collect();
+    let mut orbits = Vec::new();
+    let mut i = 0;
+    while i < lines.len() {
+        let name = if lines[i].starts_with("1 ") { None } else {
+            i += 1;
+            Some(lines[i - 1].trim().trim_start_matches("0 ").to_string())
+        };
+        let (Some(line1), Some(line2)) = (lines.get(i), lines.get(i + 1)) else {
+            return Err(format!("incomplete TLE at line {}", i + 1));
+        };
+        let orbit = Orbit::from_tle(line1, line2).map_err(|error| format!("{error} (line {})", i + 1))?;
+        let name = name.unwrap_or_else(|| format!("NORAD {}", line1[2..7].trim()));
+        orbits.push((name, orbit));
+        i += 2;
+    }
+    Ok(orbits)
+}
+
+/// Gets the revisit times in seconds, i.e. the delays between the starts of
+/// successive access windows.
+pub fn revisit_times_s(windows: &[AccessWindow]) -> Vec<f64> {
+    windows.windows(2).map(|pair| pair[1].start_s - pair[0].start_s).collect()
+}
+
+/// Checks a TLE line number, length and modulo-10 checksum.
+fn check_tle_line(line: &str, number: char) -> Result<(), String> {
+    if !line.is_ascii() || line.len() < 69 || !line.starts_with(number) {
+        return Err(format!("TLE line {number} must be 69 ASCII characters starting with '{number}'"));
+    }
+    let checksum: u32 = line[..68].chars()
+        .map(|c| match c {
+            '-' => 1,
+            _ => c.to_digit(10).unwrap_or(0),
+        })
+        .sum();
+    if line[68..69].parse::<u32>() != Ok(checksum % 10) {
+        return Err(format!("TLE line {number} checksum mismatch"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coordinates::GeographicPoint;
+
+    fn assert_close(value: f64, expected: f64, abs_tol: f64) {
+        assert!(
+            (value - expected).abs() <= abs_tol,
+            "value = {value}, expected = {expected}"
+        );
+    }
+
+    // ISS (ZARYA), CelesTrak format
+    const ISS_TLE: [&str; 2] = [
+        "1 25544U 98067A   08264.51782528 -.00002182  00000-0 -11606-4 0  2927",
+        "2 25544  51.6416 247.4627 0006703 130.5360 325.0288 15.72125391563537",
+    ];
+
+    #[test]
+    fn reads_tle_mean_elements() {
+        let orbit = Orbit::from_tle(ISS_TLE[0], ISS_TLE[1]).unwrap();
+        assert_close(orbit.inclination_deg, 51.6416, 1e-12);
+        assert_close(orbit.raan_deg, 247.4627, 1e-12);
+        assert_close(orbit.eccentricity, 0.0006703, 1e-12);
+        assert_close(orbit.period_s(), 86400.0 / 15.72125391, 1e-6);
+        assert_close(orbit.semi_major_axis_m - EARTH_EQUATORIAL_RADIUS_M, 352e3, 5e3);
+        // GMST at 2008-09-20 12:25:40 UT: ~0h at 0h UT near the September
+        // equinox, plus 12.43 h of sidereal rotation
+        assert_close(orbit.gmst_at_epoch_deg, 186.182, 1e-3);
+        assert_eq!(orbit.epoch.unwrap().to_string(), "2008-09-20T12:25:40.104Z");
+
+        let mut corrupted = ISS_TLE[1].to_string();
+        corrupted.replace_range(9..10, "2"); // Breaks the checksum
+        assert!(Orbit::from_tle(ISS_TLE[0], &corrupted).is_err());
+        assert!(Orbit::from_tle(ISS_TLE[1], ISS_TLE[0]).is_err());
+    }
+
+    #[test]
+    fn circular_orbit_state() {
+        let orbit = Orbit::circular(700e3, 98.0, 30.0, 0.0);
+        let (position, velocity) = orbit.state_eci_at(0.0);
+        // At the ascending node, on the line of nodes
+        assert_close(position.length(), EARTH_EQUATORIAL_RADIUS_M + 700e3, 1e-6);
+        assert_close(position.z, 0.0, 1e-6);
+        assert_close(position.y.atan2(position.x).to_degrees(), 30.0, 1e-9);
+        // Circular speed, up to the J2 correction of the mean motion
+        assert_close(velocity.length(), (EARTH_MU / orbit.semi_major_axis_m).sqrt(), 10.0);
+        assert!(velocity.z > 0.0);
+        // Sun-synchronous-like drift: the node moves eastwards
+        assert!(orbit.secular_rates_radps().0 > 0.0);
+        // The Earth-fixed velocity includes the Earth rotation
+        let (position_ecef, velocity_ecef) = orbit.state_ecef_at(0.0);
+        assert_close(position_ecef.length(), position.length(), 1e-6);
+        assert!((velocity_ecef.length() - velocity.length()).abs() > 1.0);
+    }
+
+    #[test]
+    fn access_windows_and_revisit() {
+        // Polar orbit over a scene on the equator at the ascending node longitude
+        let orbit = Orbit::circular(700e3, 90.0, 0.0, -10.0);
+        let scene = LocalCartesian::from_geographic_point(&GeographicPoint::from_degrees(0.0, 0.0, 0.0));
+        let windows = orbit.access_windows(&scene, 0.0, 86400.0, 10.0, (0.0, 45.0));
+        assert!(!windows.is_empty());
+        let first = &windows[0];
+        // Nearly overhead ~10° of orbit later, minus the Earth rotation offset
+        assert!(first.start_s > 0.0 && first.end_s < orbit.period_s() / 4.0);
+        assert!(first.min_incidence_deg < 10.0);
+        assert!(first.min_incidence_time_s > first.start_s && first.min_incidence_time_s < first.end_s);
+        for window in windows.iter() {
+            assert!(window.duration_s() > 0.0);
+            assert!(orbit.incidence_deg_at(&scene, 0.5 * (window.start_s + window.end_s)).unwrap() <= 45.0);
+        }
+        let revisits = revisit_times_s(&windows);
+        assert_eq!(revisits.len(), windows.len() - 1);
+        assert!(revisits.iter().all(|&revisit_s| revisit_s > 0.0));
+    }
+
+    #[test]
+    fn walker_constellation() {
+        // Galileo-like 56°: 24/3/1
+        let orbits = walker_delta(23_222e3, 56.0, 24, 3, 1).unwrap();
+        assert_eq!(orbits.len(), 24);
+        assert_close(orbits[8].raan_deg, 120.0, 1e-12);
+        assert_close(orbits[1].mean_anomaly_deg, 45.0, 1e-12);
+        // First satellite of the second plane shifted by 360° / 24
+        assert_close(orbits[8].mean_anomaly_deg, 15.0, 1e-12);
+        assert!(walker_delta(700e3, 98.0, 10, 3, 0).is_err());
+        assert!(walker_delta(700e3, 98.0, 12, 3, 3).is_err());
+    }
+
+    #[test]
+    fn tle_sets_and_ground_tracks() {
+        let text = format!("ISS (ZARYA)\n{}\n{}\n\n{}\n{}\n", ISS_TLE[0], ISS_TLE[1], ISS_TLE[0], ISS_TLE[1]);
+        let orbits = parse_tle_set(&text).unwrap();
+        assert_eq!(orbits.len(), 2);
+        assert_eq!(orbits[0].0, "ISS (ZARYA)");
+        assert_eq!(orbits[1].0, "NORAD 25544");
+        assert!(parse_tle_set(&format!("{}\n", ISS_TLE[0])).is_err());
+
+        let orbit = &orbits[0].1;
+        let track = orbit.ground_track(0.0, orbit.period_s(), 60.0);
+        assert_eq!(track.len(), (orbit.period_s() / 60.0).ceil() as usize + 1);
+        // The sub-satellite latitude never exceeds the inclination (geodetic ≈ geocentric + 0.2°)
+        assert!(track.iter().all(|gp| gp.lat_deg().abs() <= orbit.inclination_deg + 0.3));
+    }
+
+    #[test]
+    fn civil_days() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2000, 1, 1), 10957);
+        assert_eq!(days_from_civil(2000, 3, 1) - days_from_civil(2000, 2, 28), 2); // Leap year
+    }
+}
@@ -0,0 +1,115 @@
+//! Color palettes of the scene.
+//!
+//! The standard palette tells the contour families and the axes apart by red,
+//! green and blue, which red-green colorblind users confuse. The colorblind
+//! safe palette takes its colors from Okabe & Ito ("Color Universal Design",
+//! 2008), distinguishable under the common color vision deficiencies; the high
+//! contrast palette draws bright lines over a dark ground for projectors.
+
+/// A color (R, G, B) in sRGB.
+pub type Rgb = (u8, u8, u8);
+
+/// Palette of the contours, footprints and axes helpers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ColorPalette {
+    #[default]
+    Standard,
+    ColorblindSafe,
+    HighContrast,
+}
+
+/// Colors of a palette.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PaletteColors {
+    /// Ground of the iso-range/Doppler plane
+    pub ground_rgb: Rgb,
+    pub iso_range_rgb: Rgb,
+    pub iso_doppler_rgb: Rgb,
+    /// Footprints of the top view and of the drawings
+    pub tx_footprint_rgb: Rgb,
+    pub rx_footprint_rgb: Rgb,
+    /// Elevation and azimuth lines across the footprints
+    pub elevation_line_rgb: Rgb,
+    pub azimuth_line_rgb: Rgb,
+    /// X, Y and Z axes of the axes helpers, and their origin
+    pub axes_rgb: [Rgb; 3],
+    pub origin_rgb: Rgb,
+}
+
+impl ColorPalette {
+    pub const ALL: [Self; 3] = [Self::Standard, Self::ColorblindSafe, Self::HighContrast];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Standard => "Standard",
+            Self::ColorblindSafe => "Colorblind safe",
+            Self::HighContrast => "High contrast",
+        }
+    }
+
+    /// Gets the value of the palette in the preferences file.
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::Standard => "standard",
+            Self::ColorblindSafe => "colorblind_safe",
+            Self::HighContrast => "high_contrast",
+        }
+    }
+
+    pub fn colors(self) -> PaletteColors {
+        match self {
+            Self::Standard => PaletteColors {
+                ground_rgb: (128, 128, 128),
+                iso_range_rgb: (214, 39, 40),
+                iso_doppler_rgb: (31, 119, 180),
+                tx_footprint_rgb: (255, 127, 14),
+                rx_footprint_rgb: (44, 160, 44),
+                elevation_line_rgb: (0, 0, 255),
+                azimuth_line_rgb: (0, 255, 0),
+                axes_rgb: [(255, 0, 0), (0, 255, 0), (0, 0, 255)],
+                origin_rgb: (255, 255, 0),
+            },
+            // Okabe-Ito: vermilion, blue, orange, sky blue, bluish green, yellow
+            Self::ColorblindSafe => PaletteColors {
+                ground_rgb: (128, 128, 128),
+                iso_range_rgb: (213, 94, 0),
+                iso_doppler_rgb: (0, 114, 178),
+                tx_footprint_rgb: (230, 159, 0),
+                rx_footprint_rgb: (86, 180, 233),
+                elevation_line_rgb: (0, 114, 178),
+                azimuth_line_rgb: (230, 159, 0),
+                axes_rgb: [(213, 94, 0), (0, 158, 115), (86, 180, 233)],
+                origin_rgb: (240, 228, 66),
+            },
+            Self::HighContrast => PaletteColors {
+                ground_rgb: (32, 32, 32),
+                iso_range_rgb: (255, 255, 0),
+                iso_doppler_rgb: (0, 255, 255),
+                tx_footprint_rgb: (255, 0, 255),
+                rx_footprint_rgb: (0, 255, 0),
+                elevation_line_rgb: (0, 255, 255),
+                azimuth_line_rgb: (255, 255, 0),
+                axes_rgb: [(255, 80, 80), (80, 255, 80), (80, 160, 255)],
+                origin_rgb: (255, 255, 255),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn palette_colors_are_distinct() {
+        for palette in ColorPalette::ALL {
+            let colors = palette.colors();
+            let lines = [colors.ground_rgb, colors.iso_range_rgb, colors.iso_doppler_rgb];
+            assert!(lines.iter().enumerate().all(|(i, a)| lines[i + 1..].iter().all(|b| a != b)), "{palette:?}");
+            assert_ne!(colors.tx_footprint_rgb, colors.rx_footprint_rgb);
+            assert_ne!(colors.elevation_line_rgb, colors.azimuth_line_rgb);
+            let [x, y, z] = colors.axes_rgb;
+            assert!(x != y && y != z && z != x && !colors.axes_rgb.contains(&colors.origin_rgb));
+        }
+    }
+}
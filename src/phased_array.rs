@@ -0,0 +1,138 @@
+//! Planar phased-array antenna model.
+//!
+//! A rectangular array of elements, uniformly illuminated, with its normal
+//! along the antenna boresight: its half-power beamwidths and gain follow
+//! from the aperture in wavelengths, and degrade when the beam is steered
+//! electronically away from the normal (beam broadening and scan loss). Widely
+//! spaced elements let grating lobes enter the visible space beyond a scan
+//! angle.
+
+use crate::{constants::MAX_BEAM_WIDTH_DEG, entities::AntennaBeamState};
+
+/// Half-power beamwidth in radians of a uniformly illuminated aperture one
+/// wavelength long (λ/L law)
+const HALF_POWER_BEAMWIDTH_FACTOR: f64 = 0.886;
+/// Scan loss exponent of cos(scan angle): projected aperture (1) times the
+/// element pattern (0.5)
+const SCAN_LOSS_COS_EXPONENT: f64 = 1.5;
+
+/// Elements of the array along one of its axes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ArrayAxis {
+    pub element_count: usize,
+    /// Distance between two consecutive elements, in wavelengths
+    pub spacing_wavelengths: f64,
+}
+
+impl ArrayAxis {
+    /// Gets the aperture length in wavelengths.
+    #[inline]
+    pub fn aperture_wavelengths(&self) -> f64 {
+        self.element_count as f64 * self.spacing_wavelengths
+    }
+
+    /// Gets the half-power beamwidth in degrees when steered `scan_deg` away
+    /// from the array normal along this axis.
+    pub fn beam_width_deg(&self, scan_deg: f64) -> f64 {
+        let projected_aperture = self.aperture_wavelengths() * scan_deg.to_radians().cos();
+        if projected_aperture > 0.0 {
+            (HALF_POWER_BEAMWIDTH_FACTOR / projected_aperture).to_degrees().min(MAX_BEAM_WIDTH_DEG)
+        } else {
+            MAX_BEAM_WIDTH_DEG
+        }
+    }
+
+    /// Gets the scan angle in degrees beyond which a grating lobe enters the
+    /// visible space (sin θ = λ/d - 1), `None` when the array never has one.
+    pub fn grating_lobe_onset_deg(&self) -> Option<f64> {
+        if self.element_count < 2 || self.spacing_wavelengths <= 0.5 {
+            return None;
+        }
+        Some((1.0 / self.spacing_wavelengths - 1.0).max(0.0).asin().to_degrees())
+    }
+}
+
+/// Rectangular planar array, its elevation axis along the elevation beamwidth.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PhasedArray {
+    pub elevation: ArrayAxis,
+    pub azimuth: ArrayAxis,
+}
+
+impl Default for PhasedArray {
+    fn default() -> Self {
+        Self {
+            elevation: ArrayAxis { element_count: 16, spacing_wavelengths: 0.5 },
+            azimuth: ArrayAxis { element_count: 64, spacing_wavelengths: 0.5 },
+        }
+    }
+}
+
+impl PhasedArray {
+    /// Gets the gain in dBi at the array normal: the directivity 4π.A/λ² of the
+    /// uniformly illuminated aperture.
+    pub fn broadside_gain_dbi(&self) -> f64 {
+        10.0 * (4.0 * std::f64::consts::PI * self.elevation.aperture_wavelengths() * self.azimuth.aperture_wavelengths()).log10()
+    }
+
+    /// Gets the scan loss in dB (positive) of a beam steered by offsets in
+    /// elevation and azimuth (degrees) from the array normal.
+    pub fn scan_loss_db(&self, elevation_scan_deg: f64, azimuth_scan_deg: f64) -> f64 {
+        // Angle between the steered beam and the array normal
+        let cos_scan = elevation_scan_deg.to_radians().cos() * azimuth_scan_deg.to_radians().cos();
+        -10.0 * SCAN_LOSS_COS_EXPONENT * cos_scan.max(f64::MIN_POSITIVE).log10()
+    }
+
+    /// Checks that no grating lobe is visible at the given scan offsets in degrees.
+    pub fn is_grating_lobe_free(&self, elevation_scan_deg: f64, azimuth_scan_deg: f64) -> bool {
+        let is_free = |axis: &ArrayAxis, scan_deg: f64| {
+            axis.grating_lobe_onset_deg().is_none_or(|onset_deg| scan_deg.abs() <= onset_deg)
+        };
+        is_free(&self.elevation, elevation_scan_deg) && is_free(&self.azimuth, azimuth_scan_deg)
+    }
+
+    /// Gets the antenna beam of the array steered by offsets in elevation and
+    /// azimuth (degrees) from its normal.
+    pub fn beam_state(&self, elevation_scan_deg: f64, azimuth_scan_deg: f64) -> AntennaBeamState {
+        AntennaBeamState {
+            elevation_beam_width_deg: self.elevation.beam_width_deg(elevation_scan_deg),
+            azimuth_beam_width_deg: self.azimuth.beam_width_deg(azimuth_scan_deg),
+            one_way_gain_dbi: self.broadside_gain_dbi() - self.scan_loss_db(elevation_scan_deg, azimuth_scan_deg),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64, tolerance: f64) {
+        assert!((actual - expected).abs() <= tolerance, "{actual} != {expected}");
+    }
+
+    #[test]
+    fn beams_degrade_with_the_scan_angle() {
+        // 8 λ × 32 λ aperture
+        let array = PhasedArray {
+            elevation: ArrayAxis { element_count: 16, spacing_wavelengths: 0.5 },
+            azimuth: ArrayAxis { element_count: 32, spacing_wavelengths: 1.0 },
+        };
+        let broadside = array.beam_state(0.0, 0.0);
+        assert_close(broadside.elevation_beam_width_deg, (0.886f64 / 8.0).to_degrees(), 1e-12);
+        assert_close(broadside.azimuth_beam_width_deg, (0.886f64 / 32.0).to_degrees(), 1e-12);
+        assert_close(broadside.one_way_gain_dbi, 10.0 * (4.0 * std::f64::consts::PI * 256.0).log10(), 1e-12);
+        // 60° in elevation: twice the beamwidth, cos^1.5 scan loss (4.5 dB)
+        let steered = array.beam_state(60.0, 0.0);
+        assert_close(steered.elevation_beam_width_deg, 2.0 * broadside.elevation_beam_width_deg, 1e-9);
+        assert_close(steered.azimuth_beam_width_deg, broadside.azimuth_beam_width_deg, 1e-12);
+        assert_close(broadside.one_way_gain_dbi - steered.one_way_gain_dbi, 15.0 * 2f64.log10(), 1e-9);
+        assert_eq!(array.elevation.beam_width_deg(90.0), MAX_BEAM_WIDTH_DEG);
+        // Grating lobes: never at λ/2, at broadside already at λ, from 25.4° at 0.7 λ
+        assert_eq!(array.elevation.grating_lobe_onset_deg(), None);
+        assert_eq!(array.azimuth.grating_lobe_onset_deg(), Some(0.0));
+        let axis = ArrayAxis { element_count: 8, spacing_wavelengths: 0.7 };
+        assert_close(axis.grating_lobe_onset_deg().unwrap(), (3.0f64 / 7.0).asin().to_degrees(), 1e-12);
+        assert!(array.is_grating_lobe_free(60.0, 0.0));
+        assert!(!array.is_grating_lobe_free(0.0, 1.0));
+    }
+}
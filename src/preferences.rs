@@ -0,0 +1,246 @@
+//! User preferences persisted across sessions.
+//!
+//! Preferences are stored as `key = value` lines, in the platform
+//! configuration directory on native targets and in the browser local storage
+//! on the web, apart from the scenario files: they follow the user, not the
+//! geometry. Unknown keys and invalid values are skipped, so that a file
+//! written by another version keeps the valid preferences it holds. The
+//! parameter snippets are stored beside them (see [`crate::snippets`]).
+
+use std::ops::RangeInclusive;
+
+use crate::palette::ColorPalette;
+
+/// Application directory within the platform configuration directory
+#[cfg(not(target_arch = "wasm32"))]
+const APPLICATION_DIRECTORY: &str = "BSARGeom";
+/// File name, or local storage key on the web
+const PREFERENCES_FILE_NAME: &str = "preferences.txt";
+
+/// Theme of the interface. The colors of the windows being chosen for dark
+/// backgrounds, the themes are dark ones.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UiTheme {
+    #[default]
+    Dark,
+    /// Black panels and white text, for projectors and bright rooms
+    Black,
+}
+
+impl UiTheme {
+    pub const ALL: [Self; 2] = [Self::Dark, Self::Black];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Dark => "Dark",
+            Self::Black => "Black (high contrast)",
+        }
+    }
+
+    /// Gets the value of the theme in the preferences file.
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::Dark => "dark",
+            Self::Black => "black",
+        }
+    }
+}
+
+/// Preferences of the user, independent of the geometry.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Preferences {
+    /// egui zoom factor: logical pixels of the window per egui point
+    pub ui_scale: f32,
+    pub theme: UiTheme,
+    /// Palette of the scene at startup
+    pub palette: ColorPalette,
+    /// Overlays of the "Display" window shown at startup
+    pub show_resolution_directions: bool,
+    pub show_range_points: bool,
+    pub show_footprint_annotations: bool,
+    /// Sensitivities of the camera controls to the mouse and touch inputs
+    pub orbit_sensitivity: f32,
+    pub pan_sensitivity: f32,
+    pub zoom_sensitivity: f32,
+}
+
+impl Default for Preferences {
+    fn default() -> Self {
+        Self {
+            ui_scale: 1.0,
+            theme: UiTheme::default(),
+            palette: ColorPalette::default(),
+            show_resolution_directions: false,
+            show_range_points: false,
+            show_footprint_annotations: false,
+            orbit_sensitivity: 1.5,
+            pan_sensitivity: 1.0,
+            zoom_sensitivity: 1.0,
+        }
+    }
+}
+
+impl Preferences {
+    /// Accepted UI scales, from dense to projector-friendly.
+    pub const UI_SCALE_RANGE: RangeInclusive<f32> = 0.5..=3.0;
+    /// Accepted camera sensitivities, 0 disabling the control.
+    pub const SENSITIVITY_RANGE: RangeInclusive<f32> = 0.0..=5.0;
+
+    /// Reads the preferences from their text, the missing or invalid ones
+    /// keeping their default.
+    pub fn from_text(text: &str) -> Self {
+        let mut preferences = Self::default();
+        for (key, value) in text.lines().filter_map(|line| line.split_once('=')) {
+            let value = value.trim();
+            let number_in = |range: RangeInclusive<f32>| value.parse::<f32>().ok().filter(|number| range.contains(number));
+            let flag = value.parse::<bool>().ok();
+            match key.trim() {
+                "ui_scale" => preferences.ui_scale = number_in(Self::UI_SCALE_RANGE).unwrap_or(preferences.ui_scale),
+                "theme" => {
+                    if let Some(theme) = UiTheme::ALL.into_iter().find(|theme| theme.key() == value) {
+                        preferences.theme = theme;
+                    }
+                }
+                "palette" => {
+                    if let Some(palette) = ColorPalette::ALL.into_iter().find(|palette| palette.key() == value) {
+                        preferences.palette = palette;
+                    }
+                }
+                "show_resolution_directions" => preferences.show_resolution_directions = flag.unwrap_or(preferences.show_resolution_directions),
+                "show_range_points" => preferences.show_range_points = flag.unwrap_or(preferences.show_range_points),
+                "show_footprint_annotations" => preferences.show_footprint_annotations = flag.unwrap_or(preferences.show_footprint_annotations),
+                "orbit_sensitivity" => preferences.orbit_sensitivity = number_in(Self::SENSITIVITY_RANGE).unwrap_or(preferences.orbit_sensitivity),
+                "pan_sensitivity" => preferences.pan_sensitivity = number_in(Self::SENSITIVITY_RANGE).unwrap_or(preferences.pan_sensitivity),
+                "zoom_sensitivity" => preferences.zoom_sensitivity = number_in(Self::SENSITIVITY_RANGE).unwrap_or(preferences.zoom_sensitivity),
+                _ => {}
+            }
+        }
+        preferences
+    }
+
+    /// Gets the text of the preferences, read back by [`Self::from_text`].
+    pub fn to_text(&self) -> String {
+        [
+            format!("ui_scale = {}", self.ui_scale),
+            format!("theme = {}", self.theme.key()),
+            format!("palette = {}", self.palette.key()),
+            format!("show_resolution_directions = {}", self.show_resolution_directions),
+            format!("show_range_points = {}", self.show_range_points),
+            format!("show_footprint_annotations = {}", self.show_footprint_annotations),
+            format!("orbit_sensitivity = {}", self.orbit_sensitivity),
+            format!("pan_sensitivity = {}", self.pan_sensitivity),
+            format!("zoom_sensitivity = {}", self.zoom_sensitivity),
+        ]
+        .iter()
+        .map(|line| format!("{line}\n"))
+        .collect()
+    }
+
+    /// Gets the path of the preferences file.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn path() -> Option<std::path::PathBuf> {
+        config_file_path(PREFERENCES_FILE_NAME)
+    }
+
+    /// Loads the saved preferences, the defaults without file.
+    pub fn load() -> Self {
+        read_config_file(PREFERENCES_FILE_NAME).map_or_else(Self::default, |text| Self::from_text(&text))
+    }
+
+    /// Saves the preferences.
+    pub fn save(&self) -> Result<(), String> {
+        write_config_file(PREFERENCES_FILE_NAME, &self.to_text())
+    }
+}
+
+/// Gets the path of the `file_name` file of the application configuration
+/// directory: `%APPDATA%` on Windows, `~/Library/Application Support` on macOS
+/// and `$XDG_CONFIG_HOME` (defaulting to `~/.config`) elsewhere.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn config_file_path(file_name: &str) -> Option<std::path::PathBuf> {
+    use std::{env, path::PathBuf};
+
+    let home = || env::var_os("HOME").filter(|home| !home.is_empty()).map(PathBuf::from);
+    let config_directory = if cfg!(target_os = "windows") {
+        env::var_os("APPDATA").filter(|appdata| !appdata.is_empty()).map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        home().map(|home| home.join("Library").join("Application Support"))
+    } else {
+        env::var_os("XDG_CONFIG_HOME")
+            .filter(|config_home| !config_home.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| home().map(|home| home.join(".config")))
+    };
+    config_directory.map(|directory| directory.join(APPLICATION_DIRECTORY).join(file_name))
+}
+
+/// Reads the `file_name` file of the configuration directory, `None` without
+/// it.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn read_config_file(file_name: &str) -> Option<String> {
+    config_file_path(file_name).and_then(|path| std::fs::read_to_string(path).ok())
+}
+
+/// Writes the `file_name` file of the configuration directory, creating it.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn write_config_file(file_name: &str, text: &str) -> Result<(), String> {
+    let path = config_file_path(file_name).ok_or("No configuration directory")?;
+    if let Some(directory) = path.parent() {
+        std::fs::create_dir_all(directory).map_err(|error| format!("{}: {error}", directory.display()))?;
+    }
+    std::fs::write(&path, text).map_err(|error| format!("{}: {error}", path.display()))
+}
+
+/// Reads the `file_name` entry of the browser local storage, `None` without
+/// it.
+#[cfg(target_arch = "wasm32")]
+pub fn read_config_file(file_name: &str) -> Option<String> {
+    local_storage()?.get_item(file_name).ok().flatten()
+}
+
+/// Writes the `file_name` entry of the browser local storage.
+#[cfg(target_arch = "wasm32")]
+pub fn write_config_file(file_name: &str, text: &str) -> Result<(), String> {
+    local_storage()
+        .ok_or("No local storage")?
+        .set_item(file_name, text)
+        .map_err(|_| "Local storage is full or disabled".to_string())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok().flatten()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn preferences_text_roundtrip() {
+        let preferences = Preferences { ui_scale: 1.5, ..Preferences::default() };
+        assert_eq!(Preferences::from_text(&preferences.to_text()), preferences);
+        // Unknown keys, comments and invalid values are skipped
+        let text = "# Saved preferences\ntheme = dark\nui_scale = 12\n";
+        assert_eq!(Preferences::from_text(text), Preferences::default());
+        assert_eq!(Preferences::from_text(" ui_scale=0.75 ").ui_scale, 0.75);
+    }
+
+    #[test]
+    fn all_preferences_roundtrip() {
+        let preferences = Preferences {
+            ui_scale: 2.0,
+            theme: UiTheme::Black,
+            palette: ColorPalette::ColorblindSafe,
+            show_resolution_directions: true,
+            show_range_points: false,
+            show_footprint_annotations: true,
+            orbit_sensitivity: 0.5,
+            pan_sensitivity: 0.0,
+            zoom_sensitivity: 2.5,
+        };
+        assert_eq!(Preferences::from_text(&preferences.to_text()), preferences);
+        let text = "palette = rainbow\nshow_range_points = yes\nzoom_sensitivity = -1\n";
+        assert_eq!(Preferences::from_text(text), Preferences::default());
+    }
+}
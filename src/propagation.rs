@@ -0,0 +1,268 @@
+//! Atmospheric propagation delays along the slant paths.
+//!
+//! Approximate models, enough to size the range bias of a bistatic pair:
+//!
+//! - troposphere: Saastamoinen zenith hydrostatic and wet delays from the
+//!   surface conditions, scaled down by exponential profiles for carriers
+//!   flying inside the troposphere, and mapped to the path elevation with the
+//!   Chao mapping functions;
+//! - ionosphere: first order group delay `40.3 TEC / f²` of a thin shell at
+//!   [`IONOSPHERE_SHELL_HEIGHT_M`], crossed only by carriers above it;
+//! - Faraday rotation: `2.365e4 B·k TEC / f²` of the same shell, the
+//!   geomagnetic field B being an axial dipole.
+
+use bevy::math::DVec3;
+
+/// Height in m of the ionosphere thin shell.
+pub const IONOSPHERE_SHELL_HEIGHT_M: f64 = 350e3;
+/// Ionospheric group delay constant \[m<sup>3</sup>.s<sup>-2</sup>\].
+const IONOSPHERE_DELAY_CONSTANT: f64 = 40.3;
+/// One TEC unit \[electrons.m<sup>-2</sup>\].
+pub const TECU: f64 = 1e16;
+/// Faraday rotation constant \[rad.Hz<sup>2</sup>.T<sup>-1</sup>.m<sup>2</sup>\].
+const FARADAY_ROTATION_CONSTANT: f64 = 2.365e4;
+/// Geomagnetic dipole field in T at the equator, on the ground.
+const EQUATORIAL_MAGNETIC_FIELD_T: f64 = 3.12e-5;
+/// Scale heights in m of the hydrostatic and wet refractivities.
+const HYDROSTATIC_SCALE_HEIGHT_M: f64 = 8000.0;
+const WET_SCALE_HEIGHT_M: f64 = 2000.0;
+/// Mean Earth radius in m (ionospheric mapping).
+const EARTH_MEAN_RADIUS_M: f64 = 6371e3;
+
+/// Surface meteorological conditions and ionospheric content at the scene.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Atmosphere {
+    pub pressure_hpa: f64,
+    pub temperature_k: f64,
+    /// Relative humidity in [0, 1]
+    pub relative_humidity: f64,
+    /// Vertical Total Electron Content in TECU
+    pub vtec_tecu: f64,
+    /// Scene latitude in degrees and height above the ellipsoid in m
+    pub latitude_deg: f64,
+    pub height_m: f64,
+}
+
+impl Default for Atmosphere {
+    /// Standard atmosphere at sea level, 50 % humidity and a moderate 10 TECU.
+    fn default() -> Self {
+        Self {
+            pressure_hpa: 1013.25,
+            temperature_k: 288.15,
+            relative_humidity: 0.5,
+            vtec_tecu: 10.0,
+            latitude_deg: 45.0,
+            height_m: 0.0,
+        }
+    }
+}
+
+/// One-way excess path lengths in m.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct PathDelays {
+    pub tropospheric_m: f64,
+    pub ionospheric_m: f64,
+}
+
+impl PathDelays {
+    /// Gets the total excess path length in m.
+    #[inline]
+    pub fn total_m(&self) -> f64 {
+        self.tropospheric_m + self.ionospheric_m
+    }
+}
+
+impl Atmosphere {
+    /// Gets the (hydrostatic, wet) zenith tropospheric delays in m at the
+    /// scene (Saastamoinen).
+    pub fn zenith_tropospheric_delays_m(&self) -> (f64, f64) {
+        let gravity_factor = 1.0 - 0.00266 * (2.0 * self.latitude_deg.to_radians()).cos() - 0.28e-6 * self.height_m;
+        let hydrostatic_m = 0.0022768 * self.pressure_hpa / gravity_factor;
+        // Water vapour partial pressure (Magnus formula)
+        let temperature_c = self.temperature_k - 273.15;
+        let vapour_pressure_hpa = self.relative_humidity.clamp(0.0, 1.0)
+            * 6.11 * 10f64.powf(7.5 * temperature_c / (temperature_c + 237.3));
+        let wet_m = 0.002277 * (1255.0 / self.temperature_k + 0.05) * vapour_pressure_hpa;
+        (hydrostatic_m, wet_m)
+    }
+
+    /// Gets the one-way delays along the path from the scene to a carrier at
+    /// `elevation_deg` above the scene horizon, flying `carrier_height_m`
+    /// above the scene, for a carrier frequency `frequency_hz`.
+    pub fn slant_path_delays(&self, elevation_deg: f64, carrier_height_m: f64, frequency_hz: f64) -> PathDelays {
+        let elevation = elevation_deg.clamp(1.0, 90.0).to_radians();
+        // Chao mapping functions
+        let mapping = |a: f64, b: f64| 1.0 / (elevation.sin() + a / (elevation.tan() + b));
+        let (hydrostatic_m, wet_m) = self.zenith_tropospheric_delays_m();
+        // Fraction of the exponential refractivity profiles below the carrier
+        let below = |scale_height_m: f64| 1.0 - (-carrier_height_m.max(0.0) / scale_height_m).exp();
+        let tropospheric_m = hydrostatic_m * below(HYDROSTATIC_SCALE_HEIGHT_M) * mapping(0.00143, 0.0445)
+            + wet_m * below(WET_SCALE_HEIGHT_M) * mapping(0.00035, 0.017);
+        let ionospheric_m = if frequency_hz > 0.0 {
+            IONOSPHERE_DELAY_CONSTANT * self.slant_tec(elevation, carrier_height_m) / (frequency_hz * frequency_hz)
+        } else {
+            0.0
+        };
+        PathDelays { tropospheric_m, ionospheric_m }
+    }
+
+    /// Gets the one-way Faraday rotation angle in rad along the path from the
+    /// scene to a carrier, `direction` being the ENU direction from the scene
+    /// to the carrier.
+    ///
+    /// The angle is signed in the scene to carrier sense: the rotations of
+    /// the Tx and Rx paths add up (twice the one-way angle when monostatic).
+    pub fn faraday_rotation_rad(&self, direction: &DVec3, carrier_height_m: f64, frequency_hz: f64) -> f64 {
+        let Some(direction) = direction.try_normalize() else {
+            return 0.0;
+        };
+        if frequency_hz <= 0.0 {
+            return 0.0;
+        }
+        let elevation = direction.z.clamp(-1.0, 1.0).asin().max(1f64.to_radians());
+        FARADAY_ROTATION_CONSTANT * self.magnetic_field_t().dot(direction) * self.slant_tec(elevation, carrier_height_m)
+            / (frequency_hz * frequency_hz)
+    }
+
+    /// Gets the ENU geomagnetic field in T at the ionosphere shell above the
+    /// scene, from an axial dipole: northwards, and downwards in the Northern
+    /// hemisphere.
+    pub fn magnetic_field_t(&self) -> DVec3 {
+        let latitude = self.latitude_deg.to_radians();
+        let radius_ratio = EARTH_MEAN_RADIUS_M / (EARTH_MEAN_RADIUS_M + IONOSPHERE_SHELL_HEIGHT_M);
+        EQUATORIAL_MAGNETIC_FIELD_T * radius_ratio.powi(3) * DVec3::new(0.0, latitude.cos(), -2.0 * latitude.sin())
+    }
+
+    /// Gets the slant TEC in electrons.m<sup>-2</sup> along a path at
+    /// `elevation` (rad), zero when the carrier is below the ionosphere.
+    fn slant_tec(&self, elevation: f64, carrier_height_m: f64) -> f64 {
+        if self.height_m + carrier_height_m <= IONOSPHERE_SHELL_HEIGHT_M {
+            return 0.0;
+        }
+        // Thin shell mapping function
+        let sin_zenith = EARTH_MEAN_RADIUS_M / (EARTH_MEAN_RADIUS_M + IONOSPHERE_SHELL_HEIGHT_M) * elevation.cos();
+        self.vtec_tecu * TECU / (1.0 - sin_zenith * sin_zenith).sqrt()
+    }
+}
+
+/// Polarization of the bistatic channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Polarization {
+    #[default]
+    Hh,
+    Vv,
+    Hv,
+    Vh,
+    /// Circular polarizations, insensitive to the Faraday rotation
+    Circular,
+}
+
+impl Polarization {
+    pub const ALL: [Polarization; 5] = [Self::Hh, Self::Vv, Self::Hv, Self::Vh, Self::Circular];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Hh => "HH",
+            Self::Vv => "VV",
+            Self::Hv => "HV",
+            Self::Vh => "VH",
+            Self::Circular => "Circular",
+        }
+    }
+
+    /// Gets the two-way Faraday rotation in degrees above which the channel
+    /// is significantly affected, if any.
+    ///
+    /// A rotation Ω leaks sin²Ω of the power into the orthogonal channel:
+    /// co-polarized channels tolerate ~10° (-15 dB of leakage), but the
+    /// cross-polarized ones, typically 10 dB weaker than the co-polarized ones
+    /// leaking into them, only ~3° (-25 dB).
+    pub fn faraday_rotation_threshold_deg(&self) -> Option<f64> {
+        match self {
+            Self::Hh | Self::Vv => Some(10.0),
+            Self::Hv | Self::Vh => Some(3.0),
+            Self::Circular => None,
+        }
+    }
+}
+
+/// Propagation delays of a bistatic pair.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct BistaticDelays {
+    pub tx: PathDelays,
+    pub rx: PathDelays,
+}
+
+impl BistaticDelays {
+    /// Gets the bias in m of the bistatic range (Tx path + Rx path).
+    #[inline]
+    pub fn range_bias_m(&self) -> f64 {
+        self.tx.total_m() + self.rx.total_m()
+    }
+
+    /// Gets the differential delay in m between the Tx and the Rx paths.
+    #[inline]
+    pub fn differential_delay_m(&self) -> f64 {
+        self.tx.total_m() - self.rx.total_m()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zenith_delays_of_the_standard_atmosphere() {
+        let atmosphere = Atmosphere::default();
+        let (hydrostatic_m, wet_m) = atmosphere.zenith_tropospheric_delays_m();
+        assert!((hydrostatic_m - 2.31).abs() < 0.01, "{hydrostatic_m}");
+        assert!((0.05..0.15).contains(&wet_m), "{wet_m}");
+        // Spaceborne carrier at zenith: the whole troposphere
+        let delays = atmosphere.slant_path_delays(90.0, 700e3, 1.0e9);
+        assert!((delays.tropospheric_m - hydrostatic_m - wet_m).abs() < 1e-3);
+        // 40.3 × 10 TECU / (1 GHz)² with the shell mapping at zenith
+        assert!((delays.ionospheric_m - 4.03).abs() < 1e-9);
+    }
+
+    #[test]
+    fn airborne_and_slant_paths() {
+        let atmosphere = Atmosphere::default();
+        let zenith = atmosphere.slant_path_delays(90.0, 3000.0, 10e9);
+        assert_eq!(zenith.ionospheric_m, 0.0);
+        // Most of the wet delay, ~31 % of the hydrostatic one below 3 km
+        let (hydrostatic_m, wet_m) = atmosphere.zenith_tropospheric_delays_m();
+        let expected_m = hydrostatic_m * (1.0 - (-3000.0f64 / 8000.0).exp()) + wet_m * (1.0 - (-1.5f64).exp());
+        assert!((zenith.tropospheric_m - expected_m).abs() < 1e-3);
+        // ~1 / sin(30°) at 30° elevation
+        let slant = atmosphere.slant_path_delays(30.0, 3000.0, 10e9);
+        assert!((slant.tropospheric_m / zenith.tropospheric_m - 2.0).abs() < 0.02);
+
+        let delays = BistaticDelays { tx: slant, rx: zenith };
+        assert_eq!(delays.range_bias_m(), slant.total_m() + zenith.total_m());
+        assert!(delays.differential_delay_m() > 0.0);
+    }
+
+    #[test]
+    fn faraday_rotation_at_low_frequencies() {
+        let atmosphere = Atmosphere { latitude_deg: 0.0, ..Default::default() };
+        // At the equator the field is horizontal, towards North
+        let field_t = atmosphere.magnetic_field_t();
+        assert!(field_t.z.abs() < 1e-12 && field_t.y > 0.0);
+        // Looking North at 30° elevation: B·k = |B| cos(30°), and the ratio
+        // to the ionospheric delay is set by the constants
+        let direction = DVec3::new(0.0, 30f64.to_radians().cos(), 0.5);
+        let rotation_rad = atmosphere.faraday_rotation_rad(&direction, 700e3, 1.25e9);
+        let delays = atmosphere.slant_path_delays(30.0, 700e3, 1.25e9);
+        let expected_rad = FARADAY_ROTATION_CONSTANT / IONOSPHERE_DELAY_CONSTANT * field_t.length() * 30f64.to_radians().cos()
+            * delays.ionospheric_m;
+        assert!((rotation_rad - expected_rad).abs() < 1e-9);
+        // A few degrees at L band, 16 times more at P band (f⁻²)
+        assert!((1.0..10.0).contains(&rotation_rad.to_degrees()), "{}", rotation_rad.to_degrees());
+        let p_band_rad = atmosphere.faraday_rotation_rad(&direction, 700e3, 1.25e9 / 4.0);
+        assert!((p_band_rad / rotation_rad - 16.0).abs() < 1e-9);
+        // Looking East, across the field, and from an airborne carrier: none
+        assert!(atmosphere.faraday_rotation_rad(&DVec3::new(1.0, 0.0, 1.0), 700e3, 1.25e9).abs() < 1e-12);
+        assert_eq!(atmosphere.faraday_rotation_rad(&direction, 3000.0, 1.25e9), 0.0);
+        assert_eq!(Polarization::Circular.faraday_rotation_threshold_deg(), None);
+    }
+}
@@ -0,0 +1,374 @@
+//! Idealized point-scatterer raw data simulation.
+//!
+//! Generates the range-compressed phase history of a set of point targets seen
+//! by the current BSAR geometry, both carriers moving in straight lines at
+//! constant velocity around the current scenario time:
+//!
+//! ```text
+//! s(η, τ) = Σₖ Aₖ · sinc(B · (τ - Rₖ(η)/c0)) · exp(-j2π·fc·Rₖ(η)/c0)
+//! Rₖ(η)   = |Tx(η) - Pₖ| + |Rx(η) - Pₖ|
+//! ```
+//!
+//! with `η` the slow time (one sample per pulse, at the Tx PRF) and `τ` the fast
+//! time (sampled at the oversampled bandwidth). The result can be exported as a
+//! NumPy `.npy` array of `complex128`, shaped `(pulses, samples)`, to prototype
+//! processing algorithms against the visualized geometry, or focused over a
+//! small ground patch by time-domain back-projection ([`RawData::backproject`])
+//! as a quicklook of the resolution cell.
+
+use bevy::math::DVec3;
+
+use crate::bsar::{sinc, SPEED_OF_LIGHT_IN_VACUUM};
+
+/// Maximum number of complex samples of a simulation (256 MiB as `complex128`).
+pub const RAW_DATA_MAX_SAMPLES: usize = 1 << 24;
+/// Fast-time margin, in resolution cells (1/B), kept around the target echoes.
+const FAST_TIME_MARGIN_CELLS: f64 = 8.0;
+/// Maximum number of pulses back-projected: longer phase histories are
+/// decimated in slow time, which folds grating lobes in large patches.
+pub const BACKPROJECTION_MAX_PULSES: usize = 2048;
+
+/// A point scatterer of the scene.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PointTarget {
+    /// Position in the scene (ENU) frame in meters
+    pub position_m: DVec3,
+    /// Amplitude of the echo
+    pub amplitude: f64,
+}
+
+impl Default for PointTarget {
+    fn default() -> Self {
+        Self { position_m: DVec3::ZERO, amplitude: 1.0 }
+    }
+}
+
+/// Acquisition parameters of a raw data simulation, positions and velocities
+/// expressed in the scene (ENU) frame at the center of the acquisition.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RawDataParameters {
+    pub tx_position_m: DVec3,
+    pub tx_velocity_mps: DVec3,
+    pub rx_position_m: DVec3,
+    pub rx_velocity_mps: DVec3,
+    pub center_frequency_hz: f64,
+    pub bandwidth_hz: f64,
+    pub prf_hz: f64,
+    pub integration_time_s: f64,
+    /// Fast-time sampling frequency over bandwidth ratio
+    pub range_oversampling: f64,
+}
+
+/// Simulated range-compressed phase history, stored row-major as
+/// `(pulses, samples)` complex values `[re, im]`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RawData {
+    pub parameters: RawDataParameters,
+    pub num_pulses: usize,
+    pub num_samples: usize,
+    /// Slow time of the first pulse, relative to the acquisition center
+    pub slow_time_start_s: f64,
+    /// Fast time (bistatic delay) of the first sample
+    pub fast_time_start_s: f64,
+    pub sampling_frequency_hz: f64,
+    pub samples: Vec<[f64; 2]>,
+}
+
+/// Back-projected intensity over a square ground patch, in dB relative to its
+/// maximum. Row 0 is +North (top of the image), column 0 is -East.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BackProjectionImage {
+    /// Center of the patch in the scene (ENU) frame in meters
+    pub center_m: DVec3,
+    /// Half side of the patch in meters
+    pub half_extent_m: f64,
+    /// Number of pixels per side
+    pub size: usize,
+    pub data_db: Vec<f64>,
+}
+
+impl RawDataParameters {
+    /// Gets the Tx and Rx positions at slow time `slow_time_s`.
+    #[inline]
+    pub fn positions_at(&self, slow_time_s: f64) -> (DVec3, DVec3) {
+        (
+            self.tx_position_m + slow_time_s * self.tx_velocity_mps,
+            self.rx_position_m + slow_time_s * self.rx_velocity_mps,
+        )
+    }
+
+    /// Gets the bistatic range of `point` at slow time `slow_time_s`.
+    #[inline]
+    pub fn bistatic_range_m(&self, point: &DVec3, slow_time_s: f64) -> f64 {
+        let (tx, rx) = self.positions_at(slow_time_s);
+        tx.distance(*point) + rx.distance(*point)
+    }
+
+    /// Gets the number of pulses over the integration time.
+    #[inline]
+    pub fn num_pulses(&self) -> usize {
+        (self.integration_time_s * self.prf_hz).ceil().max(1.0) as usize
+    }
+}
+
+impl RawData {
+    /// Simulates the phase history of `targets`.
+    ///
+    /// Fails on invalid parameters, an empty target list, or if the simulation
+    /// would exceed [`RAW_DATA_MAX_SAMPLES`].
+    pub fn simulate(parameters: &RawDataParameters, targets: &[PointTarget]) -> Result<Self, String> {
+        if targets.is_empty() {
+            return Err("no point target to simulate".to_string());
+        }
+        let is_positive = |value: f64| value.is_finite() && value > 0.0;
+        if !is_positive(parameters.center_frequency_hz) || !is_positive(parameters.bandwidth_hz)
+            || !is_positive(parameters.prf_hz) || !is_positive(parameters.integration_time_s)
+            || parameters.range_oversampling < 1.0
+        {
+            return Err("invalid acquisition parameters".to_string());
+        }
+        let num_pulses = parameters.num_pulses();
+        let slow_time_start_s = -0.5 * (num_pulses - 1) as f64 / parameters.prf_hz;
+        let slow_time = |pulse: usize| slow_time_start_s + pulse as f64 / parameters.prf_hz;
+
+        // Fast-time window: all echoes over the acquisition, plus a margin
+        let (mut range_min_m, mut range_max_m) = (f64::INFINITY, f64::NEG_INFINITY);
+        for pulse in 0..num_pulses {
+            for target in targets {
+                let range_m = parameters.bistatic_range_m(&target.position_m, slow_time(pulse));
+                range_min_m = range_min_m.min(range_m);
+                range_max_m = range_max_m.max(range_m);
+            }
+        }
+        if !range_min_m.is_finite() || !range_max_m.is_finite() {
+            return Err("invalid geometry".to_string());
+        }
+        let sampling_frequency_hz = parameters.range_oversampling * parameters.bandwidth_hz;
+        let margin_s = FAST_TIME_MARGIN_CELLS / parameters.bandwidth_hz;
+        let fast_time_start_s = range_min_m / SPEED_OF_LIGHT_IN_VACUUM - margin_s;
+        let fast_time_span_s = (range_max_m - range_min_m) / SPEED_OF_LIGHT_IN_VACUUM + 2.0 * margin_s;
+        let num_samples = (fast_time_span_s * sampling_frequency_hz).ceil() as usize + 1;
+        if num_pulses.saturating_mul(num_samples) > RAW_DATA_MAX_SAMPLES {
+            return Err(format!(
+                "{num_pulses} pulses × {num_samples} samples exceeds the {RAW_DATA_MAX_SAMPLES} samples limit"
+            ));
+        }
+
+        let mut samples = vec![[0.0; 2]; num_pulses * num_samples];
+        // Only the samples within the sinc main lobe and first sidelobes are filled
+        let half_width = (FAST_TIME_MARGIN_CELLS * parameters.range_oversampling).ceil() as isize;
+        for (pulse, row) in samples.chunks_exact_mut(num_samples).enumerate() {
+            let eta = slow_time(pulse);
+            for target in targets {
+                let delay_s = parameters.bistatic_range_m(&target.position_m, eta) / SPEED_OF_LIGHT_IN_VACUUM;
+                let phase = -2.0 * std::f64::consts::PI * parameters.center_frequency_hz * delay_s;
+                let (sin, cos) = phase.sin_cos();
+                let center = ((delay_s - fast_time_start_s) * sampling_frequency_hz).round() as isize;
+                let first = (center - half_width).max(0) as usize;
+                let last = ((center + half_width).max(0) as usize).min(num_samples - 1);
+                for (sample, value) in row.iter_mut().enumerate().take(last + 1).skip(first) {
+                    let tau = fast_time_start_s + sample as f64 / sampling_frequency_hz;
+                    let amplitude = target.amplitude * sinc(parameters.bandwidth_hz * (tau - delay_s));
+                    value[0] += amplitude * cos;
+                    value[1] += amplitude * sin;
+                }
+            }
+        }
+
+        Ok(Self {
+            parameters: parameters.clone(),
+            num_pulses,
+            num_samples,
+            slow_time_start_s,
+            fast_time_start_s,
+            sampling_frequency_hz,
+            samples,
+        })
+    }
+
+    /// Encodes the phase history as a NumPy `.npy` (format 1.0) array of
+    /// little-endian `complex128`, shaped `(pulses, samples)`.
+    pub fn to_npy(&self) -> Vec<u8> {
+        let mut header = format!(
+            "{{'descr': '<c16', 'fortran_order': False, 'shape': ({}, {}), }}",
+            self.num_pulses, self.num_samples
+        );
+        // Magic (6) + version (2) + header length (2) + header, padded with spaces
+        // and ended by a newline to a multiple of 64 bytes
+        let unpadded_len = 10 + header.len() + 1;
+        header.push_str(&" ".repeat(unpadded_len.next_multiple_of(64) - unpadded_len));
+        header.push('\n');
+
+        let mut bytes = Vec::with_capacity(10 + header.len() + 16 * self.samples.len());
+        bytes.extend_from_slice(b"\x93NUMPY\x01\x00");
+        bytes.extend_from_slice(&(header.len() as u16).to_le_bytes());
+        bytes.extend_from_slice(header.as_bytes());
+        for [re, im] in self.samples.iter() {
+            bytes.extend_from_slice(&re.to_le_bytes());
+            bytes.extend_from_slice(&im.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Describes the acquisition as a `name,value` CSV table, to be saved next
+    /// to the `.npy` array.
+    pub fn parameters_as_csv(&self) -> String {
+        let p = &self.parameters;
+        let vector = |v: DVec3| format!("{} {} {}", v.x, v.y, v.z);
+        [
+            ("num_pulses", self.num_pulses.to_string()),
+            ("num_samples", self.num_samples.to_string()),
+            ("center_frequency_hz", p.center_frequency_hz.to_string()),
+            ("bandwidth_hz", p.bandwidth_hz.to_string()),
+            ("prf_hz", p.prf_hz.to_string()),
+            ("sampling_frequency_hz", self.sampling_frequency_hz.to_string()),
+            ("slow_time_start_s", self.slow_time_start_s.to_string()),
+            ("fast_time_start_s", self.fast_time_start_s.to_string()),
+            ("tx_position_m", vector(p.tx_position_m)),
+            ("tx_velocity_mps", vector(p.tx_velocity_mps)),
+            ("rx_position_m", vector(p.rx_position_m)),
+            ("rx_velocity_mps", vector(p.rx_velocity_mps)),
+        ]
+        .iter()
+        .fold("name,value\n".to_string(), |csv, (name, value)| csv + &format!("{name},{value}\n"))
+    }
+
+    /// Focuses the phase history by time-domain back-projection over a square
+    /// horizontal ground patch of `size` × `size` pixels centered on `center_m`:
+    ///
+    /// ```text
+    /// I(P) = |Σₙ s(ηₙ, R(P, ηₙ)/c0) · exp(+j2π·fc·R(P, ηₙ)/c0)|²
+    /// ```
+    ///
+    /// with the fast-time samples linearly interpolated.
+    pub fn backproject(&self, center_m: &DVec3, half_extent_m: f64, size: usize) -> BackProjectionImage {
+        let size = size.max(2);
+        let stride = self.num_pulses.div_ceil(BACKPROJECTION_MAX_PULSES);
+        let step = 2.0 * half_extent_m / (size - 1) as f64;
+        let mut intensity = vec![0.0f64; size * size];
+        for (i, value) in intensity.iter_mut().enumerate() {
+            let (row, col) = (i / size, i % size);
+            let point = *center_m + DVec3::new(
+                -half_extent_m + step * col as f64,
+                half_extent_m - step * row as f64,
+                0.0
+            );
+            let (mut re, mut im) = (0.0, 0.0);
+            for pulse in (0..self.num_pulses).step_by(stride) {
+                let eta = self.slow_time_start_s + pulse as f64 / self.parameters.prf_hz;
+                let delay_s = self.parameters.bistatic_range_m(&point, eta) / SPEED_OF_LIGHT_IN_VACUUM;
+                let position = (delay_s - self.fast_time_start_s) * self.sampling_frequency_hz;
+                if position < 0.0 || position >= (self.num_samples - 1) as f64 {
+                    continue; // Outside the recorded echoes
+                }
+                let (sample, fraction) = (position as usize, position.fract());
+                let row = &self.samples[pulse * self.num_samples..(pulse + 1) * self.num_samples];
+                let sample_re = (1.0 - fraction) * row[sample][0] + fraction * row[sample + 1][0];
+                let sample_im = (1.0 - fraction) * row[sample][1] + fraction * row[sample + 1][1];
+                let phase = 2.0 * std::f64::consts::PI * self.parameters.center_frequency_hz * delay_s;
+                let (sin, cos) = phase.sin_cos();
+                re += sample_re * cos - sample_im * sin;
+                im += sample_re * sin + sample_im * cos;
+            }
+            *value = re * re + im * im;
+        }
+        let max = intensity.iter().copied().fold(f64::MIN_POSITIVE, f64::max);
+        BackProjectionImage {
+            center_m: *center_m,
+            half_extent_m,
+            size,
+            data_db: intensity.iter().map(|value| 10.0 * (value / max).log10()).collect(),
+        }
+    }
+
+    /// Gets the size in bytes of the phase history as `complex128`.
+    #[inline]
+    pub fn size_bytes(&self) -> usize {
+        16 * self.samples.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parameters() -> RawDataParameters {
+        RawDataParameters {
+            tx_position_m: DVec3::new(-3000.0, 0.0, 3000.0),
+            tx_velocity_mps: DVec3::new(0.0, 120.0, 0.0),
+            rx_position_m: DVec3::new(-1000.0, 0.0, 1000.0),
+            rx_velocity_mps: DVec3::new(0.0, 36.0, 0.0),
+            center_frequency_hz: 10e9,
+            bandwidth_hz: 100e6,
+            prf_hz: 1000.0,
+            integration_time_s: 0.2,
+            range_oversampling: 1.2,
+        }
+    }
+
+    #[test]
+    fn echo_peaks_at_the_bistatic_delay_with_its_phase() {
+        let parameters = parameters();
+        let target = PointTarget { position_m: DVec3::new(10.0, -5.0, 0.0), amplitude: 2.0 };
+        let raw_data = RawData::simulate(&parameters, &[target]).unwrap();
+        assert_eq!(raw_data.num_pulses, 200);
+        assert_eq!(raw_data.samples.len(), raw_data.num_pulses * raw_data.num_samples);
+
+        for pulse in [0, 100, 199] {
+            let eta = raw_data.slow_time_start_s + pulse as f64 / parameters.prf_hz;
+            let delay_s = parameters.bistatic_range_m(&target.position_m, eta) / SPEED_OF_LIGHT_IN_VACUUM;
+            let row = &raw_data.samples[pulse * raw_data.num_samples..(pulse + 1) * raw_data.num_samples];
+            let (peak, value) = row.iter().enumerate()
+                .max_by(|a, b| a.1[0].hypot(a.1[1]).total_cmp(&b.1[0].hypot(b.1[1])))
+                .unwrap();
+            let tau = raw_data.fast_time_start_s + peak as f64 / raw_data.sampling_frequency_hz;
+            assert!((tau - delay_s).abs() <= 0.5 / raw_data.sampling_frequency_hz);
+            // Off-grid peak: the sinc is sampled within half a sample of its maximum
+            let magnitude = value[0].hypot(value[1]);
+            assert!(magnitude <= 2.0 + 1e-12 && magnitude > 2.0 * sinc(0.5 / 1.2));
+            let expected_phase = -2.0 * std::f64::consts::PI * parameters.center_frequency_hz * delay_s;
+            let phase_error = (value[1].atan2(value[0]) - expected_phase).rem_euclid(2.0 * std::f64::consts::PI);
+            assert!(phase_error.min(2.0 * std::f64::consts::PI - phase_error) < 1e-6);
+        }
+    }
+
+    #[test]
+    fn npy_header_is_aligned_and_describes_the_array() {
+        let raw_data = RawData::simulate(&parameters(), &[PointTarget::default()]).unwrap();
+        let bytes = raw_data.to_npy();
+        assert_eq!(&bytes[..8], b"\x93NUMPY\x01\x00");
+        let header_len = u16::from_le_bytes([bytes[8], bytes[9]]) as usize;
+        assert_eq!((10 + header_len) % 64, 0);
+        let header = std::str::from_utf8(&bytes[10..10 + header_len]).unwrap();
+        assert!(header.starts_with("{'descr': '<c16', 'fortran_order': False, "));
+        assert!(header.contains(&format!("'shape': ({}, {})", raw_data.num_pulses, raw_data.num_samples)));
+        assert!(header.ends_with('\n'));
+        assert_eq!(bytes.len(), 10 + header_len + raw_data.size_bytes());
+    }
+
+    #[test]
+    fn backprojection_focuses_on_the_target() {
+        let target = PointTarget { position_m: DVec3::new(3.0, -2.0, 0.0), amplitude: 1.0 };
+        let raw_data = RawData::simulate(&parameters(), &[target]).unwrap();
+        // 1 m pixels over a ±10 m patch centered on the scene center
+        let image = raw_data.backproject(&DVec3::ZERO, 10.0, 21);
+        let (peak, peak_db) = image.data_db.iter().enumerate()
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .unwrap();
+        assert_eq!(*peak_db, 0.0);
+        assert_eq!((peak / 21, peak % 21), (12, 13)); // (North 10 - 2, East -10 + 3)
+        // Far from the target the response is well below the peak
+        assert!(image.data_db[0] < -20.0);
+    }
+
+    #[test]
+    fn invalid_simulations_are_rejected() {
+        assert!(RawData::simulate(&parameters(), &[]).is_err());
+        let mut too_long = parameters();
+        too_long.integration_time_s = 1e4;
+        assert!(RawData::simulate(&too_long, &[PointTarget::default()]).is_err());
+        let mut no_prf = parameters();
+        no_prf.prf_hz = 0.0;
+        assert!(RawData::simulate(&no_prf, &[PointTarget::default()]).is_err());
+    }
+}
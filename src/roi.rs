@@ -0,0 +1,208 @@
+//! Ground region of interest.
+//!
+//! An axis-aligned rectangle of the scene ENU ground plane (x East, y North)
+//! in meters, drawn by the user: the scene quantities are sampled over it and
+//! the overlays and exports can be restricted to it.
+
+use bevy::math::DVec2;
+
+use crate::vector_export::{VectorLayer, VectorPath};
+
+/// A rectangle of the ENU ground plane, in meters.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GroundRect {
+    /// South-West corner
+    pub min_m: DVec2,
+    /// North-East corner
+    pub max_m: DVec2,
+}
+
+impl GroundRect {
+    /// Creates the rectangle spanned by two opposite corners.
+    pub fn from_corners(a: DVec2, b: DVec2) -> Self {
+        Self { min_m: a.min(b), max_m: a.max(b) }
+    }
+
+    #[inline]
+    pub fn center_m(&self) -> DVec2 {
+        0.5 * (self.min_m + self.max_m)
+    }
+
+    /// Gets the (East, North) sides in meters.
+    #[inline]
+    pub fn size_m(&self) -> DVec2 {
+        self.max_m - self.min_m
+    }
+
+    #[inline]
+    pub fn area_m2(&self) -> f64 {
+        self.size_m().element_product()
+    }
+
+    #[inline]
+    pub fn contains(&self, point: DVec2) -> bool {
+        point.cmpge(self.min_m).all() && point.cmple(self.max_m).all()
+    }
+
+    /// Gets the corners, counter-clockwise from the South-West one.
+    pub fn corners(&self) -> [DVec2; 4] {
+        [
+            self.min_m,
+            DVec2::new(self.max_m.x, self.min_m.y),
+            self.max_m,
+            DVec2::new(self.min_m.x, self.max_m.y),
+        ]
+    }
+
+    /// Gets the centers of a `count` x `count` grid of cells covering the
+    /// rectangle, row by row from the South.
+    pub fn sample_points(&self, count: usize) -> impl Iterator<Item = DVec2> + '_ {
+        let step = self.size_m() / count as f64;
+        (0..count * count).map(move |i| {
+            let cell = DVec2::new((i % count) as f64, (i / count) as f64);
+            self.min_m + (cell + 0.5) * step
+        })
+    }
+
+    /// Clips the segment from `a` to `b` (Liang–Barsky), returning the
+    /// parameters of its part inside the rectangle, `None` if outside.
+    fn clip_segment(&self, a: DVec2, b: DVec2) -> Option<(f64, f64)> {
+        let d = b - a;
+        let (mut t0, mut t1) = (0.0f64, 1.0f64);
+        let edges = [
+            (-d.x, a.x - self.min_m.x),
+            (d.x, self.max_m.x - a.x),
+            (-d.y, a.y - self.min_m.y),
+            (d.y, self.max_m.y - a.y),
+        ];
+        for (p, q) in edges {
+            if p == 0.0 {
+                if q < 0.0 {
+                    return None;
+                }
+            } else if p < 0.0 {
+                t0 = t0.max(q / p);
+            } else {
+                t1 = t1.min(q / p);
+            }
+        }
+        // Touching the rectangle at a single point is outside
+        (t0 < t1).then_some((t0, t1))
+    }
+
+    /// Clips a polyline, closed when `closed`, to the rectangle: gets its
+    /// parts inside, split where it leaves the rectangle.
+    pub fn clip_polyline(&self, points: &[DVec2], closed: bool) -> Vec<Vec<DVec2>> {
+        let segment_count = if closed && points.len() > 2 { points.len() } else { points.len().saturating_sub(1) };
+        let mut lines: Vec<Vec<DVec2>> = Vec::new();
+        let mut is_connected = false; // The last line ends at the start of the segment
+        for i in 0..segment_count {
+            let (a, b) = (points[i], points[(i + 1) % points.len()]);
+            let Some((t0, t1)) = self.clip_segment(a, b) else {
+                is_connected = false;
+                continue;
+            };
+            let (start, end) = (a + t0 * (b - a), a + t1 * (b - a));
+            match lines.last_mut() {
+                Some(line) if is_connected && t0 == 0.0 => line.push(end),
+                _ => lines.push(vec![start, end]),
+            }
+            is_connected = t1 == 1.0;
+        }
+        // A closed polyline crossing the rectangle: its last and first parts
+        // are joined at the first vertex
+        if closed && lines.len() > 1 && is_connected && lines[0][0] == points[0] {
+            let first = lines.remove(0);
+            lines.last_mut().unwrap().extend(first.into_iter().skip(1));
+        }
+        lines
+    }
+
+    /// Clips the paths of a vector layer to the rectangle.
+    pub fn clip_layer(&self, layer: VectorLayer) -> VectorLayer {
+        let paths = layer.paths.into_iter()
+            .flat_map(|path| {
+                let lines = self.clip_polyline(&path.points, path.closed);
+                // An uncut closed path stays closed
+                let is_uncut = path.closed && lines.len() == 1 && lines[0].len() == path.points.len() + 1;
+                lines.into_iter().map(move |mut points| {
+                    if is_uncut {
+                        points.pop();
+                    }
+                    VectorPath { points, closed: is_uncut, ..path.clone() }
+                })
+            })
+            .collect();
+        VectorLayer { paths, ..layer }
+    }
+}
+
+/// Minimum, maximum and mean of a quantity over the region of interest.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Statistics {
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+    /// Number of valid (finite) samples
+    pub count: usize,
+}
+
+impl Statistics {
+    /// Gets the statistics of the finite values, `None` if there are none.
+    pub fn from_values(values: impl IntoIterator<Item = f64>) -> Option<Self> {
+        let (mut min, mut max, mut sum, mut count) = (f64::INFINITY, f64::NEG_INFINITY, 0.0, 0);
+        for value in values.into_iter().filter(|value| value.is_finite()) {
+            min = min.min(value);
+            max = max.max(value);
+            sum += value;
+            count += 1;
+        }
+        (count > 0).then_some(Self { min, max, mean: sum / count as f64, count })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn polylines_clipped_to_the_rectangle() {
+        let rect = GroundRect::from_corners(DVec2::new(10.0, 10.0), DVec2::new(-10.0, -10.0));
+        assert_eq!(rect.min_m, DVec2::new(-10.0, -10.0));
+        assert_eq!(rect.area_m2(), 400.0);
+        // Crossing the rectangle, in and out twice
+        let line = [DVec2::new(-20.0, 0.0), DVec2::new(0.0, 0.0), DVec2::new(0.0, 20.0), DVec2::new(5.0, 20.0), DVec2::new(5.0, -20.0)];
+        let lines = rect.clip_polyline(&line, false);
+        assert_eq!(lines, vec![
+            vec![DVec2::new(-10.0, 0.0), DVec2::new(0.0, 0.0), DVec2::new(0.0, 10.0)],
+            vec![DVec2::new(5.0, 10.0), DVec2::new(5.0, -10.0)],
+        ]);
+        // A closed square around a corner, starting on the rectangle edge
+        let square = [DVec2::new(10.0, 5.0), DVec2::new(15.0, 5.0), DVec2::new(15.0, 15.0), DVec2::new(5.0, 15.0), DVec2::new(5.0, 5.0)];
+        let lines = rect.clip_polyline(&square, true);
+        assert_eq!(lines, vec![vec![DVec2::new(5.0, 10.0), DVec2::new(5.0, 5.0), DVec2::new(10.0, 5.0)]]);
+        // Leaving and entering again: joined across its first vertex, inside
+        let rectangle = [DVec2::ZERO, DVec2::new(20.0, 0.0), DVec2::new(20.0, 5.0), DVec2::new(0.0, 5.0)];
+        let lines = rect.clip_polyline(&rectangle, true);
+        assert_eq!(lines, vec![vec![DVec2::new(10.0, 5.0), DVec2::new(0.0, 5.0), DVec2::ZERO, DVec2::new(10.0, 0.0)]]);
+        // Inside, the closed path stays closed
+        let layer = VectorLayer {
+            name: "A".to_string(),
+            rgb: (0, 0, 0),
+            paths: vec![VectorPath { points: vec![DVec2::ZERO, DVec2::X, DVec2::Y], closed: true, ..Default::default() }],
+        };
+        assert_eq!(rect.clip_layer(layer.clone()), layer);
+        assert!(GroundRect::from_corners(DVec2::splat(20.0), DVec2::splat(30.0)).clip_layer(layer).paths.is_empty());
+    }
+
+    #[test]
+    fn statistics_of_the_samples() {
+        let rect = GroundRect::from_corners(DVec2::ZERO, DVec2::new(4.0, 2.0));
+        let points: Vec<DVec2> = rect.sample_points(2).collect();
+        assert_eq!(points, vec![DVec2::new(1.0, 0.5), DVec2::new(3.0, 0.5), DVec2::new(1.0, 1.5), DVec2::new(3.0, 1.5)]);
+        assert!(points.iter().all(|&point| rect.contains(point)));
+        let statistics = Statistics::from_values([1.0, f64::NAN, 3.0, 2.0]).unwrap();
+        assert_eq!(statistics, Statistics { min: 1.0, max: 3.0, mean: 2.0, count: 3 });
+        assert!(Statistics::from_values([f64::NAN]).is_none());
+    }
+}
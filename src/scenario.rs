@@ -0,0 +1,236 @@
+//! Scenario files and their descriptive metadata.
+//!
+//! A title, an author, a description and a timestamp document a geometry once
+//! exported: they are written as comments of the CSV tables and of the DXF
+//! drawings, as the title and description of the SVG drawings and as the
+//! `extras` of the glTF asset.
+//!
+//! A scenario file (`.bsargeom`) saves the metadata and the parameters of the
+//! geometry as `key = value` lines, the parameters being numbers keyed by
+//! carrier (`tx.height_m`, `rx.integration_time_s`), flags being 0 or 1.
+//! Lines starting with `#` are comments.
+//!
+//! The compute endpoint takes a scenario as a JSON object, the metadata fields
+//! as strings beside a `parameters` object of numbers and booleans:
+//! `{"title": "Pass 1", "parameters": {"tx.height_m": 3000, "rx.is_stationary": true}}`.
+
+use std::collections::BTreeMap;
+
+use crate::epoch::UtcTime;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::json::JsonValue;
+
+/// File name extension of the scenario files (without the dot).
+pub const SCENARIO_EXTENSION: &str = "bsargeom";
+
+/// Metadata of the scenario, every field being optional.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ScenarioMetadata {
+    pub title: String,
+    pub author: String,
+    /// Free text, possibly over several lines
+    pub description: String,
+    /// Date of the scenario, `None` when undated
+    pub timestamp: Option<UtcTime>,
+}
+
+impl ScenarioMetadata {
+    /// Gets the filled fields as (name, value) pairs, trimmed.
+    pub fn fields(&self) -> Vec<(&'static str, String)> {
+        [
+            ("title", self.title.trim().to_string()),
+            ("author", self.author.trim().to_string()),
+            ("description", self.description.trim().to_string()),
+            ("timestamp", self.timestamp.map(|timestamp| timestamp.to_string()).unwrap_or_default()),
+        ]
+        .into_iter()
+        .filter(|(_, value)| !value.is_empty())
+        .collect()
+    }
+
+    /// Gets the filled fields as `name: value` lines, the description lines
+    /// after the first one being indented.
+    pub fn lines(&self) -> Vec<String> {
+        self.fields().into_iter()
+            .flat_map(|(name, value)| {
+                value.lines()
+                    .enumerate()
+                    .map(|(i, line)| if i == 0 { format!("{name}: {line}") } else { format!("  {line}") })
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    /// Gets the metadata as comment lines heading a CSV table (`# name: value`),
+    /// empty without metadata.
+    pub fn csv_comments(&self) -> String {
+        self.lines().iter().map(|line| format!("# {line}\n")).collect()
+    }
+}
+
+/// A saved geometry.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Scenario {
+    pub metadata: ScenarioMetadata,
+    /// Parameters of the geometry by key
+    pub parameters: BTreeMap<String, f64>,
+}
+
+impl Scenario {
+    /// Reads a scenario file, failing on the first invalid line.
+    pub fn from_text(text: &str) -> Result<Self, String> {
+        let mut scenario = Self::default();
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let invalid = |reason: &str| format!("line {}: {reason}", i + 1);
+            let (key, value) = line.split_once('=').ok_or_else(|| invalid("expected key = value"))?;
+            let (key, value) = (key.trim(), value.trim());
+            let metadata = &mut scenario.metadata;
+            match key {
+                "title" => metadata.title = unescape(value),
+                "author" => metadata.author = unescape(value),
+                "description" => metadata.description = unescape(value),
+                "timestamp" => {
+                    metadata.timestamp = if value.is_empty() { None } else { Some(UtcTime::parse(value).map_err(|error| invalid(&error))?) };
+                }
+                _ => {
+                    let number = match value {
+                        "true" => 1.0,
+                        "false" => 0.0,
+                        _ => value.parse::<f64>().map_err(|_| invalid(&format!("\"{value}\" is not a number")))?,
+                    };
+                    scenario.parameters.insert(key.to_string(), number);
+                }
+            }
+        }
+        Ok(scenario)
+    }
+
+    /// Reads a scenario JSON object, the missing fields keeping their default.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn from_json(json: &JsonValue) -> Result<Self, String> {
+        let JsonValue::Object(members) = json else {
+            return Err("a scenario is a JSON object".to_string());
+        };
+        let mut scenario = Self::default();
+        for (key, value) in members {
+            let metadata = &mut scenario.metadata;
+            match (key.as_str(), value) {
+                ("title", JsonValue::String(title)) => metadata.title = title.clone(),
+                ("author", JsonValue::String(author)) => metadata.author = author.clone(),
+                ("description", JsonValue::String(description)) => metadata.description = description.clone(),
+                ("timestamp", JsonValue::String(timestamp)) => {
+                    metadata.timestamp = if timestamp.is_empty() { None } else { Some(UtcTime::parse(timestamp).map_err(|error| format!("timestamp: {error}"))?) };
+                }
+                ("timestamp", JsonValue::Null) => metadata.timestamp = None,
+                ("parameters", JsonValue::Object(parameters)) => {
+                    for (name, value) in parameters {
+                        let number = match value {
+                            JsonValue::Number(number) => *number,
+                            JsonValue::Bool(flag) => if *flag { 1.0 } else { 0.0 },
+                            _ => return Err(format!("{name}: not a number")),
+                        };
+                        scenario.parameters.insert(name.clone(), number);
+                    }
+                }
+                ("title" | "author" | "description" | "timestamp", _) => return Err(format!("{key}: not a string")),
+                ("parameters", _) => return Err("parameters: not an object".to_string()),
+                _ => return Err(format!("{key}: unknown field")),
+            }
+        }
+        Ok(scenario)
+    }
+
+    /// Gets the text of the scenario file, read back by [`Self::from_text`].
+    pub fn to_text(&self) -> String {
+        let mut text = String::from("# BSARGeom scenario\n");
+        for (key, value) in self.metadata.fields() {
+            let value = if key == "timestamp" { value } else { escape(&value) };
+            text.push_str(&format!("{key} = {value}\n"));
+        }
+        for (key, value) in self.parameters.iter() {
+            text.push_str(&format!("{key} = {value}\n"));
+        }
+        text
+    }
+}
+
+/// Escapes the backslashes and the line breaks of a metadata value, kept on
+/// one line.
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+/// Reverts [`escape`].
+fn unescape(value: &str) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => unescaped.push('\n'),
+            Some(c) => unescaped.push(c),
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn metadata_lines_skip_empty_fields() {
+        assert!(ScenarioMetadata::default().csv_comments().is_empty());
+        let metadata = ScenarioMetadata {
+            title: " Bistatic pass ".to_string(),
+            author: String::new(),
+            description: "Tx airborne\nRx fixed".to_string(),
+            timestamp: Some(UtcTime::from_unix_s(0.0)),
+        };
+        assert_eq!(
+            metadata.csv_comments(),
+            "# title: Bistatic pass\n# description: Tx airborne\n#   Rx fixed\n# timestamp: 1970-01-01T00:00:00.000Z\n"
+        );
+    }
+
+    #[test]
+    fn scenario_text_roundtrip() {
+        let scenario = Scenario {
+            metadata: ScenarioMetadata {
+                title: "Pass = 1".to_string(),
+                author: String::new(),
+                description: "C:\\data\nsecond line".to_string(),
+                timestamp: Some(UtcTime::from_unix_s(86400.5)),
+            },
+            parameters: BTreeMap::from([("tx.height_m".to_string(), 3000.0), ("rx.is_stationary".to_string(), 1.0)]),
+        };
+        let text = scenario.to_text();
+        assert!(text.contains("description = C:\\\\data\\nsecond line\n"), "{text}");
+        assert_eq!(Scenario::from_text(&text), Ok(scenario));
+        let parsed = Scenario::from_text("# comment\n\nrx.is_stationary = true\n").unwrap();
+        assert_eq!(parsed.parameters["rx.is_stationary"], 1.0);
+        assert_eq!(Scenario::from_text("tx.height_m = 3000\ntx.velocity_mps = fast"), Err("line 2: \"fast\" is not a number".to_string()));
+        assert!(Scenario::from_text("tx.height_m").is_err());
+    }
+
+    #[test]
+    fn scenario_from_json() {
+        let json = JsonValue::parse(r#"{"title": "Pass 1", "timestamp": "1970-01-02T00:00:00Z",
+            "parameters": {"tx.height_m": 3000, "rx.is_stationary": true}}"#).unwrap();
+        let scenario = Scenario::from_json(&json).unwrap();
+        assert_eq!(scenario.metadata.title, "Pass 1");
+        assert_eq!(scenario.metadata.timestamp, Some(UtcTime::from_unix_s(86400.0)));
+        assert_eq!(scenario.parameters, BTreeMap::from([("tx.height_m".to_string(), 3000.0), ("rx.is_stationary".to_string(), 1.0)]));
+        for text in [r#"[]"#, r#"{"title": 1}"#, r#"{"parameters": {"tx.height_m": "high"}}"#, r#"{"height": 1}"#] {
+            assert!(Scenario::from_json(&JsonValue::parse(text).unwrap()).is_err(), "{text}");
+        }
+    }
+}
@@ -1,11 +1,14 @@
+use std::collections::BTreeMap;
+
 use bevy::{
     prelude::*,
     math::DVec3
 };
 
 use crate::{
-    bsar::BsarInfos,
+    bsar::{BsarInfos, ResolutionFormula},
     camera::CameraPlugin,
+    chirp::Chirp,
     entities::{
         iso_range_doppler_plane_transform_from_state,
         iso_range_ellipsoid_transform_from_state,
@@ -13,8 +16,10 @@ use crate::{
         spawn_iso_range_doppler_plane,
         spawn_iso_range_ellipsoid,
         AntennaBeamFootprintState, AntennaBeamState, AntennaState,
-        CarrierState, IsoRangeDopplerPlaneState
+        CarrierState, IsoContours, IsoRangeDopplerPlaneState
     },
+    limits::CarrierLimits,
+    motion::MotionProfile,
     world::WorldPlugin
 };
 
@@ -33,6 +38,8 @@ impl Plugin for ScenePlugin {
             .init_resource::<RxAntennaBeamFootprintState>()
             .init_resource::<BsarInfosState>()
             .init_resource::<IsoRangeDopplerPlaneState>()
+            .init_resource::<IsoContours>()
+            .insert_resource(CarrierLimitsState::from_env())
             .add_plugins((CameraPlugin, WorldPlugin))
             .add_systems(Startup, spawn_scene);
     }
@@ -43,7 +50,7 @@ impl Plugin for ScenePlugin {
 pub struct Tx;
 
 /// Resource to keep old state of Transmitter
-#[derive(Resource)]
+#[derive(Resource, Clone)]
 pub struct TxCarrierState {
     pub inner: CarrierState,
     pub center_frequency_ghz: f64, // Center frequency of the carrier
@@ -65,6 +72,9 @@ impl Default for TxCarrierState {
                 velocity_mps: 120.0,
                 position_m: DVec3::ZERO,
                 velocity_vector_mps: DVec3::ZERO,
+                acceleration_vector_mps2: None,
+                time_s: 0.0,
+                motion: MotionProfile::default(),
             },
             center_frequency_ghz: 10.0,
             bandwidth_mhz: 800.0,
@@ -76,6 +86,16 @@ impl Default for TxCarrierState {
     }
 }
 
+impl TxCarrierState {
+    /// Gets the transmitted chirp pulse.
+    pub fn chirp(&self) -> Chirp {
+        Chirp {
+            bandwidth_hz: self.bandwidth_mhz * 1e6,
+            pulse_duration_s: self.pulse_duration_us * 1e-6,
+        }
+    }
+}
+
 /// Resource to keep old state of Transmitter
 #[derive(Resource)]
 pub struct TxAntennaState {
@@ -88,7 +108,8 @@ impl Default for TxAntennaState {
             inner: AntennaState {
                 heading_deg: 90.0,
                 elevation_deg: -30.0,
-                bank_deg: 0.0
+                bank_deg: 0.0,
+                lever_arm_m: DVec3::ZERO,
             }
         }
     }
@@ -140,14 +161,24 @@ impl PixelResolution {
 }
 
 /// Resource to keep old state of Transmitter
-#[derive(Resource)]
+#[derive(Resource, Clone)]
 pub struct RxCarrierState {
     pub inner: CarrierState,
     pub noise_temperature_k: f64,
     pub noise_factor_db: f64,
+    /// Noise bandwidth of the receiver, the Tx bandwidth for a matched receiver
+    pub noise_bandwidth_mhz: f64,
+    /// Range sampling frequency over the noise bandwidth
+    pub range_oversampling: f64,
     pub integration_time_s: f64,
-    pub squared_pixels: bool,
+    /// The integration time is solved for `pixel_aspect_ratio` instead of being set
+    pub fixed_pixel_aspect_ratio: bool,
+    /// Target range:lateral resolution ratio (1 for squared pixels)
+    pub pixel_aspect_ratio: f64,
     pub pixel_resolution: PixelResolution,
+    pub resolution_formula: ResolutionFormula,
+    /// Fixed (ground-based) receiver: zero velocity, `inner.height_m` being its tower height
+    pub is_stationary: bool,
 }
 
 impl Default for RxCarrierState {
@@ -161,12 +192,20 @@ impl Default for RxCarrierState {
                 velocity_mps: 36.0,
                 position_m: DVec3::ZERO,
                 velocity_vector_mps: DVec3::ZERO,
+                acceleration_vector_mps2: None,
+                time_s: 0.0,
+                motion: MotionProfile::default(),
             },
             noise_temperature_k: 290.0,
             noise_factor_db: 5.0,
+            noise_bandwidth_mhz: 800.0,
+            range_oversampling: 1.2,
             integration_time_s: 1.0,
-            squared_pixels: true,
-            pixel_resolution: PixelResolution::Ground
+            fixed_pixel_aspect_ratio: true,
+            pixel_aspect_ratio: 1.0,
+            pixel_resolution: PixelResolution::Ground,
+            resolution_formula: ResolutionFormula::Bisector,
+            is_stationary: false,
         }
     }
 }
@@ -183,7 +222,8 @@ impl Default for RxAntennaState {
             inner: AntennaState {
                 heading_deg: 90.0, // 0°, right-looking
                 elevation_deg: -45.0, // 45° of depression
-                bank_deg: 0.0
+                bank_deg: 0.0,
+                lever_arm_m: DVec3::ZERO,
             }
         }
     }
@@ -214,6 +254,113 @@ pub struct RxAntennaBeamFootprintState {
     pub inner: AntennaBeamFootprintState
 }
 
+/// Mutable view on the states saved in the scenario files.
+pub struct ScenarioStates<'a> {
+    pub tx: (&'a mut TxCarrierState, &'a mut TxAntennaState, &'a mut TxAntennaBeamState),
+    pub rx: (&'a mut RxCarrierState, &'a mut RxAntennaState, &'a mut RxAntennaBeamState),
+}
+
+impl ScenarioStates<'_> {
+    /// Gets the numeric parameters by scenario key (`tx.height_m`).
+    fn numbers(&mut self) -> Vec<(String, &mut f64)> {
+        let (tx_carrier, tx_antenna, tx_beam) = &mut self.tx;
+        let (rx_carrier, rx_antenna, rx_beam) = &mut self.rx;
+        let mut numbers = carrier_numbers("tx", &mut tx_carrier.inner, &mut tx_antenna.inner, &mut tx_beam.inner);
+        numbers.extend([
+            ("tx.center_frequency_ghz".to_string(), &mut tx_carrier.center_frequency_ghz),
+            ("tx.bandwidth_mhz".to_string(), &mut tx_carrier.bandwidth_mhz),
+            ("tx.pulse_duration_us".to_string(), &mut tx_carrier.pulse_duration_us),
+            ("tx.prf_hz".to_string(), &mut tx_carrier.prf_hz),
+            ("tx.peak_power_w".to_string(), &mut tx_carrier.peak_power_w),
+            ("tx.loss_factor_db".to_string(), &mut tx_carrier.loss_factor_db),
+        ]);
+        numbers.extend(carrier_numbers("rx", &mut rx_carrier.inner, &mut rx_antenna.inner, &mut rx_beam.inner));
+        numbers.extend([
+            ("rx.noise_temperature_k".to_string(), &mut rx_carrier.noise_temperature_k),
+            ("rx.noise_factor_db".to_string(), &mut rx_carrier.noise_factor_db),
+            ("rx.noise_bandwidth_mhz".to_string(), &mut rx_carrier.noise_bandwidth_mhz),
+            ("rx.range_oversampling".to_string(), &mut rx_carrier.range_oversampling),
+            ("rx.integration_time_s".to_string(), &mut rx_carrier.integration_time_s),
+            ("rx.pixel_aspect_ratio".to_string(), &mut rx_carrier.pixel_aspect_ratio),
+        ]);
+        numbers
+    }
+
+    /// Gets the parameters saved in a scenario file, flags being 0 or 1.
+    pub fn parameters(&mut self) -> BTreeMap<String, f64> {
+        let mut parameters: BTreeMap<String, f64> = self.numbers().into_iter()
+            .map(|(key, value)| (key, *value))
+            .collect();
+        let rx_carrier = &self.rx.0;
+        let flags = [
+            ("rx.is_stationary", rx_carrier.is_stationary),
+            ("rx.fixed_pixel_aspect_ratio", rx_carrier.fixed_pixel_aspect_ratio),
+            ("rx.is_ground_resolution", rx_carrier.pixel_resolution.is_ground()),
+            ("rx.is_exact_gradient_resolution", rx_carrier.resolution_formula == ResolutionFormula::ExactGradient),
+        ];
+        parameters.extend(flags.map(|(key, flag)| (key.to_string(), if flag { 1.0 } else { 0.0 })));
+        parameters
+    }
+
+    /// Sets the parameters of a scenario file, the missing ones being kept.
+    /// Returns the unknown keys.
+    pub fn apply(&mut self, parameters: &BTreeMap<String, f64>) -> Vec<String> {
+        let mut unknown_keys: Vec<String> = parameters.keys().cloned().collect();
+        for (key, value) in self.numbers() {
+            if let Some(parameter) = parameters.get(&key) {
+                *value = *parameter;
+                unknown_keys.retain(|unknown_key| *unknown_key != key);
+            }
+        }
+        let rx_carrier = &mut self.rx.0;
+        let flag = |key: &str| parameters.get(key).map(|value| *value != 0.0);
+        if let Some(is_stationary) = flag("rx.is_stationary") {
+            rx_carrier.is_stationary = is_stationary;
+        }
+        if let Some(fixed_pixel_aspect_ratio) = flag("rx.fixed_pixel_aspect_ratio") {
+            rx_carrier.fixed_pixel_aspect_ratio = fixed_pixel_aspect_ratio;
+        }
+        if let Some(is_ground) = flag("rx.is_ground_resolution") {
+            rx_carrier.pixel_resolution = if is_ground { PixelResolution::Ground } else { PixelResolution::Slant };
+        }
+        if let Some(is_exact_gradient) = flag("rx.is_exact_gradient_resolution") {
+            rx_carrier.resolution_formula = if is_exact_gradient { ResolutionFormula::ExactGradient } else { ResolutionFormula::Bisector };
+        }
+        let flag_keys = ["rx.is_stationary", "rx.fixed_pixel_aspect_ratio", "rx.is_ground_resolution", "rx.is_exact_gradient_resolution"];
+        unknown_keys.retain(|key| !flag_keys.contains(&key.as_str()));
+        unknown_keys
+    }
+}
+
+/// Gets the numeric parameters of a carrier, its antenna and its beam, keyed
+/// by `prefix`.
+fn carrier_numbers<'a>(
+    prefix: &str,
+    carrier: &'a mut CarrierState,
+    antenna: &'a mut AntennaState,
+    beam: &'a mut AntennaBeamState,
+) -> Vec<(String, &'a mut f64)> {
+    [
+        ("heading_deg", &mut carrier.heading_deg),
+        ("elevation_deg", &mut carrier.elevation_deg),
+        ("bank_deg", &mut carrier.bank_deg),
+        ("height_m", &mut carrier.height_m),
+        ("velocity_mps", &mut carrier.velocity_mps),
+        ("antenna_heading_deg", &mut antenna.heading_deg),
+        ("antenna_elevation_deg", &mut antenna.elevation_deg),
+        ("antenna_bank_deg", &mut antenna.bank_deg),
+        ("lever_arm_forward_m", &mut antenna.lever_arm_m.x),
+        ("lever_arm_right_m", &mut antenna.lever_arm_m.y),
+        ("lever_arm_down_m", &mut antenna.lever_arm_m.z),
+        ("elevation_beam_width_deg", &mut beam.elevation_beam_width_deg),
+        ("azimuth_beam_width_deg", &mut beam.azimuth_beam_width_deg),
+        ("one_way_gain_dbi", &mut beam.one_way_gain_dbi),
+    ]
+    .into_iter()
+    .map(|(name, value)| (format!("{prefix}.{name}"), value))
+    .collect()
+}
+
 
 /// Iso-range ellipsoid marker component
 #[derive(Component)]
@@ -223,6 +370,30 @@ pub struct IsoRangeEllipsoid;
 #[derive(Component)]
 pub struct IsoRangeDopplerPlane;
 
+/// Resource to keep the carrier input limits
+#[derive(Resource)]
+#[derive(Default)]
+pub struct CarrierLimitsState {
+    pub inner: CarrierLimits,
+    /// Invalid startup configuration, reported once by a toast
+    pub warning: Option<String>,
+}
+
+impl CarrierLimitsState {
+    /// Reads the limits from the environment variables, the defaults being kept
+    /// when they are invalid.
+    pub fn from_env() -> Self {
+        match CarrierLimits::from_env() {
+            Ok(inner) => Self { inner, warning: None },
+            Err(error) => {
+                let warning = format!("{error}: default carrier limits used");
+                warn!("{warning}");
+                Self { inner: CarrierLimits::default(), warning: Some(warning) }
+            }
+        }
+    }
+}
+
 /// Resource to keep state of BSAR system
 #[derive(Resource)]
 #[derive(Default)]
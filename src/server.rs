@@ -0,0 +1,276 @@
+//! Compute endpoint: the BSAR infos and footprints of a scenario over HTTP.
+//!
+//! `bsargeom --serve [address]` (default `127.0.0.1:8080`) answers
+//! `POST /compute`, whose body is a scenario JSON object (see
+//! [`crate::scenario`]) with an optional `"footprint_model": "analytic"` member
+//! approximating the footprints by their analytic ellipses, with
+//! `{"infos": {...}, "tx_footprint": {...}, "rx_footprint": {...}, "unknown_parameters": [...]}`:
+//! the infos keyed as the columns of [`BsarInfos::CSV_HEADER`], the footprint
+//! polygons as ENU points in meters, invalid values being `null`. Requests are
+//! served one at a time, each scenario in a fresh headless App.
+
+use std::{
+    io::{Read, Write},
+    net::{TcpListener, TcpStream},
+    time::Duration,
+};
+
+use crate::{
+    batch::{evaluate, Evaluation},
+    bsar::BsarInfos,
+    entities::{AntennaBeamFootprintState, FootprintModel},
+    json::JsonValue,
+    scenario::Scenario,
+};
+
+/// Command line flag starting the compute endpoint
+pub const SERVE_FLAG: &str = "--serve";
+/// Address listened to without address argument: local connections only
+const DEFAULT_ADDRESS: &str = "127.0.0.1:8080";
+/// Largest accepted request head and body
+const MAX_HEAD_BYTES: usize = 16 * 1024;
+const MAX_BODY_BYTES: usize = 1024 * 1024;
+
+/// An HTTP request read from a connection.
+#[derive(Debug, PartialEq)]
+struct Request {
+    method: String,
+    path: String,
+    body: String,
+}
+
+/// An HTTP response with a JSON body.
+#[derive(Debug)]
+struct Response {
+    status: u16,
+    body: String,
+}
+
+impl Response {
+    fn json(status: u16, json: &JsonValue) -> Self {
+        Self { status, body: json.to_string() }
+    }
+
+    fn error(status: u16, error: &str) -> Self {
+        Self::json(status, &JsonValue::Object(vec![("error".to_string(), JsonValue::String(error.to_string()))]))
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let reason = match self.status {
+            200 => "OK",
+            204 => "No Content",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            413 => "Payload Too Large",
+            _ => "Error",
+        };
+        // Browsers may call the endpoint from any page
+        format!(
+            "HTTP/1.1 {} {reason}\r\n\
+             Content-Type: application/json\r\n\
+             Content-Length: {}\r\n\
+             Access-Control-Allow-Origin: *\r\n\
+             Access-Control-Allow-Methods: POST, OPTIONS\r\n\
+             Access-Control-Allow-Headers: Content-Type\r\n\
+             Connection: close\r\n\r\n{}",
+            self.status,
+            self.body.len(),
+            self.body
+        )
+        .into_bytes()
+    }
+}
+
+/// Serves the compute endpoint from the arguments following [`SERVE_FLAG`],
+/// returning the process exit code once the address cannot be listened to.
+pub fn run(args: &[String]) -> i32 {
+    let address = args.first().map_or(DEFAULT_ADDRESS, String::as_str);
+    let listener = match TcpListener::bind(address) {
+        Ok(listener) => listener,
+        Err(error) => {
+            eprintln!("{address}: {error}");
+            return 1;
+        }
+    };
+    eprintln!("Serving POST /compute on http://{address}");
+    for stream in listener.incoming() {
+        let result = stream.and_then(|mut stream| serve(&mut stream));
+        if let Err(error) = result {
+            eprintln!("{error}");
+        }
+    }
+    0
+}
+
+/// Answers the request of a connection.
+fn serve(stream: &mut TcpStream) -> std::io::Result<()> {
+    stream.set_read_timeout(Some(Duration::from_secs(10)))?;
+    let response = match read_request(stream) {
+        Ok(request) => respond(&request),
+        Err((status, error)) => Response::error(status, &error),
+    };
+    stream.write_all(&response.to_bytes())
+}
+
+/// Reads a request, failing with the status and the error to answer.
+fn read_request(stream: &mut impl Read) -> Result<Request, (u16, String)> {
+    let mut bytes = Vec::new();
+    let mut buffer = [0u8; 4096];
+    // Head, up to the blank line
+    let head_end = loop {
+        if let Some(i) = bytes.windows(4).position(|window| window == b"\r\n\r\n") {
+            break i;
+        }
+        if bytes.len() > MAX_HEAD_BYTES {
+            return Err((413, "request head too large".to_string()));
+        }
+        let n = stream.read(&mut buffer).map_err(|error| (400, error.to_string()))?;
+        if n == 0 {
+            return Err((400, "incomplete request".to_string()));
+        }
+        bytes.extend_from_slice(&buffer[..n]);
+    };
+    let head = String::from_utf8_lossy(&bytes[..head_end]).into_owned();
+    let (method, path, content_length) = parse_head(&head).map_err(|error| (400, error))?;
+    if content_length > MAX_BODY_BYTES {
+        return Err((413, format!("body larger than {MAX_BODY_BYTES} bytes")));
+    }
+    let mut body = bytes.split_off(head_end + 4);
+    while body.len() < content_length {
+        let n = stream.read(&mut buffer).map_err(|error| (400, error.to_string()))?;
+        if n == 0 {
+            return Err((400, "incomplete body".to_string()));
+        }
+        body.extend_from_slice(&buffer[..n]);
+    }
+    body.truncate(content_length);
+    let body = String::from_utf8(body).map_err(|_| (400, "body is not UTF-8".to_string()))?;
+    Ok(Request { method, path, body })
+}
+
+/// Gets the method, the path (without query) and the body length of a
+/// request head.
+fn parse_head(head: &str) -> Result<(String, String, usize), String> {
+    let mut lines = head.lines();
+    let mut request_line = lines.next().unwrap_or_default().split_whitespace();
+    let (Some(method), Some(target)) = (request_line.next(), request_line.next()) else {
+        return Err("invalid request line".to_string());
+    };
+    let path = target.split_once('?').map_or(target, |(path, _)| path);
+    let mut content_length = 0;
+    for (name, value) in lines.filter_map(|line| line.split_once(':')) {
+        if name.trim().eq_ignore_ascii_case("content-length") {
+            content_length = value.trim().parse().map_err(|_| "invalid Content-Length".to_string())?;
+        }
+    }
+    Ok((method.to_string(), path.to_string(), content_length))
+}
+
+fn respond(request: &Request) -> Response {
+    match (request.method.as_str(), request.path.as_str()) {
+        ("OPTIONS", _) => Response { status: 204, body: String::new() },
+        ("POST", "/compute") => {
+            let scenario = JsonValue::parse(&request.body)
+                .map_err(|error| format!("invalid JSON: {error}"))
+                .and_then(compute_request);
+            match scenario {
+                Ok((scenario, footprint_model)) => Response::json(200, &evaluation_json(&evaluate(&scenario, footprint_model))),
+                Err(error) => Response::error(400, &error),
+            }
+        }
+        (_, "/compute") => Response::error(405, "use POST"),
+        _ => Response::error(404, "the endpoint is POST /compute"),
+    }
+}
+
+/// Gets the scenario of a compute request and its footprint model, numeric
+/// by default.
+fn compute_request(mut json: JsonValue) -> Result<(Scenario, FootprintModel), String> {
+    let mut footprint_model = FootprintModel::Numeric;
+    if let JsonValue::Object(members) = &mut json
+        && let Some(i) = members.iter().position(|(name, _)| name == "footprint_model") {
+        footprint_model = match members.remove(i).1 {
+            JsonValue::String(model) if model == "numeric" => FootprintModel::Numeric,
+            JsonValue::String(model) if model == "analytic" => FootprintModel::AnalyticEllipse,
+            _ => return Err("footprint_model: \"numeric\" or \"analytic\"".to_string()),
+        };
+    }
+    Ok((Scenario::from_json(&json)?, footprint_model))
+}
+
+/// Gets the JSON answer of an evaluation.
+fn evaluation_json(evaluation: &Evaluation) -> JsonValue {
+    let infos = BsarInfos::CSV_HEADER.split(',')
+        .zip(evaluation.infos.csv_values())
+        .map(|(name, value)| (name.to_string(), JsonValue::Number(value)))
+        .collect();
+    JsonValue::Object(vec![
+        ("infos".to_string(), JsonValue::Object(infos)),
+        ("tx_footprint".to_string(), footprint_json(&evaluation.tx_footprint)),
+        ("rx_footprint".to_string(), footprint_json(&evaluation.rx_footprint)),
+        (
+            "unknown_parameters".to_string(),
+            JsonValue::Array(evaluation.unknown_keys.iter().cloned().map(JsonValue::String).collect())
+        ),
+    ])
+}
+
+/// Gets a footprint polygon (ENU, meters) and its figures.
+fn footprint_json(footprint: &AntennaBeamFootprintState) -> JsonValue {
+    // World frame is Y-up: x = North, y = Up, z = East
+    let points = footprint.points.iter()
+        .map(|point| JsonValue::numbers([point.z, point.x, point.y]))
+        .collect();
+    let figures = [
+        ("range_min_m", footprint.range_min_m),
+        ("range_center_m", footprint.range_center_m),
+        ("range_max_m", footprint.range_max_m),
+        ("local_incidence_center_deg", footprint.loc_incidence_center_deg),
+        ("ground_range_swath_m", footprint.ground_range_swath_m),
+        ("area_m2", footprint.area_m2),
+    ];
+    let mut members = vec![("points_enu_m".to_string(), JsonValue::Array(points))];
+    members.extend(figures.map(|(name, value)| (name.to_string(), JsonValue::Number(value))));
+    JsonValue::Object(members)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(method: &str, path: &str, body: &str) -> Request {
+        Request { method: method.to_string(), path: path.to_string(), body: body.to_string() }
+    }
+
+    #[test]
+    fn requests_are_read_from_the_head() {
+        let mut bytes: &[u8] = b"POST /compute?v=1 HTTP/1.1\r\nHost: localhost\r\ncontent-length: 2\r\n\r\n{}";
+        assert_eq!(read_request(&mut bytes), Ok(request("POST", "/compute", "{}")));
+        let mut bytes: &[u8] = b"POST /compute HTTP/1.1\r\nContent-Length: 10\r\n\r\n{}";
+        assert_eq!(read_request(&mut bytes), Err((400, "incomplete body".to_string())));
+        assert!(parse_head("GET").is_err());
+    }
+
+    #[test]
+    fn compute_answers_infos_and_footprints() {
+        assert_eq!(respond(&request("GET", "/", "")).status, 404);
+        assert_eq!(respond(&request("GET", "/compute", "")).status, 405);
+        assert_eq!(respond(&request("POST", "/compute", "{")).status, 400);
+
+        let response = respond(&request("POST", "/compute", r#"{"parameters": {"tx.height_m": 4000}}"#));
+        assert_eq!(response.status, 200, "{}", response.body);
+        let json = JsonValue::parse(&response.body).unwrap();
+        let infos = json.get("infos").unwrap();
+        assert!(matches!(infos.get("nesz_db"), Some(JsonValue::Number(_))));
+        let Some(JsonValue::Array(points)) = json.get("tx_footprint").and_then(|footprint| footprint.get("points_enu_m")) else {
+            panic!("no Tx footprint points");
+        };
+        assert!(!points.is_empty());
+        assert_eq!(json.get("unknown_parameters"), Some(&JsonValue::Array(Vec::new())));
+
+        let response = respond(&request("POST", "/compute", r#"{"parameters": {}, "footprint_model": "analytic"}"#));
+        assert_eq!(response.status, 200, "{}", response.body);
+        assert_eq!(respond(&request("POST", "/compute", r#"{"footprint_model": "exact"}"#)).status, 400);
+    }
+}
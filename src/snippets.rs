@@ -0,0 +1,222 @@
+//! Named parameter sets of one subsystem, reusable across scenarios.
+//!
+//! A snippet holds the scenario parameters of the Tx system (waveform and
+//! power), of the Rx system (noise and processing) or of an antenna
+//! (orientation, lever arm and beam), keyed without their carrier prefix: an
+//! antenna snippet applies to either carrier. The snippets are stored beside
+//! the preferences, as sections headed by `[block] name` holding
+//! `key = value` lines:
+//!
+//! ```text
+//! [tx_system] X-band 800 MHz chirp
+//! center_frequency_ghz = 10
+//! bandwidth_mhz = 800
+//! ```
+//!
+//! Unknown blocks and parameters are skipped, as invalid values.
+
+use std::collections::BTreeMap;
+
+use crate::preferences::{read_config_file, write_config_file};
+
+/// File name, or local storage key on the web
+const SNIPPETS_FILE_NAME: &str = "snippets.txt";
+
+/// Subsystem of a snippet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnippetBlock {
+    TxSystem,
+    RxSystem,
+    Antenna,
+}
+
+impl SnippetBlock {
+    pub const ALL: [Self; 3] = [Self::TxSystem, Self::RxSystem, Self::Antenna];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::TxSystem => "Tx system",
+            Self::RxSystem => "Rx system",
+            Self::Antenna => "Antenna",
+        }
+    }
+
+    /// Gets the header key of the block in the snippets file.
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::TxSystem => "tx_system",
+            Self::RxSystem => "rx_system",
+            Self::Antenna => "antenna",
+        }
+    }
+
+    /// Gets the scenario parameters of the block, without their carrier
+    /// prefix.
+    pub fn parameter_names(self) -> &'static [&'static str] {
+        match self {
+            Self::TxSystem => &[
+                "center_frequency_ghz", "bandwidth_mhz", "pulse_duration_us", "prf_hz", "peak_power_w", "loss_factor_db",
+            ],
+            Self::RxSystem => &[
+                "noise_temperature_k", "noise_factor_db", "noise_bandwidth_mhz", "range_oversampling",
+                "integration_time_s", "fixed_pixel_aspect_ratio", "pixel_aspect_ratio", "is_ground_resolution",
+                "is_exact_gradient_resolution",
+            ],
+            Self::Antenna => &[
+                "antenna_heading_deg", "antenna_elevation_deg", "antenna_bank_deg",
+                "lever_arm_forward_m", "lever_arm_right_m", "lever_arm_down_m",
+                "elevation_beam_width_deg", "azimuth_beam_width_deg", "one_way_gain_dbi",
+            ],
+        }
+    }
+
+    /// Gets the carrier prefix of the system blocks, `None` for the antenna
+    /// block, applying to either carrier.
+    pub fn carrier_prefix(self) -> Option<&'static str> {
+        match self {
+            Self::TxSystem => Some("tx"),
+            Self::RxSystem => Some("rx"),
+            Self::Antenna => None,
+        }
+    }
+}
+
+/// Named parameter set of a subsystem.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Snippet {
+    pub name: String,
+    pub block: SnippetBlock,
+    /// Parameters keyed without their carrier prefix (`bandwidth_mhz`)
+    pub parameters: BTreeMap<String, f64>,
+}
+
+impl Snippet {
+    /// Takes the parameters of `block` from the scenario parameters of the
+    /// carrier `prefix` (`tx` or `rx`).
+    pub fn capture(name: &str, block: SnippetBlock, prefix: &str, scenario_parameters: &BTreeMap<String, f64>) -> Self {
+        let parameters = block.parameter_names().iter()
+            .filter_map(|name| {
+                let value = scenario_parameters.get(&format!("{prefix}.{name}"))?;
+                Some((name.to_string(), *value))
+            })
+            .collect();
+        Self { name: name.trim().to_string(), block, parameters }
+    }
+
+    /// Gets the scenario parameters of the snippet for the carrier `prefix`,
+    /// set by `ScenarioStates::apply`.
+    pub fn scenario_parameters(&self, prefix: &str) -> BTreeMap<String, f64> {
+        self.parameters.iter()
+            .map(|(name, value)| (format!("{prefix}.{name}"), *value))
+            .collect()
+    }
+}
+
+/// Snippets of the user, in order of creation.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Snippets {
+    pub snippets: Vec<Snippet>,
+}
+
+impl Snippets {
+    /// Reads the snippets from their text, skipping the unknown blocks and the
+    /// unknown or invalid parameters.
+    pub fn from_text(text: &str) -> Self {
+        let mut snippets: Vec<Snippet> = Vec::new();
+        // Parameters of an unknown block are skipped up to the next header
+        let mut is_known_block = false;
+        for line in text.lines().map(str::trim) {
+            if let Some(header) = line.strip_prefix('[') {
+                let (key, name) = header.split_once(']').unwrap_or((header, ""));
+                let block = SnippetBlock::ALL.into_iter().find(|block| block.key() == key.trim());
+                is_known_block = block.is_some();
+                if let Some(block) = block {
+                    snippets.push(Snippet { name: name.trim().to_string(), block, parameters: BTreeMap::new() });
+                }
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let Some(snippet) = snippets.last_mut().filter(|_| is_known_block) else {
+                continue;
+            };
+            let key = key.trim();
+            if let Ok(value) = value.trim().parse::<f64>()
+                && value.is_finite()
+                && snippet.block.parameter_names().contains(&key)
+            {
+                snippet.parameters.insert(key.to_string(), value);
+            }
+        }
+        Self { snippets }
+    }
+
+    /// Gets the text of the snippets, read back by [`Self::from_text`].
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        for snippet in self.snippets.iter() {
+            text.push_str(&format!("[{}] {}\n", snippet.block.key(), snippet.name));
+            for (key, value) in snippet.parameters.iter() {
+                text.push_str(&format!("{key} = {value}\n"));
+            }
+        }
+        text
+    }
+
+    /// Adds a snippet, replacing the one of the same block and name.
+    pub fn insert(&mut self, snippet: Snippet) {
+        match self.snippets.iter_mut().find(|other| other.block == snippet.block && other.name == snippet.name) {
+            Some(other) => *other = snippet,
+            None => self.snippets.push(snippet),
+        }
+    }
+
+    /// Loads the saved snippets, none without file.
+    pub fn load() -> Self {
+        read_config_file(SNIPPETS_FILE_NAME).map_or_else(Self::default, |text| Self::from_text(&text))
+    }
+
+    /// Saves the snippets.
+    pub fn save(&self) -> Result<(), String> {
+        write_config_file(SNIPPETS_FILE_NAME, &self.to_text())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snippets_text_roundtrip() {
+        let scenario_parameters = BTreeMap::from([
+            ("tx.center_frequency_ghz".to_string(), 9.65),
+            ("tx.bandwidth_mhz".to_string(), 800.0),
+            ("tx.height_m".to_string(), 3000.0),
+            ("rx.one_way_gain_dbi".to_string(), 16.0),
+            ("rx.lever_arm_down_m".to_string(), -0.5),
+        ]);
+        let tx_system = Snippet::capture(" X-band 800 MHz chirp ", SnippetBlock::TxSystem, "tx", &scenario_parameters);
+        assert_eq!(tx_system.name, "X-band 800 MHz chirp");
+        // The carrier parameters are not of the block
+        assert_eq!(tx_system.parameters.len(), 2);
+        let antenna = Snippet::capture("Horn", SnippetBlock::Antenna, "rx", &scenario_parameters);
+        assert_eq!(antenna.scenario_parameters("tx").get("tx.lever_arm_down_m"), Some(&-0.5));
+        let snippets = Snippets { snippets: vec![tx_system, antenna] };
+        assert_eq!(Snippets::from_text(&snippets.to_text()), snippets);
+    }
+
+    #[test]
+    fn unknown_snippets_are_skipped() {
+        let text = "bandwidth_mhz = 1\n[radar] Old\nbandwidth_mhz = 2\n[tx_system] Ku\nbandwidth_mhz = 3\nheight_m = 4\nprf_hz = fast\n";
+        let snippets = Snippets::from_text(text);
+        assert_eq!(snippets.snippets.len(), 1);
+        assert_eq!(snippets.snippets[0].parameters, BTreeMap::from([("bandwidth_mhz".to_string(), 3.0)]));
+        // Saved again under the same name: replaced
+        let mut snippets = snippets;
+        snippets.insert(Snippet { name: "Ku".to_string(), block: SnippetBlock::TxSystem, parameters: BTreeMap::new() });
+        snippets.insert(Snippet { name: "Ku".to_string(), block: SnippetBlock::Antenna, parameters: BTreeMap::new() });
+        assert_eq!(snippets.snippets.len(), 2);
+        assert!(snippets.snippets[0].parameters.is_empty());
+    }
+}
@@ -0,0 +1,190 @@
+//! Squinted geometry tuning towards a target Doppler centroid.
+//!
+//! The carriers antenna boresights always intersect the scene center: turning
+//! an antenna in heading moves its carrier around the scene center, and so
+//! changes its contribution `v·u / λ` to the Doppler centroid at scene center,
+//! `u` being the unit vector from the carrier to the scene center.
+//!
+//! The antenna squint angle itself is reported with a configurable convention,
+//! the communities using conflicting ones: from broadside or from the velocity
+//! vector, positive forward or backward.
+
+use bevy::math::DVec3;
+
+/// Headings sampled over the bounds to bracket the solutions
+const HEADING_SAMPLES: usize = 721;
+/// Bisection iterations refining a bracketed solution
+const BISECTION_ITERATIONS: usize = 60;
+
+/// Result of an antenna heading tuning.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HeadingTuning {
+    pub heading_deg: f64,
+    /// Doppler frequency in Hz reached at `heading_deg`
+    pub doppler_hz: f64,
+    /// `false` when the target is out of reach within the bounds, `heading_deg`
+    /// being then the closest heading
+    pub is_reached: bool,
+}
+
+/// Gets the Doppler contribution in Hz at the scene center (origin) of a
+/// carrier at `position_m` moving at `velocity_mps` (World frame, Z-up).
+pub fn carrier_doppler_hz(lem: f64, position_m: &DVec3, velocity_mps: &DVec3) -> f64 {
+    velocity_mps.dot(-position_m.normalize_or_zero()) / lem
+}
+
+/// Finds the heading in degrees within `bounds_deg` at which `doppler_hz`
+/// reaches `target_hz`, the closest solution to `current_deg` when several
+/// exist.
+///
+/// Returns `None` when the bounds are empty or `doppler_hz` is nowhere finite.
+pub fn tune_heading(
+    doppler_hz: impl Fn(f64) -> f64,
+    target_hz: f64,
+    current_deg: f64,
+    bounds_deg: (f64, f64),
+) -> Option<HeadingTuning> {
+    let (min_deg, max_deg) = bounds_deg;
+    if min_deg > max_deg || min_deg.is_nan() || max_deg.is_nan() {
+        return None;
+    }
+    let samples: Vec<(f64, f64)> = (0..HEADING_SAMPLES)
+        .map(|i| min_deg + (max_deg - min_deg) * i as f64 / (HEADING_SAMPLES - 1) as f64)
+        .map(|heading_deg| (heading_deg, doppler_hz(heading_deg) - target_hz))
+        .filter(|(_, error_hz)| error_hz.is_finite())
+        .collect();
+    // Sign changes between consecutive samples, closest to the current heading first
+    let bracket = samples.windows(2)
+        .filter(|pair| pair[0].1 == 0.0 || pair[0].1.signum() != pair[1].1.signum())
+        .min_by(|a, b| {
+            let distance = |pair: &[(f64, f64)]| (0.5 * (pair[0].0 + pair[1].0) - current_deg).abs();
+            distance(a).total_cmp(&distance(b))
+        });
+    if let Some(pair) = bracket {
+        let ((mut low_deg, mut low_hz), (mut high_deg, _)) = (pair[0], pair[1]);
+        if low_hz == 0.0 {
+            high_deg = low_deg;
+        }
+        for _ in 0..BISECTION_ITERATIONS {
+            let middle_deg = 0.5 * (low_deg + high_deg);
+            let middle_hz = doppler_hz(middle_deg) - target_hz;
+            if middle_hz.signum() == low_hz.signum() {
+                (low_deg, low_hz) = (middle_deg, middle_hz);
+            } else {
+                high_deg = middle_deg;
+            }
+        }
+        let heading_deg = 0.5 * (low_deg + high_deg);
+        Some(HeadingTuning { heading_deg, doppler_hz: doppler_hz(heading_deg), is_reached: true })
+    } else {
+        // Out of reach: the closest sample
+        samples.iter()
+            .min_by(|a, b| a.1.abs().total_cmp(&b.1.abs()))
+            .map(|&(heading_deg, error_hz)| HeadingTuning {
+                heading_deg,
+                doppler_hz: error_hz + target_hz,
+                is_reached: false,
+            })
+    }
+}
+
+/// Reference direction of the antenna squint angle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SquintReference {
+    /// Angle from the broadside (zero-Doppler) plane, 0° at broadside
+    #[default]
+    Broadside,
+    /// Angle from the velocity vector, 90° at broadside, unsigned
+    VelocityVector,
+}
+
+/// Sign of the antenna squint angle from broadside.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SquintSign {
+    /// Positive when the beam looks forward, along the velocity vector
+    #[default]
+    ForwardPositive,
+    BackwardPositive,
+}
+
+/// Convention of the antenna squint angle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SquintConvention {
+    pub reference: SquintReference,
+    /// Ignored from the velocity vector, the angle being unsigned
+    pub sign: SquintSign,
+}
+
+impl SquintConvention {
+    /// Gets the squint angle in degrees of the antenna beam along the unit
+    /// vector `boresight` of a carrier moving at `velocity_mps` (same frame),
+    /// the beam being taken at broadside without velocity.
+    pub fn squint_deg(&self, boresight: &DVec3, velocity_mps: &DVec3) -> f64 {
+        // Cosine of the angle between the boresight and the velocity vector
+        // note: the dot product of two unit vectors can exceed ±1 by a few ulps
+        let cos_angle = velocity_mps.try_normalize()
+            .map_or(0.0, |direction| boresight.dot(direction).clamp(-1.0, 1.0));
+        match (self.reference, self.sign) {
+            (SquintReference::VelocityVector, _) => cos_angle.acos().to_degrees(),
+            (SquintReference::Broadside, SquintSign::ForwardPositive) => cos_angle.asin().to_degrees(),
+            (SquintReference::Broadside, SquintSign::BackwardPositive) => -cos_angle.asin().to_degrees(),
+        }
+    }
+
+    /// Describes the convention, e.g. "from broadside, positive forward".
+    pub fn description(&self) -> &'static str {
+        match (self.reference, self.sign) {
+            (SquintReference::VelocityVector, _) => "from the velocity vector (90° at broadside)",
+            (SquintReference::Broadside, SquintSign::ForwardPositive) => "from broadside, positive forward",
+            (SquintReference::Broadside, SquintSign::BackwardPositive) => "from broadside, positive backward",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tunes_towards_the_closest_solution() {
+        let doppler_hz = |heading_deg: f64| 100.0 * heading_deg.to_radians().sin();
+        // sin = 0.5 at 30° and 150°: the closest to 170° is 150°
+        let tuning = tune_heading(doppler_hz, 50.0, 170.0, (-180.0, 180.0)).unwrap();
+        assert!(tuning.is_reached);
+        assert!((tuning.heading_deg - 150.0).abs() < 1e-9, "{}", tuning.heading_deg);
+        assert!((tuning.doppler_hz - 50.0).abs() < 1e-9);
+        let tuning = tune_heading(doppler_hz, 50.0, 170.0, (-180.0, 90.0)).unwrap();
+        assert!((tuning.heading_deg - 30.0).abs() < 1e-9, "{}", tuning.heading_deg);
+        // Out of reach: the maximum
+        let tuning = tune_heading(doppler_hz, 200.0, 0.0, (-180.0, 180.0)).unwrap();
+        assert!(!tuning.is_reached);
+        assert!((tuning.heading_deg - 90.0).abs() < 0.5);
+        assert!(tune_heading(doppler_hz, 0.0, 0.0, (10.0, -10.0)).is_none());
+    }
+
+    #[test]
+    fn carrier_doppler_at_scene_center() {
+        // Flying towards the scene center at 100 m/s, λ = 3 cm
+        let position_m = DVec3::new(-4000.0, 0.0, 3000.0);
+        let velocity_mps = DVec3::new(100.0, 0.0, 0.0);
+        assert!((carrier_doppler_hz(0.03, &position_m, &velocity_mps) - 0.8 * 100.0 / 0.03).abs() < 1e-9);
+        // Broadside: zero Doppler
+        assert_eq!(carrier_doppler_hz(0.03, &DVec3::new(0.0, -4000.0, 3000.0), &velocity_mps), 0.0);
+    }
+
+    #[test]
+    fn squint_conventions() {
+        // Beam looking 30° forward of broadside, down to the right of a carrier flying East
+        let boresight = DVec3::new(0.5, -0.75f64.sqrt() * 0.6, -0.75f64.sqrt() * 0.8);
+        let velocity_mps = DVec3::new(200.0, 0.0, 0.0);
+        let forward = SquintConvention::default();
+        assert!((forward.squint_deg(&boresight, &velocity_mps) - 30.0).abs() < 1e-9);
+        let backward = SquintConvention { sign: SquintSign::BackwardPositive, ..forward };
+        assert!((backward.squint_deg(&boresight, &velocity_mps) + 30.0).abs() < 1e-9);
+        let from_velocity = SquintConvention { reference: SquintReference::VelocityVector, ..backward };
+        assert!((from_velocity.squint_deg(&boresight, &velocity_mps) - 60.0).abs() < 1e-9);
+        // Without velocity: broadside
+        assert_eq!(forward.squint_deg(&boresight, &DVec3::ZERO), 0.0);
+        assert_eq!(from_velocity.squint_deg(&boresight, &DVec3::ZERO), 90.0);
+    }
+}
@@ -0,0 +1,197 @@
+//! Electronic beam steering schedules and simultaneous beams.
+//!
+//! An electronically scanned antenna hops its beam between pointing offsets
+//! (e.g. elevation sub-swaths) without moving: the schedule cycles through
+//! beam positions, each one held during its dwell time. A digital beamforming
+//! receiver rather forms several beams at once, at fixed offsets. The offsets
+//! are added to the mechanical antenna pointing, whose boresight keeps
+//! intersecting the scene center.
+
+use crate::entities::AntennaState;
+
+/// Reads a table whose lines hold the space, comma or semicolon separated
+/// values named by `columns`, `#` starting a comment.
+fn read_table(text: &str, columns: &str) -> Result<Vec<Vec<f64>>, String> {
+    let column_count = columns.split(' ').count();
+    text.lines()
+        .enumerate()
+        .map(|(i, line)| (i, line.split('#').next().unwrap_or_default().trim()))
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(i, line)| {
+            let values = line.split([' ', '\t', ',', ';'])
+                .filter(|value| !value.is_empty())
+                .map(str::parse::<f64>)
+                .collect::<Result<Vec<_>, _>>();
+            match values {
+                Ok(values) if values.len() == column_count => Ok(values),
+                _ => Err(format!("Line {}: expected \"{columns}\"", i + 1)),
+            }
+        })
+        .collect()
+}
+
+/// Checks that pointing offsets in degrees lie within -90° - 90°.
+fn check_offsets(elevation_offset_deg: f64, azimuth_offset_deg: f64) -> Result<(), String> {
+    if (-90.0..=90.0).contains(&elevation_offset_deg) && (-90.0..=90.0).contains(&azimuth_offset_deg) {
+        Ok(())
+    } else {
+        Err(format!("Offsets ({elevation_offset_deg}°, {azimuth_offset_deg}°) are not within -90° - 90°"))
+    }
+}
+
+/// Gets the antenna pointing steered by offsets in degrees in elevation and
+/// azimuth (heading).
+pub fn steer(antenna_state: &AntennaState, elevation_offset_deg: f64, azimuth_offset_deg: f64) -> AntennaState {
+    AntennaState {
+        heading_deg: antenna_state.heading_deg + azimuth_offset_deg,
+        elevation_deg: antenna_state.elevation_deg + elevation_offset_deg,
+        ..antenna_state.clone()
+    }
+}
+
+/// One beam position of a steering schedule.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BeamPosition {
+    /// Offsets in degrees added to the antenna elevation and heading
+    pub elevation_offset_deg: f64,
+    pub azimuth_offset_deg: f64,
+    /// Time in seconds spent at this position
+    pub dwell_s: f64,
+}
+
+impl BeamPosition {
+    /// Gets the antenna pointing steered to this position.
+    pub fn steer(&self, antenna_state: &AntennaState) -> AntennaState {
+        steer(antenna_state, self.elevation_offset_deg, self.azimuth_offset_deg)
+    }
+}
+
+/// Cyclic sequence of beam positions, starting at time 0 s.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SteeringSchedule {
+    positions: Vec<BeamPosition>,
+}
+
+impl SteeringSchedule {
+    /// Creates a schedule from its beam positions, in order.
+    pub fn new(positions: Vec<BeamPosition>) -> Result<Self, String> {
+        if positions.is_empty() {
+            return Err("A steering schedule needs at least one beam position".to_string());
+        }
+        if let Some(position) = positions.iter().find(|position| !(position.dwell_s > 0.0 && position.dwell_s.is_finite())) {
+            return Err(format!("Dwell time {} s is not strictly positive", position.dwell_s));
+        }
+        for position in positions.iter() {
+            check_offsets(position.elevation_offset_deg, position.azimuth_offset_deg)?;
+        }
+        Ok(Self { positions })
+    }
+
+    /// Reads a schedule table: one "elevation azimuth dwell" beam position per
+    /// line (offsets in degrees, dwell in seconds), separated by spaces, commas
+    /// or semicolons, `#` starting a comment.
+    pub fn from_table(text: &str) -> Result<Self, String> {
+        let positions = read_table(text, "elevation azimuth dwell")?
+            .into_iter()
+            .map(|values| BeamPosition {
+                elevation_offset_deg: values[0],
+                azimuth_offset_deg: values[1],
+                dwell_s: values[2],
+            })
+            .collect();
+        Self::new(positions)
+    }
+
+    /// Gets the beam positions, in order.
+    #[inline]
+    pub fn positions(&self) -> &[BeamPosition] {
+        &self.positions
+    }
+
+    /// Gets the schedule period in seconds, the sum of the dwell times.
+    pub fn period_s(&self) -> f64 {
+        self.positions.iter().map(|position| position.dwell_s).sum()
+    }
+
+    /// Gets the index of the beam position active at `time_s`, the schedule
+    /// repeating itself (also before 0 s).
+    pub fn index_at(&self, time_s: f64) -> usize {
+        let mut time_s = time_s.rem_euclid(self.period_s());
+        for (i, position) in self.positions.iter().enumerate() {
+            if time_s < position.dwell_s {
+                return i;
+            }
+            time_s -= position.dwell_s;
+        }
+        self.positions.len() - 1 // Rounding at the end of the period
+    }
+}
+
+/// Simultaneous beams formed by one antenna (digital beamforming), as pointing
+/// offsets in degrees from the antenna pointing.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultiBeam {
+    /// (elevation, azimuth) offsets in degrees
+    offsets: Vec<(f64, f64)>,
+}
+
+impl MultiBeam {
+    /// Creates the beams from their (elevation, azimuth) offsets in degrees.
+    pub fn new(offsets: Vec<(f64, f64)>) -> Result<Self, String> {
+        if offsets.is_empty() {
+            return Err("A multi-beam antenna needs at least one beam".to_string());
+        }
+        for &(elevation_offset_deg, azimuth_offset_deg) in offsets.iter() {
+            check_offsets(elevation_offset_deg, azimuth_offset_deg)?;
+        }
+        Ok(Self { offsets })
+    }
+
+    /// Reads a beams table: one "elevation azimuth" offsets pair in degrees per
+    /// line, separated by spaces, commas or semicolons, `#` starting a comment.
+    pub fn from_table(text: &str) -> Result<Self, String> {
+        let offsets = read_table(text, "elevation azimuth")?
+            .into_iter()
+            .map(|values| (values[0], values[1]))
+            .collect();
+        Self::new(offsets)
+    }
+
+    /// Gets the (elevation, azimuth) offsets in degrees of the beams.
+    #[inline]
+    pub fn offsets(&self) -> &[(f64, f64)] {
+        &self.offsets
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cycles_through_the_beam_positions() {
+        let schedule = SteeringSchedule::from_table("# el az dwell\n-2 0 0.5\n0, 0, 1  # Mid swath\n2;1.5;0.5\n").unwrap();
+        assert_eq!(schedule.positions().len(), 3);
+        assert_eq!(schedule.positions()[2], BeamPosition { elevation_offset_deg: 2.0, azimuth_offset_deg: 1.5, dwell_s: 0.5 });
+        assert_eq!(schedule.period_s(), 2.0);
+        assert_eq!(schedule.index_at(0.0), 0);
+        assert_eq!(schedule.index_at(0.5), 1);
+        assert_eq!(schedule.index_at(1.49), 1);
+        assert_eq!(schedule.index_at(1.5), 2);
+        assert_eq!(schedule.index_at(2.1), 0);
+        assert_eq!(schedule.index_at(-0.25), 2);
+        assert!(SteeringSchedule::from_table("1 2").is_err());
+        assert!(SteeringSchedule::from_table("0 0 0").is_err());
+        assert!(SteeringSchedule::from_table("95 0 1").is_err());
+        assert!(SteeringSchedule::from_table("").is_err());
+
+        let beams = MultiBeam::from_table("-3 0\n0 0 # Main beam\n3, 0.5\n").unwrap();
+        assert_eq!(beams.offsets(), &[(-3.0, 0.0), (0.0, 0.0), (3.0, 0.5)]);
+        assert!(MultiBeam::from_table("1 2 3").is_err());
+        assert!(MultiBeam::from_table("0 -95").is_err());
+
+        let antenna = AntennaState { heading_deg: 90.0, elevation_deg: -30.0, bank_deg: 0.0, lever_arm_m: bevy::math::DVec3::ZERO };
+        let steered = schedule.positions()[2].steer(&antenna);
+        assert_eq!((steered.heading_deg, steered.elevation_deg, steered.bank_deg), (91.5, -28.0, 0.0));
+    }
+}
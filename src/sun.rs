@@ -0,0 +1,64 @@
+//! Sun position.
+//!
+//! The sun direction is computed from the low precision solar coordinates of
+//! the Astronomical Almanac (about 0.01° over 1950 - 2050), which is plenty to
+//! light the scene and cast the terrain shadows of an optical context image.
+
+use bevy::math::DVec3;
+
+use crate::{coordinates::LocalCartesian, epoch::UtcTime};
+
+/// Astronomical unit in meters.
+const ASTRONOMICAL_UNIT_M: f64 = 1.495978707e11;
+
+/// Gets the unit vector pointing to the sun in the Earth-fixed (ECEF) frame at `utc`.
+pub fn sun_direction_ecef(utc: &UtcTime) -> DVec3 {
+    let days = utc.days_since_j2000();
+    let mean_longitude_deg = 280.460 + 0.9856474 * days;
+    let mean_anomaly = (357.528 + 0.9856003 * days).to_radians();
+    let ecliptic_longitude = (mean_longitude_deg + 1.915 * mean_anomaly.sin() + 0.020 * (2.0 * mean_anomaly).sin()).to_radians();
+    let obliquity = (23.439 - 4e-7 * days).to_radians();
+    // Equatorial inertial frame, then turned by the Greenwich sidereal time
+    let (sin_longitude, cos_longitude) = ecliptic_longitude.sin_cos();
+    let direction_eci = DVec3::new(cos_longitude, obliquity.cos() * sin_longitude, obliquity.sin() * sin_longitude);
+    let (sin_gmst, cos_gmst) = utc.gmst_deg().to_radians().sin_cos();
+    DVec3::new(
+        cos_gmst * direction_eci.x + sin_gmst * direction_eci.y,
+        -sin_gmst * direction_eci.x + cos_gmst * direction_eci.y,
+        direction_eci.z,
+    )
+}
+
+/// Gets the unit vector pointing to the sun in the ENU frame of `scene` at `utc`.
+pub fn sun_direction_enu(scene: &LocalCartesian, utc: &UtcTime) -> DVec3 {
+    let sun_ecef = ASTRONOMICAL_UNIT_M * sun_direction_ecef(utc);
+    scene.transform_from_cartesian_ecef_point_to_enu_point(&sun_ecef).normalize()
+}
+
+/// Gets the (azimuth, elevation) in degrees of an ENU direction, the azimuth
+/// clockwise from the north.
+pub fn azimuth_elevation_deg(direction: &DVec3) -> (f64, f64) {
+    (
+        direction.x.atan2(direction.y).to_degrees().rem_euclid(360.0),
+        direction.z.atan2(direction.x.hypot(direction.y)).to_degrees(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coordinates::GeographicPoint;
+
+    #[test]
+    fn sun_at_noon() {
+        // Summer solstice, solar noon at the Greenwich meridian (equation of time ~ -1.8 min)
+        let utc = UtcTime::parse("2024-06-20T12:02:00Z").unwrap();
+        let scene = LocalCartesian::from_geographic_point(&GeographicPoint::from_degrees(0.0, 45.0, 0.0));
+        let (azimuth_deg, elevation_deg) = azimuth_elevation_deg(&sun_direction_enu(&scene, &utc));
+        assert!((azimuth_deg - 180.0).abs() < 0.5, "{azimuth_deg}");
+        assert!((elevation_deg - (90.0 - 45.0 + 23.44)).abs() < 0.1, "{elevation_deg}");
+        // Night at the antimeridian
+        let scene = LocalCartesian::from_geographic_point(&GeographicPoint::from_degrees(180.0, 45.0, 0.0));
+        assert!(azimuth_elevation_deg(&sun_direction_enu(&scene, &utc)).1 < -20.0);
+    }
+}
@@ -0,0 +1,419 @@
+//! Terrain shadow and layover prediction.
+//!
+//! The DEM heights are sampled on a square raster centered on the scene center,
+//! relative to the height at the scene center (which is on the ground plane).
+//! For the current bistatic geometry:
+//!
+//! - a cell is in *shadow* when the terrain hides it from the transmitter (not
+//!   illuminated) or from the receiver (not seen);
+//! - a cell is in *layover* when the terrain folds the bistatic range / Doppler
+//!   mapping, i.e. when the Jacobian of the (bistatic range, bistatic range
+//!   rate) image coordinates over the ground changes its sign compared to the
+//!   flat ground. Layover therefore needs moving carriers.
+//!
+//! The terrain can also be lit by the sun (hill shading and cast shadows) as
+//! an optical context image to compare with the radar geometry.
+
+use bevy::math::DVec3;
+
+use crate::{coordinates::LocalCartesian, dem::Dem, horizon::HorizonMask};
+
+/// Shadow overlay color (RGBA).
+const SHADOW_COLOR: [u8; 4] = [20, 20, 30, 200];
+/// Layover overlay color (RGBA).
+const LAYOVER_COLOR: [u8; 4] = [255, 60, 200, 200];
+/// Lift in m of the terrain point above the surface when testing its line of
+/// sight, so that a cell does not shadow itself.
+const LINE_OF_SIGHT_LIFT_M: f64 = 0.5;
+/// Distance in m of the sun "sensor" casting the sun shadows, far enough for
+/// parallel rays over the raster.
+const SUN_DISTANCE_M: f64 = 1e8;
+/// Brightness of the terrain not lit by the sun (sky light), from 0 to 1.
+const SKY_LIGHT: f64 = 0.2;
+
+/// Terrain heights and masks over a `size` × `size` square raster of side
+/// `2 * half_extent_m` centered on the scene center, row-major from the
+/// north-west cell: columns go East, rows go South.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TerrainMasks {
+    half_extent_m: f64,
+    size: usize,
+    /// Heights in m relative to the scene center, NaN outside the DEM
+    heights_m: Vec<f64>,
+    tx_shadow: Vec<bool>,
+    rx_shadow: Vec<bool>,
+    layover: Vec<bool>,
+    /// Unit vector (ENU) pointing to the sun, `None` until lit
+    sun_direction: Option<DVec3>,
+    sun_shadow: Vec<bool>,
+}
+
+impl TerrainMasks {
+    /// Samples the DEM around the origin of `scene`, with empty masks.
+    pub fn new(dem: &Dem, scene: &LocalCartesian, half_extent_m: f64, size: usize) -> Result<Self, String> {
+        let height_at = |east_m: f64, north_m: f64| {
+            let gp = scene.transform_from_enu_point_to_geographic_point(&DVec3::new(east_m, north_m, 0.0));
+            dem.height_m_at(gp.lon_deg(), gp.lat_deg())
+        };
+        let center_height_m = height_at(0.0, 0.0)
+            .ok_or_else(|| "The scene center is outside the DEM (or on a void)".to_string())?;
+        let mut masks = Self::from_heights(half_extent_m, size, vec![f64::NAN; size.max(1) * size.max(1)]);
+        for row in 0..masks.size {
+            for col in 0..masks.size {
+                let (east_m, north_m) = masks.cell_center_m(row, col);
+                masks.heights_m[row * masks.size + col] = height_at(east_m, north_m)
+                    .map_or(f64::NAN, |height_m| height_m - center_height_m);
+            }
+        }
+        Ok(masks)
+    }
+
+    /// Creates the raster from heights in m relative to the scene center, row-major.
+    pub fn from_heights(half_extent_m: f64, size: usize, heights_m: Vec<f64>) -> Self {
+        let size = size.max(1);
+        assert_eq!(heights_m.len(), size * size, "Expected {size} × {size} heights");
+        Self {
+            half_extent_m,
+            size,
+            heights_m,
+            tx_shadow: vec![false; size * size],
+            rx_shadow: vec![false; size * size],
+            layover: vec![false; size * size],
+            sun_direction: None,
+            sun_shadow: vec![false; size * size],
+        }
+    }
+
+    /// Computes the masks for the transmitter and receiver positions and
+    /// velocities, in the scene ENU frame.
+    pub fn update(&mut self, tx: (DVec3, DVec3), rx: (DVec3, DVec3)) {
+        self.tx_shadow = self.shadow_mask(&tx.0);
+        self.rx_shadow = self.shadow_mask(&rx.0);
+        self.layover = self.layover_mask(tx, rx);
+    }
+
+    /// Lights the terrain by the sun in `sun_direction` (ENU unit vector): the
+    /// cells hidden from the sun are in shadow, all of them at night.
+    pub fn update_sun(&mut self, sun_direction: DVec3) {
+        self.sun_shadow = if sun_direction.z > 0.0 {
+            self.shadow_mask(&(SUN_DISTANCE_M * sun_direction))
+        } else {
+            vec![true; self.heights_m.len()]
+        };
+        self.sun_direction = Some(sun_direction);
+    }
+
+    /// Gets the (East, North) coordinates in m of the center of a cell.
+    #[inline]
+    pub fn cell_center_m(&self, row: usize, col: usize) -> (f64, f64) {
+        let cell_m = self.cell_size_m();
+        (
+            -self.half_extent_m + (col as f64 + 0.5) * cell_m,
+            self.half_extent_m - (row as f64 + 0.5) * cell_m,
+        )
+    }
+
+    /// Gets the side of a cell in meters.
+    #[inline]
+    pub fn cell_size_m(&self) -> f64 {
+        2.0 * self.half_extent_m / self.size as f64
+    }
+
+    /// Gets the half side of the raster in meters.
+    #[inline]
+    pub const fn half_extent_m(&self) -> f64 {
+        self.half_extent_m
+    }
+
+    /// Gets the number of cells per side.
+    #[inline]
+    pub const fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Gets the terrain heights in m relative to the scene center, row-major.
+    #[inline]
+    pub fn heights_m(&self) -> &[f64] {
+        &self.heights_m
+    }
+
+    /// Gets the (min, max) terrain heights in m relative to the scene center.
+    pub fn height_range_m(&self) -> (f64, f64) {
+        self.heights_m.iter()
+            .filter(|height_m| height_m.is_finite())
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &height_m| (min.min(height_m), max.max(height_m)))
+    }
+
+    /// Gets the fraction of the cells with terrain heights in shadow (from the
+    /// transmitter, or from the receiver).
+    pub fn shadow_fraction(&self) -> f64 {
+        self.fraction(|i| self.tx_shadow[i] || self.rx_shadow[i])
+    }
+
+    /// Gets the fraction of the cells with terrain heights in sun shadow.
+    pub fn sun_shadow_fraction(&self) -> f64 {
+        self.fraction(|i| self.sun_shadow[i])
+    }
+
+    /// Gets the fraction of the cells with terrain heights in layover.
+    pub fn layover_fraction(&self) -> f64 {
+        self.fraction(|i| self.layover[i])
+    }
+
+    fn fraction(&self, is_set: impl Fn(usize) -> bool) -> f64 {
+        let (set, valid) = (0..self.heights_m.len())
+            .filter(|&i| self.heights_m[i].is_finite())
+            .fold((0usize, 0usize), |(set, valid), i| (set + is_set(i) as usize, valid + 1));
+        if valid > 0 { set as f64 / valid as f64 } else { 0.0 }
+    }
+
+    /// Gets the terrain height in m at (East, North), nearest cell, `None`
+    /// outside the raster or the DEM.
+    fn height_m_at(&self, east_m: f64, north_m: f64) -> Option<f64> {
+        let cell_m = self.cell_size_m();
+        let col = ((east_m + self.half_extent_m) / cell_m).floor();
+        let row = ((self.half_extent_m - north_m) / cell_m).floor();
+        let range = 0.0..self.size as f64;
+        if !range.contains(&col) || !range.contains(&row) {
+            return None;
+        }
+        Some(self.heights_m[row as usize * self.size + col as usize]).filter(|height_m| height_m.is_finite())
+    }
+
+    /// Derives the horizon mask seen from `position` (ENU) from the terrain
+    /// heights, every `azimuth_step_deg`: the highest terrain elevation angle
+    /// along each azimuth over the raster (-90° where the raster is not crossed).
+    pub fn horizon_mask(&self, position: &DVec3, azimuth_step_deg: f64) -> HorizonMask {
+        let cell_m = self.cell_size_m();
+        // Farthest raster cell from the position
+        let max_distance_m = position.truncate().length() + std::f64::consts::SQRT_2 * self.half_extent_m;
+        let num_azimuths = (360.0 / azimuth_step_deg.max(0.1)).round().max(1.0) as usize;
+        let points = (0..num_azimuths)
+            .map(|i| {
+                let azimuth_deg = i as f64 * 360.0 / num_azimuths as f64;
+                let (sin, cos) = azimuth_deg.to_radians().sin_cos();
+                let elevation_deg = (1..)
+                    .map(|step| step as f64 * cell_m)
+                    .take_while(|&distance_m| distance_m <= max_distance_m)
+                    .filter_map(|distance_m| {
+                        self.height_m_at(position.x + distance_m * sin, position.y + distance_m * cos)
+                            .map(|height_m| (height_m - position.z).atan2(distance_m).to_degrees())
+                    })
+                    .fold(-90.0, f64::max);
+                (azimuth_deg, elevation_deg)
+            })
+            .collect();
+        HorizonMask::new(points).unwrap_or_else(|_| unreachable!("Elevations are within -90° - 90°"))
+    }
+
+    /// Cells hidden by the terrain from `sensor`: the line of sight of each
+    /// cell is marched towards the sensor, one cell at a time, until it leaves
+    /// the raster or rises above the highest terrain.
+    fn shadow_mask(&self, sensor: &DVec3) -> Vec<bool> {
+        let cell_m = self.cell_size_m();
+        let (_, max_height_m) = self.height_range_m();
+        let mut mask = vec![false; self.heights_m.len()];
+        for row in 0..self.size {
+            for col in 0..self.size {
+                let height_m = self.heights_m[row * self.size + col];
+                if !height_m.is_finite() {
+                    continue;
+                }
+                let (east_m, north_m) = self.cell_center_m(row, col);
+                let point = DVec3::new(east_m, north_m, height_m + LINE_OF_SIGHT_LIFT_M);
+                let line_of_sight = *sensor - point;
+                let ground_length_m = line_of_sight.truncate().length();
+                if ground_length_m < cell_m {
+                    continue;
+                }
+                let step = line_of_sight * (cell_m / ground_length_m);
+                let mut sample = point;
+                loop {
+                    sample += step;
+                    if sample.z > max_height_m || (sample - point).truncate().length() >= ground_length_m {
+                        break;
+                    }
+                    match self.height_m_at(sample.x, sample.y) {
+                        Some(terrain_m) if terrain_m > sample.z => {
+                            mask[row * self.size + col] = true;
+                            break;
+                        }
+                        Some(_) => {}
+                        None => break,
+                    }
+                }
+            }
+        }
+        mask
+    }
+
+    /// Cells where the terrain folds the (bistatic range, range rate) mapping.
+    fn layover_mask(&self, tx: (DVec3, DVec3), rx: (DVec3, DVec3)) -> Vec<bool> {
+        // Image coordinates of a ground point: bistatic range and range rate
+        let image = |point: DVec3| {
+            let (u_tx, u_rx) = ((point - tx.0).normalize_or_zero(), (point - rx.0).normalize_or_zero());
+            (tx.0.distance(point) + rx.0.distance(point), -tx.1.dot(u_tx) - rx.1.dot(u_rx))
+        };
+        let cell_m = self.cell_size_m();
+        let height = |row: usize, col: usize| self.heights_m[row * self.size + col];
+        // Jacobian determinant of the image coordinates from finite differences
+        // along the rows and columns, with or without the terrain heights
+        let determinant = |row: usize, col: usize, with_terrain: bool| -> Option<f64> {
+            let point = |row: usize, col: usize| {
+                let (east_m, north_m) = self.cell_center_m(row, col);
+                let height_m = if with_terrain { height(row, col) } else { 0.0 };
+                height_m.is_finite().then(|| DVec3::new(east_m, north_m, height_m))
+            };
+            let (col0, col1) = (col.saturating_sub(1), (col + 1).min(self.size - 1));
+            let (row0, row1) = (row.saturating_sub(1), (row + 1).min(self.size - 1));
+            let (east0, east1) = (image(point(row, col0)?), image(point(row, col1)?));
+            let (north0, north1) = (image(point(row1, col)?), image(point(row0, col)?));
+            let (d_east, d_north) = ((col1 - col0) as f64 * cell_m, (row1 - row0) as f64 * cell_m);
+            let (range_e, rate_e) = ((east1.0 - east0.0) / d_east, (east1.1 - east0.1) / d_east);
+            let (range_n, rate_n) = ((north1.0 - north0.0) / d_north, (north1.1 - north0.1) / d_north);
+            Some(range_e * rate_n - range_n * rate_e)
+        };
+        let mut mask = vec![false; self.heights_m.len()];
+        if self.size < 2 {
+            return mask;
+        }
+        for row in 0..self.size {
+            for col in 0..self.size {
+                if let (Some(terrain), Some(flat)) = (determinant(row, col, true), determinant(row, col, false)) {
+                    mask[row * self.size + col] = terrain * flat < 0.0;
+                }
+            }
+        }
+        mask
+    }
+
+    /// Renders the masks as an RGBA8 overlay, row-major: shadow over layover,
+    /// transparent elsewhere.
+    pub fn to_rgba8(&self) -> Vec<u8> {
+        let mut rgba = vec![0u8; 4 * self.heights_m.len()];
+        for (i, pixel) in rgba.chunks_exact_mut(4).enumerate() {
+            if self.tx_shadow[i] || self.rx_shadow[i] {
+                pixel.copy_from_slice(&SHADOW_COLOR);
+            } else if self.layover[i] {
+                pixel.copy_from_slice(&LAYOVER_COLOR);
+            }
+        }
+        rgba
+    }
+
+    /// Renders the terrain lit by the sun as an opaque RGBA8 gray image,
+    /// row-major: Lambertian hill shading from the slopes plus the sky light,
+    /// transparent outside the DEM or until lit.
+    pub fn to_optical_rgba8(&self) -> Vec<u8> {
+        let mut rgba = vec![0u8; 4 * self.heights_m.len()];
+        let Some(sun_direction) = self.sun_direction else {
+            return rgba;
+        };
+        let cell_m = self.cell_size_m();
+        // Neighbors outside the raster or the DEM fall back to the cell height
+        let height = |row: usize, col: usize, default_m: f64| {
+            let height_m = self.heights_m[row * self.size + col];
+            if height_m.is_finite() { height_m } else { default_m }
+        };
+        for (i, pixel) in rgba.chunks_exact_mut(4).enumerate() {
+            let (row, col) = (i / self.size, i % self.size);
+            let height_m = self.heights_m[i];
+            if !height_m.is_finite() {
+                continue;
+            }
+            let (col0, col1) = (col.saturating_sub(1), (col + 1).min(self.size - 1));
+            let (row0, row1) = (row.saturating_sub(1), (row + 1).min(self.size - 1));
+            let slope_east = (height(row, col1, height_m) - height(row, col0, height_m)) / ((col1 - col0).max(1) as f64 * cell_m);
+            let slope_north = (height(row0, col, height_m) - height(row1, col, height_m)) / ((row1 - row0).max(1) as f64 * cell_m);
+            let normal = DVec3::new(-slope_east, -slope_north, 1.0).normalize();
+            let sun_light = if self.sun_shadow[i] { 0.0 } else { normal.dot(sun_direction).max(0.0) };
+            let gray = (255.0 * (SKY_LIGHT + (1.0 - SKY_LIGHT) * sun_light)).round() as u8;
+            pixel.copy_from_slice(&[gray, gray, gray, 255]);
+        }
+        rgba
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// North-South ridge of `height_m` at East = 0 over a ±`half_extent_m` flat raster.
+    fn ridge(half_extent_m: f64, size: usize, height_m: f64, half_width_m: f64) -> TerrainMasks {
+        let mut heights_m = vec![0.0; size * size];
+        let cell_m = 2.0 * half_extent_m / size as f64;
+        for (i, h) in heights_m.iter_mut().enumerate() {
+            let east_m = -half_extent_m + ((i % size) as f64 + 0.5) * cell_m;
+            *h = (height_m * (1.0 - east_m.abs() / half_width_m)).max(0.0);
+        }
+        TerrainMasks::from_heights(half_extent_m, size, heights_m)
+    }
+
+    /// Index of the cell at (East, -10 m).
+    fn at(east_m: f64) -> usize {
+        50 * 100 + ((east_m + 1000.0) / 20.0) as usize
+    }
+
+    #[test]
+    fn ridge_shadows_the_far_side() {
+        let mut masks = ridge(1000.0, 100, 200.0, 200.0);
+        // Monostatic sensor in the West at ~16° elevation, moving North
+        let sensor = (DVec3::new(-10000.0, 0.0, 3000.0), DVec3::new(0.0, 100.0, 0.0));
+        masks.update(sensor, sensor);
+        // The line of sight passes the ridge top ~90 m below it from 300 m
+        // behind, and ~50 m above it from 900 m behind
+        assert!(masks.tx_shadow[at(300.0)]);
+        assert!(masks.rx_shadow[at(300.0)]);
+        assert!(!masks.tx_shadow[at(-300.0)]);
+        assert!(!masks.tx_shadow[at(900.0)]);
+        assert!(masks.shadow_fraction() > 0.0);
+    }
+
+    #[test]
+    fn horizon_mask_from_the_terrain() {
+        let masks = ridge(1000.0, 100, 200.0, 200.0);
+        // Ground receiver 10 m high, 500 m West of the ridge top
+        let mask = masks.horizon_mask(&DVec3::new(-500.0, 0.0, 10.0), 10.0);
+        assert_eq!(mask.points().len(), 36);
+        // The ridge top cells (190 m at ±10 m) are first met 480 m East
+        let east_deg = mask.elevation_deg_at(90.0);
+        assert!((east_deg - 180f64.atan2(480.0).to_degrees()).abs() < 0.01, "{east_deg}");
+        // Flat ground seen 10 m below in the West
+        assert!(mask.elevation_deg_at(270.0) < 0.0);
+    }
+
+    #[test]
+    fn sun_lights_and_shadows_the_terrain() {
+        let mut masks = ridge(1000.0, 100, 200.0, 200.0);
+        assert!(masks.to_optical_rgba8().iter().all(|&value| value == 0));
+        // Low sun in the West at ~16° elevation
+        masks.update_sun(DVec3::new(-10.0, 0.0, 3.0).normalize());
+        assert!(masks.sun_shadow[at(300.0)]);
+        assert!(!masks.sun_shadow[at(-100.0)]);
+        let rgba = masks.to_optical_rgba8();
+        let gray = |east_m: f64| rgba[4 * at(east_m)];
+        // Slope facing the sun brighter than the flat ground, shadow at the sky light
+        assert!(gray(-100.0) > gray(-500.0));
+        assert_eq!(gray(300.0), (255.0 * SKY_LIGHT).round() as u8);
+        assert_eq!(rgba[4 * at(-500.0) + 3], 255);
+        // Night
+        masks.update_sun(DVec3::new(0.0, 0.6, -0.8));
+        assert_eq!(masks.sun_shadow_fraction(), 1.0);
+    }
+
+    #[test]
+    fn steep_slopes_facing_the_sensor_are_in_layover() {
+        // 60° slopes with a sensor at 60° elevation (30° incidence): the
+        // facing slope lays over
+        let mut masks = ridge(1000.0, 100, 200.0 * 3f64.sqrt(), 200.0);
+        let sensor = (DVec3::new(-2000.0, 0.0, 2000.0 * 3f64.sqrt()), DVec3::new(0.0, 100.0, 0.0));
+        masks.update(sensor, sensor);
+        assert!(masks.layover[at(-100.0)]);
+        assert!(!masks.layover[at(100.0)]); // Back slope
+        assert!(!masks.layover[at(-500.0)]); // Flat ground
+        assert!(masks.layover_fraction() > 0.0);
+        let rgba = masks.to_rgba8();
+        assert_eq!(rgba[4 * at(-100.0)..4 * at(-100.0) + 4], LAYOVER_COLOR);
+        assert_eq!(rgba[4 * at(-500.0) + 3], 0);
+    }
+}
@@ -1,16 +1,90 @@
+mod ambiguity;
+pub use ambiguity::{AmbiguityPlugin, AmbiguityWidget};
+
+mod annotations;
+pub use annotations::{AnnotationPlugin, FootprintAnnotations};
+
+mod antenna_pattern;
+pub use antenna_pattern::{AntennaPatternPlugin, AntennaPatternWidget, CarrierPattern, PatternModel};
+
+mod antennas;
+pub use antennas::{AdditionalAntenna, AntennaCarrier, AntennasPlugin, AntennasWidget, CarrierAntennas};
+
 mod app;
 pub use app::{AppPlugin, SidePanelRects};
 
+mod burst;
+pub use burst::{BurstCarrier, BurstPlugin, BurstWidget};
+
 mod carrier_ui;
-pub use carrier_ui::{carrier_ui, heading_with_reset};
+pub use carrier_ui::{carrier_ui, gain_menu, heading_with_reset, log_slider, reset_menu};
+
+mod clutter_locus;
+pub use clutter_locus::{ClutterLocusPlugin, ClutterLocusWidget};
+
+mod constellation;
+pub use constellation::{BistaticOpportunity, ConstellationDefinition, ConstellationPlugin, ConstellationWidget};
+
+mod coverage;
+pub use coverage::{CoverageLayer, CoveragePlugin, CoverageWidget};
+
+mod display;
+pub use display::{ContourRendering, ContourWindow, DisplayPlugin, DisplayWidget};
+
+mod doppler_spectrum;
+pub use doppler_spectrum::{DopplerSpectrumPlugin, DopplerSpectrumWidget};
+
+mod exercise;
+pub use exercise::{ExerciseMetric, ExercisePlugin, ExerciseWidget};
+mod extensions;
+pub use extensions::ExtensionsPlugin;
+
+mod export;
+pub use export::{ExportPlugin, ExportWidget};
+
+mod feed;
+pub use feed::{FeedPlugin, FeedWidget, PlatformMarkers};
+
+mod frequency_offset;
+pub use frequency_offset::{FrequencyOffsetPlugin, FrequencyOffsetWidget};
+
+mod geo;
+pub use geo::{GeoReferencePlugin, GeoReferenceWidget};
 
 mod gaf;
 pub use gaf::{show_gaf_window, GafState};
 #[cfg(test)]
 pub(crate) use gaf::gaf_key;
 
+mod ground_track;
+pub use ground_track::{FootprintHistory, GroundTrace, GroundTrackPlugin, GroundTrackWidget};
+
+mod hud;
+pub use hud::{pin_menu, CarrierMetric, HudMetric, HudPlugin, HudWidget};
+
+mod horizon;
+pub use horizon::{CarrierHorizon, HorizonPlugin, HorizonSource, HorizonWidget};
+
+mod incidence;
+pub use incidence::{CarrierIncidence, IncidenceOverlay, IncidencePlugin, IncidenceWidget};
+
+mod interferometry;
+pub use interferometry::{InterferometryPlugin, InterferometryWidget};
+
+mod iso_probe;
+pub use iso_probe::{IsoProbePlugin, IsoProbeWidget, ProbeContour};
+
+mod iso_spacing;
+pub use iso_spacing::{IsoSpacingPlugin, IsoSpacingWidget};
+
+mod kspace;
+pub use kspace::KSpacePlugin;
+
 mod menu;
-pub use menu::{CameraFocus, MenuPlugin, MenuWidget};
+pub use menu::{CameraFocus, GeometryMode, MenuPlugin, MenuWidget};
+
+mod moving_target;
+pub use moving_target::{MovingTargetOverlay, MovingTargetPlugin, MovingTargetWidget};
 
 mod infos;
 pub use infos::{bsar_infos_ui, carrier_infos_ui};
@@ -18,82 +92,126 @@ pub use infos::{bsar_infos_ui, carrier_infos_ui};
 mod tx_panel;
 pub use tx_panel::{TxPanelPlugin, TxPanelWidget};
 
+mod multi_beam;
+pub use multi_beam::{MultiBeamPlugin, MultiBeamWidget, RxBeamFootprint};
+
+mod nadir;
+pub use nadir::{NadirEchoOverlay, NadirPlugin, NadirWidget};
+
+mod noise_temperature;
+pub use noise_temperature::{NoiseTemperaturePlugin, NoiseTemperatureWidget};
+
+mod orbit;
+pub use orbit::{OrbitDefinition, OrbitPlugin, OrbitWidget};
+
+mod pair_matrix;
+pub use pair_matrix::{PairCriterion, PairMatrixPlugin, PairMatrixWidget, PairMetrics, PairSample, Platform, PlatformState};
+
+mod phased_array;
+pub use phased_array::{CarrierArray, PhasedArrayPlugin, PhasedArrayWidget};
+
+mod pipeline;
+pub use pipeline::{PipelinePlugin, UpdateStage};
+
+mod polar_coverage;
+pub use polar_coverage::{PolarAzimuthReference, PolarCoveragePlugin, PolarCoverageWidget};
+
+mod preferences;
+pub use preferences::{PreferencesPlugin, PreferencesWidget};
+
+mod prf_suggestion;
+pub use prf_suggestion::PrfSuggestionPlugin;
+
+mod prf_timing;
+pub use prf_timing::{PrfTimingPlugin, PrfTimingWidget};
+
+mod propagation;
+pub use propagation::{PropagationPlugin, PropagationWidget};
+
+mod psf;
+pub use psf::{PsfPlugin, PsfWidget};
+
+mod raw_data;
+pub use raw_data::{RawDataPlugin, RawDataWidget};
+
+mod roi;
+pub use roi::{RoiOutline, RoiPlugin, RoiWidget};
+
 mod rx_panel;
 pub use rx_panel::{RxPanelPlugin, RxPanelWidget};
 
+mod scenario;
+pub use scenario::{ScenarioPlugin, ScenarioWidget};
+
+mod scene_statistics;
+pub use scene_statistics::{SceneStatisticsPlugin, SceneStatisticsWidget};
+
+mod second_window;
+pub use second_window::{detach_button, SecondWindowContextPass, SecondWindowPlugin, SecondWindowWidget};
+
+mod snippets;
+pub use snippets::{SnippetsPlugin, SnippetsWidget};
+
+mod squint;
+pub use squint::{SquintAntennas, SquintPlugin, SquintWidget};
+
+mod status_bar;
+pub use status_bar::{status_bar_ui, BsarMetric, StatusBarWidget};
+
+mod steering;
+pub use steering::{CarrierSteering, SteeringOverlay, SteeringPlugin, SteeringWidget};
+
+mod terrain;
+pub use terrain::{SunLight, TerrainLayer, TerrainPlugin, TerrainWidget};
+
+mod toast;
+pub use toast::{Toast, ToastLevel, ToastPlugin, Toasts};
+
+mod top_view;
+pub use top_view::{TopViewPlugin, TopViewWidget};
+
+mod timeline;
+pub use timeline::{TimelinePlugin, TimelineWidget};
+
+mod tutorial;
+pub use tutorial::{TutorialPlugin, TutorialStep, TutorialWidget};
+
+mod visibility;
+pub use visibility::{VisibilityPlugin, VisibilityWidget};
+
 #[cfg(test)]
 mod tests {
-    use bevy::asset::AssetPlugin;
     use bevy::prelude::*;
 
-    use crate::entities::IsoRangeDopplerPlaneState;
-    use crate::scene::{
-        spawn_scene, BsarInfosState,
-        RxAntennaBeamFootprintState, RxAntennaBeamState, RxAntennaState, RxCarrierState,
-        TxAntennaBeamFootprintState, TxAntennaBeamState, TxAntennaState, TxCarrierState,
-    };
-    use super::{MenuWidget, RxPanelPlugin, RxPanelWidget, TxPanelPlugin, TxPanelWidget};
-
-    /// Headless App running the real spawned scene graph and the real panel
-    /// update systems (update_rx ordered before update_tx), without rendering.
-    fn test_app() -> App {
-        let mut app = App::new();
-        app.add_plugins((MinimalPlugins, AssetPlugin::default()));
-        app.init_asset::<Mesh>();
-        app.init_asset::<StandardMaterial>();
-        app.init_asset::<Image>();
-        // Scene state resources, as ScenePlugin initializes them (without its
-        // camera/world rendering plugins)
-        app.init_resource::<TxCarrierState>();
-        app.init_resource::<TxAntennaState>();
-        app.init_resource::<TxAntennaBeamState>();
-        app.init_resource::<TxAntennaBeamFootprintState>();
-        app.init_resource::<RxCarrierState>();
-        app.init_resource::<RxAntennaState>();
-        app.init_resource::<RxAntennaBeamState>();
-        app.init_resource::<RxAntennaBeamFootprintState>();
-        app.init_resource::<BsarInfosState>();
-        app.init_resource::<IsoRangeDopplerPlaneState>();
-        app.init_resource::<MenuWidget>();
-        app.add_plugins((TxPanelPlugin, RxPanelPlugin));
-        app.add_systems(Startup, spawn_scene);
-        app
-    }
+    use crate::batch::headless_app;
+    use crate::scene::{BsarInfosState, RxCarrierState, TxCarrierState};
+    use super::{GeometryMode, MenuWidget};
 
     /// Regression test: in monostatic mode, dragging the Tx velocity to zero
     /// must invalidate the BSAR infos (NaN) in the same frame. The Rx state
-    /// mirrored during the egui pass still carries the stale (non-zero)
-    /// derived velocity vector, which update_rx refreshes — so update_tx,
-    /// which computes the infos from both carriers, must run after it.
+    /// mirrored by apply_geometry_mode still carries the stale (non-zero)
+    /// derived velocity vector, which update_rx refreshes — so
+    /// update_bsar_system, which computes the infos from both carriers, must
+    /// run after it.
     #[test]
     fn monostatic_zero_velocity_invalidates_infos_same_frame() {
-        let mut app = test_app();
-        {
-            let mut menu = app.world_mut().resource_mut::<MenuWidget>();
-            menu.is_monostatic = true;
-            menu.was_monostatic = true;
-        }
+        let mut app = headless_app();
+        app.world_mut().resource_mut::<MenuWidget>().geometry_mode = GeometryMode::Monostatic;
         app.update(); // Startup: spawns the scene and computes the initial infos
 
         // Baseline sanity: the default scene produces finite infos
         assert!(app.world().resource::<BsarInfosState>().inner.nesz.is_finite());
 
-        // Simulate the egui pass for "Tx velocity dragged to 0" exactly as
-        // TxPanelWidget::ui performs it in monostatic mode: scalar updated,
-        // Rx mirrored from Tx (stale derived velocity vector included),
-        // dirty flags mirrored.
+        // Simulate the egui pass for "Tx velocity dragged to 0": scalar
+        // updated, the Tx state marked changed and mirrored onto the Rx one
         {
-            let world = app.world_mut();
-            let mut tx_carrier_state = world.resource_mut::<TxCarrierState>();
+            let mut tx_carrier_state = app.world_mut().resource_mut::<TxCarrierState>();
             tx_carrier_state.inner.velocity_mps = 0.0;
-            let tx_inner = tx_carrier_state.inner.clone();
             // Premise of the regression: the mirrored derived vector is stale
-            assert!(tx_inner.velocity_vector_mps.length() > 0.0);
-            world.resource_mut::<RxCarrierState>().inner = tx_inner;
-            world.resource_mut::<TxPanelWidget>().velocity_vector_needs_update = true;
-            world.resource_mut::<RxPanelWidget>().velocity_vector_needs_update = true;
+            assert!(tx_carrier_state.inner.velocity_vector_mps.length() > 0.0);
         }
         app.update();
+        assert_eq!(app.world().resource::<RxCarrierState>().inner.velocity_mps, 0.0);
 
         let infos = &app.world().resource::<BsarInfosState>().inner;
         assert!(
@@ -101,9 +219,6 @@ mod tests {
             "nesz = {} — infos were computed from the stale Rx velocity vector",
             infos.nesz
         );
-        // Flags must have been consumed by the update systems
-        assert!(!app.world().resource::<TxPanelWidget>().velocity_vector_needs_update);
-        assert!(!app.world().resource::<RxPanelWidget>().velocity_vector_needs_update);
     }
 
     /// The camera focus system follows the menu selection (camera tracks the
@@ -117,7 +232,7 @@ mod tests {
         use crate::scene::Tx;
         use super::CameraFocus;
 
-        let mut app = test_app();
+        let mut app = headless_app();
         app.add_systems(Update, crate::camera::update_camera_focus);
         let camera = app.world_mut().spawn(PanOrbitCamera::default()).id();
         app.update(); // Startup: spawns the scene
@@ -171,26 +286,9 @@ mod tests {
     /// content every frame, i.e. visible flicker.
     #[test]
     fn gaf_key_is_stable_across_frames_in_monostatic() {
-        let mut app = test_app();
-        {
-            let mut menu = app.world_mut().resource_mut::<MenuWidget>();
-            menu.is_monostatic = true;
-            menu.was_monostatic = true;
-        }
-        app.update(); // startup
-        // Mirror Tx onto Rx as the egui pass does in monostatic mode
-        {
-            let world = app.world_mut();
-            let tx_inner = world.resource::<TxCarrierState>().inner.clone();
-            let tx_ant = world.resource::<TxAntennaState>().inner.clone();
-            let tx_beam = world.resource::<TxAntennaBeamState>().inner.clone();
-            world.resource_mut::<RxCarrierState>().inner = tx_inner;
-            world.resource_mut::<RxAntennaState>().inner = tx_ant;
-            world.resource_mut::<RxAntennaBeamState>().inner = tx_beam;
-            world.resource_mut::<RxPanelWidget>().transform_needs_update = true;
-            world.resource_mut::<RxPanelWidget>().velocity_vector_needs_update = true;
-            world.resource_mut::<RxPanelWidget>().system_needs_update = true;
-        }
+        let mut app = headless_app();
+        app.world_mut().resource_mut::<MenuWidget>().geometry_mode = GeometryMode::Monostatic;
+        app.update(); // startup, Tx mirrored onto Rx
         app.update();
 
         let key_of = |app: &App| {
@@ -210,4 +308,76 @@ mod tests {
         }
     }
 
+    /// In quasi-monostatic mode the Rx flies at the lever arm from the Tx and
+    /// follows the Tx when it moves.
+    #[test]
+    fn quasi_monostatic_rx_follows_the_tx_at_the_lever_arm() {
+        use crate::formation::LeverArm;
+
+        let mut app = headless_app();
+        let lever_arm = LeverArm { along_track_m: 200.0, across_track_m: -50.0, vertical_m: 30.0 };
+        {
+            let mut menu = app.world_mut().resource_mut::<MenuWidget>();
+            menu.geometry_mode = GeometryMode::QuasiMonostatic;
+            menu.lever_arm = lever_arm;
+        }
+        app.update(); // Startup: spawns the scene and derives the Rx
+
+        let offset_error_of = |app: &App| {
+            let tx = &app.world().resource::<TxCarrierState>().inner;
+            let rx = &app.world().resource::<RxCarrierState>().inner;
+            (rx.position_m - tx.position_m).distance(lever_arm.offset_m(tx.heading_deg))
+        };
+        assert!(offset_error_of(&app) < 1e-6, "offset error = {}", offset_error_of(&app));
+
+        // The Tx climbs and turns: the Rx follows in the same frame
+        {
+            let mut tx_carrier_state = app.world_mut().resource_mut::<TxCarrierState>();
+            tx_carrier_state.inner.height_m += 500.0;
+            tx_carrier_state.inner.heading_deg += 30.0;
+        }
+        app.update();
+        assert!(offset_error_of(&app) < 1e-6, "offset error = {}", offset_error_of(&app));
+        assert!(app.world().resource::<BsarInfosState>().inner.nesz.is_finite());
+    }
+
+    /// The timeline moves both carriers along their velocity vectors (update
+    /// systems driven by the change detection of the carrier states) and the
+    /// recorder samples the BSAR infos recomputed for the moved geometry.
+    #[test]
+    fn timeline_moves_carriers_and_records_infos() {
+        use super::{TimelinePlugin, TimelineWidget};
+
+        let mut app = headless_app();
+        app.add_plugins(TimelinePlugin);
+        app.update(); // Startup: spawns the scene and computes the initial infos
+
+        let tx_start = app.world().resource::<TxCarrierState>().inner.clone();
+        let rx_start = app.world().resource::<RxCarrierState>().inner.clone();
+        let range_center_start_m = app.world().resource::<BsarInfosState>().inner.range_center_m;
+        {
+            let mut timeline = app.world_mut().resource_mut::<TimelineWidget>();
+            timeline.is_recording = true;
+            timeline.time_s = 10.0;
+        }
+        app.update();
+
+        let tx = &app.world().resource::<TxCarrierState>().inner;
+        let rx = &app.world().resource::<RxCarrierState>().inner;
+        let tx_expected = tx_start.position_m + 10.0 * tx_start.velocity_vector_mps;
+        let rx_expected = rx_start.position_m + 10.0 * rx_start.velocity_vector_mps;
+        assert!(tx.position_m.distance(tx_expected) < 1e-6, "{} != {}", tx.position_m, tx_expected);
+        assert!(rx.position_m.distance(rx_expected) < 1e-6, "{} != {}", rx.position_m, rx_expected);
+        // The BSAR infos follow the moved carriers
+        let range_center_m = app.world().resource::<BsarInfosState>().inner.range_center_m;
+        assert!((range_center_m - range_center_start_m).abs() > 1.0);
+        // One recorded sample (header + 1 row), at the timeline time
+        let csv = app.world().resource::<TimelineWidget>().records_as_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[1].starts_with("10,"));
+        // No new sample while the time does not change
+        app.update();
+        assert_eq!(app.world().resource::<TimelineWidget>().records_as_csv().lines().count(), 2);
+    }
 }
@@ -0,0 +1,194 @@
+use bevy::{math::DVec3, prelude::*};
+use bevy_egui::{egui, EguiContexts, EguiPrimaryContextPass};
+
+use crate::{
+    bsar::{ambiguity::{cut_metrics, AmbiguityGeometry, CutMetrics}, BsarInfos},
+    scene::{BsarInfosState, RxCarrierState, TxCarrierState},
+    ui::UpdateStage,
+};
+
+const TEXT_COLOR: egui::Color32 = egui::Color32::from_rgb(200, 200, 200);
+/// Colors of the cuts, as the iso-range and iso-Doppler contours they cross
+const RANGE_CUT_COLOR: egui::Color32 = egui::Color32::from_rgb(31, 119, 180);
+const DOPPLER_CUT_COLOR: egui::Color32 = egui::Color32::from_rgb(214, 39, 40);
+/// Half extent of the cuts in resolutions of the bisector formulas
+const CUT_HALF_EXTENT_RESOLUTIONS: f64 = 8.0;
+/// Samples of each cut
+const CUT_SAMPLES: usize = 401;
+/// Lowest displayed level in dB
+const CUT_FLOOR_DB: f64 = -40.0;
+
+pub struct AmbiguityPlugin;
+
+impl Plugin for AmbiguityPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<AmbiguityWidget>()
+            // Computed for the system updated by the update systems
+            .add_systems(
+                PostUpdate,
+                update_ambiguity
+                    .in_set(UpdateStage::Derived)
+                    .after(super::tx_panel::update_bsar_system)
+            )
+            .add_systems(EguiPrimaryContextPass, ambiguity_ui_system);
+    }
+}
+
+/// Cuts of the bistatic point-target ambiguity function at the scene center,
+/// along the ground range and lateral directions (across the iso-range and
+/// iso-Doppler lines).
+#[derive(Resource, Default)]
+pub struct AmbiguityWidget {
+    pub is_enabled: bool,
+    /// Cuts as (offset in m, amplitude in dB) with their metrics
+    range_cut: Vec<[f64; 2]>,
+    doppler_cut: Vec<[f64; 2]>,
+    range_metrics: Option<CutMetrics>,
+    doppler_metrics: Option<CutMetrics>,
+    needs_update: bool,
+}
+
+/// Gets the ambiguity geometry of the system, at the scene center.
+pub(super) fn ambiguity_geometry(tx_carrier_state: &TxCarrierState, rx_carrier_state: &RxCarrierState, bsar_infos: &BsarInfos) -> AmbiguityGeometry {
+    let (tx, rx) = (&tx_carrier_state.inner, &rx_carrier_state.inner);
+    // As the BSAR infos: a fixed receiver does not move
+    let (vrx, arx) = if rx_carrier_state.is_stationary {
+        (DVec3::ZERO, DVec3::ZERO)
+    } else {
+        (rx.velocity_vector_mps, rx.acceleration_vector_mps2.unwrap_or(DVec3::ZERO))
+    };
+    AmbiguityGeometry {
+        txp: -tx.position_m,
+        rxp: -rx.position_m,
+        vtx: tx.velocity_vector_mps,
+        vrx,
+        atx: tx.acceleration_vector_mps2.unwrap_or(DVec3::ZERO),
+        arx,
+        center_frequency_hz: tx_carrier_state.center_frequency_ghz * 1e9,
+        bandwidth_hz: tx_carrier_state.bandwidth_mhz * 1e6,
+        integration_time_s: bsar_infos.integration_time_s,
+    }
+}
+
+/// Computes the cuts when the system changes.
+fn update_ambiguity(
+    mut ambiguity_widget: ResMut<AmbiguityWidget>,
+    bsar_infos_state: Res<BsarInfosState>,
+    tx_carrier_state: Res<TxCarrierState>,
+    rx_carrier_state: Res<RxCarrierState>,
+) {
+    if !ambiguity_widget.is_enabled {
+        return;
+    }
+    // The widget is written by its window every frame: only a new system is computed
+    if !ambiguity_widget.needs_update && !bsar_infos_state.is_changed() {
+        return;
+    }
+    let widget = ambiguity_widget.bypass_change_detection();
+    let bsar_infos = &bsar_infos_state.inner;
+    let geometry = ambiguity_geometry(&tx_carrier_state, &rx_carrier_state, bsar_infos);
+    let cut = |direction: Option<DVec3>, resolution_m: f64| match direction {
+        Some(direction) if resolution_m > 0.0 => geometry.cut_db(&direction, CUT_HALF_EXTENT_RESOLUTIONS * resolution_m, CUT_SAMPLES),
+        _ => Vec::new(),
+    };
+    widget.range_cut = cut(bsar_infos.ground_range_direction(), bsar_infos.ground_range_resolution_m);
+    widget.doppler_cut = cut(bsar_infos.ground_lateral_direction(), bsar_infos.ground_lateral_resolution_m);
+    widget.range_metrics = cut_metrics(&widget.range_cut);
+    widget.doppler_metrics = cut_metrics(&widget.doppler_cut);
+    widget.needs_update = false;
+}
+
+fn ambiguity_ui_system(
+    mut contexts: EguiContexts,
+    mut ambiguity_widget: ResMut<AmbiguityWidget>,
+    bsar_infos_state: Res<BsarInfosState>,
+) -> Result {
+    let ctx = contexts.ctx_mut()?;
+
+    let ambiguity_window = egui::Window::new("Ambiguity Function")
+        .resizable(false)
+        .constrain(true)
+        .collapsible(true)
+        .title_bar(true)
+        .enabled(true)
+        .default_open(false)
+        .default_pos(egui::pos2(320.0, 1280.0));
+    ambiguity_window.show(ctx, |ui| {
+        ambiguity_ui(ui, &mut ambiguity_widget, &bsar_infos_state.inner);
+    });
+
+    Ok(())
+}
+
+fn ambiguity_ui(ui: &mut egui::Ui, widget: &mut AmbiguityWidget, bsar_infos: &BsarInfos) {
+    let hover_text = egui::RichText::new("Focuses a point target at the scene center by backprojection, the carriers following\ntheir trajectories over the integration time, and cuts its response along the ground\nrange and lateral directions (across the iso-range and iso-Doppler lines)")
+        .color(TEXT_COLOR)
+        .monospace();
+    if ui.checkbox(&mut widget.is_enabled, "Compute").on_hover_text(hover_text).changed() {
+        // Computed again once enabled
+        widget.needs_update = true;
+    }
+    if !widget.is_enabled {
+        return;
+    }
+    if widget.range_cut.is_empty() && widget.doppler_cut.is_empty() {
+        ui.label(egui::RichText::new("No resolution cell: the geometry is degenerate").color(TEXT_COLOR));
+        return;
+    }
+    let hover_text = egui::RichText::new("Width of the main lobe at -3 dB (resolution), against the formula of the BSAR infos,\nand Peak Sidelobe Ratio (PSLR): the highest sidelobe under the main lobe")
+        .color(TEXT_COLOR)
+        .monospace();
+    egui::Grid::new("ambiguity_grid")
+        .num_columns(3)
+        .striped(false)
+        .spacing([20.0, 5.0])
+        .show(ui, |ui| {
+            ui.label("");
+            ui.label(egui::RichText::new("Resolution").strong()).on_hover_text(hover_text.clone());
+            ui.label(egui::RichText::new("PSLR").strong()).on_hover_text(hover_text);
+            ui.end_row();
+            let cuts = [
+                ("Range cut:", widget.range_metrics, bsar_infos.ground_range_resolution_m),
+                ("Doppler cut:", widget.doppler_metrics, bsar_infos.ground_lateral_resolution_m),
+            ];
+            for (name, metrics, resolution_m) in cuts {
+                ui.label(name);
+                match metrics {
+                    Some(metrics) => {
+                        ui.label(format!("{:.3} m (formula {resolution_m:.3} m)", metrics.half_power_width_m));
+                        ui.label(if metrics.pslr_db.is_finite() { format!("{:.2} dB", metrics.pslr_db) } else { "-".to_string() });
+                    }
+                    None => {
+                        ui.label("-");
+                        ui.label("-");
+                    }
+                }
+                ui.end_row();
+            }
+        });
+
+    let floored = |cut: &[[f64; 2]]| -> Vec<[f64; 2]> {
+        cut.iter().map(|&[offset_m, value_db]| [offset_m, value_db.max(CUT_FLOOR_DB)]).collect()
+    };
+    egui_plot::Plot::new("ambiguity_plot")
+        .width(320.0)
+        .height(200.0)
+        .include_y(0.0)
+        .include_y(CUT_FLOOR_DB)
+        .x_axis_label("Ground offset [m]")
+        .y_axis_label("Amplitude [dB]")
+        .legend(egui_plot::Legend::default().follow_insertion_order(true))
+        .allow_drag(false)
+        .allow_zoom(false)
+        .allow_scroll(false)
+        .show(ui, |plot_ui| {
+            plot_ui.line(egui_plot::Line::new("Range cut", floored(&widget.range_cut)).color(RANGE_CUT_COLOR).width(1.5));
+            plot_ui.line(egui_plot::Line::new("Doppler cut", floored(&widget.doppler_cut)).color(DOPPLER_CUT_COLOR).width(1.5));
+            plot_ui.hline(
+                egui_plot::HLine::new("-3 dB", -3.0)
+                    .color(TEXT_COLOR)
+                    .style(egui_plot::LineStyle::dotted_dense())
+            );
+        });
+}
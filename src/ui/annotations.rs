@@ -0,0 +1,136 @@
+use bevy::{math::DVec3, prelude::*};
+use bevy_egui::{egui, EguiContexts, EguiPrimaryContextPass};
+use bevy_panorbit_camera::PanOrbitCamera;
+
+use crate::{
+    bsar::{bsar_extrema, BsarExtrema, SPEED_OF_LIGHT_IN_VACUUM},
+    scene::{RxAntennaBeamFootprintState, RxCarrierState, TxAntennaBeamFootprintState, TxCarrierState},
+    ui::{DisplayWidget, SidePanelRects, UpdateStage},
+};
+
+/// Colors of the annotations, as the ground range (orange) and lateral (cyan)
+/// resolution directions of the "Display" window
+const RANGE_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 128, 0);
+const DOPPLER_COLOR: egui::Color32 = egui::Color32::from_rgb(0, 255, 255);
+/// Radius of the annotated point markers in points
+const MARKER_RADIUS: f32 = 3.0;
+
+pub struct AnnotationPlugin;
+
+impl Plugin for AnnotationPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<FootprintAnnotations>()
+            // The carriers and the footprints are updated by the update systems
+            .add_systems(
+                PostUpdate,
+                update_footprint_annotations.in_set(UpdateStage::RenderAssets)
+            )
+            .add_systems(EguiPrimaryContextPass, footprint_annotations_ui_system);
+    }
+}
+
+/// Bistatic range and Doppler frequency extrema over the footprint, labelled
+/// at their footprint points in the 3D view when enabled in the "Display"
+/// window.
+#[derive(Resource, Default)]
+pub struct FootprintAnnotations {
+    /// Extrema of the current geometry, `None` when disabled
+    extrema: Option<BsarExtrema>,
+    /// Height of the labelled points above the ground plane in meters
+    lift_m: f64,
+}
+
+/// Recomputes the extrema when the carriers or the footprints change.
+fn update_footprint_annotations(
+    display_widget: Res<DisplayWidget>,
+    mut annotations: ResMut<FootprintAnnotations>,
+    tx: (Res<TxCarrierState>, Res<TxAntennaBeamFootprintState>),
+    rx: (Res<RxCarrierState>, Res<RxAntennaBeamFootprintState>),
+) {
+    let are_states_changed = display_widget.is_changed()
+        || tx.0.is_changed() || tx.1.is_changed() || rx.0.is_changed() || rx.1.is_changed();
+    if !are_states_changed {
+        return;
+    }
+    if !display_widget.show_footprint_annotations {
+        annotations.extrema = None;
+        return;
+    }
+    // As the BSAR infos: a fixed receiver does not contribute to the Doppler frequency
+    let vrx = if rx.0.is_stationary { DVec3::ZERO } else { rx.0.inner.velocity_vector_mps };
+    let lem = SPEED_OF_LIGHT_IN_VACUUM / (tx.0.center_frequency_ghz * 1e9); // wavelength in m
+    annotations.extrema = bsar_extrema(
+        &(-tx.0.inner.position_m),
+        &tx.0.inner.velocity_vector_mps,
+        &(-rx.0.inner.position_m),
+        &vrx,
+        lem,
+        &tx.1.inner,
+        &rx.1.inner,
+    );
+    // Above the footprints, as the ground tracks
+    annotations.lift_m = 2.0 * tx.1.inner.lift_m;
+}
+
+fn meters_text(value_m: f64) -> String {
+    if value_m >= 1e3 {
+        format!("{:.3} km", value_m * 1e-3)
+    } else {
+        format!("{:.3} m", value_m)
+    }
+}
+
+fn hertz_text(value_hz: f64) -> String {
+    if value_hz.abs() >= 1e3 {
+        format!("{:.3} kHz", value_hz * 1e-3)
+    } else {
+        format!("{:.3} Hz", value_hz)
+    }
+}
+
+/// Paints the extrema labels next to their footprint points, projected by
+/// the 3D camera, under the windows and within the 3D view.
+fn footprint_annotations_ui_system(
+    mut contexts: EguiContexts,
+    annotations: Res<FootprintAnnotations>,
+    side_panel_rects: Res<SidePanelRects>,
+    camera_q: Single<(&Camera, &GlobalTransform), With<PanOrbitCamera>>,
+) -> Result {
+    let Some(extrema) = annotations.extrema else {
+        return Ok(());
+    };
+    let ctx = contexts.ctx_mut()?;
+    let (camera, camera_transform) = *camera_q;
+
+    let view_rect = side_panel_rects.view_rect(ctx);
+    let painter = ctx
+        .layer_painter(egui::LayerId::new(egui::Order::Background, egui::Id::new("footprint_annotations")))
+        .with_clip_rect(view_rect);
+    let labels = [
+        (extrema.range_min, format!("R min {}", meters_text(extrema.range_min.value)), RANGE_COLOR),
+        (extrema.range_max, format!("R max {}", meters_text(extrema.range_max.value)), RANGE_COLOR),
+        (extrema.doppler_min, format!("fD min {}", hertz_text(extrema.doppler_min.value)), DOPPLER_COLOR),
+        (extrema.doppler_max, format!("fD max {}", hertz_text(extrema.doppler_max.value)), DOPPLER_COLOR),
+    ];
+    for (extremum, text, color) in labels {
+        let point = Vec3::new(extremum.point_m.x as f32, annotations.lift_m as f32, extremum.point_m.z as f32);
+        // Points behind the camera have no viewport position
+        let Ok(position) = camera.world_to_viewport(camera_transform, point) else {
+            continue;
+        };
+        // From logical pixels to egui points
+        let position = position / ctx.zoom_factor();
+        let position = egui::pos2(position.x, position.y);
+        painter.circle_filled(position, MARKER_RADIUS, color);
+        painter.text(
+            position + egui::vec2(2.0 * MARKER_RADIUS, 0.0),
+            egui::Align2::LEFT_CENTER,
+            text,
+            egui::FontId::monospace(12.0),
+            color,
+        );
+    }
+
+    Ok(())
+}
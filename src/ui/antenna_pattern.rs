@@ -0,0 +1,227 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts, EguiPrimaryContextPass};
+
+use crate::{
+    antenna_pattern::{AntennaPattern, PatternCut, PatternTable, PATTERN_FLOOR_DB},
+    entities::AntennaBeamState,
+    scene::{RxAntennaBeamState, TxAntennaBeamState},
+};
+
+const TEXT_COLOR: egui::Color32 = egui::Color32::from_rgb(200, 200, 200);
+const ERROR_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 120, 120);
+/// Colors of the elevation and azimuth cuts, and of their beamwidth marks
+const ELEVATION_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 128, 0);
+const AZIMUTH_COLOR: egui::Color32 = egui::Color32::from_rgb(0, 200, 255);
+/// Plotted angles, in half-power beamwidths on each side of the boresight
+/// (up to the first sinc² sidelobes)
+const PLOT_HALF_SPAN_BEAM_WIDTHS: f64 = 2.0;
+/// Points per plotted cut
+const PLOT_POINT_COUNT: usize = 801;
+
+pub struct AntennaPatternPlugin;
+
+impl Plugin for AntennaPatternPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<AntennaPatternWidget>()
+            .add_systems(EguiPrimaryContextPass, antenna_pattern_ui_system);
+    }
+}
+
+/// Pattern model selected for an antenna.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PatternModel {
+    Sinc,
+    Gaussian,
+    Table,
+}
+
+/// Assumed pattern of one antenna.
+pub struct CarrierPattern {
+    pub model: PatternModel,
+    pub elevation_table_text: String,
+    pub azimuth_table_text: String,
+    /// Applied tables, the elevation cut then the azimuth cut
+    pub tables: Option<(PatternTable, PatternTable)>,
+    /// Result of the last table reading
+    status: Option<Result<String, String>>,
+}
+
+impl Default for CarrierPattern {
+    fn default() -> Self {
+        let table_text = "# angle [°] gain [dB]\n-10 -20\n0 0\n10 -20\n".to_string();
+        Self {
+            model: PatternModel::Sinc,
+            elevation_table_text: table_text.clone(),
+            azimuth_table_text: table_text,
+            tables: None,
+            status: None,
+        }
+    }
+}
+
+impl CarrierPattern {
+    /// Gets the pattern of the selected model, the sinc² one while no table
+    /// has been applied.
+    pub fn pattern(&self) -> AntennaPattern {
+        match (self.model, &self.tables) {
+            (PatternModel::Gaussian, _) => AntennaPattern::Gaussian,
+            (PatternModel::Table, Some((elevation, azimuth))) => AntennaPattern::Table {
+                elevation: elevation.clone(),
+                azimuth: azimuth.clone(),
+            },
+            _ => AntennaPattern::Sinc,
+        }
+    }
+}
+
+/// Elevation and azimuth pattern cuts of the transmitter and receiver
+/// antennas, marking the half-power beamwidths in use.
+#[derive(Resource, Default)]
+pub struct AntennaPatternWidget {
+    pub tx: CarrierPattern,
+    pub rx: CarrierPattern,
+}
+
+fn antenna_pattern_ui_system(
+    mut contexts: EguiContexts,
+    mut antenna_pattern_widget: ResMut<AntennaPatternWidget>,
+    tx_antenna_beam_state: Res<TxAntennaBeamState>,
+    rx_antenna_beam_state: Res<RxAntennaBeamState>,
+) -> Result {
+    let ctx = contexts.ctx_mut()?;
+
+    let antenna_pattern_window = egui::Window::new("Antenna Patterns")
+        .resizable(false)
+        .constrain(true)
+        .collapsible(true)
+        .title_bar(true)
+        .enabled(true)
+        .default_open(false)
+        .default_pos(egui::pos2(320.0, 880.0));
+    antenna_pattern_window.show(ctx, |ui| {
+        let widget = &mut *antenna_pattern_widget;
+        carrier_pattern_ui(ui, "tx", "TRANSMITTER", &mut widget.tx, &tx_antenna_beam_state.inner);
+        carrier_pattern_ui(ui, "rx", "RECEIVER", &mut widget.rx, &rx_antenna_beam_state.inner);
+    });
+
+    Ok(())
+}
+
+fn carrier_pattern_ui(
+    ui: &mut egui::Ui,
+    id: &str,
+    title: &str,
+    carrier_pattern: &mut CarrierPattern,
+    antenna_beam_state: &AntennaBeamState,
+) {
+    ui.separator();
+    ui.vertical_centered(|ui| ui.label(egui::RichText::new(title).strong()));
+    ui.separator();
+
+    ui.horizontal(|ui| {
+        let hover_text = egui::RichText::new("Sets the pattern assumed around the half-power beamwidths: a uniformly\nilluminated aperture (sinc²), a Gaussian beam, or user tables")
+            .color(TEXT_COLOR)
+            .monospace();
+        ui.label("Pattern: ").on_hover_text(hover_text);
+        ui.radio_value(&mut carrier_pattern.model, PatternModel::Sinc, "Sinc²");
+        ui.radio_value(&mut carrier_pattern.model, PatternModel::Gaussian, "Gaussian");
+        ui.radio_value(&mut carrier_pattern.model, PatternModel::Table, "Table");
+    });
+    if carrier_pattern.model == PatternModel::Table {
+        let table_texts = [
+            ("Elevation", &mut carrier_pattern.elevation_table_text),
+            ("Azimuth", &mut carrier_pattern.azimuth_table_text),
+        ];
+        ui.horizontal(|ui| {
+            for (cut, table_text) in table_texts {
+                ui.vertical(|ui| {
+                    ui.label(cut);
+                    ui.add(
+                        egui::TextEdit::multiline(table_text)
+                            .id_salt(format!("{id}_{cut}_pattern_table"))
+                            .font(egui::TextStyle::Monospace)
+                            .desired_rows(4)
+                            .desired_width(136.0)
+                    );
+                });
+            }
+        });
+        let hover_text = egui::RichText::new("Reads the tables: one \"angle gain\" pair in degrees and dB per line,\nangles from the boresight, linearly interpolated and normalized to the peak")
+            .color(TEXT_COLOR)
+            .monospace();
+        if ui.button("Apply").on_hover_text(hover_text).clicked() {
+            let tables = PatternTable::from_table(&carrier_pattern.elevation_table_text)
+                .map_err(|error| format!("Elevation: {error}"))
+                .and_then(|elevation| PatternTable::from_table(&carrier_pattern.azimuth_table_text)
+                    .map(|azimuth| (elevation, azimuth))
+                    .map_err(|error| format!("Azimuth: {error}"))
+                );
+            carrier_pattern.status = Some(tables.map(|tables| {
+                let beam_width_text = |table: &PatternTable| table.half_power_beam_width_deg()
+                    .map_or("-".to_string(), |beam_width_deg| format!("{beam_width_deg:.2}°"));
+                let status = format!(
+                    "Half-power beamwidths: {} (elevation), {} (azimuth)",
+                    beam_width_text(&tables.0),
+                    beam_width_text(&tables.1),
+                );
+                carrier_pattern.tables = Some(tables);
+                status
+            }));
+        }
+        match &carrier_pattern.status {
+            Some(Ok(status)) => { ui.label(egui::RichText::new(status).color(TEXT_COLOR)); }
+            Some(Err(error)) => { ui.label(egui::RichText::new(error).color(ERROR_COLOR)); }
+            None => {}
+        }
+        if carrier_pattern.tables.is_none() {
+            ui.label(egui::RichText::new("No table applied: sinc² pattern").color(TEXT_COLOR));
+        }
+    }
+
+    let pattern = carrier_pattern.pattern();
+    let cuts = [
+        (PatternCut::Elevation, "Elevation", antenna_beam_state.elevation_beam_width_deg, ELEVATION_COLOR),
+        (PatternCut::Azimuth, "Azimuth", antenna_beam_state.azimuth_beam_width_deg, AZIMUTH_COLOR),
+    ];
+    let half_span_deg = PLOT_HALF_SPAN_BEAM_WIDTHS * antenna_beam_state.elevation_beam_width_deg
+        .max(antenna_beam_state.azimuth_beam_width_deg)
+        .min(180.0 / PLOT_HALF_SPAN_BEAM_WIDTHS);
+    egui_plot::Plot::new(format!("{id}_antenna_pattern_plot"))
+        .width(280.0)
+        .height(160.0)
+        .include_x(-half_span_deg)
+        .include_x(half_span_deg)
+        .include_y(0.0)
+        .include_y(PATTERN_FLOOR_DB)
+        .x_axis_label("Angle [°]")
+        .y_axis_label("Gain [dB]")
+        .legend(egui_plot::Legend::default().follow_insertion_order(true))
+        .allow_drag(false)
+        .allow_zoom(false)
+        .allow_scroll(false)
+        .show(ui, |plot_ui| {
+            for (cut, name, beam_width_deg, color) in cuts {
+                let line: Vec<[f64; 2]> = (0..PLOT_POINT_COUNT)
+                    .map(|i| {
+                        let angle_deg = half_span_deg * (2.0 * i as f64 / (PLOT_POINT_COUNT - 1) as f64 - 1.0);
+                        [angle_deg, pattern.gain_db(cut, angle_deg, beam_width_deg)]
+                    })
+                    .collect();
+                plot_ui.line(egui_plot::Line::new(name, line).color(color).width(1.5));
+                // Half-power beamwidth in use
+                for edge_deg in [-0.5 * beam_width_deg, 0.5 * beam_width_deg] {
+                    plot_ui.vline(
+                        egui_plot::VLine::new(name, edge_deg)
+                            .color(color)
+                            .style(egui_plot::LineStyle::dashed_loose())
+                    );
+                }
+            }
+            plot_ui.hline(
+                egui_plot::HLine::new("-3 dB", -3.0)
+                    .color(TEXT_COLOR)
+                    .style(egui_plot::LineStyle::dotted_dense())
+            );
+        });
+}
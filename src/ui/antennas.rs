@@ -0,0 +1,467 @@
+use bevy::{math::DVec3, prelude::*};
+use bevy_egui::{egui, EguiContexts, EguiPrimaryContextPass};
+
+use crate::{
+    bsar::SPEED_OF_LIGHT_IN_VACUUM,
+    constants::ANTENNA_SIZE,
+    entities::{
+        antenna_beam_footprint_state_from_state, antenna_beam_transform_from_state, antenna_transform_from_state,
+        carrier_state_at_antenna, spawn_antenna_beam, spawn_axes_helper,
+        AntennaBeamFootprintState, AntennaBeamState, AntennaState, Carrier, LineStrip
+    },
+    expression,
+    gmti::{phase_std_rad, AlongTrackInterferometer},
+    scene::{
+        BsarInfosState, Rx, RxAntennaBeamFootprintState, RxAntennaBeamState, RxAntennaState, RxCarrierState,
+        Tx, TxAntennaBeamFootprintState, TxAntennaBeamState, TxAntennaState, TxCarrierState
+    },
+    ui::UpdateStage,
+};
+
+const TEXT_COLOR: egui::Color32 = egui::Color32::from_rgb(200, 200, 200);
+/// Colors of the additional antenna beams and footprints
+const BEAM_COLOR: Srgba = Srgba::new(1.0, 0.6, 0.2, 0.15);
+const BEAM_FOOTPRINT_COLOR: Srgba = Srgba::rgb(1.0, 0.6, 0.2);
+/// Maximum displacement of an additional antenna in meters
+const MAX_DISPLACEMENT_M: f64 = 100.0;
+
+pub struct AntennasPlugin;
+
+impl Plugin for AntennasPlugin {
+    fn build(&self, app: &mut App) {
+        // The additional antennas follow the carriers once moved
+        app
+            .init_resource::<AntennasWidget>()
+            .add_systems(
+                PostUpdate,
+                update_additional_antennas
+                    .in_set(UpdateStage::Geometry)
+                    .after(super::tx_panel::update_tx)
+                    .after(super::rx_panel::update_rx)
+            )
+            .add_systems(EguiPrimaryContextPass, antennas_ui_system);
+    }
+}
+
+/// Carrier of an additional antenna.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AntennaCarrier {
+    Tx,
+    Rx,
+}
+
+/// Additional antennas of one carrier, displaced from its antenna.
+#[derive(Default)]
+pub struct CarrierAntennas {
+    /// Phase center displacements from the carrier antenna in the Carrier NED
+    /// frame (x forward, y right, z down), in meters, in order
+    pub displacements_m: Vec<DVec3>,
+    /// Footprints of the additional antennas at the current time, in order
+    pub footprints: Vec<AntennaBeamFootprintState>,
+    footprints_need_update: bool,
+}
+
+impl CarrierAntennas {
+    /// Gets the state of each additional antenna, oriented as `antenna_state`
+    /// and displaced from its phase center.
+    pub fn antenna_states(&self, antenna_state: &AntennaState) -> Vec<AntennaState> {
+        self.displacements_m.iter()
+            .map(|displacement_m| AntennaState {
+                lever_arm_m: antenna_state.lever_arm_m + *displacement_m,
+                ..antenna_state.clone()
+            })
+            .collect()
+    }
+}
+
+/// Detection settings of the GMTI channels.
+pub struct GmtiSettings {
+    /// Clutter backscattering coefficient in dB
+    pub clutter_sigma0_db: f64,
+    pub looks: u32,
+    /// Detection threshold in phase standard deviations
+    pub threshold: f64,
+}
+
+impl Default for GmtiSettings {
+    fn default() -> Self {
+        Self {
+            clutter_sigma0_db: -10.0,
+            looks: 4,
+            threshold: 3.0,
+        }
+    }
+}
+
+/// Additional antennas of the transmitter and the receiver (e.g. fore/aft
+/// displaced phase centers of DPCA/ATI configurations), sharing the
+/// orientation and the beam of the carrier antenna, each one drawn with its
+/// own beam and footprint.
+///
+/// The receiver antenna and its first additional antenna form the GMTI
+/// channels.
+#[derive(Resource, Default)]
+pub struct AntennasWidget {
+    pub tx: CarrierAntennas,
+    pub rx: CarrierAntennas,
+    pub gmti: GmtiSettings,
+}
+
+/// Part of an additional antenna, by carrier and index.
+#[derive(Component, Clone, Copy, PartialEq, Eq)]
+pub enum AdditionalAntenna {
+    Antenna(AntennaCarrier, usize),
+    Beam(AntennaCarrier, usize),
+    Footprint(AntennaCarrier, usize),
+}
+
+impl AdditionalAntenna {
+    fn carrier(self) -> AntennaCarrier {
+        match self {
+            Self::Antenna(carrier, _) | Self::Beam(carrier, _) | Self::Footprint(carrier, _) => carrier,
+        }
+    }
+}
+
+/// Gets the footprint edge, lifted above the footprints to stay visible over them.
+fn footprint_line(footprint: &AntennaBeamFootprintState) -> LineStrip {
+    let lift_m = 2.0 * footprint.lift_m as f32;
+    LineStrip {
+        points: footprint.points.iter()
+            .map(|point| Vec3::new(point.x as f32, lift_m, point.z as f32))
+            .collect()
+    }
+}
+
+/// Computes the additional antenna footprints and keeps one antenna, beam and
+/// footprint entity per additional antenna of each carrier.
+fn update_additional_antennas(
+    mut commands: Commands,
+    mut antennas_widget: ResMut<AntennasWidget>,
+    tx: (Res<TxCarrierState>, Res<TxAntennaState>, Res<TxAntennaBeamState>, Res<TxAntennaBeamFootprintState>),
+    rx: (Res<RxCarrierState>, Res<RxAntennaState>, Res<RxAntennaBeamState>, Res<RxAntennaBeamFootprintState>),
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    tx_carrier_q: Single<Entity, (With<Tx>, With<Carrier>)>,
+    rx_carrier_q: Single<Entity, (With<Rx>, With<Carrier>)>,
+    mut additional_antenna_q: Query<(Entity, &AdditionalAntenna, &mut Transform, Option<(&Mesh3d, &mut Visibility)>)>,
+) -> Result {
+    let widget = &mut *antennas_widget; // Borrows the fields separately
+    let sides = [
+        (AntennaCarrier::Tx, &mut widget.tx, *tx_carrier_q, &tx.0.inner, &tx.1.inner, &tx.2.inner, &tx.3.inner,
+            tx.0.is_changed() || tx.1.is_changed() || tx.2.is_changed() || tx.3.is_changed()),
+        (AntennaCarrier::Rx, &mut widget.rx, *rx_carrier_q, &rx.0.inner, &rx.1.inner, &rx.2.inner, &rx.3.inner,
+            rx.0.is_changed() || rx.1.is_changed() || rx.2.is_changed() || rx.3.is_changed()),
+    ];
+    for (side, antennas, carrier_entity, carrier_state, antenna_state, antenna_beam_state, antenna_beam_footprint_state, are_states_changed) in sides {
+        if !(are_states_changed || antennas.footprints_need_update) {
+            continue;
+        }
+        let antenna_states = antennas.antenna_states(antenna_state);
+        antennas.footprints = antenna_states.iter()
+            .map(|displaced_antenna_state| antenna_beam_footprint_state_from_state(
+                &carrier_state_at_antenna(carrier_state, antenna_state, displaced_antenna_state),
+                displaced_antenna_state,
+                antenna_beam_state,
+                antenna_beam_footprint_state.lift_m
+            ))
+            .collect();
+
+        let footprint_count = additional_antenna_q.iter()
+            .filter(|(_, part, _, _)| matches!(part, AdditionalAntenna::Footprint(carrier, _) if *carrier == side))
+            .count();
+        if footprint_count == antenna_states.len() {
+            for (_, part, mut transform, mesh) in additional_antenna_q.iter_mut() {
+                match *part {
+                    AdditionalAntenna::Antenna(carrier, index) if carrier == side => {
+                        *transform = antenna_transform_from_state(&antenna_states[index]);
+                    }
+                    AdditionalAntenna::Beam(carrier, index) if carrier == side => {
+                        *transform = antenna_beam_transform_from_state(antenna_beam_state, &antennas.footprints[index]);
+                    }
+                    AdditionalAntenna::Footprint(carrier, index) if carrier == side => {
+                        if let Some((mesh, visibility)) = mesh {
+                            super::display::replace_line_mesh(&mut meshes, mesh, visibility, Some(footprint_line(&antennas.footprints[index])))?;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        } else {
+            // The beams are despawned with their antenna
+            for (entity, part, _, _) in additional_antenna_q.iter() {
+                if part.carrier() == side && !matches!(part, AdditionalAntenna::Beam(..)) {
+                    commands.entity(entity).despawn();
+                }
+            }
+            spawn_additional_antennas(
+                &mut commands,
+                &mut meshes,
+                &mut materials,
+                side,
+                carrier_entity,
+                &antenna_states,
+                antenna_beam_state,
+                &antennas.footprints
+            );
+        }
+        antennas.footprints_need_update = false;
+    }
+
+    Ok(())
+}
+
+fn spawn_additional_antennas(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<StandardMaterial>>,
+    side: AntennaCarrier,
+    carrier_entity: Entity,
+    antenna_states: &[AntennaState],
+    antenna_beam_state: &AntennaBeamState,
+    footprints: &[AntennaBeamFootprintState],
+) {
+    let footprint_material = materials.add(StandardMaterial {
+        base_color: BEAM_FOOTPRINT_COLOR.into(),
+        unlit: true,
+        ..default()
+    });
+    for (index, (antenna_state, footprint)) in antenna_states.iter().zip(footprints.iter()).enumerate() {
+        let name = format!("{side:?} Additional Antenna {}", index + 1);
+        // Antenna, child of the carrier, and its beam
+        let antenna_entity = spawn_axes_helper(
+            commands,
+            meshes,
+            materials,
+            ANTENNA_SIZE // Size of the axes
+        );
+        commands
+            .entity(antenna_entity)
+            .insert(antenna_transform_from_state(antenna_state))
+            .insert(AdditionalAntenna::Antenna(side, index))
+            .insert(Name::new(name.clone()));
+        let antenna_beam_entity = spawn_antenna_beam(
+            commands,
+            meshes,
+            materials,
+            StandardMaterial {
+                base_color: BEAM_COLOR.into(),
+                alpha_mode: AlphaMode::Blend,
+                cull_mode: None, // Disable culling to see the beam from all sides
+                unlit: true,
+                ..default()
+            }
+        );
+        commands
+            .entity(antenna_beam_entity)
+            .insert(antenna_beam_transform_from_state(antenna_beam_state, footprint))
+            .insert(AdditionalAntenna::Beam(side, index))
+            .insert(Name::new(format!("{name} Beam")));
+        commands
+            .entity(antenna_entity)
+            .add_child(antenna_beam_entity);
+        commands
+            .entity(carrier_entity)
+            .add_child(antenna_entity);
+        // Footprint in World frame
+        commands.spawn((
+            Mesh3d(meshes.add(footprint_line(footprint))),
+            MeshMaterial3d(footprint_material.clone()),
+            Transform::IDENTITY,
+            AdditionalAntenna::Footprint(side, index),
+            Name::new(format!("{name} Beam Footprint")),
+        ));
+    }
+}
+
+fn antennas_ui_system(
+    mut contexts: EguiContexts,
+    mut antennas_widget: ResMut<AntennasWidget>,
+    tx_carrier_state: Res<TxCarrierState>,
+    rx_carrier_state: Res<RxCarrierState>,
+    bsar_infos_state: Res<BsarInfosState>,
+) -> Result {
+    let ctx = contexts.ctx_mut()?;
+
+    let antennas_window = egui::Window::new("Antennas")
+        .resizable(false)
+        .constrain(true)
+        .collapsible(true)
+        .title_bar(true)
+        .enabled(true)
+        .default_open(false)
+        .default_pos(egui::pos2(320.0, 1120.0));
+    antennas_window.show(ctx, |ui| {
+        let widget = &mut *antennas_widget; // Borrows the fields separately
+        carrier_antennas_ui(ui, "tx_antennas", "TRANSMITTER", &mut widget.tx);
+        ui.separator();
+        carrier_antennas_ui(ui, "rx_antennas", "RECEIVER", &mut widget.rx);
+        ui.separator();
+        gmti_ui(ui, widget, &tx_carrier_state, &rx_carrier_state, bsar_infos_state.inner.nesz);
+    });
+
+    Ok(())
+}
+
+fn carrier_antennas_ui(ui: &mut egui::Ui, id: &str, title: &str, antennas: &mut CarrierAntennas) {
+    let hover_text = egui::RichText::new("Additional antennas of the carrier, oriented as its antenna and sharing its beam,\ntheir phase centers displaced from its own (e.g. fore/aft phase centers)")
+        .color(TEXT_COLOR)
+        .monospace();
+    ui.label(egui::RichText::new(title).strong()).on_hover_text(hover_text);
+    let mut removed_index = None;
+    if !antennas.displacements_m.is_empty() {
+        egui::Grid::new(id)
+            .num_columns(6)
+            .striped(true)
+            .spacing([10.0, 5.0])
+            .show(ui, |ui| {
+                for header in ["Antenna", "Forward", "Right", "Down", "Incidence", ""] {
+                    ui.label(egui::RichText::new(header).strong());
+                }
+                ui.end_row();
+                for (index, displacement_m) in antennas.displacements_m.iter_mut().enumerate() {
+                    ui.label(format!("{}", index + 1));
+                    for value_m in [&mut displacement_m.x, &mut displacement_m.y, &mut displacement_m.z] {
+                        let response = ui.add(
+                            egui::DragValue::new(value_m)
+                                .custom_parser(expression::parse)
+                                .update_while_editing(false)
+                                .speed(0.1)
+                                .range(-MAX_DISPLACEMENT_M..=MAX_DISPLACEMENT_M)
+                                .fixed_decimals(2)
+                                .suffix(" m")
+                        );
+                        antennas.footprints_need_update |= response.changed();
+                    }
+                    ui.label(
+                        antennas.footprints.get(index)
+                            .map_or("-".to_string(), |footprint| format!("{:.2}°", footprint.loc_incidence_center_deg))
+                    );
+                    if ui.small_button("🗑").on_hover_text("Removes the antenna").clicked() {
+                        removed_index = Some(index);
+                    }
+                    ui.end_row();
+                }
+            });
+    }
+    if let Some(index) = removed_index {
+        antennas.displacements_m.remove(index);
+        antennas.footprints_need_update = true;
+    }
+    let hover_text = egui::RichText::new("Adds an antenna 1 m behind the last one")
+        .color(TEXT_COLOR)
+        .monospace();
+    if ui.button("Add").on_hover_text(hover_text).clicked() {
+        let last_m = antennas.displacements_m.last().copied().unwrap_or(DVec3::ZERO);
+        antennas.displacements_m.push(last_m - DVec3::X);
+        antennas.footprints_need_update = true;
+    }
+}
+
+fn gmti_ui(
+    ui: &mut egui::Ui,
+    widget: &mut AntennasWidget,
+    tx_carrier_state: &TxCarrierState,
+    rx_carrier_state: &RxCarrierState,
+    nesz: f64,
+) {
+    let hover_text = egui::RichText::new("Ground moving target indication with the receiver antenna and its first\nadditional antenna: along-track interferometry (ATI) and displaced phase\ncenter antenna (DPCA) velocity sensitivity")
+        .color(TEXT_COLOR)
+        .monospace();
+    ui.label(egui::RichText::new("GMTI").strong()).on_hover_text(hover_text);
+    let Some(displacement_m) = widget.rx.displacements_m.first() else {
+        ui.label(egui::RichText::new("Add a receiver antenna to form the GMTI channels").color(TEXT_COLOR));
+        return;
+    };
+    // Separation of the phase centers along the Rx velocity
+    let carrier_state = &rx_carrier_state.inner;
+    // A fixed receiver sees the scene from the same positions: no time lag
+    let velocity_mps = if rx_carrier_state.is_stationary { 0.0 } else { carrier_state.velocity_vector_mps.length() };
+    let baseline_m = (carrier_state.rotation() * *displacement_m).dot(carrier_state.velocity_vector_mps.normalize_or_zero());
+    let wavelength_m = SPEED_OF_LIGHT_IN_VACUUM / (tx_carrier_state.center_frequency_ghz * 1e9);
+    let interferometer = AlongTrackInterferometer::new(baseline_m, velocity_mps, wavelength_m);
+
+    let settings = &mut widget.gmti;
+    egui::Grid::new("gmti_settings_grid")
+        .num_columns(2)
+        .striped(false)
+        .spacing([20.0, 5.0])
+        .show(ui, |ui| {
+            let hover_text = egui::RichText::new("Clutter backscattering coefficient, compared to the NESZ")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Clutter σ0:").on_hover_text(hover_text.clone());
+            ui.add(
+                egui::DragValue::new(&mut settings.clutter_sigma0_db)
+                    .custom_parser(expression::parse)
+                    .update_while_editing(false)
+                    .speed(0.1)
+                    .range(-50.0..=20.0)
+                    .fixed_decimals(1)
+                    .suffix(" dB")
+            )
+            .on_hover_text(hover_text);
+            ui.end_row();
+            ui.label("Looks:");
+            ui.add(egui::DragValue::new(&mut settings.looks).speed(0.1).range(1..=100));
+            ui.end_row();
+            let hover_text = egui::RichText::new("Interferometric phase of the minimum detectable velocity, in clutter phase\nstandard deviations")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Detection threshold:").on_hover_text(hover_text.clone());
+            ui.add(
+                egui::DragValue::new(&mut settings.threshold)
+                    .speed(0.1)
+                    .range(1.0..=10.0)
+                    .fixed_decimals(1)
+                    .suffix(" σ")
+            )
+            .on_hover_text(hover_text);
+            ui.end_row();
+        });
+
+    let clutter_to_noise_ratio = 10f64.powf(0.1 * settings.clutter_sigma0_db) / nesz;
+    let hover = |text: &str| egui::RichText::new(text).color(TEXT_COLOR).monospace();
+    egui::Grid::new("gmti_grid")
+        .num_columns(2)
+        .striped(true)
+        .spacing([20.0, 5.0])
+        .show(ui, |ui| {
+            ui.label("Eff. along-track baseline:").on_hover_text(hover("Half the along-track separation of the receive phase centers, with a single\ntransmitter"));
+            ui.label(interferometer.map_or("-".to_string(), |interferometer| format!("{:.3} m", interferometer.effective_baseline_m)));
+            ui.end_row();
+            ui.label("ATI time lag:");
+            ui.label(interferometer.map_or("-".to_string(), |interferometer| format!("{:.3} ms", interferometer.time_lag_s() * 1e3)));
+            ui.end_row();
+            ui.label("Blind velocity:").on_hover_text(hover("Radial velocity wrapping the interferometric phase by 2π"));
+            ui.label(interferometer.map_or("-".to_string(), |interferometer| format!("{:.3} m/s", interferometer.blind_velocity_mps())));
+            ui.end_row();
+            ui.label("Clutter-to-noise ratio:");
+            ui.label(
+                Some(clutter_to_noise_ratio)
+                    .filter(|ratio| ratio.is_finite())
+                    .map_or("-".to_string(), |ratio| format!("{:.2} dB", 10.0 * ratio.log10()))
+            );
+            ui.end_row();
+            ui.label("Clutter phase std:");
+            ui.label(
+                phase_std_rad(clutter_to_noise_ratio, settings.looks)
+                    .map_or("-".to_string(), |phase_std_rad| format!("{:.2}°", phase_std_rad.to_degrees()))
+            );
+            ui.end_row();
+            ui.label("Min. detectable velocity:").on_hover_text(hover("Minimum detectable radial velocity (half the bistatic range rate)"));
+            ui.label(
+                interferometer
+                    .and_then(|interferometer| interferometer.minimum_detectable_velocity_mps(clutter_to_noise_ratio, settings.looks, settings.threshold))
+                    .map_or("-".to_string(), |mdv_mps| format!("{mdv_mps:.3} m/s"))
+            );
+            ui.end_row();
+            ui.label("DPCA pulse lag:").on_hover_text(hover("Pulses best matching the time lag at the Tx PRF, and the remaining\ndisplacement of the phase centers (0 m when the DPCA condition is met)"));
+            ui.label(
+                interferometer
+                    .and_then(|interferometer| interferometer.dpca_pulse_lag(tx_carrier_state.prf_hz))
+                    .map_or("-".to_string(), |(pulses, residual_m)| format!("{pulses} pulses, {:.1} mm off", residual_m * 1e3))
+            );
+            ui.end_row();
+        });
+}
@@ -2,14 +2,16 @@ use bevy::prelude::*;
 use bevy_egui::{egui, EguiContexts, EguiPlugin, EguiPrimaryContextPass};
 
 use crate::{
+    preferences::UiTheme,
     scene::{
         TxCarrierState, TxAntennaState, TxAntennaBeamState, TxAntennaBeamFootprintState,
         RxCarrierState, RxAntennaState, RxAntennaBeamState, RxAntennaBeamFootprintState,
-        BsarInfosState
+        BsarInfosState, CarrierLimitsState
     },
     ui::{
-        bsar_infos_ui, carrier_infos_ui, show_gaf_window, GafState,
-        MenuPlugin, MenuWidget, TxPanelPlugin, TxPanelWidget, RxPanelPlugin, RxPanelWidget
+        bsar_infos_ui, carrier_infos_ui, detach_button, show_gaf_window, status_bar_ui, AmbiguityPlugin, AnnotationPlugin, AntennaPatternPlugin, AntennasPlugin, BurstPlugin, ClutterLocusPlugin, ConstellationPlugin, CoveragePlugin, DisplayPlugin, DopplerSpectrumPlugin, ExercisePlugin, ExportPlugin, ExtensionsPlugin, FeedPlugin, FrequencyOffsetPlugin, GafState, GeoReferencePlugin, GroundTrackPlugin, HorizonPlugin, HudPlugin, HudWidget, IncidencePlugin, InterferometryPlugin, IsoProbePlugin, IsoSpacingPlugin, KSpacePlugin,
+        MenuPlugin, MenuWidget, MovingTargetPlugin, MultiBeamPlugin, NadirPlugin, NoiseTemperaturePlugin, OrbitPlugin, PairMatrixPlugin, PhasedArrayPlugin, PipelinePlugin, PolarCoveragePlugin, PreferencesPlugin, PrfSuggestionPlugin, PrfTimingPlugin, PropagationPlugin, PsfPlugin, RawDataPlugin, RoiPlugin, SceneStatisticsPlugin, SecondWindowPlugin, SecondWindowWidget, StatusBarWidget, TimelinePlugin, TxPanelPlugin, TxPanelWidget, RxPanelPlugin, RxPanelWidget, ScenarioPlugin, SnippetsPlugin, SquintPlugin,
+        SteeringPlugin, TerrainPlugin, ToastPlugin, Toasts, TopViewPlugin, TutorialPlugin, VisibilityPlugin
     }
 };
 
@@ -20,14 +22,19 @@ impl Plugin for AppPlugin {
         app
             .init_resource::<SidePanelRects>()
             .init_resource::<GafState>()
+            .init_resource::<StatusBarWidget>()
             .add_plugins(EguiPlugin::default())
-            .add_plugins((MenuPlugin, TxPanelPlugin, RxPanelPlugin))
+            .add_plugins((PipelinePlugin, MenuPlugin, TxPanelPlugin, RxPanelPlugin, GeoReferencePlugin, TimelinePlugin, DisplayPlugin))
+            .add_plugins((RawDataPlugin, CoveragePlugin, OrbitPlugin, ConstellationPlugin, VisibilityPlugin, TerrainPlugin, HorizonPlugin, PropagationPlugin, SquintPlugin, ExportPlugin, TopViewPlugin, IncidencePlugin, SteeringPlugin, PhasedArrayPlugin, MultiBeamPlugin))
+            .add_plugins((BurstPlugin, NadirPlugin, FeedPlugin, ToastPlugin, HudPlugin, SecondWindowPlugin, GroundTrackPlugin, AnnotationPlugin, AntennaPatternPlugin, PolarCoveragePlugin, IsoProbePlugin, RoiPlugin, SceneStatisticsPlugin, InterferometryPlugin, AntennasPlugin))
+            .add_plugins((MovingTargetPlugin, ClutterLocusPlugin, TutorialPlugin, ExercisePlugin, PreferencesPlugin, ScenarioPlugin, ExtensionsPlugin, IsoSpacingPlugin, PrfSuggestionPlugin, PrfTimingPlugin, AmbiguityPlugin, NoiseTemperaturePlugin, SnippetsPlugin, FrequencyOffsetPlugin, PairMatrixPlugin))
+            .add_plugins((KSpacePlugin, PsfPlugin, DopplerSpectrumPlugin))
             .add_systems(Startup, ui_setup)
             .add_systems(EguiPrimaryContextPass, ui_system);
     }
 }
 
-/// Screen-space horizontal extents of the side panels in logical points
+/// Screen-space extents of the side panels and status bar in logical points
 /// (same unit and origin as [`Window::cursor_position`]).
 ///
 /// Updated every frame by [`ui_system`] from the actual panel rectangles and
@@ -41,6 +48,22 @@ pub struct SidePanelRects {
     pub left_max_x: f32,
     /// Left edge of the right (Receiver) panel
     pub right_min_x: f32,
+    /// Top edge of the bottom status bar
+    pub bottom_min_y: f32,
+}
+
+impl SidePanelRects {
+    /// Gets the 3D view between the panels in egui points, within the
+    /// content area of `ctx`.
+    pub fn view_rect(&self, ctx: &egui::Context) -> egui::Rect {
+        // From logical pixels to egui points
+        let points_per_pixel = ctx.zoom_factor().recip();
+        egui::Rect::from_min_max(
+            egui::pos2(points_per_pixel * self.left_max_x, 0.0),
+            egui::pos2(points_per_pixel * self.right_min_x, points_per_pixel * self.bottom_min_y),
+        )
+        .intersect(ctx.content_rect())
+    }
 }
 
 impl Default for SidePanelRects {
@@ -48,6 +71,7 @@ impl Default for SidePanelRects {
         Self {
             left_max_x: 0.0,
             right_min_x: f32::INFINITY,
+            bottom_min_y: f32::INFINITY,
         }
     }
 }
@@ -60,7 +84,15 @@ fn ui_setup(
     // Install image loaders for egui
     egui_extras::install_image_loaders(ctx); // This gives us image support
 
-    // UI style
+    // UI style, the theme of the preferences being applied by the preferences
+    // UI system
+    ctx.set_visuals_of(egui::Theme::Dark, ui_visuals(UiTheme::default()));
+
+    Ok(())
+}
+
+/// Gets the egui visuals of a theme.
+pub(crate) fn ui_visuals(theme: UiTheme) -> egui::Visuals {
     let mut dark_visuals = egui::Theme::Dark.default_visuals();
     // Squared corners
     dark_visuals.window_corner_radius = egui::CornerRadius::ZERO;
@@ -74,9 +106,16 @@ fn ui_setup(
     dark_visuals.slider_trailing_fill = true;
     // Alternate background color for striped tables
     dark_visuals.faint_bg_color = egui::Color32::BLACK; // Use black for faint background color
-    ctx.set_visuals_of(egui::Theme::Dark, dark_visuals);
-
-    Ok(())
+    if theme == UiTheme::Black {
+        dark_visuals.override_text_color = Some(egui::Color32::WHITE);
+        dark_visuals.panel_fill = egui::Color32::BLACK;
+        dark_visuals.window_fill = egui::Color32::BLACK;
+        dark_visuals.extreme_bg_color = egui::Color32::BLACK;
+        dark_visuals.window_stroke = egui::Stroke::new(1.0, egui::Color32::WHITE);
+        // Black being the background, stripes are lighter
+        dark_visuals.faint_bg_color = egui::Color32::from_gray(40);
+    }
+    dark_visuals
 }
 
 
@@ -97,9 +136,14 @@ fn ui_system(
     mut rx_antenna_beam_state: ResMut<RxAntennaBeamState>,
     rx_antenna_beam_footprint_state: Res<RxAntennaBeamFootprintState>,
     // BSAR infos resource
-    mut bsar_infos_state: ResMut<BsarInfosState>,
-    // GAF plot texture cache
-    mut gaf_state: ResMut<GafState>,
+    bsar_infos_state: Res<BsarInfosState>,
+    // Carrier input limits
+    carrier_limits_state: Res<CarrierLimitsState>,
+    // GAF plot texture cache, status bar and HUD metrics, detached infos
+    (mut gaf_state, mut status_bar_widget, mut hud_widget, mut second_window_widget):
+        (ResMut<GafState>, ResMut<StatusBarWidget>, ResMut<HudWidget>, ResMut<SecondWindowWidget>),
+    // Save outcomes of the GAF plot
+    mut toasts: ResMut<Toasts>,
     // Panel extents for camera input blocking (see camera.rs)
     mut side_panel_rects: ResMut<SidePanelRects>
 ) -> Result {
@@ -129,20 +173,26 @@ fn ui_system(
         }
     );
 
-        // Receiver panel
+    // The panels are drawn every frame: the state resources are only marked
+    // changed when edited, which triggers the update systems
+    let (mut is_tx_changed, mut is_rx_changed) = (false, false);
+
+    // Receiver panel
     let rx_panel_response = egui::Panel::right("Receiver")
         .resizable(false)
         .default_size(300.0)
         .max_size(300.0)
         .show_separator_line(true)
         .show_animated_inside(&mut viewport_ui, menu_widget.is_rx_panel_opened, |ui| {
-            rx_panel_widget.ui(
+            // Written without change detection, set below when edited
+            is_rx_changed |= rx_panel_widget.ui(
                 ui,
-                &menu_widget,
-                &mut rx_carrier_state,
-                &mut rx_antenna_state,
-                &mut rx_antenna_beam_state,
-                &mut bsar_infos_state,
+                &mut menu_widget,
+                rx_carrier_state.bypass_change_detection(),
+                rx_antenna_state.bypass_change_detection(),
+                rx_antenna_beam_state.bypass_change_detection(),
+                &bsar_infos_state,
+                &carrier_limits_state,
             );
             ui.allocate_rect(ui.available_rect_before_wrap(), egui::Sense::hover());
         });
@@ -154,42 +204,53 @@ fn ui_system(
         .max_size(300.0)
         .show_separator_line(true)
         .show_animated_inside(&mut viewport_ui, menu_widget.is_tx_panel_opened, |ui| {
-            tx_panel_widget.ui(
+            // Written without change detection, set below when edited
+            is_tx_changed |= tx_panel_widget.ui(
                 ui,
-                &mut menu_widget,
-                &mut rx_panel_widget,
-                &mut tx_carrier_state,
-                &mut tx_antenna_state,
-                &mut tx_antenna_beam_state,
-                &mut rx_carrier_state,
-                &mut rx_antenna_state,
-                &mut rx_antenna_beam_state,
+                tx_carrier_state.bypass_change_detection(),
+                tx_antenna_state.bypass_change_detection(),
+                tx_antenna_beam_state.bypass_change_detection(),
+                &carrier_limits_state,
             );
             ui.allocate_rect(ui.available_rect_before_wrap(), egui::Sense::hover());
         });
 
+    if is_tx_changed {
+        tx_carrier_state.set_changed();
+        tx_antenna_state.set_changed();
+        tx_antenna_beam_state.set_changed();
+    }
+    if is_rx_changed {
+        rx_carrier_state.set_changed();
+        rx_antenna_state.set_changed();
+        rx_antenna_beam_state.set_changed();
+    }
+
+    // Status bar, between the side panels
+    let status_bar_response = egui::Panel::bottom("status_bar")
+        .resizable(false)
+        .show_separator_line(true)
+        .show_inside(&mut viewport_ui, |ui| {
+            status_bar_ui(ui, &mut status_bar_widget, &bsar_infos_state.inner);
+        });
+
     // Update the panel extents used to block the camera when the pointer is over
-    // a panel (includes the open/close animation since the actual rects are used)
-    side_panel_rects.left_max_x = menu_response.response.rect.max.x.max(
+    // a panel (includes the open/close animation since the actual rects are used),
+    // from egui points to logical pixels
+    let zoom_factor = ctx.zoom_factor();
+    side_panel_rects.left_max_x = zoom_factor * menu_response.response.rect.max.x.max(
         tx_panel_response.as_ref().map_or(0.0, |r| r.response.rect.max.x)
     );
-    side_panel_rects.right_min_x = rx_panel_response
+    side_panel_rects.right_min_x = zoom_factor * rx_panel_response
         .as_ref()
         .map_or(f32::INFINITY, |r| r.response.rect.min.x);
-    // Forces Rx updates in Monostatic case when Tx panel is closed
-    if menu_widget.is_monostatic &&
-       !menu_widget.was_monostatic &&
-       !menu_widget.is_tx_panel_opened {
-        rx_carrier_state.inner = tx_carrier_state.inner.clone();
-        rx_antenna_state.inner = tx_antenna_state.inner.clone();
-        rx_antenna_beam_state.inner = tx_antenna_beam_state.inner.clone();
-        rx_panel_widget.transform_needs_update = true;
-        rx_panel_widget.velocity_vector_needs_update = true;
-        rx_panel_widget.system_needs_update = true;
-        menu_widget.force_rx_system_update = true;
-        menu_widget.was_monostatic = true;
+    side_panel_rects.bottom_min_y = zoom_factor * status_bar_response.response.rect.min.y;
+
+    // The infos and the GAF plot live in the second window while detached
+    if second_window_widget.is_detached {
+        return Ok(());
     }
-    
+
     // Tx Infos
     let tx_infos_window = egui::Window::new("Tx Infos")
         .resizable(false)
@@ -212,7 +273,8 @@ fn ui_system(
             ui,
             &tx_carrier_state.inner,
             &tx_antenna_beam_footprint_state.inner,
-            "tx"
+            "tx",
+            &mut hud_widget,
         );
     });
 
@@ -238,7 +300,8 @@ fn ui_system(
             ui,
             &rx_carrier_state.inner,
             &rx_antenna_beam_footprint_state.inner,
-            "rx"
+            "rx",
+            &mut hud_widget,
         );
     });
 
@@ -253,9 +316,12 @@ fn ui_system(
         .default_open(false)
         .anchor(egui::Align2::CENTER_TOP, egui::Vec2::ZERO);
     bsar_infos_window.show(ctx, |ui| {
+        detach_button(ui, &mut second_window_widget);
         bsar_infos_ui(
             ui,
-            &bsar_infos_state.inner
+            &bsar_infos_state.inner,
+            menu_widget.geometry_mode,
+            &mut hud_widget,
         );
     });
 
@@ -264,6 +330,7 @@ fn ui_system(
         ctx,
         &mut menu_widget.is_gaf_opened,
         &mut gaf_state,
+        &mut toasts,
         &bsar_infos_state.inner,
         tx_carrier_state.bandwidth_mhz * 1e6, // MHz -> Hz
         tx_carrier_state.center_frequency_ghz * 1e9, // GHz -> Hz
@@ -0,0 +1,227 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts, EguiPrimaryContextPass};
+
+use crate::{
+    burst::{burst_dwell, resolution_for_dwell_m, BurstMode},
+    expression,
+    scene::{BsarInfosState, RxCarrierState, TxCarrierState},
+    ui::{CarrierSteering, SteeringWidget},
+};
+
+const TEXT_COLOR: egui::Color32 = egui::Color32::from_rgb(200, 200, 200);
+const ERROR_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 120, 120);
+/// Colors of the bursts on the timeline, the active one being brighter
+const BURST_COLOR: egui::Color32 = egui::Color32::from_rgb(150, 100, 200);
+const ACTIVE_BURST_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 100, 255);
+
+pub struct BurstPlugin;
+
+impl Plugin for BurstPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<BurstWidget>()
+            .add_systems(EguiPrimaryContextPass, burst_ui_system);
+    }
+}
+
+/// Carrier whose steering schedule defines the bursts.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum BurstCarrier {
+    Tx,
+    Rx,
+}
+
+/// Burst timeline of a ScanSAR / TOPS acquisition: each beam position of the
+/// "Beam Steering" schedule is the burst of one sub-swath.
+#[derive(Resource)]
+pub struct BurstWidget {
+    pub carrier: BurstCarrier,
+    pub is_tops: bool,
+    /// Azimuth steering rate of the TOPS bursts
+    pub tops_steering_rate_degps: f64,
+}
+
+impl Default for BurstWidget {
+    fn default() -> Self {
+        Self {
+            carrier: BurstCarrier::Tx,
+            is_tops: false,
+            tops_steering_rate_degps: 1.0,
+        }
+    }
+}
+
+impl BurstWidget {
+    /// Gets the burst acquisition mode.
+    pub fn mode(&self) -> BurstMode {
+        if self.is_tops {
+            BurstMode::Tops { steering_rate_degps: self.tops_steering_rate_degps }
+        } else {
+            BurstMode::ScanSar
+        }
+    }
+}
+
+fn burst_ui_system(
+    mut contexts: EguiContexts,
+    mut burst_widget: ResMut<BurstWidget>,
+    steering_widget: Res<SteeringWidget>,
+    bsar_infos_state: Res<BsarInfosState>,
+    tx_carrier_state: Res<TxCarrierState>,
+    rx_carrier_state: Res<RxCarrierState>,
+) -> Result {
+    let ctx = contexts.ctx_mut()?;
+
+    let burst_window = egui::Window::new("Burst Timeline")
+        .resizable(false)
+        .constrain(true)
+        .collapsible(true)
+        .title_bar(true)
+        .enabled(true)
+        .default_open(false)
+        .default_pos(egui::pos2(320.0, 680.0));
+    burst_window.show(ctx, |ui| {
+        let (steering, time_s) = match burst_widget.carrier {
+            BurstCarrier::Tx => (&steering_widget.tx, tx_carrier_state.inner.time_s),
+            BurstCarrier::Rx => (&steering_widget.rx, rx_carrier_state.inner.time_s),
+        };
+        burst_ui(ui, &mut burst_widget, steering, &bsar_infos_state, time_s);
+    });
+
+    Ok(())
+}
+
+fn burst_ui(
+    ui: &mut egui::Ui,
+    widget: &mut BurstWidget,
+    steering: &CarrierSteering,
+    bsar_infos_state: &BsarInfosState,
+    time_s: f64,
+) {
+    ui.horizontal(|ui| {
+        ui.label("Bursts: ");
+        ui.radio_value(&mut widget.carrier, BurstCarrier::Tx, "Tx");
+        ui.radio_value(&mut widget.carrier, BurstCarrier::Rx, "Rx");
+    });
+    let hover_text = egui::RichText::new("ScanSAR: the beam stays still during a burst, the targets dwell varying with their position\nTOPS: the beam sweeps along azimuth during a burst, every target getting the same dwell")
+        .color(TEXT_COLOR)
+        .monospace();
+    ui.horizontal(|ui| {
+        ui.label("Mode: ").on_hover_text(hover_text.clone());
+        ui.radio_value(&mut widget.is_tops, false, "ScanSAR").on_hover_text(hover_text.clone());
+        ui.radio_value(&mut widget.is_tops, true, "TOPS").on_hover_text(hover_text);
+    });
+    if widget.is_tops {
+        let hover_text = egui::RichText::new("Sets the azimuth steering rate of the beam during the bursts (0.01 - 10 °/s)")
+            .color(TEXT_COLOR)
+            .monospace();
+        ui.horizontal(|ui| {
+            ui.label("Steering rate: ").on_hover_text(hover_text.clone());
+            ui.add(
+                egui::DragValue::new(&mut widget.tops_steering_rate_degps)
+                    .custom_parser(expression::parse)
+                    .update_while_editing(false)
+                    .speed(0.01)
+                    .range(0.01..=10.0)
+                    .fixed_decimals(2)
+                    .suffix(" °/s")
+            ).on_hover_text(hover_text);
+        });
+    }
+
+    let schedule = steering.schedule.as_ref().filter(|_| steering.is_enabled);
+    let Some(schedule) = schedule.filter(|_| !steering.beam_footprints.is_empty()) else {
+        ui.label(egui::RichText::new("Define the bursts with a \"Beam Steering\" schedule").color(TEXT_COLOR));
+        return;
+    };
+    let cycle_s = schedule.period_s();
+    let bursts: Vec<(f64, f64)> = schedule.positions().iter()
+        .scan(0.0, |start_s, position| {
+            let burst = (*start_s, position.dwell_s);
+            *start_s += position.dwell_s;
+            Some(burst)
+        })
+        .collect();
+
+    // Two burst cycles from the one of the current time
+    let cycle_start_s = (time_s / cycle_s).floor() * cycle_s;
+    egui_plot::Plot::new("burst_timeline_plot")
+        .width(280.0)
+        .height(40.0 + 20.0 * bursts.len() as f32)
+        .include_x(cycle_start_s)
+        .include_x(cycle_start_s + 2.0 * cycle_s)
+        .include_y(0.5)
+        .include_y(bursts.len() as f64 + 0.5)
+        .x_axis_label("Time [s]")
+        .y_axis_label("Sub-swath")
+        .allow_drag(false)
+        .allow_zoom(false)
+        .allow_scroll(false)
+        .show(ui, |plot_ui| {
+            for cycle in 0..2 {
+                for (index, &(burst_start_s, burst_s)) in bursts.iter().enumerate() {
+                    let start_s = cycle_start_s + cycle as f64 * cycle_s + burst_start_s;
+                    let sub_swath = index as f64 + 1.0;
+                    let color = if cycle == 0 && steering.active_index == Some(index) { ACTIVE_BURST_COLOR } else { BURST_COLOR };
+                    plot_ui.polygon(
+                        egui_plot::Polygon::new(
+                            format!("Sub-swath {}", index + 1),
+                            vec![
+                                [start_s, sub_swath - 0.3],
+                                [start_s + burst_s, sub_swath - 0.3],
+                                [start_s + burst_s, sub_swath + 0.3],
+                                [start_s, sub_swath + 0.3],
+                            ]
+                        )
+                        .fill_color(color.gamma_multiply(0.6))
+                        .stroke(egui::Stroke::new(1.0, color))
+                    );
+                }
+            }
+            plot_ui.vline(egui_plot::VLine::new("Time", time_s).color(egui::Color32::WHITE).width(1.5));
+        });
+
+    // The resolution scales from the scene center one, integrated over the full illumination
+    let bsar_infos = &bsar_infos_state.inner;
+    let resolution_m = |dwell_s: f64| resolution_for_dwell_m(bsar_infos.ground_lateral_resolution_m, bsar_infos.integration_time_s, dwell_s);
+    let mode = widget.mode();
+    let mut missing_sub_swaths = Vec::new();
+    let hover_text = egui::RichText::new("Dwell: time during which a target of the sub-swath is seen by the bursts (min - max over\nthe target positions), its variation driving the scalloping\nResolution: ground lateral resolution at the mean and the min dwell, scaled from the\nscene center one (inversely proportional to the integration time)")
+        .color(TEXT_COLOR)
+        .monospace();
+    egui::Grid::new("burst_grid")
+        .num_columns(5)
+        .striped(true)
+        .spacing([10.0, 5.0])
+        .show(ui, |ui| {
+            for header in ["Sub-swath", "Burst", "Dwell", "Variation", "Resolution"] {
+                ui.label(egui::RichText::new(header).strong()).on_hover_text(hover_text.clone());
+            }
+            ui.end_row();
+            for (index, (&(burst_start_s, burst_s), footprint)) in bursts.iter().zip(steering.beam_footprints.iter()).enumerate() {
+                let dwell = burst_dwell(
+                    mode,
+                    footprint.illumination_time_s,
+                    burst_start_s,
+                    burst_s,
+                    cycle_s,
+                    footprint.ground_angular_velocity_degps
+                );
+                if dwell.min_s <= 0.0 {
+                    missing_sub_swaths.push(index + 1);
+                }
+                ui.label(format!("{}", index + 1));
+                ui.label(format!("{burst_s:.3} s"));
+                ui.label(format!("{:.3} - {:.3} s", dwell.min_s, dwell.max_s));
+                ui.label(format!("{:.1} %", 100.0 * dwell.variation()));
+                ui.label(format!("{:.2} / {:.2} m", resolution_m(dwell.mean_s), resolution_m(dwell.min_s)));
+                ui.end_row();
+            }
+        });
+    ui.label(egui::RichText::new(format!("Burst cycle: {cycle_s:.3} s")).color(TEXT_COLOR));
+    if !missing_sub_swaths.is_empty() {
+        let sub_swaths = missing_sub_swaths.iter().map(usize::to_string).collect::<Vec<_>>().join(", ");
+        let text = format!("⚠ Targets missed between the bursts in sub-swaths {sub_swaths}\n(illumination shorter than the burst cycle gap)");
+        ui.label(egui::RichText::new(text).color(ERROR_COLOR));
+    }
+}
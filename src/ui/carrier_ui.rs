@@ -1,8 +1,12 @@
+use std::ops::RangeInclusive;
+
 use bevy_egui::egui;
 
 use crate::{
-    constants::{MAX_HEIGHT_M, MAX_VELOCITY_MPS},
+    constants::{MAX_BEAM_WIDTH_DEG, MAX_SPEED_RATE_MPS2, MAX_TOWER_HEIGHT_M, MAX_TURN_RATE_DEGPS},
     entities::{AntennaBeamState, AntennaState, CarrierState},
+    expression,
+    limits::CarrierLimits,
     ui::menu::RESET_ICON,
 };
 
@@ -22,13 +26,71 @@ pub fn heading_with_reset(ui: &mut egui::Ui, title: egui::RichText, hover: &str)
         .clicked()
 }
 
+/// Right-click menu of a parameter widget restoring that single parameter to
+/// its default value; the caller's change check then raises the update flags.
+pub fn reset_menu(response: &egui::Response, value: &mut f64, default: f64) {
+    response.context_menu(|ui| reset_button(ui, value, default));
+}
+
+/// "Reset to default" entry of the right-click menus, disabled when `value`
+/// is already `default`.
+fn reset_button(ui: &mut egui::Ui, value: &mut f64, default: f64) {
+    let button = egui::Button::image_and_text(RESET_ICON, format!("Reset to default ({default})"));
+    if ui.add_enabled(*value != default, button).clicked() {
+        *value = default;
+    }
+}
+
+/// Right-click menu of an antenna gain widget: the reset of [`reset_menu`]
+/// plus the gain of a typical aperture antenna with the beam widths of
+/// `antenna_beam_state`.
+pub fn gain_menu(response: &egui::Response, antenna_beam_state: &mut AntennaBeamState, default: f64) {
+    response.context_menu(|ui| {
+        reset_button(ui, &mut antenna_beam_state.one_way_gain_dbi, default);
+        let gain_dbi = (10.0 * antenna_beam_state.gain_from_beam_widths_dbi()).round() / 10.0;
+        let button = egui::Button::new(format!("From the beam widths ({gain_dbi:.1} dBi)"));
+        let hover_text = "Typical aperture antenna: G = 26000 / (azimuth beam width × elevation beam width), in degrees";
+        if ui.add_enabled(gain_dbi.is_finite() && antenna_beam_state.one_way_gain_dbi != gain_dbi, button)
+            .on_hover_text(hover_text)
+            .clicked()
+        {
+            antenna_beam_state.one_way_gain_dbi = gain_dbi;
+        }
+    });
+}
+
+/// Logarithmic slider for the wide-range parameters (height, velocity, PRF,
+/// bandwidth): every decade gets the same slider length, so that both UAV-scale
+/// and satellite-scale values can be set precisely, and the value box still
+/// accepts a typed value or expression. Values below `smallest_positive` snap to 0 when the
+/// range includes it.
+pub fn log_slider<'a>(
+    value: &'a mut f64,
+    range: RangeInclusive<f64>,
+    smallest_positive: f64,
+    decimals: usize,
+    suffix: &str,
+) -> egui::Slider<'a> {
+    egui::Slider::new(value, range)
+        .logarithmic(true)
+        .smallest_positive(smallest_positive)
+        .custom_parser(expression::parse)
+        .update_while_editing(false)
+        .fixed_decimals(decimals)
+        .suffix(suffix)
+}
+
 /// Carrier + antenna geometry settings UI, shared by the Transmitter and
 /// Receiver panels.
 ///
 /// `id_salt` ("tx" | "rx") rebuilds the historical egui grid ids
 /// ("tx_carrier_grid", ...) so widget memory is preserved; it must not change.
 /// The `default_*` states are the side-specific defaults restored by the
-/// per-section reset buttons.
+/// per-section reset buttons, and `limits` bound the height and velocity.
+///
+/// `is_stationary` (Receiver only) adds the "fixed receiver" toggle: the
+/// carrier then stands still on a tower, whose height replaces the carrier
+/// height and velocity settings.
 ///
 /// Returns `true` when the title-row reset was clicked, i.e. the whole side
 /// must go back to its defaults. The carrier/antenna sections are restored
@@ -43,8 +105,10 @@ pub fn carrier_ui(
     default_carrier_state: &CarrierState,
     default_antenna_state: &AntennaState,
     default_antenna_beam_state: &AntennaBeamState,
+    limits: &CarrierLimits,
     transform_needs_update: &mut bool,
     velocity_vector_needs_update: &mut bool,
+    mut is_stationary: Option<&mut bool>,
 ) -> bool {
     let mut old_state = 0.0f64;
 
@@ -69,6 +133,10 @@ pub fn carrier_ui(
         carrier_state.heading_deg = default_carrier_state.heading_deg;
         carrier_state.elevation_deg = default_carrier_state.elevation_deg;
         carrier_state.bank_deg = default_carrier_state.bank_deg;
+        carrier_state.motion = default_carrier_state.motion;
+        if let Some(is_stationary) = is_stationary.as_deref_mut() {
+            *is_stationary = false;
+        }
         *transform_needs_update = true;
         *velocity_vector_needs_update = true;
     }
@@ -80,43 +148,116 @@ pub fn carrier_ui(
         .striped(false)
         .spacing([20.0, 5.0])
         .show(ui, |ui| {
+            // ***** Fixed receiver ***** //
+            if let Some(is_stationary) = is_stationary.as_deref_mut() {
+                let hover_text = egui::RichText::new("Pins the Receiver on a ground tower with zero velocity (fixed-receiver bistatic SAR):
+only the Transmitter motion contributes to the Doppler and the integration time")
+                    .color(egui::Color32::from_rgb(200, 200, 200))
+                    .monospace();
+                ui.label("Fixed receiver: ").on_hover_text(hover_text.clone());
+                if ui.checkbox(is_stationary, "").on_hover_text(hover_text).changed() {
+                    if *is_stationary {
+                        carrier_state.velocity_mps = 0.0;
+                        carrier_state.motion = default_carrier_state.motion;
+                        carrier_state.height_m = carrier_state.height_m.min(MAX_TOWER_HEIGHT_M);
+                    } else {
+                        carrier_state.velocity_mps = default_carrier_state.velocity_mps;
+                    }
+                    *transform_needs_update = true;
+                    *velocity_vector_needs_update = true;
+                }
+                ui.end_row();
+            }
+            let is_stationary = is_stationary.as_deref().copied().unwrap_or(false);
+
             // ***** Carrier height ***** //
-            let hover_text = egui::RichText::new(format!("Sets the Carrier's height relative to ground (0 - {} m)", MAX_HEIGHT_M))
+            let (label, max_height_m) = if is_stationary {
+                ("Tower height: ", MAX_TOWER_HEIGHT_M)
+            } else {
+                ("Height: ", limits.max_height_m)
+            };
+            let hover_text = egui::RichText::new(if is_stationary {
+                format!("Sets the Receiver's tower height relative to ground (0 - {} m)", MAX_TOWER_HEIGHT_M)
+            } else {
+                format!("Sets the Carrier's height relative to ground (0 - {} m)", limits.max_height_m)
+            })
                 .color(egui::Color32::from_rgb(200, 200, 200))
                 .monospace();
-            ui.label("Height: ").on_hover_text(hover_text.clone());
+            ui.label(label).on_hover_text(hover_text.clone());
             old_state = carrier_state.height_m;
-            ui.add(
-                egui::DragValue::new(&mut carrier_state.height_m)
-                    .update_while_editing(false)
-                    .speed(10.0)
-                    .range(0.0..=MAX_HEIGHT_M)
-                    .fixed_decimals(3)
-                    .suffix(" m")
-            ).on_hover_text(hover_text);
+            let response = ui.add(log_slider(&mut carrier_state.height_m, 0.0..=max_height_m, 0.1, 3, " m"))
+                .on_hover_text(hover_text);
+            reset_menu(&response, &mut carrier_state.height_m, default_carrier_state.height_m.min(max_height_m));
             if old_state != carrier_state.height_m {
                 *transform_needs_update = true;
             }
             ui.end_row();
 
-            // ***** Carrier velocity ***** //
-            let hover_text = egui::RichText::new(format!("Sets the Carrier's velocity (0 - {} m/s)", MAX_VELOCITY_MPS))
-                .color(egui::Color32::from_rgb(200, 200, 200))
-                .monospace();
-            ui.label("Velocity: ").on_hover_text(hover_text.clone());
-            old_state = carrier_state.velocity_mps;
-            ui.add(
-                egui::DragValue::new(&mut carrier_state.velocity_mps)
-                    .update_while_editing(false)
-                    .speed(10.0)
-                    .range(0.0..=MAX_VELOCITY_MPS)
-                    .fixed_decimals(3)
-                    .suffix(" m/s")
-            ).on_hover_text(hover_text);
-            if old_state != carrier_state.velocity_mps {
-                *velocity_vector_needs_update = true;
+            // ***** Carrier velocity (none for a fixed receiver) ***** //
+            if !is_stationary {
+                let hover_text = egui::RichText::new(format!("Sets the Carrier's velocity (0 - {} m/s)", limits.max_velocity_mps))
+                    .color(egui::Color32::from_rgb(200, 200, 200))
+                    .monospace();
+                ui.label("Velocity: ").on_hover_text(hover_text.clone());
+                old_state = carrier_state.velocity_mps;
+                let response = ui.add(log_slider(&mut carrier_state.velocity_mps, 0.0..=limits.max_velocity_mps, 0.1, 3, " m/s"))
+                    .on_hover_text(hover_text);
+                reset_menu(&response, &mut carrier_state.velocity_mps, default_carrier_state.velocity_mps.min(limits.max_velocity_mps));
+                if old_state != carrier_state.velocity_mps {
+                    *velocity_vector_needs_update = true;
+                }
+                ui.end_row();
+
+                // ***** Carrier turn rate ***** //
+                let hover_text = egui::RichText::new(format!("Sets the Carrier's heading change rate along its trajectory (-{0} - {0}°/s):
+  < 0 => turning left
+    0 => straight trajectory
+  > 0 => turning right
+note: the orientation set below is the one at time 0 s", MAX_TURN_RATE_DEGPS))
+                    .color(egui::Color32::from_rgb(200, 200, 200))
+                    .monospace();
+                ui.label("Turn rate: ").on_hover_text(hover_text.clone());
+                old_state = carrier_state.motion.turn_rate_degps;
+                let response = ui.add(
+                    egui::DragValue::new(&mut carrier_state.motion.turn_rate_degps)
+                        .custom_parser(expression::parse)
+                        .update_while_editing(false)
+                        .speed(0.1)
+                        .range(-MAX_TURN_RATE_DEGPS..=MAX_TURN_RATE_DEGPS)
+                        .fixed_decimals(3)
+                        .suffix("°/s")
+                ).on_hover_text(hover_text);
+                reset_menu(&response, &mut carrier_state.motion.turn_rate_degps, default_carrier_state.motion.turn_rate_degps);
+                if old_state != carrier_state.motion.turn_rate_degps {
+                    *transform_needs_update = true;
+                    *velocity_vector_needs_update = true;
+                }
+                ui.end_row();
+
+                // ***** Carrier speed rate ***** //
+                let hover_text = egui::RichText::new(format!("Sets the Carrier's speed change rate along its trajectory (-{0} - {0} m/s²),
+the speed stopping at 0 m/s
+note: the velocity set above is the one at time 0 s", MAX_SPEED_RATE_MPS2))
+                    .color(egui::Color32::from_rgb(200, 200, 200))
+                    .monospace();
+                ui.label("Speed rate: ").on_hover_text(hover_text.clone());
+                old_state = carrier_state.motion.speed_rate_mps2;
+                let response = ui.add(
+                    egui::DragValue::new(&mut carrier_state.motion.speed_rate_mps2)
+                        .custom_parser(expression::parse)
+                        .update_while_editing(false)
+                        .speed(0.1)
+                        .range(-MAX_SPEED_RATE_MPS2..=MAX_SPEED_RATE_MPS2)
+                        .fixed_decimals(3)
+                        .suffix(" m/s²")
+                ).on_hover_text(hover_text);
+                reset_menu(&response, &mut carrier_state.motion.speed_rate_mps2, default_carrier_state.motion.speed_rate_mps2);
+                if old_state != carrier_state.motion.speed_rate_mps2 {
+                    *transform_needs_update = true;
+                    *velocity_vector_needs_update = true;
+                }
+                ui.end_row();
             }
-            ui.end_row();
 
             // ***** Carrier heading ***** //
             let hover_text = egui::RichText::new("Sets the Carrier's heading angle (0 - 360°):\n    0° => North\n   90° => East\n  180° => South\n  270° => West\nnote: rotation along yaw axis, i.e. z-axis of Carrier's NED frame")
@@ -124,7 +265,7 @@ pub fn carrier_ui(
                 .monospace();
             ui.label("Heading: ").on_hover_text(hover_text.clone());
             old_state = carrier_state.heading_deg;
-            ui.add(
+            let response = ui.add(
                 egui::Slider::new(&mut carrier_state.heading_deg, 0.0..=360.0)
                     .suffix("°")
                     .smart_aim(false)
@@ -132,6 +273,7 @@ pub fn carrier_ui(
                     .drag_value_speed(1.0)
                     .fixed_decimals(3)
             ).on_hover_text(hover_text);
+            reset_menu(&response, &mut carrier_state.heading_deg, default_carrier_state.heading_deg);
             if old_state != carrier_state.heading_deg {
                 *transform_needs_update = true;
             }
@@ -143,7 +285,7 @@ pub fn carrier_ui(
                 .monospace();
             ui.label("Elevation: ").on_hover_text(hover_text.clone());
             old_state = carrier_state.elevation_deg;
-            ui.add(
+            let response = ui.add(
                 egui::Slider::new(&mut carrier_state.elevation_deg, -90.0..=90.0)
                     .suffix("°")
                     .smart_aim(false)
@@ -151,6 +293,7 @@ pub fn carrier_ui(
                     .drag_value_speed(1.0)
                     .fixed_decimals(3)
             ).on_hover_text(hover_text);
+            reset_menu(&response, &mut carrier_state.elevation_deg, default_carrier_state.elevation_deg);
             if old_state != carrier_state.elevation_deg {
                 *transform_needs_update = true;
             }
@@ -162,7 +305,7 @@ pub fn carrier_ui(
                 .monospace();
             ui.label("Bank: ").on_hover_text(hover_text.clone());
             old_state = carrier_state.bank_deg;
-            ui.add(
+            let response = ui.add(
                 egui::Slider::new(&mut carrier_state.bank_deg, -90.0..=90.0)
                     .suffix("°")
                     .smart_aim(false)
@@ -170,6 +313,7 @@ pub fn carrier_ui(
                     .drag_value_speed(1.0)
                     .fixed_decimals(3)
             ).on_hover_text(hover_text);
+            reset_menu(&response, &mut carrier_state.bank_deg, default_carrier_state.bank_deg);
             if old_state != carrier_state.bank_deg {
                 *transform_needs_update = true;
             }
@@ -206,7 +350,7 @@ pub fn carrier_ui(
                 .monospace();
             ui.label("Bearing: ").on_hover_text(hover_text.clone());
             old_state = antenna_state.heading_deg;
-            ui.add(
+            let response = ui.add(
                 egui::Slider::new(&mut antenna_state.heading_deg, -180.0..=180.0)
                     .suffix("°")
                     .smart_aim(false)
@@ -215,6 +359,7 @@ pub fn carrier_ui(
                     .fixed_decimals(3)
             )
             .on_hover_text(hover_text);
+            reset_menu(&response, &mut antenna_state.heading_deg, default_antenna_state.heading_deg);
             if old_state != antenna_state.heading_deg {
                 *transform_needs_update = true;
             }
@@ -226,7 +371,7 @@ pub fn carrier_ui(
                 .monospace();
             ui.label("Depression: ").on_hover_text(hover_text.clone());
             old_state = antenna_state.elevation_deg;
-            ui.add(
+            let response = ui.add(
                 egui::Slider::new(&mut antenna_state.elevation_deg, -90.0..=0.0)
                     .suffix("°")
                     .smart_aim(false)
@@ -235,6 +380,7 @@ pub fn carrier_ui(
                     .fixed_decimals(3)
             )
             .on_hover_text(hover_text);
+            reset_menu(&response, &mut antenna_state.elevation_deg, default_antenna_state.elevation_deg);
             if old_state != antenna_state.elevation_deg {
                 *transform_needs_update = true;
             }
@@ -246,7 +392,7 @@ pub fn carrier_ui(
                 .monospace();
             ui.label("Bank: ").on_hover_text(hover_text.clone());
             old_state = antenna_state.bank_deg;
-            ui.add(
+            let response = ui.add(
                 egui::Slider::new(&mut antenna_state.bank_deg, -90.0..=90.0)
                     .suffix("°")
                     .smart_aim(false)
@@ -255,12 +401,58 @@ pub fn carrier_ui(
                     .fixed_decimals(3)
             )
             .on_hover_text(hover_text);
+            reset_menu(&response, &mut antenna_state.bank_deg, default_antenna_state.bank_deg);
             if old_state != antenna_state.bank_deg {
                 *transform_needs_update = true;
             }
             ui.end_row();
         });
 
+    ui.separator();
+    if heading_with_reset(
+        ui,
+        egui::RichText::new("Mounting (lever arm)"),
+        "Resets the Antenna mounting offsets to their defaults"
+    ) || reset_all {
+        antenna_state.lever_arm_m = default_antenna_state.lever_arm_m;
+        *transform_needs_update = true;
+    }
+    ui.separator();
+
+    egui::Grid::new(format!("{id_salt}_antenna_mounting_grid"))
+        .num_columns(2)
+        .striped(false)
+        .spacing([20.0, 5.0])
+        .show(ui, |ui| {
+            // ***** Antenna lever arm ***** //
+            for (label, hover, value, default) in [
+                ("Forward: ", "Sets the Antenna phase center offset ahead of the Carrier reference point (-100 - 100 m)\nnote: x-axis of Carrier's NED frame", &mut antenna_state.lever_arm_m.x, default_antenna_state.lever_arm_m.x),
+                ("Right: ", "Sets the Antenna phase center offset right of the Carrier reference point (-100 - 100 m)\nnote: y-axis of Carrier's NED frame", &mut antenna_state.lever_arm_m.y, default_antenna_state.lever_arm_m.y),
+                ("Down: ", "Sets the Antenna phase center offset below the Carrier reference point (-100 - 100 m),\nthe ranges being taken from the phase center\nnote: z-axis of Carrier's NED frame", &mut antenna_state.lever_arm_m.z, default_antenna_state.lever_arm_m.z),
+            ] {
+                let hover_text = egui::RichText::new(hover)
+                    .color(egui::Color32::from_rgb(200, 200, 200))
+                    .monospace();
+                ui.label(label).on_hover_text(hover_text.clone());
+                old_state = *value;
+                let response = ui.add(
+                    egui::DragValue::new(value)
+                        .custom_parser(expression::parse)
+                        .update_while_editing(false)
+                        .speed(0.1)
+                        .range(-100.0..=100.0)
+                        .fixed_decimals(2)
+                        .suffix(" m")
+                )
+                .on_hover_text(hover_text);
+                reset_menu(&response, value, default);
+                if old_state != *value {
+                    *transform_needs_update = true;
+                }
+                ui.end_row();
+            }
+        });
+
     ui.separator();
     if heading_with_reset(
         ui,
@@ -280,13 +472,13 @@ pub fn carrier_ui(
         .spacing([20.0, 5.0])
         .show(ui, |ui| {
             // ***** Antenna beamwidth elevation ***** //
-            let hover_text = egui::RichText::new("Sets the Antenna's elevation half-power beamwidth (0 - 90°)\nnote: elevation beamwidth angle is defined in the x-z plane of Antenna's NED frame")
+            let hover_text = egui::RichText::new(format!("Sets the Antenna's elevation half-power beamwidth (0 - {MAX_BEAM_WIDTH_DEG}°)\nnote: elevation beamwidth angle is defined in the x-z plane of Antenna's NED frame"))
                 .color(egui::Color32::from_rgb(200, 200, 200))
                 .monospace();
             ui.label("Elevation: ").on_hover_text(hover_text.clone());
             old_state = antenna_beam_state.elevation_beam_width_deg;
-            ui.add(
-                egui::Slider::new(&mut antenna_beam_state.elevation_beam_width_deg, 0.0..=90.0)
+            let response = ui.add(
+                egui::Slider::new(&mut antenna_beam_state.elevation_beam_width_deg, 0.0..=MAX_BEAM_WIDTH_DEG)
                     .suffix("°")
                     .smart_aim(false)
                     .step_by(0.0)
@@ -294,19 +486,20 @@ pub fn carrier_ui(
                     .fixed_decimals(3)
             )
             .on_hover_text(hover_text);
+            reset_menu(&response, &mut antenna_beam_state.elevation_beam_width_deg, default_antenna_beam_state.elevation_beam_width_deg);
             if old_state != antenna_beam_state.elevation_beam_width_deg {
                 *transform_needs_update = true;
             }
             ui.end_row();
 
             // ***** Antenna azimuth ***** //
-            let hover_text = egui::RichText::new("Sets the Antenna's azimuth half-power beamwidth (0 - 90°)\nnote: azimuth beamwidth angle is defined in the x-y plane of Antenna's NED frame")
+            let hover_text = egui::RichText::new(format!("Sets the Antenna's azimuth half-power beamwidth (0 - {MAX_BEAM_WIDTH_DEG}°)\nnote: azimuth beamwidth angle is defined in the x-y plane of Antenna's NED frame"))
                 .color(egui::Color32::from_rgb(200, 200, 200))
                 .monospace();
             ui.label("Azimuth: ").on_hover_text(hover_text.clone());
             old_state = antenna_beam_state.azimuth_beam_width_deg;
-            ui.add(
-                egui::Slider::new(&mut antenna_beam_state.azimuth_beam_width_deg, 0.0..=90.0)
+            let response = ui.add(
+                egui::Slider::new(&mut antenna_beam_state.azimuth_beam_width_deg, 0.0..=MAX_BEAM_WIDTH_DEG)
                     .suffix("°")
                     .smart_aim(false)
                     .step_by(0.0)
@@ -314,6 +507,7 @@ pub fn carrier_ui(
                     .fixed_decimals(3)
             )
             .on_hover_text(hover_text);
+            reset_menu(&response, &mut antenna_beam_state.azimuth_beam_width_deg, default_antenna_beam_state.azimuth_beam_width_deg);
             if old_state != antenna_beam_state.azimuth_beam_width_deg {
                 *transform_needs_update = true;
             }
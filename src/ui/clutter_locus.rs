@@ -0,0 +1,208 @@
+use bevy::{math::{DVec2, DVec3}, prelude::*};
+use bevy_egui::{egui, EguiContexts, EguiPrimaryContextPass};
+
+use crate::{
+    bsar::{bistatic_range_sg, SPEED_OF_LIGHT_IN_VACUUM},
+    clutter::{ClutterLocus, RidgeBin},
+    coverage::CoverageRaster,
+    scene::{BsarInfosState, RxAntennaBeamFootprintState, RxCarrierState, TxAntennaBeamFootprintState, TxCarrierState},
+    ui::{MovingTargetWidget, UpdateStage},
+};
+
+const TEXT_COLOR: egui::Color32 = egui::Color32::from_rgb(200, 200, 200);
+/// Colors of the clutter samples and of the ridge edges
+const CLUTTER_COLOR: egui::Color32 = egui::Color32::from_rgb(0, 220, 220);
+const RIDGE_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 220, 0);
+/// Color of the moving target, as its markers
+const TARGET_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 140, 0);
+/// Cells per side of the ground grid sampled over the common footprint
+const LOCUS_GRID_SIZE: usize = 64;
+/// Range bins of the clutter ridge
+const RIDGE_BIN_COUNT: usize = 32;
+
+pub struct ClutterLocusPlugin;
+
+impl Plugin for ClutterLocusPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<ClutterLocusWidget>()
+            // Sampled over the system updated by the update systems
+            .add_systems(
+                PostUpdate,
+                update_clutter_locus
+                    .in_set(UpdateStage::Derived)
+                    .after(super::tx_panel::update_bsar_system)
+            )
+            .add_systems(EguiPrimaryContextPass, clutter_locus_ui_system);
+    }
+}
+
+/// Clutter locus of the common (Tx ∩ Rx) footprint in the range-Doppler
+/// domain, sampled on a ground grid, with its ridge: the Doppler span of the
+/// clutter per range bin.
+#[derive(Resource)]
+pub struct ClutterLocusWidget {
+    pub is_enabled: bool,
+    locus: ClutterLocus,
+    ridge: Vec<RidgeBin>,
+    needs_update: bool,
+}
+
+impl Default for ClutterLocusWidget {
+    fn default() -> Self {
+        Self {
+            is_enabled: false,
+            locus: ClutterLocus::default(),
+            ridge: Vec::new(),
+            needs_update: false,
+        }
+    }
+}
+
+/// Samples the clutter on a [`LOCUS_GRID_SIZE`] x [`LOCUS_GRID_SIZE`] ground
+/// grid framing the common footprint.
+pub(super) fn clutter_locus(
+    tx: (&TxCarrierState, &TxAntennaBeamFootprintState),
+    rx: (&RxCarrierState, &RxAntennaBeamFootprintState),
+) -> ClutterLocus {
+    // Footprints are in World frame (Y-up): their ground coordinates are (X, Z)
+    let ground = |points: &[DVec3]| -> Vec<(f64, f64)> {
+        points.iter().map(|point| (point.x, point.z)).collect()
+    };
+    let (tx_polygon, rx_polygon) = (ground(&tx.1.inner.points), ground(&rx.1.inner.points));
+    let max_abs = |polygon: &[(f64, f64)]| polygon.iter()
+        .map(|&(a, b)| a.abs().max(b.abs()))
+        .filter(|value| value.is_finite())
+        .fold(0.0, f64::max);
+    // The intersection lies within the smallest of the two footprints
+    let half_extent_m = max_abs(&tx_polygon).min(max_abs(&rx_polygon));
+    if half_extent_m <= 0.0 {
+        return ClutterLocus::default();
+    }
+    let grid = CoverageRaster::new(half_extent_m, LOCUS_GRID_SIZE);
+    let lem = SPEED_OF_LIGHT_IN_VACUUM / (tx.0.center_frequency_ghz * 1e9);
+    let (tx_carrier_state, rx_carrier_state) = (&tx.0.inner, &rx.0.inner);
+    // As the BSAR infos: a fixed receiver does not contribute to the Doppler frequency
+    let vrx = if rx.0.is_stationary { DVec3::ZERO } else { rx_carrier_state.velocity_vector_mps };
+    ClutterLocus::new(
+        lem,
+        &tx_carrier_state.position_m,
+        &tx_carrier_state.velocity_vector_mps,
+        &rx_carrier_state.position_m,
+        &vrx,
+        // World frame (Y-up) to ENU ground plane
+        grid.cell_centers_inside(&[&tx_polygon, &rx_polygon]).into_iter().map(|(a, b)| DVec2::new(b, a)),
+    )
+}
+
+/// Samples the locus when the system changes.
+fn update_clutter_locus(
+    mut clutter_locus_widget: ResMut<ClutterLocusWidget>,
+    bsar_infos_state: Res<BsarInfosState>,
+    tx: (Res<TxCarrierState>, Res<TxAntennaBeamFootprintState>),
+    rx: (Res<RxCarrierState>, Res<RxAntennaBeamFootprintState>),
+) {
+    if !clutter_locus_widget.is_enabled {
+        return;
+    }
+    // The widget is written by its window every frame: only a new system is sampled
+    if !clutter_locus_widget.needs_update && !bsar_infos_state.is_changed() {
+        return;
+    }
+    let widget = clutter_locus_widget.bypass_change_detection();
+    widget.locus = clutter_locus((&tx.0, &tx.1), (&rx.0, &rx.1));
+    widget.ridge = widget.locus.ridge(RIDGE_BIN_COUNT);
+    widget.needs_update = false;
+}
+
+fn clutter_locus_ui_system(
+    mut contexts: EguiContexts,
+    mut clutter_locus_widget: ResMut<ClutterLocusWidget>,
+    moving_target_widget: Res<MovingTargetWidget>,
+    tx_carrier_state: Res<TxCarrierState>,
+    rx_carrier_state: Res<RxCarrierState>,
+) -> Result {
+    let ctx = contexts.ctx_mut()?;
+
+    let clutter_locus_window = egui::Window::new("Clutter Locus")
+        .resizable(false)
+        .constrain(true)
+        .collapsible(true)
+        .title_bar(true)
+        .enabled(true)
+        .default_open(false)
+        .default_pos(egui::pos2(320.0, 1200.0));
+    clutter_locus_window.show(ctx, |ui| {
+        // Moving target in the range-Doppler domain, when drawn
+        let target = moving_target_widget.signature().map(|signature| {
+            let point_m = moving_target_widget.target.position_m.extend(0.0);
+            let range_m = bistatic_range_sg(
+                &(point_m - tx_carrier_state.inner.position_m),
+                &(point_m - rx_carrier_state.inner.position_m)
+            );
+            [range_m * 1e-3, signature.target_doppler_hz()]
+        });
+        clutter_locus_ui(ui, &mut clutter_locus_widget, target);
+    });
+
+    Ok(())
+}
+
+fn clutter_locus_ui(ui: &mut egui::Ui, widget: &mut ClutterLocusWidget, target: Option<[f64; 2]>) {
+    let hover_text = egui::RichText::new("Samples the common (Tx ∩ Rx) footprint on a ground grid and plots the bistatic\nrange and Doppler frequency of the clutter, with the Doppler span per range bin")
+        .color(TEXT_COLOR)
+        .monospace();
+    if ui.checkbox(&mut widget.is_enabled, "Compute").on_hover_text(hover_text).changed() {
+        // Sampled again once enabled
+        widget.needs_update = true;
+    }
+    if !widget.is_enabled {
+        return;
+    }
+    if widget.locus.samples.is_empty() {
+        ui.label(egui::RichText::new("The footprints do not intersect").color(TEXT_COLOR));
+        return;
+    }
+    egui::Grid::new("clutter_locus_grid")
+        .num_columns(2)
+        .striped(false)
+        .spacing([20.0, 5.0])
+        .show(ui, |ui| {
+            ui.label("Doppler spread:");
+            ui.label(widget.locus.doppler_spread_hz().map_or("-".to_string(), |spread_hz| format!("{spread_hz:.3} Hz")));
+            ui.end_row();
+            let hover_text = egui::RichText::new("Widest Doppler span of the clutter within a range bin")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Max. spread per range bin:").on_hover_text(hover_text);
+            ui.label(
+                widget.ridge.iter()
+                    .map(RidgeBin::doppler_spread_hz)
+                    .reduce(f64::max)
+                    .map_or("-".to_string(), |spread_hz| format!("{spread_hz:.3} Hz"))
+            );
+            ui.end_row();
+        });
+
+    // Ranges in km
+    let samples: Vec<[f64; 2]> = widget.locus.samples.iter().map(|&[range_m, doppler_hz]| [range_m * 1e-3, doppler_hz]).collect();
+    let ridge_edge = |doppler_hz: fn(&RidgeBin) -> f64| -> Vec<[f64; 2]> {
+        widget.ridge.iter().map(|bin| [bin.range_m * 1e-3, doppler_hz(bin)]).collect()
+    };
+    egui_plot::Plot::new("clutter_locus_plot")
+        .width(320.0)
+        .height(240.0)
+        .x_axis_label("Bistatic range [km]")
+        .y_axis_label("Doppler frequency [Hz]")
+        .allow_drag(false)
+        .allow_zoom(false)
+        .allow_scroll(false)
+        .show(ui, |plot_ui| {
+            plot_ui.points(egui_plot::Points::new("Clutter", samples).color(CLUTTER_COLOR).radius(1.0));
+            plot_ui.line(egui_plot::Line::new("Ridge", ridge_edge(|bin| bin.doppler_min_hz)).color(RIDGE_COLOR).width(1.5));
+            plot_ui.line(egui_plot::Line::new("Ridge", ridge_edge(|bin| bin.doppler_max_hz)).color(RIDGE_COLOR).width(1.5));
+            if let Some(target) = target {
+                plot_ui.points(egui_plot::Points::new("Moving target", vec![target]).color(TARGET_COLOR).radius(4.0));
+            }
+        });
+}
@@ -0,0 +1,379 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts, EguiPrimaryContextPass};
+
+use crate::{
+    bsar::bistatic_angle_sg,
+    expression,
+    orbit::{parse_tle_set, walker_delta, AccessWindow, Orbit},
+    scene::RxCarrierState,
+    ui::{GeoReferenceWidget, OrbitWidget},
+};
+
+const TEXT_COLOR: egui::Color32 = egui::Color32::from_rgb(200, 200, 200);
+const ERROR_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 120, 120);
+/// Ground track sampling step in seconds
+const GROUND_TRACK_STEP_S: f64 = 30.0;
+/// Maximum number of platforms of a constellation
+const MAX_PLATFORMS: usize = 200;
+
+pub struct ConstellationPlugin;
+
+impl Plugin for ConstellationPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<ConstellationWidget>()
+            .add_systems(EguiPrimaryContextPass, constellation_ui_system);
+    }
+}
+
+/// How the constellation is defined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConstellationDefinition {
+    /// Walker delta pattern of circular orbits
+    Walker,
+    /// List of TLEs, optionally named (3LE format)
+    Tle,
+}
+
+/// A bistatic opportunity: a platform illuminates the scene center within the
+/// incidence constraints while the receiver sees it within the bistatic angle limit.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BistaticOpportunity {
+    /// Index of the platform in [`ConstellationWidget::platforms`]
+    pub platform: usize,
+    pub window: AccessWindow,
+    /// Bistatic angle at the minimum incidence time in degrees
+    pub bistatic_angle_deg: f64,
+}
+
+/// Constellation of spaceborne transmitters, their ground tracks and their
+/// bistatic opportunities with the configured receiver. The access constraints
+/// (incidence, number of orbits, step) are the ones of the "Orbit" window.
+#[derive(Resource)]
+pub struct ConstellationWidget {
+    pub definition: ConstellationDefinition,
+    pub altitude_km: f64,
+    pub inclination_deg: f64,
+    pub total: usize,
+    pub planes: usize,
+    pub phasing: usize,
+    pub tle_text: String,
+    /// Maximum bistatic angle of an opportunity in degrees
+    pub max_bistatic_angle_deg: f64,
+    /// Built platforms (name, orbit)
+    pub platforms: Vec<(String, Orbit)>,
+    /// Ground tracks over one orbit, split at the antimeridian ([lon, lat] in degrees)
+    ground_tracks: Vec<Vec<Vec<[f64; 2]>>>,
+    pub opportunities: Vec<BistaticOpportunity>,
+    /// Result of the last build / computation
+    status: Option<Result<String, String>>,
+}
+
+impl Default for ConstellationWidget {
+    fn default() -> Self {
+        Self {
+            definition: ConstellationDefinition::Walker,
+            altitude_km: 700.0,
+            inclination_deg: 98.2,
+            total: 6,
+            planes: 3,
+            phasing: 1,
+            tle_text: String::new(),
+            max_bistatic_angle_deg: 90.0,
+            platforms: Vec::new(),
+            ground_tracks: Vec::new(),
+            opportunities: Vec::new(),
+            status: None,
+        }
+    }
+}
+
+impl ConstellationWidget {
+    /// Builds the platforms from the current definition and their ground tracks.
+    pub fn build(&mut self) {
+        let platforms = match self.definition {
+            ConstellationDefinition::Walker => walker_delta(
+                self.altitude_km * 1e3, // Convert km to m
+                self.inclination_deg,
+                self.total,
+                self.planes,
+                self.phasing
+            ).map(|orbits| orbits.into_iter()
+                .enumerate()
+                .map(|(i, orbit)| (format!("P{}-S{}", i / (self.total / self.planes) + 1, i % (self.total / self.planes) + 1), orbit))
+                .collect::<Vec<_>>()
+            ),
+            ConstellationDefinition::Tle => parse_tle_set(&self.tle_text),
+        };
+        self.opportunities.clear();
+        match platforms {
+            Ok(platforms) if platforms.len() > MAX_PLATFORMS => {
+                self.status = Some(Err(format!("{} platforms exceed the {MAX_PLATFORMS} platforms limit", platforms.len())));
+            }
+            Ok(platforms) => {
+                self.ground_tracks = platforms.iter()
+                    .map(|(_, orbit)| split_at_antimeridian(
+                        orbit.ground_track(0.0, orbit.period_s(), GROUND_TRACK_STEP_S)
+                            .iter()
+                            .map(|gp| [gp.lon_deg(), gp.lat_deg()])
+                            .collect()
+                    ))
+                    .collect();
+                self.status = Some(Ok(format!("{} platforms", platforms.len())));
+                self.platforms = platforms;
+            }
+            Err(error) => self.status = Some(Err(error)),
+        }
+    }
+
+    /// Computes the bistatic opportunities of all platforms with the receiver,
+    /// sorted by start time.
+    pub fn compute_opportunities(
+        &mut self,
+        orbit_widget: &OrbitWidget,
+        geo_reference_widget: &GeoReferenceWidget,
+        rx_carrier_state: &RxCarrierState,
+    ) {
+        let scene = &geo_reference_widget.scene_origin;
+        let rxp = -rx_carrier_state.inner.position_m; // Receiver -> scene center
+        self.opportunities = self.platforms.iter()
+            .enumerate()
+            .flat_map(|(platform, (_, orbit))| {
+                orbit.access_windows(
+                    scene,
+                    0.0,
+                    orbit_widget.num_orbits * orbit.period_s(),
+                    orbit_widget.step_s,
+                    (orbit_widget.incidence_min_deg, orbit_widget.incidence_max_deg)
+                )
+                .into_iter()
+                .map(move |window| {
+                    let txp = -orbit.position_enu_at(scene, window.min_incidence_time_s);
+                    BistaticOpportunity {
+                        platform,
+                        bistatic_angle_deg: bistatic_angle_sg(&txp, &rxp).to_degrees(),
+                        window,
+                    }
+                })
+            })
+            .filter(|opportunity| opportunity.bistatic_angle_deg <= self.max_bistatic_angle_deg)
+            .collect();
+        self.opportunities.sort_by(|a, b| a.window.start_s.total_cmp(&b.window.start_s));
+        self.status = Some(Ok(format!("{} bistatic opportunities", self.opportunities.len())));
+    }
+}
+
+/// Splits a ground track where it crosses the antimeridian.
+fn split_at_antimeridian(track: Vec<[f64; 2]>) -> Vec<Vec<[f64; 2]>> {
+    let mut chunks: Vec<Vec<[f64; 2]>> = vec![Vec::new()];
+    for point in track {
+        if let Some(last) = chunks.last().and_then(|chunk| chunk.last())
+            && (point[0] - last[0]).abs() > 180.0
+        {
+            chunks.push(Vec::new());
+        }
+        if let Some(chunk) = chunks.last_mut() {
+            chunk.push(point);
+        }
+    }
+    chunks
+}
+
+fn constellation_ui_system(
+    mut contexts: EguiContexts,
+    mut constellation_widget: ResMut<ConstellationWidget>,
+    orbit_widget: Res<OrbitWidget>,
+    geo_reference_widget: Res<GeoReferenceWidget>,
+    rx_carrier_state: Res<RxCarrierState>,
+) -> Result {
+    let ctx = contexts.ctx_mut()?;
+
+    let constellation_window = egui::Window::new("Constellation")
+        .resizable(false)
+        .constrain(true)
+        .collapsible(true)
+        .title_bar(true)
+        .enabled(true)
+        .default_open(false)
+        .default_pos(egui::pos2(320.0, 160.0));
+    constellation_window.show(ctx, |ui| {
+        constellation_ui(ui, &mut constellation_widget, &orbit_widget, &geo_reference_widget, &rx_carrier_state);
+    });
+
+    Ok(())
+}
+
+fn constellation_ui(
+    ui: &mut egui::Ui,
+    widget: &mut ConstellationWidget,
+    orbit_widget: &OrbitWidget,
+    geo_reference_widget: &GeoReferenceWidget,
+    rx_carrier_state: &RxCarrierState,
+) {
+    ui.horizontal(|ui| {
+        ui.radio_value(&mut widget.definition, ConstellationDefinition::Walker, "Walker delta");
+        ui.radio_value(&mut widget.definition, ConstellationDefinition::Tle, "TLE list");
+    });
+    match widget.definition {
+        ConstellationDefinition::Walker => walker_ui(ui, widget),
+        ConstellationDefinition::Tle => {
+            ui.add(
+                egui::TextEdit::multiline(&mut widget.tle_text)
+                    .font(egui::TextStyle::Monospace)
+                    .desired_rows(6)
+                    .desired_width(520.0)
+                    .hint_text("Paste TLEs, each optionally preceded by a name line")
+            );
+        }
+    }
+    if ui.button("Build").on_hover_text(
+        egui::RichText::new("Builds the platforms and their ground tracks over one orbit")
+            .color(TEXT_COLOR)
+            .monospace()
+    ).clicked() {
+        widget.build();
+    }
+
+    if !widget.platforms.is_empty() {
+        let scene_center = geo_reference_widget.scene_origin.origin_as_geographic_point();
+        egui_plot::Plot::new("constellation_ground_tracks_plot")
+            .width(520.0)
+            .height(260.0)
+            .data_aspect(1.0)
+            .include_x(-180.0)
+            .include_x(180.0)
+            .include_y(-90.0)
+            .include_y(90.0)
+            .x_axis_label("Longitude [°]")
+            .y_axis_label("Latitude [°]")
+            .show(ui, |plot_ui| {
+                for ((name, _), chunks) in widget.platforms.iter().zip(widget.ground_tracks.iter()) {
+                    for chunk in chunks {
+                        plot_ui.line(egui_plot::Line::new(name.clone(), chunk.clone()).width(1.0));
+                    }
+                }
+                plot_ui.points(
+                    egui_plot::Points::new("Scene center", vec![[scene_center.lon_deg(), scene_center.lat_deg()]])
+                        .radius(4.0)
+                        .color(egui::Color32::RED)
+                );
+            });
+    }
+
+    ui.separator();
+    ui.vertical_centered(|ui| ui.label(egui::RichText::new("BISTATIC OPPORTUNITIES").strong()));
+    ui.separator();
+
+    egui::Grid::new("constellation_opportunities_grid")
+        .num_columns(2)
+        .striped(false)
+        .spacing([20.0, 5.0])
+        .show(ui, |ui| {
+            let hover_text = egui::RichText::new("Sets the maximum bistatic angle with the receiver at the scene center (0 - 180°)")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Max. bistatic angle: ").on_hover_text(hover_text.clone());
+            ui.add(
+                egui::DragValue::new(&mut widget.max_bistatic_angle_deg)
+                    .custom_parser(expression::parse)
+                    .update_while_editing(false)
+                    .speed(0.1)
+                    .range(0.0..=180.0)
+                    .fixed_decimals(1)
+                    .suffix("°")
+            ).on_hover_text(hover_text);
+            ui.end_row();
+        });
+    let hover_text = egui::RichText::new("Lists the access windows of every platform (constraints of the \"Orbit\" window)\nwith the bistatic angle to the receiver at their minimum incidence")
+        .color(TEXT_COLOR)
+        .monospace();
+    if ui
+        .add_enabled(!widget.platforms.is_empty(), egui::Button::new("Compute opportunities"))
+        .on_hover_text(hover_text)
+        .clicked()
+    {
+        widget.compute_opportunities(orbit_widget, geo_reference_widget, rx_carrier_state);
+    }
+    match &widget.status {
+        Some(Ok(status)) => { ui.label(egui::RichText::new(status).color(TEXT_COLOR)); }
+        Some(Err(error)) => { ui.label(egui::RichText::new(error).color(ERROR_COLOR)); }
+        None => {}
+    }
+    if widget.opportunities.is_empty() {
+        return;
+    }
+    egui::ScrollArea::vertical()
+        .max_height(200.0)
+        .show(ui, |ui| {
+            egui::Grid::new("constellation_opportunities_table")
+                .num_columns(5)
+                .striped(true)
+                .spacing([20.0, 3.0])
+                .show(ui, |ui| {
+                    for header in ["Platform", "Start [h]", "Duration [s]", "Min. incidence", "Bistatic angle"] {
+                        ui.label(egui::RichText::new(header).strong());
+                    }
+                    ui.end_row();
+                    for opportunity in widget.opportunities.iter() {
+                        ui.label(&widget.platforms[opportunity.platform].0);
+                        ui.label(format!("{:.3}", opportunity.window.start_s / 3600.0));
+                        ui.label(format!("{:.1}", opportunity.window.duration_s()));
+                        ui.label(format!("{:.2}°", opportunity.window.min_incidence_deg));
+                        ui.label(format!("{:.2}°", opportunity.bistatic_angle_deg));
+                        ui.end_row();
+                    }
+                });
+        });
+}
+
+fn walker_ui(ui: &mut egui::Ui, widget: &mut ConstellationWidget) {
+    egui::Grid::new("constellation_walker_grid")
+        .num_columns(2)
+        .striped(false)
+        .spacing([20.0, 5.0])
+        .show(ui, |ui| {
+            let hover_text = egui::RichText::new("Sets the orbits altitude above the equatorial radius (150 - 40000 km)")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Altitude: ").on_hover_text(hover_text.clone());
+            ui.add(
+                egui::DragValue::new(&mut widget.altitude_km)
+                    .custom_parser(expression::parse)
+                    .update_while_editing(false)
+                    .speed(1.0)
+                    .range(150.0..=40000.0)
+                    .fixed_decimals(1)
+                    .suffix(" km")
+            ).on_hover_text(hover_text);
+            ui.end_row();
+
+            let hover_text = egui::RichText::new("Sets the orbits inclination (0 - 180°)")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Inclination: ").on_hover_text(hover_text.clone());
+            ui.add(
+                egui::DragValue::new(&mut widget.inclination_deg)
+                    .custom_parser(expression::parse)
+                    .update_while_editing(false)
+                    .speed(0.1)
+                    .range(0.0..=180.0)
+                    .fixed_decimals(2)
+                    .suffix("°")
+            ).on_hover_text(hover_text);
+            ui.end_row();
+
+            let hover_text = egui::RichText::new("Sets the Walker parameters: total satellites / planes / phasing\n(the satellites are evenly spread over the planes, phasing < planes)")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("t / p / f: ").on_hover_text(hover_text.clone());
+            ui.horizontal(|ui| {
+                ui.add(egui::DragValue::new(&mut widget.total).range(1..=MAX_PLATFORMS)).on_hover_text(hover_text.clone());
+                ui.label("/");
+                ui.add(egui::DragValue::new(&mut widget.planes).range(1..=MAX_PLATFORMS)).on_hover_text(hover_text.clone());
+                ui.label("/");
+                let max_phasing = widget.planes.saturating_sub(1);
+                ui.add(egui::DragValue::new(&mut widget.phasing).range(0..=max_phasing)).on_hover_text(hover_text);
+            });
+            ui.end_row();
+        });
+}
@@ -0,0 +1,303 @@
+use bevy::{
+    asset::RenderAssetUsages,
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+use bevy_egui::{egui, EguiContexts, EguiPrimaryContextPass};
+
+use crate::{
+    constants::HALF_PLANE_LENGTH,
+    coverage::CoverageRaster,
+    expression,
+    scene::{RxAntennaBeamFootprintState, TxAntennaBeamFootprintState},
+    ui::{MultiBeamWidget, SteeringWidget, TimelineWidget, UpdateStage},
+};
+
+const TEXT_COLOR: egui::Color32 = egui::Color32::from_rgb(200, 200, 200);
+/// Cells per side of the coverage raster
+const COVERAGE_RASTER_SIZE: usize = 512;
+/// Height of the heat layer above the ground, above the iso-range/Doppler plane (0.1 m)
+const COVERAGE_LAYER_HEIGHT_M: f32 = 0.2;
+
+pub struct CoveragePlugin;
+
+impl Plugin for CoveragePlugin {
+    fn build(&self, app: &mut App) {
+        // The coverage samples the footprints once the update systems moved them,
+        // the steered beams once they hopped and the Rx beams once formed
+        app
+            .init_resource::<CoverageWidget>()
+            .add_systems(Startup, spawn_coverage_layer)
+            .add_systems(
+                PostUpdate,
+                (
+                    accumulate_coverage.in_set(UpdateStage::Derived),
+                    update_coverage_layer.in_set(UpdateStage::RenderAssets),
+                )
+            )
+            .add_systems(EguiPrimaryContextPass, coverage_ui_system);
+    }
+}
+
+/// Component marker for the coverage heat layer
+#[derive(Component)]
+pub struct CoverageLayer;
+
+/// Mission coverage: the ground covered by the intersection of the Tx and Rx
+/// footprints is accumulated along the timeline and shown as a heat layer
+/// (number of samples covered).
+#[derive(Resource)]
+pub struct CoverageWidget {
+    /// Accumulates while the timeline time changes
+    pub is_accumulating: bool,
+    /// Shows the heat layer
+    pub is_visible: bool,
+    /// Minimum timeline step in seconds between two samples
+    pub step_s: f64,
+    pub raster: CoverageRaster,
+    last_time_s: Option<f64>,
+    /// Heat layer texture, set at startup
+    image: Option<Handle<Image>>,
+    material: Option<Handle<StandardMaterial>>,
+    layer_needs_update: bool,
+}
+
+impl Default for CoverageWidget {
+    fn default() -> Self {
+        Self {
+            is_accumulating: false,
+            is_visible: true,
+            step_s: 1.0,
+            raster: CoverageRaster::new(5000.0, COVERAGE_RASTER_SIZE),
+            last_time_s: None,
+            image: None,
+            material: None,
+            layer_needs_update: true,
+        }
+    }
+}
+
+impl CoverageWidget {
+    /// Clears the accumulated coverage, with a new raster half extent.
+    pub fn reset(&mut self, half_extent_m: f64) {
+        self.raster = CoverageRaster::new(half_extent_m, COVERAGE_RASTER_SIZE);
+        self.last_time_s = None;
+        self.layer_needs_update = true;
+    }
+}
+
+fn spawn_coverage_layer(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut images: ResMut<Assets<Image>>,
+    mut coverage_widget: ResMut<CoverageWidget>,
+) {
+    let image = images.add(Image::new_fill(
+        Extent3d {
+            width: COVERAGE_RASTER_SIZE as u32,
+            height: COVERAGE_RASTER_SIZE as u32,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &[0, 0, 0, 0], // Initial color (transparent)
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::MAIN_WORLD | RenderAssetUsages::RENDER_WORLD
+    ));
+    let material = materials.add(StandardMaterial {
+        base_color: Color::WHITE,
+        base_color_texture: Some(image.clone()),
+        alpha_mode: AlphaMode::Blend,
+        cull_mode: None,
+        unlit: true,
+        ..default()
+    });
+    // Plane texture coordinates: columns along +X, rows along +Z (Y-up), as
+    // the raster is filled from the footprints (X, Z) ground coordinates
+    commands.spawn((
+        Mesh3d(meshes.add(Plane3d::new(Vec3::Y, Vec2::splat(0.5)))),
+        MeshMaterial3d(material.clone()),
+        Transform::from_xyz(0.0, COVERAGE_LAYER_HEIGHT_M, 0.0),
+        CoverageLayer,
+        Name::new("Coverage heat layer"),
+    ));
+    coverage_widget.image = Some(image);
+    coverage_widget.material = Some(material);
+}
+
+/// Adds the Tx ∩ Rx footprints to the coverage at each timeline step.
+fn accumulate_coverage(
+    mut coverage_widget: ResMut<CoverageWidget>,
+    timeline_widget: Res<TimelineWidget>,
+    steering_widget: Res<SteeringWidget>,
+    multi_beam_widget: Res<MultiBeamWidget>,
+    tx_antenna_beam_footprint_state: Res<TxAntennaBeamFootprintState>,
+    rx_antenna_beam_footprint_state: Res<RxAntennaBeamFootprintState>,
+) {
+    if !coverage_widget.is_accumulating {
+        return;
+    }
+    let time_s = timeline_widget.time_s;
+    let is_new_step = coverage_widget.last_time_s.is_none_or(
+        |last_time_s| (time_s - last_time_s).abs() >= coverage_widget.step_s.max(f64::EPSILON)
+    );
+    if !is_new_step {
+        return;
+    }
+    // Footprints are in World frame (Y-up): their ground coordinates are (X, Z)
+    let ground = |points: &[bevy::math::DVec3]| -> Vec<(f64, f64)> {
+        points.iter().map(|point| (point.x, point.z)).collect()
+    };
+    // A steered antenna covers the footprint of its active beam position
+    let tx_footprint = steering_widget.tx.active_footprint().unwrap_or(&tx_antenna_beam_footprint_state.inner);
+    let rx_footprint = steering_widget.rx.active_footprint().unwrap_or(&rx_antenna_beam_footprint_state.inner);
+    let tx_polygon = ground(&tx_footprint.points);
+    if let Some(rx_beam_footprints) = multi_beam_widget.enabled_footprints() {
+        // Simultaneous Rx beams: the Tx footprint within any of them
+        let rx_polygons: Vec<Vec<(f64, f64)>> = rx_beam_footprints.iter()
+            .map(|footprint| ground(&footprint.points))
+            .collect();
+        let rx_polygons: Vec<&[(f64, f64)]> = rx_polygons.iter().map(Vec::as_slice).collect();
+        coverage_widget.raster.accumulate_any_of(&[&tx_polygon], &rx_polygons);
+    } else {
+        let rx_polygon = ground(&rx_footprint.points);
+        coverage_widget.raster.accumulate(&[&tx_polygon, &rx_polygon]);
+    }
+    coverage_widget.last_time_s = Some(time_s);
+    coverage_widget.layer_needs_update = true;
+}
+
+/// Uploads the heat layer texture and applies the raster extent and visibility.
+fn update_coverage_layer(
+    mut coverage_widget: ResMut<CoverageWidget>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut images: ResMut<Assets<Image>>,
+    mut coverage_layer_q: Query<(&mut Transform, &mut Visibility), With<CoverageLayer>>,
+) {
+    for (mut transform, mut visibility) in coverage_layer_q.iter_mut() {
+        let extent = 2.0 * coverage_widget.raster.half_extent_m() as f32;
+        transform.scale = Vec3::new(extent, 1.0, extent);
+        visibility.set_if_neq(if coverage_widget.is_visible { Visibility::Inherited } else { Visibility::Hidden });
+    }
+    if !coverage_widget.layer_needs_update {
+        return;
+    }
+    if let Some(image_handle) = &coverage_widget.image
+        && let Some(mut image) = images.get_mut(image_handle)
+    {
+        image.data = Some(coverage_widget.raster.to_rgba8());
+        // Touch the material so that it picks the new texture up
+        if let Some(material_handle) = &coverage_widget.material
+            && let Some(mut material) = materials.get_mut(material_handle)
+        {
+            material.base_color_texture = Some(image_handle.clone());
+        }
+    }
+    coverage_widget.layer_needs_update = false;
+}
+
+fn coverage_ui_system(
+    mut contexts: EguiContexts,
+    mut coverage_widget: ResMut<CoverageWidget>,
+) -> Result {
+    let ctx = contexts.ctx_mut()?;
+
+    let coverage_window = egui::Window::new("Coverage")
+        .resizable(false)
+        .constrain(true)
+        .collapsible(true)
+        .title_bar(true)
+        .enabled(true)
+        .default_open(false)
+        .default_pos(egui::pos2(320.0, 40.0));
+    coverage_window.show(ctx, |ui| {
+        coverage_ui(ui, &mut coverage_widget);
+    });
+
+    Ok(())
+}
+
+fn coverage_ui(ui: &mut egui::Ui, widget: &mut CoverageWidget) {
+    egui::Grid::new("coverage_grid")
+        .num_columns(2)
+        .striped(false)
+        .spacing([20.0, 5.0])
+        .show(ui, |ui| {
+            let hover_text = egui::RichText::new("Accumulates the ground covered by both the Tx and Rx footprints\nwhile the timeline time changes")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.checkbox(&mut widget.is_accumulating, "Accumulate").on_hover_text(hover_text);
+            ui.checkbox(&mut widget.is_visible, "Show heat layer");
+            ui.end_row();
+
+            let hover_text = egui::RichText::new("Sets the minimum timeline step between two samples (0 => every frame)")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Step: ").on_hover_text(hover_text.clone());
+            ui.add(
+                egui::DragValue::new(&mut widget.step_s)
+                    .custom_parser(expression::parse)
+                    .update_while_editing(false)
+                    .speed(0.1)
+                    .range(0.0..=3600.0)
+                    .fixed_decimals(2)
+                    .suffix(" s")
+            ).on_hover_text(hover_text);
+            ui.end_row();
+
+            let hover_text = egui::RichText::new(format!(
+                "Sets the half side of the coverage area centered on the scene center (100 - {HALF_PLANE_LENGTH} m),\n\
+                 changing it clears the coverage"
+            ))
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Half extent: ").on_hover_text(hover_text.clone());
+            let mut half_extent_m = widget.raster.half_extent_m();
+            let response = ui.add(
+                egui::DragValue::new(&mut half_extent_m)
+                    .custom_parser(expression::parse)
+                    .update_while_editing(false)
+                    .speed(10.0)
+                    .range(100.0..=HALF_PLANE_LENGTH as f64)
+                    .fixed_decimals(0)
+                    .suffix(" m")
+            ).on_hover_text(hover_text);
+            if response.changed() {
+                widget.reset(half_extent_m);
+            }
+            ui.end_row();
+
+            ui.label(format!("{} samples", widget.raster.samples()));
+            if ui.add_enabled(widget.raster.samples() > 0, egui::Button::new("Clear")).clicked() {
+                widget.reset(widget.raster.half_extent_m());
+            }
+            ui.end_row();
+        });
+
+    ui.separator();
+    ui.vertical_centered(|ui| ui.label(egui::RichText::new("STATISTICS").strong()));
+    ui.separator();
+
+    egui::Grid::new("coverage_statistics_grid")
+        .num_columns(2)
+        .striped(false)
+        .spacing([20.0, 5.0])
+        .show(ui, |ui| {
+            let raster = &widget.raster;
+            ui.label("Covered area: ");
+            ui.label(format!("{:.3} km²", raster.covered_area_m2() * 1e-6));
+            ui.end_row();
+
+            ui.label("Max times covered: ");
+            ui.label(format!("{}", raster.max_count()));
+            ui.end_row();
+
+            ui.label("Mean times covered: ");
+            ui.label(format!("{:.2}", raster.mean_count()));
+            ui.end_row();
+
+            ui.label("Cell size: ");
+            ui.label(format!("{:.2} m", raster.cell_size_m()));
+            ui.end_row();
+        });
+}
@@ -0,0 +1,968 @@
+use bevy::{math::DVec2, prelude::*};
+use bevy_egui::{egui, EguiContexts, EguiPrimaryContextPass};
+use bevy_panorbit_camera::PanOrbitCamera;
+
+use crate::{
+    constants::{FOOTPRINT_MIN_LIFT_M, HALF_PLANE_LENGTH, TO_Y_UP_F64},
+    entities::{
+        AntennaBeamAzimuthLine, AntennaBeamElevationLine, AxesHelperPart, CarrierState, FootprintModel, IsoContours,
+        IsoRangeDopplerPlaneState, LevelContours, LineList, LineStrip, PlaneWindow,
+        beam_iso_range_curve,
+    },
+    expression,
+    limits::CarrierLimits,
+    palette::{ColorPalette, Rgb},
+    scene::{
+        BsarInfosState, CarrierLimitsState, IsoRangeDopplerPlane,
+        RxAntennaBeamFootprintState, RxAntennaBeamState, RxAntennaState, RxCarrierState,
+        TxAntennaBeamFootprintState, TxAntennaBeamState, TxAntennaState, TxCarrierState
+    },
+    squint::{SquintConvention, SquintReference, SquintSign},
+    ui::{RoiWidget, Toasts, UpdateStage},
+};
+
+const TEXT_COLOR: egui::Color32 = egui::Color32::from_rgb(200, 200, 200);
+/// Relative change of the footprint lift below which the footprints are not redrawn
+const FOOTPRINT_LIFT_TOLERANCE: f64 = 1e-3;
+/// Length of the resolution direction arrows, as a fraction of the scene extent
+const RESOLUTION_DIRECTION_LENGTH_RATIO: f64 = 0.05;
+/// Colors of the ground range and lateral resolution direction arrows
+const RANGE_DIRECTION_COLOR: Srgba = Srgba::rgb(1.0, 0.5, 0.0);
+const LATERAL_DIRECTION_COLOR: Srgba = Srgba::rgb(0.0, 1.0, 1.0);
+/// Radius of the min/max slant range point spheres, as a fraction of the scene extent
+const RANGE_POINT_RADIUS_RATIO: f64 = 0.004;
+/// Colors of the min and max slant range point spheres
+const RANGE_MIN_POINT_COLOR: Srgba = Srgba::rgb(0.2, 0.9, 0.2);
+const RANGE_MAX_POINT_COLOR: Srgba = Srgba::rgb(0.9, 0.2, 0.2);
+/// Height of the contour lines above the ground plane, as a fraction of the
+/// footprints lift, so that the footprints are drawn over them
+const CONTOUR_LINES_LIFT_RATIO: f64 = 0.5;
+/// Side of the contours window following the camera, in camera distances
+const CAMERA_WINDOW_RADIUS_RATIO: f64 = 2.0;
+/// Relative move of the camera window, in window sides, below which the plane
+/// is not redrawn, so that it is not redrawn on every orbiting frame
+const CAMERA_WINDOW_TOLERANCE: f64 = 0.1;
+
+pub struct DisplayPlugin;
+
+impl Plugin for DisplayPlugin {
+    fn build(&self, app: &mut App) {
+        // The footprints lift, model and squint convention, the contours window and
+        // the palette are picked up by the update systems through the change
+        // detection of the states; the overlays are drawn from the BSAR infos
+        // and the contours
+        app
+            .init_resource::<DisplayWidget>()
+            .add_systems(Startup, (spawn_resolution_directions, spawn_range_points, spawn_contour_lines, spawn_beam_iso_range_curves))
+            .add_systems(
+                PostUpdate,
+                (
+                    (update_footprint_lift, update_squint_convention, update_footprint_model, update_plane_window, update_plane_palette)
+                        .in_set(UpdateStage::Input),
+                    (
+                        update_resolution_directions, update_range_points, update_contour_lines,
+                        update_beam_iso_range_curves, update_palette_materials
+                    )
+                        .in_set(UpdateStage::RenderAssets),
+                )
+            )
+            .add_systems(EguiPrimaryContextPass, display_ui_system);
+    }
+}
+
+/// Display settings of the 3D scene.
+#[derive(Resource)]
+pub struct DisplayWidget {
+    /// Scales the footprints lift with the scene extent
+    pub is_footprint_lift_auto: bool,
+    /// Footprints lift in meters above the ground plane when not automatic
+    pub footprint_lift_m: f64,
+    /// Automatic footprints lift, as a fraction of the scene extent
+    pub footprint_lift_ratio: f64,
+    /// Draws the ground range and lateral resolution directions at the scene center
+    pub show_resolution_directions: bool,
+    /// Marks the min/max slant range points of the footprints
+    pub show_range_points: bool,
+    /// Labels the footprint points of the bistatic range and Doppler extrema
+    pub show_footprint_annotations: bool,
+    /// Draws the intersections of the Tx and Rx beams with the iso-range ellipsoid
+    pub show_beam_iso_range_curves: bool,
+    /// Reference and sign of the antennas squint angle
+    pub squint_convention: SquintConvention,
+    /// Computation of the footprints
+    pub footprint_model: FootprintModel,
+    /// Colors of the contours, of the footprint lines and of the axes helpers
+    pub palette: ColorPalette,
+    /// Rendering of the iso-range/iso-Doppler contours
+    pub contour_rendering: ContourRendering,
+    /// Ground window over which the contours are evaluated
+    pub contour_window: ContourWindow,
+    /// Center (East, North) in meters of the region of interest
+    pub region_center_m: DVec2,
+    /// Side in meters of the region of interest
+    pub region_extent_m: f64,
+}
+
+/// Ground window over which the iso-range/iso-Doppler contours are evaluated.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ContourWindow {
+    /// Around the footprints
+    #[default]
+    Footprints,
+    /// Around the camera focus, scaled with the camera distance
+    Camera,
+    /// A region of interest set by the user
+    Region,
+}
+
+/// Rendering of the iso-range/iso-Doppler contours.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ContourRendering {
+    /// Drawn into the texture of the iso-range/Doppler plane, with their values
+    #[default]
+    Texture,
+    /// Line meshes laid on the ground, crisp at any zoom, the plane hidden
+    Lines,
+}
+
+/// Ground resolution directions drawn at the scene center.
+#[derive(Component, Clone, Copy, PartialEq, Eq)]
+pub enum ResolutionDirection {
+    Range,
+    Lateral,
+}
+
+/// Min/max slant range points of the footprints.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RangePoint {
+    TxMin,
+    TxMax,
+    RxMin,
+    RxMax,
+}
+
+impl RangePoint {
+    const ALL: [Self; 4] = [Self::TxMin, Self::TxMax, Self::RxMin, Self::RxMax];
+}
+
+/// Intersection curves of the beams with the iso-range ellipsoid.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BeamIsoRangeCurve {
+    Tx,
+    Rx,
+}
+
+/// Iso-range and iso-Doppler contour line meshes.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContourLines {
+    Range,
+    Doppler,
+}
+
+impl Default for DisplayWidget {
+    fn default() -> Self {
+        Self {
+            is_footprint_lift_auto: true,
+            footprint_lift_m: FOOTPRINT_MIN_LIFT_M,
+            footprint_lift_ratio: 1e-5,
+            show_resolution_directions: false,
+            show_range_points: false,
+            show_footprint_annotations: false,
+            show_beam_iso_range_curves: false,
+            squint_convention: SquintConvention::default(),
+            footprint_model: FootprintModel::default(),
+            palette: ColorPalette::default(),
+            contour_rendering: ContourRendering::default(),
+            contour_window: ContourWindow::default(),
+            region_center_m: DVec2::ZERO,
+            region_extent_m: 2000.0,
+        }
+    }
+}
+
+impl DisplayWidget {
+    /// Gets the footprints lift in meters for a scene extending `scene_extent_m`
+    /// around the scene center.
+    ///
+    /// The depth buffer precision decreases with the camera distance, which
+    /// follows the scene extent: a fixed lift makes the footprints z-fight with
+    /// the ground plane in large scenes.
+    pub fn footprint_lift_m(&self, scene_extent_m: f64) -> f64 {
+        if self.is_footprint_lift_auto {
+            (self.footprint_lift_ratio * scene_extent_m).max(FOOTPRINT_MIN_LIFT_M)
+        } else {
+            self.footprint_lift_m
+        }
+    }
+}
+
+/// Sets the footprints lift, redrawing the footprints when it changes.
+fn update_footprint_lift(
+    display_widget: Res<DisplayWidget>,
+    mut tx_antenna_beam_footprint_state: ResMut<TxAntennaBeamFootprintState>,
+    mut rx_antenna_beam_footprint_state: ResMut<RxAntennaBeamFootprintState>,
+) {
+    // Scene extent: the farthest carrier from the scene center
+    let scene_extent_m = tx_antenna_beam_footprint_state.inner.range_center_m.max(rx_antenna_beam_footprint_state.inner.range_center_m);
+    let lift_m = display_widget.footprint_lift_m(scene_extent_m);
+    let is_changed = |old_lift_m: f64| (lift_m - old_lift_m).abs() > FOOTPRINT_LIFT_TOLERANCE * old_lift_m;
+    // Only written when changed, which triggers the update systems
+    if is_changed(tx_antenna_beam_footprint_state.inner.lift_m) {
+        tx_antenna_beam_footprint_state.inner.lift_m = lift_m;
+    }
+    if is_changed(rx_antenna_beam_footprint_state.inner.lift_m) {
+        rx_antenna_beam_footprint_state.inner.lift_m = lift_m;
+    }
+}
+
+/// Sets the squint convention, recomputing the squint angles when it changes.
+fn update_squint_convention(
+    display_widget: Res<DisplayWidget>,
+    mut tx_antenna_beam_footprint_state: ResMut<TxAntennaBeamFootprintState>,
+    mut rx_antenna_beam_footprint_state: ResMut<RxAntennaBeamFootprintState>,
+) {
+    let squint_convention = display_widget.squint_convention;
+    if tx_antenna_beam_footprint_state.inner.squint_convention != squint_convention {
+        tx_antenna_beam_footprint_state.inner.squint_convention = squint_convention;
+    }
+    if rx_antenna_beam_footprint_state.inner.squint_convention != squint_convention {
+        rx_antenna_beam_footprint_state.inner.squint_convention = squint_convention;
+    }
+}
+
+/// Sets the footprints model, recomputing the footprints when it changes.
+fn update_footprint_model(
+    display_widget: Res<DisplayWidget>,
+    mut tx_antenna_beam_footprint_state: ResMut<TxAntennaBeamFootprintState>,
+    mut rx_antenna_beam_footprint_state: ResMut<RxAntennaBeamFootprintState>,
+) {
+    let footprint_model = display_widget.footprint_model;
+    if tx_antenna_beam_footprint_state.inner.model != footprint_model {
+        tx_antenna_beam_footprint_state.inner.model = footprint_model;
+    }
+    if rx_antenna_beam_footprint_state.inner.model != footprint_model {
+        rx_antenna_beam_footprint_state.inner.model = footprint_model;
+    }
+}
+
+/// Sets the contours window, redrawing the plane when it changes.
+fn update_plane_window(
+    display_widget: Res<DisplayWidget>,
+    roi_widget: Res<RoiWidget>,
+    camera_q: Single<&PanOrbitCamera>,
+    mut iso_range_doppler_plane_state: ResMut<IsoRangeDopplerPlaneState>,
+) {
+    // The contours restricted to the region of interest are computed over it
+    let contour_window = if roi_widget.restriction().is_some() { ContourWindow::Region } else { display_widget.contour_window };
+    let (window, tolerance) = match contour_window {
+        ContourWindow::Footprints => (PlaneWindow::Footprints, 0.0),
+        ContourWindow::Camera => {
+            let Some(radius) = camera_q.radius else {
+                return;
+            };
+            // Focus in World frame (Y-up) to ENU ground plane
+            let focus = camera_q.focus;
+            let window = PlaneWindow::Region {
+                center_m: DVec2::new(focus.z as f64, focus.x as f64),
+                extent_m: CAMERA_WINDOW_RADIUS_RATIO * radius as f64,
+            };
+            (window, CAMERA_WINDOW_TOLERANCE)
+        }
+        ContourWindow::Region => {
+            let window = match roi_widget.restriction() {
+                Some(rect) => PlaneWindow::Region {
+                    center_m: rect.center_m(),
+                    extent_m: rect.size_m().max_element(),
+                },
+                None => PlaneWindow::Region {
+                    center_m: display_widget.region_center_m,
+                    extent_m: display_widget.region_extent_m,
+                },
+            };
+            (window, 0.0)
+        }
+    };
+    let is_changed = match (iso_range_doppler_plane_state.window, window) {
+        (PlaneWindow::Region { center_m: old_center_m, extent_m: old_extent_m }, PlaneWindow::Region { center_m, extent_m }) => {
+            center_m.distance(old_center_m) > tolerance * old_extent_m
+                || (extent_m - old_extent_m).abs() > tolerance * old_extent_m
+        }
+        (old_window, window) => old_window != window,
+    };
+    if is_changed {
+        // Triggers the refresh of the plane by update_tx
+        iso_range_doppler_plane_state.window = window;
+    }
+}
+
+/// Sets the palette of the plane, redrawing it when it changes.
+fn update_plane_palette(
+    display_widget: Res<DisplayWidget>,
+    mut iso_range_doppler_plane_state: ResMut<IsoRangeDopplerPlaneState>,
+) {
+    if iso_range_doppler_plane_state.palette != display_widget.palette {
+        // Triggers the refresh of the plane by update_bsar_system
+        iso_range_doppler_plane_state.palette = display_widget.palette;
+    }
+}
+
+/// Recolors the contour lines, the footprint elevation and azimuth lines and
+/// the axes helpers with the palette.
+fn update_palette_materials(
+    display_widget: Res<DisplayWidget>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    contour_lines_q: Query<(&ContourLines, &MeshMaterial3d<StandardMaterial>)>,
+    footprint_lines_q: Query<(Has<AntennaBeamElevationLine>, &MeshMaterial3d<StandardMaterial>), Or<(With<AntennaBeamElevationLine>, With<AntennaBeamAzimuthLine>)>>,
+    axes_helper_q: Query<(&AxesHelperPart, &MeshMaterial3d<StandardMaterial>)>,
+    added_axes_helper_q: Query<(), Added<AxesHelperPart>>,
+    mut palette: Local<Option<ColorPalette>>,
+) {
+    // The additional antennas spawn axes helpers at any time: recolored as
+    // soon as they are added
+    if *palette == Some(display_widget.palette) && added_axes_helper_q.is_empty() {
+        return;
+    }
+    *palette = Some(display_widget.palette);
+    let colors = display_widget.palette.colors();
+    let mut set_color = |material: &MeshMaterial3d<StandardMaterial>, (r, g, b): Rgb| {
+        if let Some(mut material) = materials.get_mut(material) {
+            material.base_color = Color::srgb_u8(r, g, b);
+        }
+    };
+    for (contour_lines, material) in contour_lines_q.iter() {
+        set_color(material, match contour_lines {
+            ContourLines::Range => colors.iso_range_rgb,
+            ContourLines::Doppler => colors.iso_doppler_rgb,
+        });
+    }
+    for (is_elevation, material) in footprint_lines_q.iter() {
+        set_color(material, if is_elevation { colors.elevation_line_rgb } else { colors.azimuth_line_rgb });
+    }
+    for (part, material) in axes_helper_q.iter() {
+        set_color(material, match part {
+            AxesHelperPart::X => colors.axes_rgb[0],
+            AxesHelperPart::Y => colors.axes_rgb[1],
+            AxesHelperPart::Z => colors.axes_rgb[2],
+            AxesHelperPart::Origin => colors.origin_rgb,
+        });
+    }
+}
+
+/// Spawns the resolution direction arrows: unit arrows along +X (Y-up), in
+/// the ground plane, oriented and scaled by [`update_resolution_directions`].
+fn spawn_resolution_directions(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let arrow = meshes.add(LineList {
+        lines: vec![
+            (Vec3::ZERO, Vec3::X),
+            (Vec3::X, Vec3::new(0.85, 0.0, 0.08)),
+            (Vec3::X, Vec3::new(0.85, 0.0, -0.08)),
+        ],
+    });
+    let directions = [
+        (ResolutionDirection::Range, RANGE_DIRECTION_COLOR, "Ground range direction"),
+        (ResolutionDirection::Lateral, LATERAL_DIRECTION_COLOR, "Ground lateral direction"),
+    ];
+    for (direction, color, name) in directions {
+        commands.spawn((
+            Mesh3d(arrow.clone()),
+            MeshMaterial3d(materials.add(StandardMaterial {
+                base_color: color.into(),
+                unlit: true,
+                ..default()
+            })),
+            Transform::IDENTITY,
+            Visibility::Hidden,
+            direction,
+            Name::new(name),
+        ));
+    }
+}
+
+/// Orients the resolution direction arrows along the current BSAR geometry.
+fn update_resolution_directions(
+    display_widget: Res<DisplayWidget>,
+    bsar_infos_state: Res<BsarInfosState>,
+    tx_antenna_beam_footprint_state: Res<TxAntennaBeamFootprintState>,
+    rx_antenna_beam_footprint_state: Res<RxAntennaBeamFootprintState>,
+    mut resolution_direction_q: Query<(&ResolutionDirection, &mut Transform, &mut Visibility)>,
+) {
+    let tx_footprint = &tx_antenna_beam_footprint_state.inner;
+    let scene_extent_m = tx_footprint.range_center_m.max(rx_antenna_beam_footprint_state.inner.range_center_m);
+    for (direction, mut transform, mut visibility) in resolution_direction_q.iter_mut() {
+        let unit_vector = match direction {
+            ResolutionDirection::Range => bsar_infos_state.inner.ground_range_direction(),
+            ResolutionDirection::Lateral => bsar_infos_state.inner.ground_lateral_direction(),
+        };
+        let Some(unit_vector) = unit_vector.filter(|_| display_widget.show_resolution_directions) else {
+            visibility.set_if_neq(Visibility::Hidden);
+            continue;
+        };
+        // Above the footprints, to stay visible over them
+        let length = (RESOLUTION_DIRECTION_LENGTH_RATIO * scene_extent_m) as f32;
+        *transform = Transform {
+            translation: Vec3::new(0.0, 2.0 * tx_footprint.lift_m as f32, 0.0),
+            rotation: Quat::from_rotation_arc(Vec3::X, (TO_Y_UP_F64 * unit_vector).as_vec3()),
+            scale: Vec3::splat(length),
+        };
+        visibility.set_if_neq(Visibility::Inherited);
+    }
+}
+
+/// Spawns the min/max slant range point spheres, of unit radius, placed and
+/// scaled by [`update_range_points`].
+fn spawn_range_points(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let sphere = meshes.add(Sphere::new(1.0));
+    let range_min_material = materials.add(StandardMaterial {
+        base_color: RANGE_MIN_POINT_COLOR.into(),
+        unlit: true,
+        ..default()
+    });
+    let range_max_material = materials.add(StandardMaterial {
+        base_color: RANGE_MAX_POINT_COLOR.into(),
+        unlit: true,
+        ..default()
+    });
+    for range_point in RangePoint::ALL {
+        let material = match range_point {
+            RangePoint::TxMin | RangePoint::RxMin => range_min_material.clone(),
+            RangePoint::TxMax | RangePoint::RxMax => range_max_material.clone(),
+        };
+        commands.spawn((
+            Mesh3d(sphere.clone()),
+            MeshMaterial3d(material),
+            Transform::IDENTITY,
+            Visibility::Hidden,
+            range_point,
+            Name::new(format!("{range_point:?} range point")),
+        ));
+    }
+}
+
+/// Places the min/max slant range point spheres on the footprints.
+fn update_range_points(
+    display_widget: Res<DisplayWidget>,
+    tx_antenna_beam_footprint_state: Res<TxAntennaBeamFootprintState>,
+    rx_antenna_beam_footprint_state: Res<RxAntennaBeamFootprintState>,
+    mut range_point_q: Query<(&RangePoint, &mut Transform, &mut Visibility)>,
+) {
+    let is_changed = display_widget.is_changed()
+        || tx_antenna_beam_footprint_state.is_changed()
+        || rx_antenna_beam_footprint_state.is_changed();
+    if !is_changed {
+        return;
+    }
+    let tx_footprint = &tx_antenna_beam_footprint_state.inner;
+    let rx_footprint = &rx_antenna_beam_footprint_state.inner;
+    let scene_extent_m = tx_footprint.range_center_m.max(rx_footprint.range_center_m);
+    let radius = (RANGE_POINT_RADIUS_RATIO * scene_extent_m) as f32;
+    for (range_point, mut transform, mut visibility) in range_point_q.iter_mut() {
+        if !display_widget.show_range_points {
+            visibility.set_if_neq(Visibility::Hidden);
+            continue;
+        }
+        let (footprint, index) = match range_point {
+            RangePoint::TxMin => (tx_footprint, 0),
+            RangePoint::TxMax => (tx_footprint, 1),
+            RangePoint::RxMin => (rx_footprint, 0),
+            RangePoint::RxMax => (rx_footprint, 1),
+        };
+        let point = footprint.range_extrema_points()[index];
+        *transform = Transform {
+            translation: Vec3::new(point.x as f32, footprint.lift_m as f32, point.z as f32),
+            scale: Vec3::splat(radius),
+            ..default()
+        };
+        visibility.set_if_neq(Visibility::Inherited);
+    }
+}
+
+/// Spawns the contour line meshes, filled by [`update_contour_lines`].
+fn spawn_contour_lines(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // Colored by update_palette_materials
+    let contour_lines = [
+        (ContourLines::Range, "Iso-range lines"),
+        (ContourLines::Doppler, "Iso-Doppler lines"),
+    ];
+    for (lines, name) in contour_lines {
+        commands.spawn((
+            Mesh3d(meshes.add(LineList { lines: vec![] })),
+            MeshMaterial3d(materials.add(StandardMaterial {
+                unlit: true,
+                ..default()
+            })),
+            Transform::IDENTITY,
+            Visibility::Hidden,
+            lines,
+            Name::new(name),
+        ));
+    }
+}
+
+/// Gets the segments of the contour polylines, at `height_m` above the ground plane.
+fn contour_segments(contours: &[LevelContours], height_m: f64) -> Vec<(Vec3, Vec3)> {
+    IsoContours::world_polylines(contours, height_m).iter()
+        .flat_map(|polyline| polyline.windows(2).map(|segment| (segment[0], segment[1])))
+        .collect()
+}
+
+/// Shows the line mesh `mesh` with the new `lines`, or hides it without
+/// them. Line meshes live in the render world only: they are replaced when
+/// they change.
+pub(crate) fn replace_line_mesh(
+    meshes: &mut Assets<Mesh>,
+    mesh: &Mesh3d,
+    mut visibility: Mut<Visibility>,
+    lines: Option<impl Into<Mesh>>,
+) -> Result {
+    let Some(lines) = lines else {
+        visibility.set_if_neq(Visibility::Hidden);
+        return Ok(());
+    };
+    visibility.set_if_neq(Visibility::Inherited);
+    meshes.insert(mesh.id(), lines.into())?;
+    Ok(())
+}
+
+/// Draws the contours as line meshes on the ground, hiding the plane, or
+/// shows the plane texture.
+fn update_contour_lines(
+    display_widget: Res<DisplayWidget>,
+    roi_widget: Res<RoiWidget>,
+    iso_contours: Res<IsoContours>,
+    tx_antenna_beam_footprint_state: Res<TxAntennaBeamFootprintState>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut contour_lines_q: Query<(&ContourLines, &Mesh3d, &mut Visibility)>,
+    mut plane_visibility_q: Query<&mut Visibility, (With<IsoRangeDopplerPlane>, Without<ContourLines>)>,
+) -> Result {
+    let is_changed = display_widget.is_changed()
+        || roi_widget.is_changed()
+        || iso_contours.is_changed()
+        || tx_antenna_beam_footprint_state.is_changed();
+    if !is_changed {
+        return Ok(());
+    }
+    let is_lines = display_widget.contour_rendering == ContourRendering::Lines;
+    for mut visibility in plane_visibility_q.iter_mut() {
+        visibility.set_if_neq(if is_lines { Visibility::Hidden } else { Visibility::Inherited });
+    }
+    let height_m = CONTOUR_LINES_LIFT_RATIO * tx_antenna_beam_footprint_state.inner.lift_m;
+    for (contour_lines, mesh, mut visibility) in contour_lines_q.iter_mut() {
+        if !is_lines {
+            visibility.set_if_neq(Visibility::Hidden);
+            continue;
+        }
+        let contours = match contour_lines {
+            ContourLines::Range => &iso_contours.iso_range,
+            ContourLines::Doppler => &iso_contours.iso_doppler,
+        };
+        let contours: Vec<LevelContours> = match roi_widget.restriction() {
+            Some(rect) => contours.iter()
+                .map(|(level, lines)| (*level, lines.iter().flat_map(|line| rect.clip_polyline(line, false)).collect()))
+                .collect(),
+            None => contours.clone(),
+        };
+        replace_line_mesh(&mut meshes, mesh, visibility, Some(LineList { lines: contour_segments(&contours, height_m) }))?;
+    }
+
+    Ok(())
+}
+
+/// Spawns the beam / iso-range ellipsoid intersection curves, filled by
+/// [`update_beam_iso_range_curves`], in the colors of the footprints.
+fn spawn_beam_iso_range_curves(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let curves = [
+        (BeamIsoRangeCurve::Tx, Color::linear_rgb(1.0, 1.0, 1.0), "Tx beam iso-range curve"),
+        (BeamIsoRangeCurve::Rx, Color::linear_rgb(0.0, 0.0, 0.0), "Rx beam iso-range curve"),
+    ];
+    for (curve, color, name) in curves {
+        commands.spawn((
+            Mesh3d(meshes.add(LineStrip { points: vec![] })),
+            MeshMaterial3d(materials.add(StandardMaterial {
+                base_color: color,
+                unlit: true,
+                ..default()
+            })),
+            Transform::IDENTITY,
+            Visibility::Hidden,
+            curve,
+            Name::new(name),
+        ));
+    }
+}
+
+/// Draws the curves where the Tx and Rx beams intersect the iso-range
+/// ellipsoid: the illuminated part of the range shell.
+fn update_beam_iso_range_curves(
+    display_widget: Res<DisplayWidget>,
+    tx: (Res<TxCarrierState>, Res<TxAntennaState>, Res<TxAntennaBeamState>),
+    rx: (Res<RxCarrierState>, Res<RxAntennaState>, Res<RxAntennaBeamState>),
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut curve_q: Query<(&BeamIsoRangeCurve, &Mesh3d, &mut Visibility)>,
+) -> Result {
+    let is_changed = display_widget.is_changed()
+        || tx.0.is_changed() || tx.1.is_changed() || tx.2.is_changed()
+        || rx.0.is_changed() || rx.1.is_changed() || rx.2.is_changed();
+    if !is_changed {
+        return Ok(());
+    }
+    for (curve, mesh, mut visibility) in curve_q.iter_mut() {
+        if !display_widget.show_beam_iso_range_curves {
+            visibility.set_if_neq(Visibility::Hidden);
+            continue;
+        }
+        let points = match curve {
+            BeamIsoRangeCurve::Tx => beam_iso_range_curve(&tx.0.inner, &tx.1.inner, &tx.2.inner, &rx.0.inner.position_m),
+            BeamIsoRangeCurve::Rx => beam_iso_range_curve(&rx.0.inner, &rx.1.inner, &rx.2.inner, &tx.0.inner.position_m),
+        };
+        replace_line_mesh(&mut meshes, mesh, visibility, Some(LineStrip { points: points.iter().map(|point| point.as_vec3()).collect() }))?;
+    }
+
+    Ok(())
+}
+
+fn display_ui_system(
+    mut contexts: EguiContexts,
+    mut display_widget: ResMut<DisplayWidget>,
+    tx_antenna_beam_footprint_state: Res<TxAntennaBeamFootprintState>,
+    mut carrier_limits_state: ResMut<CarrierLimitsState>,
+    mut tx_carrier_state: ResMut<TxCarrierState>,
+    mut rx_carrier_state: ResMut<RxCarrierState>,
+    mut toasts: ResMut<Toasts>,
+) -> Result {
+    let ctx = contexts.ctx_mut()?;
+
+    let display_window = egui::Window::new("Display")
+        .resizable(false)
+        .constrain(true)
+        .collapsible(true)
+        .title_bar(true)
+        .enabled(true)
+        .default_open(false)
+        .default_pos(egui::pos2(320.0, 400.0));
+    display_window.show(ctx, |ui| {
+        display_ui(ui, &mut display_widget, tx_antenna_beam_footprint_state.inner.lift_m);
+        if carrier_limits_ui(ui, &mut carrier_limits_state.inner) {
+            // Carriers beyond the new limits are brought back within them
+            let limits = &carrier_limits_state.inner;
+            // Marked changed only when clamped, which triggers the update systems
+            if clamp_to_limits(&mut tx_carrier_state.bypass_change_detection().inner, limits) {
+                tx_carrier_state.set_changed();
+                toasts.warning("Transmitter brought within the new carrier limits");
+            }
+            if clamp_to_limits(&mut rx_carrier_state.bypass_change_detection().inner, limits) {
+                rx_carrier_state.set_changed();
+                toasts.warning("Receiver brought within the new carrier limits");
+            }
+        }
+    });
+
+    Ok(())
+}
+
+/// Clamps the carrier height and velocity to `limits`, returns true when changed.
+fn clamp_to_limits(carrier_state: &mut CarrierState, limits: &CarrierLimits) -> bool {
+    let (height_m, velocity_mps) = (carrier_state.height_m, carrier_state.velocity_mps);
+    carrier_state.height_m = height_m.min(limits.max_height_m);
+    carrier_state.velocity_mps = velocity_mps.min(limits.max_velocity_mps);
+    carrier_state.height_m != height_m || carrier_state.velocity_mps != velocity_mps
+}
+
+/// Returns true when the limits changed.
+fn carrier_limits_ui(ui: &mut egui::Ui, limits: &mut CarrierLimits) -> bool {
+    ui.separator();
+    ui.vertical_centered(|ui| ui.label(egui::RichText::new("CARRIER LIMITS").strong()));
+    ui.separator();
+
+    let old_limits = *limits;
+    egui::Grid::new("display_limits_grid")
+        .num_columns(2)
+        .striped(false)
+        .spacing([20.0, 5.0])
+        .show(ui, |ui| {
+            let bounds = CarrierLimits::MAX_HEIGHT_BOUNDS_M;
+            let hover_text = egui::RichText::new(format!(
+                "Sets the maximum carrier height of the panels ({} - {} m),\nalso set at startup by the {} environment variable",
+                bounds.start(), bounds.end(), crate::limits::MAX_HEIGHT_ENV_VAR
+            ))
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Max. height: ").on_hover_text(hover_text.clone());
+            ui.add(
+                egui::DragValue::new(&mut limits.max_height_m)
+                    .custom_parser(expression::parse)
+                    .update_while_editing(false)
+                    .speed(1000.0)
+                    .range(bounds)
+                    .fixed_decimals(0)
+                    .suffix(" m")
+            ).on_hover_text(hover_text);
+            ui.end_row();
+
+            let bounds = CarrierLimits::MAX_VELOCITY_BOUNDS_MPS;
+            let hover_text = egui::RichText::new(format!(
+                "Sets the maximum carrier velocity of the panels ({} - {} m/s),\nalso set at startup by the {} environment variable",
+                bounds.start(), bounds.end(), crate::limits::MAX_VELOCITY_ENV_VAR
+            ))
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Max. velocity: ").on_hover_text(hover_text.clone());
+            ui.add(
+                egui::DragValue::new(&mut limits.max_velocity_mps)
+                    .custom_parser(expression::parse)
+                    .update_while_editing(false)
+                    .speed(10.0)
+                    .range(bounds)
+                    .fixed_decimals(0)
+                    .suffix(" m/s")
+            ).on_hover_text(hover_text);
+            ui.end_row();
+        });
+    *limits != old_limits
+}
+
+fn display_ui(ui: &mut egui::Ui, widget: &mut DisplayWidget, lift_m: f64) {
+    ui.vertical_centered(|ui| ui.label(egui::RichText::new("FOOTPRINTS").strong()));
+    ui.separator();
+
+    egui::Grid::new("display_grid")
+        .num_columns(2)
+        .striped(false)
+        .spacing([20.0, 5.0])
+        .show(ui, |ui| {
+            let hover_text = egui::RichText::new("Computes the footprints from the intersection of the beam edge with the\nground, or approximates them with the ellipse of the beam widths projected\nwith the incidence (faster, exact for narrow beams)")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Model: ").on_hover_text(hover_text.clone());
+            ui.horizontal(|ui| {
+                for model in [FootprintModel::Numeric, FootprintModel::AnalyticEllipse] {
+                    ui.radio_value(&mut widget.footprint_model, model, model.label())
+                        .on_hover_text(hover_text.clone());
+                }
+            });
+            ui.end_row();
+
+            let hover_text = egui::RichText::new("Scales the footprints height above the ground with the scene extent,\nto avoid z-fighting at large camera distances")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Automatic lift: ").on_hover_text(hover_text.clone());
+            ui.checkbox(&mut widget.is_footprint_lift_auto, "").on_hover_text(hover_text);
+            ui.end_row();
+
+            if widget.is_footprint_lift_auto {
+                let hover_text = egui::RichText::new("Sets the footprints height above the ground as a fraction of the scene extent\n(farthest carrier distance to the scene center)")
+                    .color(TEXT_COLOR)
+                    .monospace();
+                ui.label("Lift ratio: ").on_hover_text(hover_text.clone());
+                ui.add(
+                    egui::DragValue::new(&mut widget.footprint_lift_ratio)
+                        .custom_parser(expression::parse)
+                        .update_while_editing(false)
+                        .speed(1e-6)
+                        .range(1e-7..=1e-3)
+                        .custom_formatter(|value, _| format!("{value:.1e}"))
+                ).on_hover_text(hover_text);
+                ui.end_row();
+
+                ui.label("Lift: ");
+                ui.label(format!("{lift_m:.3} m"));
+                ui.end_row();
+            } else {
+                let hover_text = egui::RichText::new(format!("Sets the footprints height above the ground ({FOOTPRINT_MIN_LIFT_M} - 1000 m)"))
+                    .color(TEXT_COLOR)
+                    .monospace();
+                ui.label("Lift: ").on_hover_text(hover_text.clone());
+                ui.add(
+                    egui::DragValue::new(&mut widget.footprint_lift_m)
+                        .custom_parser(expression::parse)
+                        .update_while_editing(false)
+                        .speed(0.01)
+                        .range(FOOTPRINT_MIN_LIFT_M..=1000.0)
+                        .fixed_decimals(3)
+                        .suffix(" m")
+                ).on_hover_text(hover_text);
+                ui.end_row();
+            }
+
+            let hover_text = egui::RichText::new("Marks the footprint points of min (green) and max (red) slant range")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Range points: ").on_hover_text(hover_text.clone());
+            ui.checkbox(&mut widget.show_range_points, "").on_hover_text(hover_text);
+            ui.end_row();
+        });
+
+    ui.separator();
+    ui.vertical_centered(|ui| ui.label(egui::RichText::new("CONTOURS").strong()));
+    ui.separator();
+
+    egui::Grid::new("display_contours_grid")
+        .num_columns(2)
+        .striped(false)
+        .spacing([20.0, 5.0])
+        .show(ui, |ui| {
+            let hover_text = egui::RichText::new("Sets the colors of the contours, of the footprint elevation / azimuth lines\nand of the axes helpers, in the 3D view, the top view and the drawings")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Palette: ").on_hover_text(hover_text.clone());
+            egui::ComboBox::from_id_salt("display_palette")
+                .selected_text(widget.palette.label())
+                .show_ui(ui, |ui| {
+                    for palette in ColorPalette::ALL {
+                        ui.selectable_value(&mut widget.palette, palette, palette.label());
+                    }
+                })
+                .response
+                .on_hover_text(hover_text);
+            ui.end_row();
+
+            let hover_text = egui::RichText::new("Sets the rendering of the iso-range/iso-Doppler contours: drawn into the
+plane texture with their values, or as line meshes on the ground, crisp at
+any zoom (the plane hidden)")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Rendering: ").on_hover_text(hover_text.clone());
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut widget.contour_rendering, ContourRendering::Texture, "Texture")
+                    .on_hover_text(hover_text.clone());
+                ui.radio_value(&mut widget.contour_rendering, ContourRendering::Lines, "Lines")
+                    .on_hover_text(hover_text);
+            });
+            ui.end_row();
+
+            let hover_text = egui::RichText::new("Sets the ground window over which the contours are evaluated, their\nresolution following its size: around the footprints, around the camera\nfocus (scaled with the camera distance), or a region of interest")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Window: ").on_hover_text(hover_text.clone());
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut widget.contour_window, ContourWindow::Footprints, "Footprints")
+                    .on_hover_text(hover_text.clone());
+                ui.radio_value(&mut widget.contour_window, ContourWindow::Camera, "Camera")
+                    .on_hover_text(hover_text.clone());
+                ui.radio_value(&mut widget.contour_window, ContourWindow::Region, "Region")
+                    .on_hover_text(hover_text);
+            });
+            ui.end_row();
+
+            if widget.contour_window == ContourWindow::Region {
+                let max_extent_m = 2.0 * HALF_PLANE_LENGTH as f64;
+                let hover_text = egui::RichText::new(format!("Sets the center of the region of interest, East and North of the scene\ncenter (-{max_extent_m} - {max_extent_m} m)"))
+                    .color(TEXT_COLOR)
+                    .monospace();
+                ui.label("Region center: ").on_hover_text(hover_text.clone());
+                ui.horizontal(|ui| {
+                    for (value, prefix) in [(&mut widget.region_center_m.x, "E "), (&mut widget.region_center_m.y, "N ")] {
+                        ui.add(
+                            egui::DragValue::new(value)
+                                .custom_parser(expression::parse)
+                                .update_while_editing(false)
+                                .speed(10.0)
+                                .range(-max_extent_m..=max_extent_m)
+                                .fixed_decimals(1)
+                                .prefix(prefix)
+                                .suffix(" m")
+                        ).on_hover_text(hover_text.clone());
+                    }
+                });
+                ui.end_row();
+
+                let hover_text = egui::RichText::new(format!("Sets the side of the square region of interest (1 - {max_extent_m} m)"))
+                    .color(TEXT_COLOR)
+                    .monospace();
+                ui.label("Region side: ").on_hover_text(hover_text.clone());
+                ui.add(
+                    egui::DragValue::new(&mut widget.region_extent_m)
+                        .custom_parser(expression::parse)
+                        .update_while_editing(false)
+                        .speed(10.0)
+                        .range(1.0..=max_extent_m)
+                        .fixed_decimals(1)
+                        .suffix(" m")
+                ).on_hover_text(hover_text);
+                ui.end_row();
+            }
+        });
+
+    ui.separator();
+    ui.vertical_centered(|ui| ui.label(egui::RichText::new("RESOLUTION").strong()));
+    ui.separator();
+
+    egui::Grid::new("display_resolution_grid")
+        .num_columns(2)
+        .striped(false)
+        .spacing([20.0, 5.0])
+        .show(ui, |ui| {
+            let hover_text = egui::RichText::new("Draws the ground range (orange) and lateral (cyan) resolution directions at the scene center")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Resolution directions: ").on_hover_text(hover_text.clone());
+            ui.checkbox(&mut widget.show_resolution_directions, "").on_hover_text(hover_text);
+            ui.end_row();
+
+            let hover_text = egui::RichText::new("Labels the footprint points of the min/max bistatic range (orange)\nand of the min/max Doppler frequency (cyan)")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Extrema annotations: ").on_hover_text(hover_text.clone());
+            ui.checkbox(&mut widget.show_footprint_annotations, "").on_hover_text(hover_text);
+            ui.end_row();
+
+            let hover_text = egui::RichText::new("Draws the curves where the Tx (white) and Rx (black) beams intersect the\niso-range ellipsoid: the illuminated part of the range shell")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Beam iso-range curves: ").on_hover_text(hover_text.clone());
+            ui.checkbox(&mut widget.show_beam_iso_range_curves, "").on_hover_text(hover_text);
+            ui.end_row();
+        });
+
+    ui.separator();
+    ui.vertical_centered(|ui| ui.label(egui::RichText::new("SQUINT").strong()));
+    ui.separator();
+
+    egui::Grid::new("display_squint_grid")
+        .num_columns(2)
+        .striped(false)
+        .spacing([20.0, 5.0])
+        .show(ui, |ui| {
+            let convention = &mut widget.squint_convention;
+            let hover_text = egui::RichText::new("Sets the reference of the antenna squint angle: the broadside (zero-Doppler)\nplane, or the velocity vector (90° at broadside)")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Reference: ").on_hover_text(hover_text.clone());
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut convention.reference, SquintReference::Broadside, "Broadside")
+                    .on_hover_text(hover_text.clone());
+                ui.radio_value(&mut convention.reference, SquintReference::VelocityVector, "Velocity vector")
+                    .on_hover_text(hover_text);
+            });
+            ui.end_row();
+
+            let hover_text = egui::RichText::new("Sets the sign of the squint angle from broadside, the angle from the\nvelocity vector being unsigned")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Sign: ").on_hover_text(hover_text.clone());
+            ui.add_enabled_ui(convention.reference == SquintReference::Broadside, |ui| {
+                ui.horizontal(|ui| {
+                    ui.radio_value(&mut convention.sign, SquintSign::ForwardPositive, "Forward +")
+                        .on_hover_text(hover_text.clone());
+                    ui.radio_value(&mut convention.sign, SquintSign::BackwardPositive, "Backward +")
+                        .on_hover_text(hover_text);
+                });
+            });
+            ui.end_row();
+        });
+}
@@ -0,0 +1,163 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts, EguiPrimaryContextPass};
+
+use crate::{
+    clutter::{ClutterLocus, DopplerBin},
+    scene::{BsarInfosState, RxAntennaBeamFootprintState, RxCarrierState, TxAntennaBeamFootprintState, TxCarrierState},
+    ui::UpdateStage,
+};
+
+const TEXT_COLOR: egui::Color32 = egui::Color32::from_rgb(200, 200, 200);
+const ERROR_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 120, 120);
+/// Color of the spectrum, as the clutter samples of the clutter locus
+const SPECTRUM_COLOR: egui::Color32 = egui::Color32::from_rgb(0, 220, 220);
+/// Doppler bins of the spectrum
+const DOPPLER_BIN_COUNT: usize = 48;
+
+pub struct DopplerSpectrumPlugin;
+
+impl Plugin for DopplerSpectrumPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<DopplerSpectrumWidget>()
+            // Sampled over the system updated by the update systems
+            .add_systems(
+                PostUpdate,
+                update_doppler_spectrum
+                    .in_set(UpdateStage::Derived)
+                    .after(super::tx_panel::update_bsar_system)
+            )
+            .add_systems(EguiPrimaryContextPass, doppler_spectrum_ui_system);
+    }
+}
+
+/// Doppler spectrum of the clutter: the histogram of the Doppler frequencies
+/// of the common (Tx ∩ Rx) footprint sampled on a ground grid, against the
+/// Doppler band sampled by the PRF.
+#[derive(Resource, Default)]
+pub struct DopplerSpectrumWidget {
+    pub is_enabled: bool,
+    locus: ClutterLocus,
+    histogram: Vec<DopplerBin>,
+    needs_update: bool,
+}
+
+/// Samples the spectrum when the system changes.
+fn update_doppler_spectrum(
+    mut doppler_spectrum_widget: ResMut<DopplerSpectrumWidget>,
+    bsar_infos_state: Res<BsarInfosState>,
+    tx: (Res<TxCarrierState>, Res<TxAntennaBeamFootprintState>),
+    rx: (Res<RxCarrierState>, Res<RxAntennaBeamFootprintState>),
+) {
+    if !doppler_spectrum_widget.is_enabled {
+        return;
+    }
+    // The widget is written by its window every frame: only a new system is sampled
+    if !doppler_spectrum_widget.needs_update && !bsar_infos_state.is_changed() {
+        return;
+    }
+    let widget = doppler_spectrum_widget.bypass_change_detection();
+    widget.locus = super::clutter_locus::clutter_locus((&tx.0, &tx.1), (&rx.0, &rx.1));
+    widget.histogram = widget.locus.doppler_histogram(DOPPLER_BIN_COUNT);
+    widget.needs_update = false;
+}
+
+fn doppler_spectrum_ui_system(
+    mut contexts: EguiContexts,
+    mut doppler_spectrum_widget: ResMut<DopplerSpectrumWidget>,
+    bsar_infos_state: Res<BsarInfosState>,
+    tx_carrier_state: Res<TxCarrierState>,
+) -> Result {
+    let ctx = contexts.ctx_mut()?;
+
+    let doppler_spectrum_window = egui::Window::new("Doppler Spectrum")
+        .resizable(false)
+        .constrain(true)
+        .collapsible(true)
+        .title_bar(true)
+        .enabled(true)
+        .default_open(false)
+        .default_pos(egui::pos2(320.0, 1240.0));
+    doppler_spectrum_window.show(ctx, |ui| {
+        // Band sampled by the PRF, around the Doppler frequency of the scene center
+        let doppler_center_hz = bsar_infos_state.inner.doppler_frequency_hz;
+        doppler_spectrum_ui(ui, &mut doppler_spectrum_widget, doppler_center_hz, tx_carrier_state.prf_hz);
+    });
+
+    Ok(())
+}
+
+fn doppler_spectrum_ui(ui: &mut egui::Ui, widget: &mut DopplerSpectrumWidget, doppler_center_hz: f64, prf_hz: f64) {
+    let hover_text = egui::RichText::new("Samples the common (Tx ∩ Rx) footprint on a ground grid and plots the histogram\nof the clutter Doppler frequencies, against the band sampled by the PRF")
+        .color(TEXT_COLOR)
+        .monospace();
+    if ui.checkbox(&mut widget.is_enabled, "Compute").on_hover_text(hover_text).changed() {
+        // Sampled again once enabled
+        widget.needs_update = true;
+    }
+    if !widget.is_enabled {
+        return;
+    }
+    if widget.histogram.is_empty() {
+        ui.label(egui::RichText::new("The footprints do not intersect").color(TEXT_COLOR));
+        return;
+    }
+    egui::Grid::new("doppler_spectrum_grid")
+        .num_columns(2)
+        .striped(false)
+        .spacing([20.0, 5.0])
+        .show(ui, |ui| {
+            ui.label("Doppler spread:");
+            ui.label(widget.locus.doppler_spread_hz().map_or("-".to_string(), |spread_hz| format!("{spread_hz:.3} Hz")));
+            ui.end_row();
+
+            ui.label("PRF:");
+            ui.label(format!("{prf_hz:.3} Hz"));
+            ui.end_row();
+
+            let hover_text = egui::RichText::new("Fraction of the footprint beyond ±PRF/2 of the Doppler frequency of the scene center,\nfolded back into the band by the pulse sampling: azimuth ambiguities")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Aliased footprint:").on_hover_text(hover_text);
+            let aliased_fraction = widget.locus.aliased_fraction(doppler_center_hz, prf_hz);
+            if aliased_fraction > 0.0 {
+                ui.label(egui::RichText::new(format!("⚠ {:.1} %", 100.0 * aliased_fraction)).color(ERROR_COLOR));
+            } else {
+                ui.label(format!("{:.1} %", 100.0 * aliased_fraction));
+            }
+            ui.end_row();
+        });
+
+    let bin_hz = match widget.histogram.as_slice() {
+        [first, second, ..] => second.doppler_hz - first.doppler_hz,
+        _ => 1.0,
+    };
+    let bars: Vec<egui_plot::Bar> = widget.histogram.iter()
+        .map(|bin| egui_plot::Bar::new(bin.doppler_hz, 100.0 * bin.fraction))
+        .collect();
+    egui_plot::Plot::new("doppler_spectrum_plot")
+        .width(320.0)
+        .height(200.0)
+        .include_y(0.0)
+        .x_axis_label("Doppler frequency [Hz]")
+        .y_axis_label("Footprint [%]")
+        .legend(egui_plot::Legend::default().follow_insertion_order(true))
+        .allow_drag(false)
+        .allow_zoom(false)
+        .allow_scroll(false)
+        .show(ui, |plot_ui| {
+            plot_ui.bar_chart(egui_plot::BarChart::new("Clutter", bars).color(SPECTRUM_COLOR).width(bin_hz));
+            plot_ui.vline(
+                egui_plot::VLine::new("Scene center", doppler_center_hz)
+                    .color(TEXT_COLOR)
+                    .style(egui_plot::LineStyle::dotted_dense())
+            );
+            for limit_hz in [doppler_center_hz - 0.5 * prf_hz, doppler_center_hz + 0.5 * prf_hz] {
+                plot_ui.vline(
+                    egui_plot::VLine::new("±PRF/2", limit_hz)
+                        .color(ERROR_COLOR)
+                        .width(1.5)
+                );
+            }
+        });
+}
@@ -0,0 +1,229 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts, EguiPrimaryContextPass};
+
+use crate::{
+    bsar::BsarInfos,
+    entities::{AntennaState, CarrierState},
+    epoch::UtcTime,
+    exercise::{relative_error, CarrierDraw, ExerciseGeometry, SplitMix64},
+    scene::{BsarInfosState, CarrierLimitsState, RxAntennaState, RxCarrierState, TxAntennaState, TxCarrierState},
+};
+
+const TEXT_COLOR: egui::Color32 = egui::Color32::from_rgb(200, 200, 200);
+/// Colors of the revealed predictions, within and beyond the tolerance
+const PASS_COLOR: egui::Color32 = egui::Color32::from_rgb(0, 200, 80);
+const FAIL_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 80, 80);
+/// Relative error of a prediction accepted as correct
+const TOLERANCE: f64 = 0.1;
+
+pub struct ExercisePlugin;
+
+impl Plugin for ExercisePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<ExerciseWidget>()
+            .add_systems(EguiPrimaryContextPass, exercise_ui_system);
+    }
+}
+
+/// A metric of the geometry to predict.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExerciseMetric {
+    BistaticAngle,
+    RangeCenter,
+    DirectRange,
+    GroundRangeResolution,
+    GroundLateralResolution,
+    DopplerFrequency,
+}
+
+impl ExerciseMetric {
+    pub const ALL: [Self; 6] = [
+        Self::BistaticAngle,
+        Self::RangeCenter,
+        Self::DirectRange,
+        Self::GroundRangeResolution,
+        Self::GroundLateralResolution,
+        Self::DopplerFrequency,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::BistaticAngle => "Bistatic angle [°]",
+            Self::RangeCenter => "Bistatic range [km]",
+            Self::DirectRange => "Tx-Rx distance [km]",
+            Self::GroundRangeResolution => "Ground range res. [m]",
+            Self::GroundLateralResolution => "Ground lateral res. [m]",
+            Self::DopplerFrequency => "Doppler centroid [Hz]",
+        }
+    }
+
+    /// Gets the value of the metric, in the unit of its label.
+    pub fn value(self, bsar_infos: &BsarInfos) -> f64 {
+        match self {
+            Self::BistaticAngle => bsar_infos.bistatic_angle_deg,
+            Self::RangeCenter => bsar_infos.range_center_m * 1e-3,
+            Self::DirectRange => bsar_infos.direct_range_m * 1e-3,
+            Self::GroundRangeResolution => bsar_infos.ground_range_resolution_m,
+            Self::GroundLateralResolution => bsar_infos.ground_lateral_resolution_m,
+            Self::DopplerFrequency => bsar_infos.doppler_frequency_hz,
+        }
+    }
+}
+
+/// Prediction of a metric by the student.
+#[derive(Default)]
+struct Prediction {
+    is_selected: bool,
+    text: String,
+}
+
+/// Teaching exercise: draws a random plausible bistatic geometry, the student
+/// predicts the selected metrics before revealing them.
+#[derive(Resource)]
+pub struct ExerciseWidget {
+    /// Seed of the current geometry, to draw it again
+    pub seed: u64,
+    predictions: Vec<Prediction>,
+    is_revealed: bool,
+}
+
+impl Default for ExerciseWidget {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            predictions: ExerciseMetric::ALL.iter()
+                .map(|&metric| Prediction {
+                    is_selected: matches!(metric, ExerciseMetric::BistaticAngle | ExerciseMetric::GroundRangeResolution),
+                    ..default()
+                })
+                .collect(),
+            is_revealed: false,
+        }
+    }
+}
+
+/// Sets the drawn state on a carrier flying straight and level.
+fn apply_draw(draw: &CarrierDraw, carrier_state: &mut CarrierState, antenna_state: &mut AntennaState) {
+    carrier_state.heading_deg = draw.heading_deg;
+    carrier_state.elevation_deg = 0.0;
+    carrier_state.bank_deg = 0.0;
+    carrier_state.height_m = draw.height_m;
+    carrier_state.velocity_mps = draw.velocity_mps;
+    antenna_state.heading_deg = draw.antenna_heading_deg;
+    antenna_state.elevation_deg = draw.antenna_elevation_deg;
+    antenna_state.bank_deg = 0.0;
+}
+
+fn exercise_ui_system(
+    mut contexts: EguiContexts,
+    mut exercise_widget: ResMut<ExerciseWidget>,
+    bsar_infos_state: Res<BsarInfosState>,
+    carrier_limits_state: Res<CarrierLimitsState>,
+    mut tx: (ResMut<TxCarrierState>, ResMut<TxAntennaState>),
+    mut rx: (ResMut<RxCarrierState>, ResMut<RxAntennaState>),
+) -> Result {
+    let ctx = contexts.ctx_mut()?;
+
+    let exercise_window = egui::Window::new("Exercise")
+        .resizable(false)
+        .constrain(true)
+        .collapsible(true)
+        .title_bar(true)
+        .enabled(true)
+        .default_open(false)
+        .default_pos(egui::pos2(320.0, 1280.0));
+    let mut draw_seed = None;
+    exercise_window.show(ctx, |ui| {
+        draw_seed = exercise_ui(ui, &mut exercise_widget, &bsar_infos_state.inner);
+    });
+    if let Some(seed) = draw_seed {
+        let limits = &carrier_limits_state.inner;
+        let geometry = ExerciseGeometry::random(&mut SplitMix64::new(seed), limits.max_height_m, limits.max_velocity_mps);
+        apply_draw(&geometry.tx, &mut tx.0.inner, &mut tx.1.inner);
+        apply_draw(&geometry.rx, &mut rx.0.inner, &mut rx.1.inner);
+    }
+
+    Ok(())
+}
+
+/// Shows the exercise, returning the seed of the geometry to draw.
+fn exercise_ui(ui: &mut egui::Ui, widget: &mut ExerciseWidget, bsar_infos: &BsarInfos) -> Option<u64> {
+    let mut draw_seed = None;
+    ui.horizontal(|ui| {
+        let hover_text = egui::RichText::new("Sets the Tx and Rx to a random airborne geometry: straight and level flights,\nside-looking antennas pointing at the scene center")
+            .color(TEXT_COLOR)
+            .monospace();
+        if ui.button("New exercise").on_hover_text(hover_text).clicked() {
+            widget.seed = (UtcTime::now().unix_s() * 1e6) as u64;
+            draw_seed = Some(widget.seed);
+        }
+        let hover_text = egui::RichText::new("Draws the geometry of the seed again, to share an exercise with a class")
+            .color(TEXT_COLOR)
+            .monospace();
+        ui.label("Seed:");
+        ui.add(egui::DragValue::new(&mut widget.seed).speed(1.0));
+        if ui.button("Draw").on_hover_text(hover_text).clicked() {
+            draw_seed = Some(widget.seed);
+        }
+    });
+    if draw_seed.is_some() {
+        widget.is_revealed = false;
+        for prediction in widget.predictions.iter_mut() {
+            prediction.text.clear();
+        }
+    }
+    ui.separator();
+
+    egui::Grid::new("exercise_grid")
+        .num_columns(4)
+        .striped(true)
+        .spacing([20.0, 5.0])
+        .show(ui, |ui| {
+            ui.label("Metric");
+            ui.label("Prediction");
+            ui.label("Value");
+            ui.label("Error");
+            ui.end_row();
+            for (metric, prediction) in ExerciseMetric::ALL.iter().zip(widget.predictions.iter_mut()) {
+                ui.checkbox(&mut prediction.is_selected, metric.label());
+                if !prediction.is_selected {
+                    ui.end_row();
+                    continue;
+                }
+                ui.add_enabled(
+                    !widget.is_revealed,
+                    egui::TextEdit::singleline(&mut prediction.text).desired_width(80.0)
+                );
+                if widget.is_revealed {
+                    let value = metric.value(bsar_infos);
+                    ui.label(format!("{value:.3}"));
+                    let error = prediction.text.trim().parse::<f64>().ok()
+                        .and_then(|predicted| relative_error(predicted, value));
+                    match error {
+                        Some(error) => {
+                            let color = if error <= TOLERANCE { PASS_COLOR } else { FAIL_COLOR };
+                            ui.label(egui::RichText::new(format!("{:.1} %", error * 1e2)).color(color));
+                        }
+                        None => {
+                            ui.label("-");
+                        }
+                    }
+                }
+                ui.end_row();
+            }
+        });
+
+    ui.horizontal(|ui| {
+        let hover_text = egui::RichText::new(format!("Shows the metrics of the geometry, predictions within {:.0} % being correct", TOLERANCE * 1e2))
+            .color(TEXT_COLOR)
+            .monospace();
+        if ui.add_enabled(!widget.is_revealed, egui::Button::new("Reveal")).on_hover_text(hover_text).clicked() {
+            widget.is_revealed = true;
+        }
+        if ui.add_enabled(widget.is_revealed, egui::Button::new("Hide")).clicked() {
+            widget.is_revealed = false;
+        }
+    });
+    draw_seed
+}
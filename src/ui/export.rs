@@ -0,0 +1,359 @@
+use bevy::{
+    math::DVec2,
+    mesh::{Indices, PrimitiveTopology, VertexAttributeValues},
+    prelude::*,
+};
+use bevy_egui::{egui, EguiContexts, EguiPrimaryContextPass};
+
+use crate::{
+    constants::TO_Y_UP_F64,
+    download::{SaveFileType, SaveRequest},
+    entities::{AntennaBeamFootprintState, IsoContours, LevelContours},
+    gltf::{to_glb, GltfMesh, GltfPrimitiveMode},
+    scene::{RxAntennaBeamFootprintState, RxCarrierState, TxAntennaBeamFootprintState, TxCarrierState},
+    ui::{menu::SAVE_ICON, DisplayWidget, RoiWidget, ScenarioWidget, Toasts},
+    vector_export::{to_dxf, to_svg, VectorLayer, VectorPath},
+};
+
+const TEXT_COLOR: egui::Color32 = egui::Color32::from_rgb(200, 200, 200);
+const ERROR_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 120, 120);
+/// Suggested file name of the 3D scene export
+const SCENE_EXPORT_FILE_NAME: &str = "bsargeom_scene.glb";
+/// Suggested file name of the top view exports, without extension
+const TOP_VIEW_EXPORT_FILE_STEM: &str = "bsargeom_top_view";
+/// Suggested file names of the footprint vertices exports
+const TX_FOOTPRINT_EXPORT_FILE_NAME: &str = "bsargeom_tx_footprint.csv";
+const RX_FOOTPRINT_EXPORT_FILE_NAME: &str = "bsargeom_rx_footprint.csv";
+/// Color of the exported Tx–Rx baseline
+const BASELINE_COLOR: [f32; 4] = [1.0, 1.0, 0.0, 1.0];
+
+pub struct ExportPlugin;
+
+impl Plugin for ExportPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<ExportWidget>()
+            .add_systems(EguiPrimaryContextPass, export_ui_system);
+    }
+}
+
+/// Exports of the scene to files for other tools.
+#[derive(Resource)]
+pub struct ExportWidget {
+    /// Adds the Tx–Rx baseline to the exported 3D scene
+    pub with_baseline: bool,
+    /// Adds the footprints and the iso-range/iso-Doppler contours to the top view
+    pub with_footprints: bool,
+    pub with_contours: bool,
+    /// Result of the last export click
+    status: Option<Result<String, String>>,
+    /// Export in flight
+    save_request: Option<SaveRequest>,
+}
+
+impl Default for ExportWidget {
+    fn default() -> Self {
+        Self {
+            with_baseline: true,
+            with_footprints: true,
+            with_contours: true,
+            status: None,
+            save_request: None,
+        }
+    }
+}
+
+/// Gets the glTF primitive mode of a Bevy mesh topology.
+fn gltf_primitive_mode(topology: PrimitiveTopology) -> GltfPrimitiveMode {
+    match topology {
+        PrimitiveTopology::PointList => GltfPrimitiveMode::Points,
+        PrimitiveTopology::LineList => GltfPrimitiveMode::Lines,
+        PrimitiveTopology::LineStrip => GltfPrimitiveMode::LineStrip,
+        PrimitiveTopology::TriangleList => GltfPrimitiveMode::Triangles,
+        PrimitiveTopology::TriangleStrip => GltfPrimitiveMode::TriangleStrip,
+    }
+}
+
+/// Bakes a visible mesh of the scene in World frame.
+///
+/// Returns `None` for meshes whose vertices are not kept in the main world
+/// (helpers) and for textured layers, which have no flat color.
+fn gltf_mesh(
+    name: String,
+    mesh: &Mesh,
+    material: &StandardMaterial,
+    global_transform: &GlobalTransform,
+) -> Option<GltfMesh> {
+    if material.base_color_texture.is_some() {
+        return None;
+    }
+    let Ok(VertexAttributeValues::Float32x3(positions)) = mesh.try_attribute(Mesh::ATTRIBUTE_POSITION) else {
+        return None;
+    };
+    let positions = positions.iter()
+        .map(|position| global_transform.transform_point(Vec3::from_array(*position)).to_array())
+        .collect();
+    let indices = mesh.try_indices().ok().map(|indices| match indices {
+        Indices::U16(indices) => indices.iter().map(|&i| i as u32).collect(),
+        Indices::U32(indices) => indices.clone(),
+    });
+    let color = material.base_color.to_linear();
+    Some(GltfMesh {
+        name,
+        positions,
+        indices,
+        mode: gltf_primitive_mode(mesh.primitive_topology()),
+        color: [color.red, color.green, color.blue, color.alpha],
+    })
+}
+
+/// Gets the top view layer of a footprint, in the ENU ground plane.
+pub(super) fn footprint_layer(name: &str, rgb: (u8, u8, u8), footprint_state: &AntennaBeamFootprintState) -> VectorLayer {
+    VectorLayer {
+        name: name.to_string(),
+        rgb,
+        paths: vec![VectorPath {
+            points: footprint_state.points.iter().map(|p| DVec2::new(p.z, p.x)).collect(), // Y-up to ENU
+            closed: true,
+            ..Default::default()
+        }],
+    }
+}
+
+/// Gets the top view layer of contours, negative levels dashed when `dash_negative`.
+pub(super) fn contours_layer(name: &str, rgb: (u8, u8, u8), contours: Vec<LevelContours>, unit: &str, dash_negative: bool) -> VectorLayer {
+    let paths = contours.into_iter().flat_map(|(level, lines)| {
+        lines.into_iter().map(move |points| VectorPath {
+            points,
+            closed: false,
+            dashed: dash_negative && level < 0.0,
+            label: Some(format!("{level:.1} {unit}")),
+        })
+    }).collect();
+    VectorLayer { name: name.to_string(), rgb, paths }
+}
+
+fn export_ui_system(
+    mut contexts: EguiContexts,
+    mut export_widget: ResMut<ExportWidget>,
+    tx_carrier_state: Res<TxCarrierState>,
+    rx_carrier_state: Res<RxCarrierState>,
+    tx_antenna_beam_footprint_state: Res<TxAntennaBeamFootprintState>,
+    rx_antenna_beam_footprint_state: Res<RxAntennaBeamFootprintState>,
+    iso_contours: Res<IsoContours>,
+    roi_widget: Res<RoiWidget>,
+    display_widget: Res<DisplayWidget>,
+    scenario_widget: Res<ScenarioWidget>,
+    meshes: Res<Assets<Mesh>>,
+    materials: Res<Assets<StandardMaterial>>,
+    mesh_q: Query<(Entity, &Mesh3d, &MeshMaterial3d<StandardMaterial>, &GlobalTransform, &InheritedVisibility)>,
+    name_q: Query<&Name>,
+    child_of_q: Query<&ChildOf>,
+    mut toasts: ResMut<Toasts>,
+) -> Result {
+    let ctx = contexts.ctx_mut()?;
+    // Drawings colored as the 3D view
+    let colors = display_widget.palette.colors();
+    let metadata = &scenario_widget.metadata;
+
+    if let Some(status) = SaveRequest::drive(&mut export_widget.save_request, ctx, &mut toasts) {
+        export_widget.status = Some(Ok(status));
+    }
+
+    let export_window = egui::Window::new("Export")
+        .resizable(false)
+        .constrain(true)
+        .collapsible(true)
+        .title_bar(true)
+        .enabled(true)
+        .default_open(false)
+        .default_pos(egui::pos2(320.0, 440.0));
+    export_window.show(ctx, |ui| {
+        let widget = &mut *export_widget; // Borrows the fields separately
+        ui.vertical_centered(|ui| ui.label(egui::RichText::new("3D SCENE").strong()));
+        ui.separator();
+
+        egui::Grid::new("export_scene_grid")
+            .num_columns(2)
+            .striped(false)
+            .spacing([20.0, 5.0])
+            .show(ui, |ui| {
+                let hover_text = egui::RichText::new("Adds the Tx–Rx baseline to the exported scene")
+                    .color(TEXT_COLOR)
+                    .monospace();
+                ui.label("Baseline: ").on_hover_text(hover_text.clone());
+                ui.checkbox(&mut widget.with_baseline, "").on_hover_text(hover_text);
+                ui.end_row();
+
+                let hover_text = egui::RichText::new(
+                    "Saves the visible carriers, beams and footprints as a binary glTF\n\
+                     scene (Y-up, meters, unlit flat colors). Textured layers (iso-range\n\
+                     Doppler plane, coverage, terrain) and helpers are not exported"
+                )
+                    .color(TEXT_COLOR)
+                    .monospace();
+                ui.label("Scene: ").on_hover_text(hover_text.clone());
+                if ui
+                    .add_enabled(
+                        widget.save_request.is_none(),
+                        egui::Button::image_and_text(SAVE_ICON, "GLB").frame_when_inactive(false)
+                    )
+                    .on_hover_text(hover_text)
+                    .clicked()
+                {
+                    let mut gltf_meshes: Vec<GltfMesh> = mesh_q.iter()
+                        .filter(|(.., visibility)| visibility.get())
+                        .filter_map(|(entity, mesh, material, global_transform, _)| {
+                            // Unnamed meshes take the name of their nearest named ancestor
+                            let name = std::iter::once(entity)
+                                .chain(child_of_q.iter_ancestors(entity))
+                                .find_map(|entity| name_q.get(entity).ok())
+                                .map_or_else(|| format!("Mesh {entity}"), |name| name.to_string());
+                            gltf_mesh(name, meshes.get(&mesh.0)?, materials.get(&material.0)?, global_transform)
+                        })
+                        .collect();
+                    if widget.with_baseline {
+                        let (txp, rxp) = (tx_carrier_state.inner.position_m, rx_carrier_state.inner.position_m);
+                        gltf_meshes.push(GltfMesh {
+                            name: "Baseline".to_string(),
+                            positions: vec![
+                                (TO_Y_UP_F64 * txp).as_vec3().to_array(),
+                                (TO_Y_UP_F64 * rxp).as_vec3().to_array(),
+                            ],
+                            indices: None,
+                            mode: GltfPrimitiveMode::Lines,
+                            color: BASELINE_COLOR,
+                        });
+                    }
+                    if gltf_meshes.is_empty() {
+                        widget.status = Some(Err("Nothing visible to export".to_string()));
+                    } else {
+                        widget.status = None;
+                        widget.save_request = Some(SaveRequest::with_file_type(
+                            SCENE_EXPORT_FILE_NAME,
+                            to_glb(&gltf_meshes, metadata),
+                            SaveFileType::Glb
+                        ));
+                    }
+                }
+                ui.end_row();
+            });
+
+        ui.separator();
+        ui.vertical_centered(|ui| ui.label(egui::RichText::new("2D TOP VIEW").strong()));
+        ui.separator();
+
+        egui::Grid::new("export_top_view_grid")
+            .num_columns(2)
+            .striped(false)
+            .spacing([20.0, 5.0])
+            .show(ui, |ui| {
+                let hover_text = egui::RichText::new("Adds the Tx and Rx half-power footprints to the top view")
+                    .color(TEXT_COLOR)
+                    .monospace();
+                ui.label("Footprints: ").on_hover_text(hover_text.clone());
+                ui.checkbox(&mut widget.with_footprints, "").on_hover_text(hover_text);
+                ui.end_row();
+
+                let hover_text = egui::RichText::new(
+                    "Adds the iso-range and iso-Doppler contours of the ground plane\n\
+                     to the top view (negative Doppler levels dashed)"
+                )
+                    .color(TEXT_COLOR)
+                    .monospace();
+                ui.label("Contours: ").on_hover_text(hover_text.clone());
+                ui.checkbox(&mut widget.with_contours, "").on_hover_text(hover_text);
+                ui.end_row();
+
+                let hover_text = egui::RichText::new(
+                    "Saves the top view as an SVG or DXF drawing in the scene ENU\n\
+                     frame: x East, y North, in meters from the scene center"
+                )
+                    .color(TEXT_COLOR)
+                    .monospace();
+                ui.label("Drawing: ").on_hover_text(hover_text.clone());
+                ui.horizontal(|ui| {
+                    let can_save = widget.save_request.is_none() && (widget.with_footprints || widget.with_contours);
+                    for (text, extension, file_type) in [("SVG", "svg", SaveFileType::Svg), ("DXF", "dxf", SaveFileType::Dxf)] {
+                        if ui
+                            .add_enabled(can_save, egui::Button::image_and_text(SAVE_ICON, text).frame_when_inactive(false))
+                            .on_hover_text(hover_text.clone())
+                            .clicked()
+                        {
+                            let mut layers = Vec::new();
+                            if widget.with_contours {
+                                layers.push(contours_layer(
+                                    "ISO_RANGE", colors.iso_range_rgb,
+                                    iso_contours.iso_range.clone(), "m", false
+                                ));
+                                layers.push(contours_layer(
+                                    "ISO_DOPPLER", colors.iso_doppler_rgb,
+                                    iso_contours.iso_doppler.clone(), "Hz", true
+                                ));
+                            }
+                            if widget.with_footprints {
+                                layers.push(footprint_layer("TX_FOOTPRINT", colors.tx_footprint_rgb, &tx_antenna_beam_footprint_state.inner));
+                                layers.push(footprint_layer("RX_FOOTPRINT", colors.rx_footprint_rgb, &rx_antenna_beam_footprint_state.inner));
+                            }
+                            if let Some(rect) = roi_widget.restriction() {
+                                layers = layers.into_iter().map(|layer| rect.clip_layer(layer)).collect();
+                            }
+                            let bytes = if file_type == SaveFileType::Dxf { to_dxf(&layers, metadata) } else { to_svg(&layers, metadata) };
+                            widget.status = None;
+                            widget.save_request = Some(SaveRequest::with_file_type(
+                                &format!("{TOP_VIEW_EXPORT_FILE_STEM}.{extension}"),
+                                bytes.into_bytes(),
+                                file_type
+                            ));
+                        }
+                    }
+                });
+                ui.end_row();
+            });
+
+        ui.separator();
+        ui.vertical_centered(|ui| ui.label(egui::RichText::new("FOOTPRINT VERTICES").strong()));
+        ui.separator();
+
+        let hover_text = egui::RichText::new(
+            "Saves the vertices of the half-power footprint as a CSV table: ENU\n\
+             coordinates from the scene center, slant range from the antenna and\n\
+             local incidence on the flat ground, headed by the scenario metadata\n\
+             as # comments"
+        )
+            .color(TEXT_COLOR)
+            .monospace();
+        ui.horizontal(|ui| {
+            ui.label("Vertices: ").on_hover_text(hover_text.clone());
+            let footprints = [
+                ("Tx", TX_FOOTPRINT_EXPORT_FILE_NAME, &tx_carrier_state.inner, &tx_antenna_beam_footprint_state.inner),
+                ("Rx", RX_FOOTPRINT_EXPORT_FILE_NAME, &rx_carrier_state.inner, &rx_antenna_beam_footprint_state.inner),
+            ];
+            for (text, file_name, carrier_state, footprint_state) in footprints {
+                if ui
+                    .add_enabled(
+                        widget.save_request.is_none(),
+                        egui::Button::image_and_text(SAVE_ICON, text).frame_when_inactive(false)
+                    )
+                    .on_hover_text(hover_text.clone())
+                    .clicked()
+                {
+                    widget.status = None;
+                    widget.save_request = Some(SaveRequest::with_file_type(
+                        file_name,
+                        format!("{}{}", metadata.csv_comments(), footprint_state.vertices_csv(&carrier_state.position_m)).into_bytes(),
+                        SaveFileType::Csv
+                    ));
+                }
+            }
+        });
+
+        match &widget.status {
+            Some(Ok(status)) => { ui.label(egui::RichText::new(status).color(TEXT_COLOR)); }
+            Some(Err(error)) => { ui.label(egui::RichText::new(error).color(ERROR_COLOR)); }
+            None => {}
+        }
+    });
+
+    Ok(())
+}
@@ -0,0 +1,156 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts, EguiPrimaryContextPass};
+use bevy_panorbit_camera::PanOrbitCamera;
+
+use crate::{
+    extension::{AppExtensionsExt, Extensions, FootprintCentroids, SceneView},
+    scene::{
+        BsarInfosState,
+        RxAntennaBeamFootprintState, RxAntennaState, RxCarrierState,
+        TxAntennaBeamFootprintState, TxAntennaState, TxCarrierState
+    },
+    ui::{SidePanelRects, UpdateStage},
+};
+
+const TEXT_COLOR: egui::Color32 = egui::Color32::from_rgb(200, 200, 200);
+/// Radius of the labelled point markers in points
+const MARKER_RADIUS: f32 = 3.0;
+
+pub struct ExtensionsPlugin;
+
+impl Plugin for ExtensionsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_extension(FootprintCentroids)
+            // After the BSAR infos, as the footprint annotations
+            .add_systems(
+                PostUpdate,
+                update_extensions.in_set(UpdateStage::RenderAssets)
+            )
+            .add_systems(EguiPrimaryContextPass, (extensions_ui_system, extension_overlays_ui_system));
+    }
+}
+
+type TxStates<'w> = (Res<'w, TxCarrierState>, Res<'w, TxAntennaState>, Res<'w, TxAntennaBeamFootprintState>);
+type RxStates<'w> = (Res<'w, RxCarrierState>, Res<'w, RxAntennaState>, Res<'w, RxAntennaBeamFootprintState>);
+
+fn scene_view<'a>(tx: &'a TxStates, rx: &'a RxStates, bsar_infos_state: &'a BsarInfosState) -> SceneView<'a> {
+    SceneView {
+        tx_carrier: &tx.0,
+        tx_antenna: &tx.1.inner,
+        tx_footprint: &tx.2.inner,
+        rx_carrier: &rx.0,
+        rx_antenna: &rx.1.inner,
+        rx_footprint: &rx.2.inner,
+        infos: &bsar_infos_state.inner,
+    }
+}
+
+/// Recomputes the metrics and the overlays of the extensions when the
+/// geometry changes.
+fn update_extensions(
+    mut extensions: ResMut<Extensions>,
+    tx: TxStates,
+    rx: RxStates,
+    bsar_infos_state: Res<BsarInfosState>,
+) {
+    let are_states_changed = extensions.is_added()
+        || tx.0.is_changed() || tx.1.is_changed() || tx.2.is_changed()
+        || rx.0.is_changed() || rx.1.is_changed() || rx.2.is_changed()
+        || bsar_infos_state.is_changed();
+    if are_states_changed {
+        extensions.update(&scene_view(&tx, &rx, &bsar_infos_state));
+    }
+}
+
+fn extensions_ui_system(
+    mut contexts: EguiContexts,
+    mut extensions: ResMut<Extensions>,
+    tx: TxStates,
+    rx: RxStates,
+    bsar_infos_state: Res<BsarInfosState>,
+) -> Result {
+    let ctx = contexts.ctx_mut()?;
+    let scene = scene_view(&tx, &rx, &bsar_infos_state);
+
+    let extensions_window = egui::Window::new("Extensions")
+        .resizable(false)
+        .constrain(true)
+        .collapsible(true)
+        .title_bar(true)
+        .enabled(true)
+        .default_open(false)
+        .default_pos(egui::pos2(320.0, 1400.0));
+    extensions_window.show(ctx, |ui| {
+        // Avoids marking the extensions as changed every frame
+        for (i, registered) in extensions.bypass_change_detection().registered.iter_mut().enumerate() {
+            if i > 0 {
+                ui.add_space(8.0);
+            }
+            ui.separator();
+            ui.vertical_centered(|ui| ui.label(egui::RichText::new(registered.extension.name().to_uppercase()).strong()));
+            ui.separator();
+
+            egui::Grid::new(("extension_metrics_grid", i))
+                .num_columns(2)
+                .striped(true)
+                .show(ui, |ui| {
+                    for metric in registered.metrics.iter() {
+                        ui.label(format!("{}:", metric.name));
+                        ui.label(if metric.value.is_finite() { format!("{:.3} {}", metric.value, metric.unit) } else { "-".to_string() });
+                        ui.end_row();
+                    }
+                    if !registered.labels.is_empty() {
+                        let hover_text = egui::RichText::new("Labels the points of the extension in the 3D view")
+                            .color(TEXT_COLOR)
+                            .monospace();
+                        ui.label("Overlay:").on_hover_text(hover_text.clone());
+                        ui.checkbox(&mut registered.show_overlay, "").on_hover_text(hover_text);
+                        ui.end_row();
+                    }
+                });
+            registered.extension.panel_ui(ui, &scene);
+        }
+    });
+
+    Ok(())
+}
+
+/// Paints the labels of the extensions next to their points, projected by the
+/// 3D camera, under the windows and within the 3D view.
+fn extension_overlays_ui_system(
+    mut contexts: EguiContexts,
+    extensions: Res<Extensions>,
+    side_panel_rects: Res<SidePanelRects>,
+    camera_q: Single<(&Camera, &GlobalTransform), With<PanOrbitCamera>>,
+) -> Result {
+    let ctx = contexts.ctx_mut()?;
+    let (camera, camera_transform) = *camera_q;
+
+    let view_rect = side_panel_rects.view_rect(ctx);
+    let painter = ctx
+        .layer_painter(egui::LayerId::new(egui::Order::Background, egui::Id::new("extension_overlays")))
+        .with_clip_rect(view_rect);
+    let labels = extensions.registered.iter()
+        .filter(|registered| registered.show_overlay)
+        .flat_map(|registered| registered.labels.iter());
+    for label in labels {
+        // Points behind the camera have no viewport position
+        let Ok(position) = camera.world_to_viewport(camera_transform, label.point_m.as_vec3()) else {
+            continue;
+        };
+        // From logical pixels to egui points
+        let position = position / ctx.zoom_factor();
+        let position = egui::pos2(position.x, position.y);
+        painter.circle_filled(position, MARKER_RADIUS, label.color);
+        painter.text(
+            position + egui::vec2(2.0 * MARKER_RADIUS, 0.0),
+            egui::Align2::LEFT_CENTER,
+            &label.text,
+            egui::FontId::monospace(12.0),
+            label.color,
+        );
+    }
+
+    Ok(())
+}
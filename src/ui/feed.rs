@@ -0,0 +1,288 @@
+use bevy::{math::DVec3, prelude::*};
+use bevy_egui::{egui, EguiContexts, EguiPrimaryContextPass};
+
+use crate::{
+    coordinates::GeographicPoint,
+    entities::{AntennaState, CarrierState, LineList},
+    feed::{pointing_to_scene_center, Platform, PlatformTable},
+    limits::CarrierLimits,
+    scene::{CarrierLimitsState, RxAntennaState, RxCarrierState, TxAntennaState, TxCarrierState},
+    ui::{GeoReferenceWidget, UpdateStage},
+};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::feed::{FeedConnection, PlatformUpdate};
+
+const TEXT_COLOR: egui::Color32 = egui::Color32::from_rgb(200, 200, 200);
+const ERROR_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 120, 120);
+/// Color of the platform markers
+const PLATFORM_COLOR: Srgba = Srgba::rgb(0.2, 0.9, 0.9);
+
+pub struct FeedPlugin;
+
+impl Plugin for FeedPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<FeedWidget>()
+            .add_systems(Startup, spawn_platform_markers)
+            .add_systems(PostUpdate, update_platform_markers.in_set(UpdateStage::RenderAssets))
+            .add_systems(EguiPrimaryContextPass, feed_ui_system);
+        #[cfg(not(target_arch = "wasm32"))]
+        app.add_systems(PostUpdate, read_feed.in_set(UpdateStage::Input));
+    }
+}
+
+/// Live platform positions read from a network feed (ADS-B, AIS, targets),
+/// drawn in the scene and usable as the transmitter or the receiver.
+#[derive(Resource)]
+pub struct FeedWidget {
+    /// Feed `host:port`
+    pub address: String,
+    pub table: PlatformTable,
+    #[cfg(not(target_arch = "wasm32"))]
+    connection: Option<FeedConnection>,
+    /// Result of the last connection or platform use
+    status: Option<Result<String, String>>,
+    markers_need_update: bool,
+}
+
+impl Default for FeedWidget {
+    fn default() -> Self {
+        Self {
+            address: "127.0.0.1:30003".to_string(), // dump1090 SBS-1 output
+            table: PlatformTable::default(),
+            #[cfg(not(target_arch = "wasm32"))]
+            connection: None,
+            status: None,
+            markers_need_update: false,
+        }
+    }
+}
+
+/// Markers of the feed platforms: a cross at the platform over a line down
+/// to the ground.
+#[derive(Component)]
+pub struct PlatformMarkers;
+
+fn spawn_platform_markers(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.spawn((
+        Mesh3d(meshes.add(LineList { lines: vec![] })),
+        MeshMaterial3d(materials.add(StandardMaterial {
+            base_color: PLATFORM_COLOR.into(),
+            unlit: true,
+            ..default()
+        })),
+        Transform::IDENTITY,
+        Visibility::Hidden,
+        PlatformMarkers,
+        Name::new("Feed platforms"),
+    ));
+}
+
+/// Gets the platform position in the scene (ENU), when located.
+fn platform_position_m(geo_reference_widget: &GeoReferenceWidget, platform: &Platform) -> Option<DVec3> {
+    let (latitude_deg, longitude_deg, altitude_m) = platform.location()?;
+    let point = GeographicPoint::from_degrees(longitude_deg, latitude_deg, altitude_m);
+    Some(geo_reference_widget.scene_origin.transform_from_geographic_point_to_enu_point(&point))
+}
+
+/// Merges the lines received from the feed into the platform table.
+#[cfg(not(target_arch = "wasm32"))]
+fn read_feed(mut feed_widget: ResMut<FeedWidget>) {
+    let widget = feed_widget.bypass_change_detection(); // Changed only by new lines
+    let Some(connection) = &widget.connection else {
+        return;
+    };
+    let lines = connection.take_lines();
+    if let Some(error) = connection.error() {
+        widget.status = Some(Err(error));
+        widget.connection = None;
+    }
+    if lines.is_empty() {
+        return;
+    }
+    for update in lines.iter().filter_map(|line| PlatformUpdate::from_line(line)) {
+        widget.table.update(update);
+    }
+    widget.markers_need_update = true;
+    feed_widget.set_changed();
+}
+
+/// Redraws the platform markers.
+fn update_platform_markers(
+    mut feed_widget: ResMut<FeedWidget>,
+    geo_reference_widget: Res<GeoReferenceWidget>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut markers_q: Query<(&Mesh3d, &mut Visibility), With<PlatformMarkers>>,
+) -> Result {
+    if !(feed_widget.markers_need_update || geo_reference_widget.is_changed()) {
+        return Ok(());
+    }
+    // World frame is Y-up
+    let to_world = |point_m: DVec3| Vec3::new(point_m.y as f32, point_m.z as f32, point_m.x as f32);
+    let lines: Vec<(Vec3, Vec3)> = feed_widget.table.platforms().iter()
+        .filter_map(|platform| platform_position_m(&geo_reference_widget, platform))
+        .flat_map(|position_m| {
+            // Sized with the distance to stay visible from the scene
+            let size_m = (0.02 * position_m.length()).max(50.0);
+            let ground_m = DVec3::new(position_m.x, position_m.y, 0.0);
+            [
+                (ground_m, position_m),
+                (position_m - size_m * DVec3::X, position_m + size_m * DVec3::X),
+                (position_m - size_m * DVec3::Y, position_m + size_m * DVec3::Y),
+            ]
+        })
+        .map(|(start_m, end_m)| (to_world(start_m), to_world(end_m)))
+        .collect();
+    for (mesh, visibility) in markers_q.iter_mut() {
+        super::display::replace_line_mesh(&mut meshes, mesh, visibility, (!lines.is_empty()).then(|| LineList { lines: lines.clone() }))?;
+    }
+    feed_widget.markers_need_update = false;
+
+    Ok(())
+}
+
+/// Places a carrier at the platform, level along its track at its speed, its
+/// antenna pointing to the scene center.
+fn use_platform(
+    platform: &Platform,
+    position_m: &DVec3,
+    limits: &CarrierLimits,
+    carrier_state: &mut CarrierState,
+    antenna_state: &mut AntennaState,
+) -> Result<String, String> {
+    if position_m.z <= 0.0 {
+        return Err(format!("{} is below the scene horizon", platform.name()));
+    }
+    if position_m.z > limits.max_height_m {
+        return Err(format!("{} is above the maximum height ({} m)", platform.name(), limits.max_height_m));
+    }
+    let carrier_heading_deg = platform.track_deg.unwrap_or(carrier_state.heading_deg);
+    let (height_m, antenna_heading_deg, antenna_elevation_deg) = pointing_to_scene_center(position_m, carrier_heading_deg);
+    carrier_state.heading_deg = carrier_heading_deg;
+    carrier_state.elevation_deg = 0.0;
+    carrier_state.bank_deg = 0.0;
+    carrier_state.height_m = height_m;
+    if let Some(ground_speed_mps) = platform.ground_speed_mps {
+        carrier_state.velocity_mps = ground_speed_mps.min(limits.max_velocity_mps);
+    }
+    antenna_state.heading_deg = antenna_heading_deg;
+    antenna_state.elevation_deg = antenna_elevation_deg;
+    Ok(format!("{} used at {:.1} m height, looking {antenna_heading_deg:+.1}° / {antenna_elevation_deg:+.1}°", platform.name(), height_m))
+}
+
+fn feed_ui_system(
+    mut contexts: EguiContexts,
+    mut feed_widget: ResMut<FeedWidget>,
+    geo_reference_widget: Res<GeoReferenceWidget>,
+    carrier_limits_state: Res<CarrierLimitsState>,
+    tx: (ResMut<TxCarrierState>, ResMut<TxAntennaState>),
+    rx: (ResMut<RxCarrierState>, ResMut<RxAntennaState>),
+) -> Result {
+    let ctx = contexts.ctx_mut()?;
+    let (mut tx_carrier_state, mut tx_antenna_state) = tx;
+    let (mut rx_carrier_state, mut rx_antenna_state) = rx;
+
+    let feed_window = egui::Window::new("Platform Feed")
+        .resizable(false)
+        .constrain(true)
+        .collapsible(true)
+        .title_bar(true)
+        .enabled(true)
+        .default_open(false)
+        .default_pos(egui::pos2(320.0, 760.0));
+    feed_window.show(ctx, |ui| {
+        let widget = &mut *feed_widget; // Borrows the fields separately
+        feed_connection_ui(ui, widget);
+        match &widget.status {
+            Some(Ok(status)) => { ui.label(egui::RichText::new(status).color(TEXT_COLOR)); }
+            Some(Err(error)) => { ui.label(egui::RichText::new(error).color(ERROR_COLOR)); }
+            None => {}
+        }
+        if widget.table.platforms().is_empty() {
+            return;
+        }
+
+        let mut used = None;
+        egui::ScrollArea::vertical().max_height(240.0).show(ui, |ui| {
+            egui::Grid::new("feed_grid")
+                .num_columns(5)
+                .striped(true)
+                .spacing([10.0, 5.0])
+                .show(ui, |ui| {
+                    for header in ["Platform", "Distance", "Altitude", "Speed", "Use as"] {
+                        ui.label(egui::RichText::new(header).strong());
+                    }
+                    ui.end_row();
+                    for platform in widget.table.platforms() {
+                        let position_m = platform_position_m(&geo_reference_widget, platform);
+                        ui.label(platform.name());
+                        ui.label(position_m.map_or("-".to_string(), |position_m| {
+                            format!("{:.2} km", position_m.x.hypot(position_m.y) * 1e-3)
+                        }));
+                        ui.label(platform.altitude_m.map_or("-".to_string(), |altitude_m| format!("{altitude_m:.0} m")));
+                        ui.label(platform.ground_speed_mps.map_or("-".to_string(), |speed_mps| format!("{speed_mps:.1} m/s")));
+                        ui.horizontal(|ui| {
+                            if let Some(position_m) = position_m {
+                                if ui.button("Tx").clicked() {
+                                    used = Some((platform.clone(), position_m, true));
+                                }
+                                if ui.button("Rx").clicked() {
+                                    used = Some((platform.clone(), position_m, false));
+                                }
+                            }
+                        });
+                        ui.end_row();
+                    }
+                });
+        });
+        // The states are only written on use, which triggers the update systems
+        if let Some((platform, position_m, is_tx)) = used {
+            let status = if is_tx {
+                use_platform(&platform, &position_m, &carrier_limits_state.inner, &mut tx_carrier_state.inner, &mut tx_antenna_state.inner)
+            } else {
+                use_platform(&platform, &position_m, &carrier_limits_state.inner, &mut rx_carrier_state.inner, &mut rx_antenna_state.inner)
+            };
+            widget.status = Some(status);
+        }
+    });
+
+    Ok(())
+}
+
+/// Feed address and connection buttons.
+#[cfg(not(target_arch = "wasm32"))]
+fn feed_connection_ui(ui: &mut egui::Ui, widget: &mut FeedWidget) {
+    let hover_text = egui::RichText::new("Reads platform positions from a TCP feed (host:port), one per line:\nSBS-1 (BaseStation) ADS-B messages, e.g. dump1090 on port 30003,\nor \"id,latitude,longitude,altitude[,speed,track]\" lines (degrees, m, m/s)")
+        .color(TEXT_COLOR)
+        .monospace();
+    ui.horizontal(|ui| {
+        ui.label("Feed: ").on_hover_text(hover_text.clone());
+        let is_connected = widget.connection.as_ref().is_some_and(FeedConnection::is_running);
+        ui.add_enabled(
+            !is_connected,
+            egui::TextEdit::singleline(&mut widget.address).desired_width(160.0)
+        ).on_hover_text(hover_text);
+        if is_connected {
+            if ui.button("Disconnect").clicked() {
+                widget.connection = None;
+                widget.status = Some(Ok(format!("Disconnected from {}", widget.address)));
+            }
+        } else if ui.button("Connect").clicked() {
+            widget.connection = Some(FeedConnection::connect(&widget.address));
+            widget.status = Some(Ok(format!("Reading {}", widget.address)));
+        }
+        if ui.button("Clear").on_hover_text("Removes the received platforms").clicked() {
+            widget.table.clear();
+            widget.markers_need_update = true;
+        }
+    });
+}
+
+#[cfg(target_arch = "wasm32")]
+fn feed_connection_ui(ui: &mut egui::Ui, _widget: &mut FeedWidget) {
+    ui.label(egui::RichText::new("Network feeds are only available in the desktop application").color(TEXT_COLOR));
+}
@@ -0,0 +1,148 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts, EguiPrimaryContextPass};
+
+use crate::scene::{BsarInfosState, TxCarrierState};
+
+const TEXT_COLOR: egui::Color32 = egui::Color32::from_rgb(200, 200, 200);
+const ERROR_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 120, 120);
+/// Largest frequency offset of the slider in ppm
+const MAX_OFFSET_PPM: f64 = 50.0;
+/// Quadratic phase error defocusing the image, in degrees
+const QPE_WARNING_DEG: f64 = 45.0;
+
+pub struct FrequencyOffsetPlugin;
+
+impl Plugin for FrequencyOffsetPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<FrequencyOffsetWidget>()
+            .add_systems(EguiPrimaryContextPass, frequency_offset_ui_system);
+    }
+}
+
+/// What-if offset of the Tx center frequency, modeling the drift of the Tx
+/// oscillator (temperature, aging) against the receiver one in distributed
+/// systems. The Tx state is left untouched.
+#[derive(Resource)]
+pub struct FrequencyOffsetWidget {
+    pub offset_ppm: f64,
+}
+
+impl Default for FrequencyOffsetWidget {
+    fn default() -> Self {
+        Self { offset_ppm: 1.0 }
+    }
+}
+
+fn frequency_offset_ui_system(
+    mut contexts: EguiContexts,
+    mut frequency_offset_widget: ResMut<FrequencyOffsetWidget>,
+    bsar_infos_state: Res<BsarInfosState>,
+    tx_carrier_state: Res<TxCarrierState>,
+) -> Result {
+    let ctx = contexts.ctx_mut()?;
+
+    let frequency_offset_window = egui::Window::new("Frequency Offset")
+        .resizable(false)
+        .constrain(true)
+        .collapsible(true)
+        .title_bar(true)
+        .enabled(true)
+        .default_open(false)
+        .default_pos(egui::pos2(320.0, 720.0));
+    frequency_offset_window.show(ctx, |ui| {
+        frequency_offset_ui(ui, &mut frequency_offset_widget, &bsar_infos_state, &tx_carrier_state);
+    });
+
+    Ok(())
+}
+
+fn frequency_offset_ui(
+    ui: &mut egui::Ui,
+    widget: &mut FrequencyOffsetWidget,
+    bsar_infos_state: &BsarInfosState,
+    tx_carrier_state: &TxCarrierState,
+) {
+    let bsar_infos = &bsar_infos_state.inner;
+    let center_frequency_hz = tx_carrier_state.center_frequency_ghz * 1e9;
+    let hover_text = egui::RichText::new(
+        "Offsets the Tx center frequency from the nominal one, the receiver demodulating and focusing\n\
+         at the nominal frequency: typical drifts are 0.1 - 1 ppm for an OCXO, 1 - 10 ppm for a TCXO"
+    )
+        .color(TEXT_COLOR)
+        .monospace();
+    ui.horizontal(|ui| {
+        ui.label("Offset: ").on_hover_text(hover_text);
+        ui.add(
+            egui::Slider::new(&mut widget.offset_ppm, -MAX_OFFSET_PPM..=MAX_OFFSET_PPM)
+                .step_by(0.01)
+                .fixed_decimals(2)
+                .suffix(" ppm")
+        );
+        if ui.button("Reset").on_hover_text("Sets the offset to 0 ppm").clicked() {
+            widget.offset_ppm = 0.0;
+        }
+    });
+
+    let impact = bsar_infos.frequency_offset_impact(center_frequency_hz, widget.offset_ppm);
+    egui::Grid::new("frequency_offset_grid")
+        .num_columns(2)
+        .striped(false)
+        .spacing([20.0, 5.0])
+        .show(ui, |ui| {
+            ui.label("Center frequency: ");
+            ui.label(format!(
+                "{:.9} GHz ({:+.3} kHz)",
+                1e-9 * (center_frequency_hz + impact.carrier_offset_hz),
+                1e-3 * impact.carrier_offset_hz
+            ));
+            ui.end_row();
+
+            let hover_text = egui::RichText::new("Change of the Doppler frequency and rate at the scene center, scaling with the carrier frequency")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Doppler change: ").on_hover_text(hover_text.clone());
+            ui.label(format!("{:+.4} Hz", impact.doppler_change_hz));
+            ui.end_row();
+
+            ui.label("Doppler rate change: ").on_hover_text(hover_text);
+            ui.label(format!("{:+.6} Hz/s", impact.doppler_rate_change_hzps));
+            ui.end_row();
+
+            let hover_text = egui::RichText::new(
+                "Shift of the image across the iso-Doppler lines: the carrier offset and the Doppler change\n\
+                 read as Doppler frequencies, over the ground Doppler gradient"
+            )
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Lateral shift: ").on_hover_text(hover_text);
+            ui.label(format!(
+                "{:+.2} m ({:+.1} resolutions)",
+                impact.lateral_shift_m,
+                impact.lateral_shift_m / bsar_infos.ground_lateral_resolution_m
+            ));
+            ui.end_row();
+
+            let hover_text = egui::RichText::new("Range error at the scene center, the PRF and sampling clocks derived from the drifting oscillator")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Range scaling: ").on_hover_text(hover_text);
+            ui.label(format!(
+                "{:+.3} m ({:+.2} resolutions)",
+                impact.range_scaling_m,
+                impact.range_scaling_m / bsar_infos.slant_range_resolution_m
+            ));
+            ui.end_row();
+
+            let hover_text = egui::RichText::new("Phase error at the edges of the integration time, focusing with the nominal Doppler rate")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Quadratic phase error: ").on_hover_text(hover_text);
+            if impact.quadratic_phase_error_deg > QPE_WARNING_DEG {
+                ui.label(egui::RichText::new(format!("⚠ {:.2}°", impact.quadratic_phase_error_deg)).color(ERROR_COLOR));
+            } else {
+                ui.label(format!("{:.2}°", impact.quadratic_phase_error_deg));
+            }
+            ui.end_row();
+        });
+}
@@ -19,7 +19,7 @@ use crate::{
     download::SaveRequest,
     raster::{draw_polyline_bgrx, fill_bgrx},
     textdraw::{draw_text_bgrx, text_width},
-    ui::menu::{HELP_ICON, SAVE_ICON},
+    ui::{menu::{HELP_ICON, SAVE_ICON}, Toasts},
 };
 
 /// Ground patch resolution (pixels per side) of the rendered GAF image.
@@ -82,6 +82,14 @@ pub struct GafState {
     last_bounds: Option<([f64; 2], [f64; 2])>,
 }
 
+impl GafState {
+    /// Drops the cached texture, to be rebuilt in another egui context.
+    pub fn invalidate_texture(&mut self) {
+        self.texture = None;
+        self.cache_key = None;
+    }
+}
+
 /// Whether the per-frame GAF diagnostic is enabled (`BSARGEOM_DEBUG_GAF=1`).
 ///
 /// It logs a line only when something it watches changes, so a value that
@@ -245,13 +253,13 @@ fn gaf_contours(
 // scale and multiplied by `EXPORT_SCALE`, so the saved figure is rendered (not
 // upscaled) at print resolution: the GAF is analytic, so the patch and its
 // contours are recomputed at the export resolution.
-const EXPORT_SCALE: usize = 3;
-const EXPORT_PATCH_PX: usize = GAF_RENDER_SIZE * EXPORT_SCALE;
-const EXPORT_MARGIN_LEFT: usize = 108 * EXPORT_SCALE;
-const EXPORT_MARGIN_RIGHT: usize = 34 * EXPORT_SCALE;
-const EXPORT_MARGIN_TOP: usize = 46 * EXPORT_SCALE;
-const EXPORT_MARGIN_BOTTOM: usize = 84 * EXPORT_SCALE;
-const EXPORT_PAPER_RGB: (u8, u8, u8) = (255, 255, 255);
+pub(super) const EXPORT_SCALE: usize = 3;
+pub(super) const EXPORT_PATCH_PX: usize = GAF_RENDER_SIZE * EXPORT_SCALE;
+pub(super) const EXPORT_MARGIN_LEFT: usize = 108 * EXPORT_SCALE;
+pub(super) const EXPORT_MARGIN_RIGHT: usize = 34 * EXPORT_SCALE;
+pub(super) const EXPORT_MARGIN_TOP: usize = 46 * EXPORT_SCALE;
+pub(super) const EXPORT_MARGIN_BOTTOM: usize = 84 * EXPORT_SCALE;
+pub(super) const EXPORT_PAPER_RGB: (u8, u8, u8) = (255, 255, 255);
 const EXPORT_INK_RGB: (u8, u8, u8) = (20, 20, 20);
 const EXPORT_TITLE_PX: f32 = 21.0 * EXPORT_SCALE as f32;
 const EXPORT_LABEL_PX: f32 = 17.0 * EXPORT_SCALE as f32;
@@ -263,7 +271,7 @@ const EXPORT_HAIRLINE_PX: f32 = 1.5 * EXPORT_SCALE as f32;
 const EXPORT_DPI: f64 = 300.0;
 
 /// A "nice" tick step (1, 2 or 5 times a power of ten) close to `rough`.
-fn nice_step(rough: f64) -> f64 {
+pub(super) fn nice_step(rough: f64) -> f64 {
     // Also rejects NaN, which would poison the log10 below
     if rough <= 0.0 || !rough.is_finite() {
         return 1.0;
@@ -366,6 +374,26 @@ fn gaf_png_bytes(key: &GafKey) -> Option<Vec<u8>> {
         }
     }
 
+    draw_figure_axes_bgrx(&mut bgrx, width, height, patch, key.half_extent_m, "Generalized Ambiguity Function [dB]");
+    let entries: Vec<(String, (u8, u8, u8))> = contours
+        .iter()
+        .map(|(level, color, _)| (format!("{level:.0} dB"), (color.r(), color.g(), color.b())))
+        .collect();
+    draw_figure_legend_bgrx(&mut bgrx, width, height, patch, &entries);
+    figure_png_bytes(&bgrx, width, height)
+}
+
+/// Draws the frame of the square plot area (`patch` pixels, at the export
+/// margins) of a figure, its metric ticks over `[-half_extent_m, +half_extent_m]`
+/// on both axes, the Easting/Northing axis titles and the figure `title`.
+pub(super) fn draw_figure_axes_bgrx(
+    bgrx: &mut [u8],
+    width: usize,
+    height: usize,
+    patch: usize,
+    half_extent_m: f64,
+    title: &str,
+) {
     // Axis frame
     let (left, top) = (EXPORT_MARGIN_LEFT as f32, EXPORT_MARGIN_TOP as f32);
     let (right, bottom) = (left + patch as f32 - 1.0, top + patch as f32 - 1.0);
@@ -376,17 +404,18 @@ fn gaf_png_bytes(key: &GafKey) -> Option<Vec<u8>> {
         (left, bottom),
         (left, top),
     ];
-    draw_polyline_bgrx(&mut bgrx, width, height, &frame, EXPORT_HAIRLINE_PX, EXPORT_INK_RGB, None);
+    draw_polyline_bgrx(bgrx, width, height, &frame, EXPORT_HAIRLINE_PX, EXPORT_INK_RGB, None);
 
     // Ticks and their values
-    let (ticks, decimals) = axis_ticks(key.half_extent_m);
-    let to_pixels = |value: f64| ((value + key.half_extent_m) / step) as f32;
+    let step = 2.0 * half_extent_m / (patch - 1) as f64;
+    let (ticks, decimals) = axis_ticks(half_extent_m);
+    let to_pixels = |value: f64| ((value + half_extent_m) / step) as f32;
     for tick in ticks {
         let text = format!("{tick:.decimals$}");
         // X axis (bottom)
         let x = left + to_pixels(tick);
         draw_polyline_bgrx(
-            &mut bgrx,
+            bgrx,
             width,
             height,
             &[(x, bottom), (x, bottom + EXPORT_TICK_LEN)],
@@ -395,7 +424,7 @@ fn gaf_png_bytes(key: &GafKey) -> Option<Vec<u8>> {
             None,
         );
         draw_text_bgrx(
-            &mut bgrx,
+            bgrx,
             width,
             height,
             (x, bottom + EXPORT_TICK_LEN + 12.0 * EXPORT_SCALE as f32),
@@ -409,7 +438,7 @@ fn gaf_png_bytes(key: &GafKey) -> Option<Vec<u8>> {
         // Y axis (left); patch rows grow southwards, so mirror the value
         let y = top + to_pixels(-tick);
         draw_polyline_bgrx(
-            &mut bgrx,
+            bgrx,
             width,
             height,
             &[(left - EXPORT_TICK_LEN, y), (left, y)],
@@ -418,7 +447,7 @@ fn gaf_png_bytes(key: &GafKey) -> Option<Vec<u8>> {
             None,
         );
         draw_text_bgrx(
-            &mut bgrx,
+            bgrx,
             width,
             height,
             (
@@ -437,7 +466,7 @@ fn gaf_png_bytes(key: &GafKey) -> Option<Vec<u8>> {
 
     // Axis titles and figure title
     draw_text_bgrx(
-        &mut bgrx,
+        bgrx,
         width,
         height,
         (0.5 * (left + right), height as f32 - 22.0 * EXPORT_SCALE as f32),
@@ -449,7 +478,7 @@ fn gaf_png_bytes(key: &GafKey) -> Option<Vec<u8>> {
         "Easting [m]",
     );
     draw_text_bgrx(
-        &mut bgrx,
+        bgrx,
         width,
         height,
         (24.0 * EXPORT_SCALE as f32, 0.5 * (top + bottom)),
@@ -461,7 +490,7 @@ fn gaf_png_bytes(key: &GafKey) -> Option<Vec<u8>> {
         "Northing [m]",
     );
     draw_text_bgrx(
-        &mut bgrx,
+        bgrx,
         width,
         height,
         (0.5 * (left + right), 0.5 * top),
@@ -470,17 +499,25 @@ fn gaf_png_bytes(key: &GafKey) -> Option<Vec<u8>> {
         EXPORT_INK_RGB,
         None,
         0.0,
-        "Generalized Ambiguity Function [dB]",
+        title,
     );
+}
 
+/// Draws a legend of colored line swatches in the top right corner of the plot
+/// area of a figure, on its own opaque background.
+pub(super) fn draw_figure_legend_bgrx(
+    bgrx: &mut [u8],
+    width: usize,
+    height: usize,
+    patch: usize,
+    entries: &[(String, (u8, u8, u8))],
+) {
     // Legend, inside the plot area so it needs its own opaque background
-    let entries: Vec<String> = contours
-        .iter()
-        .map(|(level, _, _)| format!("{level:.0} dB"))
-        .collect();
+    let (left, top) = (EXPORT_MARGIN_LEFT as f32, EXPORT_MARGIN_TOP as f32);
+    let right = left + patch as f32 - 1.0;
     let text_widest = entries
         .iter()
-        .fold(0.0f32, |widest, text| widest.max(text_width(text, EXPORT_TICK_PX)));
+        .fold(0.0f32, |widest, (text, _)| widest.max(text_width(text, EXPORT_TICK_PX)));
     let swatch = 26.0 * EXPORT_SCALE as f32;
     let padding = 8.0 * EXPORT_SCALE as f32;
     let row_height = EXPORT_TICK_PX + 7.0 * EXPORT_SCALE as f32;
@@ -507,11 +544,11 @@ fn gaf_png_bytes(key: &GafKey) -> Option<Vec<u8>> {
         (box_left, box_top + box_height),
         (box_left, box_top),
     ];
-    draw_polyline_bgrx(&mut bgrx, width, height, &legend_frame, EXPORT_SCALE as f32, EXPORT_INK_RGB, None);
-    for (row, ((_, color, _), text)) in contours.iter().zip(&entries).enumerate() {
+    draw_polyline_bgrx(bgrx, width, height, &legend_frame, EXPORT_SCALE as f32, EXPORT_INK_RGB, None);
+    for (row, (text, color)) in entries.iter().enumerate() {
         let y = box_top + padding + row_height * (row as f32 + 0.5);
         draw_polyline_bgrx(
-            &mut bgrx,
+            bgrx,
             width,
             height,
             &[
@@ -519,11 +556,11 @@ fn gaf_png_bytes(key: &GafKey) -> Option<Vec<u8>> {
                 (box_left + padding + swatch, y),
             ],
             2.5 * EXPORT_SCALE as f32,
-            (color.r(), color.g(), color.b()),
+            *color,
             None,
         );
         draw_text_bgrx(
-            &mut bgrx,
+            bgrx,
             width,
             height,
             (
@@ -538,7 +575,10 @@ fn gaf_png_bytes(key: &GafKey) -> Option<Vec<u8>> {
             text,
         );
     }
+}
 
+/// Encodes a BGRX figure buffer to PNG.
+pub(super) fn figure_png_bytes(bgrx: &[u8], width: usize, height: usize) -> Option<Vec<u8>> {
     let mut rgb = vec![0u8; width * height * 3];
     for (i, pixel) in bgrx.chunks_exact(4).enumerate() {
         rgb[i * 3] = pixel[2]; // R
@@ -581,22 +621,19 @@ fn zoom_on_plain_scroll(plot_ui: &mut egui_plot::PlotUi<'_>) {
 /// Shows the GAF window while `open` is true, (re)building the cached texture
 /// only when the geometry changed. `open` is the menu's toggle flag and is set
 /// to `false` when the window's close button is clicked.
-/// `bandwidth_hz`/`center_frequency_hz` are the Tx system parameters.
+/// `bandwidth_hz`/`center_frequency_hz` are the Tx system parameters, and
+/// the save outcomes are also reported to `toasts`.
 pub fn show_gaf_window(
     ctx: &egui::Context,
     open: &mut bool,
     gaf_state: &mut GafState,
+    toasts: &mut Toasts,
     bsar_infos: &BsarInfos,
     bandwidth_hz: f64,
     center_frequency_hz: f64,
 ) {
-    // Drive an in-flight save first: on native its dialog is a window of its
-    // own, so it must keep running even if the GAF window was closed meanwhile.
-    if let Some(request) = &mut gaf_state.save_request
-        && let Some(status) = request.update(ctx)
-    {
+    if let Some(status) = SaveRequest::drive(&mut gaf_state.save_request, ctx, toasts) {
         gaf_state.save_status = Some(status);
-        gaf_state.save_request = None;
     }
 
     if !*open {
@@ -918,7 +955,7 @@ mod tests {
             &(-position), &velocity, &(-position), &velocity,
             &AntennaBeamFootprintState::default(),
             &AntennaBeamFootprintState::default(),
-            9.65e9, 300.0e6, 1.0, true, true,
+            9.65e9, 300.0e6, 1.0, Some(1.0), true,
         );
         assert!(
             gaf_key(&infos, 300.0e6, 9.65e9).is_some(),
@@ -937,7 +974,7 @@ mod tests {
                 ..Default::default()
             };
             let output = ctx.run_ui(input, |ui| {
-                show_gaf_window(ui.ctx(), &mut open, &mut gaf_state, &infos, 300.0e6, 9.65e9);
+                show_gaf_window(ui.ctx(), &mut open, &mut gaf_state, &mut Toasts::default(), &infos, 300.0e6, 9.65e9);
             });
             format!("{:?}", output.shapes)
         };
@@ -1073,7 +1110,7 @@ mod tests {
                 ..Default::default()
             };
             let _ = ctx.run_ui(input, |ui| {
-                show_gaf_window(ui.ctx(), &mut open, &mut gaf_state, &infos, 800.0e6, 10.0e9);
+                show_gaf_window(ui.ctx(), &mut open, &mut gaf_state, &mut Toasts::default(), &infos, 800.0e6, 10.0e9);
             });
             if frame >= 8 {
                 seen.push(format!("{:?}", gaf_state.last_bounds));
@@ -1115,7 +1152,7 @@ mod tests {
                 ..Default::default()
             };
             let _ = ctx.run_ui(input, |ui| {
-                show_gaf_window(ui.ctx(), &mut open, gaf_state, infos, 800.0e6, 10.0e9);
+                show_gaf_window(ui.ctx(), &mut open, gaf_state, &mut Toasts::default(), infos, 800.0e6, 10.0e9);
             });
         }
         gaf_state.last_bounds
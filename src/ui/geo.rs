@@ -0,0 +1,339 @@
+use bevy::{math::DVec3, prelude::*};
+use bevy_egui::{egui, EguiContexts, EguiPrimaryContextPass};
+
+use crate::{
+    constants::TO_Y_UP_F64,
+    coordinates::{Ellipsoid, GeographicPoint, LocalCartesian, MgrsPoint},
+    entities::AntennaBeamFootprintState,
+    expression,
+    scene::{RxAntennaBeamFootprintState, RxCarrierState, TxAntennaBeamFootprintState, TxCarrierState},
+    ui::{MenuWidget, UpdateStage},
+};
+
+const TEXT_COLOR: egui::Color32 = egui::Color32::from_rgb(200, 200, 200);
+const ERROR_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 120, 120);
+
+/// Footprint edge point labels, in [`AntennaBeamFootprintState::edge_points`] order.
+const EDGE_POINT_LABELS: [&str; 4] = ["Azimuth edge (+)", "Elevation edge (+)", "Azimuth edge (-)", "Elevation edge (-)"];
+
+pub struct GeoReferencePlugin;
+
+impl Plugin for GeoReferencePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<GeoReferenceWidget>()
+            .add_systems(PostUpdate, update_earth_curvature.in_set(UpdateStage::Input))
+            .add_systems(EguiPrimaryContextPass, geo_reference_ui_system);
+    }
+}
+
+/// Geographic referencing of the scene.
+///
+/// The scene World frame (ENU, Z-up) is the local tangent plane of
+/// `scene_origin` on its selected Ellipsoid, which lets scene points be reported
+/// as geographic coordinates / MGRS grid references and the origin be entered as
+/// one. MGRS grid references are only available on the WGS84 Ellipsoid.
+#[derive(Resource)]
+pub struct GeoReferenceWidget {
+    pub scene_origin: LocalCartesian,
+    /// Measures the footprints swath and extent along the geodesics of the
+    /// Ellipsoid instead of the tangent plane
+    pub is_earth_curvature: bool,
+    /// MGRS grid reference typed by the user
+    mgrs_input: String,
+    /// Result of the last "Set origin" / "Locate" click
+    status: Option<Result<String, String>>,
+}
+
+impl Default for GeoReferenceWidget {
+    fn default() -> Self {
+        let scene_origin = LocalCartesian::default();
+        let mgrs_input = MgrsPoint::from_geographic_point(scene_origin.origin_as_geographic_point())
+            .map(|mgrs| mgrs.to_string())
+            .unwrap_or_default();
+        Self {
+            scene_origin,
+            is_earth_curvature: false,
+            mgrs_input,
+            status: None,
+        }
+    }
+}
+
+impl GeoReferenceWidget {
+    /// Gets the geographic coordinates of a World frame point given in ENU (Z-up).
+    pub fn enu_to_geographic_point(&self, point_enu: &DVec3) -> GeographicPoint {
+        self.scene_origin.transform_from_enu_point_to_geographic_point(point_enu)
+    }
+
+    /// Returns `true` when the scene is georeferenced on the WGS84 Ellipsoid,
+    /// i.e. when MGRS grid references apply.
+    pub fn is_wgs84(&self) -> bool {
+        *self.scene_origin.ellipsoid() == Ellipsoid::WGS84
+    }
+
+    /// Gets the geographic coordinates of a World frame point given in rendering (Y-up) coordinates.
+    pub fn y_up_to_geographic_point(&self, point_y_up: &DVec3) -> GeographicPoint {
+        self.enu_to_geographic_point(&(TO_Y_UP_F64.inverse() * *point_y_up))
+    }
+}
+
+/// Sets the Earth-curvature frame of the footprints, recomputing them when it
+/// changes.
+fn update_earth_curvature(
+    geo_reference_widget: Res<GeoReferenceWidget>,
+    mut tx_antenna_beam_footprint_state: ResMut<TxAntennaBeamFootprintState>,
+    mut rx_antenna_beam_footprint_state: ResMut<RxAntennaBeamFootprintState>,
+) {
+    let earth_frame = geo_reference_widget.is_earth_curvature.then(|| geo_reference_widget.scene_origin.clone());
+    if tx_antenna_beam_footprint_state.inner.earth_frame != earth_frame {
+        tx_antenna_beam_footprint_state.inner.earth_frame = earth_frame.clone();
+    }
+    if rx_antenna_beam_footprint_state.inner.earth_frame != earth_frame {
+        rx_antenna_beam_footprint_state.inner.earth_frame = earth_frame;
+    }
+}
+
+fn geo_reference_ui_system(
+    mut contexts: EguiContexts,
+    menu_widget: Res<MenuWidget>,
+    mut geo_reference_widget: ResMut<GeoReferenceWidget>,
+    tx_carrier_state: Res<TxCarrierState>,
+    tx_antenna_beam_footprint_state: Res<TxAntennaBeamFootprintState>,
+    rx_carrier_state: Res<RxCarrierState>,
+    rx_antenna_beam_footprint_state: Res<RxAntennaBeamFootprintState>,
+) -> Result {
+    let ctx = contexts.ctx_mut()?;
+
+    let geo_infos_window = egui::Window::new("Geo Infos")
+        .resizable(false)
+        .constrain(false)
+        .collapsible(true)
+        .title_bar(true)
+        .max_width(420.0)
+        .enabled(true)
+        .default_open(false)
+        .anchor(
+            egui::Align2::LEFT_BOTTOM,
+            if menu_widget.is_tx_panel_opened {
+                egui::Vec2::new(348.0, 0.0)
+            } else {
+                egui::Vec2::new(48.0, 0.0)
+            }
+        );
+    geo_infos_window.show(ctx, |ui| {
+        scene_origin_ui(ui, &mut geo_reference_widget);
+        ui.separator();
+        geo_points_ui(
+            ui,
+            &geo_reference_widget,
+            "tx",
+            "TRANSMITTER",
+            &tx_carrier_state.inner.position_m,
+            &tx_antenna_beam_footprint_state.inner,
+        );
+        ui.separator();
+        geo_points_ui(
+            ui,
+            &geo_reference_widget,
+            "rx",
+            "RECEIVER",
+            &rx_carrier_state.inner.position_m,
+            &rx_antenna_beam_footprint_state.inner,
+        );
+    });
+
+    Ok(())
+}
+
+/// Scene origin settings: geographic coordinates and MGRS grid reference entry.
+fn scene_origin_ui(ui: &mut egui::Ui, widget: &mut GeoReferenceWidget) {
+    ui.vertical_centered(|ui| ui.label(egui::RichText::new("SCENE ORIGIN").strong()));
+    ui.separator();
+
+    let origin = widget.scene_origin.origin_as_geographic_point().clone();
+    let (mut lon_deg, mut lat_deg, mut height_m) = (origin.lon_deg(), origin.lat_deg(), origin.height_m());
+    egui::Grid::new("scene_origin_grid")
+        .num_columns(2)
+        .striped(false)
+        .spacing([20.0, 5.0])
+        .show(ui, |ui| {
+            let hover_text = egui::RichText::new("Sets the reference Ellipsoid of the scene (Earth, Mars or Moon)")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Ellipsoid: ").on_hover_text(hover_text.clone());
+            let mut ellipsoid = *widget.scene_origin.ellipsoid();
+            let ellipsoid_name = Ellipsoid::PRESETS.iter()
+                .find(|(_, preset)| *preset == ellipsoid)
+                .map_or("Custom", |(name, _)| *name);
+            egui::ComboBox::from_id_salt("scene_ellipsoid_combo")
+                .selected_text(ellipsoid_name)
+                .show_ui(ui, |ui| {
+                    for (name, preset) in Ellipsoid::PRESETS.iter() {
+                        ui.selectable_value(&mut ellipsoid, *preset, *name);
+                    }
+                })
+                .response
+                .on_hover_text(hover_text);
+            if ellipsoid != *widget.scene_origin.ellipsoid() {
+                widget.scene_origin.set_ellipsoid(&ellipsoid);
+                widget.status = None;
+            }
+            ui.end_row();
+
+            let hover_text = egui::RichText::new("Measures the footprints ground range swath and maximum extent along\nthe geodesics of the Ellipsoid instead of the tangent plane (spaceborne)")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Earth curvature: ").on_hover_text(hover_text.clone());
+            ui.checkbox(&mut widget.is_earth_curvature, "").on_hover_text(hover_text);
+            ui.end_row();
+
+            let hover_text = egui::RichText::new("Sets the scene origin longitude (-180 - 180°)")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Longitude: ").on_hover_text(hover_text.clone());
+            ui.add(
+                egui::DragValue::new(&mut lon_deg)
+                    .custom_parser(expression::parse)
+                    .update_while_editing(false)
+                    .speed(0.001)
+                    .range(-180.0..=180.0)
+                    .fixed_decimals(6)
+                    .suffix("°")
+            ).on_hover_text(hover_text);
+            ui.end_row();
+
+            let hover_text = egui::RichText::new("Sets the scene origin geodetic latitude (-90 - 90°)")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Latitude: ").on_hover_text(hover_text.clone());
+            ui.add(
+                egui::DragValue::new(&mut lat_deg)
+                    .custom_parser(expression::parse)
+                    .update_while_editing(false)
+                    .speed(0.001)
+                    .range(-90.0..=90.0)
+                    .fixed_decimals(6)
+                    .suffix("°")
+            ).on_hover_text(hover_text);
+            ui.end_row();
+
+            let hover_text = egui::RichText::new("Sets the scene origin height above the reference Ellipsoid")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Height: ").on_hover_text(hover_text.clone());
+            ui.add(
+                egui::DragValue::new(&mut height_m)
+                    .custom_parser(expression::parse)
+                    .update_while_editing(false)
+                    .speed(1.0)
+                    .fixed_decimals(3)
+                    .suffix(" m")
+            ).on_hover_text(hover_text);
+            ui.end_row();
+
+            let hover_text = egui::RichText::new(
+                    "MGRS grid reference, e.g. \"31U DQ 48251 11932\" (1 - 5 digits per coordinate).\n\
+                     Set origin => moves the scene origin to the grid reference\n\
+                     Locate     => gives the grid reference position in the scene (ENU)\n\
+                     note: only available on the WGS84 Ellipsoid"
+                )
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("MGRS: ").on_hover_text(hover_text.clone());
+            let is_wgs84 = widget.is_wgs84();
+            ui.add_enabled_ui(is_wgs84, |ui| ui.horizontal(|ui| {
+                ui.add(egui::TextEdit::singleline(&mut widget.mgrs_input).desired_width(150.0))
+                    .on_hover_text(hover_text);
+                let set_origin = ui.button("Set origin").clicked();
+                let locate = ui.button("Locate").clicked();
+                if set_origin || locate {
+                    widget.status = Some(
+                        widget.mgrs_input.parse::<MgrsPoint>()
+                            .and_then(|mgrs| mgrs.to_geographic_point())
+                            .map(|gp| {
+                                if set_origin {
+                                    let gp = GeographicPoint::from_degrees(gp.lon_deg(), gp.lat_deg(), height_m);
+                                    widget.scene_origin.set_origin_from_geographic_point(&gp);
+                                    format!("Scene origin set to {}, {}", lat_as_string(&gp), lon_as_string(&gp))
+                                } else {
+                                    let p = widget.scene_origin.transform_from_geographic_point_to_enu_point(&gp);
+                                    format!("ENU position: ({:.1} m, {:.1} m, {:.1} m)", p.x, p.y, p.z)
+                                }
+                            })
+                    );
+                }
+            }));
+            ui.end_row();
+        });
+    if let Some(status) = &widget.status {
+        match status {
+            Ok(message) => ui.label(egui::RichText::new(message).color(TEXT_COLOR)),
+            Err(message) => ui.label(egui::RichText::new(message).color(ERROR_COLOR)),
+        };
+    }
+
+    let origin = widget.scene_origin.origin_as_geographic_point();
+    if lon_deg != origin.lon_deg() || lat_deg != origin.lat_deg() || height_m != origin.height_m() {
+        widget.scene_origin.set_origin_from_geographic_point(
+            &GeographicPoint::from_degrees(lon_deg, lat_deg, height_m)
+        );
+        widget.status = None;
+    }
+}
+
+/// Geographic coordinates and MGRS grid references of a carrier and of its
+/// antenna beam footprint edge points.
+fn geo_points_ui(
+    ui: &mut egui::Ui,
+    widget: &GeoReferenceWidget,
+    name: &str,
+    title: &str,
+    carrier_position_m: &DVec3,
+    antenna_beam_footprint_state: &AntennaBeamFootprintState,
+) {
+    ui.vertical_centered(|ui| ui.label(egui::RichText::new(title).strong()));
+    ui.separator();
+    egui::Grid::new(format!("{}_geo_points_grid", name))
+        .num_columns(3)
+        .striped(true)
+        .show(ui, |ui| {
+            let carrier_gp = widget.enu_to_geographic_point(carrier_position_m);
+            geo_point_row(ui, "Carrier:", &carrier_gp, true, widget.is_wgs84());
+            for (label, point) in EDGE_POINT_LABELS.iter()
+                .zip(antenna_beam_footprint_state.edge_points().iter())
+            {
+                let gp = widget.y_up_to_geographic_point(point);
+                geo_point_row(ui, label, &gp, false, widget.is_wgs84());
+            }
+        });
+}
+
+fn geo_point_row(ui: &mut egui::Ui, label: &str, gp: &GeographicPoint, with_height: bool, with_mgrs: bool) {
+    ui.label(label);
+    ui.label(
+        if with_height {
+            format!("{}, {}, {:.1} m", lat_as_string(gp), lon_as_string(gp), gp.height_m())
+        } else {
+            format!("{}, {}", lat_as_string(gp), lon_as_string(gp))
+        }
+    );
+    if with_mgrs {
+        match MgrsPoint::from_geographic_point(gp) {
+            Ok(mgrs) => ui.label(egui::RichText::new(mgrs.to_string()).monospace()),
+            Err(message) => ui.label(egui::RichText::new("—").color(TEXT_COLOR)).on_hover_text(message),
+        };
+    } else {
+        ui.label(egui::RichText::new("—").color(TEXT_COLOR))
+            .on_hover_text("MGRS grid references are only defined on the WGS84 Ellipsoid");
+    }
+    ui.end_row();
+}
+
+fn lat_as_string(gp: &GeographicPoint) -> String {
+    format!("{:.6}°{}", gp.lat_deg().abs(), if gp.lat_deg() >= 0.0 { "N" } else { "S" })
+}
+
+fn lon_as_string(gp: &GeographicPoint) -> String {
+    format!("{:.6}°{}", gp.lon_deg().abs(), if gp.lon_deg() >= 0.0 { "E" } else { "W" })
+}
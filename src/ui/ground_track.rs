@@ -0,0 +1,439 @@
+use std::collections::VecDeque;
+
+use bevy::{math::DVec3, prelude::*};
+use bevy_egui::{egui, EguiContexts, EguiPrimaryContextPass};
+
+use crate::{
+    constants::TO_Y_UP_F64,
+    entities::{AntennaBeamFootprintState, CarrierState, LineList, LineStrip},
+    expression,
+    scene::{RxAntennaBeamFootprintState, RxCarrierState, TxAntennaBeamFootprintState, TxCarrierState},
+    ui::UpdateStage,
+};
+
+const TEXT_COLOR: egui::Color32 = egui::Color32::from_rgb(200, 200, 200);
+/// Colors of the traces, in the order of [`GroundTrace`]: the nadir traces in
+/// the colors of the footprints, the footprint center traces brighter
+const TRACE_COLORS: [Srgba; 4] = [
+    Srgba::rgb(1.0, 1.0, 1.0),
+    Srgba::rgb(1.0, 0.85, 0.2),
+    Srgba::rgb(0.0, 0.0, 0.0),
+    Srgba::rgb(0.2, 0.8, 1.0),
+];
+/// Points kept per trace over the fade duration
+const MAX_TRACE_POINTS: usize = 1000;
+/// Colors of the past footprint outlines, as the footprints, in the order of
+/// [`FootprintHistory`]
+const HISTORY_COLORS: [Srgba; 2] = [Srgba::rgb(1.0, 1.0, 1.0), Srgba::rgb(0.0, 0.0, 0.0)];
+/// Footprint points kept in the past outlines, one every stride, plus the
+/// closing point of the footprint line
+const HISTORY_OUTLINE_STRIDE: usize = 10;
+
+pub struct GroundTrackPlugin;
+
+impl Plugin for GroundTrackPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<GroundTrackWidget>()
+            .add_systems(Startup, spawn_ground_traces)
+            // The carriers and the footprints are updated by the update systems
+            .add_systems(
+                PostUpdate,
+                update_ground_tracks.in_set(UpdateStage::RenderAssets)
+            )
+            .add_systems(EguiPrimaryContextPass, ground_track_ui_system);
+    }
+}
+
+/// A trace drawn while the timeline time changes.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroundTrace {
+    TxNadir,
+    TxFootprintCenter,
+    RxNadir,
+    RxFootprintCenter,
+}
+
+impl GroundTrace {
+    const ALL: [Self; 4] = [Self::TxNadir, Self::TxFootprintCenter, Self::RxNadir, Self::RxFootprintCenter];
+}
+
+/// Past footprint outlines of a carrier, kept while the timeline time changes.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FootprintHistory {
+    Tx,
+    Rx,
+}
+
+impl FootprintHistory {
+    const ALL: [Self; 2] = [Self::Tx, Self::Rx];
+}
+
+/// Time-stamped points of a trace, oldest first.
+#[derive(Debug, Clone, Default)]
+struct Trace {
+    /// Timeline time in seconds and point in World frame (Y-up)
+    points: VecDeque<(f64, Vec3)>,
+}
+
+impl Trace {
+    /// Appends the point at `time_s`, a new point being kept every
+    /// `fade_duration_s / MAX_TRACE_POINTS` while the last one follows the
+    /// carrier in between. Going back in time restarts the trace.
+    fn push(&mut self, time_s: f64, point: Vec3, fade_duration_s: f64) {
+        if let Some(&(last_time_s, _)) = self.points.back() {
+            let min_step_s = fade_duration_s / MAX_TRACE_POINTS as f64;
+            let len = self.points.len();
+            if time_s < last_time_s {
+                self.points.clear();
+            } else if time_s == last_time_s || (len >= 2 && last_time_s - self.points[len - 2].0 < min_step_s) {
+                self.points.pop_back();
+            }
+        }
+        self.points.push_back((time_s, point));
+        while self.points.front().is_some_and(|&(point_time_s, _)| time_s - point_time_s > fade_duration_s) {
+            self.points.pop_front();
+        }
+    }
+
+    /// Gets the trace polyline, its vertices fading out with their age.
+    fn mesh(&self, color: Srgba, fade_duration_s: f64) -> Mesh {
+        let time_s = self.points.back().map_or(0.0, |&(time_s, _)| time_s);
+        let colors: Vec<[f32; 4]> = self.points.iter()
+            .map(|&(point_time_s, _)| {
+                let alpha = (1.0 - (time_s - point_time_s) / fade_duration_s).clamp(0.0, 1.0) as f32;
+                LinearRgba::from(color.with_alpha(alpha)).to_f32_array()
+            })
+            .collect();
+        Mesh::from(LineStrip { points: self.points.iter().map(|&(_, point)| point).collect() })
+            .with_inserted_attribute(Mesh::ATTRIBUTE_COLOR, colors)
+    }
+}
+
+/// Time-stamped footprint outlines, oldest first.
+#[derive(Debug, Clone, Default)]
+struct OutlineHistory {
+    /// Timeline time in seconds and outline in World frame (Y-up)
+    outlines: VecDeque<(f64, Vec<Vec3>)>,
+}
+
+impl OutlineHistory {
+    /// Keeps the outline at `time_s` when `step_s` past the last kept one, up
+    /// to `count` outlines. Going back in time restarts the history.
+    fn push(&mut self, time_s: f64, outline: impl FnOnce() -> Vec<Vec3>, step_s: f64, count: usize) {
+        match self.outlines.back() {
+            Some(&(last_time_s, _)) if time_s < last_time_s => self.outlines.clear(),
+            Some(&(last_time_s, _)) if time_s - last_time_s < step_s => return,
+            _ => {}
+        }
+        self.outlines.push_back((time_s, outline()));
+        while self.outlines.len() > count {
+            self.outlines.pop_front();
+        }
+    }
+
+    /// Gets the outlines as line segments, fading out from the newest to the
+    /// oldest over `count` outlines.
+    fn mesh(&self, color: Srgba, count: usize) -> Mesh {
+        let mut lines = Vec::new();
+        let mut colors = Vec::new();
+        for (age, (_, outline)) in self.outlines.iter().rev().take(count).enumerate() {
+            let alpha = 1.0 - (age + 1) as f32 / (count + 1) as f32;
+            let color = LinearRgba::from(color.with_alpha(alpha)).to_f32_array();
+            lines.extend(outline.windows(2).map(|segment| (segment[0], segment[1])));
+            colors.resize(2 * lines.len(), color);
+        }
+        Mesh::from(LineList { lines })
+            .with_inserted_attribute(Mesh::ATTRIBUTE_COLOR, colors)
+    }
+}
+
+/// Nadir traces (ground tracks) of the carriers and traces of their footprint
+/// centers, drawn while the timeline time changes, and past footprint
+/// outlines showing the swath progression along the pass.
+#[derive(Resource)]
+pub struct GroundTrackWidget {
+    pub is_enabled: bool,
+    /// Timeline seconds for a trace to fade out
+    pub fade_duration_s: f64,
+    /// Traces, in the order of [`GroundTrace::ALL`]
+    traces: [Trace; 4],
+    pub is_history_enabled: bool,
+    /// Timeline seconds between two kept footprint outlines
+    pub history_step_s: f64,
+    /// Footprint outlines kept per carrier
+    pub history_count: usize,
+    /// Past footprint outlines, in the order of [`FootprintHistory::ALL`]
+    histories: [OutlineHistory; 2],
+}
+
+impl Default for GroundTrackWidget {
+    fn default() -> Self {
+        Self {
+            is_enabled: false,
+            fade_duration_s: 30.0,
+            traces: Default::default(),
+            is_history_enabled: false,
+            history_step_s: 5.0,
+            history_count: 10,
+            histories: Default::default(),
+        }
+    }
+}
+
+impl GroundTrackWidget {
+    /// Clears the traces and the past footprint outlines.
+    pub fn clear(&mut self) {
+        self.traces.iter_mut().for_each(|trace| trace.points.clear());
+        self.histories.iter_mut().for_each(|history| history.outlines.clear());
+    }
+
+    fn is_empty(&self) -> bool {
+        self.traces.iter().all(|trace| trace.points.is_empty())
+            && self.histories.iter().all(|history| history.outlines.is_empty())
+    }
+}
+
+fn spawn_ground_traces(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // Colored by the vertices, which carry the fading
+    let material = materials.add(StandardMaterial {
+        base_color: Color::WHITE,
+        alpha_mode: AlphaMode::Blend,
+        unlit: true,
+        ..default()
+    });
+    for trace in GroundTrace::ALL {
+        commands.spawn((
+            Mesh3d(meshes.add(LineStrip { points: vec![] })),
+            MeshMaterial3d(material.clone()),
+            Transform::IDENTITY,
+            Visibility::Hidden,
+            trace,
+            Name::new(format!("{trace:?} trace")),
+        ));
+    }
+    for history in FootprintHistory::ALL {
+        commands.spawn((
+            Mesh3d(meshes.add(LineList { lines: vec![] })),
+            MeshMaterial3d(material.clone()),
+            Transform::IDENTITY,
+            Visibility::Hidden,
+            history,
+            Name::new(format!("{history:?} footprint history")),
+        ));
+    }
+}
+
+/// Gets the nadir of the carrier in World frame (Y-up), lifted above the footprint.
+fn nadir(carrier_state: &CarrierState, footprint: &AntennaBeamFootprintState) -> Vec3 {
+    let position_m = carrier_state.position_m;
+    let nadir_m = TO_Y_UP_F64 * DVec3::new(position_m.x, position_m.y, 0.0);
+    Vec3::new(nadir_m.x as f32, 2.0 * footprint.lift_m as f32, nadir_m.z as f32)
+}
+
+/// Gets the footprint center, between its azimuth and elevation edges, in
+/// World frame (Y-up), lifted above the footprint.
+fn footprint_center(footprint: &AntennaBeamFootprintState) -> Vec3 {
+    let center_m = footprint.edge_points().iter().sum::<DVec3>() / 4.0;
+    Vec3::new(center_m.x as f32, 2.0 * footprint.lift_m as f32, center_m.z as f32)
+}
+
+/// Gets the footprint outline in World frame (Y-up), lifted as the footprint,
+/// decimated by [`HISTORY_OUTLINE_STRIDE`].
+fn footprint_outline(footprint: &AntennaBeamFootprintState) -> Vec<Vec3> {
+    let lift_m = footprint.lift_m as f32;
+    footprint.decimated_points(HISTORY_OUTLINE_STRIDE)
+        .map(|point| Vec3::new(point.x as f32, lift_m, point.z as f32))
+        .collect()
+}
+
+/// Extends the traces and the footprint histories with the moved carriers and
+/// redraws them.
+fn update_ground_tracks(
+    mut ground_track_widget: ResMut<GroundTrackWidget>,
+    tx: (Res<TxCarrierState>, Res<TxAntennaBeamFootprintState>),
+    rx: (Res<RxCarrierState>, Res<RxAntennaBeamFootprintState>),
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut trace_q: Query<(&GroundTrace, &Mesh3d, &mut Visibility)>,
+    mut history_q: Query<(&FootprintHistory, &Mesh3d, &mut Visibility), Without<GroundTrace>>,
+) -> Result {
+    let are_states_changed = ground_track_widget.is_changed()
+        || tx.0.is_changed() || tx.1.is_changed() || rx.0.is_changed() || rx.1.is_changed();
+    if !are_states_changed {
+        return Ok(());
+    }
+    // Written without change detection, which triggers the update
+    let widget = ground_track_widget.bypass_change_detection();
+    // Both carriers follow the timeline time
+    let time_s = tx.0.inner.time_s;
+    if widget.is_enabled {
+        let points = [
+            nadir(&tx.0.inner, &tx.1.inner),
+            footprint_center(&tx.1.inner),
+            nadir(&rx.0.inner, &rx.1.inner),
+            footprint_center(&rx.1.inner),
+        ];
+        for (trace, point) in widget.traces.iter_mut().zip(points) {
+            trace.push(time_s, point, widget.fade_duration_s);
+        }
+    } else {
+        widget.traces.iter_mut().for_each(|trace| trace.points.clear());
+    }
+    if widget.is_history_enabled {
+        let footprints = [&tx.1.inner, &rx.1.inner];
+        for (history, footprint) in widget.histories.iter_mut().zip(footprints) {
+            history.push(time_s, || footprint_outline(footprint), widget.history_step_s, widget.history_count);
+        }
+    } else {
+        widget.histories.iter_mut().for_each(|history| history.outlines.clear());
+    }
+
+    for (ground_trace, mesh, visibility) in trace_q.iter_mut() {
+        let index = *ground_trace as usize;
+        let trace = &widget.traces[index];
+        let lines = (trace.points.len() >= 2).then(|| trace.mesh(TRACE_COLORS[index], widget.fade_duration_s));
+        super::display::replace_line_mesh(&mut meshes, mesh, visibility, lines)?;
+    }
+    for (footprint_history, mesh, visibility) in history_q.iter_mut() {
+        let index = *footprint_history as usize;
+        let history = &widget.histories[index];
+        let lines = (!history.outlines.is_empty()).then(|| history.mesh(HISTORY_COLORS[index], widget.history_count));
+        super::display::replace_line_mesh(&mut meshes, mesh, visibility, lines)?;
+    }
+
+    Ok(())
+}
+
+fn ground_track_ui_system(
+    mut contexts: EguiContexts,
+    mut ground_track_widget: ResMut<GroundTrackWidget>,
+) -> Result {
+    let ctx = contexts.ctx_mut()?;
+
+    let ground_track_window = egui::Window::new("Ground Tracks")
+        .resizable(false)
+        .constrain(true)
+        .collapsible(true)
+        .title_bar(true)
+        .enabled(true)
+        .default_open(false)
+        .default_pos(egui::pos2(320.0, 840.0));
+    ground_track_window.show(ctx, |ui| {
+        ground_track_ui(ui, &mut ground_track_widget);
+    });
+
+    Ok(())
+}
+
+fn ground_track_ui(ui: &mut egui::Ui, widget: &mut GroundTrackWidget) {
+    egui::Grid::new("ground_track_grid")
+        .num_columns(2)
+        .striped(false)
+        .spacing([20.0, 5.0])
+        .show(ui, |ui| {
+            // ***** Ground tracks ***** //
+            let hover_text = egui::RichText::new("Draws the nadir trace of each carrier (Tx white, Rx black) and the trace of its\nfootprint center (Tx yellow, Rx cyan) while the timeline time changes")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.checkbox(&mut widget.is_enabled, "Ground tracks").on_hover_text(hover_text);
+            ui.add_enabled_ui(widget.is_enabled, |ui| {
+                ui.horizontal(|ui| {
+                    let hover_text = egui::RichText::new("Sets the timeline duration over which the traces fade out (1 - 100000 s)")
+                        .color(TEXT_COLOR)
+                        .monospace();
+                    ui.label("Fade: ").on_hover_text(hover_text.clone());
+                    ui.add(
+                        egui::DragValue::new(&mut widget.fade_duration_s)
+                            .custom_parser(expression::parse)
+                            .update_while_editing(false)
+                            .speed(1.0)
+                            .range(1.0..=100000.0)
+                            .fixed_decimals(1)
+                            .suffix(" s")
+                    ).on_hover_text(hover_text);
+                });
+            });
+            ui.end_row();
+
+            // ***** Footprint history ***** //
+            let hover_text = egui::RichText::new("Keeps the footprint outlines at fixed timeline steps while the time changes,\nthe older ones fading out, showing the swath progression along the pass")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.checkbox(&mut widget.is_history_enabled, "Footprint history").on_hover_text(hover_text);
+            ui.add_enabled_ui(widget.is_history_enabled, |ui| {
+                ui.horizontal(|ui| {
+                    let hover_text = egui::RichText::new("Sets the timeline step between two kept outlines (0.1 - 10000 s)")
+                        .color(TEXT_COLOR)
+                        .monospace();
+                    ui.label("Step: ").on_hover_text(hover_text.clone());
+                    ui.add(
+                        egui::DragValue::new(&mut widget.history_step_s)
+                            .custom_parser(expression::parse)
+                            .update_while_editing(false)
+                            .speed(0.1)
+                            .range(0.1..=10000.0)
+                            .fixed_decimals(1)
+                            .suffix(" s")
+                    ).on_hover_text(hover_text);
+                    let hover_text = egui::RichText::new("Sets the number of outlines kept per carrier (1 - 50)")
+                        .color(TEXT_COLOR)
+                        .monospace();
+                    ui.label("Count: ").on_hover_text(hover_text.clone());
+                    ui.add(
+                        egui::DragValue::new(&mut widget.history_count)
+                            .custom_parser(expression::parse)
+                            .update_while_editing(false)
+                            .speed(0.1)
+                            .range(1..=50)
+                    ).on_hover_text(hover_text);
+                });
+            });
+            ui.end_row();
+        });
+    let is_empty = widget.is_empty();
+    if ui.add_enabled(!is_empty, egui::Button::new("Clear")).on_hover_text("Clears the traces and the footprint outlines").clicked() {
+        widget.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn traces_fade_and_restart() {
+        let mut trace = Trace::default();
+        // 15.625 s fade: a point kept every 1/64 s
+        let fade_duration_s = 15.625;
+        for step in 0..=2000 {
+            trace.push(step as f64 / 64.0, Vec3::X * step as f32, fade_duration_s);
+        }
+        assert_eq!(trace.points.len(), MAX_TRACE_POINTS + 1);
+        assert_eq!(trace.points.front().unwrap().0, 15.625);
+        // Within a step, the last point follows the carrier
+        trace.push(31.251, Vec3::Y, fade_duration_s);
+        trace.push(31.252, Vec3::Z, fade_duration_s);
+        assert_eq!(trace.points.len(), MAX_TRACE_POINTS + 1);
+        assert_eq!(trace.points.back().unwrap(), &(31.252, Vec3::Z));
+        // Going back in time
+        trace.push(5.0, Vec3::Z, fade_duration_s);
+        assert_eq!(trace.points.len(), 1);
+    }
+
+    #[test]
+    fn footprint_history_keeps_steps() {
+        let mut history = OutlineHistory::default();
+        for step in 0..=100 {
+            history.push(step as f64 * 0.5, || vec![Vec3::ZERO, Vec3::X], 2.0, 3);
+        }
+        // An outline every 2 s, the 3 newest kept
+        let times: Vec<f64> = history.outlines.iter().map(|&(time_s, _)| time_s).collect();
+        assert_eq!(times, [46.0, 48.0, 50.0]);
+        history.push(10.0, || vec![Vec3::ZERO, Vec3::Y], 2.0, 3);
+        assert_eq!(history.outlines.len(), 1);
+    }
+}
@@ -0,0 +1,189 @@
+use bevy::{math::DVec3, prelude::*};
+use bevy_egui::{egui, EguiContexts, EguiPrimaryContextPass};
+
+use crate::{
+    entities::{AntennaBeamFootprintState, CarrierState},
+    horizon::HorizonMask,
+    scene::{RxAntennaBeamFootprintState, RxCarrierState, TxAntennaBeamFootprintState, TxCarrierState},
+    ui::TerrainWidget,
+};
+
+const TEXT_COLOR: egui::Color32 = egui::Color32::from_rgb(200, 200, 200);
+const ERROR_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 120, 120);
+/// Azimuth step in degrees of the masks derived from the terrain
+const TERRAIN_MASK_AZIMUTH_STEP_DEG: f64 = 2.0;
+
+pub struct HorizonPlugin;
+
+impl Plugin for HorizonPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<HorizonWidget>()
+            .add_systems(EguiPrimaryContextPass, horizon_ui_system);
+    }
+}
+
+/// Where a carrier horizon mask comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HorizonSource {
+    /// User (azimuth, elevation) table
+    Table,
+    /// Derived from the DEM loaded in the "Terrain" window, at the carrier position
+    Terrain,
+}
+
+/// Optional horizon mask of one carrier.
+pub struct CarrierHorizon {
+    pub is_enabled: bool,
+    pub source: HorizonSource,
+    pub table_text: String,
+    pub mask: Option<HorizonMask>,
+    /// Result of the last mask update
+    status: Option<Result<String, String>>,
+}
+
+impl Default for CarrierHorizon {
+    fn default() -> Self {
+        Self {
+            is_enabled: false,
+            source: HorizonSource::Table,
+            table_text: "# azimuth [°] elevation [°]\n0 0\n".to_string(),
+            mask: None,
+            status: None,
+        }
+    }
+}
+
+/// Horizon masks of the transmitter and the receiver, warning when their beam
+/// is blocked below the mask.
+#[derive(Resource, Default)]
+pub struct HorizonWidget {
+    pub tx: CarrierHorizon,
+    pub rx: CarrierHorizon,
+}
+
+fn horizon_ui_system(
+    mut contexts: EguiContexts,
+    mut horizon_widget: ResMut<HorizonWidget>,
+    terrain_widget: Res<TerrainWidget>,
+    tx: (Res<TxCarrierState>, Res<TxAntennaBeamFootprintState>),
+    rx: (Res<RxCarrierState>, Res<RxAntennaBeamFootprintState>),
+) -> Result {
+    let ctx = contexts.ctx_mut()?;
+
+    let horizon_window = egui::Window::new("Horizon Masks")
+        .resizable(false)
+        .constrain(true)
+        .collapsible(true)
+        .title_bar(true)
+        .enabled(true)
+        .default_open(false)
+        .default_pos(egui::pos2(320.0, 280.0));
+    horizon_window.show(ctx, |ui| {
+        let widget = &mut *horizon_widget;
+        carrier_horizon_ui(ui, "tx", "TRANSMITTER", &mut widget.tx, &terrain_widget, &tx.0.inner, &tx.1.inner);
+        carrier_horizon_ui(ui, "rx", "RECEIVER", &mut widget.rx, &terrain_widget, &rx.0.inner, &rx.1.inner);
+    });
+
+    Ok(())
+}
+
+fn carrier_horizon_ui(
+    ui: &mut egui::Ui,
+    id: &str,
+    title: &str,
+    horizon: &mut CarrierHorizon,
+    terrain_widget: &TerrainWidget,
+    carrier_state: &CarrierState,
+    antenna_beam_footprint_state: &AntennaBeamFootprintState,
+) {
+    ui.separator();
+    ui.vertical_centered(|ui| ui.label(egui::RichText::new(title).strong()));
+    ui.separator();
+
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut horizon.is_enabled, "Horizon mask");
+        ui.add_enabled_ui(horizon.is_enabled, |ui| {
+            ui.radio_value(&mut horizon.source, HorizonSource::Table, "Table");
+            ui.radio_value(&mut horizon.source, HorizonSource::Terrain, "Terrain");
+        });
+    });
+    if !horizon.is_enabled {
+        return;
+    }
+    match horizon.source {
+        HorizonSource::Table => {
+            ui.add(
+                egui::TextEdit::multiline(&mut horizon.table_text)
+                    .id_salt(format!("{id}_horizon_table"))
+                    .font(egui::TextStyle::Monospace)
+                    .desired_rows(4)
+                    .desired_width(280.0)
+            );
+            let hover_text = egui::RichText::new("Reads the table: one \"azimuth elevation\" pair in degrees per line,\nazimuths clockwise from North, linearly interpolated")
+                .color(TEXT_COLOR)
+                .monospace();
+            if ui.button("Apply").on_hover_text(hover_text).clicked() {
+                horizon.status = Some(HorizonMask::from_table(&horizon.table_text)
+                    .map(|mask| {
+                        let status = format!("{} mask points", mask.points().len());
+                        horizon.mask = Some(mask);
+                        status
+                    })
+                );
+            }
+        }
+        HorizonSource::Terrain => {
+            let hover_text = egui::RichText::new("Derives the mask from the terrain heights of the \"Terrain\" window,\nseen from the current carrier position")
+                .color(TEXT_COLOR)
+                .monospace();
+            let masks = terrain_widget.masks.as_ref();
+            if ui
+                .add_enabled(masks.is_some(), egui::Button::new("From terrain"))
+                .on_hover_text(hover_text)
+                .clicked()
+                && let Some(masks) = masks
+            {
+                horizon.mask = Some(masks.horizon_mask(&carrier_state.position_m, TERRAIN_MASK_AZIMUTH_STEP_DEG));
+                horizon.status = Some(Ok("Mask derived from the terrain".to_string()));
+            }
+        }
+    }
+    match &horizon.status {
+        Some(Ok(status)) => { ui.label(egui::RichText::new(status).color(TEXT_COLOR)); }
+        Some(Err(error)) => { ui.label(egui::RichText::new(error).color(ERROR_COLOR)); }
+        None => {}
+    }
+
+    let Some(mask) = &horizon.mask else {
+        return;
+    };
+    // Footprint points are in World frame (Y-up): back to ENU (Z-up)
+    let footprint: Vec<DVec3> = antenna_beam_footprint_state.points.iter()
+        .map(|point| DVec3::new(point.z, point.x, point.y))
+        .collect();
+    let blocked_fraction = mask.blocked_fraction(&carrier_state.position_m, &footprint);
+    if mask.is_blocked(&-carrier_state.position_m) {
+        ui.label(egui::RichText::new("⚠ The boresight is blocked below the mask").color(ERROR_COLOR));
+    }
+    let text = format!("{:.0} % of the footprint edge below the mask", 100.0 * blocked_fraction);
+    ui.label(egui::RichText::new(text).color(if blocked_fraction > 0.0 { ERROR_COLOR } else { TEXT_COLOR }));
+
+    let line: Vec<[f64; 2]> = (0..=360)
+        .map(|azimuth_deg| [azimuth_deg as f64, mask.elevation_deg_at(azimuth_deg as f64)])
+        .collect();
+    egui_plot::Plot::new(format!("{id}_horizon_plot"))
+        .width(280.0)
+        .height(120.0)
+        .include_x(0.0)
+        .include_x(360.0)
+        .include_y(0.0)
+        .x_axis_label("Azimuth [°]")
+        .y_axis_label("Elevation [°]")
+        .allow_drag(false)
+        .allow_zoom(false)
+        .allow_scroll(false)
+        .show(ui, |plot_ui| {
+            plot_ui.line(egui_plot::Line::new("Mask", line).width(1.5));
+        });
+}
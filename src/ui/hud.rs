@@ -0,0 +1,218 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts, EguiPrimaryContextPass};
+
+use crate::{
+    entities::{AntennaBeamFootprintState, CarrierState},
+    scene::{BsarInfosState, RxAntennaBeamFootprintState, RxCarrierState, TxAntennaBeamFootprintState, TxCarrierState},
+    ui::BsarMetric,
+};
+
+const TEXT_COLOR: egui::Color32 = egui::Color32::from_rgb(200, 200, 200);
+
+pub struct HudPlugin;
+
+impl Plugin for HudPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<HudWidget>()
+            .add_systems(EguiPrimaryContextPass, hud_ui_system);
+    }
+}
+
+/// A carrier metric of the "Tx Infos"/"Rx Infos" windows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CarrierMetric {
+    Position,
+    VelocityVector,
+    SlantRangeMin,
+    SlantRangeCenter,
+    SlantRangeMax,
+    LocalIncidenceMin,
+    LocalIncidenceCenter,
+    LocalIncidenceMax,
+    AntennaSquint,
+    GroundRangeSwath,
+    FootprintArea,
+    IlluminationTime,
+    GroundAngularVelocity,
+}
+
+impl CarrierMetric {
+    /// Label of the metric, as in the "Tx Infos"/"Rx Infos" windows.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Position => "Carrier position",
+            Self::VelocityVector => "Carrier velocity vector",
+            Self::SlantRangeMin => "Slant range min",
+            Self::SlantRangeCenter => "Slant range center",
+            Self::SlantRangeMax => "Slant range max",
+            Self::LocalIncidenceMin => "Local incidence min",
+            Self::LocalIncidenceCenter => "Local incidence center",
+            Self::LocalIncidenceMax => "Local incidence max",
+            Self::AntennaSquint => "Antenna squint",
+            Self::GroundRangeSwath => "Ground range swath",
+            Self::FootprintArea => "Footprint area",
+            Self::IlluminationTime => "Illumination time",
+            Self::GroundAngularVelocity => "Ground angular velocity",
+        }
+    }
+
+    /// Formatted value of the metric, with the units of the "Tx Infos"/"Rx Infos" windows.
+    pub fn value_text(self, carrier_state: &CarrierState, footprint: &AntennaBeamFootprintState) -> String {
+        let meters = |value_m: f64| if value_m >= 1e3 {
+            format!("{:.3} km", value_m * 1e-3)
+        } else {
+            format!("{:.3} m", value_m)
+        };
+        match self {
+            Self::Position => {
+                let position = carrier_state.position_m;
+                format!("({:.1} m, {:.1} m, {:.1} m)", position.x, position.y, position.z)
+            }
+            Self::VelocityVector => {
+                let velocity = carrier_state.velocity_vector_mps;
+                format!("({:.1} m/s, {:.1} m/s, {:.1} m/s)", velocity.x, velocity.y, velocity.z)
+            }
+            Self::SlantRangeMin => meters(footprint.range_min_m),
+            Self::SlantRangeCenter => meters(footprint.range_center_m),
+            Self::SlantRangeMax => meters(footprint.range_max_m),
+            Self::LocalIncidenceMin => format!("{:.3}°", footprint.loc_incidence_min_deg),
+            Self::LocalIncidenceCenter => format!("{:.3}°", footprint.loc_incidence_center_deg),
+            Self::LocalIncidenceMax => format!("{:.3}°", footprint.loc_incidence_max_deg),
+            Self::AntennaSquint => format!("{:.3}°", footprint.antenna_squint_deg),
+            Self::GroundRangeSwath => meters(footprint.ground_range_swath_m),
+            Self::FootprintArea => if footprint.area_m2 >= 1e5 {
+                format!("{:.3} km²", footprint.area_m2 * 1e-6)
+            } else {
+                format!("{:.3} m²", footprint.area_m2)
+            },
+            Self::IlluminationTime => if footprint.illumination_time_s >= 60.0 {
+                format!("{:.3} min", footprint.illumination_time_s / 60.0)
+            } else {
+                format!("{:.3} s", footprint.illumination_time_s)
+            },
+            Self::GroundAngularVelocity => format!("{:.3} °/s", footprint.ground_angular_velocity_degps),
+        }
+    }
+}
+
+/// A metric pinned to the HUD.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HudMetric {
+    Tx(CarrierMetric),
+    Rx(CarrierMetric),
+    Bsar(BsarMetric),
+}
+
+impl HudMetric {
+    /// Carrier metric of the side named `name` ("tx" | "rx").
+    pub fn carrier(name: &str, metric: CarrierMetric) -> Self {
+        if name == "tx" { Self::Tx(metric) } else { Self::Rx(metric) }
+    }
+
+    pub fn label(self) -> String {
+        match self {
+            Self::Tx(metric) => format!("Tx {}", metric.label().to_lowercase()),
+            Self::Rx(metric) => format!("Rx {}", metric.label().to_lowercase()),
+            Self::Bsar(metric) => metric.label().to_owned(),
+        }
+    }
+}
+
+/// Metrics pinned from the info windows to the HUD, a floating always-on-top
+/// mini panel, in pinning order.
+#[derive(Resource, Default)]
+pub struct HudWidget {
+    pub pinned: Vec<HudMetric>,
+}
+
+impl HudWidget {
+    /// Pins `metric`, or unpins it when already pinned.
+    pub fn toggle(&mut self, metric: HudMetric) {
+        if let Some(index) = self.pinned.iter().position(|&pinned| pinned == metric) {
+            self.pinned.remove(index);
+        } else {
+            self.pinned.push(metric);
+        }
+    }
+}
+
+/// Right-click menu of an info row label pinning its metric to the HUD.
+pub fn pin_menu(response: &egui::Response, hud_widget: &mut HudWidget, metric: HudMetric) {
+    response.context_menu(|ui| {
+        let text = if hud_widget.pinned.contains(&metric) { "Unpin from HUD" } else { "📌 Pin to HUD" };
+        if ui.button(text).clicked() {
+            hud_widget.toggle(metric);
+        }
+    });
+}
+
+fn hud_ui_system(
+    mut contexts: EguiContexts,
+    mut hud_widget: ResMut<HudWidget>,
+    tx: (Res<TxCarrierState>, Res<TxAntennaBeamFootprintState>),
+    rx: (Res<RxCarrierState>, Res<RxAntennaBeamFootprintState>),
+    bsar_infos_state: Res<BsarInfosState>,
+) -> Result {
+    if hud_widget.pinned.is_empty() {
+        return Ok(());
+    }
+    let ctx = contexts.ctx_mut()?;
+
+    let hud_window = egui::Window::new("HUD")
+        .resizable(false)
+        .constrain(true)
+        .collapsible(true)
+        .title_bar(true)
+        .enabled(true)
+        .order(egui::Order::Foreground)
+        .default_pos(egui::pos2(320.0, 800.0));
+    hud_window.show(ctx, |ui| {
+        let mut unpinned = None;
+        egui::Grid::new("hud_grid")
+            .num_columns(2)
+            .striped(true)
+            .show(ui, |ui| {
+                for &metric in hud_widget.pinned.iter() {
+                    let value = match metric {
+                        HudMetric::Tx(metric) => metric.value_text(&tx.0.inner, &tx.1.inner),
+                        HudMetric::Rx(metric) => metric.value_text(&rx.0.inner, &rx.1.inner),
+                        HudMetric::Bsar(metric) => metric.value_text(&bsar_infos_state.inner),
+                    };
+                    let response = ui.label(format!("{}:", metric.label()))
+                        .on_hover_text(egui::RichText::new("Right-click to unpin").color(TEXT_COLOR).monospace());
+                    response.context_menu(|ui| {
+                        if ui.button("Unpin from HUD").clicked() {
+                            unpinned = Some(metric);
+                        }
+                    });
+                    ui.label(value);
+                    ui.end_row();
+                }
+            });
+        if let Some(metric) = unpinned {
+            hud_widget.toggle(metric);
+        }
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hud_pins_toggle() {
+        let mut hud_widget = HudWidget::default();
+        hud_widget.toggle(HudMetric::Bsar(BsarMetric::Nesz));
+        hud_widget.toggle(HudMetric::carrier("rx", CarrierMetric::AntennaSquint));
+        hud_widget.toggle(HudMetric::carrier("tx", CarrierMetric::AntennaSquint));
+        hud_widget.toggle(HudMetric::Bsar(BsarMetric::Nesz));
+        assert_eq!(hud_widget.pinned, [
+            HudMetric::Rx(CarrierMetric::AntennaSquint),
+            HudMetric::Tx(CarrierMetric::AntennaSquint),
+        ]);
+        assert_eq!(hud_widget.pinned[1].label(), "Tx antenna squint");
+    }
+}
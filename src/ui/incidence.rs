@@ -0,0 +1,222 @@
+use bevy::{math::DVec3, prelude::*};
+use bevy_egui::{egui, EguiContexts, EguiPrimaryContextPass};
+
+use crate::{
+    entities::{AntennaBeamFootprintState, CarrierState, LineList},
+    expression,
+    incidence::{incidence_range_deg, IncidenceWindow},
+    scene::{RxAntennaBeamFootprintState, RxCarrierState, TxAntennaBeamFootprintState, TxCarrierState},
+    ui::UpdateStage,
+};
+
+const TEXT_COLOR: egui::Color32 = egui::Color32::from_rgb(200, 200, 200);
+const ERROR_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 120, 120);
+/// Color of the footprint segments outside the incidence window
+const OUTSIDE_SEGMENT_COLOR: Srgba = Srgba::rgb(1.0, 0.0, 0.0);
+
+pub struct IncidencePlugin;
+
+impl Plugin for IncidencePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<IncidenceWidget>()
+            .add_systems(Startup, spawn_incidence_overlays)
+            // The footprints are updated by the update systems
+            .add_systems(
+                PostUpdate,
+                update_incidence_overlays.in_set(UpdateStage::RenderAssets)
+            )
+            .add_systems(EguiPrimaryContextPass, incidence_ui_system);
+    }
+}
+
+/// Optional allowed incidence window of one carrier.
+#[derive(Default)]
+pub struct CarrierIncidence {
+    pub is_enabled: bool,
+    pub window: IncidenceWindow,
+}
+
+/// Allowed incidence windows of the transmitter and the receiver, highlighting
+/// the footprint edge where the local incidence falls outside.
+#[derive(Resource, Default)]
+pub struct IncidenceWidget {
+    pub tx: CarrierIncidence,
+    pub rx: CarrierIncidence,
+}
+
+/// Footprint segments outside the incidence window, drawn over the footprints.
+#[derive(Component, Clone, Copy, PartialEq, Eq)]
+pub enum IncidenceOverlay {
+    Tx,
+    Rx,
+}
+
+fn spawn_incidence_overlays(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let material = materials.add(StandardMaterial {
+        base_color: OUTSIDE_SEGMENT_COLOR.into(),
+        unlit: true,
+        ..default()
+    });
+    let overlays = [
+        (IncidenceOverlay::Tx, "Tx incidence window violations"),
+        (IncidenceOverlay::Rx, "Rx incidence window violations"),
+    ];
+    for (overlay, name) in overlays {
+        commands.spawn((
+            Mesh3d(meshes.add(LineList { lines: vec![] })),
+            MeshMaterial3d(material.clone()),
+            Transform::IDENTITY,
+            Visibility::Hidden,
+            overlay,
+            Name::new(name),
+        ));
+    }
+}
+
+/// Gets the footprint edge (World frame, Y-up) back in ENU (Z-up).
+fn footprint_enu(antenna_beam_footprint_state: &AntennaBeamFootprintState) -> Vec<DVec3> {
+    antenna_beam_footprint_state.points.iter()
+        .map(|point| DVec3::new(point.z, point.x, point.y))
+        .collect()
+}
+
+/// Redraws the footprint segments outside the incidence windows.
+fn update_incidence_overlays(
+    incidence_widget: Res<IncidenceWidget>,
+    tx: (Res<TxCarrierState>, Res<TxAntennaBeamFootprintState>),
+    rx: (Res<RxCarrierState>, Res<RxAntennaBeamFootprintState>),
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut overlay_q: Query<(&IncidenceOverlay, &Mesh3d, &mut Visibility)>,
+    mut drawn_lines: Local<[Vec<(Vec3, Vec3)>; 2]>,
+) -> Result {
+    for (overlay, mesh, visibility) in overlay_q.iter_mut() {
+        let (index, incidence, carrier_state, antenna_beam_footprint_state) = match overlay {
+            IncidenceOverlay::Tx => (0, &incidence_widget.tx, &tx.0.inner, &tx.1.inner),
+            IncidenceOverlay::Rx => (1, &incidence_widget.rx, &rx.0.inner, &rx.1.inner),
+        };
+        let lines: Vec<(Vec3, Vec3)> = if incidence.is_enabled {
+            // Above the footprints, to stay visible over them
+            let lift_m = 2.0 * antenna_beam_footprint_state.lift_m as f32;
+            let points = &antenna_beam_footprint_state.points;
+            incidence.window.segments_outside(&carrier_state.position_m, &footprint_enu(antenna_beam_footprint_state))
+                .into_iter()
+                .map(|i| (
+                    Vec3::new(points[i].x as f32, lift_m, points[i].z as f32),
+                    Vec3::new(points[i + 1].x as f32, lift_m, points[i + 1].z as f32),
+                ))
+                .collect()
+        } else {
+            vec![]
+        };
+        // Redrawn only when they change, hidden without segments
+        if lines != drawn_lines[index] {
+            super::display::replace_line_mesh(&mut meshes, mesh, visibility, (!lines.is_empty()).then(|| LineList { lines: lines.clone() }))?;
+            drawn_lines[index] = lines;
+        }
+    }
+
+    Ok(())
+}
+
+fn incidence_ui_system(
+    mut contexts: EguiContexts,
+    mut incidence_widget: ResMut<IncidenceWidget>,
+    tx: (Res<TxCarrierState>, Res<TxAntennaBeamFootprintState>),
+    rx: (Res<RxCarrierState>, Res<RxAntennaBeamFootprintState>),
+) -> Result {
+    let ctx = contexts.ctx_mut()?;
+
+    let incidence_window = egui::Window::new("Incidence Windows")
+        .resizable(false)
+        .constrain(true)
+        .collapsible(true)
+        .title_bar(true)
+        .enabled(true)
+        .default_open(false)
+        .default_pos(egui::pos2(320.0, 520.0));
+    incidence_window.show(ctx, |ui| {
+        let widget = &mut *incidence_widget; // Borrows the fields separately
+        carrier_incidence_ui(ui, "tx", "TRANSMITTER", &mut widget.tx, &tx.0.inner, &tx.1.inner);
+        carrier_incidence_ui(ui, "rx", "RECEIVER", &mut widget.rx, &rx.0.inner, &rx.1.inner);
+    });
+
+    Ok(())
+}
+
+fn carrier_incidence_ui(
+    ui: &mut egui::Ui,
+    id: &str,
+    title: &str,
+    incidence: &mut CarrierIncidence,
+    carrier_state: &CarrierState,
+    antenna_beam_footprint_state: &AntennaBeamFootprintState,
+) {
+    ui.separator();
+    ui.vertical_centered(|ui| ui.label(egui::RichText::new(title).strong()));
+    ui.separator();
+
+    let hover_text = egui::RichText::new("Checks the local incidence along the footprint edge against the allowed window,\ndrawing the footprint segments outside in red")
+        .color(TEXT_COLOR)
+        .monospace();
+    ui.checkbox(&mut incidence.is_enabled, "Incidence window").on_hover_text(hover_text);
+    if !incidence.is_enabled {
+        return;
+    }
+    let footprint = footprint_enu(antenna_beam_footprint_state);
+    egui::Grid::new(format!("{id}_incidence_grid"))
+        .num_columns(2)
+        .striped(false)
+        .spacing([20.0, 5.0])
+        .show(ui, |ui| {
+            let window = &mut incidence.window;
+            let hover_text = egui::RichText::new("Sets the minimum allowed local incidence (0 - maximum)")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Minimum incidence: ").on_hover_text(hover_text.clone());
+            ui.add(
+                egui::DragValue::new(&mut window.min_deg)
+                    .custom_parser(expression::parse)
+                    .update_while_editing(false)
+                    .speed(0.1)
+                    .range(0.0..=window.max_deg)
+                    .fixed_decimals(1)
+                    .suffix("°")
+            ).on_hover_text(hover_text);
+            ui.end_row();
+
+            let hover_text = egui::RichText::new("Sets the maximum allowed local incidence (minimum - 90°)")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Maximum incidence: ").on_hover_text(hover_text.clone());
+            ui.add(
+                egui::DragValue::new(&mut window.max_deg)
+                    .custom_parser(expression::parse)
+                    .update_while_editing(false)
+                    .speed(0.1)
+                    .range(window.min_deg..=90.0)
+                    .fixed_decimals(1)
+                    .suffix("°")
+            ).on_hover_text(hover_text);
+            ui.end_row();
+
+            if let Some((min_deg, max_deg)) = incidence_range_deg(&carrier_state.position_m, &footprint) {
+                ui.label("Footprint incidences: ");
+                ui.label(format!("{min_deg:.2}° - {max_deg:.2}°"));
+                ui.end_row();
+            }
+        });
+
+    let segment_count = footprint.len().saturating_sub(1).max(1);
+    let outside_fraction = incidence.window.segments_outside(&carrier_state.position_m, &footprint).len() as f64
+        / segment_count as f64;
+    if !incidence.window.contains(antenna_beam_footprint_state.loc_incidence_center_deg) {
+        ui.label(egui::RichText::new("⚠ The footprint center incidence is outside the window").color(ERROR_COLOR));
+    }
+    let text = format!("{:.0} % of the footprint edge outside the window", 100.0 * outside_fraction);
+    ui.label(egui::RichText::new(text).color(if outside_fraction > 0.0 { ERROR_COLOR } else { TEXT_COLOR }));
+}
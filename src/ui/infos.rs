@@ -1,37 +1,69 @@
 // use bevy::prelude::*;
+use bevy::math::DVec3;
 use bevy_egui::egui;
 
 use crate::{
-    bsar::BsarInfos,
-    entities::{CarrierState, AntennaBeamFootprintState}
+    bsar::{
+        resolutions_diverge, BsarInfos, ResolutionFormula,
+        AASR_WARNING_DB, FORWARD_SCATTER_BISTATIC_ANGLE_DEG, MAX_DISPLAYED_RESOLUTION_M, RESOLUTION_DIVERGENCE
+    },
+    entities::{CarrierState, AntennaBeamFootprintState},
+    ui::{pin_menu, BsarMetric, CarrierMetric, GeometryMode, HudMetric, HudWidget},
 };
 
+const ERROR_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 120, 120);
+
+/// Formats a resolution in meters, capped at [`MAX_DISPLAYED_RESOLUTION_M`].
+pub(super) fn resolution_text(resolution_m: f64) -> String {
+    if resolution_m > MAX_DISPLAYED_RESOLUTION_M {
+        format!("> {:.0} km", MAX_DISPLAYED_RESOLUTION_M * 1e-3)
+    } else {
+        format!("{:.3} m", resolution_m)
+    }
+}
+
+/// Formats a resolution area, capped at the square of
+/// [`MAX_DISPLAYED_RESOLUTION_M`].
+pub(super) fn resolution_area_text(resolution_area_m2: f64) -> String {
+    let max_area_m2 = MAX_DISPLAYED_RESOLUTION_M * MAX_DISPLAYED_RESOLUTION_M;
+    if resolution_area_m2 > max_area_m2 {
+        format!("> {:.0} km²", max_area_m2 * 1e-6)
+    } else if resolution_area_m2 >= 1e5 {
+        format!("{:.3} km²", resolution_area_m2 * 1e-6)
+    } else {
+        format!("{:.3} m²", resolution_area_m2)
+    }
+}
+
 pub fn carrier_infos_ui(
     ui: &mut egui::Ui,
     carrier_state: &CarrierState,
     antenna_beam_footprint_state: &AntennaBeamFootprintState,
     name: &str,
+    hud_widget: &mut HudWidget,
 ) {
     egui::Grid::new(format!("{}_carrier_infos_grid", name))
         .num_columns(2)
         .striped(true)
         .show(ui, |ui| {
             // Carrier position ENU
-            ui.label("Carrier position:")
+            let response = ui.label("Carrier position:")
                 .on_hover_text(
                     egui::RichText::new("In East North Up (ENU) coordinates (x, y, z).")
                         .color(egui::Color32::from_rgb(200, 200, 200))
                         .monospace()
                 );
+            pin_menu(&response, hud_widget, HudMetric::carrier(name, CarrierMetric::Position));
             ui.label(format!("({:.1} m, {:.1} m, {:.1} m)", carrier_state.position_m.x, carrier_state.position_m.y, carrier_state.position_m.z));
             ui.end_row();
             // Carrier velocity vector ENU
-            ui.label("Carrier velocity vector:")
+            let response = ui.label("Carrier velocity vector:")
                 .on_hover_text(
                     egui::RichText::new("In East North Up (ENU) coordinates (vx, vy, vz).")
                         .color(egui::Color32::from_rgb(200, 200, 200))
                         .monospace()
                 );
+            pin_menu(&response, hud_widget, HudMetric::carrier(name, CarrierMetric::VelocityVector));
             ui.label(format!(
                 "({:.1} m/s, {:.1} m/s, {:.1} m/s)",
                 carrier_state.velocity_vector_mps.x,
@@ -48,7 +80,8 @@ pub fn carrier_infos_ui(
         .striped(true)
         .show(ui, |ui| {
             // Slant range min infos
-            ui.label("Slant range min:");
+            let response = ui.label("Slant range min:");
+            pin_menu(&response, hud_widget, HudMetric::carrier(name, CarrierMetric::SlantRangeMin));
             ui.label(
                 if antenna_beam_footprint_state.range_min_m >= 1e3 {
                     format!("{:.3} km", antenna_beam_footprint_state.range_min_m * 1e-3)
@@ -58,7 +91,8 @@ pub fn carrier_infos_ui(
             );
             ui.end_row();
             // Slant range center infos
-            ui.label("Slant range center:");
+            let response = ui.label("Slant range center:");
+            pin_menu(&response, hud_widget, HudMetric::carrier(name, CarrierMetric::SlantRangeCenter));
             ui.label(
                 if antenna_beam_footprint_state.range_center_m >= 1e3 {
                     format!("{:.3} km", antenna_beam_footprint_state.range_center_m * 1e-3)
@@ -68,7 +102,8 @@ pub fn carrier_infos_ui(
             );
             ui.end_row();
             // Slant range max infos
-            ui.label("Slant range max:");
+            let response = ui.label("Slant range max:");
+            pin_menu(&response, hud_widget, HudMetric::carrier(name, CarrierMetric::SlantRangeMax));
             ui.label(
                 if antenna_beam_footprint_state.range_max_m >= 1e3 {
                     format!("{:.3} km", antenna_beam_footprint_state.range_max_m * 1e-3)
@@ -77,27 +112,57 @@ pub fn carrier_infos_ui(
                 } 
             );
             ui.end_row();
+            // Slant range min and max points ENU
+            let [point_min_range, point_max_range] = antenna_beam_footprint_state.range_extrema_points();
+            for (label, point) in [("Range min point:", point_min_range), ("Range max point:", point_max_range)] {
+                ui.label(label)
+                    .on_hover_text(
+                        egui::RichText::new("Footprint point of the slant range, in East North Up (ENU) coordinates (x, y, z).")
+                            .color(egui::Color32::from_rgb(200, 200, 200))
+                            .monospace()
+                    );
+                ui.label(format!("({:.1} m, {:.1} m, {:.1} m)", point.z, point.x, point.y)); // Y-up to ENU
+                ui.end_row();
+            }
 
             // Local incidence min infos
-            ui.label("Local incidence min:");
+            let response = ui.label("Local incidence min:");
+            pin_menu(&response, hud_widget, HudMetric::carrier(name, CarrierMetric::LocalIncidenceMin));
             ui.label(format!("{:.3}°", antenna_beam_footprint_state.loc_incidence_min_deg));
             ui.end_row();
             // Local incidence center infos
-            ui.label("Local incidence center:");
+            let response = ui.label("Local incidence center:");
+            pin_menu(&response, hud_widget, HudMetric::carrier(name, CarrierMetric::LocalIncidenceCenter));
             ui.label(format!("{:.3}°", antenna_beam_footprint_state.loc_incidence_center_deg));
             ui.end_row();
             // Local incidence max infos
-            ui.label("Local incidence max:");
+            let response = ui.label("Local incidence max:");
+            pin_menu(&response, hud_widget, HudMetric::carrier(name, CarrierMetric::LocalIncidenceMax));
             ui.label(format!("{:.3}°", antenna_beam_footprint_state.loc_incidence_max_deg));
             ui.end_row();
 
             // Antenna squint infos
-            ui.label("Antenna squint:");
+            let response = ui.label("Antenna squint:")
+                .on_hover_text(
+                    egui::RichText::new(format!(
+                        "Angle {}, set in the Display window.",
+                        antenna_beam_footprint_state.squint_convention.description()
+                    ))
+                        .color(egui::Color32::from_rgb(200, 200, 200))
+                        .monospace()
+                );
+            pin_menu(&response, hud_widget, HudMetric::carrier(name, CarrierMetric::AntennaSquint));
             ui.label(format!("{:.3}°", antenna_beam_footprint_state.antenna_squint_deg));
             ui.end_row();
 
             // Ground range swath infos
-            ui.label("Ground range swath:");
+            let response = ui.label("Ground range swath:");
+            let response = if antenna_beam_footprint_state.earth_frame.is_some() {
+                response.on_hover_text("Along the geodesic of the Ellipsoid (Earth curvature)")
+            } else {
+                response
+            };
+            pin_menu(&response, hud_widget, HudMetric::carrier(name, CarrierMetric::GroundRangeSwath));
             ui.label(
                 if antenna_beam_footprint_state.ground_range_swath_m >= 1e3 {
                     format!("{:.3} km", antenna_beam_footprint_state.ground_range_swath_m * 1e-3)
@@ -108,7 +173,8 @@ pub fn carrier_infos_ui(
             ui.end_row();
 
             // Ground range swath infos
-            ui.label("Footprint area:");
+            let response = ui.label("Footprint area:");
+            pin_menu(&response, hud_widget, HudMetric::carrier(name, CarrierMetric::FootprintArea));
             ui.label(
                 if antenna_beam_footprint_state.area_m2 >= 1e5 {
                     format!("{:.3} km²", antenna_beam_footprint_state.area_m2 * 1e-6)
@@ -119,7 +185,8 @@ pub fn carrier_infos_ui(
             ui.end_row();
 
             // Ground range swath infos
-            ui.label("Illumination time:");
+            let response = ui.label("Illumination time:");
+            pin_menu(&response, hud_widget, HudMetric::carrier(name, CarrierMetric::IlluminationTime));
             ui.label(
                 if antenna_beam_footprint_state.illumination_time_s >= 60.0 {
                     format!("{:.3} min", antenna_beam_footprint_state.illumination_time_s/60.0)
@@ -130,105 +197,209 @@ pub fn carrier_infos_ui(
             ui.end_row();
 
             // Ground range swath infos
-            ui.label("Ground angular velocity:");
+            let response = ui.label("Ground angular velocity:");
+            pin_menu(&response, hud_widget, HudMetric::carrier(name, CarrierMetric::GroundAngularVelocity));
             ui.label(format!("{:.3} °/s", antenna_beam_footprint_state.ground_angular_velocity_degps));
             ui.end_row();
         });
 }
 
 
+/// Shows the BSAR infos. In monostatic mode the slant ranges are the one-way
+/// ones (half the Tx/footprint/Rx ranges) and the Tx/Rx direct range and
+/// bistatic angle, both zero, are not shown.
 pub fn bsar_infos_ui(
     ui: &mut egui::Ui,
     bsar_infos: &BsarInfos,
+    geometry_mode: GeometryMode,
+    hud_widget: &mut HudWidget,
 ) {
+    let is_monostatic = geometry_mode == GeometryMode::Monostatic;
+    let range_text = |range_m: f64| {
+        // One-way range in monostatic mode
+        let range_m = if is_monostatic { 0.5 * range_m } else { range_m };
+        if range_m >= 1e3 {
+            format!("{:.3} km", range_m * 1e-3)
+        } else {
+            format!("{:.3} m", range_m)
+        }
+    };
+    // Near forward scatter the bisector vanishes: the resolutions diverge
+    let is_forward_scatter = !is_monostatic && bsar_infos.is_near_forward_scatter();
+    let forward_scatter_text = egui::RichText::new(format!(
+        "Near forward scatter (bistatic angle ≥ {FORWARD_SCATTER_BISTATIC_ANGLE_DEG:.0}°) the bistatic bisector vanishes:\n\
+         the resolution formulas blow up and the resolutions are not meaningful.\n\
+         note: resolutions above {:.0} km are not displayed.",
+        MAX_DISPLAYED_RESOLUTION_M * 1e-3
+    ))
+        .color(egui::Color32::from_rgb(200, 200, 200))
+        .monospace();
+    // The other resolution formula is shown when it diverges, for validation
+    let other_formula = match bsar_infos.resolution_formula {
+        ResolutionFormula::Bisector => ResolutionFormula::ExactGradient,
+        ResolutionFormula::ExactGradient => ResolutionFormula::Bisector,
+    };
+    let divergence_text = egui::RichText::new(format!(
+        "{} formula, differing by more than {:.0} % from the {} one:\n\
+         long integration time or maneuvering carriers (small-angle approximation of the bisector formulas).",
+        other_formula.label(),
+        100.0 * RESOLUTION_DIVERGENCE,
+        bsar_infos.resolution_formula.label().to_lowercase()
+    ))
+        .color(egui::Color32::from_rgb(200, 200, 200))
+        .monospace();
+    let resolution_label = |ui: &mut egui::Ui, text: String, other_text: Option<String>| {
+        ui.horizontal(|ui| {
+            let response = ui.label(text);
+            if is_forward_scatter {
+                response.on_hover_text(forward_scatter_text.clone());
+            }
+            if let Some(other_text) = other_text {
+                ui.weak(format!("({other_text})")).on_hover_text(divergence_text.clone());
+            }
+        });
+    };
+    let other_resolution = |resolution: f64, other_value: f64, text: fn(f64) -> String| {
+        resolutions_diverge(resolution, other_value).then(|| text(other_value))
+    };
+    let other = &bsar_infos.alternative_resolutions;
     egui::Grid::new("bsar_infos_grid")
         .num_columns(2)
         .striped(true)
         .show(ui, |ui| {
             // Slant range min infos
-            ui.label("Slant range min:").on_hover_text(
-                egui::RichText::new("The minimum BSAR system slant range between Tx/footprint/Rx.\nnote: the footprint is heuristically determined by choosing the one with the smallest ground range swath.")
+            let hover_text = if is_monostatic {
+                "The minimum one-way slant range between the carrier and the footprint."
+            } else {
+                "The minimum BSAR system slant range between Tx/footprint/Rx.\nnote: the footprint is heuristically determined by choosing the one with the smallest ground range swath."
+            };
+            let response = ui.label("Slant range min:").on_hover_text(
+                egui::RichText::new(hover_text)
                     .color(egui::Color32::from_rgb(200, 200, 200))
                     .monospace()
                 );
-            ui.label(
-                if bsar_infos.range_min_m >= 1e3 {
-                    format!("{:.3} km", bsar_infos.range_min_m * 1e-3)
-                } else {
-                    format!("{:.3} m", bsar_infos.range_min_m)
-                }
-            );
+            pin_menu(&response, hud_widget, HudMetric::Bsar(BsarMetric::SlantRangeMin));
+            ui.label(range_text(bsar_infos.range_min_m));
             ui.end_row();
             // Slant range center infos
-            ui.label("Slant range center:").on_hover_text(
-                egui::RichText::new("The BSAR system slant range between Tx/center/Rx.")
+            let hover_text = if is_monostatic {
+                "The one-way slant range between the carrier and the center."
+            } else {
+                "The BSAR system slant range between Tx/center/Rx."
+            };
+            let response = ui.label("Slant range center:").on_hover_text(
+                egui::RichText::new(hover_text)
                     .color(egui::Color32::from_rgb(200, 200, 200))
                     .monospace()
                 );
-            ui.label(
-                if bsar_infos.range_center_m >= 1e3 {
-                    format!("{:.3} km", bsar_infos.range_center_m * 1e-3)
-                } else {
-                    format!("{:.3} m", bsar_infos.range_center_m)
-                }
-            );
+            pin_menu(&response, hud_widget, HudMetric::Bsar(BsarMetric::SlantRangeCenter));
+            ui.label(range_text(bsar_infos.range_center_m));
             ui.end_row();
             // Slant range max infos
-            ui.label("Slant range max:").on_hover_text(
-                egui::RichText::new("The maximum BSAR system slant range between Tx/footprint/Rx.\nnote: the footprint is heuristically determined by choosing the one with the smallest ground range swath.")
+            let hover_text = if is_monostatic {
+                "The maximum one-way slant range between the carrier and the footprint."
+            } else {
+                "The maximum BSAR system slant range between Tx/footprint/Rx.\nnote: the footprint is heuristically determined by choosing the one with the smallest ground range swath."
+            };
+            let response = ui.label("Slant range max:").on_hover_text(
+                egui::RichText::new(hover_text)
                     .color(egui::Color32::from_rgb(200, 200, 200))
                     .monospace()
             );
-            ui.label(
-                if bsar_infos.range_max_m >= 1e3 {
-                    format!("{:.3} km", bsar_infos.range_max_m * 1e-3)
-                } else {
-                    format!("{:.3} m", bsar_infos.range_max_m)
-                }
-            );
+            pin_menu(&response, hud_widget, HudMetric::Bsar(BsarMetric::SlantRangeMax));
+            ui.label(range_text(bsar_infos.range_max_m));
             ui.end_row();
-            // Tx/Rx direct range infos
-            ui.label("Tx/Rx direct range:");
-            ui.label(
-                if bsar_infos.direct_range_m >= 1e3 {
-                    format!("{:.3} km", bsar_infos.direct_range_m * 1e-3)
+            if !is_monostatic {
+                // Tx/Rx direct range infos
+                let response = ui.label("Tx/Rx direct range:");
+                pin_menu(&response, hud_widget, HudMetric::Bsar(BsarMetric::DirectRange));
+                ui.label(
+                    if bsar_infos.direct_range_m >= 1e3 {
+                        format!("{:.3} km", bsar_infos.direct_range_m * 1e-3)
+                    } else {
+                        format!("{:.3} m", bsar_infos.direct_range_m)
+                    }
+                );
+                ui.end_row();
+                // Bistatic angle infos
+                let response = ui.label("Bistatic angle:");
+                pin_menu(&response, hud_widget, HudMetric::Bsar(BsarMetric::BistaticAngle));
+                let text = format!("{:.3} °", bsar_infos.bistatic_angle_deg);
+                if is_forward_scatter {
+                    ui.label(egui::RichText::new(format!("⚠ {text}")).color(ERROR_COLOR))
+                        .on_hover_text(forward_scatter_text.clone());
                 } else {
-                    format!("{:.3} m", bsar_infos.direct_range_m)
+                    ui.label(text);
                 }
-            );
-            ui.end_row();
-            // Bistatic angle infos
-            ui.label("Bistatic angle:");
-            ui.label(format!("{:.3} °", bsar_infos.bistatic_angle_deg));
-            ui.end_row();
+                ui.end_row();
+            }
             // Slant range res infos
-            ui.label("Slant range res.:");
-            ui.label(format!("{:.3} m", bsar_infos.slant_range_resolution_m));
+            let response = ui.label("Slant range res.:");
+            pin_menu(&response, hud_widget, HudMetric::Bsar(BsarMetric::SlantRangeResolution));
+            resolution_label(
+                ui,
+                resolution_text(bsar_infos.slant_range_resolution_m),
+                other_resolution(bsar_infos.slant_range_resolution_m, other.slant_range_m, resolution_text)
+            );
             ui.end_row();
             // Ground range res infos
-            ui.label("Ground range res.:");
-            ui.label(format!("{:.3} m", bsar_infos.ground_range_resolution_m));
+            let response = ui.label("Ground range res.:");
+            pin_menu(&response, hud_widget, HudMetric::Bsar(BsarMetric::GroundRangeResolution));
+            resolution_label(
+                ui,
+                resolution_text(bsar_infos.ground_range_resolution_m),
+                other_resolution(bsar_infos.ground_range_resolution_m, other.ground_range_m, resolution_text)
+            );
             ui.end_row();
             // Slant lateral res infos
-            ui.label("Slant lateral res.:");
-            ui.label(format!("{:.3} m", bsar_infos.slant_lateral_resolution_m));
+            let response = ui.label("Slant lateral res.:");
+            pin_menu(&response, hud_widget, HudMetric::Bsar(BsarMetric::SlantLateralResolution));
+            resolution_label(
+                ui,
+                resolution_text(bsar_infos.slant_lateral_resolution_m),
+                other_resolution(bsar_infos.slant_lateral_resolution_m, other.slant_lateral_m, resolution_text)
+            );
             ui.end_row();
             // Ground lateral res infos
-            ui.label("Ground lateral res.:");
-            ui.label(format!("{:.3} m", bsar_infos.ground_lateral_resolution_m));
+            let response = ui.label("Ground lateral res.:");
+            pin_menu(&response, hud_widget, HudMetric::Bsar(BsarMetric::GroundLateralResolution));
+            resolution_label(
+                ui,
+                resolution_text(bsar_infos.ground_lateral_resolution_m),
+                other_resolution(bsar_infos.ground_lateral_resolution_m, other.ground_lateral_m, resolution_text)
+            );
+            ui.end_row();
+            // Ground resolution directions infos
+            let direction_text = |direction: Option<DVec3>| match direction {
+                Some(direction) => format!("({:.3}, {:.3}, {:.3})", direction.x, direction.y, direction.z),
+                None => "-".to_owned(),
+            };
+            ui.label("Ground range dir.:").on_hover_text(
+                egui::RichText::new("Unit vector of the ground range resolution direction, in East North Up (ENU) coordinates.\nnote: ground-projected bistatic bisector, orthogonal to the iso-range lines.")
+                    .color(egui::Color32::from_rgb(200, 200, 200))
+                    .monospace()
+            );
+            ui.label(direction_text(bsar_infos.ground_range_direction()));
+            ui.end_row();
+            ui.label("Ground lateral dir.:").on_hover_text(
+                egui::RichText::new("Unit vector of the ground lateral resolution direction, in East North Up (ENU) coordinates.\nnote: ground-projected bistatic bisector derivative, orthogonal to the iso-Doppler lines.")
+                    .color(egui::Color32::from_rgb(200, 200, 200))
+                    .monospace()
+            );
+            ui.label(direction_text(bsar_infos.ground_lateral_direction()));
             ui.end_row();
             // Resolution area infos
-            ui.label("Resolution area:");
-            ui.label(
-                if bsar_infos.resolution_area_m2 >= 1e5 {
-                    format!("{:.3} km²", bsar_infos.resolution_area_m2 * 1e-6)
-                } else {
-                    format!("{:.3} m²", bsar_infos.resolution_area_m2)
-                }
+            let response = ui.label("Resolution area:");
+            pin_menu(&response, hud_widget, HudMetric::Bsar(BsarMetric::ResolutionArea));
+            resolution_label(
+                ui,
+                resolution_area_text(bsar_infos.resolution_area_m2),
+                other_resolution(bsar_infos.resolution_area_m2, other.area_m2, resolution_area_text)
             );
             ui.end_row();
             // Doppler frequency infos
-            ui.label("Doppler frequency:");
+            let response = ui.label("Doppler frequency:");
+            pin_menu(&response, hud_widget, HudMetric::Bsar(BsarMetric::DopplerFrequency));
             ui.label(
                 if bsar_infos.doppler_frequency_hz >= 1e3 {
                     format!("{:.3} kHz", bsar_infos.doppler_frequency_hz * 1e-3)
@@ -238,7 +409,8 @@ pub fn bsar_infos_ui(
             );
             ui.end_row();
             // Doppler rate infos
-            ui.label("Doppler rate:");
+            let response = ui.label("Doppler rate:");
+            pin_menu(&response, hud_widget, HudMetric::Bsar(BsarMetric::DopplerRate));
             ui.label(
                 if bsar_infos.doppler_rate_hzps.abs() >= 1e3 {
                     format!("{:.3} kHz/s", bsar_infos.doppler_rate_hzps * 1e-3)
@@ -248,11 +420,13 @@ pub fn bsar_infos_ui(
             );
             ui.end_row();
             // Integration time infos
-            ui.label("Integration time:");
+            let response = ui.label("Integration time:");
+            pin_menu(&response, hud_widget, HudMetric::Bsar(BsarMetric::IntegrationTime));
             ui.label(format!("{:.3} s", bsar_infos.integration_time_s));
             ui.end_row();
             // Processed Doppler bandwidth infos
-            ui.label("Processed Dop. band.:");
+            let response = ui.label("Processed Dop. band.:");
+            pin_menu(&response, hud_widget, HudMetric::Bsar(BsarMetric::ProcessedDopplerBandwidth));
             ui.label(
                 if bsar_infos.processed_doppler_bandwidth_hz >= 1e3 {
                     format!("{:.3} kHz", bsar_infos.processed_doppler_bandwidth_hz * 1e-3)
@@ -262,7 +436,8 @@ pub fn bsar_infos_ui(
             );
             ui.end_row();
             // NESZ infos
-            ui.label("NESZ:");
+            let response = ui.label("NESZ:");
+            pin_menu(&response, hud_widget, HudMetric::Bsar(BsarMetric::Nesz));
             ui.label(
                 if bsar_infos.nesz.is_nan() { // Not computable (degenerate geometry or zero duty cycle)
                     "-".to_owned()
@@ -271,5 +446,65 @@ pub fn bsar_infos_ui(
                 }
             );
             ui.end_row();
+            // RASR infos
+            let response = ui.label("RASR:").on_hover_text(
+                egui::RichText::new("Range Ambiguity to Signal Ratio, worst case over the swath.\nnote: echoes of the 5 previous and next pulses along the ground range line through the center, with sinc² elevation patterns of the footprint elevation beam widths.")
+                    .color(egui::Color32::from_rgb(200, 200, 200))
+                    .monospace()
+            );
+            pin_menu(&response, hud_widget, HudMetric::Bsar(BsarMetric::Rasr));
+            ui.label(
+                if bsar_infos.rasr.is_nan() { // Not computable (degenerate geometry or footprint)
+                    "-".to_owned()
+                } else {
+                    format!("{:.3} dB", 10.0 * bsar_infos.rasr.log10())
+                }
+            );
+            ui.end_row();
+            // AASR infos
+            let hover_text = format!(
+                "Azimuth Ambiguity to Signal Ratio over the processed Doppler bandwidth.\n\
+                 note: Tx and Rx sinc² azimuth patterns spanning the Doppler frequencies of their footprints,\n\
+                 aliased by the PRF; highlighted above {AASR_WARNING_DB:.0} dB."
+            );
+            let response = ui.label("AASR:").on_hover_text(
+                egui::RichText::new(hover_text)
+                    .color(egui::Color32::from_rgb(200, 200, 200))
+                    .monospace()
+            );
+            pin_menu(&response, hud_widget, HudMetric::Bsar(BsarMetric::Aasr));
+            let aasr_db = 10.0 * bsar_infos.aasr.log10();
+            if bsar_infos.aasr.is_nan() { // Not computable (degenerate geometry or footprint)
+                ui.label("-");
+            } else if aasr_db > AASR_WARNING_DB {
+                ui.label(egui::RichText::new(format!("⚠ {aasr_db:.3} dB")).color(ERROR_COLOR));
+            } else {
+                ui.label(format!("{aasr_db:.3} dB"));
+            }
+            ui.end_row();
+            // Sampling frequency infos
+            let response = ui.label("Sampling frequency:");
+            pin_menu(&response, hud_widget, HudMetric::Bsar(BsarMetric::SamplingFrequency));
+            ui.label(format!("{:.3} MHz", bsar_infos.sampling_frequency_hz * 1e-6));
+            ui.end_row();
+            // Data rate infos
+            let response = ui.label("Data rate:").on_hover_text(
+                egui::RichText::new("Complex samples per second over the receive window of every pulse.\nnote: sampling frequency x (range extent / c + pulse duration) x PRF.")
+                    .color(egui::Color32::from_rgb(200, 200, 200))
+                    .monospace()
+            );
+            pin_menu(&response, hud_widget, HudMetric::Bsar(BsarMetric::DataRate));
+            ui.label(
+                if bsar_infos.data_rate_sps.is_nan() {
+                    "-".to_owned()
+                } else {
+                    format!("{:.3} Msamples/s", bsar_infos.data_rate_sps * 1e-6)
+                }
+            );
+            ui.end_row();
         });
-}
\ No newline at end of file
+    if is_forward_scatter {
+        ui.label(egui::RichText::new("⚠ Near forward scatter: the resolutions are not meaningful").color(ERROR_COLOR))
+            .on_hover_text(forward_scatter_text);
+    }
+}
@@ -0,0 +1,146 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts, EguiPrimaryContextPass};
+
+use crate::{
+    bsar::SPEED_OF_LIGHT_IN_VACUUM,
+    expression,
+    formation::InterferometricBaselines,
+    scene::{RxCarrierState, TxCarrierState},
+    ui::{GeometryMode, MenuWidget},
+};
+
+const TEXT_COLOR: egui::Color32 = egui::Color32::from_rgb(200, 200, 200);
+
+pub struct InterferometryPlugin;
+
+impl Plugin for InterferometryPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<InterferometryWidget>()
+            .add_systems(EguiPrimaryContextPass, interferometry_ui_system);
+    }
+}
+
+/// Interferometric quantities of the quasi-monostatic formation, the Tx and
+/// Rx channels forming a single-pass interferometer, and the lever arm tuning
+/// for a target height of ambiguity.
+#[derive(Resource)]
+pub struct InterferometryWidget {
+    pub target_height_of_ambiguity_m: f64,
+}
+
+impl Default for InterferometryWidget {
+    fn default() -> Self {
+        Self {
+            target_height_of_ambiguity_m: 50.0,
+        }
+    }
+}
+
+fn interferometry_ui_system(
+    mut contexts: EguiContexts,
+    mut interferometry_widget: ResMut<InterferometryWidget>,
+    mut menu_widget: ResMut<MenuWidget>,
+    tx_carrier_state: Res<TxCarrierState>,
+    rx_carrier_state: Res<RxCarrierState>,
+) -> Result {
+    let ctx = contexts.ctx_mut()?;
+
+    let interferometry_window = egui::Window::new("Interferometry")
+        .resizable(false)
+        .constrain(true)
+        .collapsible(true)
+        .title_bar(true)
+        .enabled(true)
+        .default_open(false)
+        .default_pos(egui::pos2(320.0, 1080.0));
+    interferometry_window.show(ctx, |ui| {
+        if menu_widget.geometry_mode != GeometryMode::QuasiMonostatic {
+            ui.label(egui::RichText::new("Available in quasi-monostatic mode (Receiver settings)").color(TEXT_COLOR));
+            return;
+        }
+        interferometry_ui(ui, &mut interferometry_widget, &mut menu_widget, &tx_carrier_state, &rx_carrier_state);
+    });
+
+    Ok(())
+}
+
+fn interferometry_ui(
+    ui: &mut egui::Ui,
+    widget: &mut InterferometryWidget,
+    menu_widget: &mut MenuWidget,
+    tx_carrier_state: &TxCarrierState,
+    rx_carrier_state: &RxCarrierState,
+) {
+    let tx_position_m = &tx_carrier_state.inner.position_m;
+    let heading_deg = tx_carrier_state.inner.heading_deg;
+    let wavelength_m = SPEED_OF_LIGHT_IN_VACUUM / (tx_carrier_state.center_frequency_ghz * 1e9);
+    let baseline_m = rx_carrier_state.inner.position_m - *tx_position_m;
+    let baselines = InterferometricBaselines::new(tx_position_m, &baseline_m, heading_deg, wavelength_m);
+
+    egui::Grid::new("interferometry_grid")
+        .num_columns(2)
+        .striped(true)
+        .spacing([20.0, 5.0])
+        .show(ui, |ui| {
+            let meters_text = |value: Option<f64>| value.map_or("-".to_string(), |value_m| format!("{value_m:.3} m"));
+            let hover_text = egui::RichText::new("Baselines of the equivalent monostatic interferometer, half the physical ones\nas the Tx transmits for both channels")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Eff. perpendicular baseline:").on_hover_text(hover_text.clone());
+            ui.label(meters_text(baselines.map(|baselines| baselines.perpendicular_m)));
+            ui.end_row();
+            ui.label("Eff. parallel baseline:").on_hover_text(hover_text.clone());
+            ui.label(meters_text(baselines.map(|baselines| baselines.parallel_m)));
+            ui.end_row();
+            ui.label("Eff. along-track baseline:").on_hover_text(hover_text);
+            ui.label(meters_text(baselines.map(|baselines| baselines.along_track_m)));
+            ui.end_row();
+            let hover_text = egui::RichText::new("Height difference wrapping the interferometric phase by 2π at the scene center")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Height of ambiguity:").on_hover_text(hover_text);
+            ui.label(meters_text(baselines.map(|baselines| baselines.height_of_ambiguity_m())));
+            ui.end_row();
+            let hover_text = egui::RichText::new("Interferometric phase change per meter of height (2π over the height of ambiguity)")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Height sensitivity:").on_hover_text(hover_text);
+            ui.label(
+                baselines.map_or("-".to_string(), |baselines| format!("{:.3} °/m", baselines.vertical_wavenumber_radpm().to_degrees()))
+            );
+            ui.end_row();
+        });
+
+    ui.separator();
+    ui.horizontal(|ui| {
+        let hover_text = egui::RichText::new("Target height of ambiguity (0.1 - 10000 m)")
+            .color(TEXT_COLOR)
+            .monospace();
+        ui.label("Target:").on_hover_text(hover_text.clone());
+        ui.add(
+            egui::DragValue::new(&mut widget.target_height_of_ambiguity_m)
+                .custom_parser(expression::parse)
+                .update_while_editing(false)
+                .speed(0.5)
+                .range(0.1..=10000.0)
+                .fixed_decimals(1)
+                .suffix(" m")
+        )
+        .on_hover_text(hover_text);
+        let lever_arm = menu_widget.lever_arm.for_height_of_ambiguity(
+            tx_position_m,
+            heading_deg,
+            wavelength_m,
+            widget.target_height_of_ambiguity_m,
+        );
+        let hover_text = egui::RichText::new("Sets the shortest lever arm across the line of sight giving the target height of\nambiguity, keeping its along-track component")
+            .color(TEXT_COLOR)
+            .monospace();
+        if ui.add_enabled(lever_arm.is_some(), egui::Button::new("Tune lever arm")).on_hover_text(hover_text).clicked()
+            && let Some(lever_arm) = lever_arm
+        {
+            menu_widget.lever_arm = lever_arm;
+        }
+    });
+}
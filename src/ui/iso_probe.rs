@@ -0,0 +1,267 @@
+use bevy::{math::DVec2, prelude::*};
+use bevy_egui::{egui, EguiContexts, EguiPrimaryContextPass};
+use bevy_panorbit_camera::PanOrbitCamera;
+
+use crate::{
+    entities::{enu_to_world, IsoRangeDopplerPlaneState, LevelContours, LineList},
+    scene::TxAntennaBeamFootprintState,
+    ui::{SidePanelRects, UpdateStage},
+};
+
+const TEXT_COLOR: egui::Color32 = egui::Color32::from_rgb(200, 200, 200);
+/// Colors of the probed iso-range and iso-Doppler contours, standing out of
+/// the red and blue contours of the plane
+const RANGE_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 220, 0);
+const DOPPLER_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 0, 255);
+/// Radius of the probed point marker in points
+const MARKER_RADIUS: f32 = 4.0;
+
+pub struct IsoProbePlugin;
+
+impl Plugin for IsoProbePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<IsoProbeWidget>()
+            .add_systems(Startup, spawn_probe_contours)
+            // The plane is updated by the update systems
+            .add_systems(
+                PostUpdate,
+                update_probe_contours.in_set(UpdateStage::RenderAssets)
+            )
+            .add_systems(EguiPrimaryContextPass, iso_probe_ui_system);
+    }
+}
+
+/// A contour drawn through the probed point.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProbeContour {
+    Range,
+    Doppler,
+}
+
+impl ProbeContour {
+    const ALL: [Self; 2] = [Self::Range, Self::Doppler];
+
+    fn color(self) -> egui::Color32 {
+        match self {
+            Self::Range => RANGE_COLOR,
+            Self::Doppler => DOPPLER_COLOR,
+        }
+    }
+}
+
+/// Iso-range and iso-Doppler contours passing exactly through a point of the
+/// iso-range/Doppler plane, Ctrl + clicked in the 3D view, labelled with
+/// their bistatic range and Doppler frequency.
+#[derive(Resource)]
+pub struct IsoProbeWidget {
+    pub is_enabled: bool,
+    /// Probed point (East, North) in meters, in the ENU ground plane
+    pub point_m: Option<DVec2>,
+    /// Bistatic range (m) and Doppler frequency (Hz) at the probed point,
+    /// `None` while the point is outside the plane
+    values: Option<(f64, f64)>,
+    /// Height of the probed contours above the ground plane in meters
+    lift_m: f64,
+}
+
+impl Default for IsoProbeWidget {
+    fn default() -> Self {
+        Self {
+            is_enabled: false,
+            point_m: None,
+            values: None,
+            lift_m: 0.0,
+        }
+    }
+}
+
+fn spawn_probe_contours(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    for contour in ProbeContour::ALL {
+        let [r, g, b, _] = contour.color().to_array();
+        commands.spawn((
+            Mesh3d(meshes.add(LineList { lines: vec![] })),
+            MeshMaterial3d(materials.add(StandardMaterial {
+                base_color: Color::srgb_u8(r, g, b),
+                unlit: true,
+                ..default()
+            })),
+            Transform::IDENTITY,
+            Visibility::Hidden,
+            contour,
+            Name::new(format!("{contour:?} probe contour")),
+        ));
+    }
+}
+
+/// Gets the segments of the contours in World frame (Y-up), at `lift_m`
+/// above the ground plane.
+fn contour_lines((_, lines): &LevelContours, lift_m: f64) -> Vec<(Vec3, Vec3)> {
+    lines.iter()
+        .flat_map(|line| line.windows(2).map(|segment| (enu_to_world(segment[0], lift_m), enu_to_world(segment[1], lift_m))))
+        .collect()
+}
+
+/// Redraws the probed contours when the point or the plane changes.
+fn update_probe_contours(
+    mut iso_probe_widget: ResMut<IsoProbeWidget>,
+    iso_range_doppler_plane_state: Res<IsoRangeDopplerPlaneState>,
+    tx_antenna_beam_footprint_state: Res<TxAntennaBeamFootprintState>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut probe_contour_q: Query<(&ProbeContour, &Mesh3d, &mut Visibility)>,
+) -> Result {
+    if !iso_probe_widget.is_changed() && !iso_range_doppler_plane_state.is_changed() {
+        return Ok(());
+    }
+    // Written without change detection, which triggers the update
+    let widget = iso_probe_widget.bypass_change_detection();
+    let contours = widget.point_m
+        .filter(|_| widget.is_enabled)
+        .and_then(|point_m| iso_range_doppler_plane_state.probe_contours_enu(point_m));
+    widget.values = contours.as_ref().map(|(range, doppler)| (range.0, doppler.0));
+    // Above the footprints, as the ground tracks
+    widget.lift_m = 2.0 * tx_antenna_beam_footprint_state.inner.lift_m;
+
+    for (probe_contour, mesh, visibility) in probe_contour_q.iter_mut() {
+        let lines = contours.as_ref().map(|(range, doppler)| {
+            let level_contours = match probe_contour {
+                ProbeContour::Range => range,
+                ProbeContour::Doppler => doppler,
+            };
+            LineList { lines: contour_lines(level_contours, widget.lift_m) }
+        });
+        super::display::replace_line_mesh(&mut meshes, mesh, visibility, lines)?;
+    }
+
+    Ok(())
+}
+
+fn meters_text(value_m: f64) -> String {
+    if value_m >= 1e3 {
+        format!("{:.3} km", value_m * 1e-3)
+    } else {
+        format!("{:.3} m", value_m)
+    }
+}
+
+fn hertz_text(value_hz: f64) -> String {
+    if value_hz.abs() >= 1e3 {
+        format!("{:.3} kHz", value_hz * 1e-3)
+    } else {
+        format!("{:.3} Hz", value_hz)
+    }
+}
+
+/// Probes the point under a Ctrl + click in the 3D view, and paints the
+/// values next to the probed point, under the windows and within the 3D view.
+fn iso_probe_ui_system(
+    mut contexts: EguiContexts,
+    mut iso_probe_widget: ResMut<IsoProbeWidget>,
+    side_panel_rects: Res<SidePanelRects>,
+    camera_q: Single<(&Camera, &GlobalTransform), With<PanOrbitCamera>>,
+) -> Result {
+    let ctx = contexts.ctx_mut()?;
+    let (camera, camera_transform) = *camera_q;
+
+    let iso_probe_window = egui::Window::new("Iso-Level Probe")
+        .resizable(false)
+        .constrain(true)
+        .collapsible(true)
+        .title_bar(true)
+        .enabled(true)
+        .default_open(false)
+        .default_pos(egui::pos2(320.0, 960.0));
+    iso_probe_window.show(ctx, |ui| {
+        iso_probe_ui(ui, &mut iso_probe_widget);
+    });
+
+    if !iso_probe_widget.is_enabled {
+        return Ok(());
+    }
+    let view_rect = side_panel_rects.view_rect(ctx);
+    // Ctrl + click in the 3D view, not on a window
+    let click_position = ctx.input(|i| {
+        i.pointer.interact_pos().filter(|_| i.pointer.primary_clicked() && i.modifiers.command)
+    });
+    if let Some(position) = click_position
+        && view_rect.contains(position)
+        && ctx.layer_id_at(position).is_none_or(|layer_id| layer_id.order == egui::Order::Background)
+        && let Ok(ray) = camera.viewport_to_world(camera_transform, ctx.zoom_factor() * Vec2::new(position.x, position.y))
+        && let Some(distance) = ray.intersect_plane(Vec3::ZERO, InfinitePlane3d::new(Vec3::Y))
+    {
+        // World frame (Y-up) to ENU ground plane
+        let point = ray.get_point(distance);
+        iso_probe_widget.point_m = Some(DVec2::new(point.z as f64, point.x as f64));
+    }
+
+    let (Some(point_m), Some((range_m, doppler_hz))) = (iso_probe_widget.point_m, iso_probe_widget.values) else {
+        return Ok(());
+    };
+    let point = enu_to_world(point_m, iso_probe_widget.lift_m);
+    // A point behind the camera has no viewport position
+    let Ok(position) = camera.world_to_viewport(camera_transform, point) else {
+        return Ok(());
+    };
+    // From logical pixels to egui points
+    let position = position / ctx.zoom_factor();
+    let position = egui::pos2(position.x, position.y);
+    let painter = ctx
+        .layer_painter(egui::LayerId::new(egui::Order::Background, egui::Id::new("iso_probe")))
+        .with_clip_rect(view_rect);
+    painter.circle_filled(position, MARKER_RADIUS, TEXT_COLOR);
+    let labels = [
+        (format!("R {}", meters_text(range_m)), ProbeContour::Range.color()),
+        (format!("fD {}", hertz_text(doppler_hz)), ProbeContour::Doppler.color()),
+    ];
+    for ((text, color), dy) in labels.into_iter().zip([-8.0, 8.0]) {
+        painter.text(
+            position + egui::vec2(2.0 * MARKER_RADIUS, dy),
+            egui::Align2::LEFT_CENTER,
+            text,
+            egui::FontId::monospace(12.0),
+            color,
+        );
+    }
+
+    Ok(())
+}
+
+fn iso_probe_ui(ui: &mut egui::Ui, widget: &mut IsoProbeWidget) {
+    let hover_text = egui::RichText::new("Ctrl + click a point of the iso-range/Doppler plane in the 3D view to draw\nthe iso-range (yellow) and iso-Doppler (magenta) contours passing through it")
+        .color(TEXT_COLOR)
+        .monospace();
+    ui.checkbox(&mut widget.is_enabled, "Probe").on_hover_text(hover_text);
+    ui.add_enabled_ui(widget.is_enabled, |ui| {
+        egui::Grid::new("iso_probe_grid")
+            .num_columns(2)
+            .striped(false)
+            .spacing([20.0, 5.0])
+            .show(ui, |ui| {
+                let (point_text, range_text, doppler_text) = match (widget.point_m, widget.values) {
+                    (Some(point_m), Some((range_m, doppler_hz))) => (
+                        format!("E {:.3} m, N {:.3} m", point_m.x, point_m.y),
+                        meters_text(range_m),
+                        hertz_text(doppler_hz),
+                    ),
+                    (Some(_), None) => ("Outside the plane".to_string(), "-".to_string(), "-".to_string()),
+                    (None, _) => ("-".to_string(), "-".to_string(), "-".to_string()),
+                };
+                ui.label("Point:");
+                ui.label(point_text);
+                ui.end_row();
+                ui.label("Bistatic range:");
+                ui.label(egui::RichText::new(range_text).color(RANGE_COLOR));
+                ui.end_row();
+                ui.label("Doppler frequency:");
+                ui.label(egui::RichText::new(doppler_text).color(DOPPLER_COLOR));
+                ui.end_row();
+            });
+        if ui.add_enabled(widget.point_m.is_some(), egui::Button::new("Clear")).on_hover_text("Clears the probed point").clicked() {
+            widget.point_m = None;
+        }
+    });
+}
@@ -0,0 +1,157 @@
+use bevy::{math::DVec2, prelude::*};
+use bevy_egui::{egui, EguiContexts, EguiPrimaryContextPass};
+
+use crate::{
+    bsar::{SINC_WIDTH_AT_HALF_POWER, SPEED_OF_LIGHT_IN_VACUUM},
+    entities::{ContourSpacing, IsoContours},
+    scene::{BsarInfosState, TxCarrierState},
+};
+
+const TEXT_COLOR: egui::Color32 = egui::Color32::from_rgb(200, 200, 200);
+const ERROR_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 120, 120);
+const OK_COLOR: egui::Color32 = egui::Color32::from_rgb(120, 220, 120);
+
+pub struct IsoSpacingPlugin;
+
+impl Plugin for IsoSpacingPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<IsoSpacingWidget>()
+            .add_systems(EguiPrimaryContextPass, iso_spacing_ui_system);
+    }
+}
+
+/// Self-consistency check of the resolution formulas and of the iso-range and
+/// iso-Doppler contours: the ground resolutions derived from the local
+/// spacing of the drawn contours at the scene center.
+#[derive(Resource)]
+pub struct IsoSpacingWidget {
+    /// Largest relative deviation from the predicted resolutions in %
+    pub tolerance_percent: f64,
+}
+
+impl Default for IsoSpacingWidget {
+    fn default() -> Self {
+        Self { tolerance_percent: 5.0 }
+    }
+}
+
+/// A resolution derived from the contours and the predicted one.
+struct ResolutionCheck {
+    spacing: ContourSpacing,
+    measured_m: f64,
+    predicted_m: f64,
+}
+
+impl ResolutionCheck {
+    /// Relative deviation of the measured resolution in %
+    fn deviation_percent(&self) -> f64 {
+        100.0 * (self.measured_m / self.predicted_m - 1.0)
+    }
+}
+
+fn iso_spacing_ui_system(
+    mut contexts: EguiContexts,
+    mut iso_spacing_widget: ResMut<IsoSpacingWidget>,
+    iso_contours: Res<IsoContours>,
+    bsar_infos_state: Res<BsarInfosState>,
+    tx_carrier_state: Res<TxCarrierState>,
+) -> Result {
+    let ctx = contexts.ctx_mut()?;
+
+    let iso_spacing_window = egui::Window::new("Iso-line Spacing")
+        .resizable(false)
+        .constrain(true)
+        .collapsible(true)
+        .title_bar(true)
+        .enabled(true)
+        .default_open(false)
+        .default_pos(egui::pos2(320.0, 1480.0));
+    // Measured only while the window is open
+    iso_spacing_window.show(ctx, |ui| {
+        iso_spacing_ui(ui, &mut iso_spacing_widget, &iso_contours, &bsar_infos_state, &tx_carrier_state);
+    });
+
+    Ok(())
+}
+
+fn iso_spacing_ui(
+    ui: &mut egui::Ui,
+    widget: &mut IsoSpacingWidget,
+    iso_contours: &IsoContours,
+    bsar_infos_state: &BsarInfosState,
+    tx_carrier_state: &TxCarrierState,
+) {
+    let bsar_infos = &bsar_infos_state.inner;
+    let bandwidth_hz = tx_carrier_state.bandwidth_mhz * 1e6;
+    // Bistatic range and Doppler gradients along their own direction, at the scene center
+    let range_check = bsar_infos.ground_range_direction()
+        .and_then(|direction| IsoContours::local_spacing(&iso_contours.iso_range, DVec2::ZERO, direction.truncate()))
+        .map(|spacing| ResolutionCheck {
+            spacing,
+            measured_m: SINC_WIDTH_AT_HALF_POWER * SPEED_OF_LIGHT_IN_VACUUM / (bandwidth_hz * spacing.gradient().abs()),
+            predicted_m: bsar_infos.ground_range_resolution_m,
+        });
+    let lateral_check = bsar_infos.ground_lateral_direction()
+        .and_then(|direction| IsoContours::local_spacing(&iso_contours.iso_doppler, DVec2::ZERO, direction.truncate()))
+        .map(|spacing| ResolutionCheck {
+            spacing,
+            measured_m: SINC_WIDTH_AT_HALF_POWER / (bsar_infos.integration_time_s * spacing.gradient().abs()),
+            predicted_m: bsar_infos.ground_lateral_resolution_m,
+        });
+
+    let hover_text = egui::RichText::new(
+        "Ground resolutions derived from the spacing of the drawn contours across the scene center,\n\
+         along the ground range and lateral directions, against the ones of the resolution formulas"
+    )
+        .color(TEXT_COLOR)
+        .monospace();
+    ui.label(egui::RichText::new("Contour spacing at the scene center").strong()).on_hover_text(hover_text);
+    egui::Grid::new("iso_spacing_grid")
+        .num_columns(2)
+        .striped(false)
+        .spacing([20.0, 5.0])
+        .show(ui, |ui| {
+            let families = [
+                ("Iso-range", "Ground range", "m", range_check),
+                ("Iso-Doppler", "Ground lateral", "Hz", lateral_check),
+            ];
+            for (family, resolution, unit, check) in families {
+                ui.label(format!("{family} spacing: "));
+                let Some(check) = check else {
+                    ui.label(egui::RichText::new("⚠ No contours across the scene center").color(ERROR_COLOR));
+                    ui.end_row();
+                    continue;
+                };
+                ui.label(format!("{:.2} m for {:.3} {unit}", check.spacing.spacing_m, check.spacing.level_step.abs()));
+                ui.end_row();
+
+                ui.label(format!("{resolution} resolution: "));
+                ui.label(format!("{:.3} m (formula {:.3} m)", check.measured_m, check.predicted_m));
+                ui.end_row();
+
+                ui.label("Deviation: ");
+                let deviation_percent = check.deviation_percent();
+                if deviation_percent.abs() <= widget.tolerance_percent {
+                    ui.label(egui::RichText::new(format!("{deviation_percent:+.2} %")).color(OK_COLOR));
+                } else {
+                    ui.label(egui::RichText::new(format!("⚠ {deviation_percent:+.2} %")).color(ERROR_COLOR));
+                }
+                ui.end_row();
+            }
+
+            let hover_text = egui::RichText::new("Largest deviation of the measured resolutions from the formulas")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Tolerance: ").on_hover_text(hover_text);
+            ui.add(egui::DragValue::new(&mut widget.tolerance_percent).range(0.1..=50.0).speed(0.1).suffix(" %"));
+            ui.end_row();
+        });
+    let hover_text = egui::RichText::new(
+        "The contours are interpolated between the grid samples of the plane: zoom the plane\n\
+         window on the scene center to reduce the curvature of the fields between two contours"
+    )
+        .color(TEXT_COLOR)
+        .monospace();
+    ui.label(egui::RichText::new("Contours of the iso-range/Doppler plane").color(TEXT_COLOR)).on_hover_text(hover_text);
+}
@@ -0,0 +1,119 @@
+use bevy::{math::DVec2, prelude::*};
+use bevy_egui::{egui, EguiContexts, EguiPrimaryContextPass};
+
+use crate::{
+    bsar::{kspace::{kspace_support, KSpaceSupport}, BsarInfos},
+    scene::{BsarInfosState, RxCarrierState, TxCarrierState},
+};
+
+const TEXT_COLOR: egui::Color32 = egui::Color32::from_rgb(200, 200, 200);
+/// Colors of the extents, as the iso-range and iso-Doppler contours they cross
+const RANGE_EXTENT_COLOR: egui::Color32 = egui::Color32::from_rgb(31, 119, 180);
+const LATERAL_EXTENT_COLOR: egui::Color32 = egui::Color32::from_rgb(214, 39, 40);
+const SUPPORT_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 153, 0);
+/// Samples of each edge of the support boundary
+const EDGE_SAMPLES: usize = 64;
+
+pub struct KSpacePlugin;
+
+impl Plugin for KSpacePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(EguiPrimaryContextPass, kspace_ui_system);
+    }
+}
+
+fn kspace_ui_system(
+    mut contexts: EguiContexts,
+    bsar_infos_state: Res<BsarInfosState>,
+    tx_carrier_state: Res<TxCarrierState>,
+    rx_carrier_state: Res<RxCarrierState>,
+) -> Result {
+    let ctx = contexts.ctx_mut()?;
+
+    let kspace_window = egui::Window::new("k-Space Support")
+        .resizable(false)
+        .constrain(true)
+        .collapsible(true)
+        .title_bar(true)
+        .enabled(true)
+        .default_open(false)
+        .default_pos(egui::pos2(320.0, 1200.0));
+    // Computed only while the window is open
+    kspace_window.show(ctx, |ui| {
+        let bsar_infos = &bsar_infos_state.inner;
+        let geometry = super::ambiguity::ambiguity_geometry(&tx_carrier_state, &rx_carrier_state, bsar_infos);
+        match kspace_support(&geometry, EDGE_SAMPLES) {
+            Some(support) => kspace_ui(ui, &support, bsar_infos),
+            None => {
+                ui.label(egui::RichText::new("No ground wavenumber: the geometry is degenerate").color(TEXT_COLOR));
+            }
+        }
+    });
+
+    Ok(())
+}
+
+fn kspace_ui(ui: &mut egui::Ui, support: &KSpaceSupport, bsar_infos: &BsarInfos) {
+    let hover_text = egui::RichText::new(
+        "Ground wavenumbers 2π.f/c.βg(t) measured over the bandwidth and the integration time,\n\
+         relative to the center one: the resolutions are k.2π/ΔK over the extents of the support"
+    )
+        .color(TEXT_COLOR)
+        .monospace();
+    ui.label(egui::RichText::new(format!(
+        "Center wavenumber: {:.2} rad/m ({:.1}° from the East)",
+        support.center_radpm.length(),
+        support.center_radpm.y.atan2(support.center_radpm.x).to_degrees()
+    )).color(TEXT_COLOR)).on_hover_text(hover_text);
+    egui::Grid::new("kspace_grid")
+        .num_columns(3)
+        .striped(false)
+        .spacing([20.0, 5.0])
+        .show(ui, |ui| {
+            ui.label("");
+            ui.label(egui::RichText::new("Extent").strong());
+            ui.label(egui::RichText::new("Resolution").strong());
+            ui.end_row();
+
+            let extents = [
+                ("Ground range:", support.range_extent_radpm, support.ground_range_resolution_m(), bsar_infos.ground_range_resolution_m),
+                ("Ground lateral:", support.lateral_extent_radpm, support.ground_lateral_resolution_m(), bsar_infos.ground_lateral_resolution_m),
+            ];
+            for (name, extent_radpm, resolution_m, formula_m) in extents {
+                ui.label(name);
+                ui.label(format!("{extent_radpm:.3} rad/m"));
+                ui.label(format!("{resolution_m:.3} m (formula {formula_m:.3} m)"));
+                ui.end_row();
+            }
+        });
+
+    let boundary: Vec<[f64; 2]> = support.boundary_radpm.iter().map(|k| [k.x, k.y]).collect();
+    // Extents through the center, along the bandwidth and the integration time edges
+    let edge = EDGE_SAMPLES - 1;
+    let midpoint = |a: usize, b: usize| {
+        let (a, b) = (support.boundary_radpm[a], support.boundary_radpm[b]);
+        0.5 * (a + b)
+    };
+    let segment = |from: DVec2, to: DVec2| vec![[from.x, from.y], [to.x, to.y]];
+    // Middles of the edges: lowest frequency, end of the integration, highest frequency, start
+    let (low, end, high, start) = (midpoint(0, edge), midpoint(edge, 2 * edge), midpoint(2 * edge, 3 * edge), midpoint(3 * edge, 4 * edge));
+    egui_plot::Plot::new("kspace_plot")
+        .width(320.0)
+        .height(320.0)
+        .data_aspect(1.0)
+        .x_axis_label("ΔK East [rad/m]")
+        .y_axis_label("ΔK North [rad/m]")
+        .legend(egui_plot::Legend::default().follow_insertion_order(true))
+        .allow_drag(false)
+        .allow_zoom(false)
+        .allow_scroll(false)
+        .show(ui, |plot_ui| {
+            plot_ui.polygon(
+                egui_plot::Polygon::new("Support", boundary)
+                    .fill_color(SUPPORT_COLOR.gamma_multiply(0.3))
+                    .stroke(egui::Stroke::new(1.5, SUPPORT_COLOR))
+            );
+            plot_ui.line(egui_plot::Line::new("Bandwidth", segment(low, high)).color(RANGE_EXTENT_COLOR).width(1.5));
+            plot_ui.line(egui_plot::Line::new("Integration time", segment(start, end)).color(LATERAL_EXTENT_COLOR).width(1.5));
+        });
+}
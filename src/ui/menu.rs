@@ -1,6 +1,8 @@
 use bevy::prelude::*;
 use bevy_egui::egui;
 
+use crate::formation::LeverArm;
+
 const TEXT_COLOR: egui::Color32 = egui::Color32::from_rgb(200, 200, 200);
 
 const TX_MENU_OPEN_ICON: egui::ImageSource<'_> = egui::include_image!("../../assets/menu-tx-open-48.png");
@@ -46,20 +48,59 @@ pub enum CameraFocus {
     Rx,
 }
 
+/// How the Rx geometry relates to the Tx one.
+///
+/// In [`GeometryMode::Monostatic`] the Rx is the Tx antenna; in
+/// [`GeometryMode::QuasiMonostatic`] it flies with the Tx at a fixed lever
+/// arm, pointing at the scene center. Both derive the Rx
+/// carrier and antenna from the Tx ones, only [`GeometryMode::Bistatic`]
+/// leaves them to the Rx panel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GeometryMode {
+    #[default]
+    Bistatic,
+    Monostatic,
+    QuasiMonostatic,
+}
+
+impl GeometryMode {
+    pub const ALL: [Self; 3] = [Self::Bistatic, Self::Monostatic, Self::QuasiMonostatic];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Bistatic => "Bistatic",
+            Self::Monostatic => "Monostatic",
+            Self::QuasiMonostatic => "Quasi-monostatic",
+        }
+    }
+}
+
 #[derive(Resource)]
-#[derive(Default)]
 pub struct MenuWidget {
     pub is_tx_panel_opened: bool,
     pub is_rx_panel_opened: bool,
-    pub is_monostatic: bool,
-    pub was_monostatic: bool,
-    pub force_rx_system_update: bool,
+    pub geometry_mode: GeometryMode,
+    /// Offset of the Rx from the Tx in quasi-monostatic mode
+    pub lever_arm: LeverArm,
     pub camera_focus: CameraFocus,
     /// One-shot request consumed by the camera system: restore the initial view.
     pub reset_view_requested: bool,
     pub is_gaf_opened: bool,
 }
 
+impl Default for MenuWidget {
+    fn default() -> Self {
+        Self {
+            is_tx_panel_opened: false,
+            is_rx_panel_opened: false,
+            geometry_mode: GeometryMode::default(),
+            lever_arm: LeverArm::default(),
+            camera_focus: CameraFocus::default(),
+            reset_view_requested: false,
+            is_gaf_opened: false,
+        }
+    }
+}
 
 impl MenuWidget {
     pub fn ui(&mut self, ui: &mut egui::Ui) {
@@ -70,7 +111,8 @@ impl MenuWidget {
 
         let tx_menu_icon = if self.is_tx_panel_opened { TX_MENU_CLOSE_ICON } else { TX_MENU_OPEN_ICON };
         let rx_menu_icon = if self.is_rx_panel_opened { RX_MENU_CLOSE_ICON } else { RX_MENU_OPEN_ICON };
-        let menu_bist_mono_icon = if self.is_monostatic { MENU_MONO_ICON } else { MENU_BIST_ICON };
+        let is_monostatic = self.geometry_mode != GeometryMode::Bistatic;
+        let menu_bist_mono_icon = if is_monostatic { MENU_MONO_ICON } else { MENU_BIST_ICON };
         let (
             menu_origin_camera_focus_icon,
             menu_tx_camera_focus_icon,
@@ -122,16 +164,16 @@ impl MenuWidget {
                         };
                     // Monostatic button
                     let monostatic_button = egui::Button::selectable(
-                        self.is_monostatic,
+                        is_monostatic,
                         menu_bist_mono_icon
                     );
-                    let hover_text = egui::RichText::new("Switch between Bistatic/Monostatic mode")
+                    let hover_text = egui::RichText::new("Switch between Bistatic/Monostatic mode\n(the quasi-monostatic mode is set in the Receiver settings panel)")
                             .color(TEXT_COLOR)
                             .monospace();
                     if ui.add(monostatic_button)
                         .on_hover_text(hover_text)
                         .clicked() {
-                            self.is_monostatic = !self.is_monostatic;
+                            self.geometry_mode = if is_monostatic { GeometryMode::Bistatic } else { GeometryMode::Monostatic };
                         };
 
                     // CAMERA FOCUS
@@ -0,0 +1,286 @@
+use bevy::{math::{DVec2, DVec3}, prelude::*};
+use bevy_egui::{egui, EguiContexts, EguiPrimaryContextPass};
+
+use crate::{
+    bsar::SPEED_OF_LIGHT_IN_VACUUM,
+    entities::{enu_to_world, IsoRangeDopplerPlaneState, LevelContours, LineList},
+    expression,
+    moving_target::{MovingTarget, MovingTargetSignature},
+    scene::{RxCarrierState, TxAntennaBeamFootprintState, TxCarrierState},
+    ui::UpdateStage,
+};
+
+const TEXT_COLOR: egui::Color32 = egui::Color32::from_rgb(200, 200, 200);
+/// Colors of the target markers and of the iso-Doppler contours through the
+/// target location (clutter) and through its apparent position (target)
+const TARGET_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 140, 0);
+const CLUTTER_DOPPLER_COLOR: egui::Color32 = egui::Color32::from_rgb(0, 220, 220);
+const TARGET_DOPPLER_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 60, 60);
+/// Size of the target markers relative to the iso-range/Doppler plane side
+const MARKER_SIZE: f64 = 0.01;
+
+pub struct MovingTargetPlugin;
+
+impl Plugin for MovingTargetPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<MovingTargetWidget>()
+            .add_systems(Startup, spawn_moving_target_overlays)
+            // The plane is updated by the update systems
+            .add_systems(
+                PostUpdate,
+                update_moving_target_overlays.in_set(UpdateStage::RenderAssets)
+            )
+            .add_systems(EguiPrimaryContextPass, moving_target_ui_system);
+    }
+}
+
+/// An overlay drawn for the moving target.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MovingTargetOverlay {
+    /// Target and apparent position markers, joined by the displacement
+    Markers,
+    ClutterDoppler,
+    TargetDoppler,
+}
+
+impl MovingTargetOverlay {
+    const ALL: [Self; 3] = [Self::Markers, Self::ClutterDoppler, Self::TargetDoppler];
+
+    fn color(self) -> egui::Color32 {
+        match self {
+            Self::Markers => TARGET_COLOR,
+            Self::ClutterDoppler => CLUTTER_DOPPLER_COLOR,
+            Self::TargetDoppler => TARGET_DOPPLER_COLOR,
+        }
+    }
+}
+
+/// A ground moving target, with its Doppler shift from the clutter iso-Doppler
+/// at its location and its apparent displacement in the image.
+#[derive(Resource)]
+pub struct MovingTargetWidget {
+    pub is_enabled: bool,
+    pub target: MovingTarget,
+    /// `None` when the clutter Doppler frequency does not vary along the
+    /// iso-range contour of the target
+    signature: Option<MovingTargetSignature>,
+}
+
+impl Default for MovingTargetWidget {
+    fn default() -> Self {
+        Self {
+            is_enabled: false,
+            target: MovingTarget::default(),
+            signature: None,
+        }
+    }
+}
+
+impl MovingTargetWidget {
+    /// Gets the signature of the target when drawn.
+    pub fn signature(&self) -> Option<&MovingTargetSignature> {
+        self.signature.as_ref().filter(|_| self.is_enabled)
+    }
+}
+
+fn spawn_moving_target_overlays(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    for overlay in MovingTargetOverlay::ALL {
+        let [r, g, b, _] = overlay.color().to_array();
+        commands.spawn((
+            Mesh3d(meshes.add(LineList { lines: vec![] })),
+            MeshMaterial3d(materials.add(StandardMaterial {
+                base_color: Color::srgb_u8(r, g, b),
+                unlit: true,
+                ..default()
+            })),
+            Transform::IDENTITY,
+            Visibility::Hidden,
+            overlay,
+            Name::new(format!("Moving target {overlay:?} overlay")),
+        ));
+    }
+}
+
+/// Gets the segments of the contours in World frame (Y-up), at `lift_m`
+/// above the ground plane.
+fn contour_lines((_, lines): &LevelContours, lift_m: f64) -> Vec<(Vec3, Vec3)> {
+    lines.iter()
+        .flat_map(|line| line.windows(2).map(|segment| (enu_to_world(segment[0], lift_m), enu_to_world(segment[1], lift_m))))
+        .collect()
+}
+
+/// Gets the target and apparent position crosses, the target heading and the
+/// displacement in World frame (Y-up), at `lift_m` above the ground plane.
+fn marker_lines(target: &MovingTarget, signature: &MovingTargetSignature, size_m: f64, lift_m: f64) -> Vec<(Vec3, Vec3)> {
+    let segment = |from: DVec2, to: DVec2| (enu_to_world(from, lift_m), enu_to_world(to, lift_m));
+    let (sin, cos) = target.heading_deg.to_radians().sin_cos();
+    let mut lines = vec![
+        segment(target.position_m, target.position_m + 2.0 * size_m * DVec2::new(sin, cos)),
+        segment(target.position_m, signature.apparent_position_m),
+    ];
+    for center in [target.position_m, signature.apparent_position_m] {
+        lines.push(segment(center - size_m * DVec2::ONE, center + size_m * DVec2::ONE));
+        lines.push(segment(center - size_m * DVec2::new(1.0, -1.0), center + size_m * DVec2::new(1.0, -1.0)));
+    }
+    lines
+}
+
+/// Computes the target signature and redraws its overlays when the target or
+/// the plane changes.
+fn update_moving_target_overlays(
+    mut moving_target_widget: ResMut<MovingTargetWidget>,
+    iso_range_doppler_plane_state: Res<IsoRangeDopplerPlaneState>,
+    tx_carrier_state: Res<TxCarrierState>,
+    rx_carrier_state: Res<RxCarrierState>,
+    tx_antenna_beam_footprint_state: Res<TxAntennaBeamFootprintState>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut overlay_q: Query<(&MovingTargetOverlay, &Mesh3d, &mut Visibility)>,
+) -> Result {
+    if !moving_target_widget.is_changed() && !iso_range_doppler_plane_state.is_changed() {
+        return Ok(());
+    }
+    // Written without change detection, which triggers the update
+    let widget = moving_target_widget.bypass_change_detection();
+    let (tx, rx) = (&tx_carrier_state.inner, &rx_carrier_state.inner);
+    // As the BSAR infos: a fixed receiver does not contribute to the Doppler frequency
+    let vrx = if rx_carrier_state.is_stationary { DVec3::ZERO } else { rx.velocity_vector_mps };
+    let lem = SPEED_OF_LIGHT_IN_VACUUM / (tx_carrier_state.center_frequency_ghz * 1e9);
+    widget.signature = widget.target.signature(lem, &tx.position_m, &tx.velocity_vector_mps, &rx.position_m, &vrx);
+    // Above the footprints, as the ground tracks
+    let lift_m = 2.0 * tx_antenna_beam_footprint_state.inner.lift_m;
+    let (_, extent_m) = iso_range_doppler_plane_state.window_m();
+
+    for (overlay, mesh, visibility) in overlay_q.iter_mut() {
+        let lines = widget.signature
+            .filter(|_| widget.is_enabled)
+            .and_then(|signature| match overlay {
+                MovingTargetOverlay::Markers => Some(marker_lines(&widget.target, &signature, MARKER_SIZE * extent_m, lift_m)),
+                MovingTargetOverlay::ClutterDoppler => iso_range_doppler_plane_state
+                    .iso_doppler_contours_at_enu(signature.clutter_doppler_hz)
+                    .map(|contours| contour_lines(&contours, lift_m)),
+                MovingTargetOverlay::TargetDoppler => iso_range_doppler_plane_state
+                    .iso_doppler_contours_at_enu(signature.target_doppler_hz())
+                    .map(|contours| contour_lines(&contours, lift_m)),
+            });
+        super::display::replace_line_mesh(&mut meshes, mesh, visibility, lines.map(|lines| LineList { lines }))?;
+    }
+
+    Ok(())
+}
+
+fn moving_target_ui_system(
+    mut contexts: EguiContexts,
+    mut moving_target_widget: ResMut<MovingTargetWidget>,
+) -> Result {
+    let ctx = contexts.ctx_mut()?;
+
+    let moving_target_window = egui::Window::new("Moving Target")
+        .resizable(false)
+        .constrain(true)
+        .collapsible(true)
+        .title_bar(true)
+        .enabled(true)
+        .default_open(false)
+        .default_pos(egui::pos2(320.0, 1160.0));
+    moving_target_window.show(ctx, |ui| {
+        // Written without change detection, the overlays being redrawn on changes only
+        if moving_target_ui(ui, moving_target_widget.bypass_change_detection()) {
+            moving_target_widget.set_changed();
+        }
+    });
+
+    Ok(())
+}
+
+fn moving_target_ui(ui: &mut egui::Ui, widget: &mut MovingTargetWidget) -> bool {
+    let hover_text = egui::RichText::new("Draws a target moving on the ground (orange), the clutter iso-Doppler at its\nlocation (cyan) and the iso-Doppler of the target (red), along which it appears\ndisplaced in the image")
+        .color(TEXT_COLOR)
+        .monospace();
+    let mut changed = ui.checkbox(&mut widget.is_enabled, "Target").on_hover_text(hover_text).changed();
+    ui.add_enabled_ui(widget.is_enabled, |ui| {
+        egui::Grid::new("moving_target_settings_grid")
+            .num_columns(2)
+            .striped(false)
+            .spacing([20.0, 5.0])
+            .show(ui, |ui| {
+                let target = &mut widget.target;
+                for (label, value_m) in [("East:", &mut target.position_m.x), ("North:", &mut target.position_m.y)] {
+                    ui.label(label);
+                    changed |= ui.add(
+                        egui::DragValue::new(value_m)
+                            .custom_parser(expression::parse)
+                            .update_while_editing(false)
+                            .speed(1.0)
+                            .fixed_decimals(1)
+                            .suffix(" m")
+                    ).changed();
+                    ui.end_row();
+                }
+                ui.label("Speed:");
+                changed |= ui.add(
+                    egui::DragValue::new(&mut target.speed_mps)
+                        .custom_parser(expression::parse)
+                        .update_while_editing(false)
+                        .speed(0.1)
+                        .range(0.0..=100.0)
+                        .fixed_decimals(1)
+                        .suffix(" m/s")
+                ).changed();
+                ui.end_row();
+                ui.label("Heading:").on_hover_text("Clockwise from the North");
+                changed |= ui.add(
+                    egui::DragValue::new(&mut target.heading_deg)
+                        .custom_parser(expression::parse)
+                        .update_while_editing(false)
+                        .speed(1.0)
+                        .range(0.0..=360.0)
+                        .fixed_decimals(1)
+                        .suffix("°")
+                ).changed();
+                ui.end_row();
+            });
+        ui.separator();
+        egui::Grid::new("moving_target_grid")
+            .num_columns(2)
+            .striped(true)
+            .spacing([20.0, 5.0])
+            .show(ui, |ui| {
+                let signature = widget.signature.filter(|_| widget.is_enabled);
+                let hertz_text = |value_hz: Option<f64>| value_hz.map_or("-".to_string(), |value_hz| format!("{value_hz:.3} Hz"));
+                ui.label("Clutter Doppler:");
+                ui.label(egui::RichText::new(hertz_text(signature.map(|signature| signature.clutter_doppler_hz))).color(CLUTTER_DOPPLER_COLOR));
+                ui.end_row();
+                ui.label("Target Doppler:");
+                ui.label(egui::RichText::new(hertz_text(signature.map(|signature| signature.target_doppler_hz()))).color(TARGET_DOPPLER_COLOR));
+                ui.end_row();
+                ui.label("Doppler shift:");
+                ui.label(hertz_text(signature.map(|signature| signature.doppler_shift_hz)));
+                ui.end_row();
+                let hover_text = egui::RichText::new("Half the bistatic range rate of the target, positive receding")
+                    .color(TEXT_COLOR)
+                    .monospace();
+                ui.label("Radial velocity:").on_hover_text(hover_text);
+                ui.label(signature.map_or("-".to_string(), |signature| format!("{:.3} m/s", signature.radial_velocity_mps)));
+                ui.end_row();
+                let hover_text = egui::RichText::new("Displacement of the target in the image, along its iso-range contour to the\nclutter point sharing its Doppler frequency")
+                    .color(TEXT_COLOR)
+                    .monospace();
+                ui.label("Azimuth displacement:").on_hover_text(hover_text);
+                ui.label(
+                    signature.map_or("-".to_string(), |signature| format!(
+                        "{:.1} m (E {:.1} m, N {:.1} m)",
+                        signature.apparent_position_m.distance(widget.target.position_m),
+                        signature.apparent_position_m.x,
+                        signature.apparent_position_m.y
+                    ))
+                );
+                ui.end_row();
+            });
+    });
+    changed
+}
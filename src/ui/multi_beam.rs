@@ -0,0 +1,232 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts, EguiPrimaryContextPass};
+
+use crate::{
+    entities::{antenna_beam_footprint_state_from_state, AntennaBeamFootprintState, LineStrip},
+    scene::{RxAntennaBeamFootprintState, RxAntennaBeamState, RxAntennaState, RxCarrierState},
+    steering::{steer, MultiBeam},
+    ui::{PhasedArrayWidget, SteeringWidget, UpdateStage},
+};
+
+const TEXT_COLOR: egui::Color32 = egui::Color32::from_rgb(200, 200, 200);
+const ERROR_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 120, 120);
+/// Color of the Rx beam footprints
+const BEAM_FOOTPRINT_COLOR: Srgba = Srgba::rgb(0.6, 1.0, 0.6);
+
+pub struct MultiBeamPlugin;
+
+impl Plugin for MultiBeamPlugin {
+    fn build(&self, app: &mut App) {
+        // The beams follow the receiver antenna once moved and steered
+        app
+            .init_resource::<MultiBeamWidget>()
+            .add_systems(
+                PostUpdate,
+                update_rx_beams
+                    .in_set(UpdateStage::Geometry)
+                    .after(super::rx_panel::update_rx)
+                    .after(super::steering::update_steered_beams)
+            )
+            .add_systems(EguiPrimaryContextPass, multi_beam_ui_system);
+    }
+}
+
+/// Simultaneous receiver beams formed from the Rx antenna (digital
+/// beamforming), at pointing offsets from its (steered) pointing.
+#[derive(Resource)]
+pub struct MultiBeamWidget {
+    pub is_enabled: bool,
+    pub table_text: String,
+    pub beams: Option<MultiBeam>,
+    /// Footprints of the beams at the current time, in order
+    pub beam_footprints: Vec<AntennaBeamFootprintState>,
+    /// Result of the last beams update
+    status: Option<Result<String, String>>,
+    footprints_need_update: bool,
+}
+
+impl Default for MultiBeamWidget {
+    fn default() -> Self {
+        Self {
+            is_enabled: false,
+            table_text: "# elevation [°] azimuth [°]\n-3 0\n0 0\n3 0\n".to_string(),
+            beams: None,
+            beam_footprints: Vec::new(),
+            status: None,
+            footprints_need_update: false,
+        }
+    }
+}
+
+impl MultiBeamWidget {
+    /// Gets the footprints of the Rx beams when enabled.
+    pub fn enabled_footprints(&self) -> Option<&[AntennaBeamFootprintState]> {
+        Some(self.beam_footprints.as_slice()).filter(|footprints| self.is_enabled && !footprints.is_empty())
+    }
+}
+
+/// Footprint of one Rx beam, by index.
+#[derive(Component, Clone, Copy, PartialEq, Eq)]
+pub struct RxBeamFootprint(pub usize);
+
+/// Gets the footprint edge, lifted above the footprints to stay visible over them.
+fn footprint_line(footprint: &AntennaBeamFootprintState) -> LineStrip {
+    let lift_m = 2.0 * footprint.lift_m as f32;
+    LineStrip {
+        points: footprint.points.iter()
+            .map(|point| Vec3::new(point.x as f32, lift_m, point.z as f32))
+            .collect()
+    }
+}
+
+/// Computes the Rx beam footprints and keeps one footprint entity per beam.
+pub(super) fn update_rx_beams(
+    mut commands: Commands,
+    mut multi_beam_widget: ResMut<MultiBeamWidget>,
+    steering_widget: Res<SteeringWidget>,
+    phased_array_widget: Res<PhasedArrayWidget>,
+    rx: (Res<RxCarrierState>, Res<RxAntennaState>, Res<RxAntennaBeamState>, Res<RxAntennaBeamFootprintState>),
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut rx_beam_footprint_q: Query<(Entity, &RxBeamFootprint, &Mesh3d, &mut Visibility)>,
+) -> Result {
+    let (rx_carrier_state, rx_antenna_state, rx_antenna_beam_state, rx_antenna_beam_footprint_state) = rx;
+    let widget = &mut *multi_beam_widget; // Borrows the fields separately
+    let beams = widget.beams.as_ref().filter(|_| widget.is_enabled);
+    let are_states_changed = rx_carrier_state.is_changed() || rx_antenna_state.is_changed()
+        || rx_antenna_beam_state.is_changed() || rx_antenna_beam_footprint_state.is_changed()
+        || steering_widget.is_changed() || phased_array_widget.is_changed();
+    if !(are_states_changed || widget.footprints_need_update) {
+        return Ok(());
+    }
+    widget.beam_footprints = match beams {
+        Some(beams) => {
+            // Offsets from the pointing of the active steering beam position, if any
+            let time_s = rx_carrier_state.inner.time_s;
+            let (elevation_scan_deg, azimuth_scan_deg) = steering_widget.rx.position_at(time_s)
+                .map_or((0.0, 0.0), |position| (position.elevation_offset_deg, position.azimuth_offset_deg));
+            let array = phased_array_widget.rx.enabled_array();
+            beams.offsets().iter()
+                .map(|&(elevation_offset_deg, azimuth_offset_deg)| {
+                    let (elevation_deg, azimuth_deg) = (elevation_scan_deg + elevation_offset_deg, azimuth_scan_deg + azimuth_offset_deg);
+                    antenna_beam_footprint_state_from_state(
+                        &rx_carrier_state.inner,
+                        &steer(&rx_antenna_state.inner, elevation_deg, azimuth_deg),
+                        &array.map_or_else(
+                            || rx_antenna_beam_state.inner.clone(),
+                            |array| array.beam_state(elevation_deg, azimuth_deg)
+                        ),
+                        rx_antenna_beam_footprint_state.inner.lift_m
+                    )
+                })
+                .collect()
+        }
+        None => Vec::new(),
+    };
+    widget.footprints_need_update = false;
+
+    if rx_beam_footprint_q.iter().count() == widget.beam_footprints.len() {
+        for (_, beam, mesh, visibility) in rx_beam_footprint_q.iter_mut() {
+            super::display::replace_line_mesh(&mut meshes, mesh, visibility, Some(footprint_line(&widget.beam_footprints[beam.0])))?;
+        }
+    } else {
+        for (entity, _, _, _) in rx_beam_footprint_q.iter() {
+            commands.entity(entity).despawn();
+        }
+        let material = materials.add(StandardMaterial {
+            base_color: BEAM_FOOTPRINT_COLOR.into(),
+            unlit: true,
+            ..default()
+        });
+        for (index, footprint) in widget.beam_footprints.iter().enumerate() {
+            commands.spawn((
+                Mesh3d(meshes.add(footprint_line(footprint))),
+                MeshMaterial3d(material.clone()),
+                Transform::IDENTITY,
+                RxBeamFootprint(index),
+                Name::new(format!("Rx beam {} footprint", index + 1)),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+fn multi_beam_ui_system(
+    mut contexts: EguiContexts,
+    mut multi_beam_widget: ResMut<MultiBeamWidget>,
+) -> Result {
+    let ctx = contexts.ctx_mut()?;
+
+    let multi_beam_window = egui::Window::new("Rx Multi-Beam")
+        .resizable(false)
+        .constrain(true)
+        .collapsible(true)
+        .title_bar(true)
+        .enabled(true)
+        .default_open(false)
+        .default_pos(egui::pos2(320.0, 640.0));
+    multi_beam_window.show(ctx, |ui| {
+        multi_beam_ui(ui, &mut multi_beam_widget);
+    });
+
+    Ok(())
+}
+
+fn multi_beam_ui(ui: &mut egui::Ui, widget: &mut MultiBeamWidget) {
+    let hover_text = egui::RichText::new("Forms several simultaneous beams from the receiver antenna (digital beamforming),\nat pointing offsets from its pointing; the coverage is the union of their footprints")
+        .color(TEXT_COLOR)
+        .monospace();
+    if ui.checkbox(&mut widget.is_enabled, "Rx beams").on_hover_text(hover_text).changed() {
+        widget.footprints_need_update = true;
+    }
+    if !widget.is_enabled {
+        return;
+    }
+    ui.add(
+        egui::TextEdit::multiline(&mut widget.table_text)
+            .id_salt("multi_beam_table")
+            .font(egui::TextStyle::Monospace)
+            .desired_rows(4)
+            .desired_width(280.0)
+    );
+    let hover_text = egui::RichText::new("Reads the beams: one \"elevation azimuth\" offsets pair in degrees per line")
+        .color(TEXT_COLOR)
+        .monospace();
+    if ui.button("Apply").on_hover_text(hover_text).clicked() {
+        widget.status = Some(MultiBeam::from_table(&widget.table_text)
+            .map(|beams| {
+                let status = format!("{} beams", beams.offsets().len());
+                widget.beams = Some(beams);
+                widget.footprints_need_update = true;
+                status
+            })
+        );
+    }
+    match &widget.status {
+        Some(Ok(status)) => { ui.label(egui::RichText::new(status).color(TEXT_COLOR)); }
+        Some(Err(error)) => { ui.label(egui::RichText::new(error).color(ERROR_COLOR)); }
+        None => {}
+    }
+
+    let Some(beams) = &widget.beams else {
+        return;
+    };
+    egui::Grid::new("multi_beam_grid")
+        .num_columns(4)
+        .striped(true)
+        .spacing([10.0, 5.0])
+        .show(ui, |ui| {
+            for header in ["Beam", "Offsets", "Incidences", "Swath"] {
+                ui.label(egui::RichText::new(header).strong());
+            }
+            ui.end_row();
+            for (index, (&(elevation_offset_deg, azimuth_offset_deg), footprint)) in beams.offsets().iter().zip(widget.beam_footprints.iter()).enumerate() {
+                ui.label(format!("{}", index + 1));
+                ui.label(format!("{elevation_offset_deg:+.2}° / {azimuth_offset_deg:+.2}°"));
+                ui.label(format!("{:.2}° - {:.2}°", footprint.loc_incidence_min_deg, footprint.loc_incidence_max_deg));
+                ui.label(format!("{:.1} m", footprint.ground_range_swath_m));
+                ui.end_row();
+            }
+        });
+}
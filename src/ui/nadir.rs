@@ -0,0 +1,208 @@
+use bevy::{math::DVec3, prelude::*};
+use bevy_egui::{egui, EguiContexts, EguiPrimaryContextPass};
+
+use crate::{
+    bsar::SPEED_OF_LIGHT_IN_VACUUM,
+    entities::{AntennaBeamFootprintState, LineList},
+    nadir::{bistatic_range_m, iso_range_curve_m, nadir_echo_bands, specular_point_m, NadirEchoBand},
+    scene::{BsarInfosState, RxAntennaBeamFootprintState, RxCarrierState, TxAntennaBeamFootprintState, TxCarrierState},
+    ui::UpdateStage,
+};
+
+const TEXT_COLOR: egui::Color32 = egui::Color32::from_rgb(200, 200, 200);
+const ERROR_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 120, 120);
+/// Color of the iso-range bands blinded by the nadir echo
+const NADIR_ECHO_BAND_COLOR: Srgba = Srgba::rgb(1.0, 0.6, 0.0);
+/// Points of the iso-range curves bounding the bands
+const ISO_RANGE_CURVE_POINTS: usize = 721;
+
+pub struct NadirPlugin;
+
+impl Plugin for NadirPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<NadirWidget>()
+            .add_systems(Startup, spawn_nadir_echo_overlay)
+            // The carriers and the footprints are updated by the update systems
+            .add_systems(
+                PostUpdate,
+                update_nadir_echo.in_set(UpdateStage::RenderAssets)
+            )
+            .add_systems(EguiPrimaryContextPass, nadir_ui_system);
+    }
+}
+
+/// Location of the nadir echo in the receive window, for the Tx PRF and the
+/// current geometry.
+#[derive(Resource, Default)]
+pub struct NadirWidget {
+    pub is_enabled: bool,
+    /// Bistatic range of the nadir (specular) echo
+    pub nadir_range_m: Option<f64>,
+    /// Iso-range bands of the scene blinded by the nadir echo
+    pub bands: Vec<NadirEchoBand>,
+}
+
+/// Iso-range bands blinded by the nadir echo, drawn over the footprint.
+#[derive(Component)]
+pub struct NadirEchoOverlay;
+
+fn spawn_nadir_echo_overlay(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.spawn((
+        Mesh3d(meshes.add(LineList { lines: vec![] })),
+        MeshMaterial3d(materials.add(StandardMaterial {
+            base_color: NADIR_ECHO_BAND_COLOR.into(),
+            unlit: true,
+            ..default()
+        })),
+        Transform::IDENTITY,
+        Visibility::Hidden,
+        NadirEchoOverlay,
+        Name::new("Nadir echo bands"),
+    ));
+}
+
+/// Checks whether `point_m` lies inside the footprint edge (ENU), on the ground plane.
+fn is_inside(footprint_m: &[DVec3], point_m: &DVec3) -> bool {
+    footprint_m.windows(2)
+        .filter(|segment| {
+            let (a, b) = (segment[0], segment[1]);
+            (a.y > point_m.y) != (b.y > point_m.y)
+                && point_m.x < a.x + (point_m.y - a.y) / (b.y - a.y) * (b.x - a.x)
+        })
+        .count() % 2 == 1
+}
+
+/// Computes the nadir echo bands and redraws them within the footprint.
+fn update_nadir_echo(
+    mut nadir_widget: ResMut<NadirWidget>,
+    bsar_infos_state: Res<BsarInfosState>,
+    tx: (Res<TxCarrierState>, Res<TxAntennaBeamFootprintState>),
+    rx: (Res<RxCarrierState>, Res<RxAntennaBeamFootprintState>),
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut overlay_q: Query<(&Mesh3d, &mut Visibility), With<NadirEchoOverlay>>,
+) -> Result {
+    let are_states_changed = nadir_widget.is_changed() || bsar_infos_state.is_changed()
+        || tx.0.is_changed() || tx.1.is_changed() || rx.0.is_changed() || rx.1.is_changed();
+    if !are_states_changed {
+        return Ok(());
+    }
+    // Written without change detection, which triggers the update
+    let widget = nadir_widget.bypass_change_detection();
+    let (tx_carrier_state, rx_carrier_state) = (&*tx.0, &rx.0.inner);
+    let (tx_position_m, rx_position_m) = (&tx_carrier_state.inner.position_m, &rx_carrier_state.position_m);
+    let bsar_infos = &bsar_infos_state.inner;
+    widget.nadir_range_m = specular_point_m(tx_position_m, rx_position_m)
+        .map(|point_m| bistatic_range_m(tx_position_m, rx_position_m, &point_m));
+    widget.bands = match widget.nadir_range_m.filter(|_| widget.is_enabled) {
+        Some(nadir_range_m) => nadir_echo_bands(
+            nadir_range_m,
+            tx_carrier_state.pulse_duration_us * 1e-6,
+            tx_carrier_state.prf_hz,
+            bsar_infos.range_min_m,
+            bsar_infos.range_max_m
+        ),
+        None => vec![],
+    };
+
+    // Within the footprint giving the receive window, as for the BSAR ranges
+    let footprint: &AntennaBeamFootprintState = if rx.1.inner.ground_range_swath_m <= tx.1.inner.ground_range_swath_m {
+        &rx.1.inner
+    } else {
+        &tx.1.inner
+    };
+    let footprint_m: Vec<DVec3> = footprint.points.iter()
+        .map(|point| DVec3::new(point.z, point.x, point.y))
+        .collect();
+    let lift_m = 2.0 * footprint.lift_m as f32;
+    let lines: Vec<(Vec3, Vec3)> = widget.bands.iter()
+        .flat_map(|band| [band.range_min_m, band.range_max_m])
+        .filter_map(|range_m| iso_range_curve_m(tx_position_m, rx_position_m, range_m, ISO_RANGE_CURVE_POINTS))
+        .flat_map(|curve| {
+            curve.windows(2)
+                .filter(|segment| is_inside(&footprint_m, &(0.5 * (segment[0] + segment[1]))))
+                .map(|segment| (
+                    Vec3::new(segment[0].y as f32, lift_m, segment[0].x as f32),
+                    Vec3::new(segment[1].y as f32, lift_m, segment[1].x as f32),
+                ))
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    for (mesh, visibility) in overlay_q.iter_mut() {
+        super::display::replace_line_mesh(&mut meshes, mesh, visibility, (!lines.is_empty()).then(|| LineList { lines: lines.clone() }))?;
+    }
+
+    Ok(())
+}
+
+fn nadir_ui_system(
+    mut contexts: EguiContexts,
+    mut nadir_widget: ResMut<NadirWidget>,
+    bsar_infos_state: Res<BsarInfosState>,
+    tx_carrier_state: Res<TxCarrierState>,
+) -> Result {
+    let ctx = contexts.ctx_mut()?;
+
+    let nadir_window = egui::Window::new("Nadir Echo")
+        .resizable(false)
+        .constrain(true)
+        .collapsible(true)
+        .title_bar(true)
+        .enabled(true)
+        .default_open(false)
+        .default_pos(egui::pos2(320.0, 720.0));
+    nadir_window.show(ctx, |ui| {
+        nadir_ui(ui, &mut nadir_widget, &bsar_infos_state, &tx_carrier_state);
+    });
+
+    Ok(())
+}
+
+fn nadir_ui(ui: &mut egui::Ui, widget: &mut NadirWidget, bsar_infos_state: &BsarInfosState, tx_carrier_state: &TxCarrierState) {
+    let hover_text = egui::RichText::new("Locates the strong specular echo of the ground (Tx nadir in monostatic) in the receive\nwindow, the echoes of the following pulses blinding the scene along iso-range bands")
+        .color(TEXT_COLOR)
+        .monospace();
+    ui.checkbox(&mut widget.is_enabled, "Nadir echo").on_hover_text(hover_text);
+    if !widget.is_enabled {
+        return;
+    }
+    let Some(nadir_range_m) = widget.nadir_range_m else {
+        ui.label(egui::RichText::new("⚠ No nadir echo: a carrier is not above the ground").color(ERROR_COLOR));
+        return;
+    };
+    let bsar_infos = &bsar_infos_state.inner;
+    egui::Grid::new("nadir_grid")
+        .num_columns(2)
+        .striped(false)
+        .spacing([20.0, 5.0])
+        .show(ui, |ui| {
+            ui.label("Nadir echo range: ");
+            ui.label(format!("{nadir_range_m:.1} m"));
+            ui.end_row();
+
+            ui.label("Receive window: ");
+            ui.label(format!("{:.1} - {:.1} m", bsar_infos.range_min_m, bsar_infos.range_max_m));
+            ui.end_row();
+
+            let hover_text = egui::RichText::new("Range between the echoes of two consecutive pulses (c/PRF)")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Range ambiguity: ").on_hover_text(hover_text);
+            ui.label(format!("{:.1} m", SPEED_OF_LIGHT_IN_VACUUM / tx_carrier_state.prf_hz));
+            ui.end_row();
+        });
+    if widget.bands.is_empty() {
+        ui.label(egui::RichText::new("The nadir echo falls outside the receive window").color(TEXT_COLOR));
+    }
+    for band in widget.bands.iter() {
+        let text = format!(
+            "⚠ Nadir echo of pulse n+{} over {:.1} - {:.1} m",
+            band.order, band.range_min_m, band.range_max_m
+        );
+        ui.label(egui::RichText::new(text).color(ERROR_COLOR));
+    }
+}
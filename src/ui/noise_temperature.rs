@@ -0,0 +1,138 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts, EguiPrimaryContextPass};
+
+use crate::{
+    bsar::{system_noise_temperature_k, REFERENCE_TEMPERATURE_K},
+    constants::MAX_NOISE_TEMPERATURE_K,
+    scene::RxCarrierState,
+};
+
+const TEXT_COLOR: egui::Color32 = egui::Color32::from_rgb(200, 200, 200);
+const ERROR_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 120, 120);
+
+pub struct NoiseTemperaturePlugin;
+
+impl Plugin for NoiseTemperaturePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<NoiseTemperatureWidget>()
+            .add_systems(EguiPrimaryContextPass, noise_temperature_ui_system);
+    }
+}
+
+/// Receiving chain of the system noise temperature: the antenna, a cable,
+/// then the LNA.
+#[derive(Resource)]
+pub struct NoiseTemperatureWidget {
+    pub antenna_temperature_k: f64,
+    pub lna_noise_figure_db: f64,
+    pub cable_loss_db: f64,
+}
+
+impl Default for NoiseTemperatureWidget {
+    fn default() -> Self {
+        Self {
+            antenna_temperature_k: REFERENCE_TEMPERATURE_K,
+            lna_noise_figure_db: 2.0,
+            cable_loss_db: 1.0,
+        }
+    }
+}
+
+fn noise_temperature_ui_system(
+    mut contexts: EguiContexts,
+    mut noise_temperature_widget: ResMut<NoiseTemperatureWidget>,
+    mut rx_carrier_state: ResMut<RxCarrierState>,
+) -> Result {
+    let ctx = contexts.ctx_mut()?;
+
+    let noise_temperature_window = egui::Window::new("System Noise Temperature")
+        .resizable(false)
+        .constrain(true)
+        .collapsible(true)
+        .title_bar(true)
+        .enabled(true)
+        .default_open(false)
+        .default_pos(egui::pos2(320.0, 640.0));
+    noise_temperature_window.show(ctx, |ui| {
+        // The state is only written when applied, not to trigger the updates every frame
+        if let Some(noise_temperature_k) = noise_temperature_ui(ui, &mut noise_temperature_widget) {
+            rx_carrier_state.noise_temperature_k = noise_temperature_k.min(MAX_NOISE_TEMPERATURE_K);
+            // The LNA noise figure is within the system noise temperature
+            rx_carrier_state.noise_factor_db = 0.0;
+        }
+    });
+
+    Ok(())
+}
+
+/// Shows the receiving chain and its system noise temperature, returning it
+/// when applied.
+fn noise_temperature_ui(ui: &mut egui::Ui, widget: &mut NoiseTemperatureWidget) -> Option<f64> {
+    let noise_temperature_k = system_noise_temperature_k(
+        widget.antenna_temperature_k, widget.lna_noise_figure_db, widget.cable_loss_db
+    );
+    egui::Grid::new("noise_temperature_grid")
+        .num_columns(2)
+        .striped(false)
+        .spacing([20.0, 5.0])
+        .show(ui, |ui| {
+            let hover_text = egui::RichText::new("Noise temperature of the antenna: sky and ground seen by its pattern")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Antenna temp.: ").on_hover_text(hover_text);
+            ui.add(
+                egui::DragValue::new(&mut widget.antenna_temperature_k)
+                    .range(0.0..=1000.0)
+                    .speed(1.0)
+                    .fixed_decimals(1)
+                    .suffix(" K")
+            );
+            ui.end_row();
+
+            let hover_text = egui::RichText::new(format!("Losses between the antenna and the LNA, at {REFERENCE_TEMPERATURE_K:.0} K"))
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Cable losses: ").on_hover_text(hover_text);
+            ui.add(
+                egui::DragValue::new(&mut widget.cable_loss_db)
+                    .range(0.0..=20.0)
+                    .speed(0.01)
+                    .fixed_decimals(2)
+                    .suffix(" dB")
+            );
+            ui.end_row();
+
+            ui.label("LNA noise figure: ");
+            ui.add(
+                egui::DragValue::new(&mut widget.lna_noise_figure_db)
+                    .range(0.0..=20.0)
+                    .speed(0.01)
+                    .fixed_decimals(2)
+                    .suffix(" dB")
+            );
+            ui.end_row();
+
+            let hover_text = egui::RichText::new("T_sys = T_A + (L - 1).T0 + L.(F - 1).T0, referred to the antenna output (Friis formula)")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("System noise temp.: ").on_hover_text(hover_text);
+            ui.label(format!("{noise_temperature_k:.1} K"));
+            ui.end_row();
+        });
+    if noise_temperature_k > MAX_NOISE_TEMPERATURE_K {
+        let text = format!("⚠ The Rx noise temperature is limited to {MAX_NOISE_TEMPERATURE_K:.0} K");
+        ui.label(egui::RichText::new(text).color(ERROR_COLOR));
+    }
+    let hover_text = egui::RichText::new(
+        "Sets the Rx noise temperature to the system one, and the Rx noise factor to 0 dB:\n\
+         the LNA noise figure is already within the system noise temperature"
+    )
+        .color(TEXT_COLOR)
+        .monospace();
+    let mut is_applied = false;
+    ui.vertical_centered(|ui| {
+        is_applied = ui.button("Apply").on_hover_text(hover_text).clicked();
+    });
+    is_applied.then_some(noise_temperature_k)
+}
@@ -0,0 +1,333 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts, EguiPrimaryContextPass};
+
+use crate::{
+    epoch::UtcTime,
+    expression,
+    orbit::{revisit_times_s, AccessWindow, Orbit},
+    ui::{GeoReferenceWidget, TimelineWidget},
+};
+
+const TEXT_COLOR: egui::Color32 = egui::Color32::from_rgb(200, 200, 200);
+const ERROR_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 120, 120);
+/// Default TLE: ISS (ZARYA), CelesTrak format
+const DEFAULT_TLE: [&str; 2] = [
+    "1 25544U 98067A   08264.51782528 -.00002182  00000-0 -11606-4 0  2927",
+    "2 25544  51.6416 247.4627 0006703 130.5360 325.0288 15.72125391563537",
+];
+
+pub struct OrbitPlugin;
+
+impl Plugin for OrbitPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<OrbitWidget>()
+            .add_systems(EguiPrimaryContextPass, orbit_ui_system);
+    }
+}
+
+/// How the transmitter orbit is defined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrbitDefinition {
+    /// Circular orbit from its altitude, inclination, node and argument of latitude
+    Circular,
+    /// Two-Line Element set
+    Tle,
+}
+
+/// Orbit mode: the transmitter orbit, and the scene center access windows and
+/// revisit times computed over several orbits.
+#[derive(Resource)]
+pub struct OrbitWidget {
+    pub definition: OrbitDefinition,
+    pub altitude_km: f64,
+    pub inclination_deg: f64,
+    pub raan_deg: f64,
+    pub arg_latitude_deg: f64,
+    pub tle_lines: [String; 2],
+    /// Number of orbits propagated from the epoch
+    pub num_orbits: f64,
+    /// Propagation time step in seconds
+    pub step_s: f64,
+    /// Accepted incidence angles at the scene center in degrees
+    pub incidence_min_deg: f64,
+    pub incidence_max_deg: f64,
+    /// Last computed access windows
+    pub access_windows: Vec<AccessWindow>,
+    /// Epoch of the orbit of the access windows
+    pub access_epoch: Option<UtcTime>,
+    /// Result of the last computation
+    status: Option<Result<String, String>>,
+}
+
+impl Default for OrbitWidget {
+    fn default() -> Self {
+        Self {
+            definition: OrbitDefinition::Circular,
+            altitude_km: 700.0,
+            inclination_deg: 98.2,
+            raan_deg: 0.0,
+            arg_latitude_deg: 0.0,
+            tle_lines: DEFAULT_TLE.map(str::to_string),
+            num_orbits: 15.0,
+            step_s: 10.0,
+            incidence_min_deg: 20.0,
+            incidence_max_deg: 50.0,
+            access_windows: Vec::new(),
+            access_epoch: None,
+            status: None,
+        }
+    }
+}
+
+impl OrbitWidget {
+    /// Gets the transmitter orbit from the current definition, circular orbits
+    /// being defined at the scenario `epoch`.
+    pub fn orbit(&self, epoch: UtcTime) -> Result<Orbit, String> {
+        match self.definition {
+            OrbitDefinition::Circular => Ok(Orbit::circular(
+                self.altitude_km * 1e3, // Convert km to m
+                self.inclination_deg,
+                self.raan_deg,
+                self.arg_latitude_deg
+            ).at_epoch(epoch)),
+            OrbitDefinition::Tle => Orbit::from_tle(&self.tle_lines[0], &self.tle_lines[1]),
+        }
+    }
+
+    /// Computes the access windows of the scene center over the orbits
+    /// propagated from the scenario `epoch`.
+    pub fn compute_access_windows(&mut self, geo_reference_widget: &GeoReferenceWidget, epoch: UtcTime) {
+        match self.orbit(epoch) {
+            Ok(orbit) => {
+                // TLE orbits are propagated from their own epoch to the scenario one
+                let start_s = orbit.epoch.map_or(0.0, |orbit_epoch| epoch.seconds_since(&orbit_epoch));
+                self.access_windows = orbit.access_windows(
+                    &geo_reference_widget.scene_origin,
+                    start_s,
+                    self.num_orbits * orbit.period_s(),
+                    self.step_s,
+                    (self.incidence_min_deg, self.incidence_max_deg)
+                );
+                self.access_epoch = orbit.epoch;
+                self.status = Some(Ok(format!(
+                    "{} access windows over {:.1} h",
+                    self.access_windows.len(),
+                    self.num_orbits * orbit.period_s() / 3600.0
+                )));
+            }
+            Err(error) => {
+                self.access_windows.clear();
+                self.access_epoch = None;
+                self.status = Some(Err(error));
+            }
+        }
+    }
+}
+
+fn orbit_ui_system(
+    mut contexts: EguiContexts,
+    mut orbit_widget: ResMut<OrbitWidget>,
+    geo_reference_widget: Res<GeoReferenceWidget>,
+    timeline_widget: Res<TimelineWidget>,
+) -> Result {
+    let ctx = contexts.ctx_mut()?;
+
+    let orbit_window = egui::Window::new("Orbit")
+        .resizable(false)
+        .constrain(true)
+        .collapsible(true)
+        .title_bar(true)
+        .enabled(true)
+        .default_open(false)
+        .default_pos(egui::pos2(320.0, 120.0));
+    orbit_window.show(ctx, |ui| {
+        orbit_ui(ui, &mut orbit_widget, &geo_reference_widget, timeline_widget.epoch);
+    });
+
+    Ok(())
+}
+
+fn orbit_ui(ui: &mut egui::Ui, widget: &mut OrbitWidget, geo_reference_widget: &GeoReferenceWidget, epoch: UtcTime) {
+    ui.vertical_centered(|ui| ui.label(egui::RichText::new("TRANSMITTER ORBIT").strong()));
+    ui.separator();
+
+    ui.horizontal(|ui| {
+        ui.radio_value(&mut widget.definition, OrbitDefinition::Circular, "Circular");
+        ui.radio_value(&mut widget.definition, OrbitDefinition::Tle, "TLE");
+    });
+    match widget.definition {
+        OrbitDefinition::Circular => circular_orbit_ui(ui, widget),
+        OrbitDefinition::Tle => {
+            for line in widget.tle_lines.iter_mut() {
+                ui.add(
+                    egui::TextEdit::singleline(line)
+                        .font(egui::TextStyle::Monospace)
+                        .desired_width(520.0)
+                );
+            }
+        }
+    }
+
+    ui.separator();
+    ui.vertical_centered(|ui| ui.label(egui::RichText::new("SCENE CENTER ACCESS").strong()));
+    ui.separator();
+
+    egui::Grid::new("orbit_access_grid")
+        .num_columns(2)
+        .striped(false)
+        .spacing([20.0, 5.0])
+        .show(ui, |ui| {
+            let hover_text = egui::RichText::new("Sets the accepted incidence angles at the scene center (0 - 90°)")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Incidence: ").on_hover_text(hover_text.clone());
+            ui.horizontal(|ui| {
+                let incidence_max_deg = widget.incidence_max_deg;
+                ui.add(
+                    egui::DragValue::new(&mut widget.incidence_min_deg)
+                        .custom_parser(expression::parse)
+                        .update_while_editing(false)
+                        .speed(0.1)
+                        .range(0.0..=incidence_max_deg)
+                        .fixed_decimals(1)
+                        .suffix("°")
+                ).on_hover_text(hover_text.clone());
+                ui.label("-");
+                let incidence_min_deg = widget.incidence_min_deg;
+                ui.add(
+                    egui::DragValue::new(&mut widget.incidence_max_deg)
+                        .custom_parser(expression::parse)
+                        .update_while_editing(false)
+                        .speed(0.1)
+                        .range(incidence_min_deg..=90.0)
+                        .fixed_decimals(1)
+                        .suffix("°")
+                ).on_hover_text(hover_text);
+            });
+            ui.end_row();
+
+            let hover_text = egui::RichText::new("Sets the number of propagated orbits from the epoch (1 - 1000)")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Orbits: ").on_hover_text(hover_text.clone());
+            ui.add(
+                egui::DragValue::new(&mut widget.num_orbits)
+                    .custom_parser(expression::parse)
+                    .update_while_editing(false)
+                    .speed(1.0)
+                    .range(1.0..=1000.0)
+                    .fixed_decimals(0)
+            ).on_hover_text(hover_text);
+            ui.end_row();
+
+            let hover_text = egui::RichText::new("Sets the propagation time step (1 - 600 s),\nshorter access windows may be missed")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Step: ").on_hover_text(hover_text.clone());
+            ui.add(
+                egui::DragValue::new(&mut widget.step_s)
+                    .custom_parser(expression::parse)
+                    .update_while_editing(false)
+                    .speed(1.0)
+                    .range(1.0..=600.0)
+                    .fixed_decimals(0)
+                    .suffix(" s")
+            ).on_hover_text(hover_text);
+            ui.end_row();
+        });
+
+    let hover_text = egui::RichText::new("Propagates the orbit from the scenario epoch (see \"Timeline\") and lists when the\nscene center (see \"Geo Infos\") is illuminated within the incidence constraints")
+        .color(TEXT_COLOR)
+        .monospace();
+    if ui.button("Compute access").on_hover_text(hover_text).clicked() {
+        widget.compute_access_windows(geo_reference_widget, epoch);
+    }
+    match &widget.status {
+        Some(Ok(status)) => { ui.label(egui::RichText::new(status).color(TEXT_COLOR)); }
+        Some(Err(error)) => { ui.label(egui::RichText::new(error).color(ERROR_COLOR)); }
+        None => {}
+    }
+    if widget.access_windows.is_empty() {
+        return;
+    }
+
+    access_windows_table_ui(ui, &widget.access_windows, widget.access_epoch);
+}
+
+fn circular_orbit_ui(ui: &mut egui::Ui, widget: &mut OrbitWidget) {
+    egui::Grid::new("orbit_circular_grid")
+        .num_columns(2)
+        .striped(false)
+        .spacing([20.0, 5.0])
+        .show(ui, |ui| {
+            let angle_row = |ui: &mut egui::Ui, label: &str, value: &mut f64, range: std::ops::RangeInclusive<f64>, hover: &str| {
+                let hover_text = egui::RichText::new(hover).color(TEXT_COLOR).monospace();
+                ui.label(label).on_hover_text(hover_text.clone());
+                ui.add(
+                    egui::DragValue::new(value)
+                        .custom_parser(expression::parse)
+                        .update_while_editing(false)
+                        .speed(0.1)
+                        .range(range)
+                        .fixed_decimals(2)
+                        .suffix("°")
+                ).on_hover_text(hover_text);
+                ui.end_row();
+            };
+            let hover_text = egui::RichText::new("Sets the orbit altitude above the equatorial radius (150 - 40000 km)")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Altitude: ").on_hover_text(hover_text.clone());
+            ui.add(
+                egui::DragValue::new(&mut widget.altitude_km)
+                    .custom_parser(expression::parse)
+                    .update_while_editing(false)
+                    .speed(1.0)
+                    .range(150.0..=40000.0)
+                    .fixed_decimals(1)
+                    .suffix(" km")
+            ).on_hover_text(hover_text);
+            ui.end_row();
+            angle_row(ui, "Inclination: ", &mut widget.inclination_deg, 0.0..=180.0, "Sets the orbit inclination (0 - 180°)");
+            angle_row(ui, "RAAN: ", &mut widget.raan_deg, 0.0..=360.0, "Sets the right ascension of the ascending node at epoch (0 - 360°),\nthe Greenwich meridian being at its sidereal time of the scenario epoch");
+            angle_row(ui, "Arg. of latitude: ", &mut widget.arg_latitude_deg, 0.0..=360.0, "Sets the angle from the ascending node to the satellite at epoch (0 - 360°)");
+        });
+}
+
+fn access_windows_table_ui(ui: &mut egui::Ui, access_windows: &[AccessWindow], epoch: Option<UtcTime>) {
+    let revisits_s = revisit_times_s(access_windows);
+    if !revisits_s.is_empty() {
+        let mean_s = revisits_s.iter().sum::<f64>() / revisits_s.len() as f64;
+        let max_s = revisits_s.iter().copied().fold(0.0, f64::max);
+        ui.label(format!("Revisit time: mean {:.2} h, max {:.2} h", mean_s / 3600.0, max_s / 3600.0));
+    }
+    egui::ScrollArea::vertical()
+        .max_height(240.0)
+        .show(ui, |ui| {
+            egui::Grid::new("orbit_access_windows_grid")
+                .num_columns(5)
+                .striped(true)
+                .spacing([20.0, 3.0])
+                .show(ui, |ui| {
+                    for header in ["#", "Start (UTC)", "Duration [s]", "Min. incidence", "Revisit [h]"] {
+                        ui.label(egui::RichText::new(header).strong());
+                    }
+                    ui.end_row();
+                    for (i, window) in access_windows.iter().enumerate() {
+                        ui.label(format!("{}", i + 1));
+                        ui.label(epoch.map_or_else(
+                            || format!("{:.3} h", window.start_s / 3600.0),
+                            |epoch| epoch.after_s(window.start_s).to_string()
+                        ));
+                        ui.label(format!("{:.1}", window.duration_s()));
+                        ui.label(format!("{:.2}°", window.min_incidence_deg));
+                        ui.label(
+                            i.checked_sub(1)
+                                .map_or_else(String::new, |previous| format!("{:.2}", revisits_s[previous] / 3600.0))
+                        );
+                        ui.end_row();
+                    }
+                });
+        });
+}
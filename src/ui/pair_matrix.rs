@@ -0,0 +1,538 @@
+use std::collections::BTreeMap;
+
+use bevy::{math::DVec3, prelude::*};
+use bevy_egui::{egui, EguiContexts, EguiPrimaryContextPass};
+
+use crate::{
+    bsar::BsarInfos,
+    entities::{
+        carrier_transform_from_state, update_antenna_beam_footprint_from_state,
+        AntennaBeamFootprintState, AntennaBeamState, AntennaState, CarrierState
+    },
+    scene::{
+        RxAntennaBeamFootprintState, RxAntennaBeamState, RxAntennaState, RxCarrierState, ScenarioStates,
+        TxAntennaBeamFootprintState, TxAntennaBeamState, TxAntennaState, TxCarrierState
+    },
+    ui::UpdateStage,
+};
+
+const TEXT_COLOR: egui::Color32 = egui::Color32::from_rgb(200, 200, 200);
+const BEST_COLOR: egui::Color32 = egui::Color32::from_rgb(120, 220, 120);
+/// Largest number of platforms of each carrier
+const MAX_PLATFORMS: usize = 8;
+/// Largest number of samples of the metric history, the oldest dropped first
+const MAX_HISTORY_SAMPLES: usize = 10_000;
+
+pub struct PairMatrixPlugin;
+
+impl Plugin for PairMatrixPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<PairMatrixWidget>()
+            // The platforms follow the timeline as the carriers
+            .add_systems(
+                PostUpdate,
+                update_pair_matrix
+                    .in_set(UpdateStage::Derived)
+                    .after(super::tx_panel::update_bsar_system)
+            )
+            .add_systems(EguiPrimaryContextPass, pair_matrix_ui_system);
+    }
+}
+
+/// Carrier system state of a platform.
+pub trait PlatformState: Clone {
+    /// Prefix of the scenario parameters of the carrier
+    const PREFIX: &'static str;
+
+    fn carrier_mut(&mut self) -> &mut CarrierState;
+}
+
+impl PlatformState for TxCarrierState {
+    const PREFIX: &'static str = "tx";
+
+    fn carrier_mut(&mut self) -> &mut CarrierState {
+        &mut self.inner
+    }
+}
+
+impl PlatformState for RxCarrierState {
+    const PREFIX: &'static str = "rx";
+
+    fn carrier_mut(&mut self) -> &mut CarrierState {
+        &mut self.inner
+    }
+}
+
+/// A Tx or Rx platform: a snapshot of a carrier with its antenna and beam,
+/// moving along its trajectory with the timeline.
+#[derive(Clone)]
+pub struct Platform<S> {
+    pub name: String,
+    /// Scenario parameters of the carrier, without their prefix, set when a
+    /// pair of the platform is selected
+    parameters: BTreeMap<String, f64>,
+    pub state: S,
+    antenna: AntennaState,
+    pub beam: AntennaBeamState,
+    pub footprint: AntennaBeamFootprintState,
+}
+
+impl<S: PlatformState> Platform<S> {
+    /// Takes a snapshot of the carrier from its states and the scenario
+    /// parameters of both carriers.
+    fn capture(
+        name: String,
+        scenario_parameters: &BTreeMap<String, f64>,
+        state: &S,
+        antenna: &AntennaState,
+        beam: &AntennaBeamState,
+        footprint: &AntennaBeamFootprintState,
+    ) -> Self {
+        let prefix = format!("{}.", S::PREFIX);
+        let parameters = scenario_parameters.iter()
+            .filter_map(|(key, value)| Some((key.strip_prefix(&prefix)?.to_string(), *value)))
+            .collect();
+        Self {
+            name,
+            parameters,
+            state: state.clone(),
+            antenna: antenna.clone(),
+            beam: beam.clone(),
+            footprint: footprint.clone(),
+        }
+    }
+
+    /// Gets the scenario parameters of the carrier.
+    fn scenario_parameters(&self) -> impl Iterator<Item = (String, f64)> + '_ {
+        self.parameters.iter().map(|(key, value)| (format!("{}.{key}", S::PREFIX), *value))
+    }
+
+    /// Moves the platform to the timeline time `time_s`, returning whether it
+    /// moved.
+    fn move_to(&mut self, time_s: f64) -> bool {
+        let carrier = self.state.carrier_mut();
+        if carrier.time_s == time_s {
+            return false;
+        }
+        carrier.time_s = time_s;
+        carrier_transform_from_state(carrier, &self.antenna);
+        update_antenna_beam_footprint_from_state(carrier, &self.antenna, &self.beam, &mut self.footprint);
+        true
+    }
+}
+
+/// Key metrics of a Tx/Rx pair at the scene center.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PairMetrics {
+    pub bistatic_angle_deg: f64,
+    pub resolution_area_m2: f64,
+    pub nesz_db: f64,
+}
+
+impl PairMetrics {
+    /// Gets the value of the metric `criterion`, the lower the better.
+    pub fn value(&self, criterion: PairCriterion) -> f64 {
+        match criterion {
+            PairCriterion::ResolutionArea => self.resolution_area_m2,
+            PairCriterion::Nesz => self.nesz_db,
+        }
+    }
+
+    fn of(tx: &Platform<TxCarrierState>, rx: &Platform<RxCarrierState>) -> Self {
+        let mut bsar_infos = BsarInfos::default();
+        // At the scene center, without the ambiguity ratios of the whole swath
+        bsar_infos.update_from_state_at(&tx.state, &rx.state, &tx.beam, &rx.beam, &tx.footprint, &rx.footprint, &DVec3::ZERO);
+        Self {
+            bistatic_angle_deg: bsar_infos.bistatic_angle_deg,
+            resolution_area_m2: bsar_infos.resolution_area_m2,
+            nesz_db: 10.0 * bsar_infos.nesz.log10(),
+        }
+    }
+}
+
+/// Metric the best pair is chosen on.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum PairCriterion {
+    /// Finest resolution area
+    #[default]
+    ResolutionArea,
+    /// Lowest NESZ
+    Nesz,
+}
+
+impl PairCriterion {
+    const ALL: [Self; 2] = [Self::ResolutionArea, Self::Nesz];
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::ResolutionArea => "Resolution area [m²]",
+            Self::Nesz => "NESZ [dB]",
+        }
+    }
+}
+
+/// Metrics of the best and the selected pairs at a time of the timeline.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PairSample {
+    pub time_s: f64,
+    pub best: PairMetrics,
+    pub selected: Option<PairMetrics>,
+}
+
+/// Tx and Rx platforms and the metrics of their pairs, the selected pair
+/// being the one of the carriers.
+#[derive(Resource, Default)]
+pub struct PairMatrixWidget {
+    pub tx_platforms: Vec<Platform<TxCarrierState>>,
+    pub rx_platforms: Vec<Platform<RxCarrierState>>,
+    /// Metrics of the pairs, by Tx platform then Rx platform
+    pub metrics: Vec<Vec<PairMetrics>>,
+    /// Pair set on the carriers, as (Tx, Rx) platforms
+    pub selected: Option<(usize, usize)>,
+    pub criterion: PairCriterion,
+    /// Pair with the best metric of `criterion`, as (Tx, Rx) platforms
+    pub best: Option<(usize, usize)>,
+    /// Whether the best pair is selected as soon as it changes
+    pub follows_best: bool,
+    /// Metrics of the best and the selected pairs over the timeline, by
+    /// increasing time
+    pub history: Vec<PairSample>,
+    needs_update: bool,
+}
+
+impl PairMatrixWidget {
+    /// Gets the metrics of the pair (Tx, Rx).
+    pub fn pair_metrics(&self, (tx_index, rx_index): (usize, usize)) -> Option<&PairMetrics> {
+        self.metrics.get(tx_index)?.get(rx_index)
+    }
+
+    /// Finds the pair with the best metric of the criterion.
+    fn find_best(&self) -> Option<(usize, usize)> {
+        self.metrics.iter()
+            .enumerate()
+            .flat_map(|(tx_index, row)| row.iter().enumerate().map(move |(rx_index, metrics)| ((tx_index, rx_index), metrics.value(self.criterion))))
+            .filter(|(_, value)| value.is_finite())
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(pair, _)| pair)
+    }
+
+    /// Records the metrics of the best and the selected pairs at `time_s`,
+    /// replacing the samples from `time_s` on when the timeline went back.
+    fn record(&mut self, time_s: f64) {
+        let Some(best) = self.best.and_then(|pair| self.pair_metrics(pair)).copied() else {
+            return;
+        };
+        let selected = self.selected.and_then(|pair| self.pair_metrics(pair)).copied();
+        while self.history.last().is_some_and(|sample| sample.time_s >= time_s) {
+            self.history.pop();
+        }
+        if self.history.len() >= MAX_HISTORY_SAMPLES {
+            self.history.remove(0);
+        }
+        self.history.push(PairSample { time_s, best, selected });
+    }
+}
+
+/// Moves the platforms with the timeline, computes the metrics of the pairs
+/// and records the best and the selected ones.
+fn update_pair_matrix(
+    mut pair_matrix_widget: ResMut<PairMatrixWidget>,
+    tx_carrier_state: Res<TxCarrierState>,
+) {
+    // The widget is written by its window every frame: only moved platforms are computed
+    let widget = pair_matrix_widget.bypass_change_detection();
+    let time_s = tx_carrier_state.inner.time_s;
+    let mut is_moved = false;
+    for platform in widget.tx_platforms.iter_mut() {
+        is_moved |= platform.move_to(time_s);
+    }
+    for platform in widget.rx_platforms.iter_mut() {
+        is_moved |= platform.move_to(time_s);
+    }
+    if !is_moved && !widget.needs_update {
+        return;
+    }
+    widget.metrics = widget.tx_platforms.iter()
+        .map(|tx| widget.rx_platforms.iter().map(|rx| PairMetrics::of(tx, rx)).collect())
+        .collect();
+    widget.best = widget.find_best();
+    widget.record(time_s);
+    widget.needs_update = false;
+}
+
+/// Edit of the platforms asked by the window.
+enum PairMatrixAction {
+    AddTx,
+    AddRx,
+    RemoveTx(usize),
+    RemoveRx(usize),
+    Select(usize, usize),
+    SetCriterion(PairCriterion),
+    FollowBest(bool),
+}
+
+fn pair_matrix_ui_system(
+    mut contexts: EguiContexts,
+    mut pair_matrix_widget: ResMut<PairMatrixWidget>,
+    tx_state: (ResMut<TxCarrierState>, ResMut<TxAntennaState>, ResMut<TxAntennaBeamState>, Res<TxAntennaBeamFootprintState>),
+    rx_state: (ResMut<RxCarrierState>, ResMut<RxAntennaState>, ResMut<RxAntennaBeamState>, Res<RxAntennaBeamFootprintState>),
+) -> Result {
+    let ctx = contexts.ctx_mut()?;
+    let (mut tx_carrier_state, mut tx_antenna_state, mut tx_antenna_beam_state, tx_footprint_state) = tx_state;
+    let (mut rx_carrier_state, mut rx_antenna_state, mut rx_antenna_beam_state, rx_footprint_state) = rx_state;
+
+    let pair_matrix_window = egui::Window::new("Tx × Rx Pairs")
+        .resizable(false)
+        .constrain(true)
+        .collapsible(true)
+        .title_bar(true)
+        .enabled(true)
+        .default_open(false)
+        .default_pos(egui::pos2(320.0, 240.0));
+    let mut action = None;
+    pair_matrix_window.show(ctx, |ui| {
+        action = pair_matrix_ui(ui, &pair_matrix_widget);
+    });
+    // Follows the best pair as the platforms move
+    if action.is_none()
+        && pair_matrix_widget.follows_best
+        && let Some((tx_index, rx_index)) = pair_matrix_widget.best
+        && pair_matrix_widget.selected != pair_matrix_widget.best
+    {
+        action = Some(PairMatrixAction::Select(tx_index, rx_index));
+    }
+    let Some(action) = action else {
+        return Ok(());
+    };
+
+    let widget = pair_matrix_widget.as_mut();
+    match action {
+        PairMatrixAction::AddTx | PairMatrixAction::AddRx => {
+            // Read without marking the states as changed
+            let parameters = ScenarioStates {
+                tx: (
+                    tx_carrier_state.bypass_change_detection(),
+                    tx_antenna_state.bypass_change_detection(),
+                    tx_antenna_beam_state.bypass_change_detection()
+                ),
+                rx: (
+                    rx_carrier_state.bypass_change_detection(),
+                    rx_antenna_state.bypass_change_detection(),
+                    rx_antenna_beam_state.bypass_change_detection()
+                ),
+            }
+            .parameters();
+            if matches!(action, PairMatrixAction::AddTx) {
+                let name = format!("Tx {}", widget.tx_platforms.len() + 1);
+                widget.tx_platforms.push(Platform::capture(
+                    name, &parameters, &*tx_carrier_state, &tx_antenna_state.inner, &tx_antenna_beam_state.inner, &tx_footprint_state.inner
+                ));
+            } else {
+                let name = format!("Rx {}", widget.rx_platforms.len() + 1);
+                widget.rx_platforms.push(Platform::capture(
+                    name, &parameters, &*rx_carrier_state, &rx_antenna_state.inner, &rx_antenna_beam_state.inner, &rx_footprint_state.inner
+                ));
+            }
+        }
+        PairMatrixAction::RemoveTx(index) => {
+            widget.tx_platforms.remove(index);
+            widget.selected = None;
+            widget.history.clear();
+        }
+        PairMatrixAction::RemoveRx(index) => {
+            widget.rx_platforms.remove(index);
+            widget.selected = None;
+            widget.history.clear();
+        }
+        PairMatrixAction::Select(tx_index, rx_index) => {
+            // Sets the states through change detection, updating the infos and the scene
+            let parameters: BTreeMap<String, f64> = widget.tx_platforms[tx_index].scenario_parameters()
+                .chain(widget.rx_platforms[rx_index].scenario_parameters())
+                .collect();
+            ScenarioStates {
+                tx: (&mut tx_carrier_state, &mut tx_antenna_state, &mut tx_antenna_beam_state),
+                rx: (&mut rx_carrier_state, &mut rx_antenna_state, &mut rx_antenna_beam_state),
+            }
+            .apply(&parameters);
+            widget.selected = Some((tx_index, rx_index));
+        }
+        PairMatrixAction::SetCriterion(criterion) => {
+            // The best pair of the history was chosen on the previous criterion
+            widget.criterion = criterion;
+            widget.history.clear();
+        }
+        PairMatrixAction::FollowBest(follows_best) => {
+            widget.follows_best = follows_best;
+        }
+    }
+    widget.needs_update = true;
+
+    Ok(())
+}
+
+/// Shows the platforms and the matrix of their pairs, returning the asked
+/// edit.
+fn pair_matrix_ui(ui: &mut egui::Ui, widget: &PairMatrixWidget) -> Option<PairMatrixAction> {
+    let mut action = None;
+    let hover_text = egui::RichText::new("Adds the current carrier, its antenna and its system as a platform,\nmoving along its trajectory with the timeline")
+        .color(TEXT_COLOR)
+        .monospace();
+    ui.horizontal(|ui| {
+        if ui.add_enabled(widget.tx_platforms.len() < MAX_PLATFORMS, egui::Button::new("Add the Tx"))
+            .on_hover_text(hover_text.clone())
+            .clicked()
+        {
+            action = Some(PairMatrixAction::AddTx);
+        }
+        if ui.add_enabled(widget.rx_platforms.len() < MAX_PLATFORMS, egui::Button::new("Add the Rx"))
+            .on_hover_text(hover_text)
+            .clicked()
+        {
+            action = Some(PairMatrixAction::AddRx);
+        }
+    });
+    if widget.tx_platforms.is_empty() || widget.rx_platforms.is_empty() {
+        ui.label(egui::RichText::new("Add Tx and Rx platforms to compare their pairs").color(TEXT_COLOR));
+        return action;
+    }
+
+    ui.separator();
+    let hover_text = egui::RichText::new("Metric of the best pair at the scene center, highlighted in the matrix")
+        .color(TEXT_COLOR)
+        .monospace();
+    ui.horizontal(|ui| {
+        ui.label("Best pair: ").on_hover_text(hover_text);
+        for criterion in PairCriterion::ALL {
+            if ui.radio(widget.criterion == criterion, criterion.label()).clicked() && widget.criterion != criterion {
+                action = Some(PairMatrixAction::SetCriterion(criterion));
+            }
+        }
+    });
+    let mut follows_best = widget.follows_best;
+    if ui.checkbox(&mut follows_best, "Follow the best pair")
+        .on_hover_text("Sets the best pair on the carriers as soon as it changes while the platforms move")
+        .changed()
+    {
+        action = Some(PairMatrixAction::FollowBest(follows_best));
+    }
+
+    let hover_text = egui::RichText::new("Bistatic angle, resolution area and NESZ at the scene center:\nselect a pair to set it on the carriers, ★ the best one")
+        .color(TEXT_COLOR)
+        .monospace();
+    egui::Grid::new("pair_matrix_grid")
+        .num_columns(widget.rx_platforms.len() + 1)
+        .striped(false)
+        .spacing([20.0, 5.0])
+        .show(ui, |ui| {
+            ui.label(egui::RichText::new("Tx \\ Rx").strong()).on_hover_text(hover_text.clone());
+            for (rx_index, platform) in widget.rx_platforms.iter().enumerate() {
+                ui.horizontal(|ui| {
+                    ui.label(egui::RichText::new(&platform.name).strong());
+                    if ui.small_button("🗑").on_hover_text("Removes the platform").clicked() {
+                        action = Some(PairMatrixAction::RemoveRx(rx_index));
+                    }
+                });
+            }
+            ui.end_row();
+
+            for (tx_index, platform) in widget.tx_platforms.iter().enumerate() {
+                ui.horizontal(|ui| {
+                    ui.label(egui::RichText::new(&platform.name).strong());
+                    if ui.small_button("🗑").on_hover_text("Removes the platform").clicked() {
+                        action = Some(PairMatrixAction::RemoveTx(tx_index));
+                    }
+                });
+                for rx_index in 0..widget.rx_platforms.len() {
+                    let is_best = widget.best == Some((tx_index, rx_index));
+                    let text = match widget.pair_metrics((tx_index, rx_index)) {
+                        Some(metrics) => format!(
+                            "{}{:.1}°\n{:.3} m²\n{:.1} dB",
+                            if is_best { "★ " } else { "" },
+                            metrics.bistatic_angle_deg, metrics.resolution_area_m2, metrics.nesz_db
+                        ),
+                        None => "-".to_string(),
+                    };
+                    let mut text = egui::RichText::new(text).monospace();
+                    if is_best {
+                        text = text.color(BEST_COLOR);
+                    }
+                    let is_selected = widget.selected == Some((tx_index, rx_index));
+                    if ui.selectable_label(is_selected, text)
+                        .on_hover_text(hover_text.clone())
+                        .clicked()
+                    {
+                        action = Some(PairMatrixAction::Select(tx_index, rx_index));
+                    }
+                }
+                ui.end_row();
+            }
+        });
+
+    if widget.history.len() > 1 {
+        ui.separator();
+        history_plot_ui(ui, widget);
+    }
+    action
+}
+
+/// Plots the metric of the best and the selected pairs over the timeline.
+fn history_plot_ui(ui: &mut egui::Ui, widget: &PairMatrixWidget) {
+    let criterion = widget.criterion;
+    let best: Vec<[f64; 2]> = widget.history.iter()
+        .map(|sample| [sample.time_s, sample.best.value(criterion)])
+        .collect();
+    let selected: Vec<[f64; 2]> = widget.history.iter()
+        .filter_map(|sample| Some([sample.time_s, sample.selected?.value(criterion)]))
+        .collect();
+    egui_plot::Plot::new("pair_history_plot")
+        .width(420.0)
+        .height(200.0)
+        .x_axis_label("Time [s]")
+        .y_axis_label(criterion.label())
+        .legend(egui_plot::Legend::default().follow_insertion_order(true))
+        .allow_drag(false)
+        .allow_zoom(false)
+        .allow_scroll(false)
+        .show(ui, |plot_ui| {
+            plot_ui.line(egui_plot::Line::new("Best pair", best).color(BEST_COLOR).width(1.5));
+            if !selected.is_empty() {
+                plot_ui.line(egui_plot::Line::new("Selected pair", selected).color(TEXT_COLOR).width(1.5));
+            }
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metrics(resolution_area_m2: f64, nesz_db: f64) -> PairMetrics {
+        PairMetrics { bistatic_angle_deg: 30.0, resolution_area_m2, nesz_db }
+    }
+
+    #[test]
+    fn best_pair_follows_the_criterion_and_is_recorded() {
+        let mut widget = PairMatrixWidget {
+            metrics: vec![
+                vec![metrics(2.0, -20.0), metrics(1.0, -15.0)],
+                vec![metrics(f64::NAN, f64::NAN), metrics(3.0, -25.0)],
+            ],
+            ..default()
+        };
+        assert_eq!(widget.find_best(), Some((0, 1)));
+        widget.criterion = PairCriterion::Nesz;
+        assert_eq!(widget.find_best(), Some((1, 1)));
+
+        widget.best = widget.find_best();
+        widget.selected = Some((0, 0));
+        widget.record(0.0);
+        widget.record(1.0);
+        widget.record(2.0);
+        assert_eq!(widget.history.len(), 3);
+        assert_eq!(widget.history[2].best, metrics(3.0, -25.0));
+        assert_eq!(widget.history[2].selected, Some(metrics(2.0, -20.0)));
+        // The timeline went back: the later samples are replaced
+        widget.record(1.0);
+        assert_eq!(widget.history.iter().map(|sample| sample.time_s).collect::<Vec<_>>(), [0.0, 1.0]);
+    }
+}
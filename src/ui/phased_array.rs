@@ -0,0 +1,224 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts, EguiPrimaryContextPass};
+
+use crate::{
+    bsar::SPEED_OF_LIGHT_IN_VACUUM,
+    entities::AntennaBeamState,
+    expression,
+    phased_array::{ArrayAxis, PhasedArray},
+    scene::{RxAntennaBeamState, RxCarrierState, TxAntennaBeamState, TxCarrierState},
+    ui::{CarrierSteering, SteeringWidget, UpdateStage},
+};
+
+const TEXT_COLOR: egui::Color32 = egui::Color32::from_rgb(200, 200, 200);
+const ERROR_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 120, 120);
+
+pub struct PhasedArrayPlugin;
+
+impl Plugin for PhasedArrayPlugin {
+    fn build(&self, app: &mut App) {
+        // The arrays set the antenna beams, picked up by the update systems, at
+        // their scan offsets for the timeline time
+        app
+            .init_resource::<PhasedArrayWidget>()
+            .add_systems(
+                PostUpdate,
+                apply_phased_arrays
+                    .in_set(UpdateStage::Input)
+                    .after(super::timeline::advance_timeline)
+            )
+            .add_systems(EguiPrimaryContextPass, phased_array_ui_system);
+    }
+}
+
+/// Optional phased-array model of one antenna.
+#[derive(Default)]
+pub struct CarrierArray {
+    pub is_enabled: bool,
+    pub array: PhasedArray,
+}
+
+impl CarrierArray {
+    /// Gets the array when it drives the antenna beam.
+    pub fn enabled_array(&self) -> Option<&PhasedArray> {
+        Some(&self.array).filter(|_| self.is_enabled)
+    }
+}
+
+/// Phased-array models of the transmitter and the receiver antennas: their
+/// beamwidths and gain are derived from the array, steered by the "Beam
+/// Steering" schedules.
+#[derive(Resource, Default)]
+pub struct PhasedArrayWidget {
+    pub tx: CarrierArray,
+    pub rx: CarrierArray,
+}
+
+/// Gets the (elevation, azimuth) scan offsets in degrees of a steered antenna
+/// at `time_s`, the array normal when not steered.
+fn scan_offsets_deg(steering: &CarrierSteering, time_s: f64) -> (f64, f64) {
+    steering.position_at(time_s)
+        .map_or((0.0, 0.0), |position| (position.elevation_offset_deg, position.azimuth_offset_deg))
+}
+
+/// Compares the beamwidths and the gain of two antenna beams.
+fn is_beam_changed(antenna_beam_state: &AntennaBeamState, beam_state: &AntennaBeamState) -> bool {
+    antenna_beam_state.elevation_beam_width_deg != beam_state.elevation_beam_width_deg
+        || antenna_beam_state.azimuth_beam_width_deg != beam_state.azimuth_beam_width_deg
+        || antenna_beam_state.one_way_gain_dbi != beam_state.one_way_gain_dbi
+}
+
+/// Derives the antenna beams of the enabled arrays at their current scan offsets.
+fn apply_phased_arrays(
+    phased_array_widget: Res<PhasedArrayWidget>,
+    steering_widget: Res<SteeringWidget>,
+    tx: (Res<TxCarrierState>, ResMut<TxAntennaBeamState>),
+    rx: (Res<RxCarrierState>, ResMut<RxAntennaBeamState>),
+) {
+    let (tx_carrier_state, mut tx_antenna_beam_state) = tx;
+    let (rx_carrier_state, mut rx_antenna_beam_state) = rx;
+    // The states are only written when they change, which triggers the update systems
+    if let Some(array) = phased_array_widget.tx.enabled_array() {
+        let (elevation_scan_deg, azimuth_scan_deg) = scan_offsets_deg(&steering_widget.tx, tx_carrier_state.inner.time_s);
+        let beam_state = array.beam_state(elevation_scan_deg, azimuth_scan_deg);
+        if is_beam_changed(&tx_antenna_beam_state.inner, &beam_state) {
+            tx_antenna_beam_state.inner = beam_state;
+        }
+    }
+    if let Some(array) = phased_array_widget.rx.enabled_array() {
+        let (elevation_scan_deg, azimuth_scan_deg) = scan_offsets_deg(&steering_widget.rx, rx_carrier_state.inner.time_s);
+        let beam_state = array.beam_state(elevation_scan_deg, azimuth_scan_deg);
+        if is_beam_changed(&rx_antenna_beam_state.inner, &beam_state) {
+            rx_antenna_beam_state.inner = beam_state;
+        }
+    }
+}
+
+fn phased_array_ui_system(
+    mut contexts: EguiContexts,
+    mut phased_array_widget: ResMut<PhasedArrayWidget>,
+    steering_widget: Res<SteeringWidget>,
+    tx_carrier_state: Res<TxCarrierState>,
+    rx_carrier_state: Res<RxCarrierState>,
+) -> Result {
+    let ctx = contexts.ctx_mut()?;
+
+    let phased_array_window = egui::Window::new("Phased Arrays")
+        .resizable(false)
+        .constrain(true)
+        .collapsible(true)
+        .title_bar(true)
+        .enabled(true)
+        .default_open(false)
+        .default_pos(egui::pos2(320.0, 600.0));
+    phased_array_window.show(ctx, |ui| {
+        let widget = &mut *phased_array_widget; // Borrows the fields separately
+        // The bistatic system works at the transmitter frequency
+        let lem = SPEED_OF_LIGHT_IN_VACUUM / (tx_carrier_state.center_frequency_ghz * 1e9); // wavelength in m
+        let tx_scan_offsets_deg = scan_offsets_deg(&steering_widget.tx, tx_carrier_state.inner.time_s);
+        let rx_scan_offsets_deg = scan_offsets_deg(&steering_widget.rx, rx_carrier_state.inner.time_s);
+        carrier_array_ui(ui, "tx", "TRANSMITTER", &mut widget.tx, lem, tx_scan_offsets_deg);
+        carrier_array_ui(ui, "rx", "RECEIVER", &mut widget.rx, lem, rx_scan_offsets_deg);
+    });
+
+    Ok(())
+}
+
+/// Element count and spacing of one array axis, on a grid row.
+fn array_axis_ui(ui: &mut egui::Ui, name: &str, axis: &mut ArrayAxis) {
+    let hover_text = egui::RichText::new(format!("Sets the {} element count (1 - 4096) and spacing (0.1 - 2 λ)", name.to_lowercase()))
+        .color(TEXT_COLOR)
+        .monospace();
+    ui.label(format!("{name} elements: ")).on_hover_text(hover_text.clone());
+    ui.horizontal(|ui| {
+        ui.add(
+            egui::DragValue::new(&mut axis.element_count)
+                .custom_parser(expression::parse)
+                .update_while_editing(false)
+                .speed(1.0)
+                .range(1..=4096)
+        ).on_hover_text(hover_text.clone());
+        ui.label("×");
+        ui.add(
+            egui::DragValue::new(&mut axis.spacing_wavelengths)
+                .custom_parser(expression::parse)
+                .update_while_editing(false)
+                .speed(0.01)
+                .range(0.1..=2.0)
+                .fixed_decimals(2)
+                .suffix(" λ")
+        ).on_hover_text(hover_text);
+    });
+    ui.end_row();
+}
+
+fn carrier_array_ui(
+    ui: &mut egui::Ui,
+    id: &str,
+    title: &str,
+    carrier_array: &mut CarrierArray,
+    lem: f64,
+    (elevation_scan_deg, azimuth_scan_deg): (f64, f64),
+) {
+    ui.separator();
+    ui.vertical_centered(|ui| ui.label(egui::RichText::new(title).strong()));
+    ui.separator();
+
+    let hover_text = egui::RichText::new("Derives the antenna beamwidths and gain from a uniformly illuminated planar array,\nsteered by the \"Beam Steering\" schedule (the panel values are overridden)")
+        .color(TEXT_COLOR)
+        .monospace();
+    ui.checkbox(&mut carrier_array.is_enabled, "Phased array").on_hover_text(hover_text);
+    if !carrier_array.is_enabled {
+        return;
+    }
+    let array = &mut carrier_array.array;
+    egui::Grid::new(format!("{id}_phased_array_grid"))
+        .num_columns(2)
+        .striped(false)
+        .spacing([20.0, 5.0])
+        .show(ui, |ui| {
+            array_axis_ui(ui, "Elevation", &mut array.elevation);
+            array_axis_ui(ui, "Azimuth", &mut array.azimuth);
+
+            ui.label("Aperture: ");
+            ui.label(format!(
+                "{:.3} m × {:.3} m",
+                array.elevation.aperture_wavelengths() * lem,
+                array.azimuth.aperture_wavelengths() * lem
+            ));
+            ui.end_row();
+
+            ui.label("Broadside gain: ");
+            ui.label(format!("{:.2} dBi", array.broadside_gain_dbi()));
+            ui.end_row();
+
+            let beam_state = array.beam_state(elevation_scan_deg, azimuth_scan_deg);
+            ui.label("Scan offsets: ");
+            ui.label(format!("{elevation_scan_deg:+.2}° / {azimuth_scan_deg:+.2}°"));
+            ui.end_row();
+
+            ui.label("Beamwidths: ");
+            ui.label(format!(
+                "{:.3}° × {:.3}°",
+                beam_state.elevation_beam_width_deg,
+                beam_state.azimuth_beam_width_deg
+            ));
+            ui.end_row();
+
+            ui.label("Scan loss: ");
+            ui.label(format!("{:.2} dB", array.scan_loss_db(elevation_scan_deg, azimuth_scan_deg)));
+            ui.end_row();
+
+            let hover_text = egui::RichText::new("Scan angles beyond which a grating lobe enters the visible space\n(sin θ = λ/d - 1)")
+                .color(TEXT_COLOR)
+                .monospace();
+            let onset = |axis: &ArrayAxis| axis.grating_lobe_onset_deg()
+                .map_or("none".to_string(), |onset_deg| format!("±{onset_deg:.2}°"));
+            ui.label("Grating lobes from: ").on_hover_text(hover_text);
+            ui.label(format!("{} / {}", onset(&array.elevation), onset(&array.azimuth)));
+            ui.end_row();
+        });
+    if !array.is_grating_lobe_free(elevation_scan_deg, azimuth_scan_deg) {
+        ui.label(egui::RichText::new("⚠ Grating lobes are visible at the current scan offsets").color(ERROR_COLOR));
+    }
+}
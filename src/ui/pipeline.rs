@@ -0,0 +1,42 @@
+use bevy::{asset::AssetEventSystems, prelude::*};
+use bevy_egui::EguiPostUpdateSet;
+
+pub struct PipelinePlugin;
+
+impl Plugin for PipelinePlugin {
+    fn build(&self, app: &mut App) {
+        // The stages run after the egui pass, which edits the states, and
+        // before the transforms are propagated and the changed meshes and
+        // images are sent to the renderer: a setting edited in a window is
+        // drawn in the same frame.
+        app.configure_sets(
+            PostUpdate,
+            (
+                UpdateStage::Input,
+                UpdateStage::Geometry,
+                UpdateStage::Derived,
+                UpdateStage::RenderAssets,
+            )
+                .chain()
+                .after(EguiPostUpdateSet::EndPass)
+                .before(TransformSystems::Propagate)
+                .before(AssetEventSystems),
+        );
+    }
+}
+
+/// Stages of the scene update pipeline, run in this order every frame.
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UpdateStage {
+    /// Writes the states from non-panel sources: timeline, feed, phased
+    /// arrays and display settings
+    Input,
+    /// Derives the carriers, antennas and footprints geometry from the states
+    Geometry,
+    /// Derives the BSAR infos, the iso-range/Doppler plane and the quantities
+    /// sampled over the footprints from the geometry
+    Derived,
+    /// Rebuilds the meshes and overlays drawn from the geometry and the
+    /// derived products
+    RenderAssets,
+}
@@ -0,0 +1,217 @@
+use bevy::{math::{DVec2, DVec3}, prelude::*};
+use bevy_egui::{egui, EguiContexts, EguiPrimaryContextPass};
+
+use crate::{
+    entities::{AntennaBeamFootprintState, CarrierState},
+    scene::{RxAntennaBeamFootprintState, RxCarrierState, TxAntennaBeamFootprintState, TxCarrierState},
+    ui::{gaf::nice_step, DisplayWidget},
+};
+
+const TEXT_COLOR: egui::Color32 = egui::Color32::from_rgb(200, 200, 200);
+/// Color of the range rings and azimuth spokes
+const GRID_COLOR: egui::Color32 = egui::Color32::from_gray(90);
+/// Side of the square polar plots, in points
+const POLAR_PLOT_SIDE: f32 = 220.0;
+/// Angle between two azimuth spokes in degrees
+const SPOKE_STEP_DEG: usize = 30;
+/// Footprint points kept in the plotted outline, one every stride, plus the
+/// closing point of the footprint line
+const OUTLINE_STRIDE: usize = 5;
+
+pub struct PolarCoveragePlugin;
+
+impl Plugin for PolarCoveragePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<PolarCoverageWidget>()
+            .add_systems(EguiPrimaryContextPass, polar_coverage_ui_system);
+    }
+}
+
+/// Reference of the azimuths of the polar diagrams.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PolarAzimuthReference {
+    /// Clockwise from North, North up
+    North,
+    /// Clockwise from the carrier track, the track up
+    Track,
+}
+
+/// Polar diagrams (azimuth versus ground range) of the footprints seen from
+/// the nadir of their carrier.
+#[derive(Resource)]
+pub struct PolarCoverageWidget {
+    pub azimuth_reference: PolarAzimuthReference,
+}
+
+impl Default for PolarCoverageWidget {
+    fn default() -> Self {
+        Self {
+            azimuth_reference: PolarAzimuthReference::North,
+        }
+    }
+}
+
+/// Gets the (azimuth in degrees within [0, 360), ground range in meters) of
+/// the ground `offset_m` (East, North), the azimuths clockwise from
+/// `reference_azimuth_deg`.
+fn to_polar(offset_m: DVec2, reference_azimuth_deg: f64) -> (f64, f64) {
+    let azimuth_deg = offset_m.x.atan2(offset_m.y).to_degrees() - reference_azimuth_deg;
+    (azimuth_deg.rem_euclid(360.0), offset_m.length())
+}
+
+/// Gets the plot point of the polar coordinates, azimuth 0° up and 90° right.
+fn from_polar(azimuth_deg: f64, ground_range_m: f64) -> [f64; 2] {
+    let (sin, cos) = azimuth_deg.to_radians().sin_cos();
+    [ground_range_m * sin, ground_range_m * cos]
+}
+
+/// Gets the azimuth of the carrier track in degrees, clockwise from North.
+fn track_azimuth_deg(carrier_state: &CarrierState) -> f64 {
+    // Forward axis of the carrier, turned along its trajectory (ENU)
+    let forward = carrier_state.rotation() * DVec3::X;
+    forward.x.atan2(forward.y).to_degrees()
+}
+
+fn polar_coverage_ui_system(
+    mut contexts: EguiContexts,
+    mut polar_coverage_widget: ResMut<PolarCoverageWidget>,
+    tx: (Res<TxCarrierState>, Res<TxAntennaBeamFootprintState>),
+    rx: (Res<RxCarrierState>, Res<RxAntennaBeamFootprintState>),
+    display_widget: Res<DisplayWidget>,
+) -> Result {
+    let ctx = contexts.ctx_mut()?;
+    let colors = display_widget.palette.colors();
+
+    let polar_coverage_window = egui::Window::new("Polar Coverage")
+        .resizable(false)
+        .constrain(true)
+        .collapsible(true)
+        .title_bar(true)
+        .enabled(true)
+        .default_open(false)
+        .default_pos(egui::pos2(320.0, 920.0));
+    polar_coverage_window.show(ctx, |ui| {
+        let widget = &mut *polar_coverage_widget;
+        ui.horizontal(|ui| {
+            let hover_text = egui::RichText::new("Sets the reference of the azimuths: North (North up), or the carrier\ntrack (track up, right-looking antennas on the right)")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Azimuth from: ").on_hover_text(hover_text);
+            ui.radio_value(&mut widget.azimuth_reference, PolarAzimuthReference::North, "North");
+            ui.radio_value(&mut widget.azimuth_reference, PolarAzimuthReference::Track, "Track");
+        });
+        ui.horizontal(|ui| {
+            polar_coverage_ui(ui, "tx", "TRANSMITTER", widget.azimuth_reference, colors.tx_footprint_rgb, &tx.0.inner, &tx.1.inner);
+            polar_coverage_ui(ui, "rx", "RECEIVER", widget.azimuth_reference, colors.rx_footprint_rgb, &rx.0.inner, &rx.1.inner);
+        });
+    });
+
+    Ok(())
+}
+
+fn polar_coverage_ui(
+    ui: &mut egui::Ui,
+    id: &str,
+    title: &str,
+    azimuth_reference: PolarAzimuthReference,
+    (r, g, b): (u8, u8, u8),
+    carrier_state: &CarrierState,
+    antenna_beam_footprint_state: &AntennaBeamFootprintState,
+) {
+    let reference_azimuth_deg = match azimuth_reference {
+        PolarAzimuthReference::North => 0.0,
+        PolarAzimuthReference::Track => track_azimuth_deg(carrier_state),
+    };
+    // Footprint points are in World frame (Y-up): (East, North) offsets from the nadir
+    let nadir_m = carrier_state.position_m.truncate();
+    let to_polar_from_nadir = |point: &DVec3| to_polar(DVec2::new(point.z, point.x) - nadir_m, reference_azimuth_deg);
+    let outline: Vec<[f64; 2]> = antenna_beam_footprint_state.decimated_points(OUTLINE_STRIDE)
+        .map(|point| {
+            let (azimuth_deg, ground_range_m) = to_polar_from_nadir(point);
+            from_polar(azimuth_deg, ground_range_m)
+        })
+        .collect();
+    let (center_azimuth_deg, center_ground_range_m) = to_polar(-nadir_m, reference_azimuth_deg);
+    let max_ground_range_m = outline.iter()
+        .map(|&[x, y]| x.hypot(y))
+        .filter(|ground_range_m| ground_range_m.is_finite())
+        .fold(center_ground_range_m, f64::max);
+    // Range rings on a nice step, the outer one beyond the footprint
+    let ring_step_m = nice_step(max_ground_range_m / 3.0);
+    let ring_count = (max_ground_range_m / ring_step_m).ceil().max(1.0) as usize;
+    let outer_ring_m = ring_count as f64 * ring_step_m;
+
+    ui.vertical(|ui| {
+        ui.vertical_centered(|ui| ui.label(egui::RichText::new(title).strong()));
+        egui_plot::Plot::new(format!("{id}_polar_coverage_plot"))
+            .width(POLAR_PLOT_SIDE)
+            .height(POLAR_PLOT_SIDE)
+            .data_aspect(1.0)
+            .show_axes(false)
+            .show_grid(false)
+            .include_x(-1.15 * outer_ring_m)
+            .include_x(1.15 * outer_ring_m)
+            .include_y(-1.15 * outer_ring_m)
+            .include_y(1.15 * outer_ring_m)
+            .allow_drag(false)
+            .allow_zoom(false)
+            .allow_scroll(false)
+            .label_formatter(|name, value| {
+                let (azimuth_deg, ground_range_m) = to_polar(DVec2::new(value.x, value.y), 0.0);
+                let prefix = if name.is_empty() { String::new() } else { format!("{name}\n") };
+                format!("{prefix}Azimuth: {azimuth_deg:.1}°\nGround range: {:.3} km", ground_range_m * 1e-3)
+            })
+            .show(ui, |plot_ui| {
+                for ring in 1..=ring_count {
+                    let ring_m = ring as f64 * ring_step_m;
+                    let circle: Vec<[f64; 2]> = (0..=360).map(|azimuth_deg| from_polar(azimuth_deg as f64, ring_m)).collect();
+                    plot_ui.line(egui_plot::Line::new("", circle).color(GRID_COLOR).width(1.0));
+                    let label = if ring_step_m >= 1e3 {
+                        format!("{} km", ring_m / 1e3)
+                    } else {
+                        format!("{ring_m:.0} m")
+                    };
+                    plot_ui.text(
+                        egui_plot::Text::new("", from_polar(45.0, ring_m).into(), egui::RichText::new(label).small())
+                            .color(TEXT_COLOR)
+                    );
+                }
+                for azimuth_deg in (0..360).step_by(SPOKE_STEP_DEG) {
+                    let azimuth_deg = azimuth_deg as f64;
+                    let spoke = vec![[0.0, 0.0], from_polar(azimuth_deg, outer_ring_m)];
+                    plot_ui.line(egui_plot::Line::new("", spoke).color(GRID_COLOR).width(1.0));
+                    plot_ui.text(
+                        egui_plot::Text::new("", from_polar(azimuth_deg, 1.08 * outer_ring_m).into(), egui::RichText::new(format!("{azimuth_deg}°")).small())
+                            .color(TEXT_COLOR)
+                    );
+                }
+                plot_ui.line(egui_plot::Line::new("Footprint", outline).color(egui::Color32::from_rgb(r, g, b)).width(2.0));
+                plot_ui.points(
+                    egui_plot::Points::new("Scene center", vec![from_polar(center_azimuth_deg, center_ground_range_m)])
+                        .color(egui::Color32::from_rgb(r, g, b))
+                        .radius(3.0)
+                );
+                plot_ui.points(egui_plot::Points::new("Nadir", vec![[0.0, 0.0]]).color(TEXT_COLOR).radius(3.0));
+            });
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn polar_coordinates_round_trip() {
+        // East of the nadir: 90° from North, 0° from an eastward track
+        let (azimuth_deg, ground_range_m) = to_polar(DVec2::new(1000.0, 0.0), 0.0);
+        assert!((azimuth_deg - 90.0).abs() < 1e-12);
+        assert!((ground_range_m - 1000.0).abs() < 1e-12);
+        assert!(to_polar(DVec2::new(1000.0, 0.0), 90.0).0.abs() < 1e-12);
+        // South-West from a northward track, wrapped within [0, 360)
+        let (azimuth_deg, _) = to_polar(DVec2::new(-1.0, -1.0), 0.0);
+        assert!((azimuth_deg - 225.0).abs() < 1e-12);
+        let [x, y] = from_polar(225.0, 2f64.sqrt());
+        assert!((x + 1.0).abs() < 1e-12 && (y + 1.0).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,257 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts, EguiPrimaryContextPass};
+use bevy_panorbit_camera::PanOrbitCamera;
+
+use crate::{
+    palette::ColorPalette,
+    preferences::{Preferences, UiTheme},
+    ui::{app::ui_visuals, DisplayWidget, Toasts},
+};
+
+const TEXT_COLOR: egui::Color32 = egui::Color32::from_rgb(200, 200, 200);
+
+pub struct PreferencesPlugin;
+
+impl Plugin for PreferencesPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .insert_resource(PreferencesWidget::load())
+            .add_systems(Startup, apply_display_preferences)
+            .add_systems(EguiPrimaryContextPass, preferences_ui_system);
+    }
+}
+
+/// User preferences, loaded at startup and saved as soon as edited.
+#[derive(Resource)]
+pub struct PreferencesWidget {
+    pub preferences: Preferences,
+    /// UI scale shown by the slider, applied once released: rescaling the UI
+    /// under the pointer would move the slider while dragging it
+    ui_scale: f32,
+    /// UI scale last applied to the egui context, `None` before the first frame
+    applied_ui_scale: Option<f32>,
+    /// Theme last applied to the egui context, `None` before the first frame
+    applied_theme: Option<UiTheme>,
+}
+
+impl PreferencesWidget {
+    pub fn load() -> Self {
+        let preferences = Preferences::load();
+        Self {
+            ui_scale: preferences.ui_scale,
+            preferences,
+            applied_ui_scale: None,
+            applied_theme: None,
+        }
+    }
+}
+
+/// Gets the palette and the overlays shown at startup.
+fn display_defaults(preferences: &Preferences) -> (ColorPalette, [bool; 3]) {
+    (
+        preferences.palette,
+        [preferences.show_resolution_directions, preferences.show_range_points, preferences.show_footprint_annotations],
+    )
+}
+
+/// Sets the palette and the overlays of the preferences on the display.
+fn set_display_defaults(preferences: &Preferences, display_widget: &mut DisplayWidget) {
+    let (palette, [show_resolution_directions, show_range_points, show_footprint_annotations]) = display_defaults(preferences);
+    display_widget.palette = palette;
+    display_widget.show_resolution_directions = show_resolution_directions;
+    display_widget.show_range_points = show_range_points;
+    display_widget.show_footprint_annotations = show_footprint_annotations;
+}
+
+fn apply_display_preferences(
+    preferences_widget: Res<PreferencesWidget>,
+    mut display_widget: ResMut<DisplayWidget>,
+) {
+    set_display_defaults(&preferences_widget.preferences, &mut display_widget);
+}
+
+fn preferences_ui_system(
+    mut contexts: EguiContexts,
+    mut preferences_widget: ResMut<PreferencesWidget>,
+    mut display_widget: ResMut<DisplayWidget>,
+    mut pan_orbit_camera_q: Query<&mut PanOrbitCamera>,
+    mut toasts: ResMut<Toasts>,
+) -> Result {
+    let ctx = contexts.ctx_mut()?;
+    let widget = preferences_widget.as_mut();
+
+    // egui zooms on Ctrl + Plus / Minus too: the zoom is then kept as the UI scale
+    let zoom_factor = ctx.zoom_factor();
+    let mut is_changed = false;
+    if widget.applied_ui_scale.is_some_and(|ui_scale| ui_scale != zoom_factor) {
+        widget.preferences.ui_scale = zoom_factor.clamp(*Preferences::UI_SCALE_RANGE.start(), *Preferences::UI_SCALE_RANGE.end());
+        widget.ui_scale = widget.preferences.ui_scale;
+        is_changed = true;
+    }
+
+    let preferences_window = egui::Window::new("Preferences")
+        .resizable(false)
+        .constrain(true)
+        .collapsible(true)
+        .title_bar(true)
+        .enabled(true)
+        .default_open(false)
+        .default_pos(egui::pos2(320.0, 1320.0));
+    let previous_display_defaults = display_defaults(&widget.preferences);
+    preferences_window.show(ctx, |ui| {
+        is_changed |= preferences_ui(ui, widget);
+    });
+
+    if widget.applied_ui_scale != Some(widget.preferences.ui_scale) {
+        ctx.set_zoom_factor(widget.preferences.ui_scale);
+        widget.applied_ui_scale = Some(widget.preferences.ui_scale);
+    }
+    if widget.applied_theme != Some(widget.preferences.theme) {
+        ctx.set_visuals_of(egui::Theme::Dark, ui_visuals(widget.preferences.theme));
+        widget.applied_theme = Some(widget.preferences.theme);
+    }
+    // The startup palette and overlays are shown as soon as edited
+    if display_defaults(&widget.preferences) != previous_display_defaults {
+        set_display_defaults(&widget.preferences, &mut display_widget);
+    }
+    let preferences = &widget.preferences;
+    let sensitivities = [preferences.orbit_sensitivity, preferences.pan_sensitivity, preferences.zoom_sensitivity];
+    for mut pan_orbit_camera in pan_orbit_camera_q.iter_mut() {
+        let camera_sensitivities = [pan_orbit_camera.orbit_sensitivity, pan_orbit_camera.pan_sensitivity, pan_orbit_camera.zoom_sensitivity];
+        if camera_sensitivities != sensitivities { // Avoids triggering change detection every frame
+            pan_orbit_camera.orbit_sensitivity = preferences.orbit_sensitivity;
+            pan_orbit_camera.pan_sensitivity = preferences.pan_sensitivity;
+            pan_orbit_camera.zoom_sensitivity = preferences.zoom_sensitivity;
+        }
+    }
+    if is_changed && let Err(error) = widget.preferences.save() {
+        toasts.warning(format!("Preferences not saved: {error}"));
+    }
+
+    Ok(())
+}
+
+/// Whether an edit of `response` is over: slider or drag value released, or
+/// value typed or stepped.
+fn is_edit_done(response: &egui::Response) -> bool {
+    response.drag_stopped() || (response.changed() && !response.dragged())
+}
+
+/// Shows the preferences, returning whether they changed.
+fn preferences_ui(ui: &mut egui::Ui, widget: &mut PreferencesWidget) -> bool {
+    let mut is_changed = false;
+    egui::Grid::new("preferences_grid")
+        .num_columns(3)
+        .striped(false)
+        .spacing([20.0, 5.0])
+        .show(ui, |ui| {
+            let hover_text = egui::RichText::new("Scales the whole interface, for HiDPI displays and projectors\n(also Ctrl + Plus / Minus)")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("UI scale:").on_hover_text(hover_text);
+            let response = ui.add(
+                egui::Slider::new(&mut widget.ui_scale, Preferences::UI_SCALE_RANGE)
+                    .step_by(0.05)
+                    .fixed_decimals(2)
+            );
+            if is_edit_done(&response) {
+                widget.preferences.ui_scale = widget.ui_scale;
+                is_changed = true;
+            }
+            if ui.button("Reset").clicked() {
+                widget.ui_scale = Preferences::default().ui_scale;
+                widget.preferences.ui_scale = widget.ui_scale;
+                is_changed = true;
+            }
+            ui.end_row();
+
+            let hover_text = egui::RichText::new("Sets the colors of the panels and windows")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Theme:").on_hover_text(hover_text);
+            egui::ComboBox::from_id_salt("preferences_theme")
+                .selected_text(widget.preferences.theme.label())
+                .show_ui(ui, |ui| {
+                    for theme in UiTheme::ALL {
+                        is_changed |= ui.selectable_value(&mut widget.preferences.theme, theme, theme.label()).changed();
+                    }
+                });
+            ui.end_row();
+        });
+
+    ui.separator();
+    ui.vertical_centered(|ui| ui.label(egui::RichText::new("DISPLAY AT STARTUP").strong()));
+    ui.separator();
+
+    egui::Grid::new("preferences_display_grid")
+        .num_columns(2)
+        .striped(false)
+        .spacing([20.0, 5.0])
+        .show(ui, |ui| {
+            let preferences = &mut widget.preferences;
+            let hover_text = egui::RichText::new("Sets the palette of the \"Display\" window at startup")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Palette:").on_hover_text(hover_text);
+            egui::ComboBox::from_id_salt("preferences_palette")
+                .selected_text(preferences.palette.label())
+                .show_ui(ui, |ui| {
+                    for palette in ColorPalette::ALL {
+                        is_changed |= ui.selectable_value(&mut preferences.palette, palette, palette.label()).changed();
+                    }
+                });
+            ui.end_row();
+
+            let overlays = [
+                ("Resolution directions:", &mut preferences.show_resolution_directions),
+                ("Range points:", &mut preferences.show_range_points),
+                ("Footprint annotations:", &mut preferences.show_footprint_annotations),
+            ];
+            for (label, is_shown) in overlays {
+                ui.label(label);
+                is_changed |= ui.checkbox(is_shown, "").changed();
+                ui.end_row();
+            }
+        });
+
+    ui.separator();
+    ui.vertical_centered(|ui| ui.label(egui::RichText::new("CAMERA").strong()));
+    ui.separator();
+
+    egui::Grid::new("preferences_camera_grid")
+        .num_columns(2)
+        .striped(false)
+        .spacing([20.0, 5.0])
+        .show(ui, |ui| {
+            let preferences = &mut widget.preferences;
+            let hover_text = egui::RichText::new("Sets the sensitivity of the camera control to the mouse, 0 disabling it")
+                .color(TEXT_COLOR)
+                .monospace();
+            let sensitivities = [
+                ("Orbit sensitivity:", &mut preferences.orbit_sensitivity),
+                ("Pan sensitivity:", &mut preferences.pan_sensitivity),
+                ("Zoom sensitivity:", &mut preferences.zoom_sensitivity),
+            ];
+            for (label, sensitivity) in sensitivities {
+                ui.label(label).on_hover_text(hover_text.clone());
+                let response = ui.add(
+                    egui::DragValue::new(sensitivity)
+                        .speed(0.01)
+                        .range(Preferences::SENSITIVITY_RANGE)
+                        .fixed_decimals(2)
+                ).on_hover_text(hover_text.clone());
+                is_changed |= is_edit_done(&response);
+                ui.end_row();
+            }
+
+            if ui.button("Reset").clicked() {
+                let default = Preferences::default();
+                preferences.orbit_sensitivity = default.orbit_sensitivity;
+                preferences.pan_sensitivity = default.pan_sensitivity;
+                preferences.zoom_sensitivity = default.zoom_sensitivity;
+                is_changed = true;
+            }
+            ui.end_row();
+        });
+    is_changed
+}
@@ -0,0 +1,125 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts, EguiPrimaryContextPass};
+
+use crate::{
+    bsar::{BsarInfos, PrfSuggestion, AASR_WARNING_DB},
+    constants::{MAX_INTEGRATION_TIME_S, MAX_PRF_HZ},
+    scene::{BsarInfosState, RxCarrierState, TxCarrierState},
+};
+
+const TEXT_COLOR: egui::Color32 = egui::Color32::from_rgb(200, 200, 200);
+const ERROR_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 120, 120);
+
+pub struct PrfSuggestionPlugin;
+
+impl Plugin for PrfSuggestionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(EguiPrimaryContextPass, prf_suggestion_ui_system);
+    }
+}
+
+fn prf_suggestion_ui_system(
+    mut contexts: EguiContexts,
+    bsar_infos_state: Res<BsarInfosState>,
+    mut tx_carrier_state: ResMut<TxCarrierState>,
+    mut rx_carrier_state: ResMut<RxCarrierState>,
+) -> Result {
+    let ctx = contexts.ctx_mut()?;
+
+    let prf_suggestion_window = egui::Window::new("Squared-Pixel PRF")
+        .resizable(false)
+        .constrain(true)
+        .collapsible(true)
+        .title_bar(true)
+        .enabled(true)
+        .default_open(false)
+        .default_pos(egui::pos2(320.0, 800.0));
+    prf_suggestion_window.show(ctx, |ui| {
+        let bsar_infos = &bsar_infos_state.inner;
+        // The states are only written when applied, not to trigger the updates every frame
+        if let Some(suggestion) = prf_suggestion_ui(ui, bsar_infos, tx_carrier_state.prf_hz, rx_carrier_state.integration_time_s) {
+            tx_carrier_state.prf_hz = suggestion.prf_hz.clamp(1.0, MAX_PRF_HZ);
+            // The integration time is no longer solved for the pixel aspect ratio
+            rx_carrier_state.fixed_pixel_aspect_ratio = false;
+            rx_carrier_state.integration_time_s = suggestion.integration_time_s.min(MAX_INTEGRATION_TIME_S);
+        }
+    });
+
+    Ok(())
+}
+
+/// Shows the PRF bounds and the suggested PRF and integration time pair,
+/// returning the suggestion when applied.
+fn prf_suggestion_ui(ui: &mut egui::Ui, bsar_infos: &BsarInfos, prf_hz: f64, integration_time_s: f64) -> Option<PrfSuggestion> {
+    let hover_text = egui::RichText::new(format!(
+        "Lowest PRF sampling the processed Doppler bandwidth with an AASR under {AASR_WARNING_DB:.0} dB,\n\
+         and highest one receiving the echoes of a pulse before the next one is transmitted"
+    ))
+        .color(TEXT_COLOR)
+        .monospace();
+    let suggestion = &bsar_infos.prf_suggestion;
+    let mut is_applied = false;
+    egui::Grid::new("prf_suggestion_grid")
+        .num_columns(2)
+        .striped(false)
+        .spacing([20.0, 5.0])
+        .show(ui, |ui| {
+            ui.label("PRF bounds: ").on_hover_text(hover_text);
+            ui.label(format!("{} - {}", hertz_text(bsar_infos.prf_min_hz), hertz_text(bsar_infos.prf_max_hz)));
+            ui.end_row();
+
+            ui.label("Current: ");
+            let text = format!("{} / {integration_time_s:.3} s", hertz_text(prf_hz));
+            // Comparisons with NaN bounds are false
+            if prf_hz < bsar_infos.prf_min_hz || prf_hz > bsar_infos.prf_max_hz {
+                ui.label(egui::RichText::new(format!("⚠ {text}")).color(ERROR_COLOR));
+            } else {
+                ui.label(text);
+            }
+            ui.end_row();
+
+            let hover_text = egui::RichText::new(
+                "PRF and integration time giving squared pixels (ground or slant as the receiver pixels),\n\
+                 the PRF 10 % above the lowest one for their processed Doppler bandwidth"
+            )
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Suggested: ").on_hover_text(hover_text);
+            if suggestion.is_valid() {
+                ui.label(format!("{} / {:.3} s", hertz_text(suggestion.prf_hz), suggestion.integration_time_s));
+            } else {
+                ui.label("-");
+            }
+            ui.end_row();
+        });
+    if !suggestion.is_valid() {
+        let text = format!("⚠ No PRF up to {} meets the AASR for squared pixels", hertz_text(MAX_PRF_HZ));
+        ui.label(egui::RichText::new(text).color(ERROR_COLOR));
+        return None;
+    }
+    if suggestion.prf_hz > bsar_infos.prf_max_hz {
+        let text = "⚠ The receive window exceeds the pulse repetition interval: range ambiguous";
+        ui.label(egui::RichText::new(text).color(ERROR_COLOR));
+    }
+    if suggestion.integration_time_s > MAX_INTEGRATION_TIME_S {
+        let text = format!("⚠ The integration time is limited to {MAX_INTEGRATION_TIME_S:.0} s");
+        ui.label(egui::RichText::new(text).color(ERROR_COLOR));
+    }
+    let hover_text = egui::RichText::new("Sets the Tx PRF and the Rx integration time to the suggested ones")
+        .color(TEXT_COLOR)
+        .monospace();
+    ui.vertical_centered(|ui| {
+        is_applied = ui.button("Apply").on_hover_text(hover_text).clicked();
+    });
+    is_applied.then_some(*suggestion)
+}
+
+fn hertz_text(value_hz: f64) -> String {
+    if !value_hz.is_finite() {
+        "-".to_string()
+    } else if value_hz >= 1e3 {
+        format!("{:.3} kHz", value_hz * 1e-3)
+    } else {
+        format!("{value_hz:.1} Hz")
+    }
+}
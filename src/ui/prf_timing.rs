@@ -0,0 +1,183 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts, EguiPrimaryContextPass};
+
+use crate::{
+    bsar::SPEED_OF_LIGHT_IN_VACUUM,
+    nadir::{bistatic_range_m, nadir_echo_bands, specular_point_m, timing_band_m, timing_band_orders},
+    scene::{BsarInfosState, RxCarrierState, TxCarrierState},
+};
+
+const TEXT_COLOR: egui::Color32 = egui::Color32::from_rgb(200, 200, 200);
+const ERROR_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 120, 120);
+const TRANSMIT_COLOR: egui::Color32 = egui::Color32::from_rgb(140, 140, 140);
+const NADIR_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 153, 0);
+const ECHO_WINDOW_COLOR: egui::Color32 = egui::Color32::from_rgb(44, 160, 44);
+/// PRF samples of the diagram bands
+const PRF_SAMPLES: usize = 64;
+/// Largest number of bands drawn per event
+const MAX_BANDS: usize = 100;
+
+pub struct PrfTimingPlugin;
+
+impl Plugin for PrfTimingPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<PrfTimingWidget>()
+            .add_systems(EguiPrimaryContextPass, prf_timing_ui_system);
+    }
+}
+
+/// PRF timing (zebra) diagram: the scene ranges blinded by the transmitted
+/// pulses and the nadir echoes versus the PRF, against the receive window.
+#[derive(Resource, Default)]
+pub struct PrfTimingWidget {
+    /// PRF interval of the diagram in Hz, centered on the Tx PRF when it
+    /// leaves it
+    pub prf_span_hz: Option<(f64, f64)>,
+}
+
+fn prf_timing_ui_system(
+    mut contexts: EguiContexts,
+    mut prf_timing_widget: ResMut<PrfTimingWidget>,
+    bsar_infos_state: Res<BsarInfosState>,
+    tx_carrier_state: Res<TxCarrierState>,
+    rx_carrier_state: Res<RxCarrierState>,
+) -> Result {
+    let ctx = contexts.ctx_mut()?;
+
+    let prf_timing_window = egui::Window::new("PRF Timing Diagram")
+        .resizable(false)
+        .constrain(true)
+        .collapsible(true)
+        .title_bar(true)
+        .enabled(true)
+        .default_open(false)
+        .default_pos(egui::pos2(320.0, 880.0));
+    prf_timing_window.show(ctx, |ui| {
+        prf_timing_ui(ui, &mut prf_timing_widget, &bsar_infos_state, &tx_carrier_state, &rx_carrier_state);
+    });
+
+    Ok(())
+}
+
+fn prf_timing_ui(
+    ui: &mut egui::Ui,
+    widget: &mut PrfTimingWidget,
+    bsar_infos_state: &BsarInfosState,
+    tx_carrier_state: &TxCarrierState,
+    rx_carrier_state: &RxCarrierState,
+) {
+    let bsar_infos = &bsar_infos_state.inner;
+    let (range_min_m, range_max_m) = (bsar_infos.range_min_m, bsar_infos.range_max_m);
+    if range_min_m.is_nan() || range_max_m.is_nan() || range_min_m > range_max_m {
+        ui.label(egui::RichText::new("⚠ No receive window: the footprints are empty").color(ERROR_COLOR));
+        return;
+    }
+    let prf_hz = tx_carrier_state.prf_hz;
+    let pulse_duration_s = tx_carrier_state.pulse_duration_us * 1e-6;
+    let (tx_position_m, rx_position_m) = (&tx_carrier_state.inner.position_m, &rx_carrier_state.inner.position_m);
+    let nadir_range_m = specular_point_m(tx_position_m, rx_position_m)
+        .map(|point_m| bistatic_range_m(tx_position_m, rx_position_m, &point_m));
+
+    // Half to twice the PRF, kept while the PRF is dragged within it
+    let center_on_prf = || (0.5 * prf_hz, 2.0 * prf_hz);
+    let (prf_min_hz, prf_max_hz) = match widget.prf_span_hz {
+        Some((prf_min_hz, prf_max_hz)) if (prf_min_hz..=prf_max_hz).contains(&prf_hz) => (prf_min_hz, prf_max_hz),
+        _ => *widget.prf_span_hz.insert(center_on_prf()),
+    };
+    // The receive window with its own width (at least a few pulses) around it
+    let margin_m = (range_max_m - range_min_m).max(4.0 * SPEED_OF_LIGHT_IN_VACUUM * pulse_duration_s);
+    let (plot_range_min_m, plot_range_max_m) = (range_min_m - margin_m, range_max_m + margin_m);
+
+    egui_plot::Plot::new("prf_timing_plot")
+        .width(360.0)
+        .height(240.0)
+        .x_axis_label("PRF [Hz]")
+        .y_axis_label("Bistatic range [km]")
+        .legend(egui_plot::Legend::default().follow_insertion_order(true))
+        .allow_drag(false)
+        .allow_zoom(false)
+        .allow_scroll(false)
+        .show(ui, |plot_ui| {
+            plot_ui.polygon(
+                egui_plot::Polygon::new(
+                    "Receive window",
+                    vec![
+                        [prf_min_hz, 1e-3 * range_min_m],
+                        [prf_max_hz, 1e-3 * range_min_m],
+                        [prf_max_hz, 1e-3 * range_max_m],
+                        [prf_min_hz, 1e-3 * range_max_m],
+                    ]
+                )
+                .fill_color(ECHO_WINDOW_COLOR.gamma_multiply(0.3))
+                .stroke(egui::Stroke::new(1.0, ECHO_WINDOW_COLOR))
+            );
+            let events = [("Transmitted pulses", Some(0.0), TRANSMIT_COLOR), ("Nadir echoes", nadir_range_m, NADIR_COLOR)];
+            for (name, offset_range_m, color) in events {
+                let Some(offset_range_m) = offset_range_m else {
+                    continue;
+                };
+                let Some(orders) = timing_band_orders(
+                    offset_range_m, pulse_duration_s, prf_min_hz, prf_max_hz, plot_range_min_m, plot_range_max_m
+                ) else {
+                    continue;
+                };
+                // Bands split into quadrilaterals, polygons being filled convex
+                for order in orders.take(MAX_BANDS) {
+                    let band_km = |i: usize| {
+                        let band_prf_hz = prf_min_hz + (prf_max_hz - prf_min_hz) * i as f64 / (PRF_SAMPLES - 1) as f64;
+                        let (band_min_m, band_max_m) = timing_band_m(offset_range_m, pulse_duration_s, order, band_prf_hz);
+                        (band_prf_hz, 1e-3 * band_min_m, 1e-3 * band_max_m)
+                    };
+                    for i in 0..PRF_SAMPLES - 1 {
+                        let ((prf_0_hz, min_0_km, max_0_km), (prf_1_hz, min_1_km, max_1_km)) = (band_km(i), band_km(i + 1));
+                        let is_visible = max_0_km.max(max_1_km) > 1e-3 * plot_range_min_m
+                            && min_0_km.min(min_1_km) < 1e-3 * plot_range_max_m;
+                        if !is_visible {
+                            continue;
+                        }
+                        plot_ui.polygon(
+                            egui_plot::Polygon::new(
+                                name,
+                                vec![[prf_0_hz, min_0_km], [prf_1_hz, min_1_km], [prf_1_hz, max_1_km], [prf_0_hz, max_0_km]]
+                            )
+                            .fill_color(color.gamma_multiply(0.6))
+                            .stroke(egui::Stroke::NONE)
+                        );
+                    }
+                }
+            }
+            plot_ui.vline(egui_plot::VLine::new("PRF", prf_hz).color(egui::Color32::WHITE).width(1.5));
+            plot_ui.set_plot_bounds(egui_plot::PlotBounds::from_min_max(
+                [prf_min_hz, 1e-3 * plot_range_min_m],
+                [prf_max_hz, 1e-3 * plot_range_max_m],
+            ));
+        });
+
+    let hover_text = egui::RichText::new("Centers the diagram on the Tx PRF, from half to twice it")
+        .color(TEXT_COLOR)
+        .monospace();
+    if ui.button("Center on the PRF").on_hover_text(hover_text).clicked() {
+        widget.prf_span_hz = Some(center_on_prf());
+    }
+
+    // Conflicts at the Tx PRF, in the receive window
+    let hover_text = egui::RichText::new("A receiver sharing the Tx antenna (monostatic) is blind while transmitting")
+        .color(TEXT_COLOR)
+        .monospace();
+    let transmit_bands = nadir_echo_bands(0.0, pulse_duration_s, prf_hz, range_min_m, range_max_m);
+    let nadir_bands = nadir_range_m
+        .map(|nadir_range_m| nadir_echo_bands(nadir_range_m, pulse_duration_s, prf_hz, range_min_m, range_max_m))
+        .unwrap_or_default();
+    if transmit_bands.is_empty() && nadir_bands.is_empty() {
+        ui.label(egui::RichText::new("No transmitted pulse nor nadir echo in the receive window").color(TEXT_COLOR));
+    }
+    for band in transmit_bands.iter() {
+        let text = format!("⚠ Pulse n+{} transmitted over {:.1} - {:.1} m", band.order, band.range_min_m, band.range_max_m);
+        ui.label(egui::RichText::new(text).color(ERROR_COLOR)).on_hover_text(hover_text.clone());
+    }
+    for band in nadir_bands.iter() {
+        let text = format!("⚠ Nadir echo of pulse n+{} over {:.1} - {:.1} m", band.order, band.range_min_m, band.range_max_m);
+        ui.label(egui::RichText::new(text).color(ERROR_COLOR));
+    }
+}
@@ -0,0 +1,264 @@
+use bevy::{math::DVec3, prelude::*};
+use bevy_egui::{egui, EguiContexts, EguiPrimaryContextPass};
+
+use crate::{
+    bsar::SPEED_OF_LIGHT_IN_VACUUM,
+    expression,
+    propagation::{Atmosphere, BistaticDelays, PathDelays, Polarization},
+    scene::{RxCarrierState, TxCarrierState},
+    ui::GeoReferenceWidget,
+};
+
+const TEXT_COLOR: egui::Color32 = egui::Color32::from_rgb(200, 200, 200);
+const ERROR_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 120, 120);
+
+pub struct PropagationPlugin;
+
+impl Plugin for PropagationPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<PropagationWidget>()
+            .add_systems(EguiPrimaryContextPass, propagation_ui_system);
+    }
+}
+
+/// Tropospheric and ionospheric delays along the Tx and Rx slant paths of the
+/// current geometry, the implied bistatic range bias, and the Faraday rotation
+/// of the selected polarization.
+#[derive(Resource, Default)]
+pub struct PropagationWidget {
+    /// Scene atmosphere, its latitude and height following the "Geo Infos" scene center
+    pub atmosphere: Atmosphere,
+    /// Delays of the current geometry
+    pub delays: BistaticDelays,
+    /// Polarization of the bistatic channel
+    pub polarization: Polarization,
+    /// Two-way (Tx path + Rx path) Faraday rotation in degrees of the current geometry
+    pub faraday_rotation_deg: f64,
+}
+
+impl PropagationWidget {
+    /// Updates the delays of the current geometry.
+    pub fn update(
+        &mut self,
+        geo_reference_widget: &GeoReferenceWidget,
+        tx_carrier_state: &TxCarrierState,
+        rx_carrier_state: &RxCarrierState,
+    ) {
+        let scene_center = geo_reference_widget.scene_origin.origin_as_geographic_point();
+        self.atmosphere.latitude_deg = scene_center.lat_deg();
+        self.atmosphere.height_m = scene_center.height_m();
+        let frequency_hz = tx_carrier_state.center_frequency_ghz * 1e9; // Convert GHz to Hz
+        let path_delays = |position_m: &DVec3| -> PathDelays {
+            let elevation_deg = (position_m.z / position_m.length().max(f64::EPSILON)).clamp(-1.0, 1.0).asin().to_degrees();
+            self.atmosphere.slant_path_delays(elevation_deg, position_m.z, frequency_hz)
+        };
+        self.delays = BistaticDelays {
+            tx: path_delays(&tx_carrier_state.inner.position_m),
+            rx: path_delays(&rx_carrier_state.inner.position_m),
+        };
+        let faraday_rotation_rad = |position_m: &DVec3| -> f64 {
+            self.atmosphere.faraday_rotation_rad(position_m, position_m.z, frequency_hz)
+        };
+        self.faraday_rotation_deg = (
+            faraday_rotation_rad(&tx_carrier_state.inner.position_m)
+            + faraday_rotation_rad(&rx_carrier_state.inner.position_m)
+        ).to_degrees();
+    }
+}
+
+fn propagation_ui_system(
+    mut contexts: EguiContexts,
+    mut propagation_widget: ResMut<PropagationWidget>,
+    geo_reference_widget: Res<GeoReferenceWidget>,
+    tx_carrier_state: Res<TxCarrierState>,
+    rx_carrier_state: Res<RxCarrierState>,
+) -> Result {
+    let ctx = contexts.ctx_mut()?;
+
+    let propagation_window = egui::Window::new("Propagation")
+        .resizable(false)
+        .constrain(true)
+        .collapsible(true)
+        .title_bar(true)
+        .enabled(true)
+        .default_open(false)
+        .default_pos(egui::pos2(320.0, 320.0));
+    propagation_window.show(ctx, |ui| {
+        propagation_widget.update(&geo_reference_widget, &tx_carrier_state, &rx_carrier_state);
+        propagation_ui(ui, &mut propagation_widget, tx_carrier_state.bandwidth_mhz * 1e6); // Convert MHz to Hz
+    });
+
+    Ok(())
+}
+
+fn propagation_ui(ui: &mut egui::Ui, widget: &mut PropagationWidget, bandwidth_hz: f64) {
+    ui.vertical_centered(|ui| ui.label(egui::RichText::new("ATMOSPHERE").strong()));
+    ui.separator();
+
+    egui::Grid::new("propagation_atmosphere_grid")
+        .num_columns(2)
+        .striped(false)
+        .spacing([20.0, 5.0])
+        .show(ui, |ui| {
+            let atmosphere = &mut widget.atmosphere;
+            let hover_text = egui::RichText::new("Sets the surface pressure at the scene (500 - 1100 hPa)")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Pressure: ").on_hover_text(hover_text.clone());
+            ui.add(
+                egui::DragValue::new(&mut atmosphere.pressure_hpa)
+                    .custom_parser(expression::parse)
+                    .update_while_editing(false)
+                    .speed(0.5)
+                    .range(500.0..=1100.0)
+                    .fixed_decimals(2)
+                    .suffix(" hPa")
+            ).on_hover_text(hover_text);
+            ui.end_row();
+
+            let hover_text = egui::RichText::new("Sets the surface temperature at the scene (200 - 330 K)")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Temperature: ").on_hover_text(hover_text.clone());
+            ui.add(
+                egui::DragValue::new(&mut atmosphere.temperature_k)
+                    .custom_parser(expression::parse)
+                    .update_while_editing(false)
+                    .speed(0.1)
+                    .range(200.0..=330.0)
+                    .fixed_decimals(2)
+                    .suffix(" K")
+            ).on_hover_text(hover_text);
+            ui.end_row();
+
+            let hover_text = egui::RichText::new("Sets the surface relative humidity at the scene (0 - 100 %)")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Humidity: ").on_hover_text(hover_text.clone());
+            let mut humidity_percent = 100.0 * atmosphere.relative_humidity;
+            if ui.add(
+                egui::DragValue::new(&mut humidity_percent)
+                    .custom_parser(expression::parse)
+                    .update_while_editing(false)
+                    .speed(0.5)
+                    .range(0.0..=100.0)
+                    .fixed_decimals(1)
+                    .suffix(" %")
+            ).on_hover_text(hover_text).changed() {
+                atmosphere.relative_humidity = 0.01 * humidity_percent;
+            }
+            ui.end_row();
+
+            let hover_text = egui::RichText::new("Sets the vertical Total Electron Content (0 - 300 TECU, 1 TECU = 10¹⁶ e⁻/m²),\nonly crossed by carriers above the ionosphere (350 km)")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("VTEC: ").on_hover_text(hover_text.clone());
+            ui.add(
+                egui::DragValue::new(&mut atmosphere.vtec_tecu)
+                    .custom_parser(expression::parse)
+                    .update_while_editing(false)
+                    .speed(0.1)
+                    .range(0.0..=300.0)
+                    .fixed_decimals(1)
+                    .suffix(" TECU")
+            ).on_hover_text(hover_text);
+            ui.end_row();
+        });
+
+    ui.separator();
+    ui.vertical_centered(|ui| ui.label(egui::RichText::new("PATH DELAYS").strong()));
+    ui.separator();
+
+    let delays = &widget.delays;
+    // Excess path length in m and delay in ns
+    let delay_text = |delay_m: f64| format!("{delay_m:.3} m ({:.2} ns)", 1e9 * delay_m / SPEED_OF_LIGHT_IN_VACUUM);
+    egui::Grid::new("propagation_delays_grid")
+        .num_columns(3)
+        .striped(false)
+        .spacing([20.0, 5.0])
+        .show(ui, |ui| {
+            ui.label("");
+            ui.label(egui::RichText::new("Tx path").strong());
+            ui.label(egui::RichText::new("Rx path").strong());
+            ui.end_row();
+
+            ui.label("Troposphere: ");
+            ui.label(delay_text(delays.tx.tropospheric_m));
+            ui.label(delay_text(delays.rx.tropospheric_m));
+            ui.end_row();
+
+            ui.label("Ionosphere: ");
+            ui.label(delay_text(delays.tx.ionospheric_m));
+            ui.label(delay_text(delays.rx.ionospheric_m));
+            ui.end_row();
+        });
+    egui::Grid::new("propagation_bias_grid")
+        .num_columns(2)
+        .striped(false)
+        .spacing([20.0, 5.0])
+        .show(ui, |ui| {
+            let hover_text = egui::RichText::new("Bistatic range bias: sum of the Tx and Rx excess path lengths")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Range bias: ").on_hover_text(hover_text);
+            ui.label(delay_text(delays.range_bias_m()));
+            ui.end_row();
+
+            let hover_text = egui::RichText::new("Differential delay between the Tx and the Rx paths")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Differential delay: ").on_hover_text(hover_text);
+            ui.label(delay_text(delays.differential_delay_m()));
+            ui.end_row();
+
+            // A bistatic range bias b shifts the image by b / (2 cos(β/2)) along
+            // the bisector, i.e. b·B/c slant range resolution cells
+            let hover_text = egui::RichText::new("Image shift due to the range bias, in slant range resolution cells")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Shift in resolution cells: ").on_hover_text(hover_text);
+            ui.label(format!("{:.2}", delays.range_bias_m() * bandwidth_hz / SPEED_OF_LIGHT_IN_VACUUM));
+            ui.end_row();
+        });
+
+    ui.separator();
+    ui.vertical_centered(|ui| ui.label(egui::RichText::new("FARADAY ROTATION").strong()));
+    ui.separator();
+
+    egui::Grid::new("propagation_faraday_grid")
+        .num_columns(2)
+        .striped(false)
+        .spacing([20.0, 5.0])
+        .show(ui, |ui| {
+            let hover_text = egui::RichText::new("Sets the polarization of the bistatic channel (Tx, Rx)")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Polarization: ").on_hover_text(hover_text);
+            egui::ComboBox::from_id_salt("propagation_polarization")
+                .selected_text(widget.polarization.label())
+                .show_ui(ui, |ui| {
+                    for polarization in Polarization::ALL {
+                        ui.selectable_value(&mut widget.polarization, polarization, polarization.label());
+                    }
+                });
+            ui.end_row();
+
+            let hover_text = egui::RichText::new("Two-way rotation of the polarization plane through the ionosphere
+(Tx path + Rx path, axial dipole geomagnetic field)")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Rotation: ").on_hover_text(hover_text);
+            ui.label(format!("{:.2}°", widget.faraday_rotation_deg));
+            ui.end_row();
+        });
+    if let Some(threshold_deg) = widget.polarization.faraday_rotation_threshold_deg()
+        && widget.faraday_rotation_deg.abs() > threshold_deg
+    {
+        let text = format!(
+            "⚠ Significant for {} (above {threshold_deg:.0}°): the channel needs a Faraday rotation correction",
+            widget.polarization.label()
+        );
+        ui.label(egui::RichText::new(text).color(ERROR_COLOR));
+    }
+}
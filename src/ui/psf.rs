@@ -0,0 +1,200 @@
+use bevy::{math::DVec3, prelude::*};
+use bevy_egui::{egui, EguiContexts, EguiPrimaryContextPass};
+
+use crate::{
+    bsar::{psf::ImpulseResponse, BsarInfos},
+    scene::{BsarInfosState, RxCarrierState, TxCarrierState},
+    ui::UpdateStage,
+};
+
+const TEXT_COLOR: egui::Color32 = egui::Color32::from_rgb(200, 200, 200);
+/// Colors of the cuts, as the iso-range and iso-Doppler contours they cross
+const RANGE_CUT_COLOR: egui::Color32 = egui::Color32::from_rgb(31, 119, 180);
+const DOPPLER_CUT_COLOR: egui::Color32 = egui::Color32::from_rgb(214, 39, 40);
+/// Half side of the patch in the coarsest resolution of the bisector formulas
+const PSF_HALF_EXTENT_RESOLUTIONS: f64 = 4.0;
+/// Pixels per side of the image
+const PSF_SIZE: usize = 101;
+/// Displayed dynamic range: `PSF_DB_MIN` dB maps to black, 0 dB to white.
+const PSF_DB_MIN: f64 = -30.0;
+/// Side of the square plot area, in points.
+const PSF_PLOT_SIDE: f32 = 320.0;
+
+pub struct PsfPlugin;
+
+impl Plugin for PsfPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<PsfWidget>()
+            // Computed for the system updated by the update systems
+            .add_systems(
+                PostUpdate,
+                update_psf
+                    .in_set(UpdateStage::Derived)
+                    .after(super::tx_panel::update_bsar_system)
+            )
+            .add_systems(EguiPrimaryContextPass, psf_ui_system);
+    }
+}
+
+/// Impulse response of a point target at the scene center, with its -3 dB
+/// widths measured along the ground range and lateral directions.
+#[derive(Resource, Default)]
+pub struct PsfWidget {
+    pub is_enabled: bool,
+    pub response: Option<ImpulseResponse>,
+    /// Ground range and lateral directions (ENU) of the measured widths
+    range_direction: Option<DVec3>,
+    lateral_direction: Option<DVec3>,
+    range_width_m: Option<f64>,
+    lateral_width_m: Option<f64>,
+    texture: Option<egui::TextureHandle>,
+    needs_update: bool,
+}
+
+/// Synthesizes the impulse response when the system changes.
+fn update_psf(
+    mut psf_widget: ResMut<PsfWidget>,
+    bsar_infos_state: Res<BsarInfosState>,
+    tx_carrier_state: Res<TxCarrierState>,
+    rx_carrier_state: Res<RxCarrierState>,
+) {
+    if !psf_widget.is_enabled {
+        return;
+    }
+    // The widget is written by its window every frame: only a new system is computed
+    if !psf_widget.needs_update && !bsar_infos_state.is_changed() {
+        return;
+    }
+    let widget = psf_widget.bypass_change_detection();
+    let bsar_infos = &bsar_infos_state.inner;
+    let half_extent_m = PSF_HALF_EXTENT_RESOLUTIONS * bsar_infos.ground_range_resolution_m.max(bsar_infos.ground_lateral_resolution_m);
+    widget.response = (half_extent_m.is_finite() && half_extent_m > 0.0).then(|| {
+        let geometry = super::ambiguity::ambiguity_geometry(&tx_carrier_state, &rx_carrier_state, bsar_infos);
+        ImpulseResponse::synthesize(&geometry, half_extent_m, PSF_SIZE)
+    });
+    widget.range_direction = bsar_infos.ground_range_direction();
+    widget.lateral_direction = bsar_infos.ground_lateral_direction();
+    let width = |direction: Option<DVec3>| widget.response.as_ref()?.half_power_width_m(&direction?);
+    widget.range_width_m = width(widget.range_direction);
+    widget.lateral_width_m = width(widget.lateral_direction);
+    widget.texture = None;
+    widget.needs_update = false;
+}
+
+fn render_psf_image(response: &ImpulseResponse) -> egui::ColorImage {
+    let grey: Vec<u8> = response.amplitude.iter()
+        .map(|&amplitude| (((20.0 * amplitude.log10() - PSF_DB_MIN) / -PSF_DB_MIN).clamp(0.0, 1.0) * 255.0).round() as u8)
+        .collect();
+    egui::ColorImage::from_gray([response.size, response.size], &grey)
+}
+
+fn psf_ui_system(
+    mut contexts: EguiContexts,
+    mut psf_widget: ResMut<PsfWidget>,
+    bsar_infos_state: Res<BsarInfosState>,
+) -> Result {
+    let ctx = contexts.ctx_mut()?;
+
+    let psf_window = egui::Window::new("Impulse Response")
+        .resizable(false)
+        .constrain(true)
+        .collapsible(true)
+        .title_bar(true)
+        .enabled(true)
+        .default_open(false)
+        .default_pos(egui::pos2(320.0, 1320.0));
+    psf_window.show(ctx, |ui| {
+        psf_ui(ui, &mut psf_widget, &bsar_infos_state.inner);
+    });
+
+    Ok(())
+}
+
+fn psf_ui(ui: &mut egui::Ui, widget: &mut PsfWidget, bsar_infos: &BsarInfos) {
+    let hover_text = egui::RichText::new("Focuses a point target at the scene center by backprojection, the carriers following\ntheir trajectories over the integration time, over a ground patch around it")
+        .color(TEXT_COLOR)
+        .monospace();
+    if ui.checkbox(&mut widget.is_enabled, "Compute").on_hover_text(hover_text).changed() {
+        // Computed again once enabled
+        widget.needs_update = true;
+    }
+    if !widget.is_enabled {
+        return;
+    }
+    let Some(response) = &widget.response else {
+        ui.label(egui::RichText::new("No resolution cell: the geometry is degenerate").color(TEXT_COLOR));
+        return;
+    };
+
+    let hover_text = egui::RichText::new("Width of the main lobe at -3 dB measured on the image, along the ground range\nand lateral directions (across the iso-range and iso-Doppler lines), against the\nformula of the BSAR infos")
+        .color(TEXT_COLOR)
+        .monospace();
+    egui::Grid::new("psf_grid")
+        .num_columns(2)
+        .striped(false)
+        .spacing([20.0, 5.0])
+        .show(ui, |ui| {
+            let widths = [
+                ("Ground range width:", widget.range_width_m, bsar_infos.ground_range_resolution_m),
+                ("Ground lateral width:", widget.lateral_width_m, bsar_infos.ground_lateral_resolution_m),
+            ];
+            for (name, width_m, resolution_m) in widths {
+                ui.label(name).on_hover_text(hover_text.clone());
+                match width_m {
+                    Some(width_m) => ui.label(format!("{width_m:.3} m (formula {resolution_m:.3} m)")),
+                    None => ui.label("-"),
+                };
+                ui.end_row();
+            }
+
+            ui.label("Pixel size:");
+            ui.label(format!("{:.3} m", response.pitch_m()));
+            ui.end_row();
+        });
+
+    let texture = widget.texture.get_or_insert_with(|| {
+        ui.ctx().load_texture("psf_image", render_psf_image(response), egui::TextureOptions::LINEAR)
+    });
+    let half_extent_m = response.half_extent_m;
+    let direction_line = |direction: Option<DVec3>| -> Vec<[f64; 2]> {
+        // Through the patch, clipped to its edges
+        let Some(direction) = direction.and_then(|direction| direction.truncate().try_normalize()) else {
+            return Vec::new();
+        };
+        let end = half_extent_m / direction.x.abs().max(direction.y.abs()) * direction;
+        vec![[-end.x, -end.y], [end.x, end.y]]
+    };
+    egui_plot::Plot::new("psf_plot")
+        .width(PSF_PLOT_SIDE)
+        .height(PSF_PLOT_SIDE)
+        .data_aspect(1.0) // Equal Easting/Northing scales
+        .x_axis_label("Easting offset [m]")
+        .y_axis_label("Northing offset [m]")
+        .legend(egui_plot::Legend::default().follow_insertion_order(true))
+        .allow_drag(false)
+        .allow_zoom(false)
+        .allow_scroll(false)
+        .show(ui, |plot_ui| {
+            plot_ui.image(egui_plot::PlotImage::new(
+                "Impulse response",
+                texture.id(),
+                egui_plot::PlotPoint::new(0.0, 0.0),
+                egui::vec2(2.0 * half_extent_m as f32, 2.0 * half_extent_m as f32),
+            ));
+            plot_ui.line(
+                egui_plot::Line::new("Range cut", direction_line(widget.range_direction))
+                    .color(RANGE_CUT_COLOR)
+                    .style(egui_plot::LineStyle::dotted_dense())
+            );
+            plot_ui.line(
+                egui_plot::Line::new("Doppler cut", direction_line(widget.lateral_direction))
+                    .color(DOPPLER_CUT_COLOR)
+                    .style(egui_plot::LineStyle::dotted_dense())
+            );
+        });
+    ui.label(
+        egui::RichText::new(format!("Amplitude from {PSF_DB_MIN:.0} dB (black) to 0 dB (white)"))
+            .color(TEXT_COLOR)
+    );
+}
@@ -0,0 +1,404 @@
+use bevy::{math::DVec3, prelude::*};
+use bevy_egui::{egui, EguiContexts, EguiPrimaryContextPass};
+
+use crate::{
+    download::{SaveFileType, SaveRequest},
+    expression,
+    raw_data::{BackProjectionImage, PointTarget, RawData, RawDataParameters},
+    scenario::ScenarioMetadata,
+    scene::{BsarInfosState, RxCarrierState, TxCarrierState},
+    ui::{menu::SAVE_ICON, ScenarioWidget, Toasts},
+};
+
+const TEXT_COLOR: egui::Color32 = egui::Color32::from_rgb(200, 200, 200);
+const ERROR_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 120, 120);
+/// Suggested file names of the raw data exports
+const RAW_DATA_EXPORT_FILE_NAME: &str = "bsargeom_raw_data.npy";
+const RAW_DATA_PARAMETERS_EXPORT_FILE_NAME: &str = "bsargeom_raw_data_parameters.csv";
+/// Maximum number of point targets
+const MAX_POINT_TARGETS: usize = 64;
+/// Pixels per side of the back-projection quicklook
+const QUICKLOOK_SIZE: usize = 128;
+/// Displayed dynamic range: `QUICKLOOK_DB_MIN` dB maps to black, 0 dB to white.
+const QUICKLOOK_DB_MIN: f64 = -30.0;
+/// Side of the quicklook window's square plot area, in points.
+const QUICKLOOK_PLOT_SIDE: f32 = 360.0;
+
+pub struct RawDataPlugin;
+
+impl Plugin for RawDataPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<RawDataWidget>()
+            .add_systems(EguiPrimaryContextPass, raw_data_ui_system);
+    }
+}
+
+/// Point-scatterer raw data simulation: user-placed point targets and the last
+/// simulated phase history.
+#[derive(Resource)]
+pub struct RawDataWidget {
+    /// Point targets in the scene (ENU) frame
+    pub targets: Vec<PointTarget>,
+    /// Fast-time sampling frequency over bandwidth ratio
+    pub range_oversampling: f64,
+    /// Last simulated phase history
+    pub raw_data: Option<RawData>,
+    /// Index of the target the quicklook patch is centered on
+    pub quicklook_target: usize,
+    /// Half side of the quicklook patch in meters
+    pub quicklook_half_extent_m: f64,
+    /// Last back-projected quicklook and its texture
+    pub quicklook: Option<BackProjectionImage>,
+    quicklook_texture: Option<egui::TextureHandle>,
+    is_quicklook_opened: bool,
+    /// Result of the last simulation or export click
+    status: Option<Result<String, String>>,
+    /// Export in flight
+    save_request: Option<SaveRequest>,
+}
+
+impl Default for RawDataWidget {
+    fn default() -> Self {
+        Self {
+            targets: vec![PointTarget::default()],
+            range_oversampling: 1.2,
+            raw_data: None,
+            quicklook_target: 0,
+            quicklook_half_extent_m: 10.0,
+            quicklook: None,
+            quicklook_texture: None,
+            is_quicklook_opened: false,
+            status: None,
+            save_request: None,
+        }
+    }
+}
+
+impl RawDataWidget {
+    /// Simulates the phase history of the point targets for the current geometry,
+    /// over the integration time centered on the current carriers positions.
+    pub fn simulate(
+        &mut self,
+        tx_carrier_state: &TxCarrierState,
+        rx_carrier_state: &RxCarrierState,
+        bsar_infos_state: &BsarInfosState,
+    ) {
+        let parameters = RawDataParameters {
+            tx_position_m: tx_carrier_state.inner.position_m,
+            tx_velocity_mps: tx_carrier_state.inner.velocity_vector_mps,
+            rx_position_m: rx_carrier_state.inner.position_m,
+            rx_velocity_mps: rx_carrier_state.inner.velocity_vector_mps,
+            center_frequency_hz: tx_carrier_state.center_frequency_ghz * 1e9, // Convert GHz to Hz
+            bandwidth_hz: tx_carrier_state.bandwidth_mhz * 1e6, // Convert MHz to Hz
+            prf_hz: tx_carrier_state.prf_hz,
+            integration_time_s: bsar_infos_state.inner.integration_time_s,
+            range_oversampling: self.range_oversampling,
+        };
+        match RawData::simulate(&parameters, &self.targets) {
+            Ok(raw_data) => {
+                self.status = Some(Ok(format!(
+                    "{} pulses × {} samples ({:.1} MiB)",
+                    raw_data.num_pulses,
+                    raw_data.num_samples,
+                    raw_data.size_bytes() as f64 / (1024.0 * 1024.0)
+                )));
+                self.raw_data = Some(raw_data);
+            }
+            Err(error) => {
+                self.status = Some(Err(format!("Simulation failed: {error}")));
+                self.raw_data = None;
+            }
+        }
+        // A new phase history invalidates the previous quicklook
+        self.quicklook = None;
+        self.quicklook_texture = None;
+    }
+
+    /// Back-projects the simulated phase history over the quicklook patch,
+    /// centered on the selected target.
+    pub fn backproject(&mut self) {
+        let Some(raw_data) = &self.raw_data else {
+            return;
+        };
+        let center_m = self.targets.get(self.quicklook_target)
+            .map_or(DVec3::ZERO, |target| target.position_m);
+        self.quicklook = Some(raw_data.backproject(&center_m, self.quicklook_half_extent_m, QUICKLOOK_SIZE));
+        self.quicklook_texture = None;
+        self.is_quicklook_opened = true;
+    }
+}
+
+/// Greyscale intensity image of the quicklook: `QUICKLOOK_DB_MIN` dB is black, 0 dB white.
+fn render_quicklook_image(image: &BackProjectionImage) -> egui::ColorImage {
+    let grey: Vec<u8> = image.data_db.iter()
+        .map(|&db| (((db - QUICKLOOK_DB_MIN) / -QUICKLOOK_DB_MIN).clamp(0.0, 1.0) * 255.0).round() as u8)
+        .collect();
+    egui::ColorImage::from_gray([image.size, image.size], &grey)
+}
+
+fn raw_data_ui_system(
+    mut contexts: EguiContexts,
+    mut raw_data_widget: ResMut<RawDataWidget>,
+    tx_carrier_state: Res<TxCarrierState>,
+    rx_carrier_state: Res<RxCarrierState>,
+    bsar_infos_state: Res<BsarInfosState>,
+    scenario_widget: Res<ScenarioWidget>,
+    mut toasts: ResMut<Toasts>,
+) -> Result {
+    let ctx = contexts.ctx_mut()?;
+
+    if let Some(status) = SaveRequest::drive(&mut raw_data_widget.save_request, ctx, &mut toasts) {
+        raw_data_widget.status = Some(Ok(status));
+    }
+
+    let raw_data_window = egui::Window::new("Raw Data")
+        .resizable(false)
+        .constrain(true)
+        .collapsible(true)
+        .title_bar(true)
+        .enabled(true)
+        .default_open(false)
+        .default_pos(egui::pos2(320.0, 80.0));
+    raw_data_window.show(ctx, |ui| {
+        raw_data_ui(ui, &mut raw_data_widget, &tx_carrier_state, &rx_carrier_state, &bsar_infos_state, &scenario_widget.metadata);
+    });
+
+    let widget = &mut *raw_data_widget; // Borrows the fields separately
+    if widget.is_quicklook_opened && let Some(image) = &widget.quicklook {
+        let texture = widget.quicklook_texture.get_or_insert_with(|| {
+            ctx.load_texture("back_projection_quicklook", render_quicklook_image(image), egui::TextureOptions::LINEAR)
+        }).clone();
+        let extent = 2.0 * image.half_extent_m;
+        let center_m = image.center_m;
+        let mut is_opened = true;
+        egui::Window::new("Back-Projection Quicklook")
+            .open(&mut is_opened)
+            .resizable(false)
+            .collapsible(true)
+            .default_pos(egui::pos2(320.0, 420.0))
+            .show(ctx, |ui| {
+                egui_plot::Plot::new("back_projection_quicklook_plot")
+                    .width(QUICKLOOK_PLOT_SIDE)
+                    .height(QUICKLOOK_PLOT_SIDE)
+                    .data_aspect(1.0) // Equal Easting/Northing scales
+                    .x_axis_label("Easting [m]")
+                    .y_axis_label("Northing [m]")
+                    .show(ui, |plot_ui| {
+                        plot_ui.image(egui_plot::PlotImage::new(
+                            "Back-projected intensity",
+                            texture.id(),
+                            egui_plot::PlotPoint::new(center_m.x, center_m.y),
+                            egui::vec2(extent as f32, extent as f32),
+                        ));
+                    });
+                ui.label(
+                    egui::RichText::new(format!("Intensity from {QUICKLOOK_DB_MIN:.0} dB (black) to 0 dB (white)"))
+                        .color(TEXT_COLOR)
+                );
+            });
+        widget.is_quicklook_opened = is_opened;
+    }
+
+    Ok(())
+}
+
+fn raw_data_ui(
+    ui: &mut egui::Ui,
+    widget: &mut RawDataWidget,
+    tx_carrier_state: &TxCarrierState,
+    rx_carrier_state: &RxCarrierState,
+    bsar_infos_state: &BsarInfosState,
+    metadata: &ScenarioMetadata,
+) {
+    ui.vertical_centered(|ui| ui.label(egui::RichText::new("POINT TARGETS").strong()));
+    ui.separator();
+
+    let position_drag_value = |value: &mut f64| {
+        egui::DragValue::new(value)
+            .custom_parser(expression::parse)
+            .update_while_editing(false)
+            .speed(1.0)
+            .range(-1e5..=1e5)
+            .fixed_decimals(1)
+            .suffix(" m")
+    };
+    let mut removed_target = None;
+    egui::Grid::new("raw_data_targets_grid")
+        .num_columns(5)
+        .striped(false)
+        .spacing([10.0, 5.0])
+        .show(ui, |ui| {
+            ui.label("East");
+            ui.label("North");
+            ui.label("Up");
+            ui.label("Amplitude");
+            ui.end_row();
+            for (i, target) in widget.targets.iter_mut().enumerate() {
+                ui.add(position_drag_value(&mut target.position_m.x));
+                ui.add(position_drag_value(&mut target.position_m.y));
+                ui.add(position_drag_value(&mut target.position_m.z));
+                ui.add(
+                    egui::DragValue::new(&mut target.amplitude)
+                        .custom_parser(expression::parse)
+                        .update_while_editing(false)
+                        .speed(0.01)
+                        .range(0.0..=1000.0)
+                        .fixed_decimals(2)
+                );
+                if ui.button("✖").on_hover_text("Removes this target").clicked() {
+                    removed_target = Some(i);
+                }
+                ui.end_row();
+            }
+        });
+    if let Some(i) = removed_target {
+        widget.targets.remove(i);
+    }
+    if ui
+        .add_enabled(widget.targets.len() < MAX_POINT_TARGETS, egui::Button::new("Add target"))
+        .on_hover_text(
+            egui::RichText::new("Adds a point target next to the last one (scene frame, ENU)")
+                .color(TEXT_COLOR)
+                .monospace()
+        )
+        .clicked()
+    {
+        let position_m = widget.targets.last()
+            .map_or(DVec3::ZERO, |target| target.position_m + DVec3::new(10.0, 0.0, 0.0));
+        widget.targets.push(PointTarget { position_m, amplitude: 1.0 });
+    }
+
+    ui.separator();
+    ui.vertical_centered(|ui| ui.label(egui::RichText::new("PHASE HISTORY").strong()));
+    ui.separator();
+
+    egui::Grid::new("raw_data_simulation_grid")
+        .num_columns(2)
+        .striped(false)
+        .spacing([20.0, 5.0])
+        .show(ui, |ui| {
+            let hover_text = egui::RichText::new("Sets the fast-time sampling frequency over bandwidth ratio (1 - 4)")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Oversampling: ").on_hover_text(hover_text.clone());
+            ui.add(
+                egui::DragValue::new(&mut widget.range_oversampling)
+                    .custom_parser(expression::parse)
+                    .update_while_editing(false)
+                    .speed(0.01)
+                    .range(1.0..=4.0)
+                    .fixed_decimals(2)
+                    .suffix(" ×")
+            ).on_hover_text(hover_text);
+            ui.end_row();
+
+            let hover_text = egui::RichText::new(
+                "Simulates the range-compressed phase history of the point targets\n\
+                 over the integration time, at the Tx PRF, centered on the current\n\
+                 carriers positions"
+            )
+                .color(TEXT_COLOR)
+                .monospace();
+            if ui.button("Simulate").on_hover_text(hover_text).clicked() {
+                widget.simulate(tx_carrier_state, rx_carrier_state, bsar_infos_state);
+            }
+            ui.horizontal(|ui| {
+                let can_save = widget.save_request.is_none() && widget.raw_data.is_some();
+                let hover = egui::RichText::new("Saves the phase history as a NumPy array (complex128, pulses × samples)")
+                    .color(TEXT_COLOR)
+                    .monospace();
+                if ui
+                    .add_enabled(can_save, egui::Button::image_and_text(SAVE_ICON, "NPY").frame_when_inactive(false))
+                    .on_hover_text(hover)
+                    .clicked()
+                    && let Some(raw_data) = &widget.raw_data
+                {
+                    widget.status = None;
+                    widget.save_request = Some(SaveRequest::with_file_type(
+                        RAW_DATA_EXPORT_FILE_NAME,
+                        raw_data.to_npy(),
+                        SaveFileType::Npy
+                    ));
+                }
+                let hover = egui::RichText::new("Saves the acquisition parameters as a CSV table")
+                    .color(TEXT_COLOR)
+                    .monospace();
+                if ui
+                    .add_enabled(can_save, egui::Button::image_and_text(SAVE_ICON, "CSV").frame_when_inactive(false))
+                    .on_hover_text(hover)
+                    .clicked()
+                    && let Some(raw_data) = &widget.raw_data
+                {
+                    widget.status = None;
+                    widget.save_request = Some(SaveRequest::with_file_type(
+                        RAW_DATA_PARAMETERS_EXPORT_FILE_NAME,
+                        format!("{}{}", metadata.csv_comments(), raw_data.parameters_as_csv()).into_bytes(),
+                        SaveFileType::Csv
+                    ));
+                }
+            });
+            ui.end_row();
+        });
+    ui.separator();
+    ui.vertical_centered(|ui| ui.label(egui::RichText::new("BACK-PROJECTION QUICKLOOK").strong()));
+    ui.separator();
+
+    egui::Grid::new("raw_data_quicklook_grid")
+        .num_columns(2)
+        .striped(false)
+        .spacing([20.0, 5.0])
+        .show(ui, |ui| {
+            let hover_text = egui::RichText::new("Selects the target the ground patch is centered on")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Center: ").on_hover_text(hover_text.clone());
+            widget.quicklook_target = widget.quicklook_target.min(widget.targets.len().saturating_sub(1));
+            egui::ComboBox::from_id_salt("raw_data_quicklook_target")
+                .selected_text(format!("Target {}", widget.quicklook_target + 1))
+                .show_ui(ui, |ui| {
+                    for i in 0..widget.targets.len() {
+                        ui.selectable_value(&mut widget.quicklook_target, i, format!("Target {}", i + 1));
+                    }
+                })
+                .response
+                .on_hover_text(hover_text);
+            ui.end_row();
+
+            let hover_text = egui::RichText::new("Sets the half side of the square ground patch (0.1 - 10000 m)")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Half extent: ").on_hover_text(hover_text.clone());
+            ui.add(
+                egui::DragValue::new(&mut widget.quicklook_half_extent_m)
+                    .custom_parser(expression::parse)
+                    .update_while_editing(false)
+                    .speed(0.1)
+                    .range(0.1..=10000.0)
+                    .fixed_decimals(1)
+                    .suffix(" m")
+            ).on_hover_text(hover_text);
+            ui.end_row();
+
+            let hover_text = egui::RichText::new(format!(
+                "Focuses the simulated phase history by time-domain back-projection\n\
+                 over a {QUICKLOOK_SIZE} × {QUICKLOOK_SIZE} pixels horizontal ground patch"
+            ))
+                .color(TEXT_COLOR)
+                .monospace();
+            if ui
+                .add_enabled(widget.raw_data.is_some(), egui::Button::new("Back-project"))
+                .on_hover_text(hover_text)
+                .clicked()
+            {
+                widget.backproject();
+            }
+            ui.end_row();
+        });
+
+    match &widget.status {
+        Some(Ok(status)) => { ui.label(egui::RichText::new(status).color(TEXT_COLOR)); }
+        Some(Err(error)) => { ui.label(egui::RichText::new(error).color(ERROR_COLOR)); }
+        None => {}
+    }
+}
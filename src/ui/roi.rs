@@ -0,0 +1,323 @@
+use bevy::{math::DVec2, prelude::*};
+use bevy_egui::{egui, EguiContexts, EguiPrimaryContextPass};
+use bevy_panorbit_camera::PanOrbitCamera;
+
+use crate::{
+    bsar::BsarInfos,
+    entities::{enu_to_world, LineStrip},
+    incidence::local_incidence_deg,
+    roi::{GroundRect, Statistics},
+    scene::{
+        BsarInfosState, RxAntennaBeamFootprintState, RxAntennaBeamState, RxCarrierState,
+        TxAntennaBeamFootprintState, TxAntennaBeamState, TxCarrierState,
+    },
+    ui::{SidePanelRects, UpdateStage},
+};
+
+const TEXT_COLOR: egui::Color32 = egui::Color32::from_rgb(200, 200, 200);
+/// Color of the region of interest outline
+const OUTLINE_COLOR: Srgba = Srgba::rgb(1.0, 1.0, 1.0);
+/// Samples along each side of the region of interest
+const SAMPLE_COUNT: usize = 16;
+
+pub struct RoiPlugin;
+
+impl Plugin for RoiPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<RoiWidget>()
+            .add_systems(Startup, spawn_roi_outline)
+            // Sampled over the system updated by the update systems
+            .add_systems(
+                PostUpdate,
+                update_roi.in_set(UpdateStage::RenderAssets)
+            )
+            .add_systems(EguiPrimaryContextPass, roi_ui_system);
+    }
+}
+
+/// Outline of the region of interest on the ground.
+#[derive(Component)]
+pub struct RoiOutline;
+
+/// Statistics of the scene quantities over the region of interest.
+#[derive(Debug, Clone, Copy, Default)]
+struct RoiStatistics {
+    nesz_db: Option<Statistics>,
+    ground_range_resolution_m: Option<Statistics>,
+    ground_lateral_resolution_m: Option<Statistics>,
+    resolution_area_m2: Option<Statistics>,
+    tx_incidence_deg: Option<Statistics>,
+    rx_incidence_deg: Option<Statistics>,
+}
+
+/// A rectangle of the ground drawn by dragging in the 3D view, over which the
+/// NESZ, resolutions and incidences are sampled, and to which the overlays and
+/// the exports can be restricted.
+#[derive(Resource)]
+pub struct RoiWidget {
+    pub is_enabled: bool,
+    /// Armed by the "Draw" button: the next drag in the 3D view draws the
+    /// rectangle instead of moving the camera
+    pub is_drawing: bool,
+    /// Ground point (East, North) in meters where the current drag started
+    drag_start_m: Option<DVec2>,
+    pub rect: Option<GroundRect>,
+    /// Restricts the iso-range/Doppler contours and the vector exports to the
+    /// region of interest
+    pub restrict_to_roi: bool,
+    /// Rectangle the statistics were sampled over
+    sampled_rect: Option<GroundRect>,
+    statistics: RoiStatistics,
+}
+
+impl Default for RoiWidget {
+    fn default() -> Self {
+        Self {
+            is_enabled: false,
+            is_drawing: false,
+            drag_start_m: None,
+            rect: None,
+            restrict_to_roi: false,
+            sampled_rect: None,
+            statistics: RoiStatistics::default(),
+        }
+    }
+}
+
+impl RoiWidget {
+    /// Gets the rectangle the overlays and exports are restricted to, if any.
+    pub fn restriction(&self) -> Option<GroundRect> {
+        self.rect.filter(|_| self.is_enabled && self.restrict_to_roi)
+    }
+}
+
+fn spawn_roi_outline(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.spawn((
+        Mesh3d(meshes.add(LineStrip { points: vec![] })),
+        MeshMaterial3d(materials.add(StandardMaterial {
+            base_color: OUTLINE_COLOR.into(),
+            unlit: true,
+            ..default()
+        })),
+        Transform::IDENTITY,
+        Visibility::Hidden,
+        RoiOutline,
+        Name::new("Region of interest outline"),
+    ));
+}
+
+/// Samples the scene quantities over the rectangle, at the centers of a
+/// [`SAMPLE_COUNT`] x [`SAMPLE_COUNT`] grid of cells.
+fn sample_statistics(
+    rect: &GroundRect,
+    tx: (&TxCarrierState, &TxAntennaBeamState, &TxAntennaBeamFootprintState),
+    rx: (&RxCarrierState, &RxAntennaBeamState, &RxAntennaBeamFootprintState),
+) -> RoiStatistics {
+    let samples: Vec<(BsarInfos, f64, f64)> = rect.sample_points(SAMPLE_COUNT)
+        .map(|point| {
+            let point_m = point.extend(0.0);
+            let mut bsar_infos = BsarInfos::default();
+            bsar_infos.update_from_state_at(
+                tx.0, rx.0,
+                &tx.1.inner, &rx.1.inner,
+                &tx.2.inner, &rx.2.inner,
+                &point_m,
+            );
+            let tx_incidence_deg = local_incidence_deg(&tx.0.inner.position_m, &point_m);
+            let rx_incidence_deg = local_incidence_deg(&rx.0.inner.position_m, &point_m);
+            (bsar_infos, tx_incidence_deg, rx_incidence_deg)
+        })
+        .collect();
+    let statistics = |value: fn(&(BsarInfos, f64, f64)) -> f64| Statistics::from_values(samples.iter().map(value));
+    RoiStatistics {
+        nesz_db: statistics(|(bsar_infos, _, _)| 10.0 * bsar_infos.nesz.log10()),
+        ground_range_resolution_m: statistics(|(bsar_infos, _, _)| bsar_infos.ground_range_resolution_m),
+        ground_lateral_resolution_m: statistics(|(bsar_infos, _, _)| bsar_infos.ground_lateral_resolution_m),
+        resolution_area_m2: statistics(|(bsar_infos, _, _)| bsar_infos.resolution_area_m2),
+        tx_incidence_deg: statistics(|&(_, tx_incidence_deg, _)| tx_incidence_deg),
+        rx_incidence_deg: statistics(|&(_, _, rx_incidence_deg)| rx_incidence_deg),
+    }
+}
+
+/// Redraws the outline and samples the statistics when the rectangle or the
+/// system changes.
+fn update_roi(
+    mut roi_widget: ResMut<RoiWidget>,
+    bsar_infos_state: Res<BsarInfosState>,
+    tx: (Res<TxCarrierState>, Res<TxAntennaBeamState>, Res<TxAntennaBeamFootprintState>),
+    rx: (Res<RxCarrierState>, Res<RxAntennaBeamState>, Res<RxAntennaBeamFootprintState>),
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut roi_outline_q: Query<(&Mesh3d, &mut Visibility), With<RoiOutline>>,
+) -> Result {
+    let rect = roi_widget.rect.filter(|_| roi_widget.is_enabled);
+    // The widget is written by its window every frame: only a new rectangle
+    // or a new system is sampled
+    if rect == roi_widget.sampled_rect && !bsar_infos_state.is_changed() && !tx.2.is_changed() {
+        return Ok(());
+    }
+    let widget = roi_widget.bypass_change_detection();
+    widget.sampled_rect = rect;
+    widget.statistics = rect
+        .map(|rect| sample_statistics(&rect, (&tx.0, &tx.1, &tx.2), (&rx.0, &rx.1, &rx.2)))
+        .unwrap_or_default();
+
+    // Above the footprints, as the ground tracks
+    let lift_m = 2.0 * tx.2.inner.lift_m;
+    let outline = |rect: &GroundRect| {
+        let mut points: Vec<Vec3> = rect.corners().into_iter().map(|corner| enu_to_world(corner, lift_m)).collect();
+        points.push(points[0]);
+        LineStrip { points }
+    };
+    for (mesh, visibility) in roi_outline_q.iter_mut() {
+        super::display::replace_line_mesh(&mut meshes, mesh, visibility, rect.as_ref().map(outline))?;
+    }
+
+    Ok(())
+}
+
+/// Gets the ground point (East, North) in meters under the viewport `position`
+/// in logical pixels.
+fn ground_point_m(camera: &Camera, camera_transform: &GlobalTransform, position: Vec2) -> Option<DVec2> {
+    let ray = camera.viewport_to_world(camera_transform, position).ok()?;
+    let distance = ray.intersect_plane(Vec3::ZERO, InfinitePlane3d::new(Vec3::Y))?;
+    // World frame (Y-up) to ENU ground plane
+    let point = ray.get_point(distance);
+    Some(DVec2::new(point.z as f64, point.x as f64))
+}
+
+/// Draws the rectangle by dragging in the 3D view once armed.
+fn roi_ui_system(
+    mut contexts: EguiContexts,
+    mut roi_widget: ResMut<RoiWidget>,
+    side_panel_rects: Res<SidePanelRects>,
+    camera_q: Single<(&Camera, &GlobalTransform), With<PanOrbitCamera>>,
+) -> Result {
+    let ctx = contexts.ctx_mut()?;
+    let (camera, camera_transform) = *camera_q;
+
+    let roi_window = egui::Window::new("Region of Interest")
+        .resizable(false)
+        .constrain(true)
+        .collapsible(true)
+        .title_bar(true)
+        .enabled(true)
+        .default_open(false)
+        .default_pos(egui::pos2(320.0, 1000.0));
+    roi_window.show(ctx, |ui| {
+        roi_ui(ui, &mut roi_widget);
+    });
+
+    if !roi_widget.is_enabled || !roi_widget.is_drawing {
+        return Ok(());
+    }
+    let view_rect = side_panel_rects.view_rect(ctx);
+    let (is_pressed, is_down, position) = ctx.input(|i| {
+        (i.pointer.primary_pressed(), i.pointer.primary_down(), i.pointer.latest_pos())
+    });
+    // Pressed in the 3D view, not on a window
+    if is_pressed
+        && let Some(position) = position
+        && view_rect.contains(position)
+        && ctx.layer_id_at(position).is_none_or(|layer_id| layer_id.order == egui::Order::Background)
+    {
+        roi_widget.drag_start_m = ground_point_m(camera, camera_transform, ctx.zoom_factor() * Vec2::new(position.x, position.y));
+    }
+    let Some(start_m) = roi_widget.drag_start_m else {
+        return Ok(());
+    };
+    if let Some(position) = position
+        && let Some(point_m) = ground_point_m(camera, camera_transform, ctx.zoom_factor() * Vec2::new(position.x, position.y))
+    {
+        roi_widget.rect = Some(GroundRect::from_corners(start_m, point_m));
+    }
+    if !is_down {
+        // One rectangle per arming
+        roi_widget.drag_start_m = None;
+        roi_widget.is_drawing = false;
+    }
+
+    Ok(())
+}
+
+fn statistics_row(ui: &mut egui::Ui, label: &str, statistics: Option<Statistics>, unit: &str, precision: usize) {
+    ui.label(label);
+    match statistics {
+        Some(Statistics { min, max, mean, .. }) => {
+            ui.label(format!("{mean:.precision$} {unit}"));
+            ui.label(format!("{min:.precision$} {unit}"));
+            ui.label(format!("{max:.precision$} {unit}"));
+        }
+        None => {
+            for _ in 0..3 {
+                ui.label("-");
+            }
+        }
+    }
+    ui.end_row();
+}
+
+fn roi_ui(ui: &mut egui::Ui, widget: &mut RoiWidget) {
+    let hover_text = egui::RichText::new("Samples the NESZ, ground resolutions and incidences over a rectangle of\nthe ground, drawn by dragging in the 3D view after clicking \"Draw\"")
+        .color(TEXT_COLOR)
+        .monospace();
+    ui.checkbox(&mut widget.is_enabled, "Region of interest").on_hover_text(hover_text);
+    ui.add_enabled_ui(widget.is_enabled, |ui| {
+        ui.horizontal(|ui| {
+            let draw_text = if widget.is_drawing { "Drag in the 3D view..." } else { "Draw" };
+            if ui.add(egui::Button::new(draw_text).selected(widget.is_drawing)).on_hover_text("Arms the drawing of the rectangle, the camera being held").clicked() {
+                widget.is_drawing = !widget.is_drawing;
+                widget.drag_start_m = None;
+            }
+            if ui.add_enabled(widget.rect.is_some(), egui::Button::new("Clear")).on_hover_text("Clears the region of interest").clicked() {
+                widget.rect = None;
+            }
+        });
+        let hover_text = egui::RichText::new("Restricts the iso-range/Doppler contours, the top view and the\nSVG/DXF exports to the region of interest")
+            .color(TEXT_COLOR)
+            .monospace();
+        ui.checkbox(&mut widget.restrict_to_roi, "Restrict overlays and exports").on_hover_text(hover_text);
+        let Some(rect) = widget.rect else {
+            ui.label(egui::RichText::new("No region of interest drawn").color(TEXT_COLOR));
+            return;
+        };
+        let (center_m, size_m) = (rect.center_m(), rect.size_m());
+        egui::Grid::new("roi_rect_grid")
+            .num_columns(2)
+            .striped(false)
+            .spacing([20.0, 5.0])
+            .show(ui, |ui| {
+                ui.label("Center:");
+                ui.label(format!("E {:.3} m, N {:.3} m", center_m.x, center_m.y));
+                ui.end_row();
+                ui.label("Size:");
+                ui.label(format!("{:.3} m x {:.3} m", size_m.x, size_m.y));
+                ui.end_row();
+                ui.label("Area:");
+                ui.label(format!("{:.6} km²", rect.area_m2() * 1e-6));
+                ui.end_row();
+            });
+        ui.separator();
+        let statistics = &widget.statistics;
+        egui::Grid::new("roi_statistics_grid")
+            .num_columns(4)
+            .striped(true)
+            .spacing([20.0, 5.0])
+            .show(ui, |ui| {
+                for header in ["", "Mean", "Min", "Max"] {
+                    ui.label(egui::RichText::new(header).strong());
+                }
+                ui.end_row();
+                statistics_row(ui, "NESZ", statistics.nesz_db, "dB", 2);
+                statistics_row(ui, "Ground range resolution", statistics.ground_range_resolution_m, "m", 3);
+                statistics_row(ui, "Ground lateral resolution", statistics.ground_lateral_resolution_m, "m", 3);
+                statistics_row(ui, "Resolution area", statistics.resolution_area_m2, "m²", 3);
+                statistics_row(ui, "Tx incidence", statistics.tx_incidence_deg, "°", 2);
+                statistics_row(ui, "Rx incidence", statistics.rx_incidence_deg, "°", 2);
+            });
+    });
+}
@@ -2,11 +2,12 @@ use bevy::prelude::*;
 use bevy_egui::egui;
 
 use crate::{
+    bsar::ResolutionFormula,
+    constants::{MAX_INTEGRATION_TIME_S, MAX_NOISE_TEMPERATURE_K},
     entities::{
-        antenna_beam_transform_from_state, antenna_transform_from_state,
-        carrier_transform_from_state,
+        antenna_beam_transform_from_state, antenna_state_pointing_to_center,
+        antenna_transform_from_state, carrier_transform_from_state,
         iso_range_ellipsoid_transform_from_state,
-        refresh_iso_range_doppler_plane,
         update_antenna_beam_footprint_azimuth_line_mesh_from_state,
         update_antenna_beam_footprint_elevation_line_mesh_from_state,
         update_antenna_beam_footprint_mesh_from_state,
@@ -15,14 +16,16 @@ use crate::{
         update_velocity_vector,
         velocity_indicator_transform_from_state,
         Antenna, AntennaBeam, AntennaBeamAzimuthLine, AntennaBeamElevationLine, AntennaBeamFootprint,
-        Carrier, IsoRangeDopplerPlaneState, VelocityVector
+        AntennaBeamState, Carrier, CarrierState, VelocityVector
     },
+    expression,
+    formation::LeverArm,
     scene::{
-        BsarInfosState, IsoRangeDopplerPlane, IsoRangeEllipsoid, PixelResolution,
+        BsarInfosState, CarrierLimitsState, IsoRangeEllipsoid, PixelResolution,
         Rx, RxAntennaBeamFootprintState, RxAntennaBeamState, RxAntennaState, RxCarrierState,
-        TxAntennaBeamFootprintState, TxAntennaBeamState, TxCarrierState
+        TxAntennaBeamState, TxAntennaState, TxCarrierState
     },
-    ui::{carrier_ui, heading_with_reset, MenuWidget},
+    ui::{carrier_ui, gain_menu, heading_with_reset, log_slider, reset_menu, GeometryMode, MenuWidget, UpdateStage},
 };
 
 
@@ -32,64 +35,145 @@ impl Plugin for RxPanelPlugin {
     fn build(&self, app: &mut App) {
         app
             .init_resource::<RxPanelWidget>()
-            .add_systems(Update, update_rx);
+            .add_systems(
+                PostUpdate,
+                (apply_geometry_mode, update_rx).chain().in_set(UpdateStage::Geometry)
+            );
     }
 }
 
+/// Receiver panel. The settings it edits are picked up by [`update_rx`] and
+/// `update_bsar_system` through the change detection of the Rx state resources.
 #[derive(Resource)]
 #[derive(Default)]
-pub struct RxPanelWidget {
-    pub transform_needs_update: bool,
-    pub velocity_vector_needs_update: bool,
-    pub system_needs_update: bool,
-}
+pub struct RxPanelWidget;
 
 
 impl RxPanelWidget {
+    /// Shows the panel, returning whether the Rx settings changed. The Rx
+    /// carrier and antenna derived from the Tx ones in the monostatic and
+    /// quasi-monostatic modes are not shown.
     pub fn ui(
         &mut self,
         ui: &mut egui::Ui,
-        menu_widget: &MenuWidget,
+        menu_widget: &mut MenuWidget,
         rx_carrier_state: &mut RxCarrierState,
         rx_antenna_state: &mut RxAntennaState,
         rx_antenna_beam_state: &mut RxAntennaBeamState,
-        bsar_infos_state: &mut BsarInfosState,
-    ) {
-        // Handle update of parameters, meshes, textures, etc...
-        self.transform_needs_update = false;
-        self.velocity_vector_needs_update = false;
-        self.system_needs_update = false;
+        bsar_infos_state: &BsarInfosState,
+        carrier_limits_state: &CarrierLimitsState,
+    ) -> bool {
+        let mut transform_changed = false;
+        let mut velocity_vector_changed = false;
+        let mut system_changed = false;
 
         // Rx Carrier UI
-        let reset_all = ui.add_enabled_ui(
-            !menu_widget.is_monostatic,
-            |ui| {
-                carrier_ui(
-                    ui,
-                    "rx",
-                    "RECEIVER SETTINGS",
-                    &mut rx_carrier_state.inner,
-                    &mut rx_antenna_state.inner,
-                    &mut rx_antenna_beam_state.inner,
-                    &RxCarrierState::default().inner,
-                    &RxAntennaState::default().inner,
-                    &RxAntennaBeamState::default().inner,
-                    &mut self.transform_needs_update,
-                    &mut self.velocity_vector_needs_update
-                )
-            }
-        ).inner;
+        let reset_all = if menu_widget.geometry_mode == GeometryMode::Bistatic {
+            carrier_ui(
+                ui,
+                "rx",
+                "RECEIVER SETTINGS",
+                &mut rx_carrier_state.inner,
+                &mut rx_antenna_state.inner,
+                &mut rx_antenna_beam_state.inner,
+                &RxCarrierState::default().inner,
+                &RxAntennaState::default().inner,
+                &RxAntennaBeamState::default().inner,
+                &carrier_limits_state.inner,
+                &mut transform_changed,
+                &mut velocity_vector_changed,
+                Some(&mut rx_carrier_state.is_stationary)
+            )
+        } else {
+            ui.separator();
+            let reset_all = heading_with_reset(
+                ui,
+                egui::RichText::new("RECEIVER SETTINGS").size(15.0).strong(),
+                "Resets every setting of this element to its defaults",
+            );
+            ui.separator();
+            reset_all
+        };
+
+        // Geometry mode UI (picked up by apply_geometry_mode)
+        geometry_mode_ui(ui, menu_widget);
 
         // Rx System UI ("reset all" from the title row also resets it)
         rx_system_ui(
             ui,
             rx_carrier_state,
             rx_antenna_beam_state,
-            menu_widget.is_monostatic,
+            menu_widget.geometry_mode == GeometryMode::Monostatic,
             bsar_infos_state,
             reset_all,
-            &mut self.system_needs_update
+            &mut system_changed
         );
+        transform_changed || velocity_vector_changed || system_changed
+    }
+}
+
+/// Derives the Rx carrier and antenna states from the Tx ones in the
+/// monostatic and quasi-monostatic modes, when the mode or the Tx states
+/// change. The Rx states are only written when the derived ones differ, as
+/// the Tx carrier state is also written by `update_tx`.
+fn apply_geometry_mode(
+    menu_widget: Res<MenuWidget>,
+    mut applied_mode: Local<Option<(GeometryMode, LeverArm)>>,
+    tx: (Res<TxCarrierState>, Res<TxAntennaState>, Res<TxAntennaBeamState>),
+    rx: (ResMut<RxCarrierState>, ResMut<RxAntennaState>, ResMut<RxAntennaBeamState>),
+) {
+    let (tx_carrier_state, tx_antenna_state, tx_antenna_beam_state) = tx;
+    let (mut rx_carrier_state, mut rx_antenna_state, mut rx_antenna_beam_state) = rx;
+    let mode = (menu_widget.geometry_mode, menu_widget.lever_arm);
+    let is_tx_changed = tx_carrier_state.is_changed() ||
+        tx_antenna_state.is_changed() ||
+        tx_antenna_beam_state.is_changed();
+    if *applied_mode == Some(mode) && !is_tx_changed {
+        return;
+    }
+    *applied_mode = Some(mode);
+
+    let (carrier, antenna, antenna_beam) = match menu_widget.geometry_mode {
+        GeometryMode::Bistatic => return, // The Rx keeps its own settings
+        GeometryMode::Monostatic => (
+            tx_carrier_state.inner.clone(),
+            tx_antenna_state.inner.clone(),
+            tx_antenna_beam_state.inner.clone(),
+        ),
+        GeometryMode::QuasiMonostatic => {
+            // Tx position at time 0 s, where the boresight hits the scene center
+            let mut tx_carrier = CarrierState { time_s: 0.0, ..tx_carrier_state.inner.clone() };
+            carrier_transform_from_state(&mut tx_carrier, &tx_antenna_state.inner);
+            // Offset in the Tx track frame, the Rx pointing at the center (kept
+            // above the ground)
+            let mut position_m = tx_carrier.position_m + menu_widget.lever_arm.offset_m(tx_carrier.heading_deg);
+            position_m.z = position_m.z.max(0.0);
+            // The Rx antenna is mounted as the Tx one: its phase center is at
+            // the offset position, the carrier reference point at its lever arm
+            let mut carrier = tx_carrier_state.inner.clone();
+            carrier.height_m = position_m.z - (carrier.initial_rotation() * tx_antenna_state.inner.lever_arm_m).z;
+            let antenna = antenna_state_pointing_to_center(&carrier, &position_m, &tx_antenna_state.inner);
+            // Derived fields as update_rx computes them
+            carrier_transform_from_state(&mut carrier, &antenna);
+            // The Rx antenna beam has the Tx widths but its own gain
+            let antenna_beam = AntennaBeamState {
+                one_way_gain_dbi: rx_antenna_beam_state.inner.one_way_gain_dbi,
+                ..tx_antenna_beam_state.inner.clone()
+            };
+            (carrier, antenna, antenna_beam)
+        }
+    };
+    if rx_carrier_state.inner != carrier {
+        rx_carrier_state.inner = carrier;
+    }
+    if rx_carrier_state.is_stationary {
+        rx_carrier_state.is_stationary = false; // The Rx flies with the Tx
+    }
+    if rx_antenna_state.inner != antenna {
+        rx_antenna_state.inner = antenna;
+    }
+    if rx_antenna_beam_state.inner != antenna_beam {
+        rx_antenna_beam_state.inner = antenna_beam;
     }
 }
 
@@ -99,211 +183,209 @@ pub(super) fn update_rx(
         Res<RxAntennaState>,              // rx_antenna_state
         Res<RxAntennaBeamState>,          // rx_antenna_beam_state
         Res<TxCarrierState>,              // tx_carrier_state
-        Res<TxAntennaBeamState>,          // tx_antenna_beam_state
-        Res<TxAntennaBeamFootprintState>, // tx_antenna_beam_footprint_state
     ),
     resmut: ( // Mutable resources
-        ResMut<RxPanelWidget>,               // rx_panel_widget
-        ResMut<Assets<StandardMaterial>>,    // materials
         ResMut<Assets<Mesh>>,                // meshes
-        ResMut<Assets<Image>>,               // images
-        ResMut<MenuWidget>,                  // menu_widget // For monostatic case
         ResMut<RxCarrierState>,              // rx_carrier_state
         ResMut<RxAntennaBeamFootprintState>, // rx_antenna_beam_footprint_state
-        ResMut<BsarInfosState>,              // bsar_infos_state
-        ResMut<IsoRangeDopplerPlaneState>,   // iso_range_doppler_plane_state
     ),
     // Queries
     rx_antenna_beam_footprint_q: Query<&Mesh3d, (With<Rx>, With<AntennaBeamFootprint>)>,
     rx_antenna_beam_elevation_line_q: Query<&Mesh3d, (With<Rx>, With<AntennaBeamElevationLine>)>,
     rx_antenna_beam_azimuth_line_q: Query<&Mesh3d, (With<Rx>, With<AntennaBeamAzimuthLine>)>,
-    iso_range_doppler_material_q: Query<&MeshMaterial3d<StandardMaterial>, With<IsoRangeDopplerPlane>>,
     // Mutable queries
     mut rx_carrier_q: Query<(&mut Transform, &Children), (With<Rx>, With<Carrier>)>,
     mut rx_antenna_q: Query<(&mut Transform, &Children), (Without<Rx>, With<Antenna>)>,
     mut rx_antenna_beam_q: Query<&mut Transform, (Without<Rx>, Without<Antenna>, With<AntennaBeam>)>,
     mut rx_velocity_indicator_q: Query<&mut Transform, (Without<Rx>, Without<Antenna>, Without<AntennaBeam>, With<VelocityVector>)>,
     mut iso_range_ellipsoid_q: Query<&mut Transform, (Without<Rx>, Without<Antenna>, Without<AntennaBeam>, Without<VelocityVector>, With<IsoRangeEllipsoid>)>,
-    mut iso_range_doppler_q: Query<&mut Transform, (Without<Rx>, Without<Antenna>, Without<AntennaBeam>, Without<VelocityVector>, Without<IsoRangeEllipsoid>, With<IsoRangeDopplerPlane>)>,
 ) {
     // Extracts resources
     let (
         rx_antenna_state,
         rx_antenna_beam_state,
         tx_carrier_state,
-        tx_antenna_beam_state,
-        tx_antenna_beam_footprint_state
     ) = res;
     // Extracts mutable resources
     let (
-        mut rx_panel_widget,
-        mut materials,
         mut meshes,
-        mut images,
-        mut menu_widget,
         mut rx_carrier_state,
         mut rx_antenna_beam_footprint_state,
-        mut bsar_infos_state,
-        mut iso_range_doppler_plane_state,
     ) = resmut;
-    // Checks if nothing needs to be done
-    if !(rx_panel_widget.transform_needs_update  ||
-         rx_panel_widget.velocity_vector_needs_update ||
-         rx_panel_widget.system_needs_update) {
+    // What changed since the last run, by the panels or any other system: the
+    // Rx geometry is derived from the Rx states (the BSAR system is refreshed
+    // by update_bsar_system, which sees the changes made here)
+    let is_rx_changed = rx_carrier_state.is_changed() ||
+        rx_antenna_state.is_changed() ||
+        rx_antenna_beam_state.is_changed() ||
+        rx_antenna_beam_footprint_state.is_changed();
+    if !is_rx_changed {
         return; // No need to update transforms if no changes were made
     }
     for (mut carrier_transform, carrier_children) in rx_carrier_q.iter_mut() {
         for carrier_child in carrier_children.iter() {
-            if rx_panel_widget.transform_needs_update
-                && let Ok((mut antenna_transform, antenna_children)) = rx_antenna_q.get_mut(carrier_child) {
-                    // Update antenna beam width
-                    for antenna_beam in antenna_children.iter() {
-                        if let Ok(mut antenna_beam_transform) = rx_antenna_beam_q.get_mut(antenna_beam) {
-                            // Update antenna beam width
-                            *antenna_beam_transform = antenna_beam_transform_from_state(
-                                &rx_antenna_beam_state.inner
-                            );
-                        }
-                    }
-                    // Update antenna transform
-                    *antenna_transform = antenna_transform_from_state(
-                        &rx_antenna_state.inner
-                    );
-                    // Update carrier transform
-                    *carrier_transform = carrier_transform_from_state(
-                        &mut rx_carrier_state.inner,
-                        &rx_antenna_state.inner
-                    );
-                    // Update antenna beam footprint mesh in the same time
-                    for mesh_handle in rx_antenna_beam_footprint_q.iter() {
-                        if let Some(mut mesh) = meshes.get_mut(mesh_handle) {
-                            update_antenna_beam_footprint_mesh_from_state(
-                                &rx_carrier_state.inner,
-                                &rx_antenna_state.inner,
-                                &rx_antenna_beam_state.inner,
-                                &mut rx_antenna_beam_footprint_state.inner,
-                                &mut mesh
-                            );
-                        }
+            if let Ok((mut antenna_transform, antenna_children)) = rx_antenna_q.get_mut(carrier_child) {
+                // Update antenna transform
+                *antenna_transform = antenna_transform_from_state(
+                    &rx_antenna_state.inner
+                );
+                // Update carrier transform
+                *carrier_transform = carrier_transform_from_state(
+                    &mut rx_carrier_state.inner,
+                    &rx_antenna_state.inner
+                );
+                // Update antenna beam footprint mesh in the same time
+                for mesh_handle in rx_antenna_beam_footprint_q.iter() {
+                    if let Some(mut mesh) = meshes.get_mut(mesh_handle) {
+                        update_antenna_beam_footprint_mesh_from_state(
+                            &rx_carrier_state.inner,
+                            &rx_antenna_state.inner,
+                            &rx_antenna_beam_state.inner,
+                            &mut rx_antenna_beam_footprint_state.inner,
+                            &mut mesh
+                        );
                     }
-                    // Update antenna beam elevation line mesh in the same time
-                    for mesh_handle in rx_antenna_beam_elevation_line_q.iter() {
-                        if let Some(mut mesh) = meshes.get_mut(mesh_handle) {
-                            update_antenna_beam_footprint_elevation_line_mesh_from_state(
-                                &rx_antenna_beam_footprint_state.inner,
-                                &mut mesh
-                            );
-                        }
+                }
+                // Update antenna beam width and length (after the footprint, which sets the length)
+                for antenna_beam in antenna_children.iter() {
+                    if let Ok(mut antenna_beam_transform) = rx_antenna_beam_q.get_mut(antenna_beam) {
+                        *antenna_beam_transform = antenna_beam_transform_from_state(
+                            &rx_antenna_beam_state.inner,
+                            &rx_antenna_beam_footprint_state.inner
+                        );
                     }
-                    // Update antenna beam azimuth line mesh in the same time
-                    for mesh_handle in rx_antenna_beam_azimuth_line_q.iter() {
-                        if let Some(mut mesh) = meshes.get_mut(mesh_handle) {
-                            update_antenna_beam_footprint_azimuth_line_mesh_from_state(
-                                &rx_antenna_beam_footprint_state.inner,
-                                &mut mesh
-                            );
-                        }
+                }
+                // Update antenna beam elevation line mesh in the same time
+                for mesh_handle in rx_antenna_beam_elevation_line_q.iter() {
+                    if let Some(mut mesh) = meshes.get_mut(mesh_handle) {
+                        update_antenna_beam_footprint_elevation_line_mesh_from_state(
+                            &rx_antenna_beam_footprint_state.inner,
+                            &mut mesh
+                        );
                     }
-                    //Update iso-range ellipsoid transform
-                    for mut iso_range_ellipsoid_transform in iso_range_ellipsoid_q.iter_mut() {
-                        *iso_range_ellipsoid_transform = iso_range_ellipsoid_transform_from_state(
-                            &tx_carrier_state.inner.position_m, // OT in world frame
-                            &rx_carrier_state.inner.position_m  // OR in world frame
+                }
+                // Update antenna beam azimuth line mesh in the same time
+                for mesh_handle in rx_antenna_beam_azimuth_line_q.iter() {
+                    if let Some(mut mesh) = meshes.get_mut(mesh_handle) {
+                        update_antenna_beam_footprint_azimuth_line_mesh_from_state(
+                            &rx_antenna_beam_footprint_state.inner,
+                            &mut mesh
                         );
                     }
                 }
-            if rx_panel_widget.velocity_vector_needs_update
-                && let Ok(mut velocity_indicator_transform) = rx_velocity_indicator_q.get_mut(carrier_child) {
-                    // Update velocity vector transform
-                    *velocity_indicator_transform = velocity_indicator_transform_from_state(
-                        &rx_carrier_state.inner
-                    );
-                    // Update carrier velocity vector in the same time (here direction does not change, only magnitude)
-                    update_velocity_vector(&mut rx_carrier_state.inner);
-                    // Update ground angular velocity only
-                    update_ground_angular_velocity(
-                        &rx_carrier_state.inner,
-                        &mut rx_antenna_beam_footprint_state.inner,
-                    );
-                    // Update illumination time
-                    update_illumination_time(
-                        &rx_carrier_state.inner,
-                        &mut rx_antenna_beam_footprint_state.inner,
+                //Update iso-range ellipsoid transform
+                for mut iso_range_ellipsoid_transform in iso_range_ellipsoid_q.iter_mut() {
+                    *iso_range_ellipsoid_transform = iso_range_ellipsoid_transform_from_state(
+                        &tx_carrier_state.inner.position_m, // OT in world frame
+                        &rx_carrier_state.inner.position_m  // OR in world frame
                     );
                 }
+            }
+            if let Ok(mut velocity_indicator_transform) = rx_velocity_indicator_q.get_mut(carrier_child) {
+                // Update carrier velocity vector (here direction does not change, only magnitude)
+                update_velocity_vector(&mut rx_carrier_state.inner);
+                // Update velocity vector transform in the same time
+                *velocity_indicator_transform = velocity_indicator_transform_from_state(
+                    &rx_carrier_state.inner
+                );
+                // Update ground angular velocity only
+                update_ground_angular_velocity(
+                    &rx_carrier_state.inner,
+                    &mut rx_antenna_beam_footprint_state.inner,
+                );
+                // Update illumination time
+                update_illumination_time(
+                    &rx_carrier_state.inner,
+                    &mut rx_antenna_beam_footprint_state.inner,
+                );
+            }
         }
     }
-    // Monostatic case
-    if menu_widget.is_monostatic {
-        if rx_panel_widget.system_needs_update {
-            bsar_infos_state.inner.update_from_state(
-                &tx_carrier_state,
-                &rx_carrier_state,
-                &tx_antenna_beam_state.inner,
-                &rx_antenna_beam_state.inner,
-                &tx_antenna_beam_footprint_state.inner,
-                &rx_antenna_beam_footprint_state.inner,
-            );
-        }
-        if menu_widget.force_rx_system_update {
-            // Update iso-range doppler plane transform and texture
-            refresh_iso_range_doppler_plane(
-                &mut materials,
-                &mut images,
-                &tx_carrier_state,
-                &rx_carrier_state,
-                &tx_antenna_beam_footprint_state.inner,
-                &rx_antenna_beam_footprint_state.inner,
-                &mut iso_range_doppler_plane_state,
-                &mut iso_range_doppler_q,
-                &iso_range_doppler_material_q,
-            );
-            menu_widget.force_rx_system_update = false;
-        }
-    } else if rx_panel_widget.transform_needs_update  ||
-              rx_panel_widget.velocity_vector_needs_update ||
-              rx_panel_widget.system_needs_update {
-        // Update BSAR infos
-        bsar_infos_state.inner.update_from_state(
-            &tx_carrier_state,
-            &rx_carrier_state,
-            &tx_antenna_beam_state.inner,
-            &rx_antenna_beam_state.inner,
-            &tx_antenna_beam_footprint_state.inner,
-            &rx_antenna_beam_footprint_state.inner,
-        );
-        // Update iso-range doppler plane transform and texture
-        refresh_iso_range_doppler_plane(
-            &mut materials,
-            &mut images,
-            &tx_carrier_state,
-            &rx_carrier_state,
-            &tx_antenna_beam_footprint_state.inner,
-            &rx_antenna_beam_footprint_state.inner,
-            &mut iso_range_doppler_plane_state,
-            &mut iso_range_doppler_q,
-            &iso_range_doppler_material_q,
+}
+
+
+fn geometry_mode_ui(ui: &mut egui::Ui, menu_widget: &mut MenuWidget) {
+    ui.separator();
+    if heading_with_reset(
+        ui,
+        egui::RichText::new("GEOMETRY").strong(),
+        "Resets the Geometry settings to their defaults"
+    ) {
+        let default_widget = MenuWidget::default();
+        menu_widget.geometry_mode = default_widget.geometry_mode;
+        menu_widget.lever_arm = default_widget.lever_arm;
+    }
+    ui.separator();
+    egui::Grid::new("rx_geometry_grid")
+        .num_columns(2)
+        .striped(false)
+        .spacing([20.0, 5.0])
+        .show(ui, |ui| {
+            // ***** Geometry mode ***** //
+            let hover_text = egui::RichText::new("Sets how the Receiver relates to the Transmitter: its own carrier and antenna (bistatic),\nthe Transmitter antenna (monostatic) or flying with the Transmitter at a fixed\nlever arm, pointing at the scene center (quasi-monostatic)")
+                .color(egui::Color32::from_rgb(200, 200, 200))
+                .monospace();
+            ui.label("Mode: ").on_hover_text(hover_text.clone());
+            egui::ComboBox::from_id_salt("rx_geometry_mode")
+                .selected_text(menu_widget.geometry_mode.label())
+                .show_ui(ui, |ui| {
+                    for mode in GeometryMode::ALL {
+                        ui.selectable_value(&mut menu_widget.geometry_mode, mode, mode.label());
+                    }
+                })
+                .response
+                .on_hover_text(hover_text);
+            ui.end_row();
+
+            // ***** Lever arm ***** //
+            if menu_widget.geometry_mode == GeometryMode::QuasiMonostatic {
+                let default_lever_arm = LeverArm::default();
+                for (label, hover, value, default) in [
+                    ("Along-track: ", "Sets the distance of the Receiver ahead of the Transmitter (-10000 - 10000 m, negative behind)", &mut menu_widget.lever_arm.along_track_m, default_lever_arm.along_track_m),
+                    ("Across-track: ", "Sets the distance of the Receiver right of the Transmitter track (-10000 - 10000 m, negative left)", &mut menu_widget.lever_arm.across_track_m, default_lever_arm.across_track_m),
+                    ("Vertical: ", "Sets the height of the Receiver above the Transmitter (-10000 - 10000 m, negative below),\nthe Receiver staying above the ground", &mut menu_widget.lever_arm.vertical_m, default_lever_arm.vertical_m),
+                ] {
+                    let hover_text = egui::RichText::new(hover)
+                        .color(egui::Color32::from_rgb(200, 200, 200))
+                        .monospace();
+                    ui.label(label).on_hover_text(hover_text.clone());
+                    let response = ui.add(
+                        egui::DragValue::new(value)
+                            .custom_parser(expression::parse)
+                            .update_while_editing(false)
+                            .speed(1.0)
+                            .range(-10000.0..=10000.0)
+                            .fixed_decimals(1)
+                            .suffix(" m")
+                    )
+                    .on_hover_text(hover_text);
+                    reset_menu(&response, value, default);
+                    ui.end_row();
+                }
+                ui.label("Baseline: ");
+                ui.label(format!("{:.1} m", menu_widget.lever_arm.length_m()));
+                ui.end_row();
+            }
+        });
+    if menu_widget.geometry_mode != GeometryMode::Bistatic {
+        ui.label(
+            egui::RichText::new("The Receiver carrier and antenna follow the Transmitter ones")
+                .color(egui::Color32::from_rgb(200, 200, 200))
         );
     }
-    // The panel flags are one-shot commands consumed by this system: clear
-    // them here so they cannot linger when the Rx panel (which resets its own
-    // flags only while it is open) is closed.
-    rx_panel_widget.transform_needs_update = false;
-    rx_panel_widget.velocity_vector_needs_update = false;
-    rx_panel_widget.system_needs_update = false;
 }
 
-
 fn rx_system_ui(
     ui: &mut egui::Ui,
     rx_carrier_state: &mut RxCarrierState,
     rx_antenna_beam_state: &mut RxAntennaBeamState,
     is_monostatic: bool,
-    bsar_infos_state: &mut BsarInfosState,
+    bsar_infos_state: &BsarInfosState,
     reset_all: bool,
-    system_needs_update: &mut bool,
+    system_changed: &mut bool,
 ) {
     let mut old_state = 0.0f64;
+    let default_state = RxCarrierState::default();
 
     ui.separator();
     if heading_with_reset(
@@ -311,16 +393,19 @@ fn rx_system_ui(
         egui::RichText::new("SYSTEM").strong(),
         "Resets the System settings to their defaults"
     ) || reset_all {
-        let default_state = RxCarrierState::default();
         rx_carrier_state.noise_temperature_k = default_state.noise_temperature_k;
         rx_carrier_state.noise_factor_db = default_state.noise_factor_db;
+        rx_carrier_state.noise_bandwidth_mhz = default_state.noise_bandwidth_mhz;
+        rx_carrier_state.range_oversampling = default_state.range_oversampling;
         rx_carrier_state.integration_time_s = default_state.integration_time_s;
-        rx_carrier_state.squared_pixels = default_state.squared_pixels;
+        rx_carrier_state.fixed_pixel_aspect_ratio = default_state.fixed_pixel_aspect_ratio;
+        rx_carrier_state.pixel_aspect_ratio = default_state.pixel_aspect_ratio;
         rx_carrier_state.pixel_resolution = default_state.pixel_resolution;
-        // In monostatic mode this is re-mirrored from Tx in the same frame
+        rx_carrier_state.resolution_formula = default_state.resolution_formula;
+        // In monostatic mode this is re-mirrored from Tx by apply_geometry_mode
         rx_antenna_beam_state.inner.one_way_gain_dbi =
             RxAntennaBeamState::default().inner.one_way_gain_dbi;
-        *system_needs_update = true;
+        *system_changed = true;
     }
     ui.separator();
     // Rx system settings
@@ -335,9 +420,10 @@ fn rx_system_ui(
                 .monospace();
             ui.label("Antenna gain: ").on_hover_text(hover_text.clone());
             old_state = rx_antenna_beam_state.inner.one_way_gain_dbi;
-            ui.add_enabled(
+            let response = ui.add_enabled(
                 !is_monostatic, // The Rx antenna mirrors the Tx antenna in monostatic mode
                 egui::DragValue::new(&mut rx_antenna_beam_state.inner.one_way_gain_dbi)
+                    .custom_parser(expression::parse)
                     .update_while_editing(false)
                     .speed(0.1)
                     .range(0.0..=100.0)
@@ -345,28 +431,31 @@ fn rx_system_ui(
                     .suffix(" dBi")
             )
             .on_hover_text(hover_text);
+            gain_menu(&response, &mut rx_antenna_beam_state.inner, RxAntennaBeamState::default().inner.one_way_gain_dbi);
             if old_state != rx_antenna_beam_state.inner.one_way_gain_dbi {
-                *system_needs_update = true;
+                *system_changed = true;
             }
             ui.end_row();
 
             // ***** Noise temperature ***** //
-            let hover_text = egui::RichText::new("Sets the noise temperature of the Receiver's system (0 - 1000 K)")
+            let hover_text = egui::RichText::new(format!("Sets the noise temperature of the Receiver's system (0 - {MAX_NOISE_TEMPERATURE_K} K)"))
                 .color(egui::Color32::from_rgb(200, 200, 200))
                 .monospace();
             ui.label("Noise temp.: ").on_hover_text(hover_text.clone());
             old_state = rx_carrier_state.noise_temperature_k;
-            ui.add(
+            let response = ui.add(
                 egui::DragValue::new(&mut rx_carrier_state.noise_temperature_k)
+                    .custom_parser(expression::parse)
                     .update_while_editing(false)
                     .speed(1.0)
-                    .range(0.0..=1000.0)
+                    .range(0.0..=MAX_NOISE_TEMPERATURE_K)
                     .fixed_decimals(1)
                     .suffix(" K")
             )
             .on_hover_text(hover_text);
+            reset_menu(&response, &mut rx_carrier_state.noise_temperature_k, default_state.noise_temperature_k);
             if old_state != rx_carrier_state.noise_temperature_k {
-                *system_needs_update = true;
+                *system_changed = true;
             }
             ui.end_row();
 
@@ -376,8 +465,9 @@ fn rx_system_ui(
                 .monospace();
             ui.label("Noise factor: ").on_hover_text(hover_text.clone());
             old_state = rx_carrier_state.noise_factor_db;
-            ui.add(
+            let response = ui.add(
                 egui::DragValue::new(&mut rx_carrier_state.noise_factor_db)
+                    .custom_parser(expression::parse)
                     .update_while_editing(false)
                     .speed(1.0)
                     .range(0.0..=100.0)
@@ -385,32 +475,87 @@ fn rx_system_ui(
                     .suffix(" dB")
             )
             .on_hover_text(hover_text);
+            reset_menu(&response, &mut rx_carrier_state.noise_factor_db, default_state.noise_factor_db);
             if old_state != rx_carrier_state.noise_factor_db {
-                *system_needs_update = true;
+                *system_changed = true;
+            }
+            ui.end_row();
+
+            // ***** Noise bandwidth ***** //
+            let hover_text = egui::RichText::new("Sets the receiver's noise bandwidth (1 - 10000 MHz), the Tx bandwidth for a matched receiver")
+                .color(egui::Color32::from_rgb(200, 200, 200))
+                .monospace();
+            ui.label("Noise band.: ").on_hover_text(hover_text.clone());
+            old_state = rx_carrier_state.noise_bandwidth_mhz;
+            let response = ui.add(log_slider(&mut rx_carrier_state.noise_bandwidth_mhz, 1.0..=10000.0, 1.0, 1, " MHz"))
+                .on_hover_text(hover_text);
+            reset_menu(&response, &mut rx_carrier_state.noise_bandwidth_mhz, default_state.noise_bandwidth_mhz);
+            if old_state != rx_carrier_state.noise_bandwidth_mhz {
+                *system_changed = true;
+            }
+            ui.end_row();
+
+            // ***** Range oversampling ***** //
+            let hover_text = egui::RichText::new("Sets the receiver's range sampling frequency over its noise bandwidth (1 - 4)")
+                .color(egui::Color32::from_rgb(200, 200, 200))
+                .monospace();
+            ui.label("Oversampling: ").on_hover_text(hover_text.clone());
+            old_state = rx_carrier_state.range_oversampling;
+            let response = ui.add(
+                egui::DragValue::new(&mut rx_carrier_state.range_oversampling)
+                    .custom_parser(expression::parse)
+                    .update_while_editing(false)
+                    .speed(0.01)
+                    .range(1.0..=4.0)
+                    .fixed_decimals(2)
+            )
+            .on_hover_text(hover_text);
+            reset_menu(&response, &mut rx_carrier_state.range_oversampling, default_state.range_oversampling);
+            if old_state != rx_carrier_state.range_oversampling {
+                *system_changed = true;
             }
             ui.end_row();
 
             // ***** Integration time ***** //
-            let hover_text = egui::RichText::new("Sets the receiver's integration time (0 - 100 s)")
+            let hover_text = egui::RichText::new(format!("Sets the receiver's integration time (0 - {MAX_INTEGRATION_TIME_S} s)"))
                 .color(egui::Color32::from_rgb(200, 200, 200))
                 .monospace();
             ui.label("Integration time: ").on_hover_text(hover_text.clone());
-            if rx_carrier_state.squared_pixels {
+            if rx_carrier_state.fixed_pixel_aspect_ratio {
                 rx_carrier_state.integration_time_s =
                     bsar_infos_state.inner.integration_time_s;
             }
             old_state = rx_carrier_state.integration_time_s;
             ui.vertical(|ui| {
-                let old_state = rx_carrier_state.squared_pixels;
-                ui.checkbox(
-                    &mut rx_carrier_state.squared_pixels,
-                    "Squared pixels",
-                );
-                if rx_carrier_state.squared_pixels != old_state {
-                    *system_needs_update = true;
-                }
+                let ratio_hover_text = egui::RichText::new("Solves the integration time for a range:lateral resolution ratio (0.1 - 10),\n1 : 1 giving squared pixels")
+                    .color(egui::Color32::from_rgb(200, 200, 200))
+                    .monospace();
+                ui.horizontal(|ui| {
+                    let old_state = rx_carrier_state.fixed_pixel_aspect_ratio;
+                    ui.checkbox(
+                        &mut rx_carrier_state.fixed_pixel_aspect_ratio,
+                        "Pixel aspect ratio",
+                    ).on_hover_text(ratio_hover_text.clone());
+                    if rx_carrier_state.fixed_pixel_aspect_ratio != old_state {
+                        *system_changed = true;
+                    }
+                    let old_state = rx_carrier_state.pixel_aspect_ratio;
+                    ui.add_enabled(
+                        rx_carrier_state.fixed_pixel_aspect_ratio,
+                        egui::DragValue::new(&mut rx_carrier_state.pixel_aspect_ratio)
+                            .custom_parser(expression::parse)
+                            .update_while_editing(false)
+                            .speed(0.01)
+                            .range(0.1..=10.0)
+                            .fixed_decimals(2)
+                            .suffix(" : 1")
+                    ).on_hover_text(ratio_hover_text);
+                    if rx_carrier_state.pixel_aspect_ratio != old_state {
+                        *system_changed = true;
+                    }
+                });
                 ui.add_enabled_ui(
-                    rx_carrier_state.squared_pixels,
+                    rx_carrier_state.fixed_pixel_aspect_ratio,
                     |ui| {
                         ui.horizontal(|ui| {
                             let old_state = rx_carrier_state.pixel_resolution.clone();
@@ -425,25 +570,43 @@ fn rx_system_ui(
                                 "Slant res."
                             );
                             if rx_carrier_state.pixel_resolution != old_state {
-                                *system_needs_update = true;
+                                *system_changed = true;
                             }
                         });
                     }
                 );
                 ui.add_enabled(
-                    !rx_carrier_state.squared_pixels,
+                    !rx_carrier_state.fixed_pixel_aspect_ratio,
                     egui::DragValue::new(&mut rx_carrier_state.integration_time_s)
+                        .custom_parser(expression::parse)
                         .update_while_editing(false)
                         .speed(1.0)
-                        .range(0.0..=100.0)
+                        .range(0.0..=MAX_INTEGRATION_TIME_S)
                         .fixed_decimals(3)
                         .suffix(" s")
                 )
                 .on_hover_text(hover_text);
             });
             if old_state != rx_carrier_state.integration_time_s {
-                *system_needs_update = true;
+                *system_changed = true;
             }
             ui.end_row();
+
+            // ***** Resolution formula ***** //
+            let hover_text = egui::RichText::new("Sets the formulas of the resolutions:\n- Bisector: bistatic bisector and its derivative at the center (small angle)\n- Exact gradient: range gradients at the start and end of the integration time\nnote: the other formula is shown in the BSAR infos when they differ by more than 1 %.")
+                .color(egui::Color32::from_rgb(200, 200, 200))
+                .monospace();
+            ui.label("Resolutions: ").on_hover_text(hover_text.clone());
+            ui.horizontal(|ui| {
+                let old_state = rx_carrier_state.resolution_formula;
+                for formula in [ResolutionFormula::Bisector, ResolutionFormula::ExactGradient] {
+                    ui.selectable_value(&mut rx_carrier_state.resolution_formula, formula, formula.label())
+                        .on_hover_text(hover_text.clone());
+                }
+                if rx_carrier_state.resolution_formula != old_state {
+                    *system_changed = true;
+                }
+            });
+            ui.end_row();
         });
 }
\ No newline at end of file
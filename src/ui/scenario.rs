@@ -0,0 +1,201 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts, EguiPrimaryContextPass};
+
+use crate::{
+    download::{dropped_file, OpenRequest, SaveFileType, SaveRequest},
+    epoch::UtcTime,
+    scenario::{Scenario, ScenarioMetadata, SCENARIO_EXTENSION},
+    scene::{
+        RxAntennaBeamState, RxAntennaState, RxCarrierState, ScenarioStates,
+        TxAntennaBeamState, TxAntennaState, TxCarrierState
+    },
+    ui::Toasts,
+};
+
+const TEXT_COLOR: egui::Color32 = egui::Color32::from_rgb(200, 200, 200);
+const ERROR_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 120, 120);
+/// Default name of the saved scenario file
+const SCENARIO_FILE_NAME: &str = "scenario.bsargeom";
+
+pub struct ScenarioPlugin;
+
+impl Plugin for ScenarioPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<ScenarioWidget>()
+            .add_systems(EguiPrimaryContextPass, scenario_ui_system);
+    }
+}
+
+/// Metadata of the scenario, embedded in the exports.
+#[derive(Resource, Default)]
+pub struct ScenarioWidget {
+    pub metadata: ScenarioMetadata,
+    /// Timestamp typed by the user, empty when undated
+    timestamp_input: String,
+    /// Error of the last typed timestamp
+    timestamp_error: Option<String>,
+    save_request: Option<SaveRequest>,
+    open_request: Option<OpenRequest>,
+}
+
+impl ScenarioWidget {
+    /// Sets the timestamp, also shown in the timestamp input.
+    pub fn set_timestamp(&mut self, timestamp: Option<UtcTime>) {
+        self.metadata.timestamp = timestamp;
+        self.timestamp_input = timestamp.map(|timestamp| timestamp.to_string()).unwrap_or_default();
+        self.timestamp_error = None;
+    }
+}
+
+fn scenario_ui_system(
+    mut contexts: EguiContexts,
+    mut scenario_widget: ResMut<ScenarioWidget>,
+    tx_state: (ResMut<TxCarrierState>, ResMut<TxAntennaState>, ResMut<TxAntennaBeamState>),
+    rx_state: (ResMut<RxCarrierState>, ResMut<RxAntennaState>, ResMut<RxAntennaBeamState>),
+    mut toasts: ResMut<Toasts>,
+) -> Result {
+    let ctx = contexts.ctx_mut()?;
+    let (mut tx_carrier_state, mut tx_antenna_state, mut tx_antenna_beam_state) = tx_state;
+    let (mut rx_carrier_state, mut rx_antenna_state, mut rx_antenna_beam_state) = rx_state;
+
+    SaveRequest::drive(&mut scenario_widget.save_request, ctx, &mut toasts);
+    // Scenario files dropped on the window, or picked in the dialog
+    let opened = dropped_file(ctx, &[SCENARIO_EXTENSION]).or_else(|| {
+        let result = scenario_widget.open_request.as_mut()?.update(ctx)?;
+        scenario_widget.open_request = None;
+        Some(result)
+    });
+    match opened.map(|result| result.and_then(|(file_name, bytes)| {
+        let text = String::from_utf8(bytes).map_err(|_| format!("{file_name}: not a text file"))?;
+        Scenario::from_text(&text).map(|scenario| (file_name, scenario)).map_err(|error| format!("{file_name}: {error}"))
+    })) {
+        Some(Ok((file_name, scenario))) => {
+            // Sets the states through change detection, updating the scene
+            let mut states = ScenarioStates {
+                tx: (&mut tx_carrier_state, &mut tx_antenna_state, &mut tx_antenna_beam_state),
+                rx: (&mut rx_carrier_state, &mut rx_antenna_state, &mut rx_antenna_beam_state),
+            };
+            let unknown_keys = states.apply(&scenario.parameters);
+            scenario_widget.metadata = scenario.metadata;
+            let timestamp = scenario_widget.metadata.timestamp;
+            scenario_widget.set_timestamp(timestamp);
+            if unknown_keys.is_empty() {
+                toasts.info(format!("Opened {file_name}"));
+            } else {
+                toasts.warning(format!("Opened {file_name}, unknown parameters skipped: {}", unknown_keys.join(", ")));
+            }
+        }
+        Some(Err(error)) => toasts.error(error),
+        None => {}
+    }
+
+    let scenario_window = egui::Window::new("Scenario")
+        .resizable(false)
+        .constrain(true)
+        .collapsible(true)
+        .title_bar(true)
+        .enabled(true)
+        .default_open(false)
+        .default_pos(egui::pos2(320.0, 1360.0));
+    scenario_window.show(ctx, |ui| {
+        let widget = scenario_widget.as_mut();
+        let is_idle = widget.save_request.is_none() && widget.open_request.is_none();
+        ui.horizontal(|ui| {
+            let hover_text = egui::RichText::new("Saves the metadata and the Tx/Rx parameters to a scenario file (.bsargeom)")
+                .color(TEXT_COLOR)
+                .monospace();
+            if ui.add_enabled(is_idle, egui::Button::new("Save…")).on_hover_text(hover_text).clicked() {
+                // Read without marking the states as changed
+                let mut states = ScenarioStates {
+                    tx: (
+                        tx_carrier_state.bypass_change_detection(),
+                        tx_antenna_state.bypass_change_detection(),
+                        tx_antenna_beam_state.bypass_change_detection()
+                    ),
+                    rx: (
+                        rx_carrier_state.bypass_change_detection(),
+                        rx_antenna_state.bypass_change_detection(),
+                        rx_antenna_beam_state.bypass_change_detection()
+                    ),
+                };
+                let scenario = Scenario { metadata: widget.metadata.clone(), parameters: states.parameters() };
+                widget.save_request = Some(SaveRequest::with_file_type(
+                    SCENARIO_FILE_NAME,
+                    scenario.to_text().into_bytes(),
+                    SaveFileType::Scenario
+                ));
+            }
+            let hover_text = egui::RichText::new("Opens a scenario file (.bsargeom), which can also be dropped on the window")
+                .color(TEXT_COLOR)
+                .monospace();
+            if ui.add_enabled(is_idle, egui::Button::new("Open…")).on_hover_text(hover_text).clicked() {
+                widget.open_request = Some(OpenRequest::with_extensions("BSARGeom scenario", &[SCENARIO_EXTENSION]));
+            }
+        });
+        ui.separator();
+        scenario_ui(ui, widget);
+    });
+
+    Ok(())
+}
+
+fn scenario_ui(ui: &mut egui::Ui, widget: &mut ScenarioWidget) {
+    egui::Grid::new("scenario_grid")
+        .num_columns(2)
+        .striped(false)
+        .spacing([20.0, 5.0])
+        .show(ui, |ui| {
+            let hover_text = egui::RichText::new("Documents the scenario in the exports: comments of the CSV tables and\nDXF drawings, title and description of the SVG drawings, extras of the\nglTF scene")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Title: ").on_hover_text(hover_text.clone());
+            ui.add(egui::TextEdit::singleline(&mut widget.metadata.title).desired_width(240.0))
+                .on_hover_text(hover_text.clone());
+            ui.end_row();
+
+            ui.label("Author: ").on_hover_text(hover_text.clone());
+            ui.add(egui::TextEdit::singleline(&mut widget.metadata.author).desired_width(240.0))
+                .on_hover_text(hover_text.clone());
+            ui.end_row();
+
+            ui.label("Description: ").on_hover_text(hover_text.clone());
+            ui.add(
+                egui::TextEdit::multiline(&mut widget.metadata.description)
+                    .desired_width(240.0)
+                    .desired_rows(4)
+            ).on_hover_text(hover_text);
+            ui.end_row();
+
+            let hover_text = egui::RichText::new("Sets the date of the scenario (YYYY-MM-DDThh:mm:ssZ), empty when undated")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Timestamp: ").on_hover_text(hover_text.clone());
+            ui.horizontal(|ui| {
+                let response = ui.add(
+                    egui::TextEdit::singleline(&mut widget.timestamp_input)
+                        .font(egui::TextStyle::Monospace)
+                        .desired_width(200.0)
+                ).on_hover_text(hover_text);
+                if response.lost_focus() {
+                    if widget.timestamp_input.trim().is_empty() {
+                        widget.set_timestamp(None);
+                    } else {
+                        match UtcTime::parse(&widget.timestamp_input) {
+                            Ok(timestamp) => widget.set_timestamp(Some(timestamp)),
+                            Err(error) => widget.timestamp_error = Some(error),
+                        }
+                    }
+                }
+                if ui.button("Now").on_hover_text("Sets the timestamp to the current time").clicked() {
+                    widget.set_timestamp(Some(UtcTime::from_unix_s(UtcTime::now().unix_s().floor())));
+                }
+            });
+            ui.end_row();
+            if let Some(error) = &widget.timestamp_error {
+                ui.label("");
+                ui.label(egui::RichText::new(error).color(ERROR_COLOR));
+                ui.end_row();
+            }
+        });
+}
@@ -0,0 +1,214 @@
+use bevy::{math::DVec3, prelude::*};
+use bevy_egui::{egui, EguiContexts, EguiPrimaryContextPass};
+
+use crate::{
+    bsar::BsarInfos,
+    coverage::CoverageRaster,
+    entities::AntennaBeamFootprintState,
+    incidence::{local_incidence_deg, IncidenceWindow},
+    roi::Statistics,
+    scene::{
+        BsarInfosState, RxAntennaBeamFootprintState, RxAntennaBeamState, RxCarrierState,
+        TxAntennaBeamFootprintState, TxAntennaBeamState, TxCarrierState,
+    },
+    ui::{IncidenceWidget, UpdateStage},
+};
+
+const TEXT_COLOR: egui::Color32 = egui::Color32::from_rgb(200, 200, 200);
+/// Cells per side of the ground grid sampled over the common footprint
+const STATISTICS_GRID_SIZE: usize = 64;
+
+pub struct SceneStatisticsPlugin;
+
+impl Plugin for SceneStatisticsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<SceneStatisticsWidget>()
+            // Sampled over the system updated by the update systems
+            .add_systems(
+                PostUpdate,
+                update_scene_statistics
+                    .in_set(UpdateStage::Derived)
+                    .after(super::tx_panel::update_bsar_system)
+            )
+            .add_systems(EguiPrimaryContextPass, scene_statistics_ui_system);
+    }
+}
+
+/// Aggregate quantities over the common (Tx ∩ Rx) footprint.
+#[derive(Debug, Clone, Copy)]
+struct SceneStatistics {
+    common_area_m2: f64,
+    /// Share of the common footprint within the enabled incidence windows in
+    /// percent, `None` when no window is enabled
+    within_incidence_percent: Option<f64>,
+    resolution_area_m2: Option<Statistics>,
+    nesz_db: Option<Statistics>,
+}
+
+/// Summary of the scene quantities sampled over the ground grid of the common
+/// footprint: its area, the share within the incidence windows, the mean
+/// resolution area and the worst-case NESZ.
+#[derive(Resource)]
+pub struct SceneStatisticsWidget {
+    pub is_enabled: bool,
+    /// Incidence windows the statistics were sampled with
+    sampled_windows: Option<[Option<IncidenceWindow>; 2]>,
+    /// `None` while the footprints do not intersect
+    statistics: Option<SceneStatistics>,
+}
+
+impl Default for SceneStatisticsWidget {
+    fn default() -> Self {
+        Self {
+            is_enabled: false,
+            sampled_windows: None,
+            statistics: None,
+        }
+    }
+}
+
+/// Samples the common footprint on a [`STATISTICS_GRID_SIZE`] x
+/// [`STATISTICS_GRID_SIZE`] ground grid framing it.
+fn scene_statistics(
+    tx: (&TxCarrierState, &TxAntennaBeamState, &TxAntennaBeamFootprintState),
+    rx: (&RxCarrierState, &RxAntennaBeamState, &RxAntennaBeamFootprintState),
+    windows: [Option<IncidenceWindow>; 2],
+) -> Option<SceneStatistics> {
+    // Footprints are in World frame (Y-up): their ground coordinates are (X, Z)
+    let ground = |points: &[DVec3]| -> Vec<(f64, f64)> {
+        points.iter().map(|point| (point.x, point.z)).collect()
+    };
+    let (tx_polygon, rx_polygon) = (ground(&tx.2.inner.points), ground(&rx.2.inner.points));
+    let max_abs = |polygon: &[(f64, f64)]| polygon.iter()
+        .map(|&(a, b)| a.abs().max(b.abs()))
+        .filter(|value| value.is_finite())
+        .fold(0.0, f64::max);
+    // The intersection lies within the smallest of the two footprints
+    let half_extent_m = max_abs(&tx_polygon).min(max_abs(&rx_polygon));
+    if half_extent_m <= 0.0 {
+        return None;
+    }
+    let grid = CoverageRaster::new(half_extent_m, STATISTICS_GRID_SIZE);
+    let cells = grid.cell_centers_inside(&[&tx_polygon, &rx_polygon]);
+    if cells.is_empty() {
+        return None;
+    }
+
+    // Only the range extrema depend on the footprints: not sampled
+    let no_footprint = AntennaBeamFootprintState::default();
+    let (mut within_count, mut resolution_areas_m2, mut neszs_db) = (0, Vec::new(), Vec::new());
+    for &(a, b) in cells.iter() {
+        // World frame (Y-up) to ENU ground plane
+        let point_m = DVec3::new(b, a, 0.0);
+        let mut bsar_infos = BsarInfos::default();
+        bsar_infos.update_from_state_at(tx.0, rx.0, &tx.1.inner, &rx.1.inner, &no_footprint, &no_footprint, &point_m);
+        resolution_areas_m2.push(bsar_infos.resolution_area_m2);
+        neszs_db.push(10.0 * bsar_infos.nesz.log10());
+        let is_within = |window: Option<IncidenceWindow>, carrier_position_m: &DVec3| {
+            window.is_none_or(|window| window.contains(local_incidence_deg(carrier_position_m, &point_m)))
+        };
+        if is_within(windows[0], &tx.0.inner.position_m) && is_within(windows[1], &rx.0.inner.position_m) {
+            within_count += 1;
+        }
+    }
+    Some(SceneStatistics {
+        common_area_m2: cells.len() as f64 * grid.cell_size_m().powi(2),
+        within_incidence_percent: windows.iter().any(Option::is_some)
+            .then(|| 100.0 * within_count as f64 / cells.len() as f64),
+        resolution_area_m2: Statistics::from_values(resolution_areas_m2),
+        nesz_db: Statistics::from_values(neszs_db),
+    })
+}
+
+/// Samples the statistics when the system or the incidence windows change.
+fn update_scene_statistics(
+    mut scene_statistics_widget: ResMut<SceneStatisticsWidget>,
+    bsar_infos_state: Res<BsarInfosState>,
+    incidence_widget: Res<IncidenceWidget>,
+    tx: (Res<TxCarrierState>, Res<TxAntennaBeamState>, Res<TxAntennaBeamFootprintState>),
+    rx: (Res<RxCarrierState>, Res<RxAntennaBeamState>, Res<RxAntennaBeamFootprintState>),
+) {
+    if !scene_statistics_widget.is_enabled {
+        return;
+    }
+    let windows = [
+        incidence_widget.tx.is_enabled.then_some(incidence_widget.tx.window),
+        incidence_widget.rx.is_enabled.then_some(incidence_widget.rx.window),
+    ];
+    // The widget is written by its window every frame: only a new system or
+    // new windows are sampled
+    if scene_statistics_widget.sampled_windows == Some(windows) && !bsar_infos_state.is_changed() {
+        return;
+    }
+    let widget = scene_statistics_widget.bypass_change_detection();
+    widget.sampled_windows = Some(windows);
+    widget.statistics = scene_statistics((&tx.0, &tx.1, &tx.2), (&rx.0, &rx.1, &rx.2), windows);
+}
+
+fn scene_statistics_ui_system(
+    mut contexts: EguiContexts,
+    mut scene_statistics_widget: ResMut<SceneStatisticsWidget>,
+) -> Result {
+    let ctx = contexts.ctx_mut()?;
+
+    let scene_statistics_window = egui::Window::new("Scene Statistics")
+        .resizable(false)
+        .constrain(true)
+        .collapsible(true)
+        .title_bar(true)
+        .enabled(true)
+        .default_open(false)
+        .default_pos(egui::pos2(320.0, 1040.0));
+    scene_statistics_window.show(ctx, |ui| {
+        scene_statistics_ui(ui, &mut scene_statistics_widget);
+    });
+
+    Ok(())
+}
+
+fn scene_statistics_ui(ui: &mut egui::Ui, widget: &mut SceneStatisticsWidget) {
+    let hover_text = egui::RichText::new("Samples the common (Tx ∩ Rx) footprint on a ground grid, the incidences\nbeing checked against the enabled windows of the \"Incidence\" window")
+        .color(TEXT_COLOR)
+        .monospace();
+    if ui.checkbox(&mut widget.is_enabled, "Compute").on_hover_text(hover_text).changed() {
+        // Sampled again once enabled
+        widget.sampled_windows = None;
+    }
+    ui.add_enabled_ui(widget.is_enabled, |ui| {
+        egui::Grid::new("scene_statistics_grid")
+            .num_columns(2)
+            .striped(false)
+            .spacing([20.0, 5.0])
+            .show(ui, |ui| {
+                let statistics = widget.statistics.filter(|_| widget.is_enabled);
+                ui.label("Common footprint area:");
+                ui.label(statistics.map_or("-".to_string(), |statistics| format!("{:.6} km²", statistics.common_area_m2 * 1e-6)));
+                ui.end_row();
+                ui.label("Within incidence windows:");
+                ui.label(
+                    statistics
+                        .and_then(|statistics| statistics.within_incidence_percent)
+                        .map_or("-".to_string(), |percent| format!("{percent:.1} %"))
+                );
+                ui.end_row();
+                ui.label("Mean resolution area:");
+                ui.label(
+                    statistics
+                        .and_then(|statistics| statistics.resolution_area_m2)
+                        .map_or("-".to_string(), |resolution_area_m2| format!("{:.3} m²", resolution_area_m2.mean))
+                );
+                ui.end_row();
+                ui.label("Worst-case NESZ:");
+                ui.label(
+                    statistics
+                        .and_then(|statistics| statistics.nesz_db)
+                        .map_or("-".to_string(), |nesz_db| format!("{:.2} dB", nesz_db.max))
+                );
+                ui.end_row();
+            });
+        if widget.is_enabled && widget.statistics.is_none() {
+            ui.label(egui::RichText::new("The footprints do not intersect").color(TEXT_COLOR));
+        }
+    });
+}
@@ -0,0 +1,167 @@
+use bevy::{
+    camera::RenderTarget,
+    ecs::schedule::ScheduleLabel,
+    prelude::*,
+    window::WindowRef,
+};
+use bevy_egui::{egui, EguiContext, EguiMultipassSchedule, PrimaryEguiContext};
+
+use crate::{
+    scene::{BsarInfosState, RxAntennaBeamFootprintState, RxCarrierState, TxAntennaBeamFootprintState, TxCarrierState},
+    ui::{bsar_infos_ui, carrier_infos_ui, show_gaf_window, GafState, HudWidget, MenuWidget, Toasts},
+};
+
+const TEXT_COLOR: egui::Color32 = egui::Color32::from_rgb(200, 200, 200);
+
+/// egui pass of the second window context, run by bevy_egui like the primary
+/// `EguiPrimaryContextPass`.
+#[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SecondWindowContextPass;
+
+pub struct SecondWindowPlugin;
+
+impl Plugin for SecondWindowPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<SecondWindowWidget>()
+            .add_systems(Update, update_second_window)
+            .add_systems(SecondWindowContextPass, second_window_ui_system);
+    }
+}
+
+/// Detaches the info windows and the GAF plot into a second native window, so
+/// that the 3D view stays unobstructed on one monitor while the numbers live
+/// on another. Native only: the web build has a single canvas.
+#[derive(Resource, Default)]
+pub struct SecondWindowWidget {
+    /// Set by the "Detach" and "Reattach" buttons, cleared when the second
+    /// window is closed.
+    pub is_detached: bool,
+    /// Second window and its camera, while detached
+    entities: Option<(Entity, Entity)>,
+}
+
+/// "Detach" button of the info windows.
+pub fn detach_button(ui: &mut egui::Ui, widget: &mut SecondWindowWidget) {
+    if cfg!(target_arch = "wasm32") {
+        return;
+    }
+    let hover_text = egui::RichText::new("Moves the infos and the GAF plot to a second window,\nto be placed on another monitor")
+        .color(TEXT_COLOR)
+        .monospace();
+    if ui.button("⧉ Detach").on_hover_text(hover_text).clicked() {
+        widget.is_detached = true;
+    }
+}
+
+/// Opens the second window on detaching, and closes it on reattaching. The
+/// GAF texture belongs to the egui context that loaded it, so it is rebuilt
+/// when the plot moves to the other window.
+fn update_second_window(
+    mut commands: Commands,
+    mut widget: ResMut<SecondWindowWidget>,
+    mut gaf_state: ResMut<GafState>,
+    window_q: Query<(), With<Window>>,
+) {
+    match widget.entities {
+        None if widget.is_detached => {
+            let window = commands.spawn(Window {
+                title: "BSAR Geometry visualizer - Infos".to_string(),
+                name: Some("bsargeom".to_string()),
+                resolution: (480, 900).into(),
+                ..default()
+            }).id();
+            let camera = commands.spawn((
+                Camera2d,
+                RenderTarget::Window(WindowRef::Entity(window)),
+                EguiMultipassSchedule::new(SecondWindowContextPass),
+            )).id();
+            widget.entities = Some((window, camera));
+            gaf_state.invalidate_texture();
+        }
+        Some((window, camera)) => {
+            // Closed by its title bar button: bevy despawned the window
+            let is_open = window_q.contains(window);
+            if widget.is_detached && is_open {
+                return;
+            }
+            if is_open {
+                commands.entity(window).despawn();
+            }
+            commands.entity(camera).despawn();
+            widget.entities = None;
+            widget.is_detached = false;
+            gaf_state.invalidate_texture();
+        }
+        None => {}
+    }
+}
+
+fn second_window_ui_system(
+    mut egui_context: Single<&mut EguiContext, Without<PrimaryEguiContext>>,
+    mut widget: ResMut<SecondWindowWidget>,
+    mut menu_widget: ResMut<MenuWidget>,
+    (mut gaf_state, mut hud_widget, mut toasts): (ResMut<GafState>, ResMut<HudWidget>, ResMut<Toasts>),
+    tx: (Res<TxCarrierState>, Res<TxAntennaBeamFootprintState>),
+    rx: (Res<RxCarrierState>, Res<RxAntennaBeamFootprintState>),
+    bsar_infos_state: Res<BsarInfosState>,
+) {
+    let ctx = egui_context.get_mut();
+    let (tx_carrier_state, tx_antenna_beam_footprint_state) = tx;
+    let (rx_carrier_state, rx_antenna_beam_footprint_state) = rx;
+
+    let mut root_ui = egui::Ui::new(
+        ctx.clone(),
+        "second_window".into(),
+        egui::UiBuilder::new()
+            .layer_id(egui::LayerId::background())
+            .max_rect(ctx.viewport_rect()),
+    );
+    egui::Frame::central_panel(root_ui.style()).show(&mut root_ui, |ui| {
+        ui.set_min_size(ui.available_size());
+        ui.horizontal(|ui| {
+            let hover_text = egui::RichText::new("Moves the infos and the GAF plot back to the main window")
+                .color(TEXT_COLOR)
+                .monospace();
+            if ui.button("Reattach").on_hover_text(hover_text).clicked() {
+                widget.is_detached = false;
+            }
+            ui.toggle_value(&mut menu_widget.is_gaf_opened, "GAF plot");
+        });
+        ui.separator();
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            egui::CollapsingHeader::new("Tx Infos").default_open(true).show(ui, |ui| {
+                carrier_infos_ui(
+                    ui,
+                    &tx_carrier_state.inner,
+                    &tx_antenna_beam_footprint_state.inner,
+                    "tx",
+                    &mut hud_widget,
+                );
+            });
+            egui::CollapsingHeader::new("Rx Infos").default_open(true).show(ui, |ui| {
+                carrier_infos_ui(
+                    ui,
+                    &rx_carrier_state.inner,
+                    &rx_antenna_beam_footprint_state.inner,
+                    "rx",
+                    &mut hud_widget,
+                );
+            });
+            egui::CollapsingHeader::new("BSAR Infos").default_open(true).show(ui, |ui| {
+                bsar_infos_ui(ui, &bsar_infos_state.inner, menu_widget.geometry_mode, &mut hud_widget);
+            });
+        });
+    });
+
+    // Generalized Ambiguity Function plot window, floating over the infos
+    show_gaf_window(
+        ctx,
+        &mut menu_widget.is_gaf_opened,
+        &mut gaf_state,
+        &mut toasts,
+        &bsar_infos_state.inner,
+        tx_carrier_state.bandwidth_mhz * 1e6, // MHz -> Hz
+        tx_carrier_state.center_frequency_ghz * 1e9, // GHz -> Hz
+    );
+}
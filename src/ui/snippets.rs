@@ -0,0 +1,210 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts, EguiPrimaryContextPass};
+
+use crate::{
+    scene::{
+        RxAntennaBeamState, RxAntennaState, RxCarrierState, ScenarioStates,
+        TxAntennaBeamState, TxAntennaState, TxCarrierState
+    },
+    snippets::{Snippet, SnippetBlock, Snippets},
+    ui::{AntennaCarrier, Toasts},
+};
+
+const TEXT_COLOR: egui::Color32 = egui::Color32::from_rgb(200, 200, 200);
+
+pub struct SnippetsPlugin;
+
+impl Plugin for SnippetsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .insert_resource(SnippetsWidget::load())
+            .add_systems(EguiPrimaryContextPass, snippets_ui_system);
+    }
+}
+
+/// Named parameter sets of the user, loaded at startup and saved as soon as
+/// edited.
+#[derive(Resource)]
+pub struct SnippetsWidget {
+    pub snippets: Snippets,
+    /// Block of the listed snippets
+    block: SnippetBlock,
+    /// Carrier the antenna snippets are taken from and applied to
+    antenna_carrier: AntennaCarrier,
+    /// Name of the next saved snippet
+    name: String,
+}
+
+impl SnippetsWidget {
+    pub fn load() -> Self {
+        Self {
+            snippets: Snippets::load(),
+            block: SnippetBlock::TxSystem,
+            antenna_carrier: AntennaCarrier::Tx,
+            name: String::new(),
+        }
+    }
+
+    /// Gets the carrier prefix of the scenario parameters of the block.
+    fn prefix(&self) -> &'static str {
+        self.block.carrier_prefix().unwrap_or(match self.antenna_carrier {
+            AntennaCarrier::Tx => "tx",
+            AntennaCarrier::Rx => "rx",
+        })
+    }
+}
+
+/// Edit of the snippets asked by the window.
+enum SnippetAction {
+    /// Saves the parameters of the block under the typed name
+    Save,
+    Apply(usize),
+    Delete(usize),
+}
+
+fn snippets_ui_system(
+    mut contexts: EguiContexts,
+    mut snippets_widget: ResMut<SnippetsWidget>,
+    tx_state: (ResMut<TxCarrierState>, ResMut<TxAntennaState>, ResMut<TxAntennaBeamState>),
+    rx_state: (ResMut<RxCarrierState>, ResMut<RxAntennaState>, ResMut<RxAntennaBeamState>),
+    mut toasts: ResMut<Toasts>,
+) -> Result {
+    let ctx = contexts.ctx_mut()?;
+    let (mut tx_carrier_state, mut tx_antenna_state, mut tx_antenna_beam_state) = tx_state;
+    let (mut rx_carrier_state, mut rx_antenna_state, mut rx_antenna_beam_state) = rx_state;
+
+    let snippets_window = egui::Window::new("Parameter Snippets")
+        .resizable(false)
+        .constrain(true)
+        .collapsible(true)
+        .title_bar(true)
+        .enabled(true)
+        .default_open(false)
+        .default_pos(egui::pos2(320.0, 1400.0));
+    let mut action = None;
+    snippets_window.show(ctx, |ui| {
+        action = snippets_ui(ui, &mut snippets_widget);
+    });
+
+    let widget = snippets_widget.as_mut();
+    let prefix = widget.prefix();
+    let is_edited = matches!(action, Some(SnippetAction::Save | SnippetAction::Delete(_)));
+    match action {
+        Some(SnippetAction::Save) => {
+            // Read without marking the states as changed
+            let mut states = ScenarioStates {
+                tx: (
+                    tx_carrier_state.bypass_change_detection(),
+                    tx_antenna_state.bypass_change_detection(),
+                    tx_antenna_beam_state.bypass_change_detection()
+                ),
+                rx: (
+                    rx_carrier_state.bypass_change_detection(),
+                    rx_antenna_state.bypass_change_detection(),
+                    rx_antenna_beam_state.bypass_change_detection()
+                ),
+            };
+            let snippet = Snippet::capture(&widget.name, widget.block, prefix, &states.parameters());
+            widget.snippets.insert(snippet);
+            widget.name.clear();
+        }
+        Some(SnippetAction::Apply(index)) => {
+            // Sets the states through change detection, updating the scene
+            let mut states = ScenarioStates {
+                tx: (&mut tx_carrier_state, &mut tx_antenna_state, &mut tx_antenna_beam_state),
+                rx: (&mut rx_carrier_state, &mut rx_antenna_state, &mut rx_antenna_beam_state),
+            };
+            let snippet = &widget.snippets.snippets[index];
+            states.apply(&snippet.scenario_parameters(prefix));
+            toasts.info(format!("Applied {}", snippet.name));
+        }
+        Some(SnippetAction::Delete(index)) => {
+            widget.snippets.snippets.remove(index);
+        }
+        None => {}
+    }
+    if is_edited && let Err(error) = widget.snippets.save() {
+        toasts.warning(format!("Snippets not saved: {error}"));
+    }
+
+    Ok(())
+}
+
+/// Shows the snippets of the selected block, returning the asked edit.
+fn snippets_ui(ui: &mut egui::Ui, widget: &mut SnippetsWidget) -> Option<SnippetAction> {
+    let mut action = None;
+    egui::Grid::new("snippets_grid")
+        .num_columns(2)
+        .striped(false)
+        .spacing([20.0, 5.0])
+        .show(ui, |ui| {
+            let hover_text = egui::RichText::new("Tx system: waveform and power\nRx system: noise and processing\nAntenna: orientation, lever arm and beam")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Block: ").on_hover_text(hover_text);
+            egui::ComboBox::from_id_salt("snippets_block")
+                .selected_text(widget.block.label())
+                .show_ui(ui, |ui| {
+                    for block in SnippetBlock::ALL {
+                        ui.selectable_value(&mut widget.block, block, block.label());
+                    }
+                });
+            ui.end_row();
+
+            if widget.block == SnippetBlock::Antenna {
+                let hover_text = egui::RichText::new("Carrier whose antenna is saved, or set by the applied snippet")
+                    .color(TEXT_COLOR)
+                    .monospace();
+                ui.label("Carrier: ").on_hover_text(hover_text);
+                ui.horizontal(|ui| {
+                    ui.radio_value(&mut widget.antenna_carrier, AntennaCarrier::Tx, "Tx");
+                    ui.radio_value(&mut widget.antenna_carrier, AntennaCarrier::Rx, "Rx");
+                });
+                ui.end_row();
+            }
+
+            let hover_text = egui::RichText::new("Saves the current parameters of the block under this name,\nreplacing the snippet of the same name")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Name: ");
+            ui.horizontal(|ui| {
+                ui.add(
+                    egui::TextEdit::singleline(&mut widget.name)
+                        .hint_text("X-band 800 MHz chirp")
+                        .desired_width(180.0)
+                );
+                let is_named = !widget.name.trim().is_empty();
+                if ui.add_enabled(is_named, egui::Button::new("Save")).on_hover_text(hover_text).clicked() {
+                    action = Some(SnippetAction::Save);
+                }
+            });
+            ui.end_row();
+        });
+
+    ui.separator();
+    let mut is_empty = true;
+    for (index, snippet) in widget.snippets.snippets.iter().enumerate().filter(|(_, snippet)| snippet.block == widget.block) {
+        is_empty = false;
+        ui.horizontal(|ui| {
+            let hover_text = egui::RichText::new(
+                snippet.parameters.iter()
+                    .map(|(key, value)| format!("{key} = {value}"))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            )
+                .color(TEXT_COLOR)
+                .monospace();
+            if ui.button("Apply").on_hover_text(hover_text).clicked() {
+                action = Some(SnippetAction::Apply(index));
+            }
+            if ui.small_button("🗑").on_hover_text("Removes the snippet").clicked() {
+                action = Some(SnippetAction::Delete(index));
+            }
+            ui.label(&snippet.name);
+        });
+    }
+    if is_empty {
+        ui.label(egui::RichText::new(format!("No {} snippet saved", widget.block.label())).color(TEXT_COLOR));
+    }
+    action
+}
@@ -0,0 +1,256 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts, EguiPrimaryContextPass};
+
+use crate::{
+    bsar::SPEED_OF_LIGHT_IN_VACUUM,
+    entities::{carrier_transform_from_state, AntennaState, CarrierState},
+    expression,
+    scene::{BsarInfosState, RxAntennaState, RxCarrierState, TxAntennaState, TxCarrierState},
+    squint::{carrier_doppler_hz, tune_heading, HeadingTuning},
+};
+
+const TEXT_COLOR: egui::Color32 = egui::Color32::from_rgb(200, 200, 200);
+const ERROR_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 120, 120);
+
+pub struct SquintPlugin;
+
+impl Plugin for SquintPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<SquintWidget>()
+            .add_systems(EguiPrimaryContextPass, squint_ui_system);
+    }
+}
+
+/// Antennas turned in heading by the tuning.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SquintAntennas {
+    Tx,
+    Rx,
+    /// Each carrier takes half of the target Doppler centroid
+    Both,
+}
+
+/// Tunes the Tx and/or Rx antenna headings, within bounds, so that the
+/// Doppler centroid at scene center reaches a target value (zero for
+/// quasi-zero-Doppler acquisitions).
+#[derive(Resource)]
+pub struct SquintWidget {
+    /// Target Doppler centroid in Hz at scene center
+    pub target_doppler_hz: f64,
+    pub antennas: SquintAntennas,
+    /// (min, max) antenna headings in degrees allowed to the tuning
+    pub tx_heading_bounds_deg: (f64, f64),
+    pub rx_heading_bounds_deg: (f64, f64),
+    /// Result of the last tuning
+    status: Option<Result<String, String>>,
+}
+
+impl Default for SquintWidget {
+    fn default() -> Self {
+        Self {
+            target_doppler_hz: 0.0,
+            antennas: SquintAntennas::Tx,
+            tx_heading_bounds_deg: (0.0, 180.0),
+            rx_heading_bounds_deg: (0.0, 180.0),
+            status: None,
+        }
+    }
+}
+
+/// Tunes the heading of one antenna so that its carrier Doppler contribution
+/// at scene center reaches `target_hz`.
+fn tune_antenna_heading(
+    lem: f64,
+    carrier_state: &CarrierState,
+    antenna_state: &AntennaState,
+    target_hz: f64,
+    bounds_deg: (f64, f64),
+) -> Option<HeadingTuning> {
+    let doppler_hz = |heading_deg: f64| {
+        // The carrier moves so that the turned boresight still intersects the scene center
+        let mut carrier_state = carrier_state.clone();
+        let antenna_state = AntennaState { heading_deg, ..antenna_state.clone() };
+        carrier_transform_from_state(&mut carrier_state, &antenna_state);
+        carrier_doppler_hz(lem, &carrier_state.position_m, &carrier_state.velocity_vector_mps)
+    };
+    tune_heading(doppler_hz, target_hz, antenna_state.heading_deg, bounds_deg)
+}
+
+fn squint_ui_system(
+    mut contexts: EguiContexts,
+    mut squint_widget: ResMut<SquintWidget>,
+    bsar_infos_state: Res<BsarInfosState>,
+    tx: (Res<TxCarrierState>, ResMut<TxAntennaState>),
+    rx: (Res<RxCarrierState>, ResMut<RxAntennaState>),
+) -> Result {
+    let ctx = contexts.ctx_mut()?;
+    let (tx_carrier_state, mut tx_antenna_state) = tx;
+    let (rx_carrier_state, mut rx_antenna_state) = rx;
+
+    let squint_window = egui::Window::new("Doppler Centroid")
+        .resizable(false)
+        .constrain(true)
+        .collapsible(true)
+        .title_bar(true)
+        .enabled(true)
+        .default_open(false)
+        .default_pos(egui::pos2(320.0, 360.0));
+    squint_window.show(ctx, |ui| {
+        if !squint_ui(ui, &mut squint_widget, bsar_infos_state.inner.doppler_frequency_hz) {
+            return;
+        }
+        let lem = SPEED_OF_LIGHT_IN_VACUUM / (tx_carrier_state.center_frequency_ghz * 1e9); // wavelength in m
+        let tx_doppler_hz = carrier_doppler_hz(
+            lem,
+            &tx_carrier_state.inner.position_m,
+            &tx_carrier_state.inner.velocity_vector_mps
+        );
+        let rx_doppler_hz = carrier_doppler_hz(
+            lem,
+            &rx_carrier_state.inner.position_m,
+            &rx_carrier_state.inner.velocity_vector_mps
+        );
+        // Doppler contribution targeted by each tuned carrier
+        let target_hz = squint_widget.target_doppler_hz;
+        let (tx_target_hz, rx_target_hz) = match squint_widget.antennas {
+            SquintAntennas::Tx => (Some(target_hz - rx_doppler_hz), None),
+            SquintAntennas::Rx => (None, Some(target_hz - tx_doppler_hz)),
+            SquintAntennas::Both => (Some(0.5 * target_hz), Some(0.5 * target_hz)),
+        };
+        let mut messages = Vec::new();
+        let mut are_reached = true;
+        let mut report = |name: &str, tuning: Option<HeadingTuning>| match tuning {
+            Some(tuning) if tuning.is_reached => {
+                messages.push(format!("{name} antenna heading: {:.3}°", tuning.heading_deg));
+            }
+            Some(tuning) => {
+                are_reached = false;
+                messages.push(format!("{name} target out of reach, closest antenna heading: {:.3}°", tuning.heading_deg));
+            }
+            None => {
+                are_reached = false;
+                messages.push(format!("{name} antenna heading bounds are invalid"));
+            }
+        };
+        if let Some(tx_target_hz) = tx_target_hz {
+            let tuning = tune_antenna_heading(
+                lem,
+                &tx_carrier_state.inner,
+                &tx_antenna_state.inner,
+                tx_target_hz,
+                squint_widget.tx_heading_bounds_deg
+            );
+            if let Some(tuning) = tuning {
+                // Triggers the update systems
+                tx_antenna_state.inner.heading_deg = tuning.heading_deg;
+            }
+            report("Tx", tuning);
+        }
+        if let Some(rx_target_hz) = rx_target_hz {
+            let tuning = tune_antenna_heading(
+                lem,
+                &rx_carrier_state.inner,
+                &rx_antenna_state.inner,
+                rx_target_hz,
+                squint_widget.rx_heading_bounds_deg
+            );
+            if let Some(tuning) = tuning {
+                // Triggers the update systems
+                rx_antenna_state.inner.heading_deg = tuning.heading_deg;
+            }
+            report("Rx", tuning);
+        }
+        let status = messages.join("\n");
+        squint_widget.status = Some(if are_reached { Ok(status) } else { Err(status) });
+    });
+
+    Ok(())
+}
+
+/// Returns true when the antenna headings must be tuned.
+fn squint_ui(ui: &mut egui::Ui, widget: &mut SquintWidget, doppler_frequency_hz: f64) -> bool {
+    let mut tune = false;
+    egui::Grid::new("squint_grid")
+        .num_columns(2)
+        .striped(false)
+        .spacing([20.0, 5.0])
+        .show(ui, |ui| {
+            ui.label("Current centroid: ");
+            ui.label(format!("{doppler_frequency_hz:.3} Hz"));
+            ui.end_row();
+
+            let hover_text = egui::RichText::new("Sets the Doppler centroid to reach at scene center (0 Hz for a zero-Doppler acquisition)")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Target centroid: ").on_hover_text(hover_text.clone());
+            ui.add(
+                egui::DragValue::new(&mut widget.target_doppler_hz)
+                    .custom_parser(expression::parse)
+                    .update_while_editing(false)
+                    .speed(1.0)
+                    .fixed_decimals(3)
+                    .suffix(" Hz")
+            ).on_hover_text(hover_text);
+            ui.end_row();
+
+            let hover_text = egui::RichText::new("Sets the antennas turned in heading, both taking half of the target centroid")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Tuned antennas: ").on_hover_text(hover_text);
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut widget.antennas, SquintAntennas::Tx, "Tx");
+                ui.radio_value(&mut widget.antennas, SquintAntennas::Rx, "Rx");
+                ui.radio_value(&mut widget.antennas, SquintAntennas::Both, "Both");
+            });
+            ui.end_row();
+
+            let tuned_antennas = [
+                ("Tx", &mut widget.tx_heading_bounds_deg, widget.antennas != SquintAntennas::Rx),
+                ("Rx", &mut widget.rx_heading_bounds_deg, widget.antennas != SquintAntennas::Tx),
+            ];
+            for (name, bounds_deg, is_tuned) in tuned_antennas {
+                let hover_text = egui::RichText::new(format!("Sets the {name} antenna heading range allowed to the tuning (-180° - 180°)"))
+                    .color(TEXT_COLOR)
+                    .monospace();
+                ui.label(format!("{name} heading bounds: ")).on_hover_text(hover_text.clone());
+                ui.add_enabled_ui(is_tuned, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.add(
+                            egui::DragValue::new(&mut bounds_deg.0)
+                                .custom_parser(expression::parse)
+                                .update_while_editing(false)
+                                .speed(0.5)
+                                .range(-180.0..=bounds_deg.1)
+                                .fixed_decimals(1)
+                                .suffix("°")
+                        ).on_hover_text(hover_text.clone());
+                        ui.label("-");
+                        ui.add(
+                            egui::DragValue::new(&mut bounds_deg.1)
+                                .custom_parser(expression::parse)
+                                .update_while_editing(false)
+                                .speed(0.5)
+                                .range(bounds_deg.0..=180.0)
+                                .fixed_decimals(1)
+                                .suffix("°")
+                        ).on_hover_text(hover_text);
+                    });
+                });
+                ui.end_row();
+            }
+
+            let hover_text = egui::RichText::new("Turns the antennas in heading, the carriers moving around the scene center,\nthe closest heading to the current one being kept when several reach the target")
+                .color(TEXT_COLOR)
+                .monospace();
+            tune = ui.button("Tune headings").on_hover_text(hover_text).clicked();
+            ui.end_row();
+        });
+
+    match &widget.status {
+        Some(Ok(status)) => { ui.label(egui::RichText::new(status).color(TEXT_COLOR)); }
+        Some(Err(error)) => { ui.label(egui::RichText::new(error).color(ERROR_COLOR)); }
+        None => {}
+    }
+    tune
+}
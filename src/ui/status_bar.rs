@@ -0,0 +1,212 @@
+use bevy::prelude::*;
+use bevy_egui::egui;
+
+use crate::{
+    bsar::BsarInfos,
+    ui::infos::{resolution_area_text, resolution_text},
+};
+
+const TEXT_COLOR: egui::Color32 = egui::Color32::from_rgb(200, 200, 200);
+
+/// A BSAR metric of the "BSAR Infos" window that can be shown in the status
+/// bar or pinned to the HUD.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BsarMetric {
+    SlantRangeMin,
+    SlantRangeCenter,
+    SlantRangeMax,
+    DirectRange,
+    BistaticAngle,
+    SlantRangeResolution,
+    GroundRangeResolution,
+    SlantLateralResolution,
+    GroundLateralResolution,
+    ResolutionArea,
+    DopplerFrequency,
+    DopplerRate,
+    IntegrationTime,
+    ProcessedDopplerBandwidth,
+    Nesz,
+    Rasr,
+    Aasr,
+    SamplingFrequency,
+    DataRate,
+}
+
+impl BsarMetric {
+    /// Every metric, in the order of the "BSAR Infos" window.
+    pub const ALL: [Self; 19] = [
+        Self::SlantRangeMin,
+        Self::SlantRangeCenter,
+        Self::SlantRangeMax,
+        Self::DirectRange,
+        Self::BistaticAngle,
+        Self::SlantRangeResolution,
+        Self::GroundRangeResolution,
+        Self::SlantLateralResolution,
+        Self::GroundLateralResolution,
+        Self::ResolutionArea,
+        Self::DopplerFrequency,
+        Self::DopplerRate,
+        Self::IntegrationTime,
+        Self::ProcessedDopplerBandwidth,
+        Self::Nesz,
+        Self::Rasr,
+        Self::Aasr,
+        Self::SamplingFrequency,
+        Self::DataRate,
+    ];
+
+    /// Label of the metric, as in the "BSAR Infos" window.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::SlantRangeMin => "Slant range min",
+            Self::SlantRangeCenter => "Slant range center",
+            Self::SlantRangeMax => "Slant range max",
+            Self::DirectRange => "Tx/Rx direct range",
+            Self::BistaticAngle => "Bistatic angle",
+            Self::SlantRangeResolution => "Slant range res.",
+            Self::GroundRangeResolution => "Ground range res.",
+            Self::SlantLateralResolution => "Slant lateral res.",
+            Self::GroundLateralResolution => "Ground lateral res.",
+            Self::ResolutionArea => "Resolution area",
+            Self::DopplerFrequency => "Doppler frequency",
+            Self::DopplerRate => "Doppler rate",
+            Self::IntegrationTime => "Integration time",
+            Self::ProcessedDopplerBandwidth => "Processed Dop. band.",
+            Self::Nesz => "NESZ",
+            Self::Rasr => "RASR",
+            Self::Aasr => "AASR",
+            Self::SamplingFrequency => "Sampling frequency",
+            Self::DataRate => "Data rate",
+        }
+    }
+
+    /// Formatted value of the metric, with the units of the "BSAR Infos" window.
+    pub fn value_text(self, bsar_infos: &BsarInfos) -> String {
+        let meters = |value_m: f64| if value_m >= 1e3 {
+            format!("{:.3} km", value_m * 1e-3)
+        } else {
+            format!("{:.3} m", value_m)
+        };
+        let hertz = |value_hz: f64, unit: &str| if value_hz.abs() >= 1e3 {
+            format!("{:.3} k{unit}", value_hz * 1e-3)
+        } else {
+            format!("{:.3} {unit}", value_hz)
+        };
+        match self {
+            Self::SlantRangeMin => meters(bsar_infos.range_min_m),
+            Self::SlantRangeCenter => meters(bsar_infos.range_center_m),
+            Self::SlantRangeMax => meters(bsar_infos.range_max_m),
+            Self::DirectRange => meters(bsar_infos.direct_range_m),
+            Self::BistaticAngle => format!("{:.3} °", bsar_infos.bistatic_angle_deg),
+            Self::SlantRangeResolution => resolution_text(bsar_infos.slant_range_resolution_m),
+            Self::GroundRangeResolution => resolution_text(bsar_infos.ground_range_resolution_m),
+            Self::SlantLateralResolution => resolution_text(bsar_infos.slant_lateral_resolution_m),
+            Self::GroundLateralResolution => resolution_text(bsar_infos.ground_lateral_resolution_m),
+            Self::ResolutionArea => resolution_area_text(bsar_infos.resolution_area_m2),
+            Self::DopplerFrequency => hertz(bsar_infos.doppler_frequency_hz, "Hz"),
+            Self::DopplerRate => hertz(bsar_infos.doppler_rate_hzps, "Hz/s"),
+            Self::IntegrationTime => format!("{:.3} s", bsar_infos.integration_time_s),
+            Self::ProcessedDopplerBandwidth => hertz(bsar_infos.processed_doppler_bandwidth_hz, "Hz"),
+            Self::Nesz => if bsar_infos.nesz.is_nan() {
+                "-".to_owned()
+            } else {
+                format!("{:.3} dBm²/m²", 10.0 * bsar_infos.nesz.log10())
+            },
+            Self::Rasr => if bsar_infos.rasr.is_nan() {
+                "-".to_owned()
+            } else {
+                format!("{:.3} dB", 10.0 * bsar_infos.rasr.log10())
+            },
+            Self::Aasr => if bsar_infos.aasr.is_nan() {
+                "-".to_owned()
+            } else {
+                format!("{:.3} dB", 10.0 * bsar_infos.aasr.log10())
+            },
+            Self::SamplingFrequency => format!("{:.3} MHz", bsar_infos.sampling_frequency_hz * 1e-6),
+            Self::DataRate => if bsar_infos.data_rate_sps.is_nan() {
+                "-".to_owned()
+            } else {
+                format!("{:.3} Msamples/s", bsar_infos.data_rate_sps * 1e-6)
+            },
+        }
+    }
+}
+
+/// Metrics shown in the bottom status bar, in the order of [`BsarMetric::ALL`].
+#[derive(Resource)]
+pub struct StatusBarWidget {
+    pub metrics: Vec<BsarMetric>,
+}
+
+impl Default for StatusBarWidget {
+    fn default() -> Self {
+        Self {
+            metrics: vec![
+                BsarMetric::BistaticAngle,
+                BsarMetric::GroundRangeResolution,
+                BsarMetric::GroundLateralResolution,
+                BsarMetric::Nesz,
+            ],
+        }
+    }
+}
+
+impl StatusBarWidget {
+    /// Shows or hides `metric`, keeping the order of [`BsarMetric::ALL`].
+    pub fn set_shown(&mut self, metric: BsarMetric, is_shown: bool) {
+        self.metrics.retain(|&shown| shown != metric);
+        if is_shown {
+            self.metrics.push(metric);
+            self.metrics.sort_by_key(|&shown| BsarMetric::ALL.iter().position(|&m| m == shown));
+        }
+    }
+}
+
+/// Status bar content: the metrics selector then the selected metrics.
+pub fn status_bar_ui(ui: &mut egui::Ui, widget: &mut StatusBarWidget, bsar_infos: &BsarInfos) {
+    ui.horizontal(|ui| {
+        ui.menu_button("☰", |ui| {
+            for metric in BsarMetric::ALL {
+                let mut is_shown = widget.metrics.contains(&metric);
+                if ui.checkbox(&mut is_shown, metric.label()).changed() {
+                    widget.set_shown(metric, is_shown);
+                }
+            }
+        })
+        .response
+        .on_hover_text(egui::RichText::new("Selects the metrics of the status bar").color(TEXT_COLOR).monospace());
+        for metric in widget.metrics.iter() {
+            ui.separator();
+            ui.label(format!("{}: {}", metric.label(), metric.value_text(bsar_infos)));
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_bar_metrics_keep_their_order() {
+        let mut widget = StatusBarWidget::default();
+        widget.set_shown(BsarMetric::SlantRangeCenter, true);
+        widget.set_shown(BsarMetric::Nesz, false);
+        widget.set_shown(BsarMetric::BistaticAngle, true); // Already shown
+        assert_eq!(widget.metrics, [
+            BsarMetric::SlantRangeCenter,
+            BsarMetric::BistaticAngle,
+            BsarMetric::GroundRangeResolution,
+            BsarMetric::GroundLateralResolution,
+        ]);
+    }
+
+    #[test]
+    fn diverging_resolutions_are_capped() {
+        let bsar_infos = BsarInfos { slant_range_resolution_m: 1.5, ground_range_resolution_m: 2e6, resolution_area_m2: 3e12, ..default() };
+        assert_eq!(BsarMetric::SlantRangeResolution.value_text(&bsar_infos), "1.500 m");
+        assert_eq!(BsarMetric::GroundRangeResolution.value_text(&bsar_infos), "> 10 km");
+        assert_eq!(BsarMetric::ResolutionArea.value_text(&bsar_infos), "> 100 km²");
+    }
+}
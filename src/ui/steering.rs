@@ -0,0 +1,314 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts, EguiPrimaryContextPass};
+
+use crate::{
+    entities::{antenna_beam_footprint_state_from_state, AntennaBeamFootprintState, LineList},
+    scene::{
+        RxAntennaBeamFootprintState, RxAntennaBeamState, RxAntennaState, RxCarrierState,
+        TxAntennaBeamFootprintState, TxAntennaBeamState, TxAntennaState, TxCarrierState
+    },
+    steering::{BeamPosition, SteeringSchedule},
+    ui::{PhasedArrayWidget, UpdateStage},
+};
+
+const TEXT_COLOR: egui::Color32 = egui::Color32::from_rgb(200, 200, 200);
+const ERROR_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 120, 120);
+/// Colors of the scheduled beam footprints, the active one being brighter
+const BEAM_FOOTPRINT_COLOR: Srgba = Srgba::rgb(0.6, 0.4, 0.8);
+const ACTIVE_BEAM_FOOTPRINT_COLOR: Srgba = Srgba::rgb(1.0, 0.4, 1.0);
+
+pub struct SteeringPlugin;
+
+impl Plugin for SteeringPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<SteeringWidget>()
+            .add_systems(Startup, spawn_steering_overlays)
+            // The carriers are moved by the update systems
+            .add_systems(
+                PostUpdate,
+                update_steered_beams
+                    .in_set(UpdateStage::Geometry)
+                    .after(super::tx_panel::update_tx)
+                    .after(super::rx_panel::update_rx)
+            )
+            .add_systems(EguiPrimaryContextPass, steering_ui_system);
+    }
+}
+
+/// Optional electronic beam steering schedule of one antenna.
+pub struct CarrierSteering {
+    pub is_enabled: bool,
+    pub table_text: String,
+    pub schedule: Option<SteeringSchedule>,
+    /// Footprints of the scheduled beam positions at the current time, in order
+    pub beam_footprints: Vec<AntennaBeamFootprintState>,
+    /// Beam position active at the current time
+    pub active_index: Option<usize>,
+    /// Result of the last schedule update
+    status: Option<Result<String, String>>,
+    footprints_need_update: bool,
+    overlays_need_update: bool,
+}
+
+impl Default for CarrierSteering {
+    fn default() -> Self {
+        Self {
+            is_enabled: false,
+            table_text: "# elevation [°] azimuth [°] dwell [s]\n-2 0 1\n0 0 1\n2 0 1\n".to_string(),
+            schedule: None,
+            beam_footprints: Vec::new(),
+            active_index: None,
+            status: None,
+            footprints_need_update: false,
+            overlays_need_update: false,
+        }
+    }
+}
+
+impl CarrierSteering {
+    /// Gets the beam position active at `time_s`, when the schedule is enabled.
+    pub fn position_at(&self, time_s: f64) -> Option<&BeamPosition> {
+        let schedule = self.schedule.as_ref().filter(|_| self.is_enabled)?;
+        schedule.positions().get(schedule.index_at(time_s))
+    }
+
+    /// Gets the footprint of the beam position active at the current time.
+    pub fn active_footprint(&self) -> Option<&AntennaBeamFootprintState> {
+        self.active_index.and_then(|index| self.beam_footprints.get(index))
+    }
+}
+
+/// Electronic beam steering schedules of the transmitter and the receiver:
+/// the beam hops between pointing offsets along the timeline, the footprint of
+/// each beam position being drawn over the scene.
+#[derive(Resource, Default)]
+pub struct SteeringWidget {
+    pub tx: CarrierSteering,
+    pub rx: CarrierSteering,
+}
+
+/// Footprints of the scheduled beam positions, drawn over the footprints.
+#[derive(Component, Clone, Copy, PartialEq, Eq)]
+pub enum SteeringOverlay {
+    TxBeams,
+    TxActiveBeam,
+    RxBeams,
+    RxActiveBeam,
+}
+
+fn spawn_steering_overlays(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let overlays = [
+        (SteeringOverlay::TxBeams, BEAM_FOOTPRINT_COLOR, "Tx steered beam footprints"),
+        (SteeringOverlay::TxActiveBeam, ACTIVE_BEAM_FOOTPRINT_COLOR, "Tx active steered beam footprint"),
+        (SteeringOverlay::RxBeams, BEAM_FOOTPRINT_COLOR, "Rx steered beam footprints"),
+        (SteeringOverlay::RxActiveBeam, ACTIVE_BEAM_FOOTPRINT_COLOR, "Rx active steered beam footprint"),
+    ];
+    for (overlay, color, name) in overlays {
+        commands.spawn((
+            Mesh3d(meshes.add(LineList { lines: vec![] })),
+            MeshMaterial3d(materials.add(StandardMaterial {
+                base_color: color.into(),
+                unlit: true,
+                ..default()
+            })),
+            Transform::IDENTITY,
+            Visibility::Hidden,
+            overlay,
+            Name::new(name),
+        ));
+    }
+}
+
+/// Gets the footprint edges as lines, lifted above the footprints to stay visible over them.
+fn footprint_lines<'a>(footprints: impl Iterator<Item = &'a AntennaBeamFootprintState>) -> Vec<(Vec3, Vec3)> {
+    footprints
+        .flat_map(|footprint| {
+            let lift_m = 2.0 * footprint.lift_m as f32;
+            footprint.points.windows(2).map(move |segment| (
+                Vec3::new(segment[0].x as f32, lift_m, segment[0].z as f32),
+                Vec3::new(segment[1].x as f32, lift_m, segment[1].z as f32),
+            ))
+        })
+        .collect()
+}
+
+/// Computes the scheduled beam footprints at the current time and redraws them.
+pub(super) fn update_steered_beams(
+    mut steering_widget: ResMut<SteeringWidget>,
+    phased_array_widget: Res<PhasedArrayWidget>,
+    tx: (Res<TxCarrierState>, Res<TxAntennaState>, Res<TxAntennaBeamState>, Res<TxAntennaBeamFootprintState>),
+    rx: (Res<RxCarrierState>, Res<RxAntennaState>, Res<RxAntennaBeamState>, Res<RxAntennaBeamFootprintState>),
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut overlay_q: Query<(&SteeringOverlay, &Mesh3d, &mut Visibility)>,
+) -> Result {
+    let widget = &mut *steering_widget; // Borrows the fields separately
+    let is_array_changed = phased_array_widget.is_changed();
+    let are_tx_states_changed = is_array_changed || tx.0.is_changed() || tx.1.is_changed() || tx.2.is_changed() || tx.3.is_changed();
+    let are_rx_states_changed = is_array_changed || rx.0.is_changed() || rx.1.is_changed() || rx.2.is_changed() || rx.3.is_changed();
+    let carriers = [
+        (&mut widget.tx, phased_array_widget.tx.enabled_array(), &tx.0.inner, &tx.1.inner, &tx.2.inner, tx.3.inner.lift_m, are_tx_states_changed),
+        (&mut widget.rx, phased_array_widget.rx.enabled_array(), &rx.0.inner, &rx.1.inner, &rx.2.inner, rx.3.inner.lift_m, are_rx_states_changed),
+    ];
+    for (steering, array, carrier_state, antenna_state, antenna_beam_state, lift_m, are_states_changed) in carriers {
+        let schedule = steering.schedule.as_ref().filter(|_| steering.is_enabled);
+        let Some(schedule) = schedule else {
+            if steering.active_index.is_some() {
+                steering.beam_footprints.clear();
+                steering.active_index = None;
+                steering.footprints_need_update = true; // When enabled again
+                steering.overlays_need_update = true;
+            }
+            continue;
+        };
+        if are_states_changed || steering.footprints_need_update {
+            // The carrier does not move: the offsets steer the beam around the antenna
+            // boresight, a phased array beam degrading with them
+            steering.beam_footprints = schedule.positions().iter()
+                .map(|position| antenna_beam_footprint_state_from_state(
+                    carrier_state,
+                    &position.steer(antenna_state),
+                    &array.map_or_else(
+                        || antenna_beam_state.clone(),
+                        |array| array.beam_state(position.elevation_offset_deg, position.azimuth_offset_deg)
+                    ),
+                    lift_m
+                ))
+                .collect();
+            steering.footprints_need_update = false;
+            steering.overlays_need_update = true;
+        }
+        let active_index = Some(schedule.index_at(carrier_state.time_s));
+        if steering.active_index != active_index {
+            steering.active_index = active_index;
+            steering.overlays_need_update = true;
+        }
+    }
+
+    for (overlay, mesh, visibility) in overlay_q.iter_mut() {
+        let (steering, is_active_beam) = match overlay {
+            SteeringOverlay::TxBeams => (&widget.tx, false),
+            SteeringOverlay::TxActiveBeam => (&widget.tx, true),
+            SteeringOverlay::RxBeams => (&widget.rx, false),
+            SteeringOverlay::RxActiveBeam => (&widget.rx, true),
+        };
+        if !steering.overlays_need_update {
+            continue;
+        }
+        let lines = if is_active_beam {
+            footprint_lines(steering.active_footprint().into_iter())
+        } else {
+            footprint_lines(
+                steering.beam_footprints.iter()
+                    .enumerate()
+                    .filter(|(index, _)| Some(*index) != steering.active_index)
+                    .map(|(_, footprint)| footprint)
+            )
+        };
+        super::display::replace_line_mesh(&mut meshes, mesh, visibility, (!lines.is_empty()).then(|| LineList { lines }))?;
+    }
+    widget.tx.overlays_need_update = false;
+    widget.rx.overlays_need_update = false;
+
+    Ok(())
+}
+
+fn steering_ui_system(
+    mut contexts: EguiContexts,
+    mut steering_widget: ResMut<SteeringWidget>,
+) -> Result {
+    let ctx = contexts.ctx_mut()?;
+
+    let steering_window = egui::Window::new("Beam Steering")
+        .resizable(false)
+        .constrain(true)
+        .collapsible(true)
+        .title_bar(true)
+        .enabled(true)
+        .default_open(false)
+        .default_pos(egui::pos2(320.0, 560.0));
+    steering_window.show(ctx, |ui| {
+        let widget = &mut *steering_widget; // Borrows the fields separately
+        carrier_steering_ui(ui, "tx", "TRANSMITTER", &mut widget.tx);
+        carrier_steering_ui(ui, "rx", "RECEIVER", &mut widget.rx);
+    });
+
+    Ok(())
+}
+
+fn carrier_steering_ui(ui: &mut egui::Ui, id: &str, title: &str, steering: &mut CarrierSteering) {
+    ui.separator();
+    ui.vertical_centered(|ui| ui.label(egui::RichText::new(title).strong()));
+    ui.separator();
+
+    let hover_text = egui::RichText::new("Hops the beam between pointing offsets along the timeline,\nthe carrier and its antenna boresight staying in place")
+        .color(TEXT_COLOR)
+        .monospace();
+    ui.checkbox(&mut steering.is_enabled, "Beam steering schedule").on_hover_text(hover_text);
+    if !steering.is_enabled {
+        return;
+    }
+    ui.add(
+        egui::TextEdit::multiline(&mut steering.table_text)
+            .id_salt(format!("{id}_steering_table"))
+            .font(egui::TextStyle::Monospace)
+            .desired_rows(4)
+            .desired_width(280.0)
+    );
+    let hover_text = egui::RichText::new("Reads the schedule: one \"elevation azimuth dwell\" beam position per line,\noffsets in degrees from the antenna pointing, dwell in seconds,\nrepeated from 0 s")
+        .color(TEXT_COLOR)
+        .monospace();
+    if ui.button("Apply").on_hover_text(hover_text).clicked() {
+        steering.status = Some(SteeringSchedule::from_table(&steering.table_text)
+            .map(|schedule| {
+                let status = format!("{} beam positions, {:.3} s period", schedule.positions().len(), schedule.period_s());
+                steering.schedule = Some(schedule);
+                steering.footprints_need_update = true;
+                status
+            })
+        );
+    }
+    match &steering.status {
+        Some(Ok(status)) => { ui.label(egui::RichText::new(status).color(TEXT_COLOR)); }
+        Some(Err(error)) => { ui.label(egui::RichText::new(error).color(ERROR_COLOR)); }
+        None => {}
+    }
+
+    let Some(schedule) = &steering.schedule else {
+        return;
+    };
+    egui::Grid::new(format!("{id}_steering_grid"))
+        .num_columns(5)
+        .striped(true)
+        .spacing([10.0, 5.0])
+        .show(ui, |ui| {
+            for header in ["Beam", "Offsets", "Dwell", "Incidences", "Swath"] {
+                ui.label(egui::RichText::new(header).strong());
+            }
+            ui.end_row();
+            for (index, (position, footprint)) in schedule.positions().iter().zip(steering.beam_footprints.iter()).enumerate() {
+                let beam = if steering.active_index == Some(index) { format!("▶ {}", index + 1) } else { format!("{}", index + 1) };
+                ui.label(beam);
+                ui.label(format!("{:+.2}° / {:+.2}°", position.elevation_offset_deg, position.azimuth_offset_deg));
+                ui.label(format!("{:.3} s", position.dwell_s));
+                ui.label(format!("{:.2}° - {:.2}°", footprint.loc_incidence_min_deg, footprint.loc_incidence_max_deg));
+                ui.label(format!("{:.1} m", footprint.ground_range_swath_m));
+                ui.end_row();
+            }
+        });
+
+    // Aggregate coverage of the scheduled beams
+    let incidences_deg = steering.beam_footprints.iter()
+        .flat_map(|footprint| [footprint.loc_incidence_min_deg, footprint.loc_incidence_max_deg]);
+    let (min_deg, max_deg) = incidences_deg.fold((f64::MAX, f64::MIN), |(min, max), incidence_deg| {
+        (min.min(incidence_deg), max.max(incidence_deg))
+    });
+    if min_deg <= max_deg {
+        let text = format!("Aggregate incidences: {min_deg:.2}° - {max_deg:.2}°");
+        ui.label(egui::RichText::new(text).color(TEXT_COLOR));
+    }
+}
@@ -0,0 +1,395 @@
+use bevy::{
+    asset::RenderAssetUsages,
+    math::DVec3,
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+use bevy_egui::{egui, EguiContexts, EguiPrimaryContextPass};
+
+use crate::{
+    constants::HALF_PLANE_LENGTH,
+    dem::Dem,
+    download::{dropped_file, OpenRequest},
+    epoch::UtcTime,
+    expression,
+    scene::{RxCarrierState, TxCarrierState},
+    sun::{azimuth_elevation_deg, sun_direction_enu},
+    terrain::TerrainMasks,
+    ui::{GeoReferenceWidget, TimelineWidget, UpdateStage},
+};
+
+const TEXT_COLOR: egui::Color32 = egui::Color32::from_rgb(200, 200, 200);
+const ERROR_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 120, 120);
+/// Extensions of the DEM files: SRTM tiles and ESRI ASCII grids
+const DEM_EXTENSIONS: [&str; 2] = ["hgt", "asc"];
+/// Cells per side of the terrain masks raster
+const TERRAIN_RASTER_SIZE: usize = 256;
+/// Height of the masks layer above the ground, above the coverage heat layer (0.2 m)
+const TERRAIN_LAYER_HEIGHT_M: f32 = 0.3;
+/// Illuminance of the sun light in lux, kept low against the ambient light
+const SUN_ILLUMINANCE_LX: f32 = 3000.0;
+
+pub struct TerrainPlugin;
+
+impl Plugin for TerrainPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<TerrainWidget>()
+            .add_systems(Startup, (spawn_terrain_layer, spawn_sun_light))
+            .add_systems(PostUpdate, (update_terrain_layer, update_sun_light).in_set(UpdateStage::RenderAssets))
+            .add_systems(EguiPrimaryContextPass, terrain_ui_system);
+    }
+}
+
+/// Component marker for the terrain shadow / layover layer
+#[derive(Component)]
+pub struct TerrainLayer;
+
+/// Component marker for the directional light of the sun
+#[derive(Component)]
+pub struct SunLight;
+
+/// Terrain shadow and layover masks of the current bistatic geometry, from a
+/// loaded DEM, shown as a ground overlay.
+#[derive(Resource)]
+pub struct TerrainWidget {
+    pub dem: Option<Dem>,
+    /// Name of the loaded DEM file
+    pub dem_name: String,
+    /// Half side of the masks area centered on the scene center
+    pub half_extent_m: f64,
+    /// Shows the masks layer
+    pub is_visible: bool,
+    /// Shows the terrain lit by the sun instead of the radar masks
+    pub is_optical: bool,
+    /// Lights the scene by the sun at the timeline UTC time
+    pub is_sun_light: bool,
+    pub masks: Option<TerrainMasks>,
+    /// Result of the last load / computation
+    status: Option<Result<String, String>>,
+    /// DEM file dialog in flight
+    open_request: Option<OpenRequest>,
+    /// Masks layer texture, set at startup
+    image: Option<Handle<Image>>,
+    material: Option<Handle<StandardMaterial>>,
+    layer_needs_update: bool,
+}
+
+impl Default for TerrainWidget {
+    fn default() -> Self {
+        Self {
+            dem: None,
+            dem_name: String::new(),
+            half_extent_m: 2000.0,
+            is_visible: true,
+            is_optical: false,
+            is_sun_light: false,
+            masks: None,
+            status: None,
+            open_request: None,
+            image: None,
+            material: None,
+            layer_needs_update: true,
+        }
+    }
+}
+
+impl TerrainWidget {
+    /// Loads a DEM file, clearing the masks.
+    pub fn load_dem(&mut self, file_name: &str, bytes: &[u8]) {
+        match Dem::from_file(file_name, bytes) {
+            Ok(dem) => {
+                let (num_cols, num_rows) = dem.size();
+                self.status = Some(Ok(format!("DEM loaded: {num_cols} × {num_rows} samples")));
+                self.dem_name = file_name.rsplit(['/', '\\']).next().unwrap_or(file_name).to_string();
+                self.dem = Some(dem);
+            }
+            Err(error) => self.status = Some(Err(error)),
+        }
+        self.masks = None;
+        self.layer_needs_update = true;
+    }
+
+    /// Computes the masks of the current geometry, and the sun shadows at `utc`.
+    pub fn compute_masks(
+        &mut self,
+        geo_reference_widget: &GeoReferenceWidget,
+        tx_carrier_state: &TxCarrierState,
+        rx_carrier_state: &RxCarrierState,
+        utc: UtcTime,
+    ) {
+        let Some(dem) = &self.dem else {
+            return;
+        };
+        match TerrainMasks::new(dem, &geo_reference_widget.scene_origin, self.half_extent_m, TERRAIN_RASTER_SIZE) {
+            Ok(mut masks) => {
+                masks.update(
+                    (tx_carrier_state.inner.position_m, tx_carrier_state.inner.velocity_vector_mps),
+                    (rx_carrier_state.inner.position_m, rx_carrier_state.inner.velocity_vector_mps)
+                );
+                masks.update_sun(sun_direction_enu(&geo_reference_widget.scene_origin, &utc));
+                self.status = Some(Ok(format!(
+                    "Shadow: {:.1} %, layover: {:.1} %, sun shadow: {:.1} %",
+                    100.0 * masks.shadow_fraction(),
+                    100.0 * masks.layover_fraction(),
+                    100.0 * masks.sun_shadow_fraction()
+                )));
+                self.masks = Some(masks);
+            }
+            Err(error) => {
+                self.masks = None;
+                self.status = Some(Err(error));
+            }
+        }
+        self.layer_needs_update = true;
+    }
+}
+
+fn spawn_terrain_layer(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut images: ResMut<Assets<Image>>,
+    mut terrain_widget: ResMut<TerrainWidget>,
+) {
+    let image = images.add(Image::new_fill(
+        Extent3d {
+            width: TERRAIN_RASTER_SIZE as u32,
+            height: TERRAIN_RASTER_SIZE as u32,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &[0, 0, 0, 0], // Initial color (transparent)
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::MAIN_WORLD | RenderAssetUsages::RENDER_WORLD
+    ));
+    let material = materials.add(StandardMaterial {
+        base_color: Color::WHITE,
+        base_color_texture: Some(image.clone()),
+        alpha_mode: AlphaMode::Blend,
+        cull_mode: None,
+        unlit: true,
+        ..default()
+    });
+    // The masks raster columns go East and its rows go South: the plane is
+    // turned by -90° around Y so that its texture u goes along +Z (East, Y-up)
+    // and v along -X (South, Y-up)
+    commands.spawn((
+        Mesh3d(meshes.add(Plane3d::new(Vec3::Y, Vec2::splat(0.5)))),
+        MeshMaterial3d(material.clone()),
+        Transform::from_xyz(0.0, TERRAIN_LAYER_HEIGHT_M, 0.0)
+            .with_rotation(Quat::from_rotation_y(-std::f32::consts::FRAC_PI_2)),
+        TerrainLayer,
+        Name::new("Terrain shadow / layover layer"),
+    ));
+    terrain_widget.image = Some(image);
+    terrain_widget.material = Some(material);
+}
+
+fn spawn_sun_light(mut commands: Commands) {
+    commands.spawn((
+        DirectionalLight {
+            illuminance: SUN_ILLUMINANCE_LX,
+            shadows_enabled: false,
+            ..default()
+        },
+        Transform::IDENTITY,
+        Visibility::Hidden,
+        SunLight,
+        Name::new("Sun light"),
+    ));
+}
+
+/// Points the sun light from the sun at the timeline UTC time, hidden at night.
+fn update_sun_light(
+    terrain_widget: Res<TerrainWidget>,
+    timeline_widget: Res<TimelineWidget>,
+    geo_reference_widget: Res<GeoReferenceWidget>,
+    mut sun_light_q: Query<(&mut Transform, &mut Visibility), With<SunLight>>,
+) {
+    if !(terrain_widget.is_changed() || timeline_widget.is_changed() || geo_reference_widget.is_changed()) {
+        return;
+    }
+    let sun_direction = sun_direction_enu(&geo_reference_widget.scene_origin, &timeline_widget.utc());
+    let is_visible = terrain_widget.is_sun_light && sun_direction.z > 0.0;
+    for (mut transform, mut visibility) in sun_light_q.iter_mut() {
+        // World frame is Y-up, the light shining along its forward axis
+        let direction = Vec3::new(sun_direction.y as f32, sun_direction.z as f32, sun_direction.x as f32);
+        *transform = Transform::default().looking_to(-direction, Vec3::X);
+        visibility.set_if_neq(if is_visible { Visibility::Inherited } else { Visibility::Hidden });
+    }
+}
+
+/// Uploads the masks layer texture and applies the masks extent and visibility.
+fn update_terrain_layer(
+    mut terrain_widget: ResMut<TerrainWidget>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut images: ResMut<Assets<Image>>,
+    mut terrain_layer_q: Query<(&mut Transform, &mut Visibility), With<TerrainLayer>>,
+) {
+    for (mut transform, mut visibility) in terrain_layer_q.iter_mut() {
+        let extent = terrain_widget.masks.as_ref().map_or(1.0, |masks| 2.0 * masks.half_extent_m() as f32);
+        transform.scale = Vec3::new(extent, 1.0, extent);
+        let is_visible = terrain_widget.is_visible && terrain_widget.masks.is_some();
+        visibility.set_if_neq(if is_visible { Visibility::Inherited } else { Visibility::Hidden });
+    }
+    if !terrain_widget.layer_needs_update {
+        return;
+    }
+    if let Some(image_handle) = &terrain_widget.image
+        && let Some(mut image) = images.get_mut(image_handle)
+    {
+        image.data = Some(terrain_widget.masks.as_ref().map_or_else(
+            || vec![0; 4 * TERRAIN_RASTER_SIZE * TERRAIN_RASTER_SIZE],
+            TerrainMasks::to_rgba8
+        ));
+        // Touch the material so that it picks the new texture up
+        if let Some(material_handle) = &terrain_widget.material
+            && let Some(mut material) = materials.get_mut(material_handle)
+        {
+            material.base_color_texture = Some(image_handle.clone());
+        }
+    }
+    terrain_widget.layer_needs_update = false;
+}
+
+fn terrain_ui_system(
+    mut contexts: EguiContexts,
+    mut terrain_widget: ResMut<TerrainWidget>,
+    geo_reference_widget: Res<GeoReferenceWidget>,
+    tx_carrier_state: Res<TxCarrierState>,
+    rx_carrier_state: Res<RxCarrierState>,
+    timeline_widget: Res<TimelineWidget>,
+) -> Result {
+    let ctx = contexts.ctx_mut()?;
+
+    // DEM files dropped on the window, or picked in the dialog
+    let opened = dropped_file(ctx, &DEM_EXTENSIONS).or_else(|| {
+        let result = terrain_widget.open_request.as_mut()?.update(ctx)?;
+        terrain_widget.open_request = None;
+        Some(result)
+    });
+    match opened {
+        Some(Ok((file_name, bytes))) => terrain_widget.load_dem(&file_name, &bytes),
+        Some(Err(error)) => terrain_widget.status = Some(Err(error)),
+        None => {}
+    }
+
+    let terrain_window = egui::Window::new("Terrain")
+        .resizable(false)
+        .constrain(true)
+        .collapsible(true)
+        .title_bar(true)
+        .enabled(true)
+        .default_open(false)
+        .default_pos(egui::pos2(320.0, 240.0));
+    terrain_window.show(ctx, |ui| {
+        let utc = timeline_widget.utc();
+        let sun_direction = sun_direction_enu(&geo_reference_widget.scene_origin, &utc);
+        if terrain_ui(ui, &mut terrain_widget, &sun_direction) {
+            terrain_widget.compute_masks(&geo_reference_widget, &tx_carrier_state, &rx_carrier_state, utc);
+        }
+    });
+
+    Ok(())
+}
+
+/// Returns true when the masks must be computed.
+fn terrain_ui(ui: &mut egui::Ui, widget: &mut TerrainWidget, sun_direction: &DVec3) -> bool {
+    ui.horizontal(|ui| {
+        let hover_text = egui::RichText::new("Opens an SRTM tile (.hgt) or an ESRI ASCII grid in degrees (.asc),\nwhich can also be dropped on the window")
+            .color(TEXT_COLOR)
+            .monospace();
+        if ui.button("Load DEM…").on_hover_text(hover_text).clicked() && widget.open_request.is_none() {
+            widget.open_request = Some(OpenRequest::with_extensions("DEM", &DEM_EXTENSIONS));
+        }
+        if let Some(dem) = &widget.dem {
+            let (west, south, east, north) = dem.bounds_deg();
+            ui.label(egui::RichText::new(&widget.dem_name).monospace()).on_hover_text(
+                egui::RichText::new(format!("Longitudes {west:.4}° - {east:.4}°\nLatitudes {south:.4}° - {north:.4}°"))
+                    .color(TEXT_COLOR)
+                    .monospace()
+            );
+        } else {
+            ui.label(egui::RichText::new("No DEM loaded").color(TEXT_COLOR));
+        }
+    });
+
+    let mut compute = false;
+    egui::Grid::new("terrain_grid")
+        .num_columns(2)
+        .striped(false)
+        .spacing([20.0, 5.0])
+        .show(ui, |ui| {
+            let hover_text = egui::RichText::new(format!(
+                "Sets the half side of the masks area centered on the scene center (100 - {HALF_PLANE_LENGTH} m)"
+            ))
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Half extent: ").on_hover_text(hover_text.clone());
+            ui.add(
+                egui::DragValue::new(&mut widget.half_extent_m)
+                    .custom_parser(expression::parse)
+                    .update_while_editing(false)
+                    .speed(10.0)
+                    .range(100.0..=HALF_PLANE_LENGTH as f64)
+                    .fixed_decimals(0)
+                    .suffix(" m")
+            ).on_hover_text(hover_text);
+            ui.end_row();
+
+            let hover_text = egui::RichText::new("Computes the terrain shadow (dark, from the Tx or the Rx) and layover\n(magenta, needs moving carriers) masks of the current geometry,\nand the sun shadows at the timeline UTC time")
+                .color(TEXT_COLOR)
+                .monospace();
+            compute = ui
+                .add_enabled(widget.dem.is_some(), egui::Button::new("Compute masks"))
+                .on_hover_text(hover_text)
+                .clicked();
+            ui.checkbox(&mut widget.is_visible, "Show masks");
+            ui.end_row();
+
+            let hover_text = egui::RichText::new("Radar: shadow and layover masks of the Tx / Rx geometry\nOptical: terrain lit by the sun (hill shading and cast shadows),\nto compare with optical context images")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Layer: ").on_hover_text(hover_text.clone());
+            ui.horizontal(|ui| {
+                let is_optical = widget.is_optical;
+                ui.radio_value(&mut widget.is_optical, false, "Radar").on_hover_text(hover_text.clone());
+                ui.radio_value(&mut widget.is_optical, true, "Optical").on_hover_text(hover_text);
+                widget.layer_needs_update |= widget.is_optical != is_optical;
+            });
+            ui.end_row();
+
+            let (azimuth_deg, elevation_deg) = azimuth_elevation_deg(sun_direction);
+            let hover_text = egui::RichText::new("Sun azimuth (clockwise from the north) and elevation at the scene center\nat the timeline UTC time, optionally lighting the scene")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Sun: ").on_hover_text(hover_text.clone());
+            ui.horizontal(|ui| {
+                ui.label(format!("{azimuth_deg:.1}° / {elevation_deg:+.1}°")).on_hover_text(hover_text);
+                ui.checkbox(&mut widget.is_sun_light, "Lighting");
+                if elevation_deg <= 0.0 {
+                    ui.label(egui::RichText::new("(night)").color(TEXT_COLOR));
+                }
+            });
+            ui.end_row();
+
+            if let Some(masks) = &widget.masks {
+                let (min_m, max_m) = masks.height_range_m();
+                ui.label("Relative heights: ");
+                ui.label(format!("{min_m:.1} m - {max_m:.1} m"));
+                ui.end_row();
+
+                ui.label("Cell size: ");
+                ui.label(format!("{:.2} m", masks.cell_size_m()));
+                ui.end_row();
+            }
+        });
+
+    match &widget.status {
+        Some(Ok(status)) => { ui.label(egui::RichText::new(status).color(TEXT_COLOR)); }
+        Some(Err(error)) => { ui.label(egui::RichText::new(error).color(ERROR_COLOR)); }
+        None => {}
+    }
+    compute
+}
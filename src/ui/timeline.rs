@@ -0,0 +1,366 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts, EguiPrimaryContextPass};
+
+use crate::{
+    bsar::BsarInfos,
+    download::{SaveFileType, SaveRequest},
+    epoch::UtcTime,
+    expression,
+    scenario::ScenarioMetadata,
+    scene::{BsarInfosState, RxCarrierState, TxCarrierState},
+    ui::{menu::SAVE_ICON, ScenarioWidget, Toasts, UpdateStage},
+};
+
+const TEXT_COLOR: egui::Color32 = egui::Color32::from_rgb(200, 200, 200);
+const ERROR_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 120, 120);
+/// Suggested file name of the BSAR infos time series export
+const BSAR_INFOS_EXPORT_FILE_NAME: &str = "bsargeom_bsar_infos.csv";
+
+pub struct TimelinePlugin;
+
+impl Plugin for TimelinePlugin {
+    fn build(&self, app: &mut App) {
+        // The timeline moves the carriers through their states, picked up by
+        // the update systems; the recorder samples the BSAR infos they computed.
+        app
+            .init_resource::<TimelineWidget>()
+            .add_systems(
+                PostUpdate,
+                (
+                    advance_timeline.in_set(UpdateStage::Input),
+                    record_bsar_infos
+                        .in_set(UpdateStage::Derived)
+                        .after(super::tx_panel::update_bsar_system),
+                )
+            )
+            .add_systems(EguiPrimaryContextPass, timeline_ui_system);
+    }
+}
+
+/// Scenario timeline: both carriers move along their velocity vectors, their
+/// antenna boresights intersecting the scene center at time 0 s.
+#[derive(Resource)]
+pub struct TimelineWidget {
+    /// UTC time of the scenario time 0 s
+    pub epoch: UtcTime,
+    pub is_playing: bool,
+    /// Current time in seconds
+    pub time_s: f64,
+    /// Playback speed (simulated seconds per real second)
+    pub speed: f64,
+    /// Playback duration in seconds, the playback pauses when reached
+    pub duration_s: f64,
+    /// BSAR infos time series recording
+    pub is_recording: bool,
+    /// Minimum time step in seconds between two recorded samples
+    pub record_step_s: f64,
+    /// Recorded CSV rows (time and UTC time first, then [`BsarInfos::CSV_HEADER`] columns)
+    records: Vec<String>,
+    last_recorded_time_s: Option<f64>,
+    /// Result of the last export click
+    save_status: Option<String>,
+    /// Export in flight
+    save_request: Option<SaveRequest>,
+    /// Epoch typed by the user
+    epoch_input: String,
+    /// Error of the last typed epoch
+    epoch_error: Option<String>,
+}
+
+impl Default for TimelineWidget {
+    fn default() -> Self {
+        let epoch = UtcTime::from_unix_s(UtcTime::now().unix_s().floor()); // Whole seconds
+        Self {
+            epoch,
+            is_playing: false,
+            time_s: 0.0,
+            speed: 1.0,
+            duration_s: 60.0,
+            is_recording: false,
+            record_step_s: 0.1,
+            records: Vec::new(),
+            last_recorded_time_s: None,
+            save_status: None,
+            save_request: None,
+            epoch_input: epoch.to_string(),
+            epoch_error: None,
+        }
+    }
+}
+
+impl TimelineWidget {
+    /// Gets the UTC time of the current scenario time.
+    #[inline]
+    pub fn utc(&self) -> UtcTime {
+        self.epoch.after_s(self.time_s)
+    }
+
+    /// Sets the scenario epoch, also shown in the epoch input.
+    pub fn set_epoch(&mut self, epoch: UtcTime) {
+        self.epoch = epoch;
+        self.epoch_input = epoch.to_string();
+        self.epoch_error = None;
+    }
+
+    /// Gets the recorded time series as a CSV document.
+    pub fn records_as_csv(&self) -> String {
+        let mut csv = format!("time_s,utc,{}\n", BsarInfos::CSV_HEADER);
+        for row in self.records.iter() {
+            csv.push_str(row);
+            csv.push('\n');
+        }
+        csv
+    }
+
+    /// Clears the recorded time series.
+    pub fn clear_records(&mut self) {
+        self.records.clear();
+        self.last_recorded_time_s = None;
+    }
+}
+
+/// Advances the timeline time while playing and moves the carriers accordingly.
+pub(super) fn advance_timeline(
+    time: Res<Time>,
+    mut timeline_widget: ResMut<TimelineWidget>,
+    mut tx_carrier_state: ResMut<TxCarrierState>,
+    mut rx_carrier_state: ResMut<RxCarrierState>,
+) {
+    if timeline_widget.is_playing {
+        let time_s = timeline_widget.time_s + timeline_widget.speed * time.delta_secs_f64();
+        if time_s >= timeline_widget.duration_s {
+            timeline_widget.is_playing = false;
+        }
+        timeline_widget.time_s = time_s.min(timeline_widget.duration_s);
+    }
+    let time_s = timeline_widget.time_s;
+    // Only written when the time changes: the carriers are then moved, and
+    // their velocity vector turned, by the update systems
+    if tx_carrier_state.inner.time_s != time_s {
+        tx_carrier_state.inner.time_s = time_s;
+    }
+    if rx_carrier_state.inner.time_s != time_s {
+        rx_carrier_state.inner.time_s = time_s;
+    }
+}
+
+/// Appends the BSAR infos to the recorded time series at each recording time step.
+fn record_bsar_infos(
+    mut timeline_widget: ResMut<TimelineWidget>,
+    bsar_infos_state: Res<BsarInfosState>,
+) {
+    if !timeline_widget.is_recording {
+        return;
+    }
+    let time_s = timeline_widget.time_s;
+    let is_new_step = timeline_widget.last_recorded_time_s.is_none_or(
+        |last_time_s| (time_s - last_time_s).abs() >= timeline_widget.record_step_s.max(f64::EPSILON)
+    );
+    if is_new_step {
+        let row = format!("{},{},{}", time_s, timeline_widget.utc(), bsar_infos_state.inner.to_csv_row());
+        timeline_widget.records.push(row);
+        timeline_widget.last_recorded_time_s = Some(time_s);
+    }
+}
+
+fn timeline_ui_system(
+    mut contexts: EguiContexts,
+    mut timeline_widget: ResMut<TimelineWidget>,
+    scenario_widget: Res<ScenarioWidget>,
+    mut toasts: ResMut<Toasts>,
+) -> Result {
+    let ctx = contexts.ctx_mut()?;
+
+    if let Some(status) = SaveRequest::drive(&mut timeline_widget.save_request, ctx, &mut toasts) {
+        timeline_widget.save_status = Some(status);
+    }
+
+    let timeline_window = egui::Window::new("Timeline")
+        .resizable(false)
+        .constrain(false)
+        .collapsible(true)
+        .title_bar(true)
+        .max_width(420.0)
+        .enabled(true)
+        .default_open(false)
+        .anchor(egui::Align2::CENTER_BOTTOM, egui::Vec2::ZERO);
+    timeline_window.show(ctx, |ui| {
+        timeline_ui(ui, &mut timeline_widget, &scenario_widget.metadata);
+    });
+
+    Ok(())
+}
+
+fn timeline_ui(ui: &mut egui::Ui, widget: &mut TimelineWidget, metadata: &ScenarioMetadata) {
+    egui::Grid::new("timeline_grid")
+        .num_columns(2)
+        .striped(false)
+        .spacing([20.0, 5.0])
+        .show(ui, |ui| {
+            // ***** Epoch ***** //
+            let hover_text = egui::RichText::new("Sets the UTC time of the scenario time 0 s (YYYY-MM-DDThh:mm:ssZ),\nfrom which the orbits are propagated")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Epoch: ").on_hover_text(hover_text.clone());
+            ui.horizontal(|ui| {
+                let response = ui.add(
+                    egui::TextEdit::singleline(&mut widget.epoch_input)
+                        .font(egui::TextStyle::Monospace)
+                        .desired_width(200.0)
+                ).on_hover_text(hover_text);
+                if response.lost_focus() {
+                    match UtcTime::parse(&widget.epoch_input) {
+                        Ok(epoch) => widget.set_epoch(epoch),
+                        Err(error) => widget.epoch_error = Some(error),
+                    }
+                }
+                if ui.button("Now").on_hover_text("Sets the epoch to the current time").clicked() {
+                    widget.set_epoch(UtcTime::from_unix_s(UtcTime::now().unix_s().floor()));
+                }
+            });
+            ui.end_row();
+            if let Some(error) = &widget.epoch_error {
+                ui.label("");
+                ui.label(egui::RichText::new(error).color(ERROR_COLOR));
+                ui.end_row();
+            }
+
+            // ***** UTC ***** //
+            ui.label("UTC: ").on_hover_text(
+                egui::RichText::new("UTC time of the scenario time")
+                    .color(TEXT_COLOR)
+                    .monospace()
+            );
+            ui.label(egui::RichText::new(widget.utc().to_string()).monospace());
+            ui.end_row();
+
+            // ***** Time ***** //
+            let hover_text = egui::RichText::new("Sets the scenario time: the carriers move along their velocity vectors,\ntheir antenna boresights intersecting the scene center at 0 s")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Time: ").on_hover_text(hover_text.clone());
+            ui.horizontal(|ui| {
+                if ui.button(if widget.is_playing { "Pause" } else { "Play" }).clicked() {
+                    if !widget.is_playing && widget.time_s >= widget.duration_s {
+                        widget.time_s = 0.0; // Replays from the start
+                    }
+                    widget.is_playing = !widget.is_playing;
+                }
+                if ui.button("Reset").on_hover_text("Pauses and goes back to 0 s").clicked() {
+                    widget.is_playing = false;
+                    widget.time_s = 0.0;
+                }
+                let duration_s = widget.duration_s;
+                ui.add(
+                    egui::Slider::new(&mut widget.time_s, 0.0..=duration_s)
+                        .suffix(" s")
+                        .smart_aim(false)
+                        .step_by(0.0)
+                        .fixed_decimals(2)
+                ).on_hover_text(hover_text);
+            });
+            ui.end_row();
+
+            // ***** Speed ***** //
+            let hover_text = egui::RichText::new("Sets the playback speed (simulated seconds per second, 0.01 - 1000)")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Speed: ").on_hover_text(hover_text.clone());
+            ui.add(
+                egui::DragValue::new(&mut widget.speed)
+                    .custom_parser(expression::parse)
+                    .update_while_editing(false)
+                    .speed(0.1)
+                    .range(0.01..=1000.0)
+                    .fixed_decimals(2)
+                    .suffix(" ×")
+            ).on_hover_text(hover_text);
+            ui.end_row();
+
+            // ***** Duration ***** //
+            let hover_text = egui::RichText::new("Sets the playback duration (1 - 100000 s)")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Duration: ").on_hover_text(hover_text.clone());
+            ui.add(
+                egui::DragValue::new(&mut widget.duration_s)
+                    .custom_parser(expression::parse)
+                    .update_while_editing(false)
+                    .speed(1.0)
+                    .range(1.0..=100000.0)
+                    .fixed_decimals(1)
+                    .suffix(" s")
+            ).on_hover_text(hover_text);
+            widget.time_s = widget.time_s.min(widget.duration_s);
+            ui.end_row();
+        });
+
+    ui.separator();
+    ui.vertical_centered(|ui| ui.label(egui::RichText::new("BSAR INFOS RECORDING").strong()));
+    ui.separator();
+
+    egui::Grid::new("timeline_recording_grid")
+        .num_columns(2)
+        .striped(false)
+        .spacing([20.0, 5.0])
+        .show(ui, |ui| {
+            let hover_text = egui::RichText::new("Records the BSAR infos at the scene center while the time changes")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.checkbox(&mut widget.is_recording, "Record").on_hover_text(hover_text);
+            ui.horizontal(|ui| {
+                ui.label("Step: ");
+                ui.add(
+                    egui::DragValue::new(&mut widget.record_step_s)
+                        .custom_parser(expression::parse)
+                        .update_while_editing(false)
+                        .speed(0.01)
+                        .range(0.0..=3600.0)
+                        .fixed_decimals(2)
+                        .suffix(" s")
+                ).on_hover_text(
+                    egui::RichText::new("Sets the minimum time step between two samples (0 => every frame)")
+                        .color(TEXT_COLOR)
+                        .monospace()
+                );
+            });
+            ui.end_row();
+
+            ui.label(format!("{} samples", widget.records.len()));
+            ui.horizontal(|ui| {
+                if ui.add_enabled(!widget.records.is_empty(), egui::Button::new("Clear")).clicked() {
+                    widget.clear_records();
+                    widget.save_status = None;
+                }
+                let hover = egui::RichText::new(
+                    #[cfg(not(target_arch = "wasm32"))]
+                    {
+                        "Saves the time series as a CSV table"
+                    },
+                    #[cfg(target_arch = "wasm32")]
+                    {
+                        "Downloads the time series as a CSV table"
+                    },
+                )
+                .color(TEXT_COLOR)
+                .monospace();
+                let can_save = widget.save_request.is_none() && !widget.records.is_empty();
+                if ui
+                    .add_enabled(can_save, egui::Button::image(SAVE_ICON).frame_when_inactive(false))
+                    .on_hover_text(hover)
+                    .clicked()
+                {
+                    widget.save_status = None;
+                    widget.save_request = Some(SaveRequest::with_file_type(
+                        BSAR_INFOS_EXPORT_FILE_NAME,
+                        format!("{}{}", metadata.csv_comments(), widget.records_as_csv()).into_bytes(),
+                        SaveFileType::Csv
+                    ));
+                }
+            });
+            ui.end_row();
+        });
+    if let Some(status) = &widget.save_status {
+        ui.label(egui::RichText::new(status).color(TEXT_COLOR));
+    }
+}
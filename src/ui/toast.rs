@@ -0,0 +1,153 @@
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts, EguiPrimaryContextPass};
+
+use crate::scene::CarrierLimitsState;
+
+const TEXT_COLOR: egui::Color32 = egui::Color32::from_rgb(200, 200, 200);
+const WARNING_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 200, 90);
+const ERROR_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 120, 120);
+/// Seconds a toast stays on screen, the last `TOAST_FADE_S` fading it out
+const TOAST_DURATION_S: f64 = 4.0;
+const TOAST_FADE_S: f64 = 1.0;
+/// Toasts shown at once, the oldest being dropped first
+const MAX_TOASTS: usize = 5;
+
+pub struct ToastPlugin;
+
+impl Plugin for ToastPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<Toasts>()
+            .add_systems(Startup, toast_startup_warnings)
+            .add_systems(EguiPrimaryContextPass, toasts_ui_system);
+    }
+}
+
+/// Severity of a toast, setting its color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToastLevel {
+    Info,
+    Warning,
+    Error,
+}
+
+/// A transient message shown in the bottom-right corner.
+#[derive(Debug, Clone)]
+pub struct Toast {
+    pub level: ToastLevel,
+    pub text: String,
+    /// egui time of the first frame showing the toast
+    shown_at_s: Option<f64>,
+}
+
+/// Queue of the toasts on screen, fed by any system (file saved, export
+/// complete, warning raised).
+#[derive(Resource, Default)]
+pub struct Toasts {
+    toasts: VecDeque<Toast>,
+}
+
+impl Toasts {
+    pub fn push(&mut self, level: ToastLevel, text: impl Into<String>) {
+        if self.toasts.len() == MAX_TOASTS {
+            self.toasts.pop_front();
+        }
+        self.toasts.push_back(Toast { level, text: text.into(), shown_at_s: None });
+    }
+
+    pub fn info(&mut self, text: impl Into<String>) {
+        self.push(ToastLevel::Info, text);
+    }
+
+    pub fn warning(&mut self, text: impl Into<String>) {
+        self.push(ToastLevel::Warning, text);
+    }
+
+    pub fn error(&mut self, text: impl Into<String>) {
+        self.push(ToastLevel::Error, text);
+    }
+
+    /// Reports the outcome of a [`SaveRequest`](crate::download::SaveRequest),
+    /// a failed save as an error.
+    pub fn save_status(&mut self, status: &str) {
+        let level = if status.starts_with("Save failed") { ToastLevel::Error } else { ToastLevel::Info };
+        self.push(level, status);
+    }
+}
+
+/// Raises the warnings of the startup configuration.
+fn toast_startup_warnings(
+    mut carrier_limits_state: ResMut<CarrierLimitsState>,
+    mut toasts: ResMut<Toasts>,
+) {
+    if let Some(warning) = carrier_limits_state.warning.take() {
+        toasts.warning(warning);
+    }
+}
+
+fn toasts_ui_system(
+    mut contexts: EguiContexts,
+    mut toasts: ResMut<Toasts>,
+) -> Result {
+    if toasts.toasts.is_empty() {
+        return Ok(());
+    }
+    let ctx = contexts.ctx_mut()?;
+    let now_s = ctx.input(|input| input.time);
+    toasts.toasts.retain(|toast| toast.shown_at_s.is_none_or(|shown_at_s| now_s - shown_at_s < TOAST_DURATION_S));
+
+    let mut dismissed = None;
+    egui::Area::new(egui::Id::new("toasts"))
+        .anchor(egui::Align2::RIGHT_BOTTOM, egui::vec2(-10.0, -10.0))
+        .order(egui::Order::Foreground)
+        .show(ctx, |ui| {
+            for (index, toast) in toasts.toasts.iter_mut().enumerate() {
+                let shown_at_s = *toast.shown_at_s.get_or_insert(now_s);
+                let opacity = ((TOAST_DURATION_S - (now_s - shown_at_s)) / TOAST_FADE_S).clamp(0.0, 1.0) as f32;
+                let (icon, color) = match toast.level {
+                    ToastLevel::Info => ("ℹ", TEXT_COLOR),
+                    ToastLevel::Warning => ("⚠", WARNING_COLOR),
+                    ToastLevel::Error => ("⚠", ERROR_COLOR),
+                };
+                let response = egui::Frame::popup(ui.style())
+                    .multiply_with_opacity(opacity)
+                    .show(ui, |ui| {
+                        ui.label(egui::RichText::new(format!("{icon} {}", toast.text)).color(color.gamma_multiply(opacity)))
+                    })
+                    .response
+                    .interact(egui::Sense::click())
+                    .on_hover_text(egui::RichText::new("Click to dismiss").color(TEXT_COLOR).monospace());
+                if response.clicked() {
+                    dismissed = Some(index);
+                }
+            }
+        });
+    if let Some(index) = dismissed {
+        toasts.toasts.remove(index);
+    }
+    // Keeps fading out without any input
+    ctx.request_repaint();
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toasts_queue() {
+        let mut toasts = Toasts::default();
+        toasts.save_status("Save failed: permission denied");
+        toasts.save_status("Saved to /tmp/bsargeom.csv");
+        assert_eq!(toasts.toasts[0].level, ToastLevel::Error);
+        assert_eq!(toasts.toasts[1].level, ToastLevel::Info);
+        for index in 0..MAX_TOASTS {
+            toasts.warning(format!("Warning {index}"));
+        }
+        assert_eq!(toasts.toasts.len(), MAX_TOASTS);
+        assert_eq!(toasts.toasts[0].text, "Warning 0");
+    }
+}
@@ -0,0 +1,322 @@
+use bevy::{math::DVec2, prelude::*};
+use bevy_egui::{egui, EguiContexts, EguiPrimaryContextPass};
+
+use crate::{
+    download::SaveRequest,
+    entities::IsoContours,
+    raster::{draw_polyline_bgrx, fill_bgrx},
+    scene::{RxAntennaBeamFootprintState, RxCarrierState, TxAntennaBeamFootprintState, TxCarrierState},
+    ui::{
+        export::{contours_layer, footprint_layer},
+        gaf::{
+            draw_figure_axes_bgrx, draw_figure_legend_bgrx, figure_png_bytes, EXPORT_MARGIN_BOTTOM, EXPORT_MARGIN_LEFT,
+            EXPORT_MARGIN_RIGHT, EXPORT_MARGIN_TOP, EXPORT_PAPER_RGB, EXPORT_PATCH_PX, EXPORT_SCALE
+        },
+        menu::SAVE_ICON,
+        DisplayWidget, RoiWidget, Toasts,
+    },
+    vector_export::VectorLayer,
+};
+
+const TEXT_COLOR: egui::Color32 = egui::Color32::from_rgb(200, 200, 200);
+const ERROR_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 120, 120);
+/// Suggested file name of the top view figure
+const TOP_VIEW_EXPORT_FILE_NAME: &str = "bsargeom_top_view.png";
+/// Side of the window's square plot area, in points.
+const TOP_VIEW_PLOT_SIDE: f32 = 460.0;
+/// Margin around the drawn geometry, as a fraction of its extent
+const TOP_VIEW_MARGIN_RATIO: f64 = 0.05;
+/// Figure strokes in pixels, and dash pattern (on, off) of the negative
+/// iso-Doppler contours
+const FOOTPRINT_STROKE_PX: f32 = 2.0 * EXPORT_SCALE as f32;
+const CONTOUR_STROKE_PX: f32 = 0.8 * EXPORT_SCALE as f32;
+const DASH_PX: (f32, f32) = (8.0 * EXPORT_SCALE as f32, 6.0 * EXPORT_SCALE as f32);
+
+pub struct TopViewPlugin;
+
+impl Plugin for TopViewPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<TopViewWidget>()
+            .add_systems(EguiPrimaryContextPass, top_view_ui_system);
+    }
+}
+
+/// Printable 2D plan view of the ground geometry: orthographic, North up, on
+/// white paper with metric Easting/Northing axes.
+#[derive(Resource)]
+pub struct TopViewWidget {
+    /// Draws the iso-range/iso-Doppler contours of the ground plane
+    pub with_contours: bool,
+    /// Result of the last export click
+    status: Option<Result<String, String>>,
+    /// Export in flight
+    save_request: Option<SaveRequest>,
+}
+
+impl Default for TopViewWidget {
+    fn default() -> Self {
+        Self {
+            with_contours: true,
+            status: None,
+            save_request: None,
+        }
+    }
+}
+
+/// A drawn layer of the top view and its legend label.
+struct TopViewLayer {
+    label: &'static str,
+    layer: VectorLayer,
+    /// Contours are drawn thinner than the footprints
+    is_contour: bool,
+}
+
+/// Converts a layer color to egui.
+fn color32((r, g, b): (u8, u8, u8)) -> egui::Color32 {
+    egui::Color32::from_rgb(r, g, b)
+}
+
+/// Gets the half side in meters of the square view, centered on the scene
+/// center, framing the vertices of `layers` and the `points`.
+fn half_extent_m(layers: &[TopViewLayer], points: &[DVec2]) -> f64 {
+    let max_abs = layers.iter()
+        .flat_map(|TopViewLayer { layer, .. }| layer.paths.iter().flat_map(|path| path.points.iter()))
+        .chain(points.iter())
+        .filter(|point| point.is_finite())
+        .fold(0.0f64, |max_abs, point| max_abs.max(point.abs().max_element()));
+    (1.0 + TOP_VIEW_MARGIN_RATIO) * max_abs.max(1.0)
+}
+
+/// Renders the top view as a standalone figure (white paper, North up, metric
+/// axes and legend) at print resolution, and encodes it to PNG.
+fn top_view_png_bytes(layers: &[TopViewLayer], half_extent_m: f64) -> Option<Vec<u8>> {
+    let patch = EXPORT_PATCH_PX;
+    let width = EXPORT_MARGIN_LEFT + patch + EXPORT_MARGIN_RIGHT;
+    let height = EXPORT_MARGIN_TOP + patch + EXPORT_MARGIN_BOTTOM;
+    let mut bgrx = vec![0u8; width * height * 4];
+    fill_bgrx(&mut bgrx, EXPORT_PAPER_RGB);
+
+    // ENU meters to figure pixels, North up
+    let step = 2.0 * half_extent_m / (patch - 1) as f64;
+    let to_pixels = |point: &DVec2| (
+        ((point.x + half_extent_m) / step) as f32 + EXPORT_MARGIN_LEFT as f32,
+        ((half_extent_m - point.y) / step) as f32 + EXPORT_MARGIN_TOP as f32,
+    );
+    for TopViewLayer { layer, is_contour, .. } in layers {
+        for path in layer.paths.iter() {
+            let mut points: Vec<(f32, f32)> = path.points.iter().map(to_pixels).collect();
+            if path.closed && let Some(&first) = points.first() {
+                points.push(first);
+            }
+            draw_polyline_bgrx(
+                &mut bgrx,
+                width,
+                height,
+                &points,
+                if *is_contour { CONTOUR_STROKE_PX } else { FOOTPRINT_STROKE_PX },
+                layer.rgb,
+                path.dashed.then_some(DASH_PX),
+            );
+        }
+    }
+    draw_figure_axes_bgrx(&mut bgrx, width, height, patch, half_extent_m, "Top view");
+    let entries: Vec<(String, (u8, u8, u8))> = layers.iter()
+        .map(|TopViewLayer { label, layer, .. }| (label.to_string(), layer.rgb))
+        .collect();
+    draw_figure_legend_bgrx(&mut bgrx, width, height, patch, &entries);
+    figure_png_bytes(&bgrx, width, height)
+}
+
+fn top_view_ui_system(
+    mut contexts: EguiContexts,
+    mut top_view_widget: ResMut<TopViewWidget>,
+    tx_carrier_state: Res<TxCarrierState>,
+    rx_carrier_state: Res<RxCarrierState>,
+    tx_antenna_beam_footprint_state: Res<TxAntennaBeamFootprintState>,
+    rx_antenna_beam_footprint_state: Res<RxAntennaBeamFootprintState>,
+    iso_contours: Res<IsoContours>,
+    roi_widget: Res<RoiWidget>,
+    display_widget: Res<DisplayWidget>,
+    mut toasts: ResMut<Toasts>,
+) -> Result {
+    let ctx = contexts.ctx_mut()?;
+    // Layers colored as the 3D view
+    let colors = display_widget.palette.colors();
+
+    if let Some(status) = SaveRequest::drive(&mut top_view_widget.save_request, ctx, &mut toasts) {
+        top_view_widget.status = Some(Ok(status));
+    }
+
+    let top_view_window = egui::Window::new("Top View")
+        .resizable(false)
+        .constrain(true)
+        .collapsible(true)
+        .title_bar(true)
+        .enabled(true)
+        .default_open(false)
+        .default_pos(egui::pos2(320.0, 480.0));
+    top_view_window.show(ctx, |ui| {
+        let widget = &mut *top_view_widget; // Borrows the fields separately
+        // Drawn layers, built only while the window is expanded
+        let mut layers = Vec::new();
+        if widget.with_contours {
+            layers.push(TopViewLayer {
+                label: "Iso-range",
+                layer: contours_layer(
+                    "ISO_RANGE", colors.iso_range_rgb, iso_contours.iso_range.clone(), "m", false
+                ),
+                is_contour: true,
+            });
+            layers.push(TopViewLayer {
+                label: "Iso-Doppler",
+                layer: contours_layer(
+                    "ISO_DOPPLER", colors.iso_doppler_rgb, iso_contours.iso_doppler.clone(), "Hz", true
+                ),
+                is_contour: true,
+            });
+        }
+        layers.push(TopViewLayer {
+            label: "Tx footprint",
+            layer: footprint_layer("TX_FOOTPRINT", colors.tx_footprint_rgb, &tx_antenna_beam_footprint_state.inner),
+            is_contour: false,
+        });
+        layers.push(TopViewLayer {
+            label: "Rx footprint",
+            layer: footprint_layer("RX_FOOTPRINT", colors.rx_footprint_rgb, &rx_antenna_beam_footprint_state.inner),
+            is_contour: false,
+        });
+        if let Some(rect) = roi_widget.restriction() {
+            for top_view_layer in layers.iter_mut() {
+                top_view_layer.layer = rect.clip_layer(top_view_layer.layer.clone());
+            }
+        }
+        // Carriers nadir
+        let (txp, rxp) = (tx_carrier_state.inner.position_m, rx_carrier_state.inner.position_m);
+        let nadirs = [DVec2::new(txp.x, txp.y), DVec2::new(rxp.x, rxp.y)];
+        let half_extent_m = half_extent_m(&layers, &nadirs);
+
+        ui.horizontal(|ui| {
+            let hover_text = egui::RichText::new("Draws the iso-range and iso-Doppler contours of the ground plane")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.checkbox(&mut widget.with_contours, "Contours").on_hover_text(hover_text);
+            let hover_text = egui::RichText::new(
+                "Saves the top view as a print resolution PNG figure\n\
+                 (white background, North up, axes in meters)"
+            )
+                .color(TEXT_COLOR)
+                .monospace();
+            if ui
+                .add_enabled(
+                    widget.save_request.is_none(),
+                    egui::Button::image_and_text(SAVE_ICON, "PNG").frame_when_inactive(false)
+                )
+                .on_hover_text(hover_text)
+                .clicked()
+            {
+                match top_view_png_bytes(&layers, half_extent_m) {
+                    Some(png) => {
+                        widget.status = None;
+                        widget.save_request = Some(SaveRequest::new(TOP_VIEW_EXPORT_FILE_NAME, png));
+                    }
+                    None => widget.status = Some(Err("Save failed: could not encode the image".to_string())),
+                }
+            }
+        });
+
+        // Paper-like plot: light visuals for this plot only
+        ui.scope(|ui| {
+            *ui.visuals_mut() = egui::Visuals::light();
+            ui.visuals_mut().extreme_bg_color = egui::Color32::WHITE;
+            egui_plot::Plot::new("top_view_plot")
+                .width(TOP_VIEW_PLOT_SIDE)
+                .height(TOP_VIEW_PLOT_SIDE)
+                .data_aspect(1.0) // Orthographic: equal Easting/Northing scales
+                .include_x(-half_extent_m)
+                .include_x(half_extent_m)
+                .include_y(-half_extent_m)
+                .include_y(half_extent_m)
+                .x_axis_label("Easting [m]")
+                .y_axis_label("Northing [m]")
+                .legend(egui_plot::Legend::default().follow_insertion_order(true))
+                .show(ui, |plot_ui| {
+                    for TopViewLayer { label, layer, is_contour } in layers.iter() {
+                        for path in layer.paths.iter() {
+                            let mut points: Vec<[f64; 2]> = path.points.iter().map(|p| [p.x, p.y]).collect();
+                            if path.closed && let Some(&first) = points.first() {
+                                points.push(first);
+                            }
+                            let style = if path.dashed { egui_plot::LineStyle::dashed_loose() } else { egui_plot::LineStyle::Solid };
+                            plot_ui.line(
+                                egui_plot::Line::new(*label, points)
+                                    .color(color32(layer.rgb))
+                                    .style(style)
+                                    .width(if *is_contour { 1.0 } else { 2.0 })
+                            );
+                        }
+                    }
+                    for (label, nadir, rgb) in [("Tx nadir", nadirs[0], colors.tx_footprint_rgb), ("Rx nadir", nadirs[1], colors.rx_footprint_rgb)] {
+                        plot_ui.points(
+                            egui_plot::Points::new(label, vec![[nadir.x, nadir.y]])
+                                .shape(egui_plot::MarkerShape::Diamond)
+                                .radius(5.0)
+                                .color(color32(rgb))
+                        );
+                    }
+                    plot_ui.points(
+                        egui_plot::Points::new("Scene center", vec![[0.0, 0.0]])
+                            .shape(egui_plot::MarkerShape::Cross)
+                            .radius(5.0)
+                            .color(egui::Color32::BLACK)
+                    );
+                });
+        });
+
+        match &widget.status {
+            Some(Ok(status)) => { ui.label(egui::RichText::new(status).color(TEXT_COLOR)); }
+            Some(Err(error)) => { ui.label(egui::RichText::new(error).color(ERROR_COLOR)); }
+            None => {}
+        }
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{palette::ColorPalette, vector_export::VectorPath};
+
+    /// The figure is North up: a footprint south-east of the scene center is
+    /// drawn in the lower right quadrant of the plot area (the legend sits in
+    /// the upper right one), on white paper.
+    #[test]
+    fn top_view_png_is_north_up_on_white_paper() {
+        let square = |center: DVec2| VectorPath {
+            points: vec![center - 10.0, center + DVec2::new(10.0, -10.0), center + 10.0, center + DVec2::new(-10.0, 10.0)],
+            closed: true,
+            ..Default::default()
+        };
+        let layers = [TopViewLayer {
+            label: "Tx footprint",
+            layer: VectorLayer { name: "TX_FOOTPRINT".to_string(), rgb: ColorPalette::default().colors().tx_footprint_rgb, paths: vec![square(DVec2::new(50.0, -50.0))] },
+            is_contour: false,
+        }];
+        let half_extent_m = half_extent_m(&layers, &[]);
+        assert!((half_extent_m - 1.05 * 60.0).abs() < 1e-9);
+        let png = top_view_png_bytes(&layers, half_extent_m).expect("the top view must encode to PNG");
+        let decoded = image::load_from_memory(&png).expect("the exported PNG must decode").to_rgb8();
+        // Pixel of an ENU point
+        let step = 2.0 * half_extent_m / (EXPORT_PATCH_PX - 1) as f64;
+        let pixel = |x: f64, y: f64| decoded.get_pixel(
+            (EXPORT_MARGIN_LEFT as f64 + (x + half_extent_m) / step).round() as u32,
+            (EXPORT_MARGIN_TOP as f64 + (half_extent_m - y) / step).round() as u32,
+        ).0;
+        let (r, g, b) = ColorPalette::default().colors().tx_footprint_rgb;
+        assert_eq!(pixel(40.0, -50.0), [r, g, b], "the footprint West edge must be inked");
+        assert_eq!(pixel(-60.0, -50.0), [255, 255, 255], "the mirrored (West) edge must stay blank");
+        assert_eq!(pixel(-60.0, 50.0), [255, 255, 255], "the North-West quadrant must stay blank");
+        assert_eq!(decoded.get_pixel(4, 4).0, [255, 255, 255]);
+    }
+}
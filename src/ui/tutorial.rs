@@ -0,0 +1,291 @@
+use bevy::{math::DVec3, prelude::*};
+use bevy_egui::{egui, EguiContexts, EguiPrimaryContextPass};
+use bevy_panorbit_camera::PanOrbitCamera;
+
+use crate::{
+    bsar::BsarInfos,
+    entities::{enu_to_world, AntennaBeamFootprintState},
+    scene::{BsarInfosState, RxAntennaBeamFootprintState, RxCarrierState, TxAntennaBeamFootprintState, TxCarrierState},
+    ui::{DisplayWidget, SidePanelRects},
+};
+
+const TEXT_COLOR: egui::Color32 = egui::Color32::from_rgb(200, 200, 200);
+/// Color of the highlighted elements and of the callouts frame
+const HIGHLIGHT_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 220, 0);
+const CALLOUT_FILL_COLOR: egui::Color32 = egui::Color32::from_rgba_premultiplied(20, 20, 20, 220);
+/// Radius of the highlighted point markers in points
+const MARKER_RADIUS: f32 = 5.0;
+/// Offset of the callouts from their point, in points
+const CALLOUT_OFFSET: egui::Vec2 = egui::vec2(40.0, -40.0);
+
+pub struct TutorialPlugin;
+
+impl Plugin for TutorialPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<TutorialWidget>()
+            .add_systems(EguiPrimaryContextPass, tutorial_ui_system);
+    }
+}
+
+/// A step of the guided tour of the geometry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TutorialStep {
+    Carriers,
+    Footprints,
+    IsoRangeEllipsoid,
+    Bisector,
+    IsoRangeDopplerPlane,
+    ResolutionCell,
+}
+
+impl TutorialStep {
+    pub const ALL: [Self; 6] = [
+        Self::Carriers,
+        Self::Footprints,
+        Self::IsoRangeEllipsoid,
+        Self::Bisector,
+        Self::IsoRangeDopplerPlane,
+        Self::ResolutionCell,
+    ];
+
+    pub fn title(self) -> &'static str {
+        match self {
+            Self::Carriers => "The transmitter and the receiver",
+            Self::Footprints => "The antenna footprints",
+            Self::IsoRangeEllipsoid => "The iso-range ellipsoid",
+            Self::Bisector => "The bistatic bisector",
+            Self::IsoRangeDopplerPlane => "Iso-range and iso-Doppler lines",
+            Self::ResolutionCell => "The resolution cell",
+        }
+    }
+
+    /// Gets the explanation of the step, with the values of the current
+    /// geometry.
+    pub fn explanation(self, bsar_infos: &BsarInfos) -> String {
+        match self {
+            Self::Carriers => format!(
+                "A bistatic SAR separates the transmitter (Tx) from the receiver (Rx).\n\
+                 Each carrier flies along its own trajectory, its antenna pointing\n\
+                 at the scene center. They are {:.3} km apart.",
+                bsar_infos.direct_range_m * 1e-3
+            ),
+            Self::Footprints => "The half-power beam of each antenna illuminates a footprint on the\n\
+                 ground. Only the common area, seen by both antennas, is imaged:\n\
+                 the Tx illuminates it while the Rx listens to it.".to_string(),
+            Self::IsoRangeEllipsoid => format!(
+                "The echo delay measures the bistatic range: Tx to ground point to Rx.\n\
+                 Points of equal bistatic range lie on an ellipsoid whose foci are the\n\
+                 carriers, here through the scene center at {:.3} km.",
+                bsar_infos.range_center_m * 1e-3
+            ),
+            Self::Bisector => format!(
+                "The bisector of the Tx and Rx lines of sight plays the role of the\n\
+                 monostatic line of sight. The wider the bistatic angle ({:.2}°), the\n\
+                 shorter the bisector and the coarser the resolutions.",
+                bsar_infos.bistatic_angle_deg
+            ),
+            Self::IsoRangeDopplerPlane => format!(
+                "On the ground, the ellipsoids trace iso-range lines (red) and the\n\
+                 Doppler frequency iso-Doppler lines (blue). The scene center echoes\n\
+                 at {:.3} Hz: a pixel is located where both lines cross.",
+                bsar_infos.doppler_frequency_hz
+            ),
+            Self::ResolutionCell => format!(
+                "Range and lateral resolutions span the resolution cell: {:.3} m across\n\
+                 the iso-range lines and {:.3} m across the iso-Doppler lines, for an\n\
+                 area of {:.3} m². Their directions are drawn at the scene center.",
+                bsar_infos.ground_range_resolution_m,
+                bsar_infos.ground_lateral_resolution_m,
+                bsar_infos.resolution_area_m2
+            ),
+        }
+    }
+}
+
+/// Optional guided tour of the geometry for classroom use: each step highlights
+/// an element of the 3D view with an explanatory callout.
+#[derive(Resource, Default)]
+pub struct TutorialWidget {
+    /// Index of the current step, `None` when the tour is not running
+    pub step_index: Option<usize>,
+    /// Setting of the resolution directions before the tour showed them
+    shown_resolution_directions: Option<bool>,
+}
+
+impl TutorialWidget {
+    pub fn step(&self) -> Option<TutorialStep> {
+        self.step_index.and_then(|index| TutorialStep::ALL.get(index).copied())
+    }
+}
+
+/// Element of the 3D view highlighted by a step, in World frame (Y-up).
+enum Highlight {
+    /// A point and its callout label
+    Point(Vec3, &'static str),
+    Segment(Vec3, Vec3),
+}
+
+/// Gets the World frame (Y-up) point of an ENU position.
+fn world_point(position_m: &DVec3) -> Vec3 {
+    enu_to_world(position_m.truncate(), position_m.z)
+}
+
+/// Gets the mean point of a footprint.
+fn footprint_center(footprint: &AntennaBeamFootprintState) -> Option<Vec3> {
+    let points: Vec<&DVec3> = footprint.points.iter().filter(|point| point.is_finite()).collect();
+    if points.is_empty() {
+        return None;
+    }
+    let center = points.iter().copied().sum::<DVec3>() / points.len() as f64;
+    Some(center.as_vec3())
+}
+
+fn highlights(
+    step: TutorialStep,
+    tx: (&TxCarrierState, &TxAntennaBeamFootprintState),
+    rx: (&RxCarrierState, &RxAntennaBeamFootprintState),
+) -> Vec<Highlight> {
+    let (tx_position_m, rx_position_m) = (&tx.0.inner.position_m, &rx.0.inner.position_m);
+    let scene_center = Vec3::ZERO;
+    match step {
+        TutorialStep::Carriers => vec![
+            Highlight::Point(world_point(tx_position_m), "Tx"),
+            Highlight::Point(world_point(rx_position_m), "Rx"),
+        ],
+        TutorialStep::Footprints => [(footprint_center(&tx.1.inner), "Tx footprint"), (footprint_center(&rx.1.inner), "Rx footprint")]
+            .into_iter()
+            .filter_map(|(center, label)| center.map(|center| Highlight::Point(center, label)))
+            .collect(),
+        TutorialStep::IsoRangeEllipsoid => vec![
+            Highlight::Segment(world_point(tx_position_m), scene_center),
+            Highlight::Segment(scene_center, world_point(rx_position_m)),
+            Highlight::Point(scene_center, "Tx-to-center + center-to-Rx"),
+        ],
+        TutorialStep::Bisector => {
+            let (utx, urx) = (tx_position_m.normalize_or_zero(), rx_position_m.normalize_or_zero());
+            let length_m = 0.5 * tx_position_m.length().min(rx_position_m.length());
+            let bisector = world_point(&((utx + urx).normalize_or_zero() * length_m));
+            vec![
+                Highlight::Segment(scene_center, world_point(&(utx * length_m))),
+                Highlight::Segment(scene_center, world_point(&(urx * length_m))),
+                Highlight::Segment(scene_center, bisector),
+                Highlight::Point(bisector, "Bisector"),
+            ]
+        }
+        TutorialStep::IsoRangeDopplerPlane | TutorialStep::ResolutionCell => vec![
+            Highlight::Point(scene_center, "Scene center"),
+        ],
+    }
+}
+
+/// Shows the resolution directions during the resolution cell step, restoring
+/// the user setting once left. Returns whether the setting changed.
+fn show_resolution_directions(widget: &mut TutorialWidget, display_widget: &mut DisplayWidget) -> bool {
+    let is_resolution_step = widget.step() == Some(TutorialStep::ResolutionCell);
+    match widget.shown_resolution_directions {
+        None if is_resolution_step => {
+            widget.shown_resolution_directions = Some(display_widget.show_resolution_directions);
+            display_widget.show_resolution_directions = true;
+            true
+        }
+        Some(was_shown) if !is_resolution_step => {
+            widget.shown_resolution_directions = None;
+            display_widget.show_resolution_directions = was_shown;
+            true
+        }
+        _ => false,
+    }
+}
+
+fn tutorial_ui_system(
+    mut contexts: EguiContexts,
+    mut tutorial_widget: ResMut<TutorialWidget>,
+    mut display_widget: ResMut<DisplayWidget>,
+    bsar_infos_state: Res<BsarInfosState>,
+    tx: (Res<TxCarrierState>, Res<TxAntennaBeamFootprintState>),
+    rx: (Res<RxCarrierState>, Res<RxAntennaBeamFootprintState>),
+    side_panel_rects: Res<SidePanelRects>,
+    camera_q: Single<(&Camera, &GlobalTransform), With<PanOrbitCamera>>,
+) -> Result {
+    let ctx = contexts.ctx_mut()?;
+    let (camera, camera_transform) = *camera_q;
+
+    let tutorial_window = egui::Window::new("Tutorial")
+        .resizable(false)
+        .constrain(true)
+        .collapsible(true)
+        .title_bar(true)
+        .enabled(true)
+        .default_open(false)
+        .default_pos(egui::pos2(320.0, 1240.0));
+    tutorial_window.show(ctx, |ui| {
+        tutorial_ui(ui, &mut tutorial_widget, &bsar_infos_state.inner);
+    });
+    if show_resolution_directions(&mut tutorial_widget, display_widget.bypass_change_detection()) {
+        display_widget.set_changed();
+    }
+
+    let Some(step) = tutorial_widget.step() else {
+        return Ok(());
+    };
+    let view_rect = side_panel_rects.view_rect(ctx);
+    let painter = ctx
+        .layer_painter(egui::LayerId::new(egui::Order::Background, egui::Id::new("tutorial")))
+        .with_clip_rect(view_rect);
+    // A point behind the camera has no viewport position, converted from
+    // logical pixels to egui points
+    let points_per_pixel = ctx.zoom_factor().recip();
+    let to_screen = |point: Vec3| camera.world_to_viewport(camera_transform, point)
+        .ok()
+        .map(|position| egui::pos2(points_per_pixel * position.x, points_per_pixel * position.y));
+    let stroke = egui::Stroke::new(2.0, HIGHLIGHT_COLOR);
+    for highlight in highlights(step, (&tx.0, &tx.1), (&rx.0, &rx.1)) {
+        match highlight {
+            Highlight::Segment(start, end) => {
+                if let (Some(start), Some(end)) = (to_screen(start), to_screen(end)) {
+                    painter.line_segment([start, end], stroke);
+                }
+            }
+            Highlight::Point(point, label) => {
+                let Some(position) = to_screen(point) else {
+                    continue;
+                };
+                painter.circle_stroke(position, MARKER_RADIUS, stroke);
+                let galley = painter.layout_no_wrap(label.to_string(), egui::FontId::proportional(14.0), HIGHLIGHT_COLOR);
+                let callout_rect = egui::Rect::from_min_size(position + CALLOUT_OFFSET, galley.size()).expand(4.0);
+                painter.line_segment([position, callout_rect.left_bottom()], stroke);
+                painter.rect_filled(callout_rect, 4.0, CALLOUT_FILL_COLOR);
+                painter.galley(callout_rect.min + egui::vec2(4.0, 4.0), galley, HIGHLIGHT_COLOR);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn tutorial_ui(ui: &mut egui::Ui, widget: &mut TutorialWidget, bsar_infos: &BsarInfos) {
+    let Some(step) = widget.step() else {
+        let hover_text = egui::RichText::new("Walks through the bistatic geometry step by step, highlighting its elements\nin the 3D view with explanatory callouts")
+            .color(TEXT_COLOR)
+            .monospace();
+        if ui.button("Start the tour").on_hover_text(hover_text).clicked() {
+            widget.step_index = Some(0);
+        }
+        return;
+    };
+    let index = widget.step_index.unwrap_or(0);
+    ui.label(egui::RichText::new(format!("{}/{} - {}", index + 1, TutorialStep::ALL.len(), step.title())).strong());
+    ui.label(egui::RichText::new(step.explanation(bsar_infos)).color(TEXT_COLOR));
+    ui.horizontal(|ui| {
+        if ui.add_enabled(index > 0, egui::Button::new("◀ Previous")).clicked() {
+            widget.step_index = Some(index - 1);
+        }
+        if ui.add_enabled(index + 1 < TutorialStep::ALL.len(), egui::Button::new("Next ▶")).clicked() {
+            widget.step_index = Some(index + 1);
+        }
+        if ui.button("End").clicked() {
+            widget.step_index = None;
+        }
+    });
+}
@@ -2,6 +2,8 @@ use bevy::prelude::*;
 use bevy_egui::egui;
 
 use crate::{
+    chirp::MIN_TIME_BANDWIDTH_PRODUCT,
+    constants::MAX_PRF_HZ,
     entities::{
         antenna_beam_transform_from_state, antenna_transform_from_state,
         carrier_transform_from_state,
@@ -12,56 +14,60 @@ use crate::{
         update_antenna_beam_footprint_mesh_from_state,
         update_ground_angular_velocity,
         update_illumination_time,
+        update_iso_contours,
         update_velocity_vector,
         velocity_indicator_transform_from_state,
         Antenna, AntennaBeam, AntennaBeamAzimuthLine, AntennaBeamElevationLine, AntennaBeamFootprint,
         Carrier, IsoRangeDopplerPlaneState, VelocityVector
     },
+    expression,
     scene::{
-        BsarInfosState, IsoRangeDopplerPlane, IsoRangeEllipsoid, RxAntennaBeamFootprintState, RxAntennaBeamState, RxAntennaState, RxCarrierState, Tx, TxAntennaBeamFootprintState, TxAntennaBeamState, TxAntennaState, TxCarrierState
+        BsarInfosState, CarrierLimitsState, IsoRangeDopplerPlane, IsoRangeEllipsoid, RxAntennaBeamFootprintState, RxAntennaBeamState, RxCarrierState, Tx, TxAntennaBeamFootprintState, TxAntennaBeamState, TxAntennaState, TxCarrierState
     },
-    ui::{carrier_ui, heading_with_reset, MenuWidget, RxPanelWidget},
+    ui::{carrier_ui, gain_menu, heading_with_reset, log_slider, reset_menu, UpdateStage},
 };
 
 pub struct TxPanelPlugin;
 
 impl Plugin for TxPanelPlugin {
     fn build(&self, app: &mut App) {
-        // update_tx must run after update_rx: in monostatic mode the mirrored
-        // Rx state only recomputes its derived fields (position, velocity
-        // vector, footprint) inside update_rx, and update_tx reads them when
-        // refreshing the BSAR infos and the iso-range/Doppler plane.
+        // The BSAR system is derived from both carriers: update_bsar_system
+        // runs after update_rx and update_tx, which recompute the derived
+        // fields of their states (position, velocity vector, footprint), and
+        // refreshes the BSAR infos and the iso-range/Doppler plane, whose
+        // contours are then refreshed.
         app
             .init_resource::<TxPanelWidget>()
-            .add_systems(Update, update_tx.after(super::rx_panel::update_rx));
+            .add_systems(
+                PostUpdate,
+                (
+                    update_tx.in_set(UpdateStage::Geometry).after(super::rx_panel::update_rx),
+                    (update_bsar_system, update_iso_contours).chain().in_set(UpdateStage::Derived),
+                )
+            );
     }
 }
 
+/// Transmitter panel. The settings it edits are picked up by [`update_tx`]
+/// through the change detection of the Tx state resources.
 #[derive(Resource)]
 #[derive(Default)]
-pub struct TxPanelWidget {
-    pub transform_needs_update: bool,
-    pub velocity_vector_needs_update: bool,
-    pub system_needs_update: bool,
-}
+pub struct TxPanelWidget;
 
 
 impl TxPanelWidget {
+    /// Shows the panel, returning whether the Tx settings changed.
     pub fn ui(
         &mut self,
         ui: &mut egui::Ui,
-        menu_widget: &mut MenuWidget,
-        rx_panel_widget: &mut RxPanelWidget,
         tx_carrier_state: &mut TxCarrierState,
         tx_antenna_state: &mut TxAntennaState,
         tx_antenna_beam_state: &mut TxAntennaBeamState,
-        rx_carrier_state: &mut RxCarrierState,
-        rx_antenna_state: &mut RxAntennaState,
-        rx_antenna_beam_state: &mut RxAntennaBeamState,
-    ) {
-        self.transform_needs_update = false;
-        self.velocity_vector_needs_update = false;
-        self.system_needs_update = false;
+        carrier_limits_state: &CarrierLimitsState,
+    ) -> bool {
+        let mut transform_changed = false;
+        let mut velocity_vector_changed = false;
+        let mut system_changed = false;
 
         // Tx Carrier UI
         let reset_all = carrier_ui(
@@ -74,8 +80,10 @@ impl TxPanelWidget {
             &TxCarrierState::default().inner,
             &TxAntennaState::default().inner,
             &TxAntennaBeamState::default().inner,
-            &mut self.transform_needs_update,
-            &mut self.velocity_vector_needs_update
+            &carrier_limits_state.inner,
+            &mut transform_changed,
+            &mut velocity_vector_changed,
+            None
         );
 
         // Tx System UI ("reset all" from the title row also resets it)
@@ -84,203 +92,183 @@ impl TxPanelWidget {
             tx_carrier_state,
             tx_antenna_beam_state,
             reset_all,
-            &mut self.system_needs_update
+            &mut system_changed
         );
-
-        // Monostatic case
-        if menu_widget.is_monostatic {
-            rx_carrier_state.inner = tx_carrier_state.inner.clone();
-            rx_antenna_state.inner = tx_antenna_state.inner.clone();
-            rx_antenna_beam_state.inner = tx_antenna_beam_state.inner.clone();
-            if menu_widget.was_monostatic {
-                rx_panel_widget.transform_needs_update = self.transform_needs_update;
-                rx_panel_widget.velocity_vector_needs_update = self.velocity_vector_needs_update;
-            } else {
-                rx_panel_widget.transform_needs_update = true;
-                rx_panel_widget.velocity_vector_needs_update = true;
-                // Make update_rx refresh the BSAR infos for the new (mirrored)
-                // geometry: no Tx flag is set by the toggle itself, so
-                // update_tx alone would leave them stale.
-                rx_panel_widget.system_needs_update = true;
-                menu_widget.force_rx_system_update = true;
-                menu_widget.was_monostatic = true;
-            }
-        } else {
-            menu_widget.was_monostatic = false;
-        }
+        transform_changed || velocity_vector_changed || system_changed
     }
 }
 
 // see: https://github.com/bevyengine/bevy/issues/4864
-fn update_tx(
+pub(super) fn update_tx(
     res: ( // Resources
         Res<TxAntennaState>,              // tx_antenna_state
         Res<TxAntennaBeamState>,          // tx_antenna_beam_state
         Res<RxCarrierState>,              // rx_carrier_state
-        Res<RxAntennaBeamState>,          // rx_antenna_beam_state
-        Res<RxAntennaBeamFootprintState>, // rx_antenna_beam_footprint_state
     ),
     resmut: ( // Mutable resources
-        ResMut<TxPanelWidget>,               // tx_panel_widget
-        ResMut<Assets<StandardMaterial>>,    // materials
         ResMut<Assets<Mesh>>,                // meshes
-        ResMut<Assets<Image>>,               // images
         ResMut<TxCarrierState>,              // tx_carrier_state
         ResMut<TxAntennaBeamFootprintState>, // tx_antenna_beam_footprint_state
-        ResMut<BsarInfosState>,              // bsar_infos_state
-        ResMut<IsoRangeDopplerPlaneState>,   // iso_range_doppler_plane_state
     ),
     // Queries,
     tx_antenna_beam_footprint_q: Query<&Mesh3d, (With<Tx>, With<AntennaBeamFootprint>)>,
     tx_antenna_beam_elevation_line_q: Query<&Mesh3d, (With<Tx>, With<AntennaBeamElevationLine>)>,
     tx_antenna_beam_azimuth_line_q: Query<&Mesh3d, (With<Tx>, With<AntennaBeamAzimuthLine>)>,
-    iso_range_doppler_material_q: Query<&MeshMaterial3d<StandardMaterial>, With<IsoRangeDopplerPlane>>,
     // Mutable queries
     mut tx_carrier_q: Query<(&mut Transform, &Children), (With<Tx>, With<Carrier>)>,
     mut tx_antenna_q: Query<(&mut Transform, &Children), (Without<Tx>, With<Antenna>)>,
     mut tx_antenna_beam_q: Query<&mut Transform, (Without<Tx>, Without<Antenna>, With<AntennaBeam>)>,
     mut tx_velocity_indicator_q: Query<&mut Transform, (Without<Tx>, Without<Antenna>, Without<AntennaBeam>, With<VelocityVector>)>,
     mut iso_range_ellipsoid_q: Query<&mut Transform, (Without<Tx>, Without<Antenna>, Without<AntennaBeam>, Without<VelocityVector>, With<IsoRangeEllipsoid>)>,
-    mut iso_range_doppler_q: Query<&mut Transform, (Without<Tx>, Without<Antenna>, Without<AntennaBeam>, Without<VelocityVector>, Without<IsoRangeEllipsoid>, With<IsoRangeDopplerPlane>)>,
 ) {
     // Extracts resources
     let (
         tx_antenna_state,
         tx_antenna_beam_state,
         rx_carrier_state,
-        rx_antenna_beam_state,
-        rx_antenna_beam_footprint_state
     ) = res;
     // Extracts mutable resources
     let (
-        mut tx_panel_widget,
-        mut materials,
         mut meshes,
-        mut images,
         mut tx_carrier_state,
         mut tx_antenna_beam_footprint_state,
-        mut bsar_infos_state,
-        mut iso_range_doppler_plane_state,
     ) = resmut;
-    // Checks if nothing needs to be done
-    if !(tx_panel_widget.transform_needs_update  ||
-         tx_panel_widget.velocity_vector_needs_update ||
-         tx_panel_widget.system_needs_update) {
+    // What changed since the last run, by the panels or any other system: the
+    // Tx geometry is derived from the Tx states
+    let is_tx_changed = tx_carrier_state.is_changed() ||
+        tx_antenna_state.is_changed() ||
+        tx_antenna_beam_state.is_changed() ||
+        tx_antenna_beam_footprint_state.is_changed();
+    if !is_tx_changed {
         return; // No need to update transforms if no changes were made
     }
     for (mut carrier_transform, carrier_children) in tx_carrier_q.iter_mut() {
         for carrier_child in carrier_children.iter() {
-            if tx_panel_widget.transform_needs_update
-                && let Ok((mut antenna_transform, antenna_children)) = tx_antenna_q.get_mut(carrier_child) {
-                    // Update antenna beam width
-                    for antenna_beam in antenna_children.iter() {
-                        if let Ok(mut antenna_beam_transform) = tx_antenna_beam_q.get_mut(antenna_beam) {
-                            // Update antenna beam width
-                            *antenna_beam_transform = antenna_beam_transform_from_state(
-                                &tx_antenna_beam_state.inner
-                            );
-                        }
-                    }
-                    // Update antenna transform
-                    *antenna_transform = antenna_transform_from_state(
-                        &tx_antenna_state.inner
-                    );
-                    // Update carrier transform                
-                    *carrier_transform = carrier_transform_from_state(
-                        &mut tx_carrier_state.inner,
-                        &tx_antenna_state.inner
-                    );
-                    // Update antenna beam footprint mesh in the same time
-                    for mesh_handle in tx_antenna_beam_footprint_q.iter() {
-                        if let Some(mut mesh) = meshes.get_mut(mesh_handle) {
-                            update_antenna_beam_footprint_mesh_from_state(
-                                &tx_carrier_state.inner,
-                                &tx_antenna_state.inner,
-                                &tx_antenna_beam_state.inner,
-                                &mut tx_antenna_beam_footprint_state.inner,
-                                &mut mesh
-                            );
-                        }
+            if let Ok((mut antenna_transform, antenna_children)) = tx_antenna_q.get_mut(carrier_child) {
+                // Update antenna transform
+                *antenna_transform = antenna_transform_from_state(
+                    &tx_antenna_state.inner
+                );
+                // Update carrier transform                
+                *carrier_transform = carrier_transform_from_state(
+                    &mut tx_carrier_state.inner,
+                    &tx_antenna_state.inner
+                );
+                // Update antenna beam footprint mesh in the same time
+                for mesh_handle in tx_antenna_beam_footprint_q.iter() {
+                    if let Some(mut mesh) = meshes.get_mut(mesh_handle) {
+                        update_antenna_beam_footprint_mesh_from_state(
+                            &tx_carrier_state.inner,
+                            &tx_antenna_state.inner,
+                            &tx_antenna_beam_state.inner,
+                            &mut tx_antenna_beam_footprint_state.inner,
+                            &mut mesh
+                        );
                     }
-                    // Update antenna beam elevation line mesh in the same time
-                    for mesh_handle in tx_antenna_beam_elevation_line_q.iter() {
-                        if let Some(mut mesh) = meshes.get_mut(mesh_handle) {
-                            update_antenna_beam_footprint_elevation_line_mesh_from_state(
-                                &tx_antenna_beam_footprint_state.inner,
-                                &mut mesh
-                            );
-                        }
+                }
+                // Update antenna beam width and length (after the footprint, which sets the length)
+                for antenna_beam in antenna_children.iter() {
+                    if let Ok(mut antenna_beam_transform) = tx_antenna_beam_q.get_mut(antenna_beam) {
+                        *antenna_beam_transform = antenna_beam_transform_from_state(
+                            &tx_antenna_beam_state.inner,
+                            &tx_antenna_beam_footprint_state.inner
+                        );
                     }
-                    // Update antenna beam azimuth line mesh in the same time
-                    for mesh_handle in tx_antenna_beam_azimuth_line_q.iter() {
-                        if let Some(mut mesh) = meshes.get_mut(mesh_handle) {
-                            update_antenna_beam_footprint_azimuth_line_mesh_from_state(
-                                &tx_antenna_beam_footprint_state.inner,
-                                &mut mesh
-                            );
-                        }
+                }
+                // Update antenna beam elevation line mesh in the same time
+                for mesh_handle in tx_antenna_beam_elevation_line_q.iter() {
+                    if let Some(mut mesh) = meshes.get_mut(mesh_handle) {
+                        update_antenna_beam_footprint_elevation_line_mesh_from_state(
+                            &tx_antenna_beam_footprint_state.inner,
+                            &mut mesh
+                        );
                     }
-                    //Update iso-range ellipsoid transform
-                    for mut iso_range_ellipsoid_transform in iso_range_ellipsoid_q.iter_mut() {
-                        *iso_range_ellipsoid_transform = iso_range_ellipsoid_transform_from_state(
-                            &tx_carrier_state.inner.position_m, // OT in world frame
-                            &rx_carrier_state.inner.position_m  // OR in world frame
+                }
+                // Update antenna beam azimuth line mesh in the same time
+                for mesh_handle in tx_antenna_beam_azimuth_line_q.iter() {
+                    if let Some(mut mesh) = meshes.get_mut(mesh_handle) {
+                        update_antenna_beam_footprint_azimuth_line_mesh_from_state(
+                            &tx_antenna_beam_footprint_state.inner,
+                            &mut mesh
                         );
                     }
                 }
-            if tx_panel_widget.velocity_vector_needs_update
-                && let Ok(mut velocity_indicator_transform) = tx_velocity_indicator_q.get_mut(carrier_child) {
-                    // Update velocity vector transform
-                    *velocity_indicator_transform = velocity_indicator_transform_from_state(
-                        &tx_carrier_state.inner
-                    );
-                    // Update carrier velocity vector in the same time (here direction does not change, only magnitude)
-                    update_velocity_vector(&mut tx_carrier_state.inner);
-                    // Update ground angular velocity only
-                    update_ground_angular_velocity(
-                        &tx_carrier_state.inner,
-                        &mut tx_antenna_beam_footprint_state.inner,
-                    );
-                    // Update illumination time
-                    update_illumination_time(
-                        &tx_carrier_state.inner,
-                        &mut tx_antenna_beam_footprint_state.inner,
+                //Update iso-range ellipsoid transform
+                for mut iso_range_ellipsoid_transform in iso_range_ellipsoid_q.iter_mut() {
+                    *iso_range_ellipsoid_transform = iso_range_ellipsoid_transform_from_state(
+                        &tx_carrier_state.inner.position_m, // OT in world frame
+                        &rx_carrier_state.inner.position_m  // OR in world frame
                     );
                 }
+            }
+            if let Ok(mut velocity_indicator_transform) = tx_velocity_indicator_q.get_mut(carrier_child) {
+                // Update carrier velocity vector (here direction does not change, only magnitude)
+                update_velocity_vector(&mut tx_carrier_state.inner);
+                // Update velocity vector transform in the same time
+                *velocity_indicator_transform = velocity_indicator_transform_from_state(
+                    &tx_carrier_state.inner
+                );
+                // Update ground angular velocity only
+                update_ground_angular_velocity(
+                    &tx_carrier_state.inner,
+                    &mut tx_antenna_beam_footprint_state.inner,
+                );
+                // Update illumination time
+                update_illumination_time(
+                    &tx_carrier_state.inner,
+                    &mut tx_antenna_beam_footprint_state.inner,
+                );
+            }
         }
     }
-    // Update BSAR infos state
-    if tx_panel_widget.transform_needs_update  ||
-       tx_panel_widget.velocity_vector_needs_update ||
-       tx_panel_widget.system_needs_update {
-        // Update BSAR infos 
-        bsar_infos_state.inner.update_from_state(
-            &tx_carrier_state,
-            &rx_carrier_state,
-            &tx_antenna_beam_state.inner,
-            &rx_antenna_beam_state.inner,
-            &tx_antenna_beam_footprint_state.inner,
-            &rx_antenna_beam_footprint_state.inner,
-        );
-        // Update iso-range doppler plane transform and texture
-        refresh_iso_range_doppler_plane(
-            &mut materials,
-            &mut images,
-            &tx_carrier_state,
-            &rx_carrier_state,
-            &tx_antenna_beam_footprint_state.inner,
-            &rx_antenna_beam_footprint_state.inner,
-            &mut iso_range_doppler_plane_state,
-            &mut iso_range_doppler_q,
-            &iso_range_doppler_material_q,
-        );
+}
+
+/// Refreshes the BSAR infos and the iso-range/Doppler plane when any carrier
+/// state, or the plane window, changed.
+pub(super) fn update_bsar_system(
+    tx: (Res<TxCarrierState>, Res<TxAntennaBeamState>, Res<TxAntennaBeamFootprintState>),
+    rx: (Res<RxCarrierState>, Res<RxAntennaBeamState>, Res<RxAntennaBeamFootprintState>),
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut images: ResMut<Assets<Image>>,
+    mut bsar_infos_state: ResMut<BsarInfosState>,
+    mut iso_range_doppler_plane_state: ResMut<IsoRangeDopplerPlaneState>,
+    iso_range_doppler_material_q: Query<&MeshMaterial3d<StandardMaterial>, With<IsoRangeDopplerPlane>>,
+    mut iso_range_doppler_q: Query<&mut Transform, With<IsoRangeDopplerPlane>>,
+) {
+    let (tx_carrier_state, tx_antenna_beam_state, tx_antenna_beam_footprint_state) = tx;
+    let (rx_carrier_state, rx_antenna_beam_state, rx_antenna_beam_footprint_state) = rx;
+    // The geometry update systems only write the states they recompute
+    let is_system_changed = tx_carrier_state.is_changed() ||
+        tx_antenna_beam_state.is_changed() ||
+        tx_antenna_beam_footprint_state.is_changed() ||
+        rx_carrier_state.is_changed() ||
+        rx_antenna_beam_state.is_changed() ||
+        rx_antenna_beam_footprint_state.is_changed() ||
+        iso_range_doppler_plane_state.is_changed();
+    if !is_system_changed {
+        return;
     }
-    // The panel flags are one-shot commands consumed by this system: clear
-    // them here so they cannot linger when the Tx panel (which resets its own
-    // flags only while it is open) is closed.
-    tx_panel_widget.transform_needs_update = false;
-    tx_panel_widget.velocity_vector_needs_update = false;
-    tx_panel_widget.system_needs_update = false;
+    // Update BSAR infos
+    bsar_infos_state.inner.update_from_state(
+        &tx_carrier_state,
+        &rx_carrier_state,
+        &tx_antenna_beam_state.inner,
+        &rx_antenna_beam_state.inner,
+        &tx_antenna_beam_footprint_state.inner,
+        &rx_antenna_beam_footprint_state.inner,
+    );
+    // Update iso-range doppler plane transform and texture
+    refresh_iso_range_doppler_plane(
+        &mut materials,
+        &mut images,
+        &tx_carrier_state,
+        &rx_carrier_state,
+        &tx_antenna_beam_footprint_state.inner,
+        &rx_antenna_beam_footprint_state.inner,
+        &mut iso_range_doppler_plane_state,
+        &mut iso_range_doppler_q,
+        &iso_range_doppler_material_q,
+    );
 }
 
 
@@ -289,9 +277,10 @@ fn tx_system_ui(
     tx_carrier_state: &mut TxCarrierState,
     tx_antenna_beam_state: &mut TxAntennaBeamState,
     reset_all: bool,
-    system_needs_update: &mut bool,
+    system_changed: &mut bool,
 ) {
     let mut old_state = 0.0f64;
+    let default_state = TxCarrierState::default();
 
     ui.separator();
     if heading_with_reset(
@@ -299,7 +288,6 @@ fn tx_system_ui(
         egui::RichText::new("SYSTEM").strong(),
         "Resets the System settings to their defaults"
     ) || reset_all {
-        let default_state = TxCarrierState::default();
         tx_carrier_state.center_frequency_ghz = default_state.center_frequency_ghz;
         tx_carrier_state.bandwidth_mhz = default_state.bandwidth_mhz;
         tx_carrier_state.pulse_duration_us = default_state.pulse_duration_us;
@@ -308,7 +296,7 @@ fn tx_system_ui(
         tx_carrier_state.loss_factor_db = default_state.loss_factor_db;
         tx_antenna_beam_state.inner.one_way_gain_dbi =
             TxAntennaBeamState::default().inner.one_way_gain_dbi;
-        *system_needs_update = true;
+        *system_changed = true;
     }
     ui.separator();
     // Tx system settings
@@ -323,8 +311,9 @@ fn tx_system_ui(
                 .monospace();
             ui.label("Center Freq.: ").on_hover_text(hover_text.clone());
             old_state = tx_carrier_state.center_frequency_ghz;
-            ui.add(
+            let response = ui.add(
                 egui::DragValue::new(&mut tx_carrier_state.center_frequency_ghz)
+                    .custom_parser(expression::parse)
                     .update_while_editing(false)
                     .speed(0.1)
                     .range(0.1..=100.0)
@@ -332,8 +321,9 @@ fn tx_system_ui(
                     .suffix(" GHz")
             )
             .on_hover_text(hover_text);
+            reset_menu(&response, &mut tx_carrier_state.center_frequency_ghz, default_state.center_frequency_ghz);
             if old_state != tx_carrier_state.center_frequency_ghz {
-                *system_needs_update = true;
+                *system_changed = true;
             }
             ui.end_row();
 
@@ -343,17 +333,11 @@ fn tx_system_ui(
                 .monospace();
             ui.label("Bandwidth: ").on_hover_text(hover_text.clone());
             old_state = tx_carrier_state.bandwidth_mhz;
-            ui.add(
-                egui::DragValue::new(&mut tx_carrier_state.bandwidth_mhz)
-                    .update_while_editing(false)
-                    .speed(1.0)
-                    .range(1.0..=10000.0)
-                    .fixed_decimals(1)
-                    .suffix(" MHz")
-            )
-            .on_hover_text(hover_text);
+            let response = ui.add(log_slider(&mut tx_carrier_state.bandwidth_mhz, 1.0..=10000.0, 1.0, 1, " MHz"))
+                .on_hover_text(hover_text);
+            reset_menu(&response, &mut tx_carrier_state.bandwidth_mhz, default_state.bandwidth_mhz);
             if old_state != tx_carrier_state.bandwidth_mhz {
-                *system_needs_update = true;
+                *system_changed = true;
             }
             ui.end_row();
 
@@ -363,8 +347,9 @@ fn tx_system_ui(
                 .monospace();
             ui.label("Pulse Dur.: ").on_hover_text(hover_text.clone());
             old_state = tx_carrier_state.pulse_duration_us;
-            ui.add(
+            let response = ui.add(
                 egui::DragValue::new(&mut tx_carrier_state.pulse_duration_us)
+                    .custom_parser(expression::parse)
                     .update_while_editing(false)
                     .speed(10.0)
                     .range(0.0..=1000000.0)
@@ -372,28 +357,23 @@ fn tx_system_ui(
                     .suffix(" µs")
             )
             .on_hover_text(hover_text);
+            reset_menu(&response, &mut tx_carrier_state.pulse_duration_us, default_state.pulse_duration_us);
             if old_state != tx_carrier_state.pulse_duration_us {
-                *system_needs_update = true;
+                *system_changed = true;
             }
             ui.end_row();
 
             // ***** PRF ***** //
-            let hover_text = egui::RichText::new("Sets the Pulse Repetition Frequency (PRF) of the transmitter (1 - 1000000 Hz)")
+            let hover_text = egui::RichText::new(format!("Sets the Pulse Repetition Frequency (PRF) of the transmitter (1 - {MAX_PRF_HZ} Hz)"))
                 .color(egui::Color32::from_rgb(200, 200, 200))
                 .monospace();
             ui.label("PRF: ").on_hover_text(hover_text.clone());
             old_state = tx_carrier_state.prf_hz;
-            ui.add(
-                egui::DragValue::new(&mut tx_carrier_state.prf_hz)
-                    .update_while_editing(false)
-                    .speed(1.0)
-                    .range(1.0..=1000000.0)
-                    .fixed_decimals(1)
-                    .suffix(" Hz")
-            )
-            .on_hover_text(hover_text);
+            let response = ui.add(log_slider(&mut tx_carrier_state.prf_hz, 1.0..=MAX_PRF_HZ, 1.0, 1, " Hz"))
+                .on_hover_text(hover_text);
+            reset_menu(&response, &mut tx_carrier_state.prf_hz, default_state.prf_hz);
             if old_state != tx_carrier_state.prf_hz {
-                *system_needs_update = true;
+                *system_changed = true;
             }
             ui.end_row();
 
@@ -403,8 +383,9 @@ fn tx_system_ui(
                 .monospace();
             ui.label("Peak Power: ").on_hover_text(hover_text.clone());
             old_state = tx_carrier_state.peak_power_w;
-            ui.add(
+            let response = ui.add(
                 egui::DragValue::new(&mut tx_carrier_state.peak_power_w)
+                    .custom_parser(expression::parse)
                     .update_while_editing(false)
                     .speed(1.0)
                     .range(1.0..=10000.0)
@@ -412,8 +393,9 @@ fn tx_system_ui(
                     .suffix(" W")
             )
             .on_hover_text(hover_text);
+            reset_menu(&response, &mut tx_carrier_state.peak_power_w, default_state.peak_power_w);
             if old_state != tx_carrier_state.peak_power_w {
-                *system_needs_update = true;
+                *system_changed = true;
             }
             ui.end_row();
 
@@ -423,8 +405,9 @@ fn tx_system_ui(
                 .monospace();
             ui.label("Loss Factor: ").on_hover_text(hover_text.clone());
             old_state = tx_carrier_state.loss_factor_db;
-            ui.add(
+            let response = ui.add(
                 egui::DragValue::new(&mut tx_carrier_state.loss_factor_db)
+                    .custom_parser(expression::parse)
                     .update_while_editing(false)
                     .speed(0.1)
                     .range(0.0..=100.0)
@@ -432,8 +415,9 @@ fn tx_system_ui(
                     .suffix(" dB")
             )
             .on_hover_text(hover_text);
+            reset_menu(&response, &mut tx_carrier_state.loss_factor_db, default_state.loss_factor_db);
             if old_state != tx_carrier_state.loss_factor_db {
-                *system_needs_update = true;
+                *system_changed = true;
             }
             ui.end_row();
 
@@ -443,8 +427,9 @@ fn tx_system_ui(
                 .monospace();
             ui.label("Antenna gain: ").on_hover_text(hover_text.clone());
             old_state = tx_antenna_beam_state.inner.one_way_gain_dbi;
-            ui.add(
+            let response = ui.add(
                 egui::DragValue::new(&mut tx_antenna_beam_state.inner.one_way_gain_dbi)
+                    .custom_parser(expression::parse)
                     .update_while_editing(false)
                     .speed(0.1)
                     .range(0.0..=100.0)
@@ -452,9 +437,32 @@ fn tx_system_ui(
                     .suffix(" dBi")
             )
             .on_hover_text(hover_text);
+            gain_menu(&response, &mut tx_antenna_beam_state.inner, TxAntennaBeamState::default().inner.one_way_gain_dbi);
             if old_state != tx_antenna_beam_state.inner.one_way_gain_dbi {
-                *system_needs_update = true;
+                *system_changed = true;
             }
             ui.end_row();
+
+            // ***** Chirp ***** //
+            let chirp = tx_carrier_state.chirp();
+            let hover_text = egui::RichText::new("Chirp rate: bandwidth / pulse duration\nTBP: time-bandwidth product, the range compression gain\nCompressed pulse: pulse width at half power after range compression")
+                .color(egui::Color32::from_rgb(200, 200, 200))
+                .monospace();
+            ui.label("Chirp rate: ").on_hover_text(hover_text.clone());
+            ui.label(format!("{:.3} MHz/µs", chirp.rate_hzps() * 1e-12)).on_hover_text(hover_text.clone());
+            ui.end_row();
+            ui.label("TBP: ").on_hover_text(hover_text.clone());
+            ui.label(format!("{:.0} ({:.1} dB)", chirp.time_bandwidth_product(), chirp.compression_gain_db()))
+                .on_hover_text(hover_text.clone());
+            ui.end_row();
+            ui.label("Comp. pulse: ").on_hover_text(hover_text.clone());
+            ui.label(format!("{:.3} ns", chirp.compressed_pulse_width_s() * 1e9)).on_hover_text(hover_text);
+            ui.end_row();
         });
+    if !tx_carrier_state.chirp().has_full_compression_gain() {
+        let text = format!(
+            "⚠ TBP below {MIN_TIME_BANDWIDTH_PRODUCT:.0}: the range compression\ngain assumed by the NESZ is not reached"
+        );
+        ui.label(egui::RichText::new(text).color(egui::Color32::from_rgb(255, 120, 120)));
+    }
 }
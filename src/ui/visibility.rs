@@ -0,0 +1,235 @@
+use bevy::{math::DQuat, prelude::*};
+use bevy_egui::{egui, EguiContexts, EguiPrimaryContextPass};
+
+use crate::{
+    entities::{AntennaBeamState, AntennaState, CarrierState},
+    expression,
+    scene::{RxAntennaBeamState, RxAntennaState, RxCarrierState, TxAntennaBeamState, TxAntennaState, TxCarrierState},
+    ui::GeoReferenceWidget,
+    visibility::{visibility_windows, BeamTrack, VisibilityConditions, VisibilityWindow},
+};
+
+const TEXT_COLOR: egui::Color32 = egui::Color32::from_rgb(200, 200, 200);
+
+pub struct VisibilityPlugin;
+
+impl Plugin for VisibilityPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<VisibilityWidget>()
+            .add_systems(EguiPrimaryContextPass, visibility_ui_system);
+    }
+}
+
+/// Tx–Rx visibility windows along the carriers trajectories: when the direct
+/// path clears the ellipsoid and, optionally, both beams illuminate the scene center.
+#[derive(Resource)]
+pub struct VisibilityWidget {
+    pub conditions: VisibilityConditions,
+    /// Searched interval in seconds, relative to the scene center crossing (0 s)
+    pub start_s: f64,
+    pub duration_s: f64,
+    /// Sampling step in seconds
+    pub step_s: f64,
+    /// Last computed windows
+    pub windows: Vec<VisibilityWindow>,
+    /// Result of the last computation
+    status: Option<String>,
+}
+
+impl Default for VisibilityWidget {
+    fn default() -> Self {
+        Self {
+            conditions: VisibilityConditions {
+                direct_path_clearance_m: 0.0,
+                beams_on_scene: true,
+            },
+            start_s: -60.0,
+            duration_s: 120.0,
+            step_s: 0.1,
+            windows: Vec::new(),
+            status: None,
+        }
+    }
+}
+
+impl VisibilityWidget {
+    /// Computes the visibility windows of the current geometry.
+    pub fn compute(
+        &mut self,
+        geo_reference_widget: &GeoReferenceWidget,
+        tx: (&TxCarrierState, &TxAntennaState, &TxAntennaBeamState),
+        rx: (&RxCarrierState, &RxAntennaState, &RxAntennaBeamState),
+    ) {
+        let tx_track = beam_track(&tx.0.inner, &tx.1.inner, &tx.2.inner);
+        let rx_track = beam_track(&rx.0.inner, &rx.1.inner, &rx.2.inner);
+        self.windows = visibility_windows(
+            &geo_reference_widget.scene_origin,
+            &tx_track,
+            &rx_track,
+            &self.conditions,
+            self.start_s,
+            self.duration_s,
+            self.step_s
+        );
+        let total_s = self.windows.iter().map(VisibilityWindow::duration_s).sum::<f64>();
+        self.status = Some(format!("{} windows, {:.2} s visible over {:.2} s", self.windows.len(), total_s, self.duration_s));
+    }
+}
+
+/// Gets the trajectory and beam of a carrier, its position, velocity and
+/// orientation being brought back to time 0 s.
+fn beam_track(
+    carrier_state: &CarrierState,
+    antenna_state: &AntennaState,
+    antenna_beam_state: &AntennaBeamState,
+) -> BeamTrack {
+    let carrier_rotation = carrier_state.initial_rotation();
+    let antenna_rotation = DQuat::from_euler(
+        EulerRot::ZYX,
+        antenna_state.heading_deg.to_radians(),
+        antenna_state.elevation_deg.to_radians(),
+        antenna_state.bank_deg.to_radians()
+    );
+    let velocity_mps = carrier_state.initial_velocity_vector_mps();
+    BeamTrack {
+        position_m: carrier_state.position_m - carrier_state.motion.displacement_at(&velocity_mps, carrier_state.time_s),
+        velocity_mps,
+        motion: carrier_state.motion,
+        rotation_world_to_antenna: (carrier_rotation * antenna_rotation).inverse(),
+        azimuth_beam_width_deg: antenna_beam_state.azimuth_beam_width_deg,
+        elevation_beam_width_deg: antenna_beam_state.elevation_beam_width_deg,
+    }
+}
+
+fn visibility_ui_system(
+    mut contexts: EguiContexts,
+    mut visibility_widget: ResMut<VisibilityWidget>,
+    geo_reference_widget: Res<GeoReferenceWidget>,
+    tx: (Res<TxCarrierState>, Res<TxAntennaState>, Res<TxAntennaBeamState>),
+    rx: (Res<RxCarrierState>, Res<RxAntennaState>, Res<RxAntennaBeamState>),
+) -> Result {
+    let ctx = contexts.ctx_mut()?;
+
+    let visibility_window = egui::Window::new("Visibility")
+        .resizable(false)
+        .constrain(true)
+        .collapsible(true)
+        .title_bar(true)
+        .enabled(true)
+        .default_open(false)
+        .default_pos(egui::pos2(320.0, 200.0));
+    visibility_window.show(ctx, |ui| {
+        if visibility_ui(ui, &mut visibility_widget) {
+            visibility_widget.compute(
+                &geo_reference_widget,
+                (&tx.0, &tx.1, &tx.2),
+                (&rx.0, &rx.1, &rx.2)
+            );
+        }
+    });
+
+    Ok(())
+}
+
+/// Returns true when the windows must be computed.
+fn visibility_ui(ui: &mut egui::Ui, widget: &mut VisibilityWidget) -> bool {
+    egui::Grid::new("visibility_grid")
+        .num_columns(2)
+        .striped(false)
+        .spacing([20.0, 5.0])
+        .show(ui, |ui| {
+            let hover_text = egui::RichText::new("Sets the searched interval, relative to the time the boresights\nintersect the scene center (0 s)")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Interval: ").on_hover_text(hover_text.clone());
+            ui.horizontal(|ui| {
+                ui.add(
+                    egui::DragValue::new(&mut widget.start_s)
+                        .custom_parser(expression::parse)
+                        .update_while_editing(false)
+                        .speed(1.0)
+                        .range(-86400.0..=86400.0)
+                        .fixed_decimals(1)
+                        .prefix("from ")
+                        .suffix(" s")
+                ).on_hover_text(hover_text.clone());
+                ui.add(
+                    egui::DragValue::new(&mut widget.duration_s)
+                        .custom_parser(expression::parse)
+                        .update_while_editing(false)
+                        .speed(1.0)
+                        .range(0.1..=86400.0)
+                        .fixed_decimals(1)
+                        .prefix("for ")
+                        .suffix(" s")
+                ).on_hover_text(hover_text);
+            });
+            ui.end_row();
+
+            let hover_text = egui::RichText::new("Sets the sampling step (0.01 - 60 s), shorter windows may be missed")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Step: ").on_hover_text(hover_text.clone());
+            ui.add(
+                egui::DragValue::new(&mut widget.step_s)
+                    .custom_parser(expression::parse)
+                    .update_while_editing(false)
+                    .speed(0.01)
+                    .range(0.01..=60.0)
+                    .fixed_decimals(2)
+                    .suffix(" s")
+            ).on_hover_text(hover_text);
+            ui.end_row();
+
+            let hover_text = egui::RichText::new("Sets the minimum height of the Tx-Rx direct path above the ellipsoid,\nstanding for the terrain (negative values ignore the Earth)")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.label("Direct path clearance: ").on_hover_text(hover_text.clone());
+            ui.add(
+                egui::DragValue::new(&mut widget.conditions.direct_path_clearance_m)
+                    .custom_parser(expression::parse)
+                    .update_while_editing(false)
+                    .speed(10.0)
+                    .range(-1e7..=1e5)
+                    .fixed_decimals(0)
+                    .suffix(" m")
+            ).on_hover_text(hover_text);
+            ui.end_row();
+
+            let hover_text = egui::RichText::new("Requires the scene center within both half-power beams")
+                .color(TEXT_COLOR)
+                .monospace();
+            ui.checkbox(&mut widget.conditions.beams_on_scene, "Both beams on the scene").on_hover_text(hover_text);
+            ui.end_row();
+        });
+
+    let compute = ui.button("Compute windows").clicked();
+    if let Some(status) = &widget.status {
+        ui.label(egui::RichText::new(status).color(TEXT_COLOR));
+    }
+    if !widget.windows.is_empty() {
+        egui::ScrollArea::vertical()
+            .max_height(200.0)
+            .show(ui, |ui| {
+                egui::Grid::new("visibility_windows_grid")
+                    .num_columns(4)
+                    .striped(true)
+                    .spacing([20.0, 3.0])
+                    .show(ui, |ui| {
+                        for header in ["#", "Start [s]", "Stop [s]", "Duration [s]"] {
+                            ui.label(egui::RichText::new(header).strong());
+                        }
+                        ui.end_row();
+                        for (i, window) in widget.windows.iter().enumerate() {
+                            ui.label(format!("{}", i + 1));
+                            ui.label(format!("{:.2}", window.start_s));
+                            ui.label(format!("{:.2}", window.end_s));
+                            ui.label(format!("{:.2}", window.duration_s()));
+                            ui.end_row();
+                        }
+                    });
+            });
+    }
+    compute
+}
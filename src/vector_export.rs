@@ -0,0 +1,287 @@
+//! Top view 2D vector exports (SVG and DXF) of the ground geometry.
+//!
+//! Paths are given in the scene ENU ground plane (x East, y North) in meters
+//! and are written with these real-world coordinates: the SVG user units and
+//! the DXF drawing units are meters, North up. The scenario metadata are
+//! written as the SVG title and description, and as DXF comments.
+
+use bevy::math::DVec2;
+
+use crate::scenario::ScenarioMetadata;
+
+/// SVG drawing largest side in pixels (the viewBox is in meters)
+const SVG_SIZE_PX: f64 = 1000.0;
+/// Margin around the drawn paths, as a fraction of their extent
+const MARGIN_RATIO: f64 = 0.02;
+/// Stroke width and dash pattern (on, off), as fractions of the drawing extent
+const STROKE_WIDTH_RATIO: f64 = 0.002;
+const DASH_RATIOS: (f64, f64) = (0.01, 0.008);
+/// Decimals of the written coordinates (mm)
+const DECIMALS: usize = 3;
+
+/// A polyline of the top view.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct VectorPath {
+    /// Vertices in the ENU ground plane (m)
+    pub points: Vec<DVec2>,
+    /// Joins the last vertex to the first one
+    pub closed: bool,
+    pub dashed: bool,
+    /// Value carried by the path (e.g. the contour level)
+    pub label: Option<String>,
+}
+
+/// Paths drawn with the same color, one SVG group or DXF layer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VectorLayer {
+    /// Layer name, letters, digits and underscores for DXF
+    pub name: String,
+    pub rgb: (u8, u8, u8),
+    pub paths: Vec<VectorPath>,
+}
+
+impl VectorLayer {
+    /// Iterates the vertices of the paths.
+    fn points(&self) -> impl Iterator<Item = &DVec2> {
+        self.paths.iter().flat_map(|path| path.points.iter())
+    }
+}
+
+/// Gets the bounds (min, max) of the finite vertices of `layers`, with a margin.
+fn bounds(layers: &[VectorLayer]) -> Option<(DVec2, DVec2)> {
+    let (min, max) = layers.iter()
+        .flat_map(VectorLayer::points)
+        .filter(|point| point.is_finite())
+        .fold((DVec2::MAX, DVec2::MIN), |(min, max), point| (min.min(*point), max.max(*point)));
+    if min.x > max.x {
+        return None; // No vertex
+    }
+    let margin = MARGIN_RATIO * (max - min).max_element().max(1.0);
+    Some((min - margin, max + margin))
+}
+
+/// Escapes a string for XML text and attribute values.
+fn xml_escape(value: &str) -> String {
+    value.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// Writes `layers` as an SVG top view, North up, whose user units are the ENU
+/// meters (a vertical flip keeps the North up).
+pub fn to_svg(layers: &[VectorLayer], metadata: &ScenarioMetadata) -> String {
+    let (min, max) = bounds(layers).unwrap_or((DVec2::ZERO, DVec2::ONE));
+    let size = max - min;
+    let extent = size.max_element();
+    let scale = SVG_SIZE_PX / extent;
+    let stroke_width = STROKE_WIDTH_RATIO * extent;
+    let (dash_on, dash_off) = (DASH_RATIOS.0 * extent, DASH_RATIOS.1 * extent);
+    let mut svg = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{:.0}\" height=\"{:.0}\" viewBox=\"{:.DECIMALS$} {:.DECIMALS$} {:.DECIMALS$} {:.DECIMALS$}\">\n",
+        scale * size.x, scale * size.y,
+        min.x, -max.y, size.x, size.y // The flip maps y to -y
+    );
+    // Metadata: the title shown by the viewers, all the fields as description
+    if !metadata.title.trim().is_empty() {
+        svg.push_str(&format!("<title>{}</title>\n", xml_escape(metadata.title.trim())));
+    }
+    let lines = metadata.lines();
+    if !lines.is_empty() {
+        svg.push_str(&format!("<desc>{}</desc>\n", xml_escape(&lines.join("\n"))));
+    }
+    svg.push_str(&format!(
+        "<!-- BSARGeom top view: x East, y North, in meters -->\n\
+         <g transform=\"scale(1,-1)\" fill=\"none\" stroke-width=\"{stroke_width:.DECIMALS$}\" stroke-linejoin=\"round\">\n"
+    ));
+    for layer in layers {
+        let (r, g, b) = layer.rgb;
+        svg.push_str(&format!("<g id=\"{}\" stroke=\"rgb({r},{g},{b})\">\n", xml_escape(&layer.name)));
+        for path in layer.paths.iter().filter(|path| path.points.len() > 1) {
+            let points: Vec<String> = path.points.iter()
+                .map(|point| format!("{:.DECIMALS$},{:.DECIMALS$}", point.x, point.y))
+                .collect();
+            let element = if path.closed { "polygon" } else { "polyline" };
+            let dash = if path.dashed {
+                format!(" stroke-dasharray=\"{dash_on:.DECIMALS$} {dash_off:.DECIMALS$}\"")
+            } else {
+                String::new()
+            };
+            match &path.label {
+                Some(label) => svg.push_str(&format!(
+                    "<{element} points=\"{}\"{dash}><title>{}</title></{element}>\n",
+                    points.join(" "), xml_escape(label)
+                )),
+                None => svg.push_str(&format!("<{element} points=\"{}\"{dash}/>\n", points.join(" "))),
+            }
+        }
+        svg.push_str("</g>\n");
+    }
+    svg.push_str("</g>\n</svg>\n");
+    svg
+}
+
+/// Gets the closest AutoCAD Color Index among the 7 standard colors.
+fn aci_color((r, g, b): (u8, u8, u8)) -> u8 {
+    const STANDARD_COLORS: [(u8, (u8, u8, u8)); 7] = [
+        (1, (255, 0, 0)), (2, (255, 255, 0)), (3, (0, 255, 0)), (4, (0, 255, 255)),
+        (5, (0, 0, 255)), (6, (255, 0, 255)), (7, (255, 255, 255)),
+    ];
+    let distance = |(sr, sg, sb): (u8, u8, u8)| {
+        (r as i32 - sr as i32).pow(2) + (g as i32 - sg as i32).pow(2) + (b as i32 - sb as i32).pow(2)
+    };
+    STANDARD_COLORS.iter()
+        .min_by_key(|(_, rgb)| distance(*rgb))
+        .map_or(7, |(aci, _)| *aci)
+}
+
+/// Writes `layers` as an ASCII DXF (R12) drawing of 2D polylines, one DXF layer
+/// per layer, in meters.
+pub fn to_dxf(layers: &[VectorLayer], metadata: &ScenarioMetadata) -> String {
+    let extent = bounds(layers).map_or(1.0, |(min, max)| (max - min).max_element());
+    let (dash_on, dash_off) = (DASH_RATIOS.0 * extent, DASH_RATIOS.1 * extent);
+    // Group code / value pairs, one per line
+    let mut dxf = String::new();
+    let mut pair = |code: u16, value: &str| dxf.push_str(&format!("{code}\n{value}\n"));
+    let number = |value: f64| format!("{value:.DECIMALS$}");
+
+    // Metadata as comments
+    for line in metadata.lines() {
+        pair(999, &line);
+    }
+    // Header: meters
+    pair(0, "SECTION");
+    pair(2, "HEADER");
+    pair(9, "$INSUNITS");
+    pair(70, "6");
+    pair(0, "ENDSEC");
+    // Tables: line types and layers
+    pair(0, "SECTION");
+    pair(2, "TABLES");
+    pair(0, "TABLE");
+    pair(2, "LTYPE");
+    pair(70, "2");
+    for (name, description, dashes) in [
+        ("CONTINUOUS", "Solid line", vec![]),
+        ("DASHED", "Dashed line", vec![dash_on, -dash_off]),
+    ] {
+        pair(0, "LTYPE");
+        pair(2, name);
+        pair(70, "0");
+        pair(3, description);
+        pair(72, "65");
+        pair(73, &dashes.len().to_string());
+        pair(40, &number(dashes.iter().map(|dash: &f64| dash.abs()).sum()));
+        for dash in dashes {
+            pair(49, &number(dash));
+        }
+    }
+    pair(0, "ENDTAB");
+    pair(0, "TABLE");
+    pair(2, "LAYER");
+    pair(70, &layers.len().to_string());
+    for layer in layers {
+        pair(0, "LAYER");
+        pair(2, &layer.name);
+        pair(70, "0");
+        pair(62, &aci_color(layer.rgb).to_string());
+        pair(6, "CONTINUOUS");
+    }
+    pair(0, "ENDTAB");
+    pair(0, "ENDSEC");
+    // Entities: a POLYLINE and its VERTEX entities per path
+    pair(0, "SECTION");
+    pair(2, "ENTITIES");
+    for layer in layers {
+        for path in layer.paths.iter().filter(|path| path.points.len() > 1) {
+            pair(0, "POLYLINE");
+            pair(8, &layer.name);
+            if path.dashed {
+                pair(6, "DASHED");
+            }
+            pair(66, "1");
+            pair(10, "0.0");
+            pair(20, "0.0");
+            pair(30, "0.0");
+            pair(70, if path.closed { "1" } else { "0" });
+            for point in path.points.iter() {
+                pair(0, "VERTEX");
+                pair(8, &layer.name);
+                pair(10, &number(point.x));
+                pair(20, &number(point.y));
+                pair(30, "0.0");
+            }
+            pair(0, "SEQEND");
+            pair(8, &layer.name);
+        }
+    }
+    pair(0, "ENDSEC");
+    pair(0, "EOF");
+    dxf
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layers() -> Vec<VectorLayer> {
+        vec![
+            VectorLayer {
+                name: "TX_FOOTPRINT".to_string(),
+                rgb: (255, 127, 14),
+                paths: vec![VectorPath {
+                    points: vec![DVec2::new(-100.0, -50.0), DVec2::new(100.0, -50.0), DVec2::new(0.0, 150.0)],
+                    closed: true,
+                    ..Default::default()
+                }],
+            },
+            VectorLayer {
+                name: "ISO_DOPPLER".to_string(),
+                rgb: (31, 119, 180),
+                paths: vec![VectorPath {
+                    points: vec![DVec2::new(0.0, 0.0), DVec2::new(10.0, 20.0)],
+                    dashed: true,
+                    label: Some("-10 Hz <neg>".to_string()),
+                    ..Default::default()
+                }],
+            },
+        ]
+    }
+
+    #[test]
+    fn svg_in_meters_north_up() {
+        let svg = to_svg(&layers(), &ScenarioMetadata::default());
+        // Bounds [-100, 100] × [-50, 150] with a 4 m margin, y flipped
+        assert!(svg.contains(r#"viewBox="-104.000 -154.000 208.000 208.000""#), "{svg}");
+        assert!(svg.contains(r#"<polygon points="-100.000,-50.000 100.000,-50.000 0.000,150.000"/>"#));
+        assert!(svg.contains("<title>-10 Hz &lt;neg&gt;</title>"));
+        assert!(svg.contains(r#"stroke="rgb(31,119,180)""#));
+        assert!(svg.contains("stroke-dasharray"));
+    }
+
+    #[test]
+    fn dxf_polylines_and_layers() {
+        let dxf = to_dxf(&layers(), &ScenarioMetadata::default());
+        let lines: Vec<&str> = dxf.lines().collect();
+        assert!(lines.len().is_multiple_of(2));
+        assert_eq!(lines[lines.len() - 2..], ["0", "EOF"]);
+        // Layer colors: orange is closest to red, the Doppler blue to blue
+        assert!(dxf.contains("2\nTX_FOOTPRINT\n70\n0\n62\n1\n"));
+        assert!(dxf.contains("2\nISO_DOPPLER\n70\n0\n62\n5\n"));
+        assert_eq!(dxf.matches("0\nPOLYLINE\n").count(), 2);
+        assert_eq!(dxf.matches("0\nVERTEX\n").count(), 5);
+        assert!(dxf.contains("0\nVERTEX\n8\nTX_FOOTPRINT\n10\n100.000\n20\n-50.000\n"));
+        assert!(dxf.contains("8\nISO_DOPPLER\n6\nDASHED\n"));
+    }
+
+    #[test]
+    fn metadata_in_svg_and_dxf() {
+        let metadata = ScenarioMetadata {
+            title: "Pass <1>".to_string(),
+            description: "Tx & Rx".to_string(),
+            ..Default::default()
+        };
+        let svg = to_svg(&layers(), &metadata);
+        assert!(svg.contains("<title>Pass &lt;1&gt;</title>\n<desc>title: Pass &lt;1&gt;\ndescription: Tx &amp; Rx</desc>\n<!--"));
+        let dxf = to_dxf(&layers(), &metadata);
+        assert!(dxf.starts_with("999\ntitle: Pass <1>\n999\ndescription: Tx & Rx\n0\nSECTION\n"));
+    }
+}
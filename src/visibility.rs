@@ -0,0 +1,213 @@
+//! Tx–Rx visibility windows.
+//!
+//! Along their trajectories, the transmitter and the receiver see each
+//! other when their direct path stays above the ellipsoid (plus a clearance
+//! margin standing for the terrain), and the bistatic acquisition is possible
+//! when, in addition, both antenna beams illuminate the scene center.
+
+use bevy::math::{DQuat, DVec3};
+
+use crate::{coordinates::LocalCartesian, motion::MotionProfile};
+
+/// Time resolution in seconds of the windows bounds.
+const WINDOW_BOUND_RESOLUTION_S: f64 = 0.01;
+/// Resolution of the direct path minimum height search, as a fraction of the path.
+const DIRECT_PATH_RESOLUTION: f64 = 1e-4;
+
+/// A carrier moving along its trajectory with an antenna beam fixed to the
+/// carrier, in the scene ENU frame.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BeamTrack {
+    /// Carrier position in m and velocity in m/s at time 0 s
+    pub position_m: DVec3,
+    pub velocity_mps: DVec3,
+    /// Turn and speed profile along the trajectory
+    pub motion: MotionProfile,
+    /// Rotation from the ENU frame to the antenna frame (boresight along X) at time 0 s
+    pub rotation_world_to_antenna: DQuat,
+    /// Half-power beam widths in degrees
+    pub azimuth_beam_width_deg: f64,
+    pub elevation_beam_width_deg: f64,
+}
+
+impl BeamTrack {
+    /// Gets the carrier position in m at `time_s`.
+    #[inline]
+    pub fn position_at(&self, time_s: f64) -> DVec3 {
+        self.position_m + self.motion.displacement_at(&self.velocity_mps, time_s)
+    }
+
+    /// Checks whether `point` lies within the half-power beam (elliptical cone,
+    /// as the footprint) at `time_s`.
+    pub fn illuminates(&self, point: &DVec3, time_s: f64) -> bool {
+        // The beam turns with the carrier
+        let rotation_world_to_antenna = self.rotation_world_to_antenna * self.motion.turn_at(time_s).inverse();
+        let direction = rotation_world_to_antenna * (*point - self.position_at(time_s));
+        if direction.x <= 0.0 {
+            return false; // Behind the antenna
+        }
+        let ty = (0.5 * self.azimuth_beam_width_deg.to_radians()).tan();
+        let tz = (0.5 * self.elevation_beam_width_deg.to_radians()).tan();
+        let (u, v) = (direction.y / (direction.x * ty), direction.z / (direction.x * tz));
+        u * u + v * v <= 1.0
+    }
+}
+
+/// A time interval of the timeline.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VisibilityWindow {
+    pub start_s: f64,
+    pub end_s: f64,
+}
+
+impl VisibilityWindow {
+    /// Gets the window duration in seconds.
+    #[inline]
+    pub fn duration_s(&self) -> f64 {
+        self.end_s - self.start_s
+    }
+}
+
+/// What the visibility windows require.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VisibilityConditions {
+    /// Minimum height in m of the Tx–Rx direct path above the ellipsoid
+    pub direct_path_clearance_m: f64,
+    /// Both beams must illuminate the scene center
+    pub beams_on_scene: bool,
+}
+
+/// Gets the minimum height in m above the ellipsoid of `scene` of the segment
+/// between the ENU points `a` and `b`, negative when the segment goes through
+/// the ellipsoid.
+pub fn direct_path_min_height_m(scene: &LocalCartesian, a: &DVec3, b: &DVec3) -> f64 {
+    // The height along a chord is unimodal: golden-section search of its minimum
+    let height = |s: f64| scene.transform_from_enu_point_to_geographic_point(&a.lerp(*b, s)).height_m();
+    let inverse_golden_ratio = 0.5 * (5f64.sqrt() - 1.0);
+    let (mut lo, mut hi) = (0.0, 1.0);
+    while hi - lo > DIRECT_PATH_RESOLUTION {
+        let c = hi - inverse_golden_ratio * (hi - lo);
+        let d = lo + inverse_golden_ratio * (hi - lo);
+        if height(c) < height(d) { hi = d; } else { lo = c; }
+    }
+    height(0.5 * (lo + hi)).min(height(0.0)).min(height(1.0))
+}
+
+/// Lists the intervals of `[start_s, start_s + duration_s]` during which
+/// `is_on` holds, sampled every `step_s` seconds and refined by bisection:
+/// intervals shorter than the step may be missed.
+pub fn time_windows(
+    is_on: impl Fn(f64) -> bool,
+    start_s: f64,
+    duration_s: f64,
+    step_s: f64,
+) -> Vec<VisibilityWindow> {
+    // Refines the switch of `is_on` within [before, after]
+    let refine = |mut before: f64, mut after: f64| {
+        let on_before = is_on(before);
+        while after - before > WINDOW_BOUND_RESOLUTION_S {
+            let middle = 0.5 * (before + after);
+            if is_on(middle) == on_before { before = middle; } else { after = middle; }
+        }
+        0.5 * (before + after)
+    };
+
+    let step_s = step_s.max(WINDOW_BOUND_RESOLUTION_S);
+    let num_steps = (duration_s / step_s).ceil().max(0.0) as usize;
+    let end_s = start_s + duration_s;
+    let mut windows = Vec::new();
+    let mut window_start_s = is_on(start_s).then_some(start_s);
+    let mut previous_s = start_s;
+    for i in 1..=num_steps {
+        let time_s = (start_s + i as f64 * step_s).min(end_s);
+        match (window_start_s, is_on(time_s)) {
+            (None, true) => window_start_s = Some(refine(previous_s, time_s)),
+            (Some(window_start), false) => {
+                windows.push(VisibilityWindow { start_s: window_start, end_s: refine(previous_s, time_s) });
+                window_start_s = None;
+            }
+            _ => {}
+        }
+        previous_s = time_s;
+    }
+    if let Some(window_start) = window_start_s {
+        windows.push(VisibilityWindow { start_s: window_start, end_s });
+    }
+    windows
+}
+
+/// Lists the Tx–Rx visibility windows over `[start_s, start_s + duration_s]`.
+pub fn visibility_windows(
+    scene: &LocalCartesian,
+    tx: &BeamTrack,
+    rx: &BeamTrack,
+    conditions: &VisibilityConditions,
+    start_s: f64,
+    duration_s: f64,
+    step_s: f64,
+) -> Vec<VisibilityWindow> {
+    let is_visible = |time_s: f64| {
+        let (txp, rxp) = (tx.position_at(time_s), rx.position_at(time_s));
+        (!conditions.beams_on_scene || (tx.illuminates(&DVec3::ZERO, time_s) && rx.illuminates(&DVec3::ZERO, time_s)))
+            && direct_path_min_height_m(scene, &txp, &rxp) >= conditions.direct_path_clearance_m
+    };
+    time_windows(is_visible, start_s, duration_s, step_s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Carrier at `height_m` above `ground_m`, looking down at it, moving East.
+    fn track(ground_m: DVec3, height_m: f64, beam_width_deg: f64) -> BeamTrack {
+        // Antenna X (boresight) down, Y North, Z East
+        let rotation_antenna_to_world = DQuat::from_mat3(&bevy::math::DMat3::from_cols(
+            DVec3::NEG_Z,
+            DVec3::Y,
+            DVec3::X,
+        ));
+        BeamTrack {
+            position_m: ground_m + height_m * DVec3::Z,
+            velocity_mps: DVec3::new(100.0, 0.0, 0.0),
+            motion: MotionProfile::default(),
+            rotation_world_to_antenna: rotation_antenna_to_world.inverse(),
+            azimuth_beam_width_deg: beam_width_deg,
+            elevation_beam_width_deg: beam_width_deg,
+        }
+    }
+
+    #[test]
+    fn direct_path_below_the_horizon() {
+        let scene = LocalCartesian::default();
+        // Carriers 1000 m high on the equator: the ellipsoid section is a circle
+        let a_m = scene.ellipsoid().equatorial_radius_m();
+        let carrier = |lon_deg: f64| scene.transform_from_geographic_point_to_enu_point(
+            &crate::coordinates::GeographicPoint::from_degrees(lon_deg, 0.0, 1000.0)
+        );
+        let height = direct_path_min_height_m(&scene, &carrier(-1.0), &carrier(1.0));
+        let expected = (a_m + 1000.0) * 1f64.to_radians().cos() - a_m;
+        assert!((height - expected).abs() < 0.1, "{height} != {expected}");
+        assert!(height > 0.0);
+        assert!(direct_path_min_height_m(&scene, &carrier(-2.0), &carrier(2.0)) < 0.0);
+    }
+
+    #[test]
+    fn windows_need_both_beams_on_the_scene() {
+        let scene = LocalCartesian::default();
+        // Nadir looking carriers over the scene center at 0 s, 10° beams:
+        // ±52.5 m at 600 m high, ±87.5 m at 1000 m high at 100 m/s
+        let tx = track(DVec3::ZERO, 600.0, 10.0);
+        let rx = track(DVec3::ZERO, 1000.0, 10.0);
+        let conditions = VisibilityConditions { direct_path_clearance_m: 0.0, beams_on_scene: true };
+        let windows = visibility_windows(&scene, &tx, &rx, &conditions, -10.0, 20.0, 0.1);
+        assert_eq!(windows.len(), 1);
+        let half_width_s = 600.0 * 5f64.to_radians().tan() / 100.0;
+        assert!((windows[0].start_s + half_width_s).abs() < 0.02, "{:?}", windows[0]);
+        assert!((windows[0].end_s - half_width_s).abs() < 0.02, "{:?}", windows[0]);
+
+        let conditions = VisibilityConditions { direct_path_clearance_m: 0.0, beams_on_scene: false };
+        let windows = visibility_windows(&scene, &tx, &rx, &conditions, -10.0, 20.0, 0.1);
+        assert_eq!(windows, vec![VisibilityWindow { start_s: -10.0, end_s: 10.0 }]);
+        assert_eq!(windows[0].duration_s(), 20.0);
+    }
+}